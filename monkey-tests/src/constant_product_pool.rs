@@ -0,0 +1,116 @@
+use crate::{ConstantProductPoolMeta, SystemTestFuzzer};
+use radix_engine::types::FromRepr;
+use radix_engine_common::prelude::{ComponentAddress, Decimal};
+use transaction::builder::ManifestBuilder;
+
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, FromRepr, Ord, PartialOrd, Eq, PartialEq)]
+pub enum ConstantProductPoolFuzzAction {
+    Contribute,
+    Redeem,
+    Swap1,
+    Swap2,
+    SwapForExactOutput1,
+    SwapForExactOutput2,
+    SetFeeRate,
+}
+
+impl ConstantProductPoolFuzzAction {
+    pub fn add_to_manifest(
+        &self,
+        builder: ManifestBuilder,
+        fuzzer: &mut SystemTestFuzzer,
+        account_address: ComponentAddress,
+        pool_meta: &ConstantProductPoolMeta,
+    ) -> (ManifestBuilder, bool) {
+        match self {
+            ConstantProductPoolFuzzAction::Contribute => {
+                let amount1 = fuzzer.next_amount();
+                let amount2 = fuzzer.next_amount();
+
+                let builder = builder
+                    .mint_fungible(pool_meta.resource_address1, amount1)
+                    .mint_fungible(pool_meta.resource_address2, amount2)
+                    .take_all_from_worktop(pool_meta.resource_address1, "resource_1")
+                    .take_all_from_worktop(pool_meta.resource_address2, "resource_2")
+                    .contribute_to_constant_product_pool(
+                        pool_meta.pool_address,
+                        "resource_1",
+                        "resource_2",
+                    );
+
+                (builder, amount1.is_zero() && amount2.is_zero())
+            }
+            ConstantProductPoolFuzzAction::Redeem => {
+                let amount = fuzzer.next_amount();
+
+                let builder = builder
+                    .withdraw_from_account(
+                        account_address,
+                        pool_meta.pool_unit_resource_address,
+                        amount,
+                    )
+                    .take_all_from_worktop(pool_meta.pool_unit_resource_address, "pool_units")
+                    .redeem_from_constant_product_pool(pool_meta.pool_address, "pool_units");
+
+                (builder, amount.is_zero())
+            }
+            ConstantProductPoolFuzzAction::Swap1 | ConstantProductPoolFuzzAction::Swap2 => {
+                let input_resource_address = if let ConstantProductPoolFuzzAction::Swap1 = self {
+                    pool_meta.resource_address1
+                } else {
+                    pool_meta.resource_address2
+                };
+                let amount = fuzzer.next_amount();
+                let minimum_output_amount = match fuzzer.next(0u32..=3u32) {
+                    0u32 => fuzzer.next_amount(),
+                    _ => Decimal::ZERO,
+                };
+
+                let builder = builder
+                    .mint_fungible(input_resource_address, amount)
+                    .take_all_from_worktop(input_resource_address, "input")
+                    .swap_with_constant_product_pool(
+                        pool_meta.pool_address,
+                        "input",
+                        minimum_output_amount,
+                    );
+
+                (builder, amount.is_zero())
+            }
+            ConstantProductPoolFuzzAction::SwapForExactOutput1
+            | ConstantProductPoolFuzzAction::SwapForExactOutput2 => {
+                let input_resource_address =
+                    if let ConstantProductPoolFuzzAction::SwapForExactOutput1 = self {
+                        pool_meta.resource_address1
+                    } else {
+                        pool_meta.resource_address2
+                    };
+                let amount = fuzzer.next_amount();
+                let output_amount = fuzzer.next_amount();
+
+                let builder = builder
+                    .mint_fungible(input_resource_address, amount)
+                    .take_all_from_worktop(input_resource_address, "input")
+                    .swap_with_constant_product_pool_for_exact_output(
+                        pool_meta.pool_address,
+                        "input",
+                        output_amount,
+                    );
+
+                (builder, amount.is_zero() || output_amount.is_zero())
+            }
+            ConstantProductPoolFuzzAction::SetFeeRate => {
+                let fee_rate = match fuzzer.next(0u32..=3u32) {
+                    0u32 => fuzzer.next_amount(),
+                    _ => Decimal::from(fuzzer.next(0u32..1000u32)) / 1000u32,
+                };
+
+                let builder =
+                    builder.set_constant_product_pool_fee_rate(pool_meta.pool_address, fee_rate);
+
+                (builder, false)
+            }
+        }
+    }
+}
@@ -1,4 +1,5 @@
 pub mod consensus_manager;
+pub mod constant_product_pool;
 pub mod multi_pool;
 pub mod one_pool;
 pub mod resource;
@@ -6,6 +7,7 @@ pub mod two_pool;
 pub mod validator;
 
 use crate::consensus_manager::ConsensusManagerFuzzAction;
+use crate::constant_product_pool::ConstantProductPoolFuzzAction;
 use crate::multi_pool::MultiPoolFuzzAction;
 use crate::one_pool::OnePoolFuzzAction;
 use crate::resource::{
@@ -180,6 +182,7 @@ pub enum FuzzAction {
     OneResourcePool(OnePoolFuzzAction),
     TwoResourcePool(TwoPoolFuzzAction),
     MultiResourcePool(MultiPoolFuzzAction),
+    ConstantProductPool(ConstantProductPoolFuzzAction),
     FungibleGetBucket(FungibleResourceFuzzGetBucketAction),
     FungibleBucketTransform(ResourceFuzzTransformBucketAction),
     FungibleUseBucket(ResourceFuzzUseBucketAction),
@@ -198,6 +201,7 @@ impl FuzzAction {
         one_resource_pool: &OnePoolMeta,
         two_resource_pool: &TwoPoolMeta,
         multi_resource_pool: &MultiPoolMeta,
+        constant_product_pool: &ConstantProductPoolMeta,
         fungible_component: &ResourceComponentMeta,
         non_fungible_component: &ResourceComponentMeta,
         account_address: ComponentAddress,
@@ -218,6 +222,9 @@ impl FuzzAction {
             FuzzAction::MultiResourcePool(action) => {
                 action.add_to_manifest(builder, fuzzer, account_address, multi_resource_pool)
             }
+            FuzzAction::ConstantProductPool(action) => {
+                action.add_to_manifest(builder, fuzzer, account_address, constant_product_pool)
+            }
             FuzzAction::FungibleGetBucket(action) => {
                 action.add_to_manifest(builder, fuzzer, fungible_component)
             }
@@ -301,6 +308,14 @@ pub struct MultiPoolMeta {
     pub pool_resources: Vec<ResourceAddress>,
 }
 
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq)]
+pub struct ConstantProductPoolMeta {
+    pub pool_address: ComponentAddress,
+    pub pool_unit_resource_address: ResourceAddress,
+    pub resource_address1: ResourceAddress,
+    pub resource_address2: ResourceAddress,
+}
+
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq)]
 pub struct ResourceComponentMeta {
     pub component_address: ComponentAddress,
@@ -315,6 +330,7 @@ pub struct FuzzTest<T: TxnFuzzer> {
     one_resource_pool: OnePoolMeta,
     two_resource_pool: TwoPoolMeta,
     multi_resource_pool: MultiPoolMeta,
+    constant_product_pool: ConstantProductPoolMeta,
     fungible_meta: ResourceComponentMeta,
     non_fungible_meta: ResourceComponentMeta,
     account_address: ComponentAddress,
@@ -480,6 +496,54 @@ impl<T: TxnFuzzer> FuzzTest<T> {
             }
         };
 
+        let constant_product_pool = {
+            let pool_resource1 = test_runner.create_freely_mintable_and_burnable_fungible_resource(
+                OwnerRole::None,
+                None,
+                fuzzer.next_valid_divisibility(),
+                account,
+            );
+            let pool_resource2 = test_runner.create_freely_mintable_and_burnable_fungible_resource(
+                OwnerRole::None,
+                None,
+                fuzzer.next_valid_divisibility(),
+                account,
+            );
+
+            let (pool_component, pool_unit_resource) = {
+                let manifest = ManifestBuilder::new()
+                    .create_constant_product_pool(
+                        OwnerRole::None,
+                        rule!(require(virtual_signature_badge.clone())),
+                        (pool_resource1, pool_resource2),
+                        dec!("0.003"),
+                    )
+                    .build();
+                let receipt = test_runner.execute_manifest_ignoring_fee(manifest, vec![]);
+                let commit_result = receipt.expect_commit_success();
+
+                (
+                    commit_result
+                        .new_component_addresses()
+                        .iter()
+                        .find(|address| {
+                            address.as_node_id().entity_type()
+                                == Some(EntityType::GlobalConstantProductPool)
+                        })
+                        .copied()
+                        .unwrap(),
+                    commit_result.new_resource_addresses()[0],
+                )
+            };
+
+            ConstantProductPoolMeta {
+                pool_address: pool_component,
+                pool_unit_resource_address: pool_unit_resource,
+                resource_address1: pool_resource1,
+                resource_address2: pool_resource2,
+            }
+        };
+
         let package_address = test_runner.publish_native_package(
             CUSTOM_PACKAGE_CODE_ID,
             PackageDefinition::new_with_field_test_definition(
@@ -624,6 +688,7 @@ impl<T: TxnFuzzer> FuzzTest<T> {
             one_resource_pool,
             two_resource_pool,
             multi_resource_pool,
+            constant_product_pool,
             fungible_meta: fungible_vault_component,
             non_fungible_meta: non_fungible_vault_component,
             account_address: account,
@@ -703,6 +768,7 @@ impl<T: TxnFuzzer> FuzzTest<T> {
                     &self.one_resource_pool,
                     &self.two_resource_pool,
                     &self.multi_resource_pool,
+                    &self.constant_product_pool,
                     &self.fungible_meta,
                     &self.non_fungible_meta,
                     self.account_address,
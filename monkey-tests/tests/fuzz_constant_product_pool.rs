@@ -0,0 +1,17 @@
+use monkey_tests::constant_product_pool::ConstantProductPoolFuzzAction;
+use monkey_tests::{FuzzAction, FuzzTest, SystemTestFuzzer, TxnFuzzer};
+use radix_engine::types::*;
+
+#[test]
+fn fuzz_constant_product_pool() {
+    struct ConstantProductPoolFuzzer;
+    impl TxnFuzzer for ConstantProductPoolFuzzer {
+        fn next_txn_intent(fuzzer: &mut SystemTestFuzzer) -> Vec<FuzzAction> {
+            let action: ConstantProductPoolFuzzAction =
+                ConstantProductPoolFuzzAction::from_repr(fuzzer.next_u8(7u8)).unwrap();
+            vec![FuzzAction::ConstantProductPool(action)]
+        }
+    }
+
+    FuzzTest::<ConstantProductPoolFuzzer>::run_fuzz(32, 100, false);
+}
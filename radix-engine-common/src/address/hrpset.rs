@@ -45,13 +45,16 @@ impl HrpSet {
             EntityType::GlobalVirtualEd25519Account => &self.account,
             EntityType::GlobalVirtualSecp256k1Identity => &self.identity,
             EntityType::GlobalVirtualEd25519Identity => &self.identity,
+            EntityType::GlobalVirtualSecp256r1Account => &self.account,
+            EntityType::GlobalVirtualSecp256r1Identity => &self.identity,
             EntityType::InternalFungibleVault => &self.internal_vault,
             EntityType::InternalNonFungibleVault => &self.internal_vault,
             EntityType::InternalGenericComponent => &self.internal_component,
             EntityType::InternalKeyValueStore => &self.internal_key_value_store,
             EntityType::GlobalOneResourcePool
             | EntityType::GlobalTwoResourcePool
-            | EntityType::GlobalMultiResourcePool => &self.pool,
+            | EntityType::GlobalMultiResourcePool
+            | EntityType::GlobalConstantProductPool => &self.pool,
             EntityType::GlobalTransactionTracker => &self.transaction_tracker,
         }
     }
@@ -33,6 +33,12 @@ pub const ED25519_SIGNATURE_VIRTUAL_BADGE: ResourceAddress = ResourceAddress::ne
     169, 229, 71, 198, 49, 140, 99, 24, 198,
 ]);
 
+/// The non-fungible badge resource which is used for virtual proofs of ECDSA Secp256r1 transacton signatures in the transaction processor.
+pub const SECP256R1_SIGNATURE_VIRTUAL_BADGE: ResourceAddress = ResourceAddress::new_or_panic([
+    154, 76, 99, 24, 198, 49, 140, 96, 170, 154, 130, 12, 99, 24, 198, 49, 140, 247, 157, 79, 245,
+    35, 74, 62, 38, 49, 140, 99, 24, 198,
+]);
+
 /// The non-fungible badge resource which is used for virtual proofs which represent the package of
 /// the immediate caller - ie the actor which made the latest (global or internal) call.
 ///
@@ -257,6 +263,11 @@ mod tests {
             EntityType::GlobalNonFungibleResourceManager,
             "resource_rdx1nfxxxxxxxxxxed25sgxxxxxxxxx002236757237xxxxxxxxxed25sg",
         );
+        check_address(
+            SECP256R1_SIGNATURE_VIRTUAL_BADGE.as_ref(),
+            EntityType::GlobalNonFungibleResourceManager,
+            "resource_rdx1nfxxxxxxxxxxp256sgxxxxxxxxx008207535503xxxxxxxxxp256sg",
+        );
         check_address(
             PACKAGE_OF_DIRECT_CALLER_VIRTUAL_BADGE.as_ref(),
             EntityType::GlobalNonFungibleResourceManager,
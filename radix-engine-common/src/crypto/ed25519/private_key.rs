@@ -12,6 +12,11 @@ impl Ed25519PrivateKey {
     }
 
     pub fn sign(&self, msg_hash: &impl IsHash) -> Ed25519Signature {
+        self.sign_message(msg_hash.as_ref())
+    }
+
+    /// Signs an arbitrary message, rather than a hash.
+    pub fn sign_message(&self, message: &[u8]) -> Ed25519Signature {
         let keypair = Keypair {
             secret: SecretKey::from_bytes(self.0.as_bytes()).expect("From a valid key bytes"),
             public: PublicKey::from(&self.0),
@@ -19,7 +24,7 @@ impl Ed25519PrivateKey {
 
         // SHA512 is used here
 
-        Ed25519Signature(keypair.sign(msg_hash.as_ref()).to_bytes())
+        Ed25519Signature(keypair.sign(message).to_bytes())
     }
 
     pub fn to_bytes(&self) -> Vec<u8> {
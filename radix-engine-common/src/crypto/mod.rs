@@ -7,6 +7,7 @@ mod keccak256;
 mod public_key;
 mod public_key_hash;
 mod secp256k1;
+mod secp256r1;
 mod signature_validator;
 pub use self::blake2b::*;
 pub use self::bls12381::*;
@@ -17,4 +18,5 @@ pub use self::keccak256::*;
 pub use self::public_key::*;
 pub use self::public_key_hash::*;
 pub use self::secp256k1::*;
+pub use self::secp256r1::*;
 pub use self::signature_validator::*;
@@ -13,6 +13,7 @@ use arbitrary::Arbitrary;
 pub enum PublicKey {
     Secp256k1(Secp256k1PublicKey),
     Ed25519(Ed25519PublicKey),
+    Secp256r1(Secp256r1PublicKey),
 }
 
 impl Describe<ScryptoCustomTypeKind> for PublicKey {
@@ -36,6 +37,12 @@ impl From<Ed25519PublicKey> for PublicKey {
     }
 }
 
+impl From<Secp256r1PublicKey> for PublicKey {
+    fn from(public_key: Secp256r1PublicKey) -> Self {
+        Self::Secp256r1(public_key)
+    }
+}
+
 impl HasPublicKeyHash for PublicKey {
     type TypedPublicKeyHash = PublicKeyHash;
 
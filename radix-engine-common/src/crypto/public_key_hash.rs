@@ -33,6 +33,7 @@ pub fn hash_public_key_bytes<T: AsRef<[u8]>>(key_bytes: T) -> [u8; NodeId::RID_L
 pub enum PublicKeyHash {
     Secp256k1(Secp256k1PublicKeyHash),
     Ed25519(Ed25519PublicKeyHash),
+    Secp256r1(Secp256r1PublicKeyHash),
 }
 
 impl Describe<ScryptoCustomTypeKind> for PublicKeyHash {
@@ -56,6 +57,12 @@ impl From<Ed25519PublicKeyHash> for PublicKeyHash {
     }
 }
 
+impl From<Secp256r1PublicKeyHash> for PublicKeyHash {
+    fn from(public_key: Secp256r1PublicKeyHash) -> Self {
+        Self::Secp256r1(public_key)
+    }
+}
+
 impl PublicKeyHash {
     pub fn new_from_public_key(public_key: &PublicKey) -> Self {
         match public_key {
@@ -65,6 +72,9 @@ impl PublicKeyHash {
             PublicKey::Ed25519(public_key) => {
                 PublicKeyHash::Ed25519(Ed25519PublicKeyHash::new_from_public_key(public_key))
             }
+            PublicKey::Secp256r1(public_key) => {
+                PublicKeyHash::Secp256r1(Secp256r1PublicKeyHash::new_from_public_key(public_key))
+            }
         }
     }
 }
@@ -74,6 +84,7 @@ impl IsPublicKeyHash for PublicKeyHash {
         match self {
             PublicKeyHash::Secp256k1(value) => value.get_hash_bytes(),
             PublicKeyHash::Ed25519(value) => value.get_hash_bytes(),
+            PublicKeyHash::Secp256r1(value) => value.get_hash_bytes(),
         }
     }
 
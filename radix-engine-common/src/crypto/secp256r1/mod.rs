@@ -0,0 +1,9 @@
+#[cfg(feature = "secp256r1_sign_and_validate")]
+mod private_key;
+mod public_key;
+mod signature;
+
+#[cfg(feature = "secp256r1_sign_and_validate")]
+pub use private_key::*;
+pub use public_key::*;
+pub use signature::*;
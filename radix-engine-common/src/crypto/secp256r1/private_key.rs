@@ -0,0 +1,77 @@
+use super::Secp256r1Signature;
+use crate::internal_prelude::*;
+use ::p256::ecdsa::signature::hazmat::PrehashSigner;
+use ::p256::ecdsa::{Signature, SigningKey};
+
+pub struct Secp256r1PrivateKey(SigningKey);
+
+impl Secp256r1PrivateKey {
+    pub const LENGTH: usize = 32;
+
+    pub fn public_key(&self) -> Secp256r1PublicKey {
+        Secp256r1PublicKey(copy_u8_array(
+            self.0.verifying_key().to_encoded_point(true).as_bytes(),
+        ))
+    }
+
+    pub fn sign(&self, msg_hash: &impl IsHash) -> Secp256r1Signature {
+        let signature: Signature = self
+            .0
+            .sign_prehash(msg_hash.as_ref())
+            .expect("Hash is always a valid prehash");
+
+        // Signatures are normalized to low-S form, to prevent malleability
+        let signature = signature.normalize_s().unwrap_or(signature);
+        Secp256r1Signature(copy_u8_array(&signature.to_bytes()))
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_bytes().to_vec()
+    }
+
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.to_bytes())
+    }
+
+    pub fn from_hex(s: &str) -> Result<Self, ()> {
+        hex::decode(s)
+            .map_err(|_| ())
+            .and_then(|v| Self::from_bytes(&v))
+    }
+
+    pub fn from_bytes(slice: &[u8]) -> Result<Self, ()> {
+        if slice.len() != Secp256r1PrivateKey::LENGTH {
+            return Err(());
+        }
+        Ok(Self(SigningKey::from_slice(slice).map_err(|_| ())?))
+    }
+
+    pub fn from_u64(n: u64) -> Result<Self, ()> {
+        let mut bytes = [0u8; Secp256r1PrivateKey::LENGTH];
+        (&mut bytes[Secp256r1PrivateKey::LENGTH - 8..Secp256r1PrivateKey::LENGTH])
+            .copy_from_slice(&n.to_be_bytes());
+
+        Ok(Self(SigningKey::from_slice(&bytes).map_err(|_| ())?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sbor::rust::str::FromStr;
+
+    #[test]
+    fn sign_and_verify() {
+        let test_sk = "0000000000000000000000000000000000000000000000000000000000000001";
+        let test_pk = "036b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296";
+        let test_message_hash = hash("Test");
+        let test_signature = "51a3800b131db9de25cc442d1399050603340c8afca8269083d2f87deeddaa4e5591717974d59cce0682e363348ed3ff12564fa6d3b3f35af5d00c6d407b2be8";
+        let sk = Secp256r1PrivateKey::from_bytes(&hex::decode(test_sk).unwrap()).unwrap();
+        let pk = Secp256r1PublicKey::from_str(test_pk).unwrap();
+        let sig = Secp256r1Signature::from_str(test_signature).unwrap();
+
+        assert_eq!(sk.public_key(), pk);
+        assert_eq!(sk.sign(&test_message_hash), sig);
+        assert!(verify_secp256r1(&test_message_hash, &pk, &sig));
+    }
+}
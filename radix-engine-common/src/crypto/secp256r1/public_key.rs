@@ -0,0 +1,138 @@
+use crate::internal_prelude::*;
+#[cfg(feature = "radix_engine_fuzzing")]
+use arbitrary::Arbitrary;
+
+/// Represents an ECDSA Secp256r1 (NIST P-256) public key.
+#[cfg_attr(feature = "radix_engine_fuzzing", derive(Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(
+    Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Categorize, Encode, Decode, BasicDescribe,
+)]
+#[sbor(transparent)]
+pub struct Secp256r1PublicKey(
+    #[cfg_attr(feature = "serde", serde(with = "hex::serde"))] pub [u8; Self::LENGTH],
+);
+
+impl Describe<ScryptoCustomTypeKind> for Secp256r1PublicKey {
+    const TYPE_ID: RustTypeId =
+        RustTypeId::WellKnown(well_known_scrypto_custom_types::SECP256R1_PUBLIC_KEY_TYPE);
+
+    fn type_data() -> ScryptoTypeData<RustTypeId> {
+        well_known_scrypto_custom_types::secp256r1_public_key_type_data()
+    }
+}
+
+impl Secp256r1PublicKey {
+    pub const LENGTH: usize = 33;
+
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+
+    pub fn to_hash(&self) -> Secp256r1PublicKeyHash {
+        Secp256r1PublicKeyHash::new_from_public_key(self)
+    }
+}
+
+impl TryFrom<&[u8]> for Secp256r1PublicKey {
+    type Error = ParseSecp256r1PublicKeyError;
+
+    fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+        if slice.len() != Secp256r1PublicKey::LENGTH {
+            return Err(ParseSecp256r1PublicKeyError::InvalidLength(slice.len()));
+        }
+
+        Ok(Secp256r1PublicKey(copy_u8_array(slice)))
+    }
+}
+
+//======
+// hash
+//======
+
+#[cfg_attr(feature = "radix_engine_fuzzing", derive(Arbitrary))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Categorize, Encode, Decode, BasicDescribe)]
+#[sbor(transparent)]
+pub struct Secp256r1PublicKeyHash(pub [u8; Self::LENGTH]);
+
+impl Describe<ScryptoCustomTypeKind> for Secp256r1PublicKeyHash {
+    const TYPE_ID: RustTypeId =
+        RustTypeId::WellKnown(well_known_scrypto_custom_types::SECP256R1_PUBLIC_KEY_HASH_TYPE);
+
+    fn type_data() -> ScryptoTypeData<RustTypeId> {
+        well_known_scrypto_custom_types::secp256r1_public_key_hash_type_data()
+    }
+}
+
+impl Secp256r1PublicKeyHash {
+    pub const LENGTH: usize = NodeId::RID_LENGTH;
+
+    pub fn new_from_public_key(public_key: &Secp256r1PublicKey) -> Self {
+        Self(hash_public_key_bytes(public_key.0))
+    }
+}
+
+impl HasPublicKeyHash for Secp256r1PublicKey {
+    type TypedPublicKeyHash = Secp256r1PublicKeyHash;
+
+    fn get_hash(&self) -> Self::TypedPublicKeyHash {
+        Self::TypedPublicKeyHash::new_from_public_key(self)
+    }
+}
+
+impl IsPublicKeyHash for Secp256r1PublicKeyHash {
+    fn get_hash_bytes(&self) -> &[u8; Self::LENGTH] {
+        &self.0
+    }
+
+    fn into_enum(self) -> PublicKeyHash {
+        PublicKeyHash::Secp256r1(self)
+    }
+}
+
+//======
+// error
+//======
+
+/// Represents an error when parsing Secp256r1 public key from hex.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseSecp256r1PublicKeyError {
+    InvalidHex(String),
+    InvalidLength(usize),
+}
+
+#[cfg(not(feature = "alloc"))]
+impl std::error::Error for ParseSecp256r1PublicKeyError {}
+
+#[cfg(not(feature = "alloc"))]
+impl fmt::Display for ParseSecp256r1PublicKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+//======
+// text
+//======
+
+impl FromStr for Secp256r1PublicKey {
+    type Err = ParseSecp256r1PublicKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes =
+            hex::decode(s).map_err(|_| ParseSecp256r1PublicKeyError::InvalidHex(s.to_owned()))?;
+        Self::try_from(bytes.as_slice())
+    }
+}
+
+impl fmt::Display for Secp256r1PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}", hex::encode(self.to_vec()))
+    }
+}
+
+impl fmt::Debug for Secp256r1PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}", self)
+    }
+}
@@ -0,0 +1,81 @@
+use sbor::rust::borrow::ToOwned;
+use sbor::rust::fmt;
+use sbor::rust::str::FromStr;
+use sbor::rust::string::String;
+use sbor::rust::vec::Vec;
+use sbor::*;
+use utils::copy_u8_array;
+
+/// Represents an ECDSA Secp256r1 signature.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Sbor)]
+pub struct Secp256r1Signature(
+    #[cfg_attr(feature = "serde", serde(with = "hex::serde"))] pub [u8; Self::LENGTH],
+);
+
+impl Secp256r1Signature {
+    pub const LENGTH: usize = 64; // r + s
+
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+}
+
+impl TryFrom<&[u8]> for Secp256r1Signature {
+    type Error = ParseSecp256r1SignatureError;
+
+    fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+        if slice.len() != Secp256r1Signature::LENGTH {
+            return Err(ParseSecp256r1SignatureError::InvalidLength(slice.len()));
+        }
+
+        Ok(Secp256r1Signature(copy_u8_array(slice)))
+    }
+}
+
+//======
+// error
+//======
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseSecp256r1SignatureError {
+    InvalidHex(String),
+    InvalidLength(usize),
+}
+
+/// Represents an error when parsing an ECDSA Secp256r1 signature from hex.
+#[cfg(not(feature = "alloc"))]
+impl std::error::Error for ParseSecp256r1SignatureError {}
+
+#[cfg(not(feature = "alloc"))]
+impl fmt::Display for ParseSecp256r1SignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+//======
+// text
+//======
+
+impl FromStr for Secp256r1Signature {
+    type Err = ParseSecp256r1SignatureError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes =
+            hex::decode(s).map_err(|_| ParseSecp256r1SignatureError::InvalidHex(s.to_owned()))?;
+        Self::try_from(bytes.as_slice())
+    }
+}
+
+impl fmt::Display for Secp256r1Signature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}", hex::encode(self.to_vec()))
+    }
+}
+
+impl fmt::Debug for Secp256r1Signature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}", self)
+    }
+}
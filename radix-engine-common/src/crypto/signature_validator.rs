@@ -42,14 +42,136 @@ pub fn verify_secp256k1(
     false
 }
 
+#[cfg(feature = "secp256r1_sign_and_validate")]
+pub fn verify_secp256r1(
+    signed_hash: &Hash,
+    public_key: &Secp256r1PublicKey,
+    signature: &Secp256r1Signature,
+) -> bool {
+    use ::p256::ecdsa::signature::hazmat::PrehashVerifier;
+
+    if let Ok(sig) = ::p256::ecdsa::Signature::from_slice(&signature.0) {
+        // High-S signatures are rejected, to prevent signature malleability
+        if sig.normalize_s().is_none() {
+            if let Ok(pk) = ::p256::ecdsa::VerifyingKey::from_sec1_bytes(&public_key.0) {
+                return pk.verify_prehash(&signed_hash.0, &sig).is_ok();
+            }
+        }
+    }
+
+    false
+}
+
+/// The length of the WebAuthn authenticator data fields which precede any extensions:
+/// RP ID hash (32), flags (1) and signature counter (4).
+#[cfg(feature = "secp256r1_sign_and_validate")]
+const WEBAUTHN_AUTHENTICATOR_DATA_MIN_LENGTH: usize = 37;
+/// The index of the flags byte within the WebAuthn authenticator data.
+#[cfg(feature = "secp256r1_sign_and_validate")]
+const WEBAUTHN_AUTHENTICATOR_DATA_FLAGS_INDEX: usize = 32;
+/// The "user present" bit of the WebAuthn authenticator data flags.
+#[cfg(feature = "secp256r1_sign_and_validate")]
+const WEBAUTHN_FLAG_USER_PRESENT: u8 = 0x01;
+
+/// Performs verification of a WebAuthn assertion (eg from a passkey) made by a Secp256r1 key,
+/// over the given signed hash.
+///
+/// As per the WebAuthn spec, the authenticator signs `authenticator_data || SHA-256(client_data_json)`
+/// using ECDSA P-256 with SHA-256. Additionally:
+/// * The authenticator data must contain the RP ID hash, flags and signature counter, and have
+///   the user present flag set.
+/// * The client data must be a JSON object of type `webauthn.get`, whose challenge is the signed
+///   hash in unpadded base64url.
+///
+/// The RP ID hash and origin are not matched against expected values, as they are application
+/// specific.
+#[cfg(feature = "secp256r1_sign_and_validate")]
+pub fn verify_secp256r1_webauthn(
+    signed_hash: &Hash,
+    public_key: &Secp256r1PublicKey,
+    signature: &Secp256r1Signature,
+    authenticator_data: &[u8],
+    client_data_json: &[u8],
+) -> bool {
+    use ::p256::ecdsa::signature::hazmat::PrehashVerifier;
+    use ::sha2::{Digest, Sha256};
+
+    if authenticator_data.len() < WEBAUTHN_AUTHENTICATOR_DATA_MIN_LENGTH
+        || authenticator_data[WEBAUTHN_AUTHENTICATOR_DATA_FLAGS_INDEX] & WEBAUTHN_FLAG_USER_PRESENT
+            == 0
+    {
+        return false;
+    }
+
+    let client_data = match ::serde_json::from_slice::<::serde_json::Value>(client_data_json) {
+        Ok(::serde_json::Value::Object(client_data)) => client_data,
+        _ => return false,
+    };
+    if client_data.get("type").and_then(|value| value.as_str()) != Some("webauthn.get") {
+        return false;
+    }
+    let expected_challenge = base64url_encode_unpadded(&signed_hash.0);
+    if client_data
+        .get("challenge")
+        .and_then(|value| value.as_str())
+        != Some(expected_challenge.as_str())
+    {
+        return false;
+    }
+
+    let message_hash = Sha256::new()
+        .chain_update(authenticator_data)
+        .chain_update(Sha256::digest(client_data_json))
+        .finalize();
+
+    if let Ok(sig) = ::p256::ecdsa::Signature::from_slice(&signature.0) {
+        // High-S signatures are rejected, to prevent signature malleability
+        if sig.normalize_s().is_none() {
+            if let Ok(pk) = ::p256::ecdsa::VerifyingKey::from_sec1_bytes(&public_key.0) {
+                return pk.verify_prehash(&message_hash, &sig).is_ok();
+            }
+        }
+    }
+
+    false
+}
+
+#[cfg(feature = "secp256r1_sign_and_validate")]
+fn base64url_encode_unpadded(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+    let mut encoded = String::with_capacity((bytes.len() * 4 + 2) / 3);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..chunk.len() + 1 {
+            encoded.push(ALPHABET[((n >> (18 - 6 * i)) & 0x3f) as usize] as char);
+        }
+    }
+    encoded
+}
+
 pub fn verify_ed25519(
     signed_hash: &Hash,
     public_key: &Ed25519PublicKey,
     signature: &Ed25519Signature,
+) -> bool {
+    verify_ed25519_message(&signed_hash.0, public_key, signature)
+}
+
+/// Performs Ed25519 signature verification of an arbitrary message.
+pub fn verify_ed25519_message(
+    message: &[u8],
+    public_key: &Ed25519PublicKey,
+    signature: &Ed25519Signature,
 ) -> bool {
     if let Ok(sig) = ed25519_dalek::Signature::from_bytes(&signature.0) {
         if let Ok(pk) = ed25519_dalek::PublicKey::from_bytes(&public_key.0) {
-            return pk.verify_strict(&signed_hash.0, &sig).is_ok();
+            return pk.verify_strict(message, &sig).is_ok();
         }
     }
 
@@ -179,3 +301,121 @@ pub fn fast_aggregate_verify_bls12381_v1(
 
     false
 }
+
+#[cfg(test)]
+#[cfg(feature = "secp256r1_sign_and_validate")]
+mod tests {
+    use super::*;
+    use sbor::rust::str::FromStr;
+
+    // An assertion for RP ID `example.com` over `hash("Test")`, signed with a software P-256 key
+    // (authenticator data flags: user present and user verified, signature counter: 1).
+    const TEST_PUBLIC_KEY: &str =
+        "02bd7c73b88b2e9b4ceda62022b2da8be13193a5b56edc26e7df7842e24cd0b5eb";
+    const TEST_AUTHENTICATOR_DATA: &str =
+        "a379a6f6eeafb9a55e378c118034e2751e682fab9f2d30ab13d2125586ce19470500000001";
+    const TEST_CLIENT_DATA_JSON: &str = r#"{"type":"webauthn.get","challenge":"jSFD12YVxRW1zIj6eAau8mjt7qh1ccj4shoZ93uZk7o","origin":"https://example.com","crossOrigin":false}"#;
+    const TEST_SIGNATURE: &str = "fa8a342aa8a7d47084788d6afce6ab93d011e4dc171c4dbc75ee52fd7a44e32667f354b57979030052e1441fc3145089d1d88bdfba110434a116cd4202e1909d";
+
+    fn verify_test_assertion(
+        signed_hash: &Hash,
+        authenticator_data: &[u8],
+        client_data_json: &[u8],
+    ) -> bool {
+        verify_secp256r1_webauthn(
+            signed_hash,
+            &Secp256r1PublicKey::from_str(TEST_PUBLIC_KEY).unwrap(),
+            &Secp256r1Signature::from_str(TEST_SIGNATURE).unwrap(),
+            authenticator_data,
+            client_data_json,
+        )
+    }
+
+    #[test]
+    fn webauthn_assertion_is_verified() {
+        assert!(verify_test_assertion(
+            &hash("Test"),
+            &hex::decode(TEST_AUTHENTICATOR_DATA).unwrap(),
+            TEST_CLIENT_DATA_JSON.as_bytes(),
+        ));
+    }
+
+    #[test]
+    fn webauthn_assertion_with_wrong_challenge_is_rejected() {
+        assert!(!verify_test_assertion(
+            &hash("Other"),
+            &hex::decode(TEST_AUTHENTICATOR_DATA).unwrap(),
+            TEST_CLIENT_DATA_JSON.as_bytes(),
+        ));
+    }
+
+    #[test]
+    fn webauthn_assertion_with_modified_authenticator_data_is_rejected() {
+        let mut authenticator_data = hex::decode(TEST_AUTHENTICATOR_DATA).unwrap();
+        authenticator_data[36] += 1;
+        assert!(!verify_test_assertion(
+            &hash("Test"),
+            &authenticator_data,
+            TEST_CLIENT_DATA_JSON.as_bytes(),
+        ));
+    }
+
+    #[test]
+    fn webauthn_assertion_without_user_presence_is_rejected() {
+        let mut authenticator_data = hex::decode(TEST_AUTHENTICATOR_DATA).unwrap();
+        authenticator_data[WEBAUTHN_AUTHENTICATOR_DATA_FLAGS_INDEX] &= !WEBAUTHN_FLAG_USER_PRESENT;
+        assert!(!verify_test_assertion(
+            &hash("Test"),
+            &authenticator_data,
+            TEST_CLIENT_DATA_JSON.as_bytes(),
+        ));
+    }
+
+    #[test]
+    fn webauthn_assertion_with_truncated_authenticator_data_is_rejected() {
+        let authenticator_data = hex::decode(TEST_AUTHENTICATOR_DATA).unwrap();
+        assert!(!verify_test_assertion(
+            &hash("Test"),
+            &authenticator_data[..WEBAUTHN_AUTHENTICATOR_DATA_MIN_LENGTH - 1],
+            TEST_CLIENT_DATA_JSON.as_bytes(),
+        ));
+    }
+
+    #[test]
+    fn webauthn_assertion_of_wrong_type_is_rejected() {
+        let client_data_json = TEST_CLIENT_DATA_JSON.replace("webauthn.get", "webauthn.create");
+        assert!(!verify_test_assertion(
+            &hash("Test"),
+            &hex::decode(TEST_AUTHENTICATOR_DATA).unwrap(),
+            client_data_json.as_bytes(),
+        ));
+    }
+
+    #[test]
+    fn webauthn_client_data_with_expected_fields_only_in_nested_values_is_rejected() {
+        let client_data_json = r#"{"type":"webauthn.create","challenge":"AAAA","extra":{"type":"webauthn.get","challenge":"jSFD12YVxRW1zIj6eAau8mjt7qh1ccj4shoZ93uZk7o"}}"#;
+        assert!(!verify_test_assertion(
+            &hash("Test"),
+            &hex::decode(TEST_AUTHENTICATOR_DATA).unwrap(),
+            client_data_json.as_bytes(),
+        ));
+    }
+
+    #[test]
+    fn webauthn_client_data_which_is_not_a_json_object_is_rejected() {
+        assert!(!verify_test_assertion(
+            &hash("Test"),
+            &hex::decode(TEST_AUTHENTICATOR_DATA).unwrap(),
+            br#"["webauthn.get"]"#,
+        ));
+    }
+
+    #[test]
+    fn base64url_encoding_is_unpadded() {
+        assert_eq!(base64url_encode_unpadded(b""), "");
+        assert_eq!(base64url_encode_unpadded(b"f"), "Zg");
+        assert_eq!(base64url_encode_unpadded(b"fo"), "Zm8");
+        assert_eq!(base64url_encode_unpadded(b"foo"), "Zm9v");
+        assert_eq!(base64url_encode_unpadded(&[0xfb, 0xff]), "-_8");
+    }
+}
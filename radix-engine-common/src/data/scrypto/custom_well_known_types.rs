@@ -388,6 +388,10 @@ create_well_known_lookup!(
                 [
                     (0u8, named_tuple("Secp256k1", [SECP256K1_PUBLIC_KEY_TYPE])),
                     (1u8, named_tuple("Ed25519", [ED25519_PUBLIC_KEY_TYPE])),
+                    (
+                        2u8,
+                        named_tuple("Secp256r1", [SECP256R1_PUBLIC_KEY_TYPE])
+                    ),
                 ]
             )
         ),
@@ -407,6 +411,14 @@ create_well_known_lookup!(
                 bytes_fixed_length_type_data(Ed25519PublicKey::LENGTH),
             )
         ),
+        (
+            SECP256R1_PUBLIC_KEY,
+            CRYPTO_TYPES_START + 3,
+            named_transparent(
+                "Secp256r1PublicKey",
+                bytes_fixed_length_type_data(Secp256r1PublicKey::LENGTH),
+            )
+        ),
         (
             PUBLIC_KEY_HASH,
             CRYPTO_TYPES_START + 8,
@@ -418,6 +430,10 @@ create_well_known_lookup!(
                         named_tuple("Secp256k1", [SECP256K1_PUBLIC_KEY_HASH_TYPE])
                     ),
                     (1u8, named_tuple("Ed25519", [ED25519_PUBLIC_KEY_HASH_TYPE])),
+                    (
+                        2u8,
+                        named_tuple("Secp256r1", [SECP256R1_PUBLIC_KEY_HASH_TYPE])
+                    ),
                 ]
             )
         ),
@@ -437,6 +453,14 @@ create_well_known_lookup!(
                 bytes_fixed_length_type_data(Ed25519PublicKeyHash::LENGTH),
             )
         ),
+        (
+            SECP256R1_PUBLIC_KEY_HASH,
+            CRYPTO_TYPES_START + 11,
+            named_transparent(
+                "Secp256r1PublicKeyHash",
+                bytes_fixed_length_type_data(Secp256r1PublicKeyHash::LENGTH),
+            )
+        ),
         // ROLE ASSIGNMENT TYPES
         (
             ACCESS_RULE,
@@ -612,6 +636,10 @@ mod tests {
             PUBLIC_KEY_TYPE,
             PublicKey::Secp256k1(Secp256k1PublicKey([0; Secp256k1PublicKey::LENGTH])),
         );
+        test_equivalence(
+            PUBLIC_KEY_TYPE,
+            PublicKey::Secp256r1(Secp256r1PublicKey([0; Secp256r1PublicKey::LENGTH])),
+        );
         test_equivalence(
             ED25519_PUBLIC_KEY_TYPE,
             Ed25519PublicKey([0; Ed25519PublicKey::LENGTH]),
@@ -620,6 +648,10 @@ mod tests {
             SECP256K1_PUBLIC_KEY_TYPE,
             Secp256k1PublicKey([0; Secp256k1PublicKey::LENGTH]),
         );
+        test_equivalence(
+            SECP256R1_PUBLIC_KEY_TYPE,
+            Secp256r1PublicKey([0; Secp256r1PublicKey::LENGTH]),
+        );
         test_equivalence(
             PUBLIC_KEY_HASH_TYPE,
             PublicKeyHash::Ed25519(Ed25519PublicKeyHash([0; Ed25519PublicKeyHash::LENGTH])),
@@ -628,6 +660,10 @@ mod tests {
             PUBLIC_KEY_HASH_TYPE,
             PublicKeyHash::Secp256k1(Secp256k1PublicKeyHash([0; Secp256k1PublicKeyHash::LENGTH])),
         );
+        test_equivalence(
+            PUBLIC_KEY_HASH_TYPE,
+            PublicKeyHash::Secp256r1(Secp256r1PublicKeyHash([0; Secp256r1PublicKeyHash::LENGTH])),
+        );
         test_equivalence(
             ED25519_PUBLIC_KEY_HASH_TYPE,
             Ed25519PublicKeyHash([0; Ed25519PublicKeyHash::LENGTH]),
@@ -636,6 +672,10 @@ mod tests {
             SECP256K1_PUBLIC_KEY_HASH_TYPE,
             Secp256k1PublicKeyHash([0; Secp256k1PublicKeyHash::LENGTH]),
         );
+        test_equivalence(
+            SECP256R1_PUBLIC_KEY_HASH_TYPE,
+            Secp256r1PublicKeyHash([0; Secp256r1PublicKeyHash::LENGTH]),
+        );
     }
 
     fn test_equivalence<T: ScryptoEncode + ScryptoDescribe>(id: WellKnownTypeId, value: T) {
@@ -1,5 +1,6 @@
 //! Definitions of safe integers and uints.
 
+use crate::math::rounding_mode::*;
 use crate::math::traits::*;
 #[cfg(feature = "radix_engine_fuzzing")]
 use arbitrary::Arbitrary;
@@ -521,6 +522,82 @@ op_impl_signed! { I448 }
 op_impl_signed! { I512 }
 op_impl_signed! { I768 }
 
+macro_rules! mul_div_impl {
+    ($($t:ty, $wide:ty),*) => {
+        $(
+            impl CheckedMulDiv for $t {
+                type Output = $t;
+
+                fn checked_mul_div(
+                    self,
+                    multiplier: Self,
+                    divisor: Self,
+                    mode: RoundingMode,
+                ) -> Option<Self::Output> {
+                    if divisor.is_zero() {
+                        return None;
+                    }
+                    let zero = <$wide>::zero();
+                    let one = <$wide>::one();
+                    let numerator = <$wide>::from(self) * <$wide>::from(multiplier);
+                    let divisor = <$wide>::from(divisor);
+
+                    // Make the divisor positive, so that the remainder has the sign of the result
+                    let (numerator, divisor) = if divisor < zero {
+                        (&zero - &numerator, &zero - &divisor)
+                    } else {
+                        (numerator, divisor)
+                    };
+                    // `/` and `%` truncate toward zero, so fix them up into floor division with a
+                    // remainder in `[0, divisor)`, as `checked_round` does for the decimal types
+                    let quotient = &numerator / &divisor;
+                    let remainder = &numerator % &divisor;
+                    let (floor, positive_remainder) = match remainder.cmp(&zero) {
+                        Ordering::Less => (&quotient - &one, &divisor + &remainder),
+                        Ordering::Equal => return <$t>::try_from(quotient).ok(),
+                        Ordering::Greater => (quotient, remainder),
+                    };
+
+                    let is_positive = numerator > zero;
+                    let resolved_strategy = ResolvedRoundingStrategy::from_mode(mode, is_positive, || {
+                        (&positive_remainder + &positive_remainder).cmp(&divisor)
+                    });
+                    let rounded = match resolved_strategy {
+                        ResolvedRoundingStrategy::RoundUp => &floor + &one,
+                        ResolvedRoundingStrategy::RoundDown => floor,
+                        ResolvedRoundingStrategy::RoundToEven => {
+                            if (&floor % &(&one + &one)).is_zero() {
+                                floor
+                            } else {
+                                &floor + &one
+                            }
+                        }
+                    };
+                    <$t>::try_from(rounded).ok()
+                }
+            }
+        )*
+    };
+}
+// Wide enough for the product of any two values, falling back to `BigInt` if there is no such
+// fixed size type.
+mul_div_impl! {
+    I192, I384,
+    I256, I512,
+    I320, I768,
+    I384, I768,
+    I448, BigInt,
+    I512, BigInt,
+    I768, BigInt,
+    U192, U384,
+    U256, U512,
+    U320, U768,
+    U384, U768,
+    U448, BigInt,
+    U512, BigInt,
+    U768, BigInt
+}
+
 macro_rules! error {
     ($($t:ident),*) => {
         paste! {
@@ -462,3 +462,90 @@ fn test_bnum_to_bnum_errors() {
     let err = U256::try_from(i384).unwrap_err();
     assert_eq!(err, ParseU256Error::Overflow);
 }
+
+macro_rules! test_checked_mul_div {
+    ($($t:ident),*) => {
+        paste! {
+            $(
+                #[test]
+                fn [<test_checked_mul_div_ $t:lower>]() {
+                    // Intermediate product does not overflow
+                    assert_eq!(<$t>::MAX.checked_mul_div(<$t>::MAX, <$t>::MAX, RoundingMode::ToZero), Some(<$t>::MAX));
+                    assert_eq!(<$t>::MAX.checked_mul_div(<$t>::from(3u8), <$t>::from(3u8), RoundingMode::ToZero), Some(<$t>::MAX));
+                    assert_eq!(<$t>::MAX.checked_mul_div(<$t>::from(2u8), <$t>::ONE, RoundingMode::ToZero), None);
+                    assert_eq!(<$t>::ONE.checked_mul_div(<$t>::ONE, <$t>::ZERO, RoundingMode::ToZero), None);
+
+                    // 7 * 5 / 10 = 3.5 and 2 * 5 / 4 = 2.5
+                    let seven = <$t>::from(7u8);
+                    let two = <$t>::from(2u8);
+                    let five = <$t>::from(5u8);
+                    let four = <$t>::from(4u8);
+                    let ten = <$t>::TEN;
+                    for (mode, expected_3_5, expected_2_5) in [
+                        (RoundingMode::ToPositiveInfinity, 4u8, 3u8),
+                        (RoundingMode::ToNegativeInfinity, 3, 2),
+                        (RoundingMode::ToZero, 3, 2),
+                        (RoundingMode::AwayFromZero, 4, 3),
+                        (RoundingMode::ToNearestMidpointTowardZero, 3, 2),
+                        (RoundingMode::ToNearestMidpointAwayFromZero, 4, 3),
+                        (RoundingMode::ToNearestMidpointToEven, 4, 2),
+                    ] {
+                        assert_eq!(seven.checked_mul_div(five, ten, mode), Some(<$t>::from(expected_3_5)));
+                        assert_eq!(two.checked_mul_div(five, four, mode), Some(<$t>::from(expected_2_5)));
+                    }
+                }
+            )*
+        }
+    };
+}
+test_checked_mul_div! { I192, I256, I320, I384, I448, I512, I768 }
+test_checked_mul_div! { U192, U256, U320, U384, U448, U512, U768 }
+
+macro_rules! test_checked_mul_div_signed {
+    ($($t:ident),*) => {
+        paste! {
+            $(
+                #[test]
+                fn [<test_checked_mul_div_signed_ $t:lower>]() {
+                    assert_eq!(<$t>::MIN.checked_mul_div(<$t>::MIN, <$t>::MIN, RoundingMode::ToZero), Some(<$t>::MIN));
+                    assert_eq!(<$t>::MIN.checked_mul_div(-<$t>::ONE, <$t>::ONE, RoundingMode::ToZero), None);
+
+                    // -7 * 5 / 10 = 7 * 5 / -10 = -3.5
+                    let seven = <$t>::from(7u8);
+                    let five = <$t>::from(5u8);
+                    let ten = <$t>::TEN;
+                    for (mode, expected) in [
+                        (RoundingMode::ToPositiveInfinity, -3i8),
+                        (RoundingMode::ToNegativeInfinity, -4),
+                        (RoundingMode::ToZero, -3),
+                        (RoundingMode::AwayFromZero, -4),
+                        (RoundingMode::ToNearestMidpointTowardZero, -3),
+                        (RoundingMode::ToNearestMidpointAwayFromZero, -4),
+                        (RoundingMode::ToNearestMidpointToEven, -4),
+                    ] {
+                        assert_eq!((-seven).checked_mul_div(five, ten, mode), Some(<$t>::from(expected)));
+                        assert_eq!(seven.checked_mul_div(five, -ten, mode), Some(<$t>::from(expected)));
+                        assert_eq!((-seven).checked_mul_div(-five, -ten, mode), Some(<$t>::from(expected)));
+                    }
+
+                    // -7 * 3 / 10 = -2.1, -9 * 3 / 10 = -2.7 and -1 * 3 / 10 = -0.3
+                    let one = <$t>::ONE;
+                    let three = <$t>::from(3u8);
+                    let nine = <$t>::from(9u8);
+                    for mode in [
+                        RoundingMode::ToNearestMidpointTowardZero,
+                        RoundingMode::ToNearestMidpointAwayFromZero,
+                        RoundingMode::ToNearestMidpointToEven,
+                    ] {
+                        assert_eq!((-seven).checked_mul_div(three, ten, mode), Some(<$t>::from(-2i8)));
+                        assert_eq!((-nine).checked_mul_div(three, ten, mode), Some(<$t>::from(-3i8)));
+                        assert_eq!((-one).checked_mul_div(three, ten, mode), Some(<$t>::ZERO));
+                        assert_eq!(seven.checked_mul_div(three, -ten, mode), Some(<$t>::from(-2i8)));
+                        assert_eq!(nine.checked_mul_div(-three, ten, mode), Some(<$t>::from(-3i8)));
+                    }
+                }
+            )*
+        }
+    };
+}
+test_checked_mul_div_signed! { I192, I256, I320, I384, I448, I512, I768 }
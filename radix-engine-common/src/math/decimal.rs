@@ -17,6 +17,7 @@ use crate::data::scrypto::*;
 use crate::math::bnum_integer::*;
 use crate::math::rounding_mode::*;
 use crate::math::traits::*;
+use crate::math::transcendental;
 use crate::math::PreciseDecimal;
 use crate::well_known_scrypto_custom_type;
 use crate::*;
@@ -187,6 +188,13 @@ impl Decimal {
         Some(Self(rounded_subunits))
     }
 
+    /// Rounds this number to an integer with the given mode, and converts it to the integer
+    /// type `T`, or returns `None` if the rounded value does not fit in `T`.
+    pub fn checked_to_integer<T: TryFrom<I192>>(&self, mode: RoundingMode) -> Option<T> {
+        let rounded = self.checked_round(0, mode)?;
+        T::try_from(rounded.0 / Self::ONE.0).ok()
+    }
+
     /// Calculates power using exponentiation by squaring".
     pub fn checked_powi(&self, exp: i64) -> Option<Self> {
         let one_256 = I256::from(Self::ONE.0);
@@ -269,6 +277,74 @@ impl Decimal {
             Some(Decimal(nth_root))
         }
     }
+
+    /// Natural logarithm of a Decimal, or `None` if it is not positive.
+    ///
+    /// The result is rounded to the nearest Decimal, with midpoints rounded away from zero, and
+    /// is within one unit in the last place of the exact value.
+    pub fn checked_ln(&self) -> Option<Self> {
+        let ln = transcendental::checked_ln(&BigInt::from(self.0), Self::SCALE, Self::BITS as u32)?;
+        I192::try_from(ln).ok().map(Self)
+    }
+
+    /// Base 2 logarithm of a Decimal, or `None` if it is not positive.
+    ///
+    /// Rounding and error bound are the same as for [`Decimal::checked_ln`].
+    pub fn checked_log2(&self) -> Option<Self> {
+        let log =
+            transcendental::checked_log(&BigInt::from(self.0), 2, Self::SCALE, Self::BITS as u32)?;
+        I192::try_from(log).ok().map(Self)
+    }
+
+    /// Base 10 logarithm of a Decimal, or `None` if it is not positive.
+    ///
+    /// Rounding and error bound are the same as for [`Decimal::checked_ln`].
+    pub fn checked_log10(&self) -> Option<Self> {
+        let log =
+            transcendental::checked_log(&BigInt::from(self.0), 10, Self::SCALE, Self::BITS as u32)?;
+        I192::try_from(log).ok().map(Self)
+    }
+
+    /// Exponential function of a Decimal, or `None` if the result overflows.
+    ///
+    /// Results smaller than the smallest positive Decimal are rounded to zero. Rounding and error
+    /// bound are the same as for [`Decimal::checked_ln`].
+    pub fn checked_exp(&self) -> Option<Self> {
+        let exp =
+            transcendental::checked_exp(&BigInt::from(self.0), Self::SCALE, Self::BITS as u32)?;
+        I192::try_from(exp).ok().map(Self)
+    }
+
+    /// Raises a Decimal to a Decimal power, or returns `None` if the result overflows or is not
+    /// a real number.
+    ///
+    /// Positive bases are raised with `exp(exp * ln(self))`, computed with enough extra
+    /// precision for the rounding and error bound to be the same as for [`Decimal::checked_ln`].
+    /// Negative bases are only supported for integer powers, which are computed with
+    /// [`Decimal::checked_powi`]. Zero raised to a negative power is `None`.
+    pub fn checked_pow(&self, exp: Self) -> Option<Self> {
+        if self.is_positive() {
+            let pow = transcendental::checked_pow(
+                &BigInt::from(self.0),
+                &BigInt::from(exp.0),
+                Self::SCALE,
+                Self::BITS as u32,
+            )?;
+            I192::try_from(pow).ok().map(Self)
+        } else if self.is_zero() {
+            if exp.is_negative() {
+                None
+            } else if exp.is_zero() {
+                Some(Self::ONE)
+            } else {
+                Some(Self::ZERO)
+            }
+        } else if (exp.0 % Self::ONE.0).is_zero() {
+            self.checked_powi(i64::try_from(exp.0 / Self::ONE.0).ok()?)
+        } else {
+            None
+        }
+    }
 }
 
 macro_rules! from_primitive_type {
@@ -409,6 +485,19 @@ impl CheckedDiv<Decimal> for Decimal {
     }
 }
 
+impl CheckedMulDiv<Decimal> for Decimal {
+    type Output = Self;
+
+    #[inline]
+    fn checked_mul_div(self, multiplier: Self, divisor: Self, mode: RoundingMode) -> Option<Self> {
+        // The scale of the product cancels out with the scale of the divisor, so the subunits
+        // can be multiplied and divided as they are.
+        self.0
+            .checked_mul_div(multiplier.0, divisor.0, mode)
+            .map(Self)
+    }
+}
+
 impl Neg for Decimal {
     type Output = Self;
 
@@ -1932,6 +2021,265 @@ mod tests {
         assert_eq!(root_0, None);
     }
 
+    #[test]
+    fn test_ln() {
+        assert_eq!(test_dec!(1).checked_ln().unwrap(), Decimal::ZERO);
+        assert_eq!(
+            test_dec!(2).checked_ln().unwrap(),
+            test_dec!("0.693147180559945309")
+        );
+        assert_eq!(
+            test_dec!(10).checked_ln().unwrap(),
+            test_dec!("2.302585092994045684")
+        );
+        assert_eq!(
+            test_dec!("123456.789").checked_ln().unwrap(),
+            test_dec!("11.723646487185880981")
+        );
+        assert_eq!(
+            Decimal(I192::ONE).checked_ln().unwrap(),
+            test_dec!("-41.446531673892822312")
+        );
+        assert_eq!(
+            Decimal::MAX.checked_ln().unwrap(),
+            test_dec!("90.944579813056731786")
+        );
+        assert_eq!(Decimal::ZERO.checked_ln(), None);
+        assert_eq!(test_dec!("-1").checked_ln(), None);
+    }
+
+    #[test]
+    fn test_log2_and_log10() {
+        assert_eq!(test_dec!(1024).checked_log2().unwrap(), test_dec!(10));
+        assert_eq!(
+            test_dec!(3).checked_log2().unwrap(),
+            test_dec!("1.584962500721156181")
+        );
+        assert_eq!(test_dec!(1000).checked_log10().unwrap(), test_dec!(3));
+        assert_eq!(test_dec!("0.001").checked_log10().unwrap(), test_dec!("-3"));
+        assert_eq!(
+            test_dec!(2).checked_log10().unwrap(),
+            test_dec!("0.301029995663981195")
+        );
+        assert_eq!(Decimal::ZERO.checked_log2(), None);
+        assert_eq!(test_dec!("-10").checked_log10(), None);
+    }
+
+    #[test]
+    fn test_exp() {
+        assert_eq!(Decimal::ZERO.checked_exp().unwrap(), Decimal::ONE);
+        assert_eq!(
+            Decimal::ONE.checked_exp().unwrap(),
+            test_dec!("2.718281828459045235")
+        );
+        assert_eq!(
+            test_dec!("-1").checked_exp().unwrap(),
+            test_dec!("0.367879441171442322")
+        );
+        assert_eq!(
+            test_dec!(10).checked_exp().unwrap(),
+            test_dec!("22026.465794806716516958")
+        );
+        assert_eq!(
+            test_dec!(90).checked_exp().unwrap(),
+            test_dec!("1220403294317840802002710035136369753970.746421099767546244")
+        );
+        assert_eq!(test_dec!(91).checked_exp(), None);
+        assert_eq!(Decimal::MAX.checked_exp(), None);
+        assert_eq!(test_dec!("-42.5").checked_exp().unwrap(), Decimal::ZERO);
+        assert_eq!(Decimal::MIN.checked_exp().unwrap(), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_pow() {
+        assert_eq!(
+            test_dec!(2).checked_pow(test_dec!("0.5")).unwrap(),
+            test_dec!("1.414213562373095049")
+        );
+        assert_eq!(
+            test_dec!(4).checked_pow(test_dec!("-0.5")).unwrap(),
+            test_dec!("0.5")
+        );
+        assert_eq!(
+            test_dec!("1.05").checked_pow(test_dec!("10.5")).unwrap(),
+            test_dec!("1.669120304352457735")
+        );
+        assert_eq!(
+            test_dec!(10).checked_pow(test_dec!("-3")).unwrap(),
+            test_dec!("0.001")
+        );
+        assert_eq!(
+            test_dec!("-2").checked_pow(test_dec!(3)).unwrap(),
+            test_dec!("-8")
+        );
+        assert_eq!(test_dec!("-2").checked_pow(test_dec!("0.5")), None);
+        assert_eq!(
+            Decimal::ZERO.checked_pow(test_dec!("0.5")).unwrap(),
+            Decimal::ZERO
+        );
+        assert_eq!(
+            Decimal::ZERO.checked_pow(Decimal::ZERO).unwrap(),
+            Decimal::ONE
+        );
+        assert_eq!(Decimal::ZERO.checked_pow(test_dec!("-0.5")), None);
+        assert_eq!(test_dec!(10).checked_pow(test_dec!(40)), None);
+    }
+
+    #[test]
+    fn test_checked_mul_div() {
+        // Multiplying first would overflow
+        assert_eq!(Decimal::MAX.checked_mul(test_dec!(2)), None);
+        assert_eq!(
+            Decimal::MAX.checked_mul_div(test_dec!(2), test_dec!(4), RoundingMode::ToZero),
+            Some(Decimal(I192::MAX / I192::from(2u8)))
+        );
+        assert_eq!(
+            Decimal::MAX.checked_mul_div(Decimal::MAX, Decimal::MAX, RoundingMode::ToZero),
+            Some(Decimal::MAX)
+        );
+
+        // The quotient is rounded once, with the given mode
+        let one = Decimal::ONE;
+        let three = test_dec!(3);
+        assert_eq!(
+            one.checked_mul_div(one, three, RoundingMode::ToPositiveInfinity),
+            Some(test_dec!("0.333333333333333334"))
+        );
+        assert_eq!(
+            one.checked_mul_div(one, three, RoundingMode::ToNegativeInfinity),
+            Some(test_dec!("0.333333333333333333"))
+        );
+        assert_eq!(
+            (-one).checked_mul_div(one, three, RoundingMode::ToNegativeInfinity),
+            Some(test_dec!("-0.333333333333333334"))
+        );
+        assert_eq!(
+            test_dec!(10).checked_mul_div(
+                test_dec!(2),
+                three,
+                RoundingMode::ToNearestMidpointToEven
+            ),
+            Some(test_dec!("6.666666666666666667"))
+        );
+        assert_eq!(
+            test_dec!("0.000000000000000001").checked_mul_div(
+                test_dec!("0.5"),
+                one,
+                RoundingMode::ToNearestMidpointToEven
+            ),
+            Some(Decimal::ZERO)
+        );
+        assert_eq!(
+            test_dec!("0.000000000000000003").checked_mul_div(
+                test_dec!("0.5"),
+                one,
+                RoundingMode::ToNearestMidpointToEven
+            ),
+            Some(test_dec!("0.000000000000000002"))
+        );
+
+        assert_eq!(
+            one.checked_mul_div(one, Decimal::ZERO, RoundingMode::ToZero),
+            None
+        );
+        assert_eq!(
+            Decimal::MAX.checked_mul_div(three, test_dec!(2), RoundingMode::ToZero),
+            None
+        );
+    }
+
+    #[test]
+    fn test_checked_mul_div_rounds_negative_results_to_nearest() {
+        let one = Decimal::ONE;
+        for mode in [
+            RoundingMode::ToNearestMidpointTowardZero,
+            RoundingMode::ToNearestMidpointAwayFromZero,
+            RoundingMode::ToNearestMidpointToEven,
+        ] {
+            // -0.3 and -0.7 subunits
+            assert_eq!(
+                test_dec!("-0.000000000000000001").checked_mul_div(test_dec!("0.3"), one, mode),
+                Some(Decimal::ZERO)
+            );
+            assert_eq!(
+                test_dec!("-0.000000000000000001").checked_mul_div(test_dec!("0.7"), one, mode),
+                Some(test_dec!("-0.000000000000000001"))
+            );
+            // -6.666... and -3.333...
+            assert_eq!(
+                test_dec!(-20).checked_mul_div(one, test_dec!(3), mode),
+                Some(test_dec!("-6.666666666666666667"))
+            );
+            assert_eq!(
+                test_dec!(10).checked_mul_div(one, test_dec!(-3), mode),
+                Some(test_dec!("-3.333333333333333333"))
+            );
+        }
+
+        // -2.5 subunits
+        let half = test_dec!("0.5");
+        let subunits = test_dec!("-0.000000000000000005");
+        assert_eq!(
+            subunits.checked_mul_div(half, one, RoundingMode::ToNearestMidpointTowardZero),
+            Some(test_dec!("-0.000000000000000002"))
+        );
+        assert_eq!(
+            subunits.checked_mul_div(half, one, RoundingMode::ToNearestMidpointAwayFromZero),
+            Some(test_dec!("-0.000000000000000003"))
+        );
+        assert_eq!(
+            subunits.checked_mul_div(half, one, RoundingMode::ToNearestMidpointToEven),
+            Some(test_dec!("-0.000000000000000002"))
+        );
+    }
+
+    #[test]
+    fn test_checked_to_integer() {
+        assert_eq!(
+            test_dec!("2.5").checked_to_integer::<i64>(RoundingMode::ToNearestMidpointToEven),
+            Some(2i64)
+        );
+        assert_eq!(
+            test_dec!("-2.5").checked_to_integer::<i64>(RoundingMode::AwayFromZero),
+            Some(-3i64)
+        );
+        assert_eq!(
+            test_dec!("1.1").checked_to_integer::<I256>(RoundingMode::ToPositiveInfinity),
+            Some(I256::from(2u8))
+        );
+        assert_eq!(
+            Decimal::MAX.checked_to_integer::<I192>(RoundingMode::ToZero),
+            Some(I192::from_str("3138550867693340381917894711603833208051").unwrap())
+        );
+        assert_eq!(
+            Decimal::MAX.checked_to_integer::<I192>(RoundingMode::ToPositiveInfinity),
+            None
+        );
+        assert_eq!(
+            Decimal::MAX.checked_to_integer::<u64>(RoundingMode::ToZero),
+            None
+        );
+        assert_eq!(
+            test_dec!("-0.5").checked_to_integer::<U192>(RoundingMode::ToNegativeInfinity),
+            None
+        );
+        assert_eq!(
+            test_dec!("-0.5").checked_to_integer::<U192>(RoundingMode::ToZero),
+            Some(U192::ZERO)
+        );
+    }
+
+    #[test]
+    fn test_exp_of_ln_is_identity() {
+        for x in ["0.5", "1", "2", "3.14159", "1000", "987654321.123456789"] {
+            let x = Decimal::try_from(x).unwrap();
+            let result = x.checked_ln().unwrap().checked_exp().unwrap();
+            // The error of `ln(x)` is scaled by `x` when taking the exponential
+            let tolerance = Decimal(x.0 / I192::from(10u64.pow(Decimal::SCALE)) + I192::from(2u8));
+            assert!((result - x).checked_abs().unwrap() <= tolerance, "{}", x);
+        }
+    }
+
     #[test]
     fn no_panic_with_18_decimal_places() {
         // Arrange
@@ -19,6 +19,7 @@ use crate::math::bnum_integer::*;
 use crate::math::decimal::*;
 use crate::math::rounding_mode::*;
 use crate::math::traits::*;
+use crate::math::transcendental;
 use crate::well_known_scrypto_custom_type;
 use crate::*;
 
@@ -208,6 +209,13 @@ impl PreciseDecimal {
         Some(Self(rounded_subunits))
     }
 
+    /// Rounds this number to an integer with the given mode, and converts it to the integer
+    /// type `T`, or returns `None` if the rounded value does not fit in `T`.
+    pub fn checked_to_integer<T: TryFrom<I256>>(&self, mode: RoundingMode) -> Option<T> {
+        let rounded = self.checked_round(0, mode)?;
+        T::try_from(rounded.0 / Self::ONE.0).ok()
+    }
+
     /// Calculates power using exponentiation by squaring.
     pub fn checked_powi(&self, exp: i64) -> Option<Self> {
         let one_384 = I384::from(Self::ONE.0);
@@ -293,6 +301,74 @@ impl PreciseDecimal {
             Some(Self(nth_root))
         }
     }
+
+    /// Natural logarithm of a PreciseDecimal, or `None` if it is not positive.
+    ///
+    /// The result is rounded to the nearest PreciseDecimal, with midpoints rounded away from zero, and
+    /// is within one unit in the last place of the exact value.
+    pub fn checked_ln(&self) -> Option<Self> {
+        let ln = transcendental::checked_ln(&BigInt::from(self.0), Self::SCALE, Self::BITS as u32)?;
+        I256::try_from(ln).ok().map(Self)
+    }
+
+    /// Base 2 logarithm of a PreciseDecimal, or `None` if it is not positive.
+    ///
+    /// Rounding and error bound are the same as for [`PreciseDecimal::checked_ln`].
+    pub fn checked_log2(&self) -> Option<Self> {
+        let log =
+            transcendental::checked_log(&BigInt::from(self.0), 2, Self::SCALE, Self::BITS as u32)?;
+        I256::try_from(log).ok().map(Self)
+    }
+
+    /// Base 10 logarithm of a PreciseDecimal, or `None` if it is not positive.
+    ///
+    /// Rounding and error bound are the same as for [`PreciseDecimal::checked_ln`].
+    pub fn checked_log10(&self) -> Option<Self> {
+        let log =
+            transcendental::checked_log(&BigInt::from(self.0), 10, Self::SCALE, Self::BITS as u32)?;
+        I256::try_from(log).ok().map(Self)
+    }
+
+    /// Exponential function of a PreciseDecimal, or `None` if the result overflows.
+    ///
+    /// Results smaller than the smallest positive PreciseDecimal are rounded to zero. Rounding and error
+    /// bound are the same as for [`PreciseDecimal::checked_ln`].
+    pub fn checked_exp(&self) -> Option<Self> {
+        let exp =
+            transcendental::checked_exp(&BigInt::from(self.0), Self::SCALE, Self::BITS as u32)?;
+        I256::try_from(exp).ok().map(Self)
+    }
+
+    /// Raises a PreciseDecimal to a PreciseDecimal power, or returns `None` if the result overflows or is not
+    /// a real number.
+    ///
+    /// Positive bases are raised with `exp(exp * ln(self))`, computed with enough extra
+    /// precision for the rounding and error bound to be the same as for [`PreciseDecimal::checked_ln`].
+    /// Negative bases are only supported for integer powers, which are computed with
+    /// [`PreciseDecimal::checked_powi`]. Zero raised to a negative power is `None`.
+    pub fn checked_pow(&self, exp: Self) -> Option<Self> {
+        if self.is_positive() {
+            let pow = transcendental::checked_pow(
+                &BigInt::from(self.0),
+                &BigInt::from(exp.0),
+                Self::SCALE,
+                Self::BITS as u32,
+            )?;
+            I256::try_from(pow).ok().map(Self)
+        } else if self.is_zero() {
+            if exp.is_negative() {
+                None
+            } else if exp.is_zero() {
+                Some(Self::ONE)
+            } else {
+                Some(Self::ZERO)
+            }
+        } else if (exp.0 % Self::ONE.0).is_zero() {
+            self.checked_powi(i64::try_from(exp.0 / Self::ONE.0).ok()?)
+        } else {
+            None
+        }
+    }
 }
 
 macro_rules! from_primitive_type {
@@ -434,6 +510,19 @@ impl CheckedDiv<PreciseDecimal> for PreciseDecimal {
     }
 }
 
+impl CheckedMulDiv<PreciseDecimal> for PreciseDecimal {
+    type Output = Self;
+
+    #[inline]
+    fn checked_mul_div(self, multiplier: Self, divisor: Self, mode: RoundingMode) -> Option<Self> {
+        // The scale of the product cancels out with the scale of the divisor, so the subunits
+        // can be multiplied and divided as they are.
+        self.0
+            .checked_mul_div(multiplier.0, divisor.0, mode)
+            .map(Self)
+    }
+}
+
 impl Neg for PreciseDecimal {
     type Output = Self;
 
@@ -2134,6 +2223,187 @@ mod tests {
         assert_eq!(root_0, None);
     }
 
+    #[test]
+    fn test_ln_pdec() {
+        assert_eq!(test_pdec!(1).checked_ln().unwrap(), PreciseDecimal::ZERO);
+        assert_eq!(
+            test_pdec!(2).checked_ln().unwrap(),
+            test_pdec!("0.693147180559945309417232121458176568")
+        );
+        assert_eq!(
+            test_pdec!(10).checked_ln().unwrap(),
+            test_pdec!("2.302585092994045684017991454684364208")
+        );
+        assert_eq!(
+            PreciseDecimal(I256::ONE).checked_ln().unwrap(),
+            test_pdec!("-82.893063347785644624647692368637111474")
+        );
+        assert_eq!(
+            PreciseDecimal::MAX.checked_ln().unwrap(),
+            test_pdec!("93.859467695000409276746498603197913386")
+        );
+        assert_eq!(PreciseDecimal::ZERO.checked_ln(), None);
+        assert_eq!(test_pdec!("-1").checked_ln(), None);
+    }
+
+    #[test]
+    fn test_log2_and_log10_pdec() {
+        assert_eq!(test_pdec!(1024).checked_log2().unwrap(), test_pdec!(10));
+        assert_eq!(
+            test_pdec!(3).checked_log2().unwrap(),
+            test_pdec!("1.584962500721156181453738943947816509")
+        );
+        assert_eq!(
+            test_pdec!("0.001").checked_log10().unwrap(),
+            test_pdec!("-3")
+        );
+        assert_eq!(
+            test_pdec!(2).checked_log10().unwrap(),
+            test_pdec!("0.301029995663981195213738894724493027")
+        );
+        assert_eq!(PreciseDecimal::ZERO.checked_log10(), None);
+    }
+
+    #[test]
+    fn test_exp_pdec() {
+        assert_eq!(
+            PreciseDecimal::ZERO.checked_exp().unwrap(),
+            PreciseDecimal::ONE
+        );
+        assert_eq!(
+            PreciseDecimal::ONE.checked_exp().unwrap(),
+            test_pdec!("2.718281828459045235360287471352662498")
+        );
+        assert_eq!(
+            test_pdec!("-50").checked_exp().unwrap(),
+            test_pdec!("0.000000000000000000000192874984796392")
+        );
+        assert_eq!(test_pdec!(94).checked_exp(), None);
+        assert_eq!(
+            test_pdec!("-100").checked_exp().unwrap(),
+            PreciseDecimal::ZERO
+        );
+        assert_eq!(
+            PreciseDecimal::MIN.checked_exp().unwrap(),
+            PreciseDecimal::ZERO
+        );
+    }
+
+    #[test]
+    fn test_checked_mul_div_pdec() {
+        assert_eq!(PreciseDecimal::MAX.checked_mul(test_pdec!(2)), None);
+        assert_eq!(
+            PreciseDecimal::MAX.checked_mul_div(
+                PreciseDecimal::MAX,
+                PreciseDecimal::MAX,
+                RoundingMode::ToZero
+            ),
+            Some(PreciseDecimal::MAX)
+        );
+
+        let one = PreciseDecimal::ONE;
+        let three = test_pdec!(3);
+        assert_eq!(
+            one.checked_mul_div(one, three, RoundingMode::ToPositiveInfinity),
+            Some(test_pdec!("0.333333333333333333333333333333333334"))
+        );
+        assert_eq!(
+            (-one).checked_mul_div(one, three, RoundingMode::ToZero),
+            Some(test_pdec!("-0.333333333333333333333333333333333333"))
+        );
+        assert_eq!(
+            one.checked_mul_div(one, PreciseDecimal::ZERO, RoundingMode::ToZero),
+            None
+        );
+    }
+
+    #[test]
+    fn test_checked_mul_div_rounds_negative_results_to_nearest_pdec() {
+        let one = PreciseDecimal::ONE;
+        for mode in [
+            RoundingMode::ToNearestMidpointTowardZero,
+            RoundingMode::ToNearestMidpointAwayFromZero,
+            RoundingMode::ToNearestMidpointToEven,
+        ] {
+            // -0.3 and -0.7 subunits
+            assert_eq!(
+                test_pdec!("-0.000000000000000000000000000000000001").checked_mul_div(
+                    test_pdec!("0.3"),
+                    one,
+                    mode
+                ),
+                Some(PreciseDecimal::ZERO)
+            );
+            assert_eq!(
+                test_pdec!("-0.000000000000000000000000000000000001").checked_mul_div(
+                    test_pdec!("0.7"),
+                    one,
+                    mode
+                ),
+                Some(test_pdec!("-0.000000000000000000000000000000000001"))
+            );
+            // -6.666... and -3.333...
+            assert_eq!(
+                test_pdec!(-20).checked_mul_div(one, test_pdec!(3), mode),
+                Some(test_pdec!("-6.666666666666666666666666666666666667"))
+            );
+            assert_eq!(
+                test_pdec!(10).checked_mul_div(one, test_pdec!(-3), mode),
+                Some(test_pdec!("-3.333333333333333333333333333333333333"))
+            );
+        }
+
+        // -2.5 subunits
+        let half = test_pdec!("0.5");
+        let subunits = test_pdec!("-0.000000000000000000000000000000000005");
+        assert_eq!(
+            subunits.checked_mul_div(half, one, RoundingMode::ToNearestMidpointTowardZero),
+            Some(test_pdec!("-0.000000000000000000000000000000000002"))
+        );
+        assert_eq!(
+            subunits.checked_mul_div(half, one, RoundingMode::ToNearestMidpointAwayFromZero),
+            Some(test_pdec!("-0.000000000000000000000000000000000003"))
+        );
+        assert_eq!(
+            subunits.checked_mul_div(half, one, RoundingMode::ToNearestMidpointToEven),
+            Some(test_pdec!("-0.000000000000000000000000000000000002"))
+        );
+    }
+
+    #[test]
+    fn test_checked_to_integer_pdec() {
+        assert_eq!(
+            test_pdec!("2.5").checked_to_integer::<u8>(RoundingMode::ToNearestMidpointToEven),
+            Some(2u8)
+        );
+        assert_eq!(
+            test_pdec!("-2.5").checked_to_integer::<I512>(RoundingMode::ToNegativeInfinity),
+            Some(I512::from(-3i8))
+        );
+        assert_eq!(
+            PreciseDecimal::MIN.checked_to_integer::<i128>(RoundingMode::ToZero),
+            None
+        );
+    }
+
+    #[test]
+    fn test_pow_pdec() {
+        assert_eq!(
+            test_pdec!(2).checked_pow(test_pdec!("0.5")).unwrap(),
+            test_pdec!("1.414213562373095048801688724209698079")
+        );
+        assert_eq!(
+            test_pdec!("1.05").checked_pow(test_pdec!("10.5")).unwrap(),
+            test_pdec!("1.669120304352457735280918327457546318")
+        );
+        assert_eq!(
+            test_pdec!("-3").checked_pow(test_pdec!(2)).unwrap(),
+            test_pdec!(9)
+        );
+        assert_eq!(test_pdec!("-3").checked_pow(test_pdec!("2.5")), None);
+        assert_eq!(PreciseDecimal::ZERO.checked_pow(test_pdec!("-1")), None);
+    }
+
     #[test]
     fn no_panic_with_36_decimal_places() {
         // Arrange
@@ -1,3 +1,5 @@
+use crate::math::rounding_mode::RoundingMode;
+
 pub trait CheckedAdd<Rhs = Self> {
     type Output;
 
@@ -30,6 +32,22 @@ pub trait CheckedDiv<Rhs = Self> {
         Self: Sized;
 }
 
+/// Computes `self * multiplier / divisor` without overflowing the intermediate product.
+pub trait CheckedMulDiv<Rhs = Self> {
+    type Output;
+
+    /// Returns `self * multiplier / divisor`, with the quotient rounded once with the given
+    /// mode, or `None` if the divisor is zero or the result overflows.
+    fn checked_mul_div(
+        self,
+        multiplier: Rhs,
+        divisor: Rhs,
+        mode: RoundingMode,
+    ) -> Option<Self::Output>
+    where
+        Self: Sized;
+}
+
 pub trait CheckedNeg<Rhs = Self> {
     type Output;
 
@@ -0,0 +1,599 @@
+//! Fixed-point implementations of the natural logarithm and exponential functions, shared by
+//! `Decimal` and `PreciseDecimal`.
+//!
+//! Numbers are represented as `BigInt`s scaled by `10^scale`. Each function converts its
+//! arguments to a working scale carrying enough guard digits for every significant digit of
+//! the target type to be exact before the final rounding, so that results are deterministic
+//! and within one unit in the last place of the target type.
+
+use crate::math::rounding_mode::*;
+use core::cmp::Ordering;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{Pow, Signed, Zero};
+
+/// The rounding applied to the results of the functions of this module.
+pub(crate) const TRANSCENDENTAL_ROUNDING_MODE: RoundingMode =
+    RoundingMode::ToNearestMidpointAwayFromZero;
+
+/// Guard digits on top of the significant digits of the target type.
+const GUARD_DIGITS: u32 = 24;
+
+/// Returns the working scale used for a target type of `bits` bits.
+///
+/// A `bits` bit integer has at most `bits * log10(2) < bits * 3 / 10 + 1` significant digits.
+fn working_scale(bits: u32) -> u32 {
+    bits * 3 / 10 + 1 + GUARD_DIGITS
+}
+
+fn pow10(exponent: u32) -> BigInt {
+    BigInt::from(10u8).pow(exponent)
+}
+
+/// Divides `numerator` by the positive `denominator`, rounding with the given mode.
+pub(crate) fn div_round(numerator: &BigInt, denominator: &BigInt, mode: RoundingMode) -> BigInt {
+    let (quotient, remainder) = numerator.div_mod_floor(denominator);
+    if remainder.is_zero() {
+        return quotient;
+    }
+
+    let strategy = ResolvedRoundingStrategy::from_mode(mode, numerator.is_positive(), || {
+        (&remainder * 2u8).cmp(denominator)
+    });
+    match strategy {
+        ResolvedRoundingStrategy::RoundUp => quotient + 1u8,
+        ResolvedRoundingStrategy::RoundDown => quotient,
+        ResolvedRoundingStrategy::RoundToEven => {
+            if quotient.is_even() {
+                quotient
+            } else {
+                quotient + 1u8
+            }
+        }
+    }
+}
+
+fn rescale(value: &BigInt, from_scale: u32, to_scale: u32) -> BigInt {
+    match from_scale.cmp(&to_scale) {
+        Ordering::Less => value * pow10(to_scale - from_scale),
+        Ordering::Equal => value.clone(),
+        Ordering::Greater => div_round(
+            value,
+            &pow10(from_scale - to_scale),
+            TRANSCENDENTAL_ROUNDING_MODE,
+        ),
+    }
+}
+
+/// Returns `2 * atanh(z)` at the given scale, for `0 <= z < 1`.
+///
+/// The series converges geometrically with ratio `z^2`.
+fn double_atanh(z: &BigInt, one: &BigInt) -> BigInt {
+    let z_squared = z * z / one;
+    let mut power = z.clone();
+    let mut sum = z.clone();
+    let mut n = 1u32;
+    loop {
+        power = &power * &z_squared / one;
+        if power.is_zero() {
+            break;
+        }
+        n += 2;
+        sum += &power / n;
+    }
+    sum * 2u8
+}
+
+/// Returns `ln(2)` at the given scale, as `2 * atanh(1/3)`.
+fn ln_2(one: &BigInt) -> BigInt {
+    double_atanh(&(one / 3u8), one)
+}
+
+/// Returns `ln(x)` at the scale of `one`, for a positive `x` at the same scale.
+fn ln_fixed(x: &BigInt, one: &BigInt) -> BigInt {
+    // Reduce to `x = 2^k * m` with `1 <= m < 2`
+    let mut k = x.bits() as i64 - one.bits() as i64;
+    let mut m = if k >= 0 {
+        x >> (k as usize)
+    } else {
+        x << ((-k) as usize)
+    };
+    let two = one * 2u8;
+    while m >= two {
+        m >>= 1;
+        k += 1;
+    }
+    while &m < one {
+        m <<= 1;
+        k -= 1;
+    }
+
+    // `ln(m) = 2 * atanh((m - 1) / (m + 1))`, with `(m - 1) / (m + 1) < 1/3`
+    let z = (&m - one) * one / (&m + one);
+    double_atanh(&z, one) + ln_2(one) * k
+}
+
+/// Returns `exp(x)` at the scale of `one`, or `None` if the result has more than
+/// `max_bits` integer bits.
+fn exp_fixed(x: &BigInt, one: &BigInt, max_bits: u32) -> Option<BigInt> {
+    // Reduce to `x = k * ln(2) + r` with `|r| <= ln(2) / 2`
+    let ln_2 = ln_2(one);
+    let k = div_round(x, &ln_2, RoundingMode::ToNearestMidpointAwayFromZero);
+    if k > BigInt::from(max_bits) {
+        return None;
+    }
+    if k < -BigInt::from(one.bits()) {
+        return Some(BigInt::zero());
+    }
+    let k = i64::try_from(k).ok()?;
+    let r = x - &ln_2 * k;
+
+    // Taylor series of `exp(r)`
+    let mut term = one.clone();
+    let mut sum = one.clone();
+    let mut n = 0u32;
+    loop {
+        n += 1;
+        term = &term * &r / one / n;
+        if term.is_zero() {
+            break;
+        }
+        sum += &term;
+    }
+
+    Some(if k >= 0 {
+        sum << (k as usize)
+    } else {
+        sum >> ((-k) as usize)
+    })
+}
+
+/// Returns `ln(value)`, for a `value` at the given scale of a `bits` bit type.
+pub(crate) fn checked_ln(value: &BigInt, scale: u32, bits: u32) -> Option<BigInt> {
+    if !value.is_positive() {
+        return None;
+    }
+    let working_scale = working_scale(bits);
+    let one = pow10(working_scale);
+    let ln = ln_fixed(&rescale(value, scale, working_scale), &one);
+    Some(rescale(&ln, working_scale, scale))
+}
+
+/// Returns `log_base(value)`, for a `value` at the given scale of a `bits` bit type.
+pub(crate) fn checked_log(value: &BigInt, base: u32, scale: u32, bits: u32) -> Option<BigInt> {
+    if !value.is_positive() {
+        return None;
+    }
+    let working_scale = working_scale(bits);
+    let one = pow10(working_scale);
+    let ln = ln_fixed(&rescale(value, scale, working_scale), &one);
+    let ln_base = ln_fixed(&(&one * base), &one);
+    let log = div_round(&(ln * &one), &ln_base, TRANSCENDENTAL_ROUNDING_MODE);
+    Some(rescale(&log, working_scale, scale))
+}
+
+/// Returns `exp(value)`, for a `value` at the given scale of a `bits` bit type.
+pub(crate) fn checked_exp(value: &BigInt, scale: u32, bits: u32) -> Option<BigInt> {
+    let working_scale = working_scale(bits);
+    let one = pow10(working_scale);
+    let exp = exp_fixed(&rescale(value, scale, working_scale), &one, bits)?;
+    Some(rescale(&exp, working_scale, scale))
+}
+
+/// Returns `base^exponent` as `exp(exponent * ln(base))`, for a positive `base` and an
+/// `exponent` at the given scale of a `bits` bit type.
+pub(crate) fn checked_pow(
+    base: &BigInt,
+    exponent: &BigInt,
+    scale: u32,
+    bits: u32,
+) -> Option<BigInt> {
+    if !base.is_positive() {
+        return None;
+    }
+    // The absolute error of `ln(base)` is multiplied by the exponent, so extra guard digits
+    // are needed for each integer digit of the exponent
+    let integer_digits = (exponent.abs() / pow10(scale)).to_string().len() as u32;
+    let working_scale = working_scale(bits) + integer_digits;
+    let one = pow10(working_scale);
+    let ln = ln_fixed(&rescale(base, scale, working_scale), &one);
+    let product = ln * rescale(exponent, scale, working_scale) / &one;
+    let pow = exp_fixed(&product, &one, bits)?;
+    Some(rescale(&pow, working_scale, scale))
+}
+
+#[cfg(test)]
+mod tests {
+    //! Tests of a fixed sample against reference values computed with 300 significant digits,
+    //! rounded to ten digits beyond the scale of the target type. See `rug_tests` for the property
+    //! tests over random samples, which require the `full_math_benches` feature.
+
+    use super::*;
+    use crate::math::{Decimal, PreciseDecimal};
+    use sbor::rust::format;
+    use sbor::rust::str::FromStr;
+
+    /// The number of digits of the reference values beyond the scale of the target type.
+    const REFERENCE_EXTRA_DIGITS: u32 = 10;
+
+    const DECIMAL_LOGARITHMS: &[(&str, &str, &str, &str)] = &[
+        (
+            "0.000000000000000001",
+            "-41.4465316738928223123238461843",
+            "-59.7947057079725222616657497308",
+            "-18.0000000000000000000000000000",
+        ),
+        (
+            "0.5",
+            "-0.6931471805599453094172321215",
+            "-1.0000000000000000000000000000",
+            "-0.3010299956639811952137388947",
+        ),
+        (
+            "1",
+            "0.0000000000000000000000000000",
+            "0.0000000000000000000000000000",
+            "0.0000000000000000000000000000",
+        ),
+        (
+            "2",
+            "0.6931471805599453094172321215",
+            "1.0000000000000000000000000000",
+            "0.3010299956639811952137388947",
+        ),
+        (
+            "10",
+            "2.3025850929940456840179914547",
+            "3.3219280948873623478703194295",
+            "1.0000000000000000000000000000",
+        ),
+        (
+            "1234567.891011121314",
+            "14.0262315809989349366155384375",
+            "20.2355747442673282970021482827",
+            "6.0915149775249612204454846346",
+        ),
+        (
+            "3000000000000000000000000000000000000000",
+            "90.8994309154358913680969119696",
+            "131.1401582013282877483961966940",
+            "39.4771212547196624372950279033",
+        ),
+    ];
+    const DECIMAL_EXPONENTIALS: &[(&str, &str)] = &[
+        ("-40", "0.0000000000000000042483542553"),
+        ("-1.5", "0.2231301601484298289332804708"),
+        ("0", "1.0000000000000000000000000000"),
+        ("0.000001", "1.0000010000005000001666667083"),
+        ("1", "2.7182818284590452353602874714"),
+        ("2.5", "12.1824939607034734380701759512"),
+        (
+            "89",
+            "448961281917434524628424557964531627776.5980586371851526049317217653",
+        ),
+    ];
+    const DECIMAL_POWERS: &[(&str, &str, &str)] = &[
+        ("2", "0.5", "1.4142135623730950488016887242"),
+        ("10", "-3.3", "0.0005011872336272722850015542"),
+        ("0.5", "7.25", "0.0065695032441696448674306678"),
+        ("123.456", "3.5", "20907047.4882693035760938185527357955"),
+        ("1.0001", "10000", "2.7181459268252248640376646749"),
+        (
+            "7",
+            "40.5",
+            "16844984689218649088717294450582086.0502035204925029447023319697",
+        ),
+    ];
+
+    const PRECISE_DECIMAL_LOGARITHMS: &[(&str, &str, &str, &str)] = &[
+        (
+            "0.000000000000000001",
+            "-41.4465316738928223123238461843185557368198267953",
+            "-59.7947057079725222616657497308090231655669650744",
+            "-18.0000000000000000000000000000000000000000000000",
+        ),
+        (
+            "0.5",
+            "-0.6931471805599453094172321214581765680755001344",
+            "-1.0000000000000000000000000000000000000000000000",
+            "-0.3010299956639811952137388947244930267681898815",
+        ),
+        (
+            "1",
+            "0.0000000000000000000000000000000000000000000000",
+            "0.0000000000000000000000000000000000000000000000",
+            "0.0000000000000000000000000000000000000000000000",
+        ),
+        (
+            "2",
+            "0.6931471805599453094172321214581765680755001344",
+            "1.0000000000000000000000000000000000000000000000",
+            "0.3010299956639811952137388947244930267681898815",
+        ),
+        (
+            "10",
+            "2.3025850929940456840179914546843642076011014886",
+            "3.3219280948873623478703194294893901758648313930",
+            "1.0000000000000000000000000000000000000000000000",
+        ),
+        (
+            "1234567.891011121314",
+            "14.0262315809989349366155384374610160546686868712",
+            "20.2355747442673282970021482827291430300659990835",
+            "6.0915149775249612204454846345945782324330660887",
+        ),
+        (
+            "3000000000000000000000000000000000000000",
+            "90.8994309154358913680969119696127298010904486143",
+            "131.1401582013282877483961966940340333674882387357",
+            "39.4771212547196624372950279032551153092001288642",
+        ),
+    ];
+    const PRECISE_DECIMAL_EXPONENTIALS: &[(&str, &str)] = &[
+        ("-40", "0.0000000000000000042483542552915889953292347829"),
+        ("-1.5", "0.2231301601484298289332804707640125213421716294"),
+        ("0", "1.0000000000000000000000000000000000000000000000"),
+        ("0.000001", "1.0000010000005000001666667083333416666680555558"),
+        ("1", "2.7182818284590452353602874713526624977572470937"),
+        ("2.5", "12.1824939607034734380701759511679661831827677901"),
+        ("89", "448961281917434524628424557964531627776.5980586371851526049317217652687150894561688890"),
+    ];
+    const PRECISE_DECIMAL_POWERS: &[(&str, &str, &str)] = &[
+        (
+            "2",
+            "0.5",
+            "1.4142135623730950488016887242096980785696718754",
+        ),
+        (
+            "10",
+            "-3.3",
+            "0.0005011872336272722850015541868849457680604720",
+        ),
+        (
+            "0.5",
+            "7.25",
+            "0.0065695032441696448674306677830719913675002677",
+        ),
+        (
+            "123.456",
+            "3.5",
+            "20907047.4882693035760938185527357954810729851025688590",
+        ),
+        (
+            "1.0001",
+            "10000",
+            "2.7181459268252248640376646749131465361138226492",
+        ),
+        (
+            "7",
+            "40.5",
+            "16844984689218649088717294450582086.0502035204925029447023319696985252502783642270",
+        ),
+    ];
+
+    /// Parses a decimal number into a fixed-point number at the given scale.
+    fn fixed(value: &str, scale: u32) -> BigInt {
+        let (sign, value) = match value.strip_prefix('-') {
+            Some(value) => (-1, value),
+            None => (1, value),
+        };
+        let (integer, fraction) = value.split_once('.').unwrap_or((value, ""));
+        assert!(fraction.len() <= scale as usize);
+        let digits = format!("{}{:0<width$}", integer, fraction, width = scale as usize);
+        BigInt::from_str(&digits).unwrap() * sign
+    }
+
+    fn assert_within_one_unit(
+        actual: Option<BigInt>,
+        expected: &str,
+        scale: u32,
+        description: &str,
+    ) {
+        let actual =
+            actual.unwrap_or_else(|| panic!("{}: got None, expected {}", description, expected));
+        let expected_fixed = fixed(expected, scale + REFERENCE_EXTRA_DIGITS);
+        let error = (&actual * pow10(REFERENCE_EXTRA_DIGITS) - expected_fixed).abs();
+        assert!(
+            error <= pow10(REFERENCE_EXTRA_DIGITS),
+            "{}: got {}, expected {}",
+            description,
+            actual,
+            expected
+        );
+    }
+
+    fn check_against_reference(
+        scale: u32,
+        bits: u32,
+        logarithms: &[(&str, &str, &str, &str)],
+        exponentials: &[(&str, &str)],
+        powers: &[(&str, &str, &str)],
+    ) {
+        for (x, ln, log2, log10) in logarithms {
+            let x_fixed = fixed(x, scale);
+            let description = format!("ln({})", x);
+            assert_within_one_unit(checked_ln(&x_fixed, scale, bits), ln, scale, &description);
+            let description = format!("log2({})", x);
+            assert_within_one_unit(
+                checked_log(&x_fixed, 2, scale, bits),
+                log2,
+                scale,
+                &description,
+            );
+            let description = format!("log10({})", x);
+            assert_within_one_unit(
+                checked_log(&x_fixed, 10, scale, bits),
+                log10,
+                scale,
+                &description,
+            );
+        }
+
+        for (y, exp) in exponentials {
+            let description = format!("exp({})", y);
+            assert_within_one_unit(
+                checked_exp(&fixed(y, scale), scale, bits),
+                exp,
+                scale,
+                &description,
+            );
+        }
+
+        for (x, y, pow) in powers {
+            let description = format!("{}^{}", x, y);
+            assert_within_one_unit(
+                checked_pow(&fixed(x, scale), &fixed(y, scale), scale, bits),
+                pow,
+                scale,
+                &description,
+            );
+        }
+
+        assert!(checked_exp(&fixed("200", scale), scale, bits).is_none());
+    }
+
+    #[test]
+    fn decimal_functions_are_within_one_unit_of_reference_sample() {
+        check_against_reference(
+            Decimal::SCALE,
+            Decimal::BITS as u32,
+            DECIMAL_LOGARITHMS,
+            DECIMAL_EXPONENTIALS,
+            DECIMAL_POWERS,
+        );
+    }
+
+    #[test]
+    fn precise_decimal_functions_are_within_one_unit_of_reference_sample() {
+        check_against_reference(
+            PreciseDecimal::SCALE,
+            PreciseDecimal::BITS as u32,
+            PRECISE_DECIMAL_LOGARITHMS,
+            PRECISE_DECIMAL_EXPONENTIALS,
+            PRECISE_DECIMAL_POWERS,
+        );
+    }
+}
+
+#[cfg(all(test, feature = "full_math_benches"))]
+mod rug_tests {
+    //! Property tests against the arbitrary precision floating point functions of `rug`.
+
+    use super::*;
+    use crate::math::{Decimal, PreciseDecimal};
+    use rug::ops::Pow as RugPow;
+    use rug::{Float, Integer};
+    use sbor::rust::format;
+    use sbor::rust::str::FromStr;
+
+    const REFERENCE_PRECISION: u32 = 1024;
+    const SAMPLES: usize = 2000;
+
+    /// A deterministic xorshift generator, so that failures can be reproduced.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        /// Returns a random fixed-point number with up to `max_digits` significant digits.
+        fn next_value(&mut self, max_digits: u32) -> BigInt {
+            let digits = (self.next() % max_digits as u64) as u32 + 1;
+            let mut value = BigInt::zero();
+            for _ in 0..digits {
+                value = value * 10u8 + self.next() % 10;
+            }
+            value
+        }
+    }
+
+    fn to_float(value: &BigInt, scale: u32) -> Float {
+        let value = Integer::from_str(&value.to_string()).unwrap();
+        let one = Integer::from(10u8).pow(scale);
+        Float::with_val(REFERENCE_PRECISION, value) / one
+    }
+
+    /// Returns the reference result `value * 10^scale`, as an unrounded float.
+    fn to_scaled(value: Float, scale: u32) -> Float {
+        value * Integer::from(10u8).pow(scale)
+    }
+
+    fn assert_within_one_unit(actual: &BigInt, expected: &Float, description: &str) {
+        let actual = Float::with_val(
+            REFERENCE_PRECISION,
+            Integer::from_str(&actual.to_string()).unwrap(),
+        );
+        let error = Float::with_val(REFERENCE_PRECISION, &actual - expected).abs();
+        assert!(
+            error <= 1,
+            "{}: got {}, expected {}",
+            description,
+            actual.to_integer().unwrap(),
+            expected
+        );
+    }
+
+    fn check_against_reference(scale: u32, bits: u32, max_digits: u32) {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        let max = Float::with_val(REFERENCE_PRECISION, 2).pow(bits - 1);
+        for _ in 0..SAMPLES {
+            let x = rng.next_value(max_digits);
+            if x.is_zero() {
+                continue;
+            }
+            let x_float = to_float(&x, scale);
+
+            let ln = checked_ln(&x, scale, bits).unwrap();
+            let expected = to_scaled(x_float.clone().ln(), scale);
+            assert_within_one_unit(&ln, &expected, &format!("ln({})", x_float));
+
+            let log2 = checked_log(&x, 2, scale, bits).unwrap();
+            let expected = to_scaled(x_float.clone().log2(), scale);
+            assert_within_one_unit(&log2, &expected, &format!("log2({})", x_float));
+
+            let log10 = checked_log(&x, 10, scale, bits).unwrap();
+            let expected = to_scaled(x_float.clone().log10(), scale);
+            assert_within_one_unit(&log10, &expected, &format!("log10({})", x_float));
+
+            // Exponents in `[-100, 100)`
+            let y = BigInt::from(rng.next() % 200) * BigInt::from(10u8).pow(scale)
+                + rng.next_value(scale)
+                - BigInt::from(100u8) * BigInt::from(10u8).pow(scale);
+            let y_float = to_float(&y, scale);
+            let expected = to_scaled(y_float.clone().exp(), scale);
+            match checked_exp(&y, scale, bits) {
+                Some(exp) if expected < max => {
+                    assert_within_one_unit(&exp, &expected, &format!("exp({})", y_float))
+                }
+                Some(_) => {}
+                None => assert!(expected >= max, "exp({}) overflowed", y_float),
+            }
+
+            // Exponents in `[-10, 10)`
+            let y = &y / 10u8;
+            let y_float = to_float(&y, scale);
+            let expected = to_scaled(x_float.clone().pow(&y_float), scale);
+            match checked_pow(&x, &y, scale, bits) {
+                Some(pow) if expected < max => {
+                    assert_within_one_unit(&pow, &expected, &format!("{}^{}", x_float, y_float))
+                }
+                Some(_) => {}
+                None => assert!(expected >= max, "{}^{} overflowed", x_float, y_float),
+            }
+        }
+    }
+
+    #[test]
+    fn decimal_functions_are_within_one_unit_of_reference() {
+        check_against_reference(Decimal::SCALE, Decimal::BITS as u32, 57);
+    }
+
+    #[test]
+    fn precise_decimal_functions_are_within_one_unit_of_reference() {
+        check_against_reference(PreciseDecimal::SCALE, PreciseDecimal::BITS as u32, 76);
+    }
+}
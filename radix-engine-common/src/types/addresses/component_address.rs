@@ -45,6 +45,11 @@ impl ComponentAddress {
                 node_id[0] = EntityType::GlobalVirtualEd25519Account as u8;
                 Self(NodeId(node_id))
             }
+            PublicKey::Secp256r1(public_key) => {
+                let mut node_id: [u8; NodeId::LENGTH] = hash(public_key.to_vec()).lower_bytes();
+                node_id[0] = EntityType::GlobalVirtualSecp256r1Account as u8;
+                Self(NodeId(node_id))
+            }
         }
     }
 
@@ -62,6 +67,11 @@ impl ComponentAddress {
                 node_id[0] = EntityType::GlobalVirtualEd25519Identity as u8;
                 Self(NodeId(node_id))
             }
+            PublicKey::Secp256r1(public_key) => {
+                let mut node_id: [u8; NodeId::LENGTH] = hash(public_key.to_vec()).lower_bytes();
+                node_id[0] = EntityType::GlobalVirtualSecp256r1Identity as u8;
+                Self(NodeId(node_id))
+            }
         }
     }
 
@@ -69,7 +69,7 @@ impl GlobalAddress {
 impl<'a> Arbitrary<'a> for GlobalAddress {
     fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
         use core::cmp::min;
-        let global_entities: [u8; 13] = [
+        let global_entities: [u8; 15] = [
             EntityType::GlobalPackage as u8,
             EntityType::GlobalFungibleResourceManager as u8,
             EntityType::GlobalNonFungibleResourceManager as u8,
@@ -83,6 +83,8 @@ impl<'a> Arbitrary<'a> for GlobalAddress {
             EntityType::GlobalVirtualEd25519Account as u8,
             EntityType::GlobalVirtualSecp256k1Identity as u8,
             EntityType::GlobalVirtualEd25519Identity as u8,
+            EntityType::GlobalVirtualSecp256r1Account as u8,
+            EntityType::GlobalVirtualSecp256r1Identity as u8,
         ];
 
         let mut node_id = [0u8; NodeId::LENGTH];
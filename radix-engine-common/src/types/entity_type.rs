@@ -56,6 +56,9 @@ pub enum EntityType {
     /// A global native pool entity (197 in decimal). Gives Bech32 prefix: `c` followed by one of `c`, `e`, `6` or `m`.
     GlobalMultiResourcePool = 0b11000110, //--------- 11000 => c, 110xx => ce6m (101 = pool)
 
+    /// A global native constant product pool entity (199 in decimal). Gives Bech32 prefix: `c` followed by one of `u`, `a`, `7` or `l`.
+    GlobalConstantProductPool = 0b11000111, //------- 11000 => c, 111xx => ua7l (111 = constant product pool)
+
     //=========================================================================
     // Secp256k1 Virtual Global Components (start with char 6 for Secp256k1)
     //=========================================================================
@@ -74,6 +77,15 @@ pub enum EntityType {
     /// A global virtual Ed25519 identity component entity (82 in decimal). Gives Bech32 prefix: `2` followed by one of `g`, `f`, `2` or `t`.
     GlobalVirtualEd25519Identity = 0b01010010, //---- 01010 => 2, 010xx => gf2t (010 = identity)
 
+    //=========================================================================
+    // Secp256r1 Virtual Global Components (start with char r for Secp256r1)
+    //=========================================================================
+    /// A global virtual Secp256r1 account component entity (25 in decimal). Gives Bech32 prefix: `r` followed by one of `y`, `9`, `x` or `8`.
+    GlobalVirtualSecp256r1Account = 0b00011001, //--- 00011 => r, 001xx => y9x8 (001 = account)
+
+    /// A global virtual Secp256r1 identity component entity (26 in decimal). Gives Bech32 prefix: `r` followed by one of `g`, `f`, `2` or `t`.
+    GlobalVirtualSecp256r1Identity = 0b00011010, //-- 00011 => r, 010xx => gf2t (010 = identity)
+
     //=========================================================================
     // Fungible-related (start with letter t for token)
     //=========================================================================
@@ -123,9 +135,12 @@ impl EntityType {
             | EntityType::GlobalVirtualEd25519Account
             | EntityType::GlobalVirtualSecp256k1Identity
             | EntityType::GlobalVirtualEd25519Identity
+            | EntityType::GlobalVirtualSecp256r1Account
+            | EntityType::GlobalVirtualSecp256r1Identity
             | EntityType::GlobalOneResourcePool
             | EntityType::GlobalTwoResourcePool
             | EntityType::GlobalMultiResourcePool
+            | EntityType::GlobalConstantProductPool
             | EntityType::GlobalTransactionTracker => true,
             EntityType::InternalFungibleVault
             | EntityType::InternalNonFungibleVault
@@ -150,9 +165,12 @@ impl EntityType {
             | EntityType::GlobalVirtualEd25519Account
             | EntityType::GlobalVirtualSecp256k1Identity
             | EntityType::GlobalVirtualEd25519Identity
+            | EntityType::GlobalVirtualSecp256r1Account
+            | EntityType::GlobalVirtualSecp256r1Identity
             | EntityType::GlobalOneResourcePool
             | EntityType::GlobalTwoResourcePool
             | EntityType::GlobalMultiResourcePool
+            | EntityType::GlobalConstantProductPool
             | EntityType::GlobalTransactionTracker => true,
             EntityType::GlobalPackage
             | EntityType::GlobalFungibleResourceManager
@@ -197,7 +215,9 @@ impl EntityType {
             EntityType::GlobalVirtualSecp256k1Account
             | EntityType::GlobalVirtualEd25519Account
             | EntityType::GlobalVirtualSecp256k1Identity
-            | EntityType::GlobalVirtualEd25519Identity => true,
+            | EntityType::GlobalVirtualEd25519Identity
+            | EntityType::GlobalVirtualSecp256r1Account
+            | EntityType::GlobalVirtualSecp256r1Identity => true,
             _ => false,
         }
     }
@@ -54,6 +54,27 @@ pub trait ClientActorIndexApi<E> {
         Ok(rtn)
     }
 
+    /// Retrieves the value of an entry of an index
+    fn actor_index_get(
+        &mut self,
+        object_handle: ActorStateHandle,
+        collection_index: CollectionIndex,
+        key: Vec<u8>,
+    ) -> Result<Option<Vec<u8>>, E>;
+
+    /// Retrieves the value of an entry of an index
+    fn actor_index_get_typed<V: ScryptoDecode>(
+        &mut self,
+        object_handle: ActorStateHandle,
+        collection_index: CollectionIndex,
+        key: Vec<u8>,
+    ) -> Result<Option<V>, E> {
+        let rtn = self
+            .actor_index_get(object_handle, collection_index, key)?
+            .map(|e| scrypto_decode(&e).unwrap());
+        Ok(rtn)
+    }
+
     /// Scans arbitrary elements of count from an index
     fn actor_index_scan_keys(
         &mut self,
@@ -81,6 +102,16 @@ pub trait ClientActorIndexApi<E> {
         Ok(entries)
     }
 
+    /// Scans elements of count from an index, continuing strictly after the given key (in the
+    /// order of the underlying storage), and returns their keys and values
+    fn actor_index_scan_after(
+        &mut self,
+        object_handle: ActorStateHandle,
+        collection_index: CollectionIndex,
+        after: Option<Vec<u8>>,
+        limit: u32,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, E>;
+
     /// Removes and returns arbitrary elements of count from an index
     fn actor_index_drain(
         &mut self,
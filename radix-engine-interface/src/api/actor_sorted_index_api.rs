@@ -84,4 +84,13 @@ pub trait ClientActorSortedIndexApi<E> {
 
         Ok(entries)
     }
+
+    /// Scans elements of count from a sorted index, continuing strictly after the given key
+    fn actor_sorted_index_scan_after(
+        &mut self,
+        object_handle: ActorStateHandle,
+        collection_index: CollectionIndex,
+        after: Option<SortedKey>,
+        count: u32,
+    ) -> Result<Vec<(SortedKey, Vec<u8>)>, E>;
 }
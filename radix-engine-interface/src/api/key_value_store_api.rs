@@ -154,4 +154,25 @@ pub trait ClientKeyValueStoreApi<E> {
         node_id: &NodeId,
         key: &Vec<u8>,
     ) -> Result<Vec<u8>, E>;
+
+    /// Scans up to `limit` entries of a key value store, starting after the key given by
+    /// `after`, or from the first entry if `after` is `None`. Entries are scanned in an
+    /// unspecified but stable order.
+    ///
+    /// Returns the keys and values of up to `limit` non-empty entries, along with the key to
+    /// continue the scan from, which is `None` once no entries are left. A `limit` of zero
+    /// scans nothing and returns the given `after` as the cursor.
+    fn key_value_store_scan(
+        &mut self,
+        node_id: &NodeId,
+        after: Option<Vec<u8>>,
+        limit: u32,
+    ) -> Result<(Vec<(Vec<u8>, Vec<u8>)>, Option<Vec<u8>>), E>;
+
+    /// Returns the number of non-empty entries of a key value store, counting at most `limit`
+    /// of them.
+    ///
+    /// Entries are only read until `limit` non-empty ones have been found, which bounds the cost
+    /// of this call.
+    fn key_value_store_count(&mut self, node_id: &NodeId, limit: u32) -> Result<u32, E>;
 }
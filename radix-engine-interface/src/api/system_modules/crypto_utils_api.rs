@@ -27,4 +27,26 @@ pub trait ClientCryptoUtilsApi<E> {
     ) -> Result<Bls12381G2Signature, E>;
 
     fn keccak256_hash(&mut self, data: &[u8]) -> Result<Hash, E>;
+
+    fn blake2b_256_hash(&mut self, data: &[u8]) -> Result<Hash, E>;
+
+    fn ed25519_verify(
+        &mut self,
+        message: &[u8],
+        public_key: &Ed25519PublicKey,
+        signature: &Ed25519Signature,
+    ) -> Result<u32, E>;
+
+    fn secp256k1_ecdsa_verify(
+        &mut self,
+        hash: &Hash,
+        public_key: &Secp256k1PublicKey,
+        signature: &Secp256k1Signature,
+    ) -> Result<u32, E>;
+
+    fn secp256k1_ecdsa_verify_and_key_recover(
+        &mut self,
+        hash: &Hash,
+        signature: &Secp256k1Signature,
+    ) -> Result<Secp256k1PublicKey, E>;
 }
@@ -265,3 +265,47 @@ pub struct AccessControllerMintRecoveryBadgesInput {
 }
 
 pub type AccessControllerMintRecoveryBadgesOutput = Bucket;
+
+//===========================================
+// Access Controller Contribute Recovery Fee
+//===========================================
+
+pub const ACCESS_CONTROLLER_CONTRIBUTE_RECOVERY_FEE_IDENT: &str = "contribute_recovery_fee";
+
+#[derive(Debug, Eq, PartialEq, ScryptoSbor)]
+pub struct AccessControllerContributeRecoveryFeeInput {
+    pub bucket: Bucket,
+}
+
+#[derive(Debug, Eq, PartialEq, ManifestSbor)]
+pub struct AccessControllerContributeRecoveryFeeManifestInput {
+    pub bucket: ManifestBucket,
+}
+
+pub type AccessControllerContributeRecoveryFeeOutput = ();
+
+//=====================================
+// Access Controller Lock Recovery Fee
+//=====================================
+
+pub const ACCESS_CONTROLLER_LOCK_RECOVERY_FEE_IDENT: &str = "lock_recovery_fee";
+
+#[derive(Debug, Clone, Eq, PartialEq, ScryptoSbor, ManifestSbor)]
+pub struct AccessControllerLockRecoveryFeeInput {
+    pub amount: Decimal,
+}
+
+pub type AccessControllerLockRecoveryFeeOutput = ();
+
+//=========================================
+// Access Controller Withdraw Recovery Fee
+//=========================================
+
+pub const ACCESS_CONTROLLER_WITHDRAW_RECOVERY_FEE_IDENT: &str = "withdraw_recovery_fee";
+
+#[derive(Debug, Clone, Eq, PartialEq, ScryptoSbor, ManifestSbor)]
+pub struct AccessControllerWithdrawRecoveryFeeInput {
+    pub amount: Decimal,
+}
+
+pub type AccessControllerWithdrawRecoveryFeeOutput = Bucket;
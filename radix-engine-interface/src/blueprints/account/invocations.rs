@@ -30,6 +30,37 @@ pub enum DefaultDepositRule {
     AllowExisting,
 }
 
+/// The party which is allowed to spend a withdrawal allowance granted by an account.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, ScryptoSbor, ManifestSbor)]
+pub enum AllowanceGrantee {
+    /// A badge which must be present in the auth zone when withdrawing.
+    Badge(ResourceOrNonFungible),
+
+    /// A global component which must be the caller of the withdrawal.
+    Component(ComponentAddress),
+}
+
+/// A withdrawal allowance of some resource granted by an account.
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor, ManifestSbor)]
+pub struct Allowance {
+    /// The amount which can be withdrawn in each period, or `None` if it is not capped.
+    pub amount: Option<Decimal>,
+
+    /// The amount which can still be withdrawn in the current period, or `None` if it is not
+    /// capped.
+    pub remaining_amount: Option<Decimal>,
+
+    /// The number of epochs after which the remaining amount is reset to the full amount, or
+    /// `None` if it is never reset.
+    pub reset_period: Option<u64>,
+
+    /// The epoch at which the current period started.
+    pub period_start_epoch: Epoch,
+
+    /// The epoch from which the allowance can no longer be spent, or `None` if it never expires.
+    pub expiry_epoch: Option<Epoch>,
+}
+
 pub const ACCOUNT_BLUEPRINT: &str = "Account";
 
 define_type_info_marker!(Some(ACCOUNT_PACKAGE), Account);
@@ -401,3 +432,63 @@ pub struct AccountRemoveAuthorizedDepositorInput {
 }
 
 pub type AccountRemoveAuthorizedDepositorOutput = ();
+
+//=======================
+// Account Set Allowance
+//=======================
+
+pub const ACCOUNT_SET_ALLOWANCE_IDENT: &str = "set_allowance";
+
+#[derive(Debug, Eq, PartialEq, ScryptoSbor, ManifestSbor)]
+pub struct AccountSetAllowanceInput {
+    pub grantee: AllowanceGrantee,
+    pub resource_address: ResourceAddress,
+    pub amount: Option<Decimal>,
+    pub reset_period: Option<u64>,
+    pub expiry_epoch: Option<Epoch>,
+}
+
+pub type AccountSetAllowanceOutput = ();
+
+//==========================
+// Account Remove Allowance
+//==========================
+
+pub const ACCOUNT_REMOVE_ALLOWANCE_IDENT: &str = "remove_allowance";
+
+#[derive(Debug, Eq, PartialEq, ScryptoSbor, ManifestSbor)]
+pub struct AccountRemoveAllowanceInput {
+    pub grantee: AllowanceGrantee,
+    pub resource_address: ResourceAddress,
+}
+
+pub type AccountRemoveAllowanceOutput = ();
+
+//=======================
+// Account Get Allowance
+//=======================
+
+pub const ACCOUNT_GET_ALLOWANCE_IDENT: &str = "get_allowance";
+
+#[derive(Debug, Eq, PartialEq, ScryptoSbor, ManifestSbor)]
+pub struct AccountGetAllowanceInput {
+    pub grantee: AllowanceGrantee,
+    pub resource_address: ResourceAddress,
+}
+
+pub type AccountGetAllowanceOutput = Option<Allowance>;
+
+//==================================
+// Account Withdraw With Allowance
+//==================================
+
+pub const ACCOUNT_WITHDRAW_WITH_ALLOWANCE_IDENT: &str = "withdraw_with_allowance";
+
+#[derive(Debug, Eq, PartialEq, ScryptoSbor, ManifestSbor)]
+pub struct AccountWithdrawWithAllowanceInput {
+    pub grantee: AllowanceGrantee,
+    pub resource_address: ResourceAddress,
+    pub amount: Decimal,
+}
+
+pub type AccountWithdrawWithAllowanceOutput = Bucket;
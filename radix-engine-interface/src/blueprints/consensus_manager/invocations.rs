@@ -540,3 +540,31 @@ pub const VALIDATOR_FINISH_UNLOCK_OWNER_STAKE_UNITS_IDENT: &str = "finish_unlock
 pub struct ValidatorFinishUnlockOwnerStakeUnitsInput {}
 
 pub type ValidatorFinishUnlockOwnerStakeUnitsOutput = Bucket;
+
+pub const VALIDATOR_REDELEGATE_IDENT: &str = "redelegate";
+
+#[derive(Debug, Eq, PartialEq, ScryptoSbor)]
+pub struct ValidatorRedelegateInput {
+    pub stake_unit_bucket: Bucket,
+    pub target_validator: ComponentAddress,
+}
+
+#[derive(Debug, Eq, PartialEq, ManifestSbor)]
+pub struct ValidatorRedelegateManifestInput {
+    pub stake_unit_bucket: ManifestBucket,
+    pub target_validator: ComponentAddress,
+}
+
+pub type ValidatorRedelegateOutput = Bucket;
+
+pub const VALIDATOR_RECEIVE_REDELEGATION_IDENT: &str = "receive_redelegation";
+
+#[derive(Debug, Eq, PartialEq, ScryptoSbor)]
+pub struct ValidatorReceiveRedelegationInput {
+    /// A bucket with the XRDs unstaked from the source validator.
+    pub xrd_bucket: Bucket,
+    /// The validator which the XRDs are redelegated from. It must be the caller.
+    pub source_validator: ComponentAddress,
+}
+
+pub type ValidatorReceiveRedelegationOutput = Bucket;
@@ -1,4 +1,6 @@
+use crate::blueprints::package::BlueprintVersion;
 use crate::blueprints::resource::*;
+use crate::math::Decimal;
 use crate::types::*;
 use crate::*;
 use radix_engine_common::data::manifest::model::ManifestAddressReservation;
@@ -87,6 +89,84 @@ pub struct PackageClaimRoyaltiesInput {}
 
 pub type PackageClaimRoyaltiesOutput = Bucket;
 
+pub const PACKAGE_CONFIGURE_BLUEPRINT_UPGRADES_IDENT: &str = "configure_blueprint_upgrades";
+
+#[derive(Debug, Clone, Eq, PartialEq, ScryptoSbor, ManifestSbor)]
+pub struct PackageConfigureBlueprintUpgradesInput {
+    pub blueprint_name: String,
+    pub timelock_in_minutes: u32,
+}
+
+pub type PackageConfigureBlueprintUpgradesOutput = ();
+
+pub const PACKAGE_PROPOSE_BLUEPRINT_UPGRADE_IDENT: &str = "propose_blueprint_upgrade";
+
+#[derive(Debug, Clone, Eq, PartialEq, ScryptoSbor)]
+pub struct PackageProposeBlueprintUpgradeInput {
+    pub blueprint_name: String,
+    pub definition: BlueprintDefinitionInit,
+    pub code: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, ManifestSbor)]
+pub struct PackageProposeBlueprintUpgradeManifestInput {
+    pub blueprint_name: String,
+    pub definition: BlueprintDefinitionInit,
+    pub code: ManifestBlobRef,
+}
+
+pub type PackageProposeBlueprintUpgradeOutput = ();
+
+pub const PACKAGE_EXECUTE_BLUEPRINT_UPGRADE_IDENT: &str = "execute_blueprint_upgrade";
+
+#[derive(Debug, Clone, Eq, PartialEq, ScryptoSbor, ManifestSbor)]
+pub struct PackageExecuteBlueprintUpgradeInput {
+    pub blueprint_name: String,
+}
+
+pub type PackageExecuteBlueprintUpgradeOutput = BlueprintVersion;
+
+pub const PACKAGE_CANCEL_BLUEPRINT_UPGRADE_IDENT: &str = "cancel_blueprint_upgrade";
+
+#[derive(Debug, Clone, Eq, PartialEq, ScryptoSbor, ManifestSbor)]
+pub struct PackageCancelBlueprintUpgradeInput {
+    pub blueprint_name: String,
+}
+
+pub type PackageCancelBlueprintUpgradeOutput = ();
+
+pub const PACKAGE_SET_ROYALTY_SPLIT_IDENT: &str = "PackageRoyalty_set_royalty_split";
+
+/// Maps each recipient to its share of a blueprint's royalties, where a share of `1` is the
+/// whole royalty. Whatever the shares do not cover is kept by the package owner.
+#[derive(Debug, Clone, Eq, PartialEq, ScryptoSbor, ManifestSbor)]
+pub struct PackageSetRoyaltySplitInput {
+    pub blueprint_name: String,
+    pub recipients: IndexMap<GlobalAddress, Decimal>,
+}
+
+pub type PackageSetRoyaltySplitOutput = ();
+
+pub const PACKAGE_LOCK_ROYALTY_SPLIT_IDENT: &str = "PackageRoyalty_lock_royalty_split";
+
+#[derive(Debug, Clone, Eq, PartialEq, ScryptoSbor, ManifestSbor)]
+pub struct PackageLockRoyaltySplitInput {
+    pub blueprint_name: String,
+}
+
+pub type PackageLockRoyaltySplitOutput = ();
+
+pub const PACKAGE_CLAIM_ROYALTY_SHARE_IDENT: &str = "PackageRoyalty_claim_royalty_share";
+
+#[derive(Debug, Clone, Eq, PartialEq, ScryptoSbor, ManifestSbor)]
+pub struct PackageClaimRoyaltyShareInput {
+    pub recipient: GlobalAddress,
+}
+
+/// The claimed royalties are deposited straight into the recipient if it is an account, otherwise
+/// they are returned to the recipient component, which must be the caller.
+pub type PackageClaimRoyaltyShareOutput = Option<Bucket>;
+
 #[derive(Debug, Clone, Eq, PartialEq, Default, ScryptoSbor, ManifestSbor)]
 pub struct PackageDefinition {
     pub blueprints: IndexMap<String, BlueprintDefinitionInit>,
@@ -0,0 +1,160 @@
+use crate::blueprints::component::*;
+use crate::blueprints::macros::*;
+use crate::blueprints::resource::*;
+use radix_engine_common::data::manifest::model::*;
+use radix_engine_common::math::*;
+use radix_engine_common::prelude::*;
+use radix_engine_common::time::Instant;
+use radix_engine_common::*;
+
+pub const CONSTANT_PRODUCT_POOL_BLUEPRINT: &str = "ConstantProductPool";
+
+define_type_info_marker!(Some(POOL_PACKAGE), ConstantProductPool);
+
+/// The state of the time-weighted average price oracle of a constant product pool.
+///
+/// The cumulative price of a resource is the sum, over every second since the pool was created,
+/// of the price of that resource in terms of the other resource of the pool. The time-weighted
+/// average price over a period is the difference between two observations of the cumulative price
+/// divided by the number of seconds between them.
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct ConstantProductPoolOracleState {
+    pub cumulative_prices: IndexMap<ResourceAddress, PreciseDecimal>,
+    pub last_update: Instant,
+}
+
+define_invocation! {
+    blueprint_name: ConstantProductPool,
+    function_name: instantiate,
+    input: struct {
+        owner_role: OwnerRole,
+        fee_manager_rule: AccessRule,
+        resource_addresses: (ResourceAddress, ResourceAddress),
+        fee_rate: Decimal,
+        address_reservation: Option<GlobalAddressReservation>
+    },
+    output: type Global<ConstantProductPoolObjectTypeInfo>,
+    manifest_input: struct {
+        owner_role: OwnerRole,
+        fee_manager_rule: AccessRule,
+        resource_addresses: (ResourceAddress, ResourceAddress),
+        fee_rate: Decimal,
+        address_reservation: Option<ManifestAddressReservation>
+    }
+}
+
+define_invocation! {
+    blueprint_name: ConstantProductPool,
+    function_name: contribute,
+    input: struct {
+        buckets: (Bucket, Bucket)
+    },
+    output: type (Bucket, Option<Bucket>),
+    manifest_input: struct {
+        buckets: (ManifestBucket, ManifestBucket)
+    }
+}
+
+define_invocation! {
+    blueprint_name: ConstantProductPool,
+    function_name: redeem,
+    input: struct {
+        bucket: Bucket
+    },
+    output: type (Bucket, Bucket),
+    manifest_input: struct {
+        bucket: ManifestBucket
+    }
+}
+
+define_invocation! {
+    blueprint_name: ConstantProductPool,
+    function_name: swap,
+    input: struct {
+        input_bucket: Bucket,
+        minimum_output_amount: Decimal
+    },
+    output: type Bucket,
+    manifest_input: struct {
+        input_bucket: ManifestBucket,
+        minimum_output_amount: Decimal
+    }
+}
+
+define_invocation! {
+    blueprint_name: ConstantProductPool,
+    function_name: swap_for_exact_output,
+    input: struct {
+        input_bucket: Bucket,
+        output_amount: Decimal
+    },
+    output: type (Bucket, Bucket),
+    manifest_input: struct {
+        input_bucket: ManifestBucket,
+        output_amount: Decimal
+    }
+}
+
+define_invocation! {
+    blueprint_name: ConstantProductPool,
+    function_name: get_swap_output_amount,
+    input: struct {
+        input_resource_address: ResourceAddress,
+        input_amount: Decimal
+    },
+    output: type Decimal,
+    manifest_input: struct {
+        input_resource_address: ResourceAddress,
+        input_amount: Decimal
+    }
+}
+
+define_invocation! {
+    blueprint_name: ConstantProductPool,
+    function_name: get_swap_input_amount,
+    input: struct {
+        output_resource_address: ResourceAddress,
+        output_amount: Decimal
+    },
+    output: type Decimal,
+    manifest_input: struct {
+        output_resource_address: ResourceAddress,
+        output_amount: Decimal
+    }
+}
+
+define_invocation! {
+    blueprint_name: ConstantProductPool,
+    function_name: set_fee_rate,
+    input: struct {
+        fee_rate: Decimal
+    },
+    output: type (),
+    manifest_input: struct {
+        fee_rate: Decimal
+    }
+}
+
+define_invocation! {
+    blueprint_name: ConstantProductPool,
+    function_name: get_fee_rate,
+    input: struct {},
+    output: type Decimal,
+    manifest_input: struct {}
+}
+
+define_invocation! {
+    blueprint_name: ConstantProductPool,
+    function_name: get_oracle_state,
+    input: struct {},
+    output: type ConstantProductPoolOracleState,
+    manifest_input: struct {}
+}
+
+define_invocation! {
+    blueprint_name: ConstantProductPool,
+    function_name: get_vault_amounts,
+    input: struct {},
+    output: type IndexMap<ResourceAddress, Decimal>,
+    manifest_input: struct {}
+}
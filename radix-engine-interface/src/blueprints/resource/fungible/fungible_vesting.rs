@@ -0,0 +1,77 @@
+use crate::blueprints::component::*;
+use crate::blueprints::macros::*;
+use crate::blueprints::resource::*;
+use radix_engine_common::data::manifest::model::*;
+use radix_engine_common::math::*;
+use radix_engine_common::prelude::*;
+use radix_engine_common::time::Instant;
+use radix_engine_common::*;
+
+pub const FUNGIBLE_VESTING_BLUEPRINT: &str = "FungibleVesting";
+
+pub const FUNGIBLE_VESTING_BENEFICIARY_ROLE: &str = "beneficiary";
+pub const FUNGIBLE_VESTING_REVOKER_ROLE: &str = "revoker";
+
+define_type_info_marker!(Some(RESOURCE_PACKAGE), FungibleVesting);
+
+/// The schedule by which the tokens held by a fungible vesting component are released.
+///
+/// Tokens vest linearly from `start` until `end`. If a `cliff` is given, nothing can be withdrawn
+/// before it, and the amount which vested between `start` and the cliff is released all at once
+/// at the cliff. A schedule with `cliff` equal to `end` releases everything at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ScryptoSbor, ManifestSbor)]
+pub struct VestingSchedule {
+    pub start: Instant,
+    pub cliff: Option<Instant>,
+    pub end: Instant,
+}
+
+define_invocation! {
+    blueprint_name: FungibleVesting,
+    function_name: create,
+    input: struct {
+        owner_role: OwnerRole,
+        bucket: Bucket,
+        schedule: VestingSchedule,
+        beneficiary_rule: AccessRule,
+        revoker_rule: Option<AccessRule>,
+        address_reservation: Option<GlobalAddressReservation>
+    },
+    output: type Global<FungibleVestingObjectTypeInfo>,
+    manifest_input: struct {
+        owner_role: OwnerRole,
+        bucket: ManifestBucket,
+        schedule: VestingSchedule,
+        beneficiary_rule: AccessRule,
+        revoker_rule: Option<AccessRule>,
+        address_reservation: Option<ManifestAddressReservation>
+    }
+}
+
+define_invocation! {
+    blueprint_name: FungibleVesting,
+    function_name: withdraw,
+    input: struct {
+        amount: Decimal
+    },
+    output: type Bucket,
+    manifest_input: struct {
+        amount: Decimal
+    }
+}
+
+define_invocation! {
+    blueprint_name: FungibleVesting,
+    function_name: get_unlocked_amount,
+    input: struct {},
+    output: type Decimal,
+    manifest_input: struct {}
+}
+
+define_invocation! {
+    blueprint_name: FungibleVesting,
+    function_name: revoke,
+    input: struct {},
+    output: type Bucket,
+    manifest_input: struct {}
+}
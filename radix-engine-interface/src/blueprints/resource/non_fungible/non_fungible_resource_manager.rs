@@ -223,6 +223,31 @@ pub struct NonFungibleResourceManagerUpdateDataGenericInput<T> {
 
 pub type NonFungibleResourceManagerUpdateDataOutput = ();
 
+pub const NON_FUNGIBLE_RESOURCE_MANAGER_BATCH_UPDATE_DATA_IDENT: &str =
+    "batch_update_non_fungible_data";
+
+/// The updates are applied in order, so a later update of the same field of the same non-fungible
+/// overwrites an earlier one.
+#[derive(Debug, Clone, Eq, PartialEq, ScryptoSbor)]
+pub struct NonFungibleResourceManagerBatchUpdateDataInput {
+    pub updates: Vec<(NonFungibleLocalId, String, ScryptoValue)>,
+}
+
+/// For manifest
+#[cfg_attr(feature = "radix_engine_fuzzing", derive(Arbitrary))]
+#[derive(Debug, Clone, Eq, PartialEq, ManifestSbor)]
+pub struct NonFungibleResourceManagerBatchUpdateDataManifestInput {
+    pub updates: Vec<(NonFungibleLocalId, String, ManifestValue)>,
+}
+
+/// For typed value, to skip any codec
+#[derive(Debug, Clone, Eq, PartialEq, ScryptoSbor)]
+pub struct NonFungibleResourceManagerBatchUpdateDataGenericInput<T> {
+    pub updates: Vec<(NonFungibleLocalId, String, T)>,
+}
+
+pub type NonFungibleResourceManagerBatchUpdateDataOutput = ();
+
 pub const NON_FUNGIBLE_RESOURCE_MANAGER_EXISTS_IDENT: &str = "non_fungible_exists";
 
 #[derive(Debug, Clone, Eq, PartialEq, ScryptoSbor)]
@@ -193,6 +193,10 @@ impl FromPublicKey for NonFungibleGlobalId {
                 ED25519_SIGNATURE_VIRTUAL_BADGE,
                 NonFungibleLocalId::bytes(public_key_hash.get_hash_bytes().to_vec()).unwrap(),
             ),
+            PublicKeyHash::Secp256r1(public_key_hash) => NonFungibleGlobalId::new(
+                SECP256R1_SIGNATURE_VIRTUAL_BADGE,
+                NonFungibleLocalId::bytes(public_key_hash.get_hash_bytes().to_vec()).unwrap(),
+            ),
         }
     }
 }
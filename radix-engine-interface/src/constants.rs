@@ -22,6 +22,7 @@ lazy_static! {
             XRD.into(),
             SECP256K1_SIGNATURE_VIRTUAL_BADGE.into(),
             ED25519_SIGNATURE_VIRTUAL_BADGE.into(),
+            SECP256R1_SIGNATURE_VIRTUAL_BADGE.into(),
             SYSTEM_TRANSACTION_BADGE.into(),
             PACKAGE_OF_DIRECT_CALLER_VIRTUAL_BADGE.into(),
             GLOBAL_CALLER_VIRTUAL_BADGE.into(),
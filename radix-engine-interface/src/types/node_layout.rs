@@ -16,6 +16,7 @@ pub const BOOT_LOADER_PARTITION: PartitionNumber = PartitionNumber(32u8);
 #[repr(u8)]
 #[derive(Debug, Copy, Clone, Sbor, PartialEq, Eq, Hash, PartialOrd, Ord, FromRepr)]
 pub enum BootLoaderField {
+    System = 1u8,
     Vm = 2u8,
 }
 
@@ -37,6 +38,10 @@ pub const ROYALTY_FIELDS_PARTITION: PartitionNumber = PartitionNumber(3u8);
 pub const ROYALTY_FIELDS_PARTITION_OFFSET: PartitionOffset = PartitionOffset(0u8);
 pub const ROYALTY_CONFIG_PARTITION: PartitionNumber = PartitionNumber(4u8);
 pub const ROYALTY_CONFIG_PARTITION_OFFSET: PartitionOffset = PartitionOffset(1u8);
+/// The royalty split collections were added after the role assignment module partitions had been
+/// laid out, so they are mapped to physical partitions past the module partitions instead.
+pub const ROYALTY_SPLIT_PARTITION: PartitionNumber = PartitionNumber(8u8);
+pub const ROYALTY_SPLIT_VAULT_PARTITION: PartitionNumber = PartitionNumber(9u8);
 
 #[repr(u8)]
 #[derive(Debug, Copy, Clone, Sbor, PartialEq, Eq, Hash, PartialOrd, Ord, FromRepr)]
@@ -176,6 +181,9 @@ blueprint_partition_offset!(
         CodeVmTypeKeyValue,
         CodeOriginalCodeKeyValue,
         CodeInstrumentedCodeKeyValue,
+        BlueprintUpgradeConfigKeyValue,
+        BlueprintRoyaltySplitKeyValue,
+        RoyaltySplitVaultKeyValue,
     }
 );
 
@@ -251,12 +259,14 @@ blueprint_partition_offset!(
 blueprint_partition_offset!(
     pub enum ValidatorPartitionOffset {
         Field,
+        RedelegationKeyValue,
     }
 );
 
 blueprint_partition_offset!(
     pub enum AccessControllerPartitionOffset {
         Field,
+        XrdFeeVaultKeyValue,
     }
 );
 
@@ -266,6 +276,7 @@ blueprint_partition_offset!(
         ResourceVaultKeyValue,
         ResourcePreferenceKeyValue,
         AuthorizedDepositorKeyValue,
+        AllowanceKeyValue,
     }
 );
 
@@ -287,6 +298,12 @@ blueprint_partition_offset!(
     }
 );
 
+blueprint_partition_offset!(
+    pub enum ConstantProductPoolPartitionOffset {
+        Field,
+    }
+);
+
 #[repr(u8)]
 #[derive(Debug, Copy, Clone, Sbor, PartialEq, Eq, Hash, PartialOrd, Ord, FromRepr)]
 pub enum TransactionTrackerField {
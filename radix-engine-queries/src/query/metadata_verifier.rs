@@ -0,0 +1,294 @@
+use crate::typed_substate_layout::*;
+use radix_engine::system::system_db_reader::{SystemDatabaseReader, SystemReaderError};
+use radix_engine::types::*;
+use radix_engine_interface::api::ModuleId;
+use radix_engine_store_interface::interface::SubstateDatabase;
+use sbor::rust::prelude::*;
+
+/// The `account_type` of an account which acts as a dApp definition.
+pub const DAPP_DEFINITION_ACCOUNT_TYPE: &str = "dapp definition";
+
+/// The type which the metadata standard defines for a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StandardMetadataType {
+    String,
+    StringArray,
+    Url,
+    GlobalAddress,
+    GlobalAddressArray,
+    OriginArray,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MetadataIssue {
+    /// The value does not have the type which the standard defines for the key.
+    UnexpectedType { expected: StandardMetadataType },
+    /// The URL or origin is not served over https.
+    InsecureUrl(String),
+    /// The `account_type` is set on an entity which is not an account.
+    NotAnAccount,
+    /// The `account_type` is not one defined by the standard.
+    UnknownAccountType(String),
+    /// The key is only meaningful on a dApp definition account.
+    NotADappDefinition,
+    /// The linked entity is not a dApp definition account.
+    LinkedEntityIsNotADappDefinition(GlobalAddress),
+    /// The linked dApp definition does not list this entity in its `claimed_entities`.
+    NotClaimedByDappDefinition(GlobalAddress),
+    /// The claimed entity does not link back to this dApp definition.
+    ClaimedEntityDoesNotLinkBack(GlobalAddress),
+    /// The linked dApp definition does not list this dApp definition in its `dapp_definitions`.
+    LinkedDappDefinitionDoesNotLinkBack(GlobalAddress),
+}
+
+/// A deviation of a single metadata entry from the metadata standard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetadataFinding {
+    pub key: String,
+    pub issue: MetadataIssue,
+}
+
+/// Verifies the metadata of entities against the standard metadata conventions, including the
+/// two-way links between dApp definitions and the entities they claim, which are otherwise only
+/// verified off-ledger by wallets.
+pub struct MetadataVerifier<'s, S: SubstateDatabase> {
+    reader: SystemDatabaseReader<'s, S>,
+}
+
+impl<'s, S: SubstateDatabase> MetadataVerifier<'s, S> {
+    pub fn new(substate_db: &'s S) -> Self {
+        MetadataVerifier {
+            reader: SystemDatabaseReader::new(substate_db),
+        }
+    }
+
+    /// Returns the findings for the metadata of the given entity, which are empty if it follows
+    /// the standard.
+    pub fn verify(
+        &self,
+        address: GlobalAddress,
+    ) -> Result<Vec<MetadataFinding>, SystemReaderError> {
+        let mut metadata = self.read_metadata(address.as_node_id())?;
+        // The database yields entries in hash order, so sort them for stable findings
+        metadata.sort_keys();
+        let is_dapp_definition = Self::is_dapp_definition(&address, &metadata);
+
+        let mut findings = Vec::new();
+        let mut report = |key: &str, issue: MetadataIssue| {
+            findings.push(MetadataFinding {
+                key: key.to_string(),
+                issue,
+            })
+        };
+
+        for (key, value) in &metadata {
+            match (key.as_str(), value) {
+                ("name" | "description" | "symbol", MetadataValue::String(_)) => {}
+                ("name" | "description" | "symbol", _) => report(
+                    key,
+                    MetadataIssue::UnexpectedType {
+                        expected: StandardMetadataType::String,
+                    },
+                ),
+                ("tags", MetadataValue::StringArray(_)) => {}
+                ("tags", _) => report(
+                    key,
+                    MetadataIssue::UnexpectedType {
+                        expected: StandardMetadataType::StringArray,
+                    },
+                ),
+                ("icon_url" | "info_url", MetadataValue::Url(url)) => {
+                    if !is_https(url.as_str()) {
+                        report(key, MetadataIssue::InsecureUrl(url.as_str().to_string()));
+                    }
+                }
+                ("icon_url" | "info_url", _) => report(
+                    key,
+                    MetadataIssue::UnexpectedType {
+                        expected: StandardMetadataType::Url,
+                    },
+                ),
+                ("account_type", MetadataValue::String(account_type)) => {
+                    if !is_account(&address) {
+                        report(key, MetadataIssue::NotAnAccount);
+                    } else if account_type != DAPP_DEFINITION_ACCOUNT_TYPE {
+                        report(key, MetadataIssue::UnknownAccountType(account_type.clone()));
+                    }
+                }
+                ("account_type", _) => report(
+                    key,
+                    MetadataIssue::UnexpectedType {
+                        expected: StandardMetadataType::String,
+                    },
+                ),
+                ("claimed_websites" | "claimed_entities", _) if !is_dapp_definition => {
+                    report(key, MetadataIssue::NotADappDefinition)
+                }
+                ("claimed_websites", MetadataValue::OriginArray(origins)) => {
+                    for origin in origins {
+                        if !is_https(origin.as_str()) {
+                            report(key, MetadataIssue::InsecureUrl(origin.as_str().to_string()));
+                        }
+                    }
+                }
+                ("claimed_websites", _) => report(
+                    key,
+                    MetadataIssue::UnexpectedType {
+                        expected: StandardMetadataType::OriginArray,
+                    },
+                ),
+                ("claimed_entities", MetadataValue::GlobalAddressArray(entities)) => {
+                    for entity in entities {
+                        if !self.links_to_dapp_definition(entity, &address) {
+                            report(key, MetadataIssue::ClaimedEntityDoesNotLinkBack(*entity));
+                        }
+                    }
+                }
+                ("claimed_entities", _) => report(
+                    key,
+                    MetadataIssue::UnexpectedType {
+                        expected: StandardMetadataType::GlobalAddressArray,
+                    },
+                ),
+                ("dapp_definition", MetadataValue::GlobalAddress(dapp_definition)) => {
+                    if let Some(issue) = self.check_claimed_by(&address, dapp_definition) {
+                        report(key, issue);
+                    }
+                }
+                ("dapp_definition", _) => report(
+                    key,
+                    MetadataIssue::UnexpectedType {
+                        expected: StandardMetadataType::GlobalAddress,
+                    },
+                ),
+                ("dapp_definitions", MetadataValue::GlobalAddressArray(dapp_definitions)) => {
+                    for dapp_definition in dapp_definitions {
+                        // On a dApp definition, this key links to other dApp definitions
+                        let issue = if is_dapp_definition {
+                            self.check_linked_dapp_definition(&address, dapp_definition)
+                        } else {
+                            self.check_claimed_by(&address, dapp_definition)
+                        };
+                        if let Some(issue) = issue {
+                            report(key, issue);
+                        }
+                    }
+                }
+                ("dapp_definitions", _) => report(
+                    key,
+                    MetadataIssue::UnexpectedType {
+                        expected: StandardMetadataType::GlobalAddressArray,
+                    },
+                ),
+                _ => {}
+            }
+        }
+
+        Ok(findings)
+    }
+
+    fn check_claimed_by(
+        &self,
+        entity: &GlobalAddress,
+        dapp_definition: &GlobalAddress,
+    ) -> Option<MetadataIssue> {
+        let metadata = self.read_metadata_or_empty(dapp_definition);
+        if !Self::is_dapp_definition(dapp_definition, &metadata) {
+            return Some(MetadataIssue::LinkedEntityIsNotADappDefinition(
+                *dapp_definition,
+            ));
+        }
+        if !address_array_contains(metadata.get("claimed_entities"), entity) {
+            return Some(MetadataIssue::NotClaimedByDappDefinition(*dapp_definition));
+        }
+        None
+    }
+
+    fn check_linked_dapp_definition(
+        &self,
+        dapp_definition: &GlobalAddress,
+        linked: &GlobalAddress,
+    ) -> Option<MetadataIssue> {
+        let metadata = self.read_metadata_or_empty(linked);
+        if !Self::is_dapp_definition(linked, &metadata) {
+            return Some(MetadataIssue::LinkedEntityIsNotADappDefinition(*linked));
+        }
+        if !address_array_contains(metadata.get("dapp_definitions"), dapp_definition) {
+            return Some(MetadataIssue::LinkedDappDefinitionDoesNotLinkBack(*linked));
+        }
+        None
+    }
+
+    fn links_to_dapp_definition(
+        &self,
+        entity: &GlobalAddress,
+        dapp_definition: &GlobalAddress,
+    ) -> bool {
+        let metadata = self.read_metadata_or_empty(entity);
+        // Components and packages link to a single dApp definition, resources may link to several
+        matches!(
+            metadata.get("dapp_definition"),
+            Some(MetadataValue::GlobalAddress(address)) if address == dapp_definition
+        ) || address_array_contains(metadata.get("dapp_definitions"), dapp_definition)
+    }
+
+    fn is_dapp_definition(
+        address: &GlobalAddress,
+        metadata: &IndexMap<String, MetadataValue>,
+    ) -> bool {
+        is_account(address)
+            && matches!(
+                metadata.get("account_type"),
+                Some(MetadataValue::String(account_type))
+                    if account_type == DAPP_DEFINITION_ACCOUNT_TYPE
+            )
+    }
+
+    /// Virtual accounts and identities have no state until they are first used, in which case
+    /// they have no metadata either.
+    fn read_metadata_or_empty(&self, address: &GlobalAddress) -> IndexMap<String, MetadataValue> {
+        self.read_metadata(address.as_node_id()).unwrap_or_default()
+    }
+
+    fn read_metadata(
+        &self,
+        node_id: &NodeId,
+    ) -> Result<IndexMap<String, MetadataValue>, SystemReaderError> {
+        Ok(self
+            .reader
+            .collection_iter(
+                node_id,
+                ModuleId::Metadata,
+                MetadataCollection::EntryKeyValue.collection_index(),
+            )?
+            .map(|(key, value)| {
+                let key = scrypto_decode::<String>(&key.into_map()).unwrap();
+                let value = scrypto_decode::<MetadataEntryEntryPayload>(&value).unwrap();
+                (key, value.into_latest())
+            })
+            .collect())
+    }
+}
+
+fn address_array_contains(value: Option<&MetadataValue>, address: &GlobalAddress) -> bool {
+    matches!(
+        value,
+        Some(MetadataValue::GlobalAddressArray(addresses)) if addresses.contains(address)
+    )
+}
+
+fn is_account(address: &GlobalAddress) -> bool {
+    matches!(
+        address.as_node_id().entity_type(),
+        Some(
+            EntityType::GlobalAccount
+                | EntityType::GlobalVirtualSecp256k1Account
+                | EntityType::GlobalVirtualEd25519Account
+                | EntityType::GlobalVirtualSecp256r1Account
+        )
+    )
+}
+
+fn is_https(url: &str) -> bool {
+    url.starts_with("https://")
+}
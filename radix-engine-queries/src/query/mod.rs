@@ -1,7 +1,11 @@
 mod accounter;
+mod metadata_verifier;
+mod transaction_tracker_reader;
 mod traverse;
 mod vault_finder;
 
 pub use accounter::*;
+pub use metadata_verifier::*;
+pub use transaction_tracker_reader::*;
 pub use traverse::*;
 pub use vault_finder::*;
@@ -0,0 +1,155 @@
+use crate::typed_substate_layout::*;
+use radix_engine::system::system_db_reader::{
+    ObjectCollectionKey, SystemDatabaseReader, SystemReaderError,
+};
+use radix_engine::types::*;
+use radix_engine_interface::api::ModuleId;
+use radix_engine_store_interface::interface::SubstateDatabase;
+use sbor::rust::prelude::*;
+use transaction::prelude::IntentHash;
+
+/// A partition of the transaction tracker, which holds the intents expiring within its epoch range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EpochPartition {
+    pub partition_number: PartitionNumber,
+    pub start_epoch_inclusive: Epoch,
+    pub end_epoch_exclusive: Epoch,
+}
+
+impl EpochPartition {
+    pub fn overlaps(&self, start_epoch_inclusive: Epoch, end_epoch_exclusive: Epoch) -> bool {
+        self.start_epoch_inclusive < end_epoch_exclusive
+            && start_epoch_inclusive < self.end_epoch_exclusive
+    }
+}
+
+/// An intent whose status is recorded by the transaction tracker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackedIntent {
+    pub intent_hash: IntentHash,
+    pub status: TransactionStatusV1,
+    pub partition: EpochPartition,
+}
+
+/// Reads the intent statuses which the transaction tracker records for replay protection.
+///
+/// Intents are filed under the partition covering their expiry epoch, and a partition is dropped
+/// in full once its epoch range has passed, so an intent is only known until it expires.
+pub struct TransactionTrackerReader<'s, S: SubstateDatabase> {
+    reader: SystemDatabaseReader<'s, S>,
+}
+
+impl<'s, S: SubstateDatabase> TransactionTrackerReader<'s, S> {
+    pub fn new(substate_db: &'s S) -> Self {
+        TransactionTrackerReader {
+            reader: SystemDatabaseReader::new(substate_db),
+        }
+    }
+
+    /// Returns the partitions of the transaction tracker, ordered by epoch.
+    pub fn epoch_partitions(&self) -> Result<Vec<EpochPartition>, SystemReaderError> {
+        Ok(epoch_partitions(&self.read_tracker()?))
+    }
+
+    /// Returns the tracked status of the given intent, or `None` if the intent is unknown, i.e.
+    /// it has not been committed or it has already expired.
+    pub fn intent_status(
+        &self,
+        intent_hash: &IntentHash,
+    ) -> Result<Option<TrackedIntent>, SystemReaderError> {
+        let tracker = self.read_tracker()?;
+        for partition in epoch_partitions(&tracker) {
+            let status = self
+                .reader
+                .read_object_collection_entry::<_, TransactionStatusSubstateContents>(
+                    TRANSACTION_TRACKER.as_node_id(),
+                    ModuleId::Main,
+                    ObjectCollectionKey::KeyValue(
+                        collection_index(&tracker, &partition),
+                        intent_hash,
+                    ),
+                )?;
+            if let Some(status) = status {
+                return Ok(Some(TrackedIntent {
+                    intent_hash: *intent_hash,
+                    status: status.into_v1(),
+                    partition,
+                }));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Returns the intents of all partitions overlapping the given epoch range, ordered by epoch.
+    ///
+    /// The tracker does not record the exact expiry epoch of an intent, so intents are selected
+    /// at the granularity of a partition.
+    pub fn tracked_intents(
+        &self,
+        start_epoch_inclusive: Epoch,
+        end_epoch_exclusive: Epoch,
+    ) -> Result<Vec<TrackedIntent>, SystemReaderError> {
+        let tracker = self.read_tracker()?;
+        let mut intents = Vec::new();
+        for partition in epoch_partitions(&tracker) {
+            if !partition.overlaps(start_epoch_inclusive, end_epoch_exclusive) {
+                continue;
+            }
+            let entries = self.reader.collection_iter(
+                TRANSACTION_TRACKER.as_node_id(),
+                ModuleId::Main,
+                collection_index(&tracker, &partition),
+            )?;
+            for (key, value) in entries {
+                let intent_hash = scrypto_decode::<IntentHash>(&key.into_map()).unwrap();
+                let status = scrypto_decode::<TransactionStatusSubstateContents>(&value).unwrap();
+                intents.push(TrackedIntent {
+                    intent_hash,
+                    status: status.into_v1(),
+                    partition,
+                });
+            }
+        }
+
+        Ok(intents)
+    }
+
+    fn read_tracker(&self) -> Result<TransactionTrackerSubstateV1, SystemReaderError> {
+        Ok(self
+            .reader
+            .read_typed_object_field::<TransactionTrackerSubstate>(
+                TRANSACTION_TRACKER.as_node_id(),
+                ModuleId::Main,
+                TransactionTrackerField::TransactionTracker.field_index(),
+            )?
+            .into_v1())
+    }
+}
+
+fn epoch_partitions(tracker: &TransactionTrackerSubstateV1) -> Vec<EpochPartition> {
+    let num_partitions =
+        tracker.partition_range_end_inclusive - tracker.partition_range_start_inclusive + 1;
+
+    (0..num_partitions)
+        .map(|i| {
+            let start_epoch = tracker.start_epoch + i as u64 * tracker.epochs_per_partition;
+            let partition_number = tracker
+                .partition_for_expiry_epoch(Epoch::of(start_epoch))
+                .expect("Epoch should be within the tracker range");
+            EpochPartition {
+                partition_number: PartitionNumber(partition_number),
+                start_epoch_inclusive: Epoch::of(start_epoch),
+                end_epoch_exclusive: Epoch::of(start_epoch + tracker.epochs_per_partition),
+            }
+        })
+        .collect()
+}
+
+/// The tracker declares one collection per partition of its range, in partition order.
+fn collection_index(
+    tracker: &TransactionTrackerSubstateV1,
+    partition: &EpochPartition,
+) -> CollectionIndex {
+    partition.partition_number.0 - tracker.partition_range_start_inclusive
+}
@@ -1,7 +1,7 @@
 use radix_engine::blueprints::resource::*;
 use radix_engine::prelude::*;
 use radix_engine::system::attached_modules::royalty::{
-    ComponentRoyaltyAccumulatorFieldPayload, ComponentRoyaltyField,
+    ComponentRoyaltyAccumulatorFieldPayload, ComponentRoyaltyCollection, ComponentRoyaltyField,
 };
 use radix_engine::system::system_db_reader::SystemDatabaseReader;
 use radix_engine::system::type_info::TypeInfoSubstate;
@@ -211,6 +211,38 @@ impl<'s, 'v, S: SubstateDatabase, V: StateTreeVisitor + 'v> StateTreeTraverser<'
                                             depth + 1,
                                             max_depth,
                                         );
+
+                                        // The split vaults only exist from royalty package v1.1
+                                        if let Ok((iter, partition_number)) = system_db_reader
+                                            .collection_iter_advanced(
+                                                &node_id,
+                                                module_id.into(),
+                                                ComponentRoyaltyCollection::SplitVaultKeyValue
+                                                    .collection_index(),
+                                                None,
+                                            )
+                                        {
+                                            for (substate_key, value) in iter {
+                                                let (_, owned_nodes, _) =
+                                                    IndexedScryptoValue::from_slice(&value)
+                                                        .unwrap()
+                                                        .unpack();
+                                                for child_node_id in owned_nodes {
+                                                    Self::traverse_recursive(
+                                                        system_db_reader,
+                                                        visitor,
+                                                        Some(&(
+                                                            node_id,
+                                                            partition_number,
+                                                            substate_key.clone(),
+                                                        )),
+                                                        child_node_id,
+                                                        depth + 1,
+                                                        max_depth,
+                                                    );
+                                                }
+                                            }
+                                        }
                                     }
                                     _ => {}
                                 }
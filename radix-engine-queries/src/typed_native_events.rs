@@ -39,43 +39,43 @@ fn resolve_typed_event_key_from_event_type_identifier(
         /* Functions on well-known packages */
         Emitter::Function(blueprint_id) => match blueprint_id.package_address {
             PACKAGE_PACKAGE => TypedPackagePackageEventKey::new(
-                &PACKAGE_PACKAGE_DEFINITION,
+                &PACKAGE_PACKAGE_DEFINITION_V1_2,
                 &blueprint_id.blueprint_name,
                 &event_name,
             )
             .map(TypedNativeEventKey::from),
             RESOURCE_PACKAGE => TypedResourcePackageEventKey::new(
-                &RESOURCE_PACKAGE_DEFINITION,
+                &RESOURCE_PACKAGE_DEFINITION_V1_2,
                 &blueprint_id.blueprint_name,
                 &event_name,
             )
             .map(TypedNativeEventKey::from),
             ACCOUNT_PACKAGE => TypedAccountPackageEventKey::new(
-                &ACCOUNT_PACKAGE_DEFINITION,
+                &ACCOUNT_PACKAGE_DEFINITION_V1_2,
                 &blueprint_id.blueprint_name,
                 &event_name,
             )
             .map(TypedNativeEventKey::from),
             IDENTITY_PACKAGE => TypedIdentityPackageEventKey::new(
-                &IDENTITY_PACKAGE_DEFINITION,
+                &IDENTITY_PACKAGE_DEFINITION_V1_1,
                 &blueprint_id.blueprint_name,
                 &event_name,
             )
             .map(TypedNativeEventKey::from),
             CONSENSUS_MANAGER_PACKAGE => TypedConsensusManagerPackageEventKey::new(
-                &CONSENSUS_MANAGER_PACKAGE_DEFINITION,
+                &CONSENSUS_MANAGER_PACKAGE_DEFINITION_V1_1,
                 &blueprint_id.blueprint_name,
                 &event_name,
             )
             .map(TypedNativeEventKey::from),
             ACCESS_CONTROLLER_PACKAGE => TypedAccessControllerPackageEventKey::new(
-                &ACCESS_CONTROLLER_PACKAGE_DEFINITION,
+                &ACCESS_CONTROLLER_PACKAGE_DEFINITION_V1_1,
                 &blueprint_id.blueprint_name,
                 &event_name,
             )
             .map(TypedNativeEventKey::from),
             POOL_PACKAGE => TypedPoolPackageEventKey::new(
-                &POOL_PACKAGE_DEFINITION_V1_0,
+                &POOL_PACKAGE_DEFINITION_V1_2,
                 &blueprint_id.blueprint_name,
                 &event_name,
             )
@@ -93,7 +93,7 @@ fn resolve_typed_event_key_from_event_type_identifier(
             )
             .map(TypedNativeEventKey::from),
             ROYALTY_MODULE_PACKAGE => TypedRoyaltyPackageEventKey::new(
-                &ROYALTY_PACKAGE_DEFINITION,
+                &ROYALTY_PACKAGE_DEFINITION_V1_1,
                 &blueprint_id.blueprint_name,
                 &event_name,
             )
@@ -133,12 +133,14 @@ fn resolve_typed_event_key_from_event_type_identifier(
             }
             EntityType::GlobalAccount
             | EntityType::GlobalVirtualSecp256k1Account
-            | EntityType::GlobalVirtualEd25519Account => {
+            | EntityType::GlobalVirtualEd25519Account
+            | EntityType::GlobalVirtualSecp256r1Account => {
                 TypedAccountBlueprintEventKey::new(&event_name).map(TypedNativeEventKey::from)
             }
             EntityType::GlobalIdentity
             | EntityType::GlobalVirtualSecp256k1Identity
-            | EntityType::GlobalVirtualEd25519Identity => {
+            | EntityType::GlobalVirtualEd25519Identity
+            | EntityType::GlobalVirtualSecp256r1Identity => {
                 TypedIdentityBlueprintEventKey::new(&event_name).map(TypedNativeEventKey::from)
             }
             EntityType::GlobalAccessController => {
@@ -157,6 +159,10 @@ fn resolve_typed_event_key_from_event_type_identifier(
                 TypedMultiResourcePoolBlueprintEventKey::new(&event_name)
                     .map(TypedNativeEventKey::from)
             }
+            EntityType::GlobalConstantProductPool => {
+                TypedConstantProductPoolBlueprintEventKey::new(&event_name)
+                    .map(TypedNativeEventKey::from)
+            }
             EntityType::GlobalFungibleResourceManager => {
                 TypedFungibleResourceManagerBlueprintEventKey::new(&event_name)
                     .map(TypedNativeEventKey::from)
@@ -194,6 +200,9 @@ define_structure! {
             LockPrimaryRoleEvent,
             UnlockPrimaryRoleEvent,
             StopTimedRecoveryEvent,
+            ContributeRecoveryFeeEvent,
+            LockRecoveryFeeEvent,
+            WithdrawRecoveryFeeEvent,
         ],
     },
     Account => {
@@ -205,14 +214,25 @@ define_structure! {
             AccountRemoveResourcePreferenceEvent,
             AccountSetDefaultDepositRuleEvent,
             AccountAddAuthorizedDepositorEvent,
-            AccountRemoveAuthorizedDepositorEvent
+            AccountRemoveAuthorizedDepositorEvent,
+            AccountSetAllowanceEvent,
+            AccountRemoveAllowanceEvent,
+            AccountAllowanceWithdrawEvent
         ]
     },
     Identity => {
         Identity => []
     },
     Package => {
-        Package => []
+        Package => [
+            ConfigureBlueprintUpgradesEvent,
+            ProposeBlueprintUpgradeEvent,
+            ExecuteBlueprintUpgradeEvent,
+            CancelBlueprintUpgradeEvent,
+            SetRoyaltySplitEvent,
+            LockRoyaltySplitEvent,
+            ClaimRoyaltyShareEvent
+        ]
     },
     ConsensusManager => {
         ConsensusManager => [
@@ -229,6 +249,8 @@ define_structure! {
             ProtocolUpdateReadinessSignalEvent,
             ValidatorEmissionAppliedEvent,
             ValidatorRewardAppliedEvent,
+            RedelegateEvent,
+            ReceiveRedelegationEvent,
         ],
     },
     Pool => {
@@ -250,6 +272,10 @@ define_structure! {
             MultiResourcePoolWithdrawEvent,
             MultiResourcePoolDepositEvent,
         ],
+        ConstantProductPool => [
+            ConstantProductPoolSwapEvent,
+            ConstantProductPoolFeeRateChangeEvent,
+        ],
     },
     Resource => {
         FungibleVault => [
@@ -273,6 +299,11 @@ define_structure! {
             VaultCreationEvent,
             MintNonFungibleResourceEvent,
             BurnNonFungibleResourceEvent,
+            BatchUpdateNonFungibleDataEvent,
+        ],
+        FungibleVesting => [
+            FungibleVestingWithdrawEvent,
+            FungibleVestingRevokeEvent
         ]
     },
     TransactionProcessor => {
@@ -297,7 +328,11 @@ define_structure! {
         ]
     },
     Royalty => {
-        ComponentRoyalty => []
+        ComponentRoyalty => [
+            SetComponentRoyaltySplitEvent,
+            LockComponentRoyaltySplitEvent,
+            ClaimComponentRoyaltyShareEvent
+        ]
     },
 }
 
@@ -317,6 +352,9 @@ type MultiResourcePoolRedemptionEvent = pool_events::multi_resource_pool::Redemp
 type MultiResourcePoolWithdrawEvent = pool_events::multi_resource_pool::WithdrawEvent;
 type MultiResourcePoolDepositEvent = pool_events::multi_resource_pool::DepositEvent;
 
+type ConstantProductPoolSwapEvent = pool_events::constant_product_pool::SwapEvent;
+type ConstantProductPoolFeeRateChangeEvent = pool_events::constant_product_pool::FeeRateChangeEvent;
+
 type FungibleVaultLockFeeEvent = fungible_vault::LockFeeEvent;
 type FungibleVaultPayFeeEvent = fungible_vault::PayFeeEvent;
 type FungibleVaultWithdrawEvent = fungible_vault::WithdrawEvent;
@@ -327,6 +365,9 @@ type NonFungibleVaultWithdrawEvent = non_fungible_vault::WithdrawEvent;
 type NonFungibleVaultDepositEvent = non_fungible_vault::DepositEvent;
 type NonFungibleVaultRecallEvent = non_fungible_vault::RecallEvent;
 
+type FungibleVestingWithdrawEvent = fungible_vesting::WithdrawEvent;
+type FungibleVestingRevokeEvent = fungible_vesting::RevokeEvent;
+
 type AccountWithdrawEvent = account::WithdrawEvent;
 type AccountDepositEvent = account::DepositEvent;
 type AccountRejectedDepositEvent = account::RejectedDepositEvent;
@@ -335,6 +376,9 @@ type AccountRemoveResourcePreferenceEvent = account::RemoveResourcePreferenceEve
 type AccountSetDefaultDepositRuleEvent = account::SetDefaultDepositRuleEvent;
 type AccountAddAuthorizedDepositorEvent = account::AddAuthorizedDepositorEvent;
 type AccountRemoveAuthorizedDepositorEvent = account::RemoveAuthorizedDepositorEvent;
+type AccountSetAllowanceEvent = account::SetAllowanceEvent;
+type AccountRemoveAllowanceEvent = account::RemoveAllowanceEvent;
+type AccountAllowanceWithdrawEvent = account::AllowanceWithdrawEvent;
 
 /// This enum uses some special syntax to define the structure of events. This makes the code for
 /// model definitions very compact, allows for very easy addition of more packages, blueprints or
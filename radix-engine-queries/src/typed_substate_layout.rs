@@ -6,6 +6,10 @@ pub use radix_engine::blueprints::account::{AccountBlueprint, AccountError, Acco
 use radix_engine::blueprints::account::{AccountTypedSubstateKey, AccountTypedSubstateValue};
 pub use radix_engine::blueprints::consensus_manager::*;
 pub use radix_engine::blueprints::package::*;
+pub use radix_engine::blueprints::pool::v1::substates::constant_product_pool;
+use radix_engine::blueprints::pool::v1::substates::constant_product_pool::{
+    ConstantProductPoolTypedSubstateKey, ConstantProductPoolTypedSubstateValue,
+};
 pub use radix_engine::blueprints::pool::v1::substates::multi_resource_pool;
 use radix_engine::blueprints::pool::v1::substates::multi_resource_pool::{
     MultiResourcePoolTypedSubstateKey, MultiResourcePoolTypedSubstateValue,
@@ -23,6 +27,7 @@ pub use radix_engine::blueprints::transaction_tracker::*;
 pub use radix_engine::system::attached_modules::metadata::*;
 pub use radix_engine::system::attached_modules::role_assignment::*;
 pub use radix_engine::system::attached_modules::royalty::*;
+use radix_engine::system::system_callback::SystemBoot;
 use radix_engine::system::system_substates::FieldSubstate;
 use radix_engine::system::system_substates::KeyValueEntrySubstate;
 pub use radix_engine::system::type_info::*;
@@ -130,6 +135,10 @@ pub enum TypedRoyaltyModuleSubstateKey {
     RoyaltyField(RoyaltyField),
     /// The key is the method ident
     RoyaltyMethodRoyaltyEntryKey(String),
+    /// The key is the method ident
+    RoyaltyMethodSplitEntryKey(String),
+    /// The key is the split recipient
+    RoyaltySplitVaultEntryKey(GlobalAddress),
 }
 
 #[derive(Debug, Clone)]
@@ -153,6 +162,7 @@ pub enum TypedMainModuleSubstateKey {
     OneResourcePool(OneResourcePoolTypedSubstateKey),
     TwoResourcePool(TwoResourcePoolTypedSubstateKey),
     MultiResourcePool(MultiResourcePoolTypedSubstateKey),
+    ConstantProductPool(ConstantProductPoolTypedSubstateKey),
     TransactionTrackerField(TransactionTrackerField),
     TransactionTrackerCollectionEntry(IntentHash),
     // Objects - Generic Scrypto Components
@@ -212,6 +222,26 @@ pub fn to_typed_substate_key(
                 .map_err(|_| error("string RoyaltyConfigEntryFnIdent key"))?,
             ),
         ),
+        ROYALTY_SPLIT_PARTITION => TypedSubstateKey::RoyaltyModule(
+            TypedRoyaltyModuleSubstateKey::RoyaltyMethodSplitEntryKey(
+                scrypto_decode(
+                    substate_key
+                        .for_map()
+                        .ok_or_else(|| error("RoyaltySplitEntryFnIdent key"))?,
+                )
+                .map_err(|_| error("string RoyaltySplitEntryFnIdent key"))?,
+            ),
+        ),
+        ROYALTY_SPLIT_VAULT_PARTITION => TypedSubstateKey::RoyaltyModule(
+            TypedRoyaltyModuleSubstateKey::RoyaltySplitVaultEntryKey(
+                scrypto_decode(
+                    substate_key
+                        .for_map()
+                        .ok_or_else(|| error("RoyaltySplitVault key"))?,
+                )
+                .map_err(|_| error("GlobalAddress RoyaltySplitVault key"))?,
+            ),
+        ),
         ROLE_ASSIGNMENT_FIELDS_PARTITION => TypedSubstateKey::RoleAssignmentModule(
             TypedRoleAssignmentSubstateKey::RoleAssignmentField(
                 RoleAssignmentField::try_from(substate_key)
@@ -303,6 +333,7 @@ fn to_typed_object_substate_key_internal(
         ),
         EntityType::GlobalVirtualSecp256k1Account
         | EntityType::GlobalVirtualEd25519Account
+        | EntityType::GlobalVirtualSecp256r1Account
         | EntityType::GlobalAccount => {
             TypedMainModuleSubstateKey::Account(AccountTypedSubstateKey::for_key_in_partition(
                 &AccountPartitionOffset::try_from(partition_offset)?,
@@ -311,6 +342,7 @@ fn to_typed_object_substate_key_internal(
         }
         EntityType::GlobalVirtualSecp256k1Identity
         | EntityType::GlobalVirtualEd25519Identity
+        | EntityType::GlobalVirtualSecp256r1Identity
         | EntityType::GlobalIdentity => Err(())?, // Identity doesn't have any substates
         EntityType::InternalFungibleVault => TypedMainModuleSubstateKey::FungibleVault(
             FungibleVaultTypedSubstateKey::for_key_at_partition_offset(
@@ -342,6 +374,12 @@ fn to_typed_object_substate_key_internal(
                 substate_key,
             )?,
         ),
+        EntityType::GlobalConstantProductPool => TypedMainModuleSubstateKey::ConstantProductPool(
+            ConstantProductPoolTypedSubstateKey::for_key_in_partition(
+                &ConstantProductPoolPartitionOffset::try_from(partition_offset)?,
+                substate_key,
+            )?,
+        ),
         EntityType::GlobalTransactionTracker => {
             if partition_offset == PartitionOffset(0) {
                 TypedMainModuleSubstateKey::TransactionTrackerField(
@@ -379,6 +417,7 @@ pub enum TypedSubstateValue {
 
 #[derive(Debug)]
 pub enum BootLoaderSubstateValue {
+    System(SystemBoot),
     Vm(VmBoot),
 }
 
@@ -397,6 +436,8 @@ pub enum TypedRoleAssignmentModuleSubstateValue {
 pub enum TypedRoyaltyModuleSubstateValue {
     ComponentRoyalty(FieldSubstate<ComponentRoyaltyAccumulatorFieldPayload>),
     ComponentMethodRoyalty(KeyValueEntrySubstate<ComponentRoyaltyMethodAmountEntryPayload>),
+    ComponentMethodRoyaltySplit(KeyValueEntrySubstate<ComponentRoyaltyMethodSplitEntryPayload>),
+    ComponentRoyaltySplitVault(KeyValueEntrySubstate<ComponentRoyaltySplitVaultEntryPayload>),
 }
 
 #[derive(Debug)]
@@ -420,6 +461,7 @@ pub enum TypedMainModuleSubstateValue {
     OneResourcePool(OneResourcePoolTypedSubstateValue),
     TwoResourcePool(TwoResourcePoolTypedSubstateValue),
     MultiResourcePool(MultiResourcePoolTypedSubstateValue),
+    ConstantProductPool(ConstantProductPoolTypedSubstateValue),
     TransactionTracker(TypedTransactionTrackerFieldValue),
     TransactionTrackerCollectionEntry(KeyValueEntrySubstate<TransactionStatusSubstateContents>),
     // Generic Scrypto Components and KV Stores
@@ -456,6 +498,9 @@ fn to_typed_substate_value_internal(
     let substate_value = match substate_key {
         TypedSubstateKey::BootLoader(boot_loader_key) => {
             TypedSubstateValue::BootLoader(match boot_loader_key {
+                TypedBootLoaderSubstateKey::BootLoaderField(BootLoaderField::System) => {
+                    BootLoaderSubstateValue::System(scrypto_decode(data)?)
+                }
                 TypedBootLoaderSubstateKey::BootLoaderField(BootLoaderField::Vm) => {
                     BootLoaderSubstateValue::Vm(scrypto_decode(data)?)
                 }
@@ -489,6 +534,16 @@ fn to_typed_substate_value_internal(
                 TypedRoyaltyModuleSubstateKey::RoyaltyMethodRoyaltyEntryKey(_) => {
                     TypedRoyaltyModuleSubstateValue::ComponentMethodRoyalty(scrypto_decode(data)?)
                 }
+                TypedRoyaltyModuleSubstateKey::RoyaltyMethodSplitEntryKey(_) => {
+                    TypedRoyaltyModuleSubstateValue::ComponentMethodRoyaltySplit(scrypto_decode(
+                        data,
+                    )?)
+                }
+                TypedRoyaltyModuleSubstateKey::RoyaltySplitVaultEntryKey(_) => {
+                    TypedRoyaltyModuleSubstateValue::ComponentRoyaltySplitVault(scrypto_decode(
+                        data,
+                    )?)
+                }
             })
         }
         TypedSubstateKey::MetadataModule(metadata_module_key) => {
@@ -574,6 +629,11 @@ fn to_typed_object_substate_value(
                 MultiResourcePoolTypedSubstateValue::from_key_and_data(key, data)?,
             )
         }
+        TypedMainModuleSubstateKey::ConstantProductPool(key) => {
+            TypedMainModuleSubstateValue::ConstantProductPool(
+                ConstantProductPoolTypedSubstateValue::from_key_and_data(key, data)?,
+            )
+        }
 
         TypedMainModuleSubstateKey::TransactionTrackerField(offset) => {
             TypedMainModuleSubstateValue::TransactionTracker(match offset {
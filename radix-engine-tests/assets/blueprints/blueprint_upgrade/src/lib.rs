@@ -0,0 +1,22 @@
+use scrypto::prelude::*;
+
+#[blueprint]
+mod counter {
+    struct Counter {
+        count: u32,
+    }
+
+    impl Counter {
+        pub fn instantiate() -> Global<Counter> {
+            Self { count: 0 }
+                .instantiate()
+                .prepare_to_globalize(OwnerRole::None)
+                .globalize()
+        }
+
+        pub fn increment(&mut self) -> u32 {
+            self.count += 1;
+            self.count
+        }
+    }
+}
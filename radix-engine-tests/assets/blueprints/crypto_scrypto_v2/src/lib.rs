@@ -0,0 +1,35 @@
+use scrypto::prelude::*;
+
+#[blueprint]
+mod component_module {
+    struct CryptoScryptoV2 {}
+
+    impl CryptoScryptoV2 {
+        pub fn blake2b_256_hash(data: Vec<u8>) -> Hash {
+            CryptoUtils::blake2b_256_hash(data)
+        }
+
+        pub fn ed25519_verify(
+            message: Vec<u8>,
+            pub_key: Ed25519PublicKey,
+            signature: Ed25519Signature,
+        ) -> bool {
+            CryptoUtils::ed25519_verify(message, pub_key, signature)
+        }
+
+        pub fn secp256k1_ecdsa_verify(
+            hash: Hash,
+            pub_key: Secp256k1PublicKey,
+            signature: Secp256k1Signature,
+        ) -> bool {
+            CryptoUtils::secp256k1_ecdsa_verify(hash, pub_key, signature)
+        }
+
+        pub fn secp256k1_ecdsa_verify_and_key_recover(
+            hash: Hash,
+            signature: Secp256k1Signature,
+        ) -> Secp256k1PublicKey {
+            CryptoUtils::secp256k1_ecdsa_verify_and_key_recover(hash, signature)
+        }
+    }
+}
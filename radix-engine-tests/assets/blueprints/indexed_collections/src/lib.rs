@@ -0,0 +1,94 @@
+use scrypto::prelude::*;
+
+#[blueprint]
+mod leaderboard {
+    struct Leaderboard {
+        players: IterableMap<String, u16>,
+        ranking: SortedMap<String, ()>,
+    }
+
+    impl Leaderboard {
+        pub fn new() -> Global<Leaderboard> {
+            Self {
+                players: IterableMap::new(),
+                ranking: SortedMap::new(),
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::None)
+            .globalize()
+        }
+
+        pub fn new_with_scores(scores: Vec<(String, u16)>) -> Global<Leaderboard> {
+            let leaderboard = Self {
+                players: IterableMap::new(),
+                ranking: SortedMap::new(),
+            }
+            .instantiate();
+
+            for (player, score) in scores {
+                leaderboard.record(player, score);
+            }
+
+            leaderboard
+                .prepare_to_globalize(OwnerRole::None)
+                .globalize()
+        }
+
+        pub fn record(&mut self, player: String, score: u16) {
+            if let Some(previous_score) = self.players.get(&player) {
+                self.ranking.remove(u16::MAX - previous_score, &player);
+            }
+            self.players.insert(player.clone(), score);
+            // Best scores come first, as entries are listed in ascending sort prefix order
+            self.ranking.insert(u16::MAX - score, player, ());
+        }
+
+        pub fn remove(&mut self, player: String) -> Option<u16> {
+            let score = self.players.remove(&player)?;
+            self.ranking.remove(u16::MAX - score, &player);
+            Some(score)
+        }
+
+        pub fn score(&self, player: String) -> Option<u16> {
+            self.players.get(&player)
+        }
+
+        pub fn players(&self, page_size: u32) -> Vec<(String, u16)> {
+            let mut players = Vec::new();
+            loop {
+                let page = self
+                    .players
+                    .scan(players.last().map(|(player, _)| player), page_size);
+                if page.is_empty() {
+                    return players;
+                }
+                players.extend(page);
+            }
+        }
+
+        pub fn top(&self, count: u32) -> Vec<(String, u16)> {
+            self.ranking
+                .scan(None, count)
+                .into_iter()
+                .map(|(sort_prefix, player, _)| (player, u16::MAX - sort_prefix))
+                .collect()
+        }
+
+        pub fn ranking(&self, page_size: u32) -> Vec<(String, u16)> {
+            let mut ranking: Vec<(String, u16)> = Vec::new();
+            loop {
+                let after = ranking
+                    .last()
+                    .map(|(player, score)| (u16::MAX - *score, player));
+                let page = self.ranking.scan(after, page_size);
+                if page.is_empty() {
+                    return ranking;
+                }
+                ranking.extend(
+                    page.into_iter()
+                        .map(|(sort_prefix, player, _)| (player, u16::MAX - sort_prefix)),
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use scrypto::prelude::*;
+
+#[blueprint]
+mod iterable {
+    struct IterableKeyValueStore {
+        map: KeyValueStore<u32, String>,
+    }
+
+    impl IterableKeyValueStore {
+        pub fn new(count: u32) -> Global<IterableKeyValueStore> {
+            let map = KeyValueStore::new();
+            for i in 0..count {
+                map.insert(i, i.to_string());
+            }
+            Self { map }
+                .instantiate()
+                .prepare_to_globalize(OwnerRole::None)
+                .globalize()
+        }
+
+        pub fn get(&self, key: u32) -> Option<String> {
+            self.map.get(&key).map(|value| (*value).clone())
+        }
+
+        pub fn insert(&mut self, key: u32, value: String) {
+            self.map.insert(key, value);
+        }
+
+        pub fn remove(&mut self, key: u32) {
+            self.map.remove(&key);
+        }
+
+        pub fn entries(&self, page_size: u32) -> Vec<(u32, String)> {
+            self.pages(page_size).into_iter().flatten().collect()
+        }
+
+        pub fn pages(&self, page_size: u32) -> Vec<Vec<(u32, String)>> {
+            let mut pages = Vec::new();
+            let mut after: Option<u32> = None;
+            loop {
+                let (page, next) = self.map.scan(after.as_ref(), page_size);
+                pages.push(page);
+                match next {
+                    Some(key) => after = Some(key),
+                    None => return pages,
+                }
+            }
+        }
+
+        pub fn count(&self, limit: u32) -> u32 {
+            self.map.count(limit)
+        }
+
+        pub fn count_local(count: u32, limit: u32) -> u32 {
+            let map = KeyValueStore::<u32, String>::new();
+            for i in 0..count {
+                map.insert(i, i.to_string());
+            }
+            map.remove(&0);
+            map.count(limit)
+        }
+    }
+}
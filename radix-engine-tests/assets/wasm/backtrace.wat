@@ -0,0 +1,17 @@
+(module
+
+  ;; Function that always traps
+  (func $inner
+    unreachable
+  )
+
+  (func $Test_f (param $0 i64) (result i64)
+    call $inner
+
+    (i64.const 0)
+  )
+
+  (memory $0 1)
+  (export "memory" (memory $0))
+  (export "Test_f" (func $Test_f))
+)
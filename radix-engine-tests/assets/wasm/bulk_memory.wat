@@ -0,0 +1,24 @@
+(module
+
+  ;; Function that returns `42u8`, written with bulk memory instructions
+  (func $Test_f (param $0 i64) (result i64)
+    ;; Fill 3 bytes at address 0x10 with 42
+    (memory.fill (i32.const 16) (i32.const 42) (i32.const 3))
+
+    ;; Encode 42u8 in SBOR at address 0x0
+    (i32.const 0)
+    (i32.const 92)  ;; prefix
+    (i32.store8)
+    (i32.const 1)
+    (i32.const 7)  ;; u8 value kind
+    (i32.store8)
+    (memory.copy (i32.const 2) (i32.const 16) (i32.const 1))
+
+    ;; Return slice (ptr = 0, len = 3)
+    (i64.const 3)
+  )
+
+  (memory $0 1)
+  (export "memory" (memory $0))
+  (export "Test_f" (func $Test_f))
+)
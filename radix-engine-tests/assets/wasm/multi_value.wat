@@ -0,0 +1,20 @@
+(module
+
+  ;; Function that returns two values, which requires the multi-value proposal
+  (func $pair (result i32 i32)
+    (i32.const 0)
+    (i32.const 3)
+  )
+
+  ;; Function that returns `()`, encoded by a slice returned from `$pair`
+  (func $Test_f (param $0 i64) (result i64)
+    (call $pair)
+    (drop)
+    (drop)
+    (i64.const 0)
+  )
+
+  (memory $0 1)
+  (export "memory" (memory $0))
+  (export "Test_f" (func $Test_f))
+)
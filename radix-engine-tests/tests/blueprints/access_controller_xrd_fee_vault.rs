@@ -0,0 +1,317 @@
+use radix_engine::blueprints::access_controller::{
+    AccessControllerError, ContributeRecoveryFeeEvent, LockRecoveryFeeEvent,
+    WithdrawRecoveryFeeEvent,
+};
+use radix_engine::errors::{ApplicationError, RuntimeError};
+use radix_engine::transaction::TransactionReceipt;
+use radix_engine::types::*;
+use radix_engine_interface::blueprints::access_controller::*;
+use scrypto_unit::*;
+use transaction::prelude::*;
+
+#[test]
+fn anyone_can_contribute_xrd_towards_recovery_fees() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+
+    // Act
+    let receipt = env.contribute_recovery_fee(XRD, dec!(100));
+
+    // Assert
+    let commit_result = receipt.expect_commit_success();
+    assert_eq!(
+        env.test_runner
+            .extract_events_of_type::<ContributeRecoveryFeeEvent>(commit_result),
+        vec![ContributeRecoveryFeeEvent { amount: dec!(100) }]
+    );
+    assert_eq!(env.recovery_fee_balance(), dec!(100));
+}
+
+#[test]
+fn contributing_a_resource_other_than_xrd_fails() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let resource_address = env
+        .test_runner
+        .create_fungible_resource(dec!(100), 18, env.account);
+
+    // Act
+    let receipt = env.contribute_recovery_fee(resource_address, dec!(100));
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::AccessControllerError(
+                AccessControllerError::RecoveryFeeMustBeXrd { .. }
+            ))
+        )
+    });
+}
+
+#[test]
+fn recovery_role_can_pay_for_initiating_a_recovery_from_the_fee_vault() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    env.contribute_recovery_fee(XRD, dec!(100))
+        .expect_commit_success();
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .create_proof_from_account_of_amount(env.account, env.recovery_badge, dec!(1))
+        .lock_access_controller_recovery_fee(env.access_controller, dec!(10))
+        .call_method(
+            env.access_controller,
+            ACCESS_CONTROLLER_INITIATE_RECOVERY_AS_RECOVERY_IDENT,
+            AccessControllerInitiateRecoveryAsRecoveryInput {
+                rule_set: RuleSet {
+                    primary_role: rule!(require(env.recovery_badge)),
+                    recovery_role: rule!(require(env.recovery_badge)),
+                    confirmation_role: rule!(require(env.confirmation_badge)),
+                },
+                timed_recovery_delay_in_minutes: None,
+            },
+        )
+        .build();
+    let receipt = env.test_runner.execute_manifest(
+        manifest,
+        [NonFungibleGlobalId::from_public_key(&env.public_key)],
+    );
+
+    // Assert
+    let commit_result = receipt.expect_commit_success();
+    assert_eq!(
+        env.test_runner
+            .extract_events_of_type::<LockRecoveryFeeEvent>(commit_result),
+        vec![LockRecoveryFeeEvent { amount: dec!(10) }]
+    );
+    assert_eq!(
+        env.recovery_fee_balance(),
+        dec!(100)
+            .checked_sub(receipt.fee_summary.total_cost())
+            .unwrap()
+    );
+}
+
+#[test]
+fn confirmation_role_can_lock_fees_from_the_fee_vault() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    env.contribute_recovery_fee(XRD, dec!(100))
+        .expect_commit_success();
+
+    // Act
+    let receipt = env.lock_recovery_fee(env.confirmation_badge, dec!(10));
+
+    // Assert
+    receipt.expect_commit_success();
+}
+
+#[test]
+fn primary_role_can_not_lock_fees_from_the_fee_vault() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    env.contribute_recovery_fee(XRD, dec!(100))
+        .expect_commit_success();
+
+    // Act
+    let receipt = env.lock_recovery_fee(env.primary_badge, dec!(10));
+
+    // Assert
+    receipt.expect_auth_failure();
+}
+
+#[test]
+fn locking_fees_before_any_contribution_fails() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .create_proof_from_account_of_amount(env.account, env.recovery_badge, dec!(1))
+        .lock_access_controller_recovery_fee(env.access_controller, dec!(10))
+        .build();
+    let receipt = env.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        [NonFungibleGlobalId::from_public_key(&env.public_key)],
+    );
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::AccessControllerError(
+                AccessControllerError::XrdFeeVaultDoesNotExist
+            ))
+        )
+    });
+}
+
+#[test]
+fn primary_role_can_withdraw_from_the_fee_vault() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    env.contribute_recovery_fee(XRD, dec!(100))
+        .expect_commit_success();
+
+    // Act
+    let receipt = env.withdraw_recovery_fee(env.primary_badge, dec!(40));
+
+    // Assert
+    let commit_result = receipt.expect_commit_success();
+    assert_eq!(
+        env.test_runner
+            .extract_events_of_type::<WithdrawRecoveryFeeEvent>(commit_result),
+        vec![WithdrawRecoveryFeeEvent { amount: dec!(40) }]
+    );
+    assert_eq!(env.recovery_fee_balance(), dec!(60));
+}
+
+#[test]
+fn recovery_role_can_not_withdraw_from_the_fee_vault() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    env.contribute_recovery_fee(XRD, dec!(100))
+        .expect_commit_success();
+
+    // Act
+    let receipt = env.withdraw_recovery_fee(env.recovery_badge, dec!(40));
+
+    // Assert
+    receipt.expect_auth_failure();
+}
+
+#[test]
+fn withdrawing_from_the_fee_vault_fails_when_the_primary_role_is_locked() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    env.contribute_recovery_fee(XRD, dec!(100))
+        .expect_commit_success();
+    let manifest = ManifestBuilder::new()
+        .create_proof_from_account_of_amount(env.account, env.recovery_badge, dec!(1))
+        .call_method(
+            env.access_controller,
+            ACCESS_CONTROLLER_LOCK_PRIMARY_ROLE_IDENT,
+            AccessControllerLockPrimaryRoleInput,
+        )
+        .build();
+    env.test_runner
+        .execute_manifest_ignoring_fee(
+            manifest,
+            [NonFungibleGlobalId::from_public_key(&env.public_key)],
+        )
+        .expect_commit_success();
+
+    // Act
+    let receipt = env.withdraw_recovery_fee(env.primary_badge, dec!(40));
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::AccessControllerError(
+                AccessControllerError::OperationRequiresUnlockedPrimaryRole
+            ))
+        )
+    });
+}
+
+struct TestEnvironment {
+    test_runner: DefaultTestRunner,
+    public_key: Secp256k1PublicKey,
+    account: ComponentAddress,
+    access_controller: ComponentAddress,
+    primary_badge: ResourceAddress,
+    recovery_badge: ResourceAddress,
+    confirmation_badge: ResourceAddress,
+}
+
+impl TestEnvironment {
+    fn new() -> Self {
+        let mut test_runner = TestRunnerBuilder::new().without_trace().build();
+        let (public_key, _, account) = test_runner.new_account(false);
+
+        let controlled_asset = test_runner.create_fungible_resource(dec!(1), 0, account);
+        let primary_badge = test_runner.create_fungible_resource(dec!(1), 0, account);
+        let recovery_badge = test_runner.create_fungible_resource(dec!(1), 0, account);
+        let confirmation_badge = test_runner.create_fungible_resource(dec!(1), 0, account);
+
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_from_account(account, controlled_asset, dec!(1))
+            .take_all_from_worktop(controlled_asset, "controlled_asset")
+            .create_access_controller(
+                "controlled_asset",
+                rule!(require(primary_badge)),
+                rule!(require(recovery_badge)),
+                rule!(require(confirmation_badge)),
+                None,
+            )
+            .build();
+        let access_controller = test_runner
+            .execute_manifest(
+                manifest,
+                [NonFungibleGlobalId::from_public_key(&public_key)],
+            )
+            .expect_commit_success()
+            .new_component_addresses()[0];
+
+        Self {
+            test_runner,
+            public_key,
+            account,
+            access_controller,
+            primary_badge,
+            recovery_badge,
+            confirmation_badge,
+        }
+    }
+
+    fn contribute_recovery_fee(
+        &mut self,
+        resource_address: ResourceAddress,
+        amount: Decimal,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(self.account, resource_address, amount)
+            .take_all_from_worktop(resource_address, "contribution")
+            .contribute_access_controller_recovery_fee(self.access_controller, "contribution")
+            .build();
+        self.test_runner.execute_manifest_ignoring_fee(
+            manifest,
+            [NonFungibleGlobalId::from_public_key(&self.public_key)],
+        )
+    }
+
+    fn lock_recovery_fee(&mut self, badge: ResourceAddress, amount: Decimal) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_amount(self.account, badge, dec!(1))
+            .lock_access_controller_recovery_fee(self.access_controller, amount)
+            .build();
+        self.test_runner.execute_manifest_ignoring_fee(
+            manifest,
+            [NonFungibleGlobalId::from_public_key(&self.public_key)],
+        )
+    }
+
+    fn withdraw_recovery_fee(
+        &mut self,
+        badge: ResourceAddress,
+        amount: Decimal,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_amount(self.account, badge, dec!(1))
+            .withdraw_access_controller_recovery_fee(self.access_controller, amount)
+            .try_deposit_entire_worktop_or_abort(self.account, None)
+            .build();
+        self.test_runner.execute_manifest_ignoring_fee(
+            manifest,
+            [NonFungibleGlobalId::from_public_key(&self.public_key)],
+        )
+    }
+
+    fn recovery_fee_balance(&mut self) -> Decimal {
+        self.test_runner
+            .get_component_balance(self.access_controller, XRD)
+    }
+}
@@ -0,0 +1,376 @@
+use radix_engine::blueprints::account::{AccountError, AllowanceWithdrawEvent};
+use radix_engine::errors::{ApplicationError, RuntimeError};
+use radix_engine::transaction::TransactionReceipt;
+use radix_engine::types::*;
+use radix_engine::vm::NoExtension;
+use radix_engine_interface::blueprints::account::*;
+use radix_engine_stores::memory_db::InMemorySubstateDatabase;
+use scrypto_unit::*;
+use transaction::prelude::*;
+
+#[test]
+fn grantee_can_withdraw_up_to_the_allowance() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    env.set_allowance(Some(dec!(100)), None, None)
+        .expect_commit_success();
+
+    // Act
+    let receipt = env.withdraw_with_allowance(dec!(60));
+
+    // Assert
+    receipt.expect_commit_success();
+    assert_eq!(env.balance(env.grantee_account), dec!(60));
+    assert_eq!(env.balance(env.account), dec!(940));
+    let allowance = env.get_allowance().unwrap();
+    assert_eq!(allowance.amount, Some(dec!(100)));
+    assert_eq!(allowance.remaining_amount, Some(dec!(40)));
+}
+
+#[test]
+fn withdrawing_more_than_the_remaining_allowance_fails() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    env.set_allowance(Some(dec!(100)), None, None)
+        .expect_commit_success();
+    env.withdraw_with_allowance(dec!(60))
+        .expect_commit_success();
+
+    // Act
+    let receipt = env.withdraw_with_allowance(dec!(50));
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::AccountError(
+                AccountError::AllowanceExceeded { .. }
+            ))
+        )
+    });
+}
+
+#[test]
+fn uncapped_allowance_can_withdraw_the_entire_balance() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    env.set_allowance(None, None, None).expect_commit_success();
+
+    // Act
+    let receipt = env.withdraw_with_allowance(dec!(1000));
+
+    // Assert
+    receipt.expect_commit_success();
+    assert_eq!(env.balance(env.grantee_account), dec!(1000));
+    assert_eq!(env.get_allowance().unwrap().remaining_amount, None);
+}
+
+#[test]
+fn withdrawing_without_the_grantee_badge_fails() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    env.set_allowance(Some(dec!(100)), None, None)
+        .expect_commit_success();
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account_with_allowance(
+            env.account,
+            env.grantee.clone(),
+            env.resource_address,
+            dec!(10),
+        )
+        .try_deposit_entire_worktop_or_abort(env.grantee_account, None)
+        .build();
+    let receipt = env.test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    receipt.expect_auth_assertion_failure();
+}
+
+#[test]
+fn setting_an_allowance_without_owner_auth_fails() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .set_account_allowance(
+            env.account,
+            env.grantee.clone(),
+            env.resource_address,
+            Some(dec!(100)),
+            None,
+            None,
+        )
+        .build();
+    let receipt = env.test_runner.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(
+            &env.grantee_public_key,
+        )],
+    );
+
+    // Assert
+    receipt.expect_auth_failure();
+}
+
+#[test]
+fn setting_a_negative_allowance_fails() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+
+    // Act
+    let receipt = env.set_allowance(Some(dec!(-1)), None, None);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::AccountError(
+                AccountError::InvalidAllowanceAmount { .. }
+            ))
+        )
+    });
+}
+
+#[test]
+fn setting_a_zero_reset_period_fails() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+
+    // Act
+    let receipt = env.set_allowance(Some(dec!(100)), Some(0), None);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::AccountError(
+                AccountError::InvalidAllowanceResetPeriod
+            ))
+        )
+    });
+}
+
+#[test]
+fn allowance_is_reset_after_the_reset_period() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    env.set_allowance(Some(dec!(100)), Some(10), None)
+        .expect_commit_success();
+    env.withdraw_with_allowance(dec!(100))
+        .expect_commit_success();
+    env.withdraw_with_allowance(dec!(1))
+        .expect_specific_failure(|e| {
+            matches!(
+                e,
+                RuntimeError::ApplicationError(ApplicationError::AccountError(
+                    AccountError::AllowanceExceeded { .. }
+                ))
+            )
+        });
+
+    // Act
+    let current_epoch = env.test_runner.get_current_epoch();
+    env.test_runner
+        .set_current_epoch(current_epoch.after(10).unwrap());
+    let receipt = env.withdraw_with_allowance(dec!(100));
+
+    // Assert
+    receipt.expect_commit_success();
+    assert_eq!(env.balance(env.grantee_account), dec!(200));
+}
+
+#[test]
+fn withdrawing_with_an_expired_allowance_fails() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let current_epoch = env.test_runner.get_current_epoch();
+    env.set_allowance(Some(dec!(100)), None, Some(current_epoch.after(5).unwrap()))
+        .expect_commit_success();
+
+    // Act
+    env.test_runner
+        .set_current_epoch(current_epoch.after(5).unwrap());
+    let receipt = env.withdraw_with_allowance(dec!(10));
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::AccountError(
+                AccountError::AllowanceExpired { .. }
+            ))
+        )
+    });
+}
+
+#[test]
+fn withdrawing_with_a_removed_allowance_fails() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    env.set_allowance(Some(dec!(100)), None, None)
+        .expect_commit_success();
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .remove_account_allowance(env.account, env.grantee.clone(), env.resource_address)
+        .build();
+    env.test_runner
+        .execute_manifest(
+            manifest,
+            vec![NonFungibleGlobalId::from_public_key(&env.public_key)],
+        )
+        .expect_commit_success();
+
+    // Act
+    let receipt = env.withdraw_with_allowance(dec!(10));
+
+    // Assert
+    assert_eq!(env.get_allowance(), None);
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::AccountError(
+                AccountError::AllowanceDoesNotExist { .. }
+            ))
+        )
+    });
+}
+
+#[test]
+fn withdrawing_with_an_allowance_emits_an_event() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    env.set_allowance(Some(dec!(100)), None, None)
+        .expect_commit_success();
+
+    // Act
+    let receipt = env.withdraw_with_allowance(dec!(25));
+
+    // Assert
+    let events = env
+        .test_runner
+        .extract_events_of_type::<AllowanceWithdrawEvent>(receipt.expect_commit_success());
+    assert_eq!(
+        events,
+        vec![AllowanceWithdrawEvent {
+            grantee: env.grantee.clone(),
+            resource_address: env.resource_address,
+            amount: dec!(25),
+            remaining_amount: Some(dec!(75)),
+        }]
+    );
+}
+
+#[test]
+fn component_grantee_can_not_withdraw_from_a_manifest() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    env.grantee = AllowanceGrantee::Component(env.grantee_account);
+    env.set_allowance(Some(dec!(100)), None, None)
+        .expect_commit_success();
+
+    // Act
+    let receipt = env.withdraw_with_allowance(dec!(10));
+
+    // Assert
+    receipt.expect_auth_assertion_failure();
+}
+
+struct TestEnvironment {
+    test_runner: TestRunner<NoExtension, InMemorySubstateDatabase>,
+    public_key: Secp256k1PublicKey,
+    account: ComponentAddress,
+    grantee_public_key: Secp256k1PublicKey,
+    grantee_account: ComponentAddress,
+    grantee: AllowanceGrantee,
+    resource_address: ResourceAddress,
+}
+
+impl TestEnvironment {
+    fn new() -> Self {
+        let mut test_runner = TestRunnerBuilder::new().without_trace().build();
+        let (public_key, _, account) = test_runner.new_account(false);
+        let (grantee_public_key, _, grantee_account) = test_runner.new_account(false);
+        let resource_address = test_runner.create_fungible_resource(dec!(1000), 18, account);
+        let grantee = AllowanceGrantee::Badge(ResourceOrNonFungible::NonFungible(
+            NonFungibleGlobalId::from_public_key(&grantee_public_key),
+        ));
+
+        Self {
+            test_runner,
+            public_key,
+            account,
+            grantee_public_key,
+            grantee_account,
+            grantee,
+            resource_address,
+        }
+    }
+
+    fn set_allowance(
+        &mut self,
+        amount: Option<Decimal>,
+        reset_period: Option<u64>,
+        expiry_epoch: Option<Epoch>,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .set_account_allowance(
+                self.account,
+                self.grantee.clone(),
+                self.resource_address,
+                amount,
+                reset_period,
+                expiry_epoch,
+            )
+            .build();
+        self.test_runner.execute_manifest(
+            manifest,
+            vec![NonFungibleGlobalId::from_public_key(&self.public_key)],
+        )
+    }
+
+    fn withdraw_with_allowance(&mut self, amount: Decimal) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_from_account_with_allowance(
+                self.account,
+                self.grantee.clone(),
+                self.resource_address,
+                amount,
+            )
+            .try_deposit_entire_worktop_or_abort(self.grantee_account, None)
+            .build();
+        self.test_runner.execute_manifest(
+            manifest,
+            vec![NonFungibleGlobalId::from_public_key(
+                &self.grantee_public_key,
+            )],
+        )
+    }
+
+    fn get_allowance(&mut self) -> Option<Allowance> {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .call_method(
+                self.account,
+                ACCOUNT_GET_ALLOWANCE_IDENT,
+                AccountGetAllowanceInput {
+                    grantee: self.grantee.clone(),
+                    resource_address: self.resource_address,
+                },
+            )
+            .build();
+        let receipt = self.test_runner.execute_manifest(manifest, vec![]);
+        receipt.expect_commit_success().output(1)
+    }
+
+    fn balance(&mut self, account: ComponentAddress) -> Decimal {
+        self.test_runner
+            .get_component_balance(account, self.resource_address)
+    }
+}
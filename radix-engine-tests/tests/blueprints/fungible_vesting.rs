@@ -0,0 +1,414 @@
+use radix_engine::blueprints::resource::{fungible_vesting::*, FungibleVestingError};
+use radix_engine::errors::{ApplicationError, RuntimeError, SystemModuleError};
+use radix_engine::transaction::TransactionReceipt;
+use radix_engine::types::*;
+use radix_engine_interface::api::ModuleId;
+use scrypto_unit::*;
+use transaction::prelude::*;
+
+#[test]
+pub fn nothing_can_be_withdrawn_before_the_cliff() {
+    // Arrange
+    let mut test_runner = TestEnvironment::new(true);
+    test_runner.advance_seconds(50);
+
+    // Act
+    let receipt = test_runner.withdraw(dec!(1));
+
+    // Assert
+    assert_eq!(test_runner.get_unlocked_amount(), dec!(0));
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::FungibleVestingError(
+                FungibleVestingError::AmountExceedsUnlockedAmount { .. }
+            ))
+        )
+    });
+}
+
+#[test]
+pub fn tokens_vest_linearly_after_the_cliff() {
+    // Arrange
+    let mut test_runner = TestEnvironment::new(true);
+
+    // Act
+    test_runner.advance_seconds(100);
+    let unlocked_at_cliff = test_runner.get_unlocked_amount();
+    test_runner.advance_seconds(150);
+    let unlocked_after_cliff = test_runner.get_unlocked_amount();
+    test_runner.advance_seconds(5000);
+    let unlocked_after_end = test_runner.get_unlocked_amount();
+
+    // Assert
+    assert_eq!(unlocked_at_cliff, dec!(100));
+    assert_eq!(unlocked_after_cliff, dec!(250));
+    assert_eq!(unlocked_after_end, dec!(1000));
+}
+
+#[test]
+pub fn beneficiary_can_withdraw_unlocked_tokens() {
+    // Arrange
+    let mut test_runner = TestEnvironment::new(true);
+    test_runner.advance_seconds(250);
+
+    // Act
+    let receipt = test_runner.withdraw(dec!(250));
+
+    // Assert
+    let commit_result = receipt.expect_commit_success();
+    let WithdrawEvent { amount } = commit_result
+        .application_events
+        .iter()
+        .find_map(|(event_type_identifier, event_data)| {
+            if test_runner.test_runner.event_name(event_type_identifier) == "WithdrawEvent"
+                && test_runner.is_vesting_emitter(event_type_identifier)
+            {
+                Some(scrypto_decode(event_data).unwrap())
+            } else {
+                None
+            }
+        })
+        .unwrap();
+    assert_eq!(amount, dec!(250));
+    assert_eq!(
+        test_runner.balance(test_runner.beneficiary_account),
+        dec!(250)
+    );
+    assert_eq!(test_runner.get_unlocked_amount(), dec!(0));
+}
+
+#[test]
+pub fn withdrawing_more_than_the_unlocked_amount_fails() {
+    // Arrange
+    let mut test_runner = TestEnvironment::new(true);
+    test_runner.advance_seconds(250);
+    test_runner.withdraw(dec!(200)).expect_commit_success();
+
+    // Act
+    let receipt = test_runner.withdraw(dec!(51));
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::FungibleVestingError(
+                FungibleVestingError::AmountExceedsUnlockedAmount { requested, unlocked }
+            )) if *requested == dec!(51) && *unlocked == dec!(50)
+        )
+    });
+}
+
+#[test]
+pub fn withdraw_without_beneficiary_role_fails() {
+    // Arrange
+    let mut test_runner = TestEnvironment::new(true);
+    test_runner.advance_seconds(250);
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .withdraw_from_fungible_vesting(test_runner.vesting_component_address, dec!(1))
+        .try_deposit_entire_worktop_or_abort(test_runner.revoker_account, None)
+        .build();
+    let public_key = test_runner.revoker_public_key;
+    let receipt = test_runner.execute_manifest(manifest, &public_key);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::SystemModuleError(SystemModuleError::AuthError(..))
+        )
+    });
+}
+
+#[test]
+pub fn revoke_returns_unvested_tokens_and_stops_vesting() {
+    // Arrange
+    let mut test_runner = TestEnvironment::new(true);
+    test_runner.advance_seconds(250);
+    test_runner.withdraw(dec!(250)).expect_commit_success();
+    test_runner.advance_seconds(150);
+
+    // Act
+    let receipt = test_runner.revoke();
+
+    // Assert
+    let commit_result = receipt.expect_commit_success();
+    let RevokeEvent { amount } = commit_result
+        .application_events
+        .iter()
+        .find_map(|(event_type_identifier, event_data)| {
+            if test_runner.test_runner.event_name(event_type_identifier) == "RevokeEvent"
+                && test_runner.is_vesting_emitter(event_type_identifier)
+            {
+                Some(scrypto_decode(event_data).unwrap())
+            } else {
+                None
+            }
+        })
+        .unwrap();
+    assert_eq!(amount, dec!(600));
+    assert_eq!(test_runner.balance(test_runner.revoker_account), dec!(600));
+
+    test_runner.advance_seconds(5000);
+    assert_eq!(test_runner.get_unlocked_amount(), dec!(150));
+    test_runner.withdraw(dec!(150)).expect_commit_success();
+    assert_eq!(
+        test_runner.balance(test_runner.beneficiary_account),
+        dec!(400)
+    );
+}
+
+#[test]
+pub fn revoking_twice_fails() {
+    // Arrange
+    let mut test_runner = TestEnvironment::new(true);
+    test_runner.revoke().expect_commit_success();
+
+    // Act
+    let receipt = test_runner.revoke();
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::FungibleVestingError(
+                FungibleVestingError::AlreadyRevoked
+            ))
+        )
+    });
+}
+
+#[test]
+pub fn vesting_without_revoker_cannot_be_revoked() {
+    // Arrange
+    let mut test_runner = TestEnvironment::new(false);
+
+    // Act
+    let receipt = test_runner.revoke();
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::SystemModuleError(SystemModuleError::AuthError(..))
+        )
+    });
+}
+
+#[test]
+pub fn vesting_cannot_be_created_with_an_invalid_schedule() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new().without_trace().build();
+    let now = test_runner.get_current_time(TimePrecision::Second);
+    let schedules = [
+        VestingSchedule {
+            start: now,
+            cliff: None,
+            end: now.add_seconds(-1).unwrap(),
+        },
+        VestingSchedule {
+            start: now,
+            cliff: Some(now.add_seconds(-1).unwrap()),
+            end: now.add_seconds(1000).unwrap(),
+        },
+        VestingSchedule {
+            start: now,
+            cliff: Some(now.add_seconds(1001).unwrap()),
+            end: now.add_seconds(1000).unwrap(),
+        },
+    ];
+
+    for schedule in schedules {
+        // Act
+        let manifest = ManifestBuilder::new()
+            .get_free_xrd_from_faucet()
+            .take_all_from_worktop(XRD, "bucket")
+            .create_fungible_vesting(OwnerRole::None, "bucket", schedule, rule!(allow_all), None)
+            .build();
+        let receipt = test_runner.execute_manifest_ignoring_fee(manifest, vec![]);
+
+        // Assert
+        receipt.expect_specific_failure(|e| {
+            matches!(
+                e,
+                RuntimeError::ApplicationError(ApplicationError::FungibleVestingError(
+                    FungibleVestingError::InvalidSchedule(..)
+                ))
+            )
+        });
+    }
+}
+
+#[test]
+pub fn vesting_cannot_be_created_with_non_fungible_resources() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new().without_trace().build();
+    let (public_key, _, account) = test_runner.new_allocated_account();
+    let resource_address = test_runner.create_non_fungible_resource(account);
+    let now = test_runner.get_current_time(TimePrecision::Second);
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .withdraw_from_account(account, resource_address, dec!(1))
+        .take_all_from_worktop(resource_address, "bucket")
+        .create_fungible_vesting(
+            OwnerRole::None,
+            "bucket",
+            VestingSchedule {
+                start: now,
+                cliff: None,
+                end: now.add_seconds(1000).unwrap(),
+            },
+            rule!(allow_all),
+            None,
+        )
+        .build();
+    let receipt = test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    );
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::FungibleVestingError(
+                FungibleVestingError::NonFungibleResourcesAreNotAccepted { .. }
+            ))
+        )
+    });
+}
+
+struct TestEnvironment {
+    test_runner: DefaultTestRunner,
+
+    vesting_component_address: ComponentAddress,
+    resource_address: ResourceAddress,
+
+    beneficiary_public_key: Secp256k1PublicKey,
+    beneficiary_account: ComponentAddress,
+    revoker_public_key: Secp256k1PublicKey,
+    revoker_account: ComponentAddress,
+}
+
+impl TestEnvironment {
+    /// Vests 1000 tokens over 1000 seconds with a cliff after 100 seconds.
+    pub fn new(with_revoker: bool) -> Self {
+        let mut test_runner = TestRunnerBuilder::new().without_trace().build();
+        let (beneficiary_public_key, _, beneficiary_account) = test_runner.new_allocated_account();
+        let (revoker_public_key, _, revoker_account) = test_runner.new_allocated_account();
+        let resource_address =
+            test_runner.create_fungible_resource(dec!(1000), 18, revoker_account);
+        let now = test_runner.get_current_time(TimePrecision::Second);
+
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(revoker_account, resource_address, dec!(1000))
+            .take_all_from_worktop(resource_address, "bucket")
+            .create_fungible_vesting(
+                OwnerRole::None,
+                "bucket",
+                VestingSchedule {
+                    start: now,
+                    cliff: Some(now.add_seconds(100).unwrap()),
+                    end: now.add_seconds(1000).unwrap(),
+                },
+                rule!(require(NonFungibleGlobalId::from_public_key(
+                    &beneficiary_public_key
+                ))),
+                with_revoker.then(|| {
+                    rule!(require(NonFungibleGlobalId::from_public_key(
+                        &revoker_public_key
+                    )))
+                }),
+            )
+            .build();
+        let vesting_component_address = test_runner
+            .execute_manifest_ignoring_fee(
+                manifest,
+                vec![NonFungibleGlobalId::from_public_key(&revoker_public_key)],
+            )
+            .expect_commit_success()
+            .new_component_addresses()[0];
+
+        Self {
+            test_runner,
+            vesting_component_address,
+            resource_address,
+            beneficiary_public_key,
+            beneficiary_account,
+            revoker_public_key,
+            revoker_account,
+        }
+    }
+
+    fn advance_seconds(&mut self, seconds: i64) {
+        let round = self
+            .test_runner
+            .get_consensus_manager_state()
+            .round
+            .number();
+        let timestamp_ms = self.test_runner.get_current_proposer_timestamp_ms();
+        self.test_runner
+            .advance_to_round_at_timestamp(Round::of(round + 1), timestamp_ms + seconds * 1000)
+            .expect_commit_success();
+    }
+
+    fn withdraw(&mut self, amount: Decimal) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_fungible_vesting(self.vesting_component_address, amount)
+            .try_deposit_entire_worktop_or_abort(self.beneficiary_account, None)
+            .build();
+        let public_key = self.beneficiary_public_key;
+        self.execute_manifest(manifest, &public_key)
+    }
+
+    fn revoke(&mut self) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .revoke_fungible_vesting(self.vesting_component_address)
+            .try_deposit_entire_worktop_or_abort(self.revoker_account, None)
+            .build();
+        let public_key = self.revoker_public_key;
+        self.execute_manifest(manifest, &public_key)
+    }
+
+    fn get_unlocked_amount(&mut self) -> Decimal {
+        let manifest = ManifestBuilder::new()
+            .call_method(
+                self.vesting_component_address,
+                FUNGIBLE_VESTING_GET_UNLOCKED_AMOUNT_IDENT,
+                FungibleVestingGetUnlockedAmountManifestInput {},
+            )
+            .build();
+        self.test_runner
+            .execute_manifest_ignoring_fee(manifest, vec![])
+            .expect_commit_success()
+            .output(1)
+    }
+
+    fn balance(&mut self, account: ComponentAddress) -> Decimal {
+        self.test_runner
+            .get_component_balance(account, self.resource_address)
+    }
+
+    fn is_vesting_emitter(&self, event_type_identifier: &EventTypeIdentifier) -> bool {
+        match event_type_identifier.0 {
+            Emitter::Method(node_id, ModuleId::Main) => {
+                node_id == *self.vesting_component_address.as_node_id()
+            }
+            _ => false,
+        }
+    }
+
+    fn execute_manifest(
+        &mut self,
+        manifest: TransactionManifestV1,
+        public_key: &Secp256k1PublicKey,
+    ) -> TransactionReceipt {
+        self.test_runner.execute_manifest_ignoring_fee(
+            manifest,
+            vec![NonFungibleGlobalId::from_public_key(public_key)],
+        )
+    }
+}
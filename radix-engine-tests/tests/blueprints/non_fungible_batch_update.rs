@@ -0,0 +1,300 @@
+use radix_engine::blueprints::resource::{
+    BatchUpdateNonFungibleDataEvent, NonFungibleResourceManagerError,
+};
+use radix_engine::errors::{ApplicationError, RuntimeError, SystemError, SystemModuleError};
+use radix_engine::transaction::TransactionReceipt;
+use radix_engine::types::*;
+use radix_engine_interface::api::node_modules::ModuleConfig;
+use scrypto::NonFungibleData;
+use scrypto_unit::*;
+use transaction::prelude::*;
+
+#[derive(ManifestSbor, ScryptoSbor, NonFungibleData, Debug, PartialEq, Eq)]
+pub struct GameAsset {
+    pub name: String,
+    #[mutable]
+    pub level: u32,
+    #[mutable]
+    pub note: String,
+}
+
+#[test]
+fn batch_update_updates_fields_of_many_non_fungibles() {
+    // Arrange
+    let mut test_runner = TestEnvironment::new();
+
+    // Act
+    let receipt = test_runner.batch_update(
+        vec![
+            (
+                NonFungibleLocalId::integer(1),
+                "level",
+                to_manifest_value_and_unwrap!(&2u32),
+            ),
+            (
+                NonFungibleLocalId::integer(1),
+                "note",
+                to_manifest_value_and_unwrap!(&"upgraded".to_string()),
+            ),
+            (
+                NonFungibleLocalId::integer(2),
+                "level",
+                to_manifest_value_and_unwrap!(&5u32),
+            ),
+        ],
+        true,
+    );
+
+    // Assert
+    receipt.expect_commit_success();
+    assert_eq!(
+        test_runner.get_asset(1),
+        GameAsset {
+            name: "Sword".to_string(),
+            level: 2,
+            note: "upgraded".to_string(),
+        }
+    );
+    assert_eq!(
+        test_runner.get_asset(2),
+        GameAsset {
+            name: "Shield".to_string(),
+            level: 5,
+            note: String::new(),
+        }
+    );
+    assert_eq!(
+        test_runner.get_asset(3),
+        GameAsset {
+            name: "Potion".to_string(),
+            level: 1,
+            note: String::new(),
+        }
+    );
+}
+
+#[test]
+fn later_update_of_the_same_field_wins() {
+    // Arrange
+    let mut test_runner = TestEnvironment::new();
+
+    // Act
+    let receipt = test_runner.batch_update(
+        vec![
+            (NonFungibleLocalId::integer(3), "level", 7u32),
+            (NonFungibleLocalId::integer(3), "level", 9u32),
+        ],
+        true,
+    );
+
+    // Assert
+    receipt.expect_commit_success();
+    assert_eq!(test_runner.get_asset(3).level, 9);
+}
+
+#[test]
+fn batch_update_emits_a_single_aggregated_event() {
+    // Arrange
+    let mut test_runner = TestEnvironment::new();
+
+    // Act
+    let receipt = test_runner.batch_update(
+        vec![
+            (NonFungibleLocalId::integer(2), "level", 2u32),
+            (NonFungibleLocalId::integer(1), "level", 3u32),
+            (NonFungibleLocalId::integer(2), "level", 4u32),
+        ],
+        true,
+    );
+
+    // Assert
+    let events: Vec<BatchUpdateNonFungibleDataEvent> = receipt
+        .expect_commit_success()
+        .application_events
+        .iter()
+        .filter(|(event_type_identifier, _)| {
+            test_runner.test_runner.event_name(event_type_identifier)
+                == "BatchUpdateNonFungibleDataEvent"
+        })
+        .map(|(_, event_data)| scrypto_decode(event_data).unwrap())
+        .collect();
+    assert_eq!(
+        events,
+        vec![BatchUpdateNonFungibleDataEvent {
+            updated_fields: indexmap!(
+                NonFungibleLocalId::integer(2) => indexset!("level".to_string()),
+                NonFungibleLocalId::integer(1) => indexset!("level".to_string()),
+            ),
+        }]
+    );
+}
+
+#[test]
+fn batch_update_without_data_updater_role_fails() {
+    // Arrange
+    let mut test_runner = TestEnvironment::new();
+
+    // Act
+    let receipt =
+        test_runner.batch_update(vec![(NonFungibleLocalId::integer(1), "level", 2u32)], false);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::SystemModuleError(SystemModuleError::AuthError(..))
+        )
+    });
+}
+
+#[test]
+fn batch_update_of_immutable_field_fails_without_applying_any_update() {
+    // Arrange
+    let mut test_runner = TestEnvironment::new();
+
+    // Act
+    let receipt = test_runner.batch_update(
+        vec![
+            (
+                NonFungibleLocalId::integer(1),
+                "level",
+                to_manifest_value_and_unwrap!(&2u32),
+            ),
+            (
+                NonFungibleLocalId::integer(1),
+                "name",
+                to_manifest_value_and_unwrap!(&"Axe".to_string()),
+            ),
+        ],
+        true,
+    );
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::NonFungibleResourceManagerError(
+                NonFungibleResourceManagerError::UnknownMutableFieldName(field_name)
+            )) if field_name == "name"
+        )
+    });
+    assert_eq!(test_runner.get_asset(1).level, 1);
+}
+
+#[test]
+fn batch_update_of_missing_non_fungible_fails() {
+    // Arrange
+    let mut test_runner = TestEnvironment::new();
+
+    // Act
+    let receipt = test_runner.batch_update(
+        vec![
+            (NonFungibleLocalId::integer(1), "level", 2u32),
+            (NonFungibleLocalId::integer(4), "level", 2u32),
+        ],
+        true,
+    );
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::NonFungibleResourceManagerError(
+                NonFungibleResourceManagerError::NonFungibleNotFound(..)
+            ))
+        )
+    });
+}
+
+#[test]
+fn batch_update_with_value_not_matching_the_schema_fails() {
+    // Arrange
+    let mut test_runner = TestEnvironment::new();
+
+    // Act
+    let receipt = test_runner.batch_update(
+        vec![(NonFungibleLocalId::integer(1), "level", "high".to_string())],
+        true,
+    );
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::SystemError(SystemError::TypeCheckError(..))
+        )
+    });
+}
+
+struct TestEnvironment {
+    test_runner: DefaultTestRunner,
+    resource_address: ResourceAddress,
+    public_key: Secp256k1PublicKey,
+}
+
+impl TestEnvironment {
+    fn new() -> Self {
+        let mut test_runner = TestRunnerBuilder::new().without_trace().build();
+        let (public_key, _, account) = test_runner.new_allocated_account();
+        let data_updater_badge = NonFungibleGlobalId::from_public_key(&public_key);
+
+        let asset = |name: &str| GameAsset {
+            name: name.to_string(),
+            level: 1,
+            note: String::new(),
+        };
+        let manifest = ManifestBuilder::new()
+            .create_non_fungible_resource(
+                OwnerRole::None,
+                NonFungibleIdType::Integer,
+                false,
+                NonFungibleResourceRoles {
+                    non_fungible_data_update_roles: non_fungible_data_update_roles! {
+                        non_fungible_data_updater => rule!(require(data_updater_badge));
+                        non_fungible_data_updater_updater => rule!(deny_all);
+                    },
+                    ..Default::default()
+                },
+                ModuleConfig::default(),
+                Some(vec![
+                    (NonFungibleLocalId::integer(1), asset("Sword")),
+                    (NonFungibleLocalId::integer(2), asset("Shield")),
+                    (NonFungibleLocalId::integer(3), asset("Potion")),
+                ]),
+            )
+            .try_deposit_entire_worktop_or_abort(account, None)
+            .build();
+        let resource_address = test_runner
+            .execute_manifest_ignoring_fee(manifest, vec![])
+            .expect_commit_success()
+            .new_resource_addresses()[0];
+
+        Self {
+            test_runner,
+            resource_address,
+            public_key,
+        }
+    }
+
+    fn batch_update<D: ManifestEncode>(
+        &mut self,
+        updates: Vec<(NonFungibleLocalId, &str, D)>,
+        sign: bool,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .batch_update_non_fungible_data(self.resource_address, updates)
+            .build();
+        let initial_proofs = if sign {
+            vec![NonFungibleGlobalId::from_public_key(&self.public_key)]
+        } else {
+            vec![]
+        };
+        self.test_runner
+            .execute_manifest_ignoring_fee(manifest, initial_proofs)
+    }
+
+    fn get_asset(&self, id: u64) -> GameAsset {
+        self.test_runner
+            .get_non_fungible_data(self.resource_address, NonFungibleLocalId::integer(id))
+    }
+}
@@ -0,0 +1,631 @@
+use radix_engine::blueprints::pool::v1::constants::*;
+use radix_engine::blueprints::pool::v1::errors::constant_product_pool::Error as ConstantProductPoolError;
+use radix_engine::blueprints::pool::v1::events::constant_product_pool::*;
+use radix_engine::errors::{ApplicationError, RuntimeError, SystemModuleError};
+use radix_engine::transaction::TransactionReceipt;
+use radix_engine::types::*;
+use radix_engine_interface::api::ModuleId;
+use radix_engine_interface::blueprints::pool::*;
+use scrypto_unit::*;
+use transaction::prelude::*;
+
+#[test]
+pub fn constant_product_pool_can_be_instantiated() {
+    TestEnvironment::new(dec!("0.003"));
+}
+
+#[test]
+pub fn constant_product_pool_cannot_be_instantiated_with_an_invalid_fee_rate() {
+    for fee_rate in [dec!("-0.1"), dec!("1"), dec!("1.5")] {
+        // Arrange
+        let mut test_runner = TestRunnerBuilder::new().without_trace().build();
+        let (_, _, account) = test_runner.new_account(false);
+        let resource1 = test_runner.create_fungible_resource(dec!(100), 18, account);
+        let resource2 = test_runner.create_fungible_resource(dec!(100), 18, account);
+
+        // Act
+        let manifest = ManifestBuilder::new()
+            .create_constant_product_pool(
+                OwnerRole::None,
+                rule!(allow_all),
+                (resource1, resource2),
+                fee_rate,
+            )
+            .build();
+        let receipt = test_runner.execute_manifest_ignoring_fee(manifest, vec![]);
+
+        // Assert
+        receipt.expect_specific_failure(|e| {
+            matches!(
+                e,
+                RuntimeError::ApplicationError(ApplicationError::ConstantProductPoolError(
+                    ConstantProductPoolError::InvalidFeeRate { .. }
+                ))
+            )
+        });
+    }
+}
+
+#[test]
+pub fn swap_returns_expected_amount_without_fee() {
+    // Arrange
+    let mut test_runner = TestEnvironment::new(dec!(0));
+    test_runner
+        .contribute(dec!(100), dec!(100))
+        .expect_commit_success();
+
+    // Act
+    let receipt = test_runner.swap(test_runner.pool_resource1, dec!(10), dec!(0));
+
+    // Assert
+    receipt.expect_commit_success();
+    assert_eq!(
+        test_runner.account_balance(test_runner.pool_resource2),
+        dec!("9.090909090909090909")
+    );
+    assert_eq!(
+        test_runner.get_vault_amounts(),
+        indexmap!(
+            test_runner.pool_resource1 => dec!(110),
+            test_runner.pool_resource2 => dec!("90.909090909090909091"),
+        )
+    );
+}
+
+#[test]
+pub fn swap_charges_fee_on_input() {
+    // Arrange
+    let mut test_runner = TestEnvironment::new(dec!("0.003"));
+    test_runner
+        .contribute(dec!(100), dec!(100))
+        .expect_commit_success();
+    let expected_output_amount =
+        test_runner.get_swap_output_amount(test_runner.pool_resource1, dec!(10));
+
+    // Act
+    let receipt = test_runner.swap(test_runner.pool_resource1, dec!(10), dec!(0));
+
+    // Assert
+    receipt.expect_commit_success();
+    assert_eq!(expected_output_amount, dec!("9.066108938801491315"));
+    assert_eq!(
+        test_runner.account_balance(test_runner.pool_resource2),
+        expected_output_amount
+    );
+}
+
+#[test]
+pub fn swap_below_minimum_output_amount_fails() {
+    // Arrange
+    let mut test_runner = TestEnvironment::new(dec!("0.003"));
+    test_runner
+        .contribute(dec!(100), dec!(100))
+        .expect_commit_success();
+
+    // Act
+    let receipt = test_runner.swap(test_runner.pool_resource1, dec!(10), dec!("9.1"));
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::ConstantProductPoolError(
+                ConstantProductPoolError::SwapOutputBelowMinimum { .. }
+            ))
+        )
+    });
+}
+
+#[test]
+pub fn swap_for_exact_output_returns_output_and_remaining_input() {
+    // Arrange
+    let mut test_runner = TestEnvironment::new(dec!("0.003"));
+    test_runner
+        .contribute(dec!(100), dec!(100))
+        .expect_commit_success();
+    let expected_input_amount =
+        test_runner.get_swap_input_amount(test_runner.pool_resource2, dec!(10));
+
+    // Act
+    let receipt = test_runner.swap_for_exact_output(test_runner.pool_resource1, dec!(20), dec!(10));
+
+    // Assert
+    receipt.expect_commit_success();
+    assert_eq!(expected_input_amount, dec!("11.14454474534715257"));
+    assert_eq!(
+        test_runner.account_balance(test_runner.pool_resource1),
+        dec!(20) - expected_input_amount
+    );
+    assert_eq!(
+        test_runner.account_balance(test_runner.pool_resource2),
+        dec!(10)
+    );
+}
+
+#[test]
+pub fn swap_for_exact_output_with_insufficient_input_fails() {
+    // Arrange
+    let mut test_runner = TestEnvironment::new(dec!("0.003"));
+    test_runner
+        .contribute(dec!(100), dec!(100))
+        .expect_commit_success();
+
+    // Act
+    let receipt = test_runner.swap_for_exact_output(test_runner.pool_resource1, dec!(11), dec!(10));
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::ConstantProductPoolError(
+                ConstantProductPoolError::SwapInputAboveMaximum { .. }
+            ))
+        )
+    });
+}
+
+#[test]
+pub fn swap_for_output_exceeding_reserves_fails() {
+    // Arrange
+    let mut test_runner = TestEnvironment::new(dec!("0.003"));
+    test_runner
+        .contribute(dec!(100), dec!(100))
+        .expect_commit_success();
+
+    // Act
+    let receipt =
+        test_runner.swap_for_exact_output(test_runner.pool_resource1, dec!(1000), dec!(100));
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::ConstantProductPoolError(
+                ConstantProductPoolError::SwapOutputExceedsReserves { .. }
+            ))
+        )
+    });
+}
+
+#[test]
+pub fn swap_with_pool_without_liquidity_fails() {
+    // Arrange
+    let mut test_runner = TestEnvironment::new(dec!("0.003"));
+
+    // Act
+    let receipt = test_runner.swap(test_runner.pool_resource1, dec!(10), dec!(0));
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::ConstantProductPoolError(
+                ConstantProductPoolError::PoolHasNoLiquidity
+            ))
+        )
+    });
+}
+
+#[test]
+pub fn swap_of_resource_not_in_pool_fails() {
+    // Arrange
+    let mut test_runner = TestEnvironment::new(dec!("0.003"));
+    test_runner
+        .contribute(dec!(100), dec!(100))
+        .expect_commit_success();
+    let foreign_resource = test_runner
+        .test_runner
+        .create_freely_mintable_and_burnable_fungible_resource(
+            OwnerRole::None,
+            None,
+            18,
+            test_runner.account_component_address,
+        );
+
+    // Act
+    let receipt = test_runner.swap(foreign_resource, dec!(10), dec!(0));
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::ConstantProductPoolError(
+                ConstantProductPoolError::ResourceDoesNotBelongToPool { .. }
+            ))
+        )
+    });
+}
+
+#[test]
+pub fn swap_emits_expected_event() {
+    // Arrange
+    let mut test_runner = TestEnvironment::new(dec!("0.003"));
+    test_runner
+        .contribute(dec!(100), dec!(100))
+        .expect_commit_success();
+
+    // Act
+    let receipt = test_runner.swap(test_runner.pool_resource1, dec!(10), dec!(0));
+
+    // Assert
+    let SwapEvent {
+        input_resource_address,
+        input_amount,
+        output_resource_address,
+        output_amount,
+        fee_amount,
+    } = receipt
+        .expect_commit_success()
+        .application_events
+        .iter()
+        .find_map(|(event_type_identifier, event_data)| {
+            if test_runner.test_runner.event_name(event_type_identifier) == "SwapEvent"
+                && is_constant_product_pool_emitter(event_type_identifier)
+            {
+                Some(scrypto_decode(event_data).unwrap())
+            } else {
+                None
+            }
+        })
+        .unwrap();
+    assert_eq!(input_resource_address, test_runner.pool_resource1);
+    assert_eq!(input_amount, dec!(10));
+    assert_eq!(output_resource_address, test_runner.pool_resource2);
+    assert_eq!(output_amount, dec!("9.066108938801491315"));
+    assert_eq!(fee_amount, dec!("0.03"));
+}
+
+#[test]
+pub fn fee_rate_can_be_set_by_fee_manager() {
+    // Arrange
+    let mut test_runner = TestEnvironment::new(dec!("0.003"));
+
+    // Act
+    let receipt = test_runner.set_fee_rate(dec!("0.01"), true);
+
+    // Assert
+    let commit_result = receipt.expect_commit_success();
+    assert!(commit_result
+        .application_events
+        .iter()
+        .any(|(event_type_identifier, _)| {
+            test_runner.test_runner.event_name(event_type_identifier) == "FeeRateChangeEvent"
+                && is_constant_product_pool_emitter(event_type_identifier)
+        }));
+    assert_eq!(test_runner.get_fee_rate(), dec!("0.01"));
+}
+
+#[test]
+pub fn fee_rate_cannot_be_set_without_fee_manager_role() {
+    // Arrange
+    let mut test_runner = TestEnvironment::new(dec!("0.003"));
+
+    // Act
+    let receipt = test_runner.set_fee_rate(dec!("0.01"), false);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::SystemModuleError(SystemModuleError::AuthError(..))
+        )
+    });
+    assert_eq!(test_runner.get_fee_rate(), dec!("0.003"));
+}
+
+#[test]
+pub fn fee_rate_cannot_be_set_to_an_invalid_value() {
+    // Arrange
+    let mut test_runner = TestEnvironment::new(dec!("0.003"));
+
+    // Act
+    let receipt = test_runner.set_fee_rate(dec!(1), true);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::ConstantProductPoolError(
+                ConstantProductPoolError::InvalidFeeRate { .. }
+            ))
+        )
+    });
+}
+
+#[test]
+pub fn pool_units_can_be_redeemed_for_reserves_including_fees() {
+    // Arrange
+    let mut test_runner = TestEnvironment::new(dec!("0.003"));
+    test_runner
+        .contribute(dec!(100), dec!(100))
+        .expect_commit_success();
+    test_runner
+        .swap(test_runner.pool_resource1, dec!(10), dec!(0))
+        .expect_commit_success();
+    let pool_units = test_runner.account_balance(test_runner.pool_unit_resource_address);
+
+    // Act
+    let receipt = test_runner.redeem(pool_units);
+
+    // Assert
+    receipt.expect_commit_success();
+    assert_eq!(
+        test_runner.account_balance(test_runner.pool_resource1),
+        dec!(110)
+    );
+    assert_eq!(
+        test_runner.account_balance(test_runner.pool_resource2),
+        dec!(100)
+    );
+}
+
+#[test]
+pub fn oracle_accumulates_prices_over_time() {
+    // Arrange
+    let mut test_runner = TestEnvironment::new(dec!("0.003"));
+    test_runner
+        .contribute(dec!(100), dec!(400))
+        .expect_commit_success();
+    let round = test_runner
+        .test_runner
+        .get_consensus_manager_state()
+        .round
+        .number();
+    let timestamp_ms = test_runner.test_runner.get_current_proposer_timestamp_ms();
+
+    // Act
+    test_runner
+        .test_runner
+        .advance_to_round_at_timestamp(Round::of(round + 1), timestamp_ms + 10_000)
+        .expect_commit_success();
+    let oracle_state = test_runner.get_oracle_state();
+
+    // Assert
+    assert_eq!(
+        oracle_state.cumulative_prices,
+        indexmap!(
+            test_runner.pool_resource1 => pdec!(40),
+            test_runner.pool_resource2 => pdec!("2.5"),
+        )
+    );
+}
+
+fn is_constant_product_pool_emitter(event_type_identifier: &EventTypeIdentifier) -> bool {
+    match event_type_identifier.0 {
+        Emitter::Method(node_id, ModuleId::Main) => {
+            node_id.entity_type() == Some(EntityType::GlobalConstantProductPool)
+        }
+        _ => false,
+    }
+}
+
+struct TestEnvironment {
+    test_runner: DefaultTestRunner,
+
+    pool_component_address: ComponentAddress,
+    pool_unit_resource_address: ResourceAddress,
+
+    pool_resource1: ResourceAddress,
+    pool_resource2: ResourceAddress,
+
+    account_public_key: PublicKey,
+    account_component_address: ComponentAddress,
+}
+
+impl TestEnvironment {
+    pub fn new(fee_rate: Decimal) -> Self {
+        let mut test_runner = TestRunnerBuilder::new().without_trace().build();
+        let (public_key, _, account) = test_runner.new_account(false);
+        let virtual_signature_badge = NonFungibleGlobalId::from_public_key(&public_key);
+
+        let pool_resource1 = test_runner.create_freely_mintable_and_burnable_fungible_resource(
+            OwnerRole::None,
+            None,
+            18,
+            account,
+        );
+        let pool_resource2 = test_runner.create_freely_mintable_and_burnable_fungible_resource(
+            OwnerRole::None,
+            None,
+            18,
+            account,
+        );
+
+        let (pool_component, pool_unit_resource) = {
+            let manifest = ManifestBuilder::new()
+                .create_constant_product_pool(
+                    OwnerRole::None,
+                    rule!(require(virtual_signature_badge)),
+                    (pool_resource1, pool_resource2),
+                    fee_rate,
+                )
+                .build();
+            let receipt = test_runner.execute_manifest_ignoring_fee(manifest, vec![]);
+            let commit_result = receipt.expect_commit_success();
+
+            (
+                commit_result
+                    .new_component_addresses()
+                    .iter()
+                    .copied()
+                    .find(|address| {
+                        address.as_node_id().entity_type()
+                            == Some(EntityType::GlobalConstantProductPool)
+                    })
+                    .unwrap(),
+                commit_result.new_resource_addresses()[0],
+            )
+        };
+
+        Self {
+            test_runner,
+            pool_component_address: pool_component,
+            pool_unit_resource_address: pool_unit_resource,
+            pool_resource1,
+            pool_resource2,
+            account_public_key: public_key.into(),
+            account_component_address: account,
+        }
+    }
+
+    fn contribute(&mut self, amount1: Decimal, amount2: Decimal) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .mint_fungible(self.pool_resource1, amount1)
+            .mint_fungible(self.pool_resource2, amount2)
+            .take_all_from_worktop(self.pool_resource1, "resource_1")
+            .take_all_from_worktop(self.pool_resource2, "resource_2")
+            .contribute_to_constant_product_pool(
+                self.pool_component_address,
+                "resource_1",
+                "resource_2",
+            )
+            .try_deposit_entire_worktop_or_abort(self.account_component_address, None)
+            .build();
+        self.execute_manifest(manifest, false)
+    }
+
+    fn redeem(&mut self, amount: Decimal) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(
+                self.account_component_address,
+                self.pool_unit_resource_address,
+                amount,
+            )
+            .take_all_from_worktop(self.pool_unit_resource_address, "pool_units")
+            .redeem_from_constant_product_pool(self.pool_component_address, "pool_units")
+            .try_deposit_entire_worktop_or_abort(self.account_component_address, None)
+            .build();
+        self.execute_manifest(manifest, true)
+    }
+
+    fn swap(
+        &mut self,
+        input_resource_address: ResourceAddress,
+        input_amount: Decimal,
+        minimum_output_amount: Decimal,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .mint_fungible(input_resource_address, input_amount)
+            .take_all_from_worktop(input_resource_address, "input")
+            .swap_with_constant_product_pool(
+                self.pool_component_address,
+                "input",
+                minimum_output_amount,
+            )
+            .try_deposit_entire_worktop_or_abort(self.account_component_address, None)
+            .build();
+        self.execute_manifest(manifest, false)
+    }
+
+    fn swap_for_exact_output(
+        &mut self,
+        input_resource_address: ResourceAddress,
+        maximum_input_amount: Decimal,
+        output_amount: Decimal,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .mint_fungible(input_resource_address, maximum_input_amount)
+            .take_all_from_worktop(input_resource_address, "input")
+            .swap_with_constant_product_pool_for_exact_output(
+                self.pool_component_address,
+                "input",
+                output_amount,
+            )
+            .try_deposit_entire_worktop_or_abort(self.account_component_address, None)
+            .build();
+        self.execute_manifest(manifest, false)
+    }
+
+    fn set_fee_rate(&mut self, fee_rate: Decimal, sign: bool) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .set_constant_product_pool_fee_rate(self.pool_component_address, fee_rate)
+            .build();
+        self.execute_manifest(manifest, sign)
+    }
+
+    fn get_fee_rate(&mut self) -> ConstantProductPoolGetFeeRateOutput {
+        self.call_getter(
+            CONSTANT_PRODUCT_POOL_GET_FEE_RATE_IDENT,
+            ConstantProductPoolGetFeeRateManifestInput,
+        )
+    }
+
+    fn get_oracle_state(&mut self) -> ConstantProductPoolGetOracleStateOutput {
+        self.call_getter(
+            CONSTANT_PRODUCT_POOL_GET_ORACLE_STATE_IDENT,
+            ConstantProductPoolGetOracleStateManifestInput,
+        )
+    }
+
+    fn get_vault_amounts(&mut self) -> ConstantProductPoolGetVaultAmountsOutput {
+        self.call_getter(
+            CONSTANT_PRODUCT_POOL_GET_VAULT_AMOUNTS_IDENT,
+            ConstantProductPoolGetVaultAmountsManifestInput,
+        )
+    }
+
+    fn get_swap_output_amount(
+        &mut self,
+        input_resource_address: ResourceAddress,
+        input_amount: Decimal,
+    ) -> ConstantProductPoolGetSwapOutputAmountOutput {
+        self.call_getter(
+            CONSTANT_PRODUCT_POOL_GET_SWAP_OUTPUT_AMOUNT_IDENT,
+            ConstantProductPoolGetSwapOutputAmountManifestInput {
+                input_resource_address,
+                input_amount,
+            },
+        )
+    }
+
+    fn get_swap_input_amount(
+        &mut self,
+        output_resource_address: ResourceAddress,
+        output_amount: Decimal,
+    ) -> ConstantProductPoolGetSwapInputAmountOutput {
+        self.call_getter(
+            CONSTANT_PRODUCT_POOL_GET_SWAP_INPUT_AMOUNT_IDENT,
+            ConstantProductPoolGetSwapInputAmountManifestInput {
+                output_resource_address,
+                output_amount,
+            },
+        )
+    }
+
+    fn call_getter<I, O>(&mut self, method_name: &str, input: I) -> O
+    where
+        I: ManifestEncode,
+        O: ScryptoDecode,
+    {
+        let manifest = ManifestBuilder::new()
+            .call_method(self.pool_component_address, method_name, input)
+            .build();
+        self.execute_manifest(manifest, false)
+            .expect_commit_success()
+            .output(1)
+    }
+
+    fn account_balance(&mut self, resource_address: ResourceAddress) -> Decimal {
+        self.test_runner
+            .get_component_balance(self.account_component_address, resource_address)
+    }
+
+    fn execute_manifest(
+        &mut self,
+        manifest: TransactionManifestV1,
+        sign: bool,
+    ) -> TransactionReceipt {
+        self.test_runner
+            .execute_manifest_ignoring_fee(manifest, self.initial_proofs(sign))
+    }
+
+    fn initial_proofs(&self, sign: bool) -> Vec<NonFungibleGlobalId> {
+        if sign {
+            vec![NonFungibleGlobalId::from_public_key(
+                &self.account_public_key,
+            )]
+        } else {
+            vec![]
+        }
+    }
+}
@@ -0,0 +1,452 @@
+use radix_engine::blueprints::consensus_manager::{
+    ReceiveRedelegationEvent, RedelegateEvent, ValidatorError, REDELEGATION_COOLDOWN_EPOCHS,
+};
+use radix_engine::errors::{ApplicationError, RuntimeError};
+use radix_engine::transaction::TransactionReceipt;
+use radix_engine::types::*;
+use radix_engine::vm::NoExtension;
+use radix_engine_interface::blueprints::consensus_manager::*;
+use radix_engine_stores::memory_db::InMemorySubstateDatabase;
+use scrypto_unit::*;
+use transaction::prelude::*;
+
+#[test]
+fn stake_units_can_be_redelegated_to_another_validator() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+
+    // Act
+    let receipt = env.redelegate(env.source_validator, dec!(500), env.target_validator);
+
+    // Assert
+    receipt.expect_commit_success();
+    assert_eq!(env.stake_unit_balance(env.source_validator), dec!(9500));
+    assert_eq!(env.stake_unit_balance(env.target_validator), dec!(500));
+    assert_eq!(env.stake_xrd_amount(env.source_validator), dec!(9500));
+    assert_eq!(env.stake_xrd_amount(env.target_validator), dec!(500));
+}
+
+#[test]
+fn redelegating_emits_events_on_both_validators() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+
+    // Act
+    let receipt = env.redelegate(env.source_validator, dec!(500), env.target_validator);
+
+    // Assert
+    let commit_result = receipt.expect_commit_success();
+    assert_eq!(
+        env.test_runner
+            .extract_events_of_type::<RedelegateEvent>(commit_result),
+        vec![RedelegateEvent {
+            target_validator: env.target_validator,
+            stake_units: dec!(500),
+            xrd_redelegated: dec!(500),
+        }]
+    );
+    assert_eq!(
+        env.test_runner
+            .extract_events_of_type::<ReceiveRedelegationEvent>(commit_result),
+        vec![ReceiveRedelegationEvent {
+            source_validator: env.source_validator,
+            xrd_received: dec!(500),
+        }]
+    );
+}
+
+#[test]
+fn redelegating_more_than_the_epoch_limit_fails() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    env.redelegate(env.source_validator, dec!(600), env.target_validator)
+        .expect_commit_success();
+
+    // Act
+    let receipt = env.redelegate(env.source_validator, dec!(500), env.target_validator);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::ValidatorError(
+                ValidatorError::RedelegationEpochLimitExceeded { .. }
+            ))
+        )
+    });
+}
+
+#[test]
+fn epoch_limit_is_shared_between_stakers() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let (other_public_key, _, other_account) = env.test_runner.new_account(false);
+    env.transfer_stake_units(env.source_validator, dec!(1000), other_account);
+    env.redelegate(env.source_validator, dec!(1000), env.target_validator)
+        .expect_commit_success();
+
+    // Act
+    let receipt = env.redelegate_from(
+        other_account,
+        other_public_key,
+        env.source_validator,
+        dec!(100),
+        env.target_validator,
+    );
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::ValidatorError(
+                ValidatorError::RedelegationEpochLimitExceeded { .. }
+            ))
+        )
+    });
+}
+
+#[test]
+fn epoch_limit_is_reset_in_the_next_epoch() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    env.redelegate(env.source_validator, dec!(1000), env.target_validator)
+        .expect_commit_success();
+
+    // Act
+    let current_epoch = env.test_runner.get_current_epoch();
+    env.test_runner
+        .set_current_epoch(current_epoch.next().unwrap());
+    let receipt = env.redelegate(env.source_validator, dec!(500), env.target_validator);
+
+    // Assert
+    receipt.expect_commit_success();
+    assert_eq!(env.stake_xrd_amount(env.source_validator), dec!(8500));
+}
+
+#[test]
+fn redelegated_stake_can_not_be_redelegated_again_during_the_cooldown() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    env.redelegate(env.source_validator, dec!(500), env.target_validator)
+        .expect_commit_success();
+
+    // Act
+    let current_epoch = env.test_runner.get_current_epoch();
+    env.test_runner.set_current_epoch(
+        current_epoch
+            .after(REDELEGATION_COOLDOWN_EPOCHS - 1)
+            .unwrap(),
+    );
+    let receipt = env.redelegate(env.target_validator, dec!(50), env.source_validator);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::ValidatorError(
+                ValidatorError::RedelegatedStakeInCooldown { .. }
+            ))
+        )
+    });
+}
+
+#[test]
+fn redelegated_stake_can_be_redelegated_again_after_the_cooldown() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    env.redelegate(env.source_validator, dec!(500), env.target_validator)
+        .expect_commit_success();
+
+    // Act
+    let current_epoch = env.test_runner.get_current_epoch();
+    env.test_runner
+        .set_current_epoch(current_epoch.after(REDELEGATION_COOLDOWN_EPOCHS).unwrap());
+    let receipt = env.redelegate(env.target_validator, dec!(50), env.source_validator);
+
+    // Assert
+    receipt.expect_commit_success();
+    assert_eq!(env.stake_xrd_amount(env.target_validator), dec!(450));
+    assert_eq!(env.stake_xrd_amount(env.source_validator), dec!(9550));
+}
+
+#[test]
+fn unstaking_redelegated_stake_releases_it_from_the_cooldown() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let (other_public_key, _, other_account) = env.test_runner.new_account(false);
+    env.stake_from(
+        other_account,
+        other_public_key,
+        env.target_validator,
+        dec!(100),
+    );
+    env.redelegate(env.source_validator, dec!(900), env.target_validator)
+        .expect_commit_success();
+    env.unstake(env.target_validator, dec!(900))
+        .expect_commit_success();
+
+    // Act
+    let current_epoch = env.test_runner.get_current_epoch();
+    env.test_runner
+        .set_current_epoch(current_epoch.next().unwrap());
+    let receipt = env.redelegate_from(
+        other_account,
+        other_public_key,
+        env.target_validator,
+        dec!(10),
+        env.source_validator,
+    );
+
+    // Assert
+    receipt.expect_commit_success();
+    assert_eq!(env.stake_xrd_amount(env.target_validator), dec!(90));
+}
+
+#[test]
+fn redelegating_to_the_same_validator_fails() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+
+    // Act
+    let receipt = env.redelegate(env.source_validator, dec!(500), env.source_validator);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::ValidatorError(
+                ValidatorError::InvalidRedelegationTarget
+            ))
+        )
+    });
+}
+
+#[test]
+fn redelegating_to_a_component_which_is_not_a_validator_fails() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+
+    // Act
+    let receipt = env.redelegate(env.source_validator, dec!(500), env.account);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::ValidatorError(
+                ValidatorError::InvalidRedelegationTarget
+            ))
+        )
+    });
+}
+
+#[test]
+fn redelegating_to_a_validator_not_accepting_delegated_stake_fails() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let (pub_key, _, account) = env.test_runner.new_account(false);
+    let target_validator = env.test_runner.new_validator_with_pub_key(pub_key, account);
+
+    // Act
+    let receipt = env.redelegate(env.source_validator, dec!(500), target_validator);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::ValidatorError(
+                ValidatorError::ValidatorIsNotAcceptingDelegatedStake
+            ))
+        )
+    });
+}
+
+#[test]
+fn receive_redelegation_can_not_be_called_directly() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .get_free_xrd_from_faucet()
+        .take_from_worktop(XRD, dec!(100), "xrd")
+        .with_bucket("xrd", |builder, bucket| {
+            builder.call_method(
+                env.target_validator,
+                VALIDATOR_RECEIVE_REDELEGATION_IDENT,
+                manifest_args!(bucket, env.source_validator),
+            )
+        })
+        .try_deposit_entire_worktop_or_abort(env.account, None)
+        .build();
+    let receipt = env.test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    receipt.expect_auth_assertion_failure();
+}
+
+struct TestEnvironment {
+    test_runner: TestRunner<NoExtension, InMemorySubstateDatabase>,
+    public_key: Secp256k1PublicKey,
+    account: ComponentAddress,
+    source_validator: ComponentAddress,
+    target_validator: ComponentAddress,
+}
+
+impl TestEnvironment {
+    fn new() -> Self {
+        let mut test_runner = TestRunnerBuilder::new().without_trace().build();
+        let (public_key, _, account) = test_runner.new_account(false);
+        let source_validator = test_runner.new_staked_validator_with_pub_key(public_key, account);
+        let target_validator = test_runner.new_validator_with_pub_key(public_key, account);
+
+        for validator in [source_validator, target_validator] {
+            let manifest = ManifestBuilder::new()
+                .lock_fee_from_faucet()
+                .create_proof_from_account_of_non_fungibles(
+                    account,
+                    VALIDATOR_OWNER_BADGE,
+                    [NonFungibleLocalId::bytes(validator.as_node_id().0).unwrap()],
+                )
+                .call_method(
+                    validator,
+                    VALIDATOR_UPDATE_ACCEPT_DELEGATED_STAKE_IDENT,
+                    ValidatorUpdateAcceptDelegatedStakeInput {
+                        accept_delegated_stake: true,
+                    },
+                )
+                .build();
+            test_runner
+                .execute_manifest(
+                    manifest,
+                    vec![NonFungibleGlobalId::from_public_key(&public_key)],
+                )
+                .expect_commit_success();
+        }
+
+        Self {
+            test_runner,
+            public_key,
+            account,
+            source_validator,
+            target_validator,
+        }
+    }
+
+    fn redelegate(
+        &mut self,
+        validator: ComponentAddress,
+        stake_units: Decimal,
+        target_validator: ComponentAddress,
+    ) -> TransactionReceipt {
+        self.redelegate_from(
+            self.account,
+            self.public_key,
+            validator,
+            stake_units,
+            target_validator,
+        )
+    }
+
+    fn redelegate_from(
+        &mut self,
+        account: ComponentAddress,
+        public_key: Secp256k1PublicKey,
+        validator: ComponentAddress,
+        stake_units: Decimal,
+        target_validator: ComponentAddress,
+    ) -> TransactionReceipt {
+        let stake_unit_resource = self.stake_unit_resource(validator);
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_from_account(account, stake_unit_resource, stake_units)
+            .take_all_from_worktop(stake_unit_resource, "stake_units")
+            .redelegate_validator_stake(validator, "stake_units", target_validator)
+            .try_deposit_entire_worktop_or_abort(account, None)
+            .build();
+        self.test_runner.execute_manifest(
+            manifest,
+            vec![NonFungibleGlobalId::from_public_key(&public_key)],
+        )
+    }
+
+    fn stake_from(
+        &mut self,
+        account: ComponentAddress,
+        public_key: Secp256k1PublicKey,
+        validator: ComponentAddress,
+        xrd_amount: Decimal,
+    ) {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .get_free_xrd_from_faucet()
+            .take_from_worktop(XRD, xrd_amount, "xrd")
+            .stake_validator(validator, "xrd")
+            .try_deposit_entire_worktop_or_abort(account, None)
+            .build();
+        self.test_runner
+            .execute_manifest(
+                manifest,
+                vec![NonFungibleGlobalId::from_public_key(&public_key)],
+            )
+            .expect_commit_success();
+    }
+
+    fn unstake(&mut self, validator: ComponentAddress, stake_units: Decimal) -> TransactionReceipt {
+        let stake_unit_resource = self.stake_unit_resource(validator);
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_from_account(self.account, stake_unit_resource, stake_units)
+            .take_all_from_worktop(stake_unit_resource, "stake_units")
+            .unstake_validator(validator, "stake_units")
+            .try_deposit_entire_worktop_or_abort(self.account, None)
+            .build();
+        self.test_runner.execute_manifest(
+            manifest,
+            vec![NonFungibleGlobalId::from_public_key(&self.public_key)],
+        )
+    }
+
+    fn transfer_stake_units(
+        &mut self,
+        validator: ComponentAddress,
+        stake_units: Decimal,
+        to_account: ComponentAddress,
+    ) {
+        let stake_unit_resource = self.stake_unit_resource(validator);
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_from_account(self.account, stake_unit_resource, stake_units)
+            .try_deposit_entire_worktop_or_abort(to_account, None)
+            .build();
+        self.test_runner
+            .execute_manifest(
+                manifest,
+                vec![NonFungibleGlobalId::from_public_key(&self.public_key)],
+            )
+            .expect_commit_success();
+    }
+
+    fn stake_unit_resource(&mut self, validator: ComponentAddress) -> ResourceAddress {
+        self.test_runner
+            .get_validator_info(validator)
+            .stake_unit_resource
+    }
+
+    fn stake_unit_balance(&mut self, validator: ComponentAddress) -> Decimal {
+        let stake_unit_resource = self.stake_unit_resource(validator);
+        self.test_runner
+            .get_component_balance(self.account, stake_unit_resource)
+    }
+
+    fn stake_xrd_amount(&mut self, validator: ComponentAddress) -> Decimal {
+        let stake_xrd_vault_id = self
+            .test_runner
+            .get_validator_info(validator)
+            .stake_xrd_vault_id;
+        self.test_runner
+            .inspect_vault_balance(stake_xrd_vault_id.0)
+            .unwrap()
+    }
+}
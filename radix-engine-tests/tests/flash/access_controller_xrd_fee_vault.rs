@@ -0,0 +1,88 @@
+use scrypto_test::prelude::*;
+use scrypto_unit::*;
+
+#[test]
+fn database_is_consistent_before_and_after_access_controller_xrd_fee_vault_protocol_update() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new()
+        .without_access_controller_xrd_fee_vault()
+        .without_trace()
+        .build();
+
+    let (_, _, account) = test_runner.new_account(false);
+    new_access_controller(&mut test_runner, account);
+    test_runner.check_database();
+
+    // Act
+    {
+        let substate_db = test_runner.substate_db_mut();
+        let state_updates = generate_access_controller_xrd_fee_vault_state_updates(substate_db);
+        let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+        substate_db.commit(&db_updates);
+    }
+
+    // Assert
+    test_runner.check_database();
+}
+
+#[test]
+fn recovery_fees_can_only_be_contributed_after_protocol_update() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new()
+        .without_access_controller_xrd_fee_vault()
+        .without_trace()
+        .build();
+
+    let (_, _, account) = test_runner.new_account(false);
+    let access_controller = new_access_controller(&mut test_runner, account);
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .get_free_xrd_from_faucet()
+        .take_from_worktop(XRD, dec!(100), "contribution")
+        .contribute_access_controller_recovery_fee(access_controller, "contribution")
+        .try_deposit_entire_worktop_or_abort(account, None)
+        .build();
+
+    // Act
+    let receipt = test_runner.execute_manifest(manifest.clone(), vec![]);
+
+    // Assert
+    receipt.expect_commit_failure();
+
+    // Act
+    {
+        let substate_db = test_runner.substate_db_mut();
+        let state_updates = generate_access_controller_xrd_fee_vault_state_updates(substate_db);
+        let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+        substate_db.commit(&db_updates);
+    }
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    receipt.expect_commit_success();
+    test_runner.check_database();
+}
+
+fn new_access_controller(
+    test_runner: &mut DefaultTestRunner,
+    account: ComponentAddress,
+) -> ComponentAddress {
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .get_free_xrd_from_faucet()
+        .take_from_worktop(XRD, dec!(1), "controlled_asset")
+        .create_access_controller(
+            "controlled_asset",
+            rule!(allow_all),
+            rule!(allow_all),
+            rule!(allow_all),
+            None,
+        )
+        .try_deposit_entire_worktop_or_abort(account, None)
+        .build();
+    test_runner
+        .execute_manifest(manifest, vec![])
+        .expect_commit_success()
+        .new_component_addresses()[0]
+}
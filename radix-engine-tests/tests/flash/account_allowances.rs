@@ -0,0 +1,72 @@
+use scrypto_test::prelude::*;
+use scrypto_unit::*;
+
+#[test]
+fn database_is_consistent_before_and_after_account_allowances_protocol_update() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new()
+        .without_account_allowances()
+        .without_trace()
+        .build();
+
+    let (_, _, account) = test_runner.new_account(false);
+    test_runner.create_fungible_resource(dec!(200), 18, account);
+    test_runner.check_database();
+
+    // Act
+    {
+        let substate_db = test_runner.substate_db_mut();
+        let state_updates = generate_account_allowances_state_updates(substate_db);
+        let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+        substate_db.commit(&db_updates);
+    }
+
+    // Assert
+    test_runner.check_database();
+}
+
+#[test]
+fn account_allowances_can_only_be_set_after_protocol_update() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new()
+        .without_account_allowances()
+        .without_trace()
+        .build();
+
+    let (pk, _, account) = test_runner.new_account(false);
+    let fungible = test_runner.create_fungible_resource(dec!(200), 18, account);
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .set_account_allowance(
+            account,
+            AllowanceGrantee::Badge(ResourceOrNonFungible::Resource(XRD)),
+            fungible,
+            Some(dec!(100)),
+            None,
+            None,
+        )
+        .build();
+
+    // Act
+    let receipt = test_runner.execute_manifest(
+        manifest.clone(),
+        vec![NonFungibleGlobalId::from_public_key(&pk)],
+    );
+
+    // Assert
+    receipt.expect_commit_failure();
+
+    // Act
+    {
+        let substate_db = test_runner.substate_db_mut();
+        let state_updates = generate_account_allowances_state_updates(substate_db);
+        let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+        substate_db.commit(&db_updates);
+    }
+    let receipt =
+        test_runner.execute_manifest(manifest, vec![NonFungibleGlobalId::from_public_key(&pk)]);
+
+    // Assert
+    receipt.expect_commit_success();
+}
@@ -0,0 +1,86 @@
+use radix_engine_tests::common::PackageLoader;
+use scrypto_test::prelude::*;
+use scrypto_unit::*;
+
+#[test]
+fn database_is_consistent_before_and_after_component_royalty_splits_protocol_update() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new()
+        .without_component_royalty_splits()
+        .without_trace()
+        .build();
+
+    create_component_with_royalty_enabled(&mut test_runner);
+    test_runner.check_database();
+
+    // Act
+    {
+        let substate_db = test_runner.substate_db_mut();
+        let state_updates = generate_component_royalty_splits_state_updates(substate_db);
+        let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+        substate_db.commit(&db_updates);
+    }
+
+    // Assert
+    test_runner.check_database();
+}
+
+#[test]
+fn component_royalty_splits_can_only_be_set_after_protocol_update() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new()
+        .without_component_royalty_splits()
+        .without_trace()
+        .build();
+    let (_, _, account) = test_runner.new_allocated_account();
+    let component_address = create_component_with_royalty_enabled(&mut test_runner);
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .set_component_royalty_split(
+            component_address,
+            "paid_method",
+            indexmap!(account.into() => dec!("0.5")),
+        )
+        .build();
+
+    // Act
+    let receipt = test_runner.execute_manifest(manifest.clone(), vec![]);
+
+    // Assert
+    receipt.expect_commit_failure();
+
+    // Act
+    {
+        let substate_db = test_runner.substate_db_mut();
+        let state_updates = generate_component_royalty_splits_state_updates(substate_db);
+        let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+        substate_db.commit(&db_updates);
+    }
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    receipt.expect_commit_success();
+    test_runner.check_database();
+}
+
+fn create_component_with_royalty_enabled(test_runner: &mut DefaultTestRunner) -> ComponentAddress {
+    let package_address = test_runner.publish_package(
+        PackageLoader::get("royalty"),
+        Default::default(),
+        OwnerRole::Fixed(rule!(allow_all)),
+    );
+    let receipt = test_runner.execute_manifest(
+        ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .call_function(
+                package_address,
+                "RoyaltyTest",
+                "create_component_with_royalty_enabled",
+                manifest_args!(),
+            )
+            .build(),
+        vec![],
+    );
+    receipt.expect_commit_success().output(1)
+}
@@ -0,0 +1,69 @@
+use scrypto_test::prelude::*;
+use scrypto_unit::*;
+
+#[test]
+fn database_is_consistent_before_and_after_fungible_vesting_protocol_update() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new()
+        .without_fungible_vesting()
+        .without_trace()
+        .build();
+    test_runner.check_database();
+
+    // Act
+    {
+        let substate_db = test_runner.substate_db_mut();
+        let state_updates = generate_fungible_vesting_state_updates(substate_db);
+        let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+        substate_db.commit(&db_updates);
+    }
+
+    // Assert
+    test_runner.check_database();
+}
+
+#[test]
+fn fungible_vesting_can_only_be_created_after_protocol_update() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new()
+        .without_fungible_vesting()
+        .without_trace()
+        .build();
+    let now = test_runner.get_current_time(TimePrecision::Second);
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .get_free_xrd_from_faucet()
+        .take_all_from_worktop(XRD, "bucket")
+        .create_fungible_vesting(
+            OwnerRole::None,
+            "bucket",
+            VestingSchedule {
+                start: now,
+                cliff: None,
+                end: now.add_seconds(1000).unwrap(),
+            },
+            rule!(allow_all),
+            None,
+        )
+        .build();
+
+    // Act
+    let receipt = test_runner.execute_manifest(manifest.clone(), vec![]);
+
+    // Assert
+    receipt.expect_commit_failure();
+
+    // Act
+    {
+        let substate_db = test_runner.substate_db_mut();
+        let state_updates = generate_fungible_vesting_state_updates(substate_db);
+        let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+        substate_db.commit(&db_updates);
+    }
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    receipt.expect_commit_success();
+    test_runner.check_database();
+}
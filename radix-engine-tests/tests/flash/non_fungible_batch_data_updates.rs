@@ -0,0 +1,72 @@
+use scrypto_test::prelude::*;
+use scrypto_unit::*;
+
+#[test]
+fn database_is_consistent_before_and_after_non_fungible_batch_data_updates_protocol_update() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new()
+        .without_non_fungible_batch_data_updates()
+        .without_trace()
+        .build();
+    let (_, _, account) = test_runner.new_allocated_account();
+    test_runner.create_non_fungible_resource(account);
+    test_runner.check_database();
+
+    // Act
+    {
+        let substate_db = test_runner.substate_db_mut();
+        let state_updates = generate_non_fungible_batch_data_updates_state_updates(substate_db);
+        let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+        substate_db.commit(&db_updates);
+    }
+
+    // Assert
+    test_runner.check_database();
+}
+
+#[test]
+fn non_fungible_data_can_only_be_batch_updated_after_protocol_update() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new()
+        .without_non_fungible_batch_data_updates()
+        .without_trace()
+        .build();
+    let (_, _, account) = test_runner.new_allocated_account();
+    let resource_address = test_runner.create_non_fungible_resource_with_roles(
+        NonFungibleResourceRoles {
+            non_fungible_data_update_roles: non_fungible_data_update_roles! {
+                non_fungible_data_updater => rule!(allow_all);
+                non_fungible_data_updater_updater => rule!(deny_all);
+            },
+            ..Default::default()
+        },
+        account,
+    );
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .batch_update_non_fungible_data(
+            resource_address,
+            Vec::<(NonFungibleLocalId, String, ())>::new(),
+        )
+        .build();
+
+    // Act
+    let receipt = test_runner.execute_manifest(manifest.clone(), vec![]);
+
+    // Assert
+    receipt.expect_commit_failure();
+
+    // Act
+    {
+        let substate_db = test_runner.substate_db_mut();
+        let state_updates = generate_non_fungible_batch_data_updates_state_updates(substate_db);
+        let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+        substate_db.commit(&db_updates);
+    }
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    receipt.expect_commit_success();
+    test_runner.check_database();
+}
@@ -0,0 +1,74 @@
+use radix_engine_tests::common::PackageLoader;
+use scrypto_test::prelude::*;
+use scrypto_unit::*;
+
+#[test]
+fn database_is_consistent_before_and_after_package_royalty_splits_protocol_update() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new()
+        .without_package_royalty_splits()
+        .without_trace()
+        .build();
+
+    test_runner.publish_package(
+        PackageLoader::get("royalty"),
+        Default::default(),
+        OwnerRole::Fixed(rule!(allow_all)),
+    );
+    test_runner.check_database();
+
+    // Act
+    {
+        let substate_db = test_runner.substate_db_mut();
+        let state_updates = generate_package_royalty_splits_state_updates(substate_db);
+        let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+        substate_db.commit(&db_updates);
+    }
+
+    // Assert
+    test_runner.check_database();
+}
+
+#[test]
+fn royalty_splits_can_only_be_set_after_protocol_update() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new()
+        .without_package_royalty_splits()
+        .without_trace()
+        .build();
+    let (_, _, account) = test_runner.new_allocated_account();
+
+    let package_address = test_runner.publish_package(
+        PackageLoader::get("royalty"),
+        Default::default(),
+        OwnerRole::Fixed(rule!(allow_all)),
+    );
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .set_package_royalty_split(
+            package_address,
+            "RoyaltyTest",
+            indexmap!(account.into() => dec!("0.5")),
+        )
+        .build();
+
+    // Act
+    let receipt = test_runner.execute_manifest(manifest.clone(), vec![]);
+
+    // Assert
+    receipt.expect_commit_failure();
+
+    // Act
+    {
+        let substate_db = test_runner.substate_db_mut();
+        let state_updates = generate_package_royalty_splits_state_updates(substate_db);
+        let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+        substate_db.commit(&db_updates);
+    }
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    receipt.expect_commit_success();
+    test_runner.check_database();
+}
@@ -401,3 +401,94 @@ fn single_sided_contributions_to_multi_resource_pool_are_only_allowed_after_prot
         dec!(200)
     );
 }
+
+#[test]
+fn database_is_consistent_before_and_after_constant_product_pool_protocol_update() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new()
+        .without_pools_v1_2()
+        .without_trace()
+        .build();
+
+    let (pk, _, account) = test_runner.new_account(false);
+    let virtual_signature_badge = NonFungibleGlobalId::from_public_key(&pk);
+
+    let fungible1 = test_runner.create_fungible_resource(dec!(200), 18, account);
+    let fungible2 = test_runner.create_fungible_resource(dec!(200), 18, account);
+
+    test_runner
+        .execute_manifest(
+            ManifestBuilder::new()
+                .lock_fee_from_faucet()
+                .call_function(
+                    POOL_PACKAGE,
+                    TWO_RESOURCE_POOL_BLUEPRINT_IDENT,
+                    TWO_RESOURCE_POOL_INSTANTIATE_IDENT,
+                    TwoResourcePoolInstantiateManifestInput {
+                        owner_role: OwnerRole::None,
+                        pool_manager_rule: rule!(require(virtual_signature_badge)),
+                        resource_addresses: (fungible1, fungible2),
+                        address_reservation: None,
+                    },
+                )
+                .try_deposit_entire_worktop_or_abort(account, None)
+                .build(),
+            vec![],
+        )
+        .expect_commit_success();
+    test_runner.check_database();
+
+    // Act
+    {
+        let substate_db = test_runner.substate_db_mut();
+        let state_updates = generate_pools_v1_2_state_updates(substate_db);
+        let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+        substate_db.commit(&db_updates);
+    }
+
+    // Assert
+    test_runner.check_database();
+}
+
+#[test]
+fn constant_product_pool_can_only_be_instantiated_after_protocol_update() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new()
+        .without_pools_v1_2()
+        .without_trace()
+        .build();
+
+    let (_, _, account) = test_runner.new_account(false);
+
+    let fungible1 = test_runner.create_fungible_resource(dec!(200), 18, account);
+    let fungible2 = test_runner.create_fungible_resource(dec!(200), 18, account);
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_constant_product_pool(
+            OwnerRole::None,
+            rule!(allow_all),
+            (fungible1, fungible2),
+            dec!("0.003"),
+        )
+        .try_deposit_entire_worktop_or_abort(account, None)
+        .build();
+
+    // Act
+    let receipt = test_runner.execute_manifest(manifest.clone(), vec![]);
+
+    // Assert
+    receipt.expect_commit_failure();
+
+    // Act
+    {
+        let substate_db = test_runner.substate_db_mut();
+        let state_updates = generate_pools_v1_2_state_updates(substate_db);
+        let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+        substate_db.commit(&db_updates);
+    }
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    receipt.expect_commit_success();
+}
@@ -0,0 +1,52 @@
+use scrypto_test::prelude::*;
+use scrypto_unit::*;
+
+#[test]
+fn database_is_consistent_before_and_after_secp256r1_signatures_protocol_update() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new()
+        .without_secp256r1_signatures()
+        .without_trace()
+        .build();
+    test_runner.new_account(false);
+    test_runner.check_database();
+
+    // Act
+    test_runner
+        .update_to_secp256r1_signatures()
+        .expect_commit_success();
+
+    // Assert
+    test_runner.check_database();
+}
+
+#[test]
+fn secp256r1_virtual_account_can_only_be_created_after_protocol_update() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new()
+        .without_secp256r1_signatures()
+        .without_trace()
+        .build();
+    let public_key = Secp256r1PrivateKey::from_u64(1).unwrap().public_key();
+    let account = ComponentAddress::virtual_account_from_public_key(&public_key);
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .get_free_xrd_from_faucet()
+        .try_deposit_entire_worktop_or_abort(account, None)
+        .build();
+
+    // Act
+    let receipt = test_runner.execute_manifest(manifest.clone(), vec![]);
+
+    // Assert
+    assert!(!receipt.is_commit_success());
+
+    // Act
+    test_runner
+        .update_to_secp256r1_signatures()
+        .expect_commit_success();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    receipt.expect_commit_success();
+}
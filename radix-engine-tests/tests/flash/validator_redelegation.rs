@@ -0,0 +1,96 @@
+use scrypto_test::prelude::*;
+use scrypto_unit::*;
+
+#[test]
+fn database_is_consistent_before_and_after_validator_redelegation_protocol_update() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new()
+        .without_validator_redelegation()
+        .without_trace()
+        .build();
+
+    let (pub_key, _, account) = test_runner.new_account(false);
+    test_runner.new_staked_validator_with_pub_key(pub_key, account);
+    test_runner.check_database();
+
+    // Act
+    {
+        let substate_db = test_runner.substate_db_mut();
+        let state_updates = generate_validator_redelegation_state_updates(substate_db);
+        let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+        substate_db.commit(&db_updates);
+    }
+
+    // Assert
+    test_runner.check_database();
+}
+
+#[test]
+fn stake_can_only_be_redelegated_after_protocol_update() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new()
+        .without_validator_redelegation()
+        .without_trace()
+        .build();
+
+    let (pub_key, _, account) = test_runner.new_account(false);
+    let source_validator = test_runner.new_staked_validator_with_pub_key(pub_key, account);
+    let target_validator = test_runner.new_validator_with_pub_key(pub_key, account);
+    let stake_unit_resource = test_runner
+        .get_validator_info(source_validator)
+        .stake_unit_resource;
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_proof_from_account_of_non_fungibles(
+            account,
+            VALIDATOR_OWNER_BADGE,
+            [NonFungibleLocalId::bytes(target_validator.as_node_id().0).unwrap()],
+        )
+        .call_method(
+            target_validator,
+            VALIDATOR_UPDATE_ACCEPT_DELEGATED_STAKE_IDENT,
+            ValidatorUpdateAcceptDelegatedStakeInput {
+                accept_delegated_stake: true,
+            },
+        )
+        .build();
+    test_runner
+        .execute_manifest(
+            manifest,
+            vec![NonFungibleGlobalId::from_public_key(&pub_key)],
+        )
+        .expect_commit_success();
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(account, stake_unit_resource, dec!(100))
+        .take_all_from_worktop(stake_unit_resource, "stake_units")
+        .redelegate_validator_stake(source_validator, "stake_units", target_validator)
+        .try_deposit_entire_worktop_or_abort(account, None)
+        .build();
+
+    // Act
+    let receipt = test_runner.execute_manifest(
+        manifest.clone(),
+        vec![NonFungibleGlobalId::from_public_key(&pub_key)],
+    );
+
+    // Assert
+    receipt.expect_commit_failure();
+
+    // Act
+    {
+        let substate_db = test_runner.substate_db_mut();
+        let state_updates = generate_validator_redelegation_state_updates(substate_db);
+        let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+        substate_db.commit(&db_updates);
+    }
+    let receipt = test_runner.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&pub_key)],
+    );
+
+    // Assert
+    receipt.expect_commit_success();
+}
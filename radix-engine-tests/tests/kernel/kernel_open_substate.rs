@@ -45,6 +45,8 @@ pub fn test_open_substate_of_invisible_package_address() {
     let mut system = SystemConfig {
         blueprint_cache: NonIterMap::new(),
         auth_cache: NonIterMap::new(),
+        blueprint_version_cache: NonIterMap::new(),
+        package_blueprint_upgrades: false,
         schema_cache: NonIterMap::new(),
         callback_obj: Vm {
             scrypto_vm: &scrypto_vm,
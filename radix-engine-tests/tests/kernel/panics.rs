@@ -138,6 +138,7 @@ impl KernelSubstateApi<SystemLockData> for MockKernel {
         _: &NodeId,
         _: PartitionNumber,
         _: u32,
+        _: Option<&SortedKey>,
     ) -> Result<Vec<(SortedKey, IndexedScryptoValue)>, RuntimeError> {
         panic1!()
     }
@@ -147,6 +148,7 @@ impl KernelSubstateApi<SystemLockData> for MockKernel {
         _: &NodeId,
         _: PartitionNumber,
         _: u32,
+        _: ScanKeysOrder,
     ) -> Result<Vec<SubstateKey>, RuntimeError> {
         panic1!()
     }
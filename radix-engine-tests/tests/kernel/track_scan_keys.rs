@@ -0,0 +1,136 @@
+use radix_engine::track::*;
+use radix_engine::types::*;
+use radix_engine_store_interface::db_key_mapper::{
+    DatabaseKeyMapper, MapKey, MappedCommittableSubstateDatabase, SpreadPrefixKeyMapper,
+};
+use radix_engine_stores::memory_db::InMemorySubstateDatabase;
+
+fn map_key(key: u8) -> SubstateKey {
+    SubstateKey::Map(scrypto_encode(&key).unwrap())
+}
+
+fn setup_database(node_id: &NodeId, db_keys: &[u8]) -> InMemorySubstateDatabase {
+    let mut database = InMemorySubstateDatabase::standard();
+    for key in db_keys {
+        database.put_mapped::<SpreadPrefixKeyMapper, _>(
+            node_id,
+            MAIN_BASE_PARTITION,
+            &map_key(*key),
+            &(),
+        );
+    }
+    database
+}
+
+fn scan_keys(
+    track: &mut Track<InMemorySubstateDatabase, SpreadPrefixKeyMapper>,
+    node_id: &NodeId,
+    count: u32,
+    order: ScanKeysOrder,
+) -> (Vec<SubstateKey>, usize) {
+    let mut db_reads = 0usize;
+    let keys = track
+        .scan_keys::<MapKey, (), _>(node_id, MAIN_BASE_PARTITION, count, order, &mut |access| {
+            if let IOAccess::ReadFromDb(..) = access {
+                db_reads += 1;
+            }
+            Ok(())
+        })
+        .unwrap();
+    (keys, db_reads)
+}
+
+fn set_tracked_key(
+    track: &mut Track<InMemorySubstateDatabase, SpreadPrefixKeyMapper>,
+    node_id: &NodeId,
+    key: u8,
+) {
+    track
+        .set_substate::<(), _>(
+            *node_id,
+            MAIN_BASE_PARTITION,
+            map_key(key),
+            IndexedScryptoValue::from_typed(&()),
+            &mut |_| Ok(()),
+        )
+        .unwrap();
+}
+
+#[test]
+fn tracked_first_scan_returns_tracked_keys_without_reading_the_database_when_count_is_filled() {
+    // Arrange
+    let node_id = NodeId::new(
+        EntityType::InternalKeyValueStore as u8,
+        &[1u8; NodeId::RID_LENGTH],
+    );
+    let database = setup_database(&node_id, &[1, 2, 3]);
+    let mut track = Track::<InMemorySubstateDatabase, SpreadPrefixKeyMapper>::new(&database);
+    set_tracked_key(&mut track, &node_id, 4);
+    set_tracked_key(&mut track, &node_id, 5);
+
+    // Act
+    let (keys, db_reads) = scan_keys(&mut track, &node_id, 2, ScanKeysOrder::TrackedFirst);
+
+    // Assert
+    let mut expected = vec![map_key(4), map_key(5)];
+    expected.sort_by_key(SpreadPrefixKeyMapper::to_db_sort_key);
+    assert_eq!(keys, expected);
+    assert_eq!(db_reads, 0);
+}
+
+#[test]
+fn tracked_first_scan_returns_tracked_keys_before_database_keys() {
+    // Arrange
+    let node_id = NodeId::new(
+        EntityType::InternalKeyValueStore as u8,
+        &[1u8; NodeId::RID_LENGTH],
+    );
+    let database = setup_database(&node_id, &[1, 2, 3]);
+    let mut track = Track::<InMemorySubstateDatabase, SpreadPrefixKeyMapper>::new(&database);
+    set_tracked_key(&mut track, &node_id, 4);
+    set_tracked_key(&mut track, &node_id, 2);
+
+    // Act
+    let (keys, _db_reads) = scan_keys(&mut track, &node_id, 10, ScanKeysOrder::TrackedFirst);
+
+    // Assert
+    let mut tracked = vec![map_key(4), map_key(2)];
+    tracked.sort_by_key(SpreadPrefixKeyMapper::to_db_sort_key);
+    let mut untracked = vec![map_key(1), map_key(3)];
+    untracked.sort_by_key(SpreadPrefixKeyMapper::to_db_sort_key);
+    assert_eq!(keys, [tracked, untracked].concat());
+}
+
+#[test]
+fn sorted_scan_merges_tracked_and_database_keys_in_database_order() {
+    // Arrange
+    let node_id = NodeId::new(
+        EntityType::InternalKeyValueStore as u8,
+        &[1u8; NodeId::RID_LENGTH],
+    );
+    let database = setup_database(&node_id, &[1, 2, 3]);
+    let mut track = Track::<InMemorySubstateDatabase, SpreadPrefixKeyMapper>::new(&database);
+    set_tracked_key(&mut track, &node_id, 4);
+    set_tracked_key(&mut track, &node_id, 2);
+    let mut expected = vec![map_key(1), map_key(2), map_key(3), map_key(4)];
+    expected.sort_by_key(SpreadPrefixKeyMapper::to_db_sort_key);
+
+    // Act
+    let (first, _) = scan_keys(
+        &mut track,
+        &node_id,
+        2,
+        ScanKeysOrder::Sorted { after: None },
+    );
+    let (rest, _) = scan_keys(
+        &mut track,
+        &node_id,
+        10,
+        ScanKeysOrder::Sorted {
+            after: first.last(),
+        },
+    );
+
+    // Assert
+    assert_eq!([first, rest].concat(), expected);
+}
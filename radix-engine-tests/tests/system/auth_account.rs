@@ -1,9 +1,10 @@
-use radix_engine_tests::common::*;
 use radix_engine::errors::{RuntimeError, SystemError};
+use radix_engine::transaction::{CostingParameters, ExecutionConfig};
 use radix_engine::types::*;
 use radix_engine_interface::api::node_modules::auth::AuthAddresses;
 use radix_engine_interface::api::AttachedModuleId;
 use radix_engine_interface::rule;
+use radix_engine_tests::common::*;
 use scrypto_unit::*;
 use transaction::prelude::*;
 
@@ -361,3 +362,35 @@ fn can_call_function_requiring_count_of_zero() {
     // Assert
     receipt.expect_commit_success();
 }
+
+#[test]
+fn can_withdraw_from_secp256r1_virtual_account_with_notarized_transaction() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new().without_trace().build();
+    let (_, sk, account) = test_runner.new_secp256r1_virtual_account();
+    let (_, _, other_account) = test_runner.new_account(false);
+    let network = NetworkDefinition::simulator();
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee(account, 500)
+        .withdraw_from_account(account, XRD, 1)
+        .try_deposit_entire_worktop_or_abort(other_account, None)
+        .build();
+    let transaction = create_notarized_transaction_advanced(
+        &mut test_runner,
+        &network,
+        manifest,
+        vec![],
+        &sk,
+        true,
+    );
+    let receipt = test_runner.execute_transaction(
+        validate_notarized_transaction(&network, &transaction).get_executable(),
+        CostingParameters::default(),
+        ExecutionConfig::for_notarized_transaction(network.clone()),
+    );
+
+    // Assert
+    receipt.expect_commit_success();
+}
@@ -0,0 +1,242 @@
+use radix_engine::errors::{ApplicationError, RuntimeError, SystemError};
+use radix_engine::system::attached_modules::royalty::*;
+use radix_engine::transaction::TransactionReceipt;
+use radix_engine::types::*;
+use radix_engine_tests::common::*;
+use scrypto_unit::*;
+use transaction::prelude::*;
+
+const METHOD: &str = "paid_method";
+
+#[test]
+fn component_royalties_are_split_between_recipients() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let (_, _, recipient1) = env.test_runner.new_allocated_account();
+    let (_, _, recipient2) = env.test_runner.new_allocated_account();
+    let recipients = indexmap!(
+        recipient1.into() => dec!("0.25"),
+        recipient2.into() => dec!("0.5"),
+    );
+    let receipt = env.set_split(METHOD, recipients.clone());
+    let commit_result = receipt.expect_commit_success();
+    assert_eq!(
+        env.test_runner
+            .extract_events_of_type::<SetComponentRoyaltySplitEvent>(commit_result),
+        vec![SetComponentRoyaltySplitEvent {
+            method: METHOD.to_string(),
+            recipients,
+        }]
+    );
+
+    // Act
+    let receipt = env.call_paid_method();
+
+    // Assert
+    receipt.expect_commit_success();
+    assert_eq!(
+        env.test_runner
+            .inspect_component_royalty(env.component_address),
+        dec!("0.25")
+    );
+
+    // Act
+    let balance1 = env.test_runner.get_component_balance(recipient1, XRD);
+    let balance2 = env.test_runner.get_component_balance(recipient2, XRD);
+    env.claim_share(recipient1.into()).expect_commit_success();
+    let receipt = env.claim_share(recipient2.into());
+
+    // Assert
+    assert_eq!(
+        env.test_runner
+            .extract_events_of_type::<ClaimComponentRoyaltyShareEvent>(
+                receipt.expect_commit_success()
+            ),
+        vec![ClaimComponentRoyaltyShareEvent {
+            recipient: recipient2.into(),
+            amount: dec!("0.5"),
+        }]
+    );
+    assert_eq!(
+        env.test_runner.get_component_balance(recipient1, XRD),
+        balance1.checked_add(dec!("0.25")).unwrap()
+    );
+    assert_eq!(
+        env.test_runner.get_component_balance(recipient2, XRD),
+        balance2.checked_add(dec!("0.5")).unwrap()
+    );
+}
+
+#[test]
+fn component_royalties_of_other_methods_are_not_split() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let (_, _, recipient) = env.test_runner.new_allocated_account();
+    env.set_split("paid_method_panic", indexmap!(recipient.into() => dec!(1)))
+        .expect_commit_success();
+
+    // Act
+    let receipt = env.call_paid_method();
+
+    // Assert
+    receipt.expect_commit_success();
+    assert_eq!(
+        env.test_runner
+            .inspect_component_royalty(env.component_address),
+        dec!(1)
+    );
+}
+
+#[test]
+fn component_royalty_shares_exceeding_one_are_rejected() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let (_, _, recipient1) = env.test_runner.new_allocated_account();
+    let (_, _, recipient2) = env.test_runner.new_allocated_account();
+
+    // Act
+    let receipt = env.set_split(
+        METHOD,
+        indexmap!(
+            recipient1.into() => dec!("0.5"),
+            recipient2.into() => dec!("0.75"),
+        ),
+    );
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::ComponentRoyaltyError(
+                ComponentRoyaltyError::RoyaltySplitSharesExceedOne(..)
+            ))
+        )
+    });
+}
+
+#[test]
+fn locked_component_royalty_split_cannot_be_changed() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let (_, _, recipient) = env.test_runner.new_allocated_account();
+    env.set_split(METHOD, indexmap!(recipient.into() => dec!("0.5")))
+        .expect_commit_success();
+    env.lock_split(METHOD).expect_commit_success();
+
+    // Act
+    let receipt = env.set_split(METHOD, indexmap!());
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::SystemError(SystemError::KeyValueEntryLocked)
+        )
+    });
+}
+
+#[test]
+fn locking_missing_component_royalty_split_fails() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+
+    // Act
+    let receipt = env.lock_split(METHOD);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::ComponentRoyaltyError(
+                ComponentRoyaltyError::RoyaltySplitNotFound(..)
+            ))
+        )
+    });
+}
+
+#[test]
+fn claiming_component_royalty_share_of_unknown_recipient_fails() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let (_, _, recipient) = env.test_runner.new_allocated_account();
+
+    // Act
+    let receipt = env.claim_share(recipient.into());
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::ComponentRoyaltyError(
+                ComponentRoyaltyError::RoyaltySplitRecipientNotFound(..)
+            ))
+        )
+    });
+}
+
+struct TestEnvironment {
+    test_runner: DefaultTestRunner,
+    component_address: ComponentAddress,
+}
+
+impl TestEnvironment {
+    fn new() -> Self {
+        let mut test_runner = TestRunnerBuilder::new().without_trace().build();
+        let package_address = test_runner.publish_package(
+            PackageLoader::get("royalty"),
+            Default::default(),
+            OwnerRole::None,
+        );
+
+        let receipt = test_runner.execute_manifest_ignoring_fee(
+            ManifestBuilder::new()
+                .call_function(
+                    package_address,
+                    "RoyaltyTest",
+                    "create_component_with_royalty_enabled",
+                    manifest_args!(),
+                )
+                .build(),
+            [],
+        );
+        let component_address: ComponentAddress = receipt.expect_commit(true).output(0);
+
+        Self {
+            test_runner,
+            component_address,
+        }
+    }
+
+    fn set_split(
+        &mut self,
+        method: &str,
+        recipients: IndexMap<GlobalAddress, Decimal>,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .set_component_royalty_split(self.component_address, method, recipients)
+            .build();
+        self.test_runner.execute_manifest_ignoring_fee(manifest, [])
+    }
+
+    fn lock_split(&mut self, method: &str) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .lock_component_royalty_split(self.component_address, method)
+            .build();
+        self.test_runner.execute_manifest_ignoring_fee(manifest, [])
+    }
+
+    fn call_paid_method(&mut self) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .call_method(self.component_address, METHOD, manifest_args!())
+            .build();
+        self.test_runner.execute_manifest(manifest, [])
+    }
+
+    fn claim_share(&mut self, recipient: GlobalAddress) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .claim_component_royalty_share(self.component_address, recipient)
+            .build();
+        self.test_runner.execute_manifest_ignoring_fee(manifest, [])
+    }
+}
@@ -660,3 +660,347 @@ fn test_crypto_scrypto_bls12381_v1_fast_aggregate_verify_costing() {
         }
     }
 }
+
+fn crypto_scrypto_blake2b_256_hash(
+    runner: &mut TestRunner<NoExtension, InMemorySubstateDatabase>,
+    package_address: PackageAddress,
+    data: Vec<u8>,
+) -> TransactionReceiptV1 {
+    runner.execute_manifest(
+        ManifestBuilder::new()
+            .lock_fee(runner.faucet_component(), 500u32)
+            .call_function(
+                package_address,
+                "CryptoScryptoV2",
+                "blake2b_256_hash",
+                manifest_args!(data),
+            )
+            .build(),
+        vec![],
+    )
+}
+
+fn crypto_scrypto_ed25519_verify(
+    runner: &mut TestRunner<NoExtension, InMemorySubstateDatabase>,
+    package_address: PackageAddress,
+    msg: Vec<u8>,
+    pub_key: Ed25519PublicKey,
+    signature: Ed25519Signature,
+) -> TransactionReceiptV1 {
+    runner.execute_manifest(
+        ManifestBuilder::new()
+            .lock_fee(runner.faucet_component(), 500u32)
+            .call_function(
+                package_address,
+                "CryptoScryptoV2",
+                "ed25519_verify",
+                manifest_args!(msg, pub_key, signature),
+            )
+            .build(),
+        vec![],
+    )
+}
+
+fn crypto_scrypto_secp256k1_ecdsa_verify(
+    runner: &mut TestRunner<NoExtension, InMemorySubstateDatabase>,
+    package_address: PackageAddress,
+    hash: Hash,
+    pub_key: Secp256k1PublicKey,
+    signature: Secp256k1Signature,
+) -> TransactionReceiptV1 {
+    runner.execute_manifest(
+        ManifestBuilder::new()
+            .lock_fee(runner.faucet_component(), 500u32)
+            .call_function(
+                package_address,
+                "CryptoScryptoV2",
+                "secp256k1_ecdsa_verify",
+                manifest_args!(hash, pub_key, signature),
+            )
+            .build(),
+        vec![],
+    )
+}
+
+fn crypto_scrypto_secp256k1_ecdsa_verify_and_key_recover(
+    runner: &mut TestRunner<NoExtension, InMemorySubstateDatabase>,
+    package_address: PackageAddress,
+    hash: Hash,
+    signature: Secp256k1Signature,
+) -> TransactionReceiptV1 {
+    runner.execute_manifest(
+        ManifestBuilder::new()
+            .lock_fee(runner.faucet_component(), 500u32)
+            .call_function(
+                package_address,
+                "CryptoScryptoV2",
+                "secp256k1_ecdsa_verify_and_key_recover",
+                manifest_args!(hash, signature),
+            )
+            .build(),
+        vec![],
+    )
+}
+
+#[test]
+fn test_crypto_scrypto_blake2b_256_hash() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new().build();
+
+    let package_address =
+        test_runner.publish_package_simple(PackageLoader::get("crypto_scrypto_v2"));
+
+    let data1 = b"Hello Radix".to_vec();
+    let data2: Vec<u8> = vec![]; // empty data
+
+    // Act
+    let data1_hash: Hash = get_output!(crypto_scrypto_blake2b_256_hash(
+        &mut test_runner,
+        package_address,
+        data1
+    ));
+    // Assert
+    assert_eq!(
+        data1_hash,
+        Hash::from_str("48f1bd08444b5e713db9e14caac2faae71836786ac94d645b00679728202a935").unwrap()
+    );
+
+    // Act
+    let data2_hash: Hash = get_output!(crypto_scrypto_blake2b_256_hash(
+        &mut test_runner,
+        package_address,
+        data2
+    ));
+    // Assert
+    assert_eq!(
+        data2_hash,
+        Hash::from_str("0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8").unwrap()
+    );
+}
+
+#[test]
+fn test_crypto_scrypto_verify_ed25519() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new().build();
+
+    let package_address =
+        test_runner.publish_package_simple(PackageLoader::get("crypto_scrypto_v2"));
+
+    // Test vector 2 from RFC 8032, section 7.1
+    let msg1 = vec![0x72u8];
+    let pk = "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c";
+    let msg1_signature = "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00";
+
+    let pk = Ed25519PublicKey::from_str(pk).unwrap();
+    let msg1_signature = Ed25519Signature::from_str(msg1_signature).unwrap();
+
+    // Act
+    let msg1_verify: bool = get_output!(crypto_scrypto_ed25519_verify(
+        &mut test_runner,
+        package_address,
+        msg1,
+        pk,
+        msg1_signature,
+    ));
+
+    // Assert
+    assert!(msg1_verify);
+
+    // Arrange
+    let msg2 = b"ExpectFailureTest".to_vec();
+
+    // Act
+    let msg2_verify: bool = get_output!(crypto_scrypto_ed25519_verify(
+        &mut test_runner,
+        package_address,
+        msg2,
+        pk,
+        msg1_signature,
+    ));
+
+    // Assert
+    assert!(!msg2_verify);
+}
+
+#[test]
+fn test_crypto_scrypto_verify_secp256k1_ecdsa() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new().build();
+
+    let package_address =
+        test_runner.publish_package_simple(PackageLoader::get("crypto_scrypto_v2"));
+
+    let secret_key = Secp256k1PrivateKey::from_u64(1).unwrap();
+    let public_key = secret_key.public_key();
+    let hash1 = hash("Test");
+    let hash1_signature = secret_key.sign(&hash1);
+
+    // Act
+    let hash1_verify: bool = get_output!(crypto_scrypto_secp256k1_ecdsa_verify(
+        &mut test_runner,
+        package_address,
+        hash1,
+        public_key,
+        hash1_signature,
+    ));
+
+    // Assert
+    assert!(hash1_verify);
+
+    // Arrange
+    let hash2 = hash("ExpectFailureTest");
+
+    // Act
+    let hash2_verify: bool = get_output!(crypto_scrypto_secp256k1_ecdsa_verify(
+        &mut test_runner,
+        package_address,
+        hash2,
+        public_key,
+        hash1_signature,
+    ));
+
+    // Assert
+    assert!(!hash2_verify);
+}
+
+#[test]
+fn test_crypto_scrypto_secp256k1_ecdsa_verify_and_key_recover() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new().build();
+
+    let package_address =
+        test_runner.publish_package_simple(PackageLoader::get("crypto_scrypto_v2"));
+
+    let secret_key = Secp256k1PrivateKey::from_u64(1).unwrap();
+    let public_key = secret_key.public_key();
+    let hash1 = hash("Test");
+    let hash1_signature = secret_key.sign(&hash1);
+
+    // Act
+    let recovered_key: Secp256k1PublicKey =
+        get_output!(crypto_scrypto_secp256k1_ecdsa_verify_and_key_recover(
+            &mut test_runner,
+            package_address,
+            hash1,
+            hash1_signature,
+        ));
+
+    // Assert
+    assert_eq!(recovered_key, public_key);
+
+    // Arrange
+    let mut invalid_signature = hash1_signature;
+    invalid_signature.0[0] = 0xff; // invalid recovery id
+
+    // Act
+    let error = get_failure!(crypto_scrypto_secp256k1_ecdsa_verify_and_key_recover(
+        &mut test_runner,
+        package_address,
+        hash1,
+        invalid_signature,
+    ));
+
+    // Assert
+    assert!(error.contains("Secp256k1KeyRecoveryError"));
+}
+
+#[test]
+fn test_crypto_scrypto_blake2b_256_costing() {
+    let mut test_runner = TestRunnerBuilder::new().build();
+
+    let package_address =
+        test_runner.publish_package_simple(PackageLoader::get("crypto_scrypto_v2"));
+
+    for size in [
+        100usize,
+        200,
+        500,
+        1024,
+        10 * 1024,
+        20 * 1024,
+        50 * 1024,
+        100 * 1024,
+        200 * 1024,
+        500 * 1024,
+        900 * 1024,
+    ] {
+        let data = vec![0u8; size];
+        let _hash = crypto_scrypto_blake2b_256_hash(&mut test_runner, package_address, data);
+    }
+}
+
+#[test]
+fn test_crypto_scrypto_verify_ed25519_costing() {
+    let mut test_runner = TestRunnerBuilder::new().build();
+
+    let package_address =
+        test_runner.publish_package_simple(PackageLoader::get("crypto_scrypto_v2"));
+
+    let secret_key = Ed25519PrivateKey::from_u64(1).unwrap();
+    let public_key = secret_key.public_key();
+
+    for size in [
+        100usize,
+        200,
+        500,
+        1024,
+        10 * 1024,
+        20 * 1024,
+        50 * 1024,
+        100 * 1024,
+        200 * 1024,
+        500 * 1024,
+        900 * 1024,
+    ] {
+        let data = vec![0u8; size];
+        let signature = secret_key.sign_message(&data);
+        let verified: bool = get_output!(crypto_scrypto_ed25519_verify(
+            &mut test_runner,
+            package_address,
+            data,
+            public_key,
+            signature,
+        ));
+        assert!(verified);
+    }
+}
+
+#[test]
+fn test_crypto_scrypto_verify_secp256k1_ecdsa_costing() {
+    let mut test_runner = TestRunnerBuilder::new().build();
+
+    let package_address =
+        test_runner.publish_package_simple(PackageLoader::get("crypto_scrypto_v2"));
+
+    let secret_key = Secp256k1PrivateKey::from_u64(1).unwrap();
+    let public_key = secret_key.public_key();
+    let hash = hash("Test");
+    let signature = secret_key.sign(&hash);
+
+    let _ = crypto_scrypto_secp256k1_ecdsa_verify(
+        &mut test_runner,
+        package_address,
+        hash,
+        public_key,
+        signature,
+    );
+}
+
+#[test]
+fn test_crypto_scrypto_secp256k1_ecdsa_verify_and_key_recover_costing() {
+    let mut test_runner = TestRunnerBuilder::new().build();
+
+    let package_address =
+        test_runner.publish_package_simple(PackageLoader::get("crypto_scrypto_v2"));
+
+    let secret_key = Secp256k1PrivateKey::from_u64(1).unwrap();
+    let hash = hash("Test");
+    let signature = secret_key.sign(&hash);
+
+    let _ = crypto_scrypto_secp256k1_ecdsa_verify_and_key_recover(
+        &mut test_runner,
+        package_address,
+        hash,
+        signature,
+    );
+}
@@ -0,0 +1,237 @@
+use radix_engine::blueprints::package::PackageError;
+use radix_engine::errors::{ApplicationError, RuntimeError};
+use radix_engine::types::*;
+use radix_engine::vm::NoExtension;
+use radix_engine_stores::memory_db::InMemorySubstateDatabase;
+use radix_engine_tests::common::*;
+use scrypto_unit::*;
+use transaction::prelude::*;
+
+fn instantiate_leaderboard(
+    test_runner: &mut TestRunner<NoExtension, InMemorySubstateDatabase>,
+    scores: Vec<(String, u16)>,
+) -> ComponentAddress {
+    let package_address =
+        test_runner.publish_package_simple(PackageLoader::get("indexed_collections"));
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(
+            package_address,
+            "Leaderboard",
+            "new_with_scores",
+            manifest_args!(scores),
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+    receipt.expect_commit_success().new_component_addresses()[0]
+}
+
+fn call_leaderboard<T: ScryptoDecode>(
+    test_runner: &mut TestRunner<NoExtension, InMemorySubstateDatabase>,
+    component_address: ComponentAddress,
+    method_name: &str,
+    args: impl ResolvableArguments,
+) -> T {
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(component_address, method_name, args)
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+    receipt.expect_commit_success().output(1)
+}
+
+fn scores(scores: &[(&str, u16)]) -> Vec<(String, u16)> {
+    scores
+        .iter()
+        .map(|(player, score)| (player.to_string(), *score))
+        .collect()
+}
+
+#[test]
+fn can_insert_get_and_remove_iterable_map_entries() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new().build();
+    let component_address = instantiate_leaderboard(&mut test_runner, vec![]);
+
+    // Act
+    call_leaderboard::<()>(
+        &mut test_runner,
+        component_address,
+        "record",
+        manifest_args!("alice", 10u16),
+    );
+    let score: Option<u16> = call_leaderboard(
+        &mut test_runner,
+        component_address,
+        "score",
+        manifest_args!("alice"),
+    );
+    let removed: Option<u16> = call_leaderboard(
+        &mut test_runner,
+        component_address,
+        "remove",
+        manifest_args!("alice"),
+    );
+    let score_after_removal: Option<u16> = call_leaderboard(
+        &mut test_runner,
+        component_address,
+        "score",
+        manifest_args!("alice"),
+    );
+
+    // Assert
+    assert_eq!(score, Some(10u16));
+    assert_eq!(removed, Some(10u16));
+    assert_eq!(score_after_removal, None);
+}
+
+#[test]
+fn paginated_iterable_map_scan_lists_every_entry_once() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new().build();
+    let component_address = instantiate_leaderboard(
+        &mut test_runner,
+        scores(&[("a", 1), ("b", 2), ("c", 3), ("d", 4), ("e", 5)]),
+    );
+
+    // Act
+    let mut players: Vec<(String, u16)> = call_leaderboard(
+        &mut test_runner,
+        component_address,
+        "players",
+        manifest_args!(2u32),
+    );
+
+    // Assert
+    players.sort();
+    assert_eq!(
+        players,
+        scores(&[("a", 1), ("b", 2), ("c", 3), ("d", 4), ("e", 5)])
+    );
+}
+
+#[test]
+fn paginated_iterable_map_scan_lists_every_entry_once_after_entries_are_touched() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new().build();
+    let component_address = instantiate_leaderboard(
+        &mut test_runner,
+        scores(&[("a", 1), ("b", 2), ("c", 3), ("d", 4), ("e", 5), ("f", 6)]),
+    );
+
+    // Act
+    // Reading and inserting entries earlier in the transaction loads them into the track, so the
+    // scan has to merge them with the entries which are only in the database
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(component_address, "score", manifest_args!("b"))
+        .call_method(component_address, "score", manifest_args!("e"))
+        .call_method(component_address, "record", manifest_args!("g", 7u16))
+        .call_method(component_address, "players", manifest_args!(2u32))
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    let mut players: Vec<(String, u16)> = receipt.expect_commit_success().output(4);
+    players.sort();
+    assert_eq!(
+        players,
+        scores(&[
+            ("a", 1),
+            ("b", 2),
+            ("c", 3),
+            ("d", 4),
+            ("e", 5),
+            ("f", 6),
+            ("g", 7)
+        ])
+    );
+}
+
+#[test]
+fn sorted_map_scan_lists_entries_in_sort_prefix_order() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new().build();
+    let component_address = instantiate_leaderboard(
+        &mut test_runner,
+        scores(&[("a", 30), ("b", 10), ("c", 50), ("d", 20), ("e", 40)]),
+    );
+    call_leaderboard::<()>(
+        &mut test_runner,
+        component_address,
+        "record",
+        manifest_args!("b", 60u16),
+    );
+
+    // Act
+    let top: Vec<(String, u16)> = call_leaderboard(
+        &mut test_runner,
+        component_address,
+        "top",
+        manifest_args!(2u32),
+    );
+    let ranking: Vec<(String, u16)> = call_leaderboard(
+        &mut test_runner,
+        component_address,
+        "ranking",
+        manifest_args!(2u32),
+    );
+
+    // Assert
+    assert_eq!(top, scores(&[("b", 60), ("c", 50)]));
+    assert_eq!(
+        ranking,
+        scores(&[("b", 60), ("c", 50), ("e", 40), ("a", 30), ("d", 20)])
+    );
+}
+
+#[test]
+fn sorted_map_scan_of_owned_component_lists_entries_in_sort_prefix_order() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new().build();
+    let package_address =
+        test_runner.publish_package_simple(PackageLoader::get("indexed_collections"));
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(
+            package_address,
+            "Leaderboard",
+            "new_with_scores",
+            manifest_args!(scores(&[("a", 3), ("b", 1), ("c", 2), ("a", 4)])),
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+    let component_address = receipt.expect_commit_success().new_component_addresses()[0];
+    let ranking: Vec<(String, u16)> = call_leaderboard(
+        &mut test_runner,
+        component_address,
+        "ranking",
+        manifest_args!(1u32),
+    );
+
+    // Assert
+    assert_eq!(ranking, scores(&[("a", 4), ("c", 2), ("b", 1)]));
+}
+
+#[test]
+fn publishing_indexed_collections_before_protocol_update_should_fail() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new()
+        .without_indexed_collections_update()
+        .build();
+
+    // Act
+    let receipt = test_runner.try_publish_package(PackageLoader::get("indexed_collections"));
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::PackageError(
+                PackageError::InvalidWasm(..)
+            ))
+        )
+    });
+}
@@ -530,3 +530,158 @@ fn nested_kv_stores_works() {
     // Assert
     receipt.expect_commit_success();
 }
+
+fn instantiate_iterable_kv_store(
+    test_runner: &mut DefaultTestRunner,
+    count: u32,
+) -> ComponentAddress {
+    let package_address = test_runner.publish_package_simple(PackageLoader::get("kv_store"));
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(
+            package_address,
+            "IterableKeyValueStore",
+            "new",
+            manifest_args!(count),
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+    receipt.expect_commit_success().new_component_addresses()[0]
+}
+
+fn kv_store_entries(keys: impl Iterator<Item = u32>) -> Vec<(u32, String)> {
+    keys.map(|i| (i, i.to_string())).collect()
+}
+
+#[test]
+fn paginated_kv_store_scan_lists_every_entry_once() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new().build();
+    let component = instantiate_iterable_kv_store(&mut test_runner, 10);
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(component, "remove", manifest_args!(3u32))
+        .build();
+    test_runner
+        .execute_manifest(manifest, vec![])
+        .expect_commit_success();
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(component, "entries", manifest_args!(3u32))
+        .call_method(component, "count", manifest_args!(u32::MAX))
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    let result = receipt.expect_commit_success();
+    let mut entries: Vec<(u32, String)> = result.output(1);
+    entries.sort();
+    assert_eq!(entries, kv_store_entries((0..10u32).filter(|i| *i != 3)));
+    let count: u32 = result.output(2);
+    assert_eq!(count, 9);
+}
+
+#[test]
+fn paginated_kv_store_scan_lists_every_entry_once_after_entries_are_touched() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new().build();
+    let component = instantiate_iterable_kv_store(&mut test_runner, 10);
+
+    // Act
+    // Reading, inserting and removing entries earlier in the transaction loads them into the
+    // track, so the scan has to merge them with the entries which are only in the database
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(component, "get", manifest_args!(2u32))
+        .call_method(component, "get", manifest_args!(7u32))
+        .call_method(component, "get", manifest_args!(20u32))
+        .call_method(component, "insert", manifest_args!(10u32, "10"))
+        .call_method(component, "remove", manifest_args!(5u32))
+        .call_method(component, "entries", manifest_args!(2u32))
+        .call_method(component, "count", manifest_args!(u32::MAX))
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    let result = receipt.expect_commit_success();
+    let mut entries: Vec<(u32, String)> = result.output(6);
+    entries.sort();
+    assert_eq!(entries, kv_store_entries((0..11u32).filter(|i| *i != 5)));
+    let count: u32 = result.output(7);
+    assert_eq!(count, 10);
+}
+
+#[test]
+fn kv_store_scan_returns_no_cursor_after_the_last_page() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new().build();
+    let component = instantiate_iterable_kv_store(&mut test_runner, 9);
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(component, "pages", manifest_args!(3u32))
+        .call_method(component, "pages", manifest_args!(9u32))
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    let result = receipt.expect_commit_success();
+    let pages: Vec<Vec<(u32, String)>> = result.output(1);
+    assert_eq!(
+        pages.iter().map(|page| page.len()).collect::<Vec<_>>(),
+        vec![3, 3, 3]
+    );
+    let pages: Vec<Vec<(u32, String)>> = result.output(2);
+    assert_eq!(
+        pages.iter().map(|page| page.len()).collect::<Vec<_>>(),
+        vec![9]
+    );
+}
+
+#[test]
+fn kv_store_count_is_bounded_by_the_limit() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new().build();
+    let component = instantiate_iterable_kv_store(&mut test_runner, 10);
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(component, "count", manifest_args!(4u32))
+        .call_method(component, "count", manifest_args!(0u32))
+        .call_method(component, "count", manifest_args!(10u32))
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    let result = receipt.expect_commit_success();
+    assert_eq!(result.output::<u32>(1), 4);
+    assert_eq!(result.output::<u32>(2), 0);
+    assert_eq!(result.output::<u32>(3), 10);
+}
+
+#[test]
+fn can_count_entries_of_local_kv_store() {
+    // Arrange
+    let mut test_runner = TestRunnerBuilder::new().build();
+    let package_address = test_runner.publish_package_simple(PackageLoader::get("kv_store"));
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(
+            package_address,
+            "IterableKeyValueStore",
+            "count_local",
+            manifest_args!(5u32, u32::MAX),
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    let count: u32 = receipt.expect_commit_success().output(1);
+    assert_eq!(count, 4);
+}
@@ -0,0 +1,255 @@
+use radix_engine::types::*;
+use radix_engine_interface::api::node_modules::metadata::{
+    MetadataValue, UncheckedOrigin, UncheckedUrl,
+};
+use radix_engine_queries::query::*;
+use scrypto_unit::*;
+use transaction::prelude::*;
+
+#[test]
+fn two_way_dapp_definition_link_has_no_findings() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let resource = env.create_resource();
+    env.set_dapp_definition_metadata(
+        "claimed_entities",
+        MetadataValue::GlobalAddressArray(vec![resource.into()]),
+    );
+    env.set_resource_metadata(
+        resource,
+        "dapp_definitions",
+        MetadataValue::GlobalAddressArray(vec![env.dapp_definition.into()]),
+    );
+
+    // Act
+    let dapp_definition_findings = env.verify(env.dapp_definition.into());
+    let resource_findings = env.verify(resource.into());
+
+    // Assert
+    assert_eq!(dapp_definition_findings, vec![]);
+    assert_eq!(resource_findings, vec![]);
+}
+
+#[test]
+fn entity_not_claimed_by_its_dapp_definition_is_reported() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let resource = env.create_resource();
+    env.set_resource_metadata(
+        resource,
+        "dapp_definitions",
+        MetadataValue::GlobalAddressArray(vec![env.dapp_definition.into()]),
+    );
+
+    // Act
+    let findings = env.verify(resource.into());
+
+    // Assert
+    assert_eq!(
+        findings,
+        vec![MetadataFinding {
+            key: "dapp_definitions".to_string(),
+            issue: MetadataIssue::NotClaimedByDappDefinition(env.dapp_definition.into()),
+        }]
+    );
+}
+
+#[test]
+fn claimed_entity_which_does_not_link_back_is_reported() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let resource = env.create_resource();
+    env.set_dapp_definition_metadata(
+        "claimed_entities",
+        MetadataValue::GlobalAddressArray(vec![resource.into()]),
+    );
+
+    // Act
+    let findings = env.verify(env.dapp_definition.into());
+
+    // Assert
+    assert_eq!(
+        findings,
+        vec![MetadataFinding {
+            key: "claimed_entities".to_string(),
+            issue: MetadataIssue::ClaimedEntityDoesNotLinkBack(resource.into()),
+        }]
+    );
+}
+
+#[test]
+fn link_to_account_which_is_not_a_dapp_definition_is_reported() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let (_, _, account) = env.test_runner.new_allocated_account();
+    let resource = env.create_resource();
+    env.set_resource_metadata(
+        resource,
+        "dapp_definitions",
+        MetadataValue::GlobalAddressArray(vec![account.into()]),
+    );
+
+    // Act
+    let findings = env.verify(resource.into());
+
+    // Assert
+    assert_eq!(
+        findings,
+        vec![MetadataFinding {
+            key: "dapp_definitions".to_string(),
+            issue: MetadataIssue::LinkedEntityIsNotADappDefinition(account.into()),
+        }]
+    );
+}
+
+#[test]
+fn values_of_unexpected_type_and_insecure_urls_are_reported() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let resource = env.create_resource();
+    env.set_resource_metadata(resource, "name", MetadataValue::U8(1));
+    env.set_resource_metadata(
+        resource,
+        "icon_url",
+        MetadataValue::String("https://www.example.com/icon.png".to_string()),
+    );
+    env.set_resource_metadata(
+        resource,
+        "info_url",
+        MetadataValue::Url(UncheckedUrl::of("http://www.example.com")),
+    );
+
+    // Act
+    let findings = env.verify(resource.into());
+
+    // Assert
+    assert_eq!(
+        findings,
+        vec![
+            MetadataFinding {
+                key: "icon_url".to_string(),
+                issue: MetadataIssue::UnexpectedType {
+                    expected: StandardMetadataType::Url,
+                },
+            },
+            MetadataFinding {
+                key: "info_url".to_string(),
+                issue: MetadataIssue::InsecureUrl("http://www.example.com".to_string()),
+            },
+            MetadataFinding {
+                key: "name".to_string(),
+                issue: MetadataIssue::UnexpectedType {
+                    expected: StandardMetadataType::String,
+                },
+            },
+        ]
+    );
+}
+
+#[test]
+fn dapp_definition_keys_on_other_entities_are_reported() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let resource = env.create_resource();
+    env.set_resource_metadata(
+        resource,
+        "account_type",
+        MetadataValue::String(DAPP_DEFINITION_ACCOUNT_TYPE.to_string()),
+    );
+    env.set_resource_metadata(
+        resource,
+        "claimed_websites",
+        MetadataValue::OriginArray(vec![UncheckedOrigin::of("https://www.example.com")]),
+    );
+
+    // Act
+    let findings = env.verify(resource.into());
+
+    // Assert
+    assert_eq!(
+        findings,
+        vec![
+            MetadataFinding {
+                key: "account_type".to_string(),
+                issue: MetadataIssue::NotAnAccount,
+            },
+            MetadataFinding {
+                key: "claimed_websites".to_string(),
+                issue: MetadataIssue::NotADappDefinition,
+            },
+        ]
+    );
+}
+
+struct TestEnvironment {
+    test_runner: DefaultTestRunner,
+    public_key: Secp256k1PublicKey,
+    dapp_definition: ComponentAddress,
+}
+
+impl TestEnvironment {
+    fn new() -> Self {
+        let mut test_runner = TestRunnerBuilder::new().without_trace().build();
+        let (public_key, _, dapp_definition) = test_runner.new_allocated_account();
+
+        let mut env = Self {
+            test_runner,
+            public_key,
+            dapp_definition,
+        };
+        env.set_dapp_definition_metadata(
+            "account_type",
+            MetadataValue::String(DAPP_DEFINITION_ACCOUNT_TYPE.to_string()),
+        );
+        env
+    }
+
+    fn create_resource(&mut self) -> ResourceAddress {
+        let manifest = ManifestBuilder::new()
+            .create_fungible_resource(
+                OwnerRole::Fixed(rule!(allow_all)),
+                true,
+                18,
+                FungibleResourceRoles::default(),
+                metadata!(),
+                None,
+            )
+            .build();
+        self.test_runner
+            .execute_manifest_ignoring_fee(manifest, [])
+            .expect_commit_success()
+            .new_resource_addresses()[0]
+    }
+
+    fn set_dapp_definition_metadata(&mut self, key: &str, value: MetadataValue) {
+        let manifest = ManifestBuilder::new()
+            .set_metadata(self.dapp_definition, key, value)
+            .build();
+        self.test_runner
+            .execute_manifest_ignoring_fee(
+                manifest,
+                [NonFungibleGlobalId::from_public_key(&self.public_key)],
+            )
+            .expect_commit_success();
+    }
+
+    fn set_resource_metadata(
+        &mut self,
+        resource: ResourceAddress,
+        key: &str,
+        value: MetadataValue,
+    ) {
+        let manifest = ManifestBuilder::new()
+            .set_metadata(resource, key, value)
+            .build();
+        self.test_runner
+            .execute_manifest_ignoring_fee(manifest, [])
+            .expect_commit_success();
+    }
+
+    fn verify(&self, address: GlobalAddress) -> Vec<MetadataFinding> {
+        MetadataVerifier::new(self.test_runner.substate_db())
+            .verify(address)
+            .unwrap()
+    }
+}
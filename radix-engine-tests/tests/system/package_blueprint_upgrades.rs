@@ -0,0 +1,621 @@
+use radix_engine::blueprints::package::*;
+use radix_engine::errors::{ApplicationError, RuntimeError, SystemError};
+use radix_engine::system::system_db_reader::{ObjectCollectionKey, SystemDatabaseReader};
+use radix_engine::transaction::TransactionReceipt;
+use radix_engine::types::*;
+use radix_engine_tests::common::*;
+use sbor::basic_well_known_types::STRING_TYPE;
+use scrypto_unit::*;
+use transaction::prelude::*;
+
+const BLUEPRINT: &str = "Counter";
+
+#[test]
+fn proposing_an_upgrade_before_configuring_upgrades_fails() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+
+    // Act
+    let (code, definition) = TestEnvironment::upgraded_blueprint();
+    let receipt = env.propose_upgrade(code, definition);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::PackageError(
+                PackageError::BlueprintUpgradesNotEnabled(..)
+            ))
+        )
+    });
+}
+
+#[test]
+fn owner_can_configure_blueprint_upgrades() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+
+    // Act
+    let receipt = env.configure_upgrades(BLUEPRINT, 60);
+
+    // Assert
+    let commit_result = receipt.expect_commit_success();
+    assert_eq!(
+        env.test_runner
+            .extract_events_of_type::<ConfigureBlueprintUpgradesEvent>(commit_result),
+        vec![ConfigureBlueprintUpgradesEvent {
+            blueprint_name: BLUEPRINT.to_string(),
+            timelock_in_minutes: 60,
+        }]
+    );
+}
+
+#[test]
+fn configuring_blueprint_upgrades_enables_the_blueprint_upgrades_feature_of_the_package() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    assert!(!env.has_blueprint_upgrades_feature());
+
+    // Act
+    let receipt = env.configure_upgrades(BLUEPRINT, 60);
+
+    // Assert
+    receipt.expect_commit_success();
+    assert!(env.has_blueprint_upgrades_feature());
+}
+
+#[test]
+fn configuring_blueprint_upgrades_without_the_owner_badge_fails() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .configure_package_blueprint_upgrades(env.package_address, BLUEPRINT, 60)
+        .build();
+    let receipt = env
+        .test_runner
+        .execute_manifest_ignoring_fee(manifest, vec![]);
+
+    // Assert
+    receipt.expect_auth_failure();
+}
+
+#[test]
+fn configuring_upgrades_of_a_blueprint_not_in_the_package_fails() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+
+    // Act
+    let receipt = env.configure_upgrades("NotABlueprint", 60);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::SystemError(SystemError::BlueprintDoesNotExist(..))
+        )
+    });
+}
+
+#[test]
+fn proposing_an_upgrade_without_the_upgrade_authority_fails() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    env.configure_upgrades(BLUEPRINT, 0).expect_commit_success();
+
+    // Act
+    let (code, definition) = TestEnvironment::upgraded_blueprint();
+    let manifest = ManifestBuilder::new()
+        .propose_package_blueprint_upgrade(env.package_address, BLUEPRINT, code, definition)
+        .build();
+    let receipt = env
+        .test_runner
+        .execute_manifest_ignoring_fee(manifest, vec![]);
+
+    // Assert
+    receipt.expect_auth_failure();
+}
+
+#[test]
+fn upgrade_authority_role_can_be_assigned_to_another_badge() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    env.configure_upgrades(BLUEPRINT, 0).expect_commit_success();
+    let authority_badge = env
+        .test_runner
+        .create_fungible_resource(dec!(1), 0, env.account);
+    let manifest = ManifestBuilder::new()
+        .create_proof_from_account_of_non_fungibles(
+            env.account,
+            env.owner_badge.resource_address(),
+            [env.owner_badge.local_id().clone()],
+        )
+        .set_main_role(
+            env.package_address,
+            UPGRADE_AUTHORITY_ROLE,
+            rule!(require(authority_badge)),
+        )
+        .build();
+    env.test_runner
+        .execute_manifest_ignoring_fee(
+            manifest,
+            [NonFungibleGlobalId::from_public_key(&env.public_key)],
+        )
+        .expect_commit_success();
+
+    // Act
+    let (code, definition) = TestEnvironment::upgraded_blueprint();
+    let manifest = ManifestBuilder::new()
+        .create_proof_from_account_of_amount(env.account, authority_badge, dec!(1))
+        .propose_package_blueprint_upgrade(env.package_address, BLUEPRINT, code, definition)
+        .build();
+    let receipt = env.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        [NonFungibleGlobalId::from_public_key(&env.public_key)],
+    );
+
+    // Assert
+    receipt.expect_commit_success();
+    let (code, definition) = TestEnvironment::upgraded_blueprint();
+    env.propose_upgrade(code, definition).expect_auth_failure();
+}
+
+#[test]
+fn proposing_an_upgrade_which_changes_the_type_of_a_field_fails() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    env.configure_upgrades(BLUEPRINT, 0).expect_commit_success();
+
+    // Act
+    let (code, mut definition) = TestEnvironment::upgraded_blueprint();
+    definition.schema.state.fields[0].field = TypeRef::Static(LocalTypeId::WellKnown(STRING_TYPE));
+    let receipt = env.propose_upgrade(code, definition);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::PackageError(
+                PackageError::IncompatibleBlueprintUpgrade {
+                    incompatibility: BlueprintUpgradeIncompatibility::FieldTypeChanged(
+                        0,
+                        PayloadIncompatibility::TypeIncompatible { .. }
+                    ),
+                    ..
+                }
+            ))
+        )
+    });
+}
+
+#[test]
+fn proposing_a_second_upgrade_while_one_is_pending_fails() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    env.configure_upgrades(BLUEPRINT, 60)
+        .expect_commit_success();
+    let (code, definition) = TestEnvironment::upgraded_blueprint();
+    env.propose_upgrade(code, definition)
+        .expect_commit_success();
+
+    // Act
+    let (code, definition) = TestEnvironment::upgraded_blueprint();
+    let receipt = env.propose_upgrade(code, definition);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::PackageError(
+                PackageError::BlueprintUpgradeAlreadyPending(..)
+            ))
+        )
+    });
+}
+
+#[test]
+fn executed_upgrade_is_used_by_existing_components() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let component_address = env.instantiate_counter();
+    assert_eq!(env.increment(component_address), 1);
+    env.configure_upgrades(BLUEPRINT, 0).expect_commit_success();
+    let (code, definition) = TestEnvironment::upgraded_blueprint();
+    let receipt = env.propose_upgrade(code, definition);
+    let commit_result = receipt.expect_commit_success();
+    assert_eq!(
+        env.test_runner
+            .extract_events_of_type::<ProposeBlueprintUpgradeEvent>(commit_result)
+            .len(),
+        1
+    );
+
+    // Act
+    let receipt = env.execute_upgrade();
+
+    // Assert
+    let commit_result = receipt.expect_commit_success();
+    assert_eq!(
+        env.test_runner
+            .extract_events_of_type::<ExecuteBlueprintUpgradeEvent>(commit_result),
+        vec![ExecuteBlueprintUpgradeEvent {
+            blueprint_name: BLUEPRINT.to_string(),
+            from_version: BlueprintVersion::default(),
+            to_version: BlueprintVersion {
+                major: 1,
+                minor: 1,
+                patch: 0,
+            },
+        }]
+    );
+    assert_eq!(env.increment(component_address), 11);
+}
+
+#[test]
+fn executed_upgrade_is_recorded_under_its_own_version() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    env.configure_upgrades(BLUEPRINT, 0).expect_commit_success();
+    let (code, definition) = TestEnvironment::upgraded_blueprint();
+    let code_hash = CodeHash::from_hash(hash(&code));
+    env.propose_upgrade(code, definition)
+        .expect_commit_success();
+
+    // Act
+    let receipt = env.execute_upgrade();
+
+    // Assert
+    receipt.expect_commit_success();
+    let published_definition = env
+        .read_definition(BlueprintVersion::default())
+        .expect("The published version should be kept");
+    let upgraded_definition = env
+        .read_definition(BlueprintVersion {
+            major: 1,
+            minor: 1,
+            patch: 0,
+        })
+        .expect("The upgraded version should be recorded");
+    assert_ne!(
+        published_definition.function_exports["increment"].code_hash,
+        code_hash
+    );
+    assert_eq!(
+        upgraded_definition.function_exports["increment"].code_hash,
+        code_hash
+    );
+    assert!(env.has_code(code_hash));
+    env.test_runner.check_database();
+}
+
+#[test]
+fn executed_upgrade_takes_effect_from_the_next_transaction() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let component_address = env.instantiate_counter();
+    env.configure_upgrades(BLUEPRINT, 0).expect_commit_success();
+    let (code, definition) = TestEnvironment::upgraded_blueprint();
+    env.propose_upgrade(code, definition)
+        .expect_commit_success();
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .create_proof_from_account_of_non_fungibles(
+            env.account,
+            env.owner_badge.resource_address(),
+            [env.owner_badge.local_id().clone()],
+        )
+        .execute_package_blueprint_upgrade(env.package_address, BLUEPRINT)
+        .call_method(component_address, "increment", manifest_args!())
+        .build();
+    let receipt = env.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        [NonFungibleGlobalId::from_public_key(&env.public_key)],
+    );
+
+    // Assert
+    let count: u32 = receipt.expect_commit_success().output(3);
+    assert_eq!(count, 1);
+    assert_eq!(env.increment(component_address), 11);
+}
+
+#[test]
+fn components_use_the_published_version_until_an_upgrade_is_executed() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let component_address = env.instantiate_counter();
+    env.configure_upgrades(BLUEPRINT, 0).expect_commit_success();
+    let (code, definition) = TestEnvironment::upgraded_blueprint();
+    env.propose_upgrade(code, definition)
+        .expect_commit_success();
+
+    // Act
+    let count = env.increment(component_address);
+
+    // Assert
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn executing_an_upgrade_before_the_timelock_has_elapsed_fails() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    env.configure_upgrades(BLUEPRINT, 60)
+        .expect_commit_success();
+    let (code, definition) = TestEnvironment::upgraded_blueprint();
+    env.propose_upgrade(code, definition)
+        .expect_commit_success();
+
+    // Act
+    let receipt = env.execute_upgrade();
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::PackageError(
+                PackageError::BlueprintUpgradeTimelockHasNotElapsed(..)
+            ))
+        )
+    });
+}
+
+#[test]
+fn executing_an_upgrade_after_the_timelock_has_elapsed_succeeds() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    env.configure_upgrades(BLUEPRINT, 60)
+        .expect_commit_success();
+    let (code, definition) = TestEnvironment::upgraded_blueprint();
+    env.propose_upgrade(code, definition)
+        .expect_commit_success();
+    env.test_runner
+        .advance_to_round_at_timestamp(Round::of(1), 60 * 60 * 1000)
+        .expect_commit_success();
+
+    // Act
+    let receipt = env.execute_upgrade();
+
+    // Assert
+    receipt.expect_commit_success();
+}
+
+#[test]
+fn cancelled_upgrade_can_not_be_executed() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    env.configure_upgrades(BLUEPRINT, 0).expect_commit_success();
+    let (code, definition) = TestEnvironment::upgraded_blueprint();
+    env.propose_upgrade(code, definition)
+        .expect_commit_success();
+    let receipt = env.cancel_upgrade();
+    let commit_result = receipt.expect_commit_success();
+    assert_eq!(
+        env.test_runner
+            .extract_events_of_type::<CancelBlueprintUpgradeEvent>(commit_result),
+        vec![CancelBlueprintUpgradeEvent {
+            blueprint_name: BLUEPRINT.to_string(),
+            version: BlueprintVersion {
+                major: 1,
+                minor: 1,
+                patch: 0,
+            },
+        }]
+    );
+
+    // Act
+    let receipt = env.execute_upgrade();
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::PackageError(
+                PackageError::NoPendingBlueprintUpgrade(..)
+            ))
+        )
+    });
+}
+
+#[test]
+fn cancelling_an_upgrade_removes_its_staged_code() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    env.configure_upgrades(BLUEPRINT, 0).expect_commit_success();
+    let (code, definition) = TestEnvironment::upgraded_blueprint();
+    let code_hash = CodeHash::from_hash(hash(&code));
+    env.propose_upgrade(code, definition)
+        .expect_commit_success();
+    assert!(env.has_code(code_hash));
+
+    // Act
+    let receipt = env.cancel_upgrade();
+
+    // Assert
+    receipt.expect_commit_success();
+    assert!(!env.has_code(code_hash));
+    env.test_runner.check_database();
+
+    // The same code can be proposed again after cancellation
+    let (code, definition) = TestEnvironment::upgraded_blueprint();
+    env.propose_upgrade(code, definition)
+        .expect_commit_success();
+    env.execute_upgrade().expect_commit_success();
+}
+
+struct TestEnvironment {
+    test_runner: DefaultTestRunner,
+    public_key: Secp256k1PublicKey,
+    account: ComponentAddress,
+    owner_badge: NonFungibleGlobalId,
+    package_address: PackageAddress,
+}
+
+impl TestEnvironment {
+    fn new() -> Self {
+        let mut test_runner = TestRunnerBuilder::new().without_trace().build();
+        let (public_key, _, account) = test_runner.new_allocated_account();
+
+        let owner_badge_resource = test_runner.create_non_fungible_resource(account);
+        let owner_badge =
+            NonFungibleGlobalId::new(owner_badge_resource, NonFungibleLocalId::integer(1));
+        let package_address = test_runner.publish_package_with_owner(
+            PackageLoader::get("blueprint_upgrade"),
+            owner_badge.clone(),
+        );
+
+        Self {
+            test_runner,
+            public_key,
+            account,
+            owner_badge,
+            package_address,
+        }
+    }
+
+    fn upgraded_blueprint() -> (Vec<u8>, BlueprintDefinitionInit) {
+        let (code, mut definition) = PackageLoader::get("blueprint_upgrade2");
+        (code, definition.blueprints.swap_remove(BLUEPRINT).unwrap())
+    }
+
+    fn configure_upgrades(
+        &mut self,
+        blueprint_name: &str,
+        timelock_in_minutes: u32,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungibles(
+                self.account,
+                self.owner_badge.resource_address(),
+                [self.owner_badge.local_id().clone()],
+            )
+            .configure_package_blueprint_upgrades(
+                self.package_address,
+                blueprint_name,
+                timelock_in_minutes,
+            )
+            .build();
+        self.test_runner.execute_manifest_ignoring_fee(
+            manifest,
+            [NonFungibleGlobalId::from_public_key(&self.public_key)],
+        )
+    }
+
+    fn propose_upgrade(
+        &mut self,
+        code: Vec<u8>,
+        definition: BlueprintDefinitionInit,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungibles(
+                self.account,
+                self.owner_badge.resource_address(),
+                [self.owner_badge.local_id().clone()],
+            )
+            .propose_package_blueprint_upgrade(self.package_address, BLUEPRINT, code, definition)
+            .build();
+        self.test_runner.execute_manifest_ignoring_fee(
+            manifest,
+            [NonFungibleGlobalId::from_public_key(&self.public_key)],
+        )
+    }
+
+    fn execute_upgrade(&mut self) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungibles(
+                self.account,
+                self.owner_badge.resource_address(),
+                [self.owner_badge.local_id().clone()],
+            )
+            .execute_package_blueprint_upgrade(self.package_address, BLUEPRINT)
+            .build();
+        self.test_runner.execute_manifest_ignoring_fee(
+            manifest,
+            [NonFungibleGlobalId::from_public_key(&self.public_key)],
+        )
+    }
+
+    fn cancel_upgrade(&mut self) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungibles(
+                self.account,
+                self.owner_badge.resource_address(),
+                [self.owner_badge.local_id().clone()],
+            )
+            .cancel_package_blueprint_upgrade(self.package_address, BLUEPRINT)
+            .build();
+        self.test_runner.execute_manifest_ignoring_fee(
+            manifest,
+            [NonFungibleGlobalId::from_public_key(&self.public_key)],
+        )
+    }
+
+    fn read_definition(&self, version: BlueprintVersion) -> Option<BlueprintDefinition> {
+        let reader = SystemDatabaseReader::new(self.test_runner.substate_db());
+        reader
+            .read_object_collection_entry::<_, PackageBlueprintVersionDefinitionEntryPayload>(
+                self.package_address.as_node_id(),
+                ModuleId::Main,
+                ObjectCollectionKey::KeyValue(
+                    PackageCollection::BlueprintVersionDefinitionKeyValue.collection_index(),
+                    &BlueprintVersionKey {
+                        blueprint: BLUEPRINT.to_string(),
+                        version,
+                    },
+                ),
+            )
+            .unwrap()
+            .map(|definition| definition.into_latest())
+    }
+
+    fn has_blueprint_upgrades_feature(&self) -> bool {
+        let reader = SystemDatabaseReader::new(self.test_runner.substate_db());
+        reader
+            .get_object_info(self.package_address)
+            .unwrap()
+            .blueprint_info
+            .features
+            .contains(PackageFeature::PackageBlueprintUpgrades.feature_name())
+    }
+
+    fn has_code(&self, code_hash: CodeHash) -> bool {
+        let reader = SystemDatabaseReader::new(self.test_runner.substate_db());
+        reader
+            .read_object_collection_entry::<_, PackageCodeOriginalCodeEntryPayload>(
+                self.package_address.as_node_id(),
+                ModuleId::Main,
+                ObjectCollectionKey::KeyValue(
+                    PackageCollection::CodeOriginalCodeKeyValue.collection_index(),
+                    &code_hash,
+                ),
+            )
+            .unwrap()
+            .is_some()
+    }
+
+    fn instantiate_counter(&mut self) -> ComponentAddress {
+        let manifest = ManifestBuilder::new()
+            .call_function(
+                self.package_address,
+                BLUEPRINT,
+                "instantiate",
+                manifest_args!(),
+            )
+            .build();
+        self.test_runner
+            .execute_manifest_ignoring_fee(manifest, vec![])
+            .expect_commit_success()
+            .new_component_addresses()[0]
+    }
+
+    fn increment(&mut self, component_address: ComponentAddress) -> u32 {
+        let manifest = ManifestBuilder::new()
+            .call_method(component_address, "increment", manifest_args!())
+            .build();
+        self.test_runner
+            .execute_manifest_ignoring_fee(manifest, vec![])
+            .expect_commit_success()
+            .output(1)
+    }
+}
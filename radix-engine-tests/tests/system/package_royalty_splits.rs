@@ -0,0 +1,407 @@
+use radix_engine::blueprints::package::*;
+use radix_engine::errors::{ApplicationError, RuntimeError, SystemError, SystemModuleError};
+use radix_engine::system::system_modules::auth::AuthError;
+use radix_engine::transaction::TransactionReceipt;
+use radix_engine::types::*;
+use radix_engine_tests::common::*;
+use scrypto_unit::*;
+use transaction::prelude::*;
+
+const BLUEPRINT: &str = "RoyaltyTest";
+
+#[test]
+fn owner_can_split_package_royalties() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let (_, _, recipient1) = env.test_runner.new_allocated_account();
+    let (_, _, recipient2) = env.test_runner.new_allocated_account();
+    let recipients = indexmap!(
+        recipient1.into() => dec!("0.25"),
+        recipient2.into() => dec!("0.5"),
+    );
+
+    // Act
+    let receipt = env.set_split(BLUEPRINT, recipients.clone());
+
+    // Assert
+    let commit_result = receipt.expect_commit_success();
+    assert_eq!(
+        env.test_runner
+            .extract_events_of_type::<SetRoyaltySplitEvent>(commit_result),
+        vec![SetRoyaltySplitEvent {
+            blueprint_name: BLUEPRINT.to_string(),
+            recipients,
+        }]
+    );
+}
+
+#[test]
+fn package_royalties_are_split_between_recipients() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let (_, _, recipient1) = env.test_runner.new_allocated_account();
+    let (_, _, recipient2) = env.test_runner.new_allocated_account();
+    env.set_split(
+        BLUEPRINT,
+        indexmap!(
+            recipient1.into() => dec!("0.25"),
+            recipient2.into() => dec!("0.5"),
+        ),
+    )
+    .expect_commit_success();
+
+    // Act
+    let receipt = env.call_paid_method();
+
+    // Assert
+    receipt.expect_commit_success();
+    assert_eq!(receipt.fee_summary.total_royalty_cost_in_xrd, dec!("3"));
+    assert_eq!(
+        env.test_runner
+            .inspect_package_royalty(env.package_address)
+            .unwrap(),
+        dec!("0.5")
+    );
+
+    // Act
+    let balance1 = env.test_runner.get_component_balance(recipient1, XRD);
+    let balance2 = env.test_runner.get_component_balance(recipient2, XRD);
+    env.claim_share(recipient1.into()).expect_commit_success();
+    env.claim_share(recipient2.into()).expect_commit_success();
+
+    // Assert
+    assert_eq!(
+        env.test_runner.get_component_balance(recipient1, XRD),
+        balance1.checked_add(dec!("0.5")).unwrap()
+    );
+    assert_eq!(
+        env.test_runner.get_component_balance(recipient2, XRD),
+        balance2.checked_add(dec!(1)).unwrap()
+    );
+}
+
+#[test]
+fn removed_recipient_can_still_claim_its_accrued_share() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let (_, _, recipient) = env.test_runner.new_allocated_account();
+    env.set_split(BLUEPRINT, indexmap!(recipient.into() => dec!(1)))
+        .expect_commit_success();
+    env.call_paid_method().expect_commit_success();
+    env.set_split(BLUEPRINT, indexmap!())
+        .expect_commit_success();
+    env.call_paid_method().expect_commit_success();
+    let balance = env.test_runner.get_component_balance(recipient, XRD);
+
+    // Act
+    let receipt = env.claim_share(recipient.into());
+
+    // Assert
+    let commit_result = receipt.expect_commit_success();
+    assert_eq!(
+        env.test_runner
+            .extract_events_of_type::<ClaimRoyaltyShareEvent>(commit_result),
+        vec![ClaimRoyaltyShareEvent {
+            recipient: recipient.into(),
+            amount: dec!(2),
+        }]
+    );
+    assert_eq!(
+        env.test_runner.get_component_balance(recipient, XRD),
+        balance.checked_add(dec!(2)).unwrap()
+    );
+    assert_eq!(
+        env.test_runner
+            .inspect_package_royalty(env.package_address)
+            .unwrap(),
+        dec!(2)
+    );
+}
+
+#[test]
+fn shares_exceeding_one_are_rejected() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let (_, _, recipient1) = env.test_runner.new_allocated_account();
+    let (_, _, recipient2) = env.test_runner.new_allocated_account();
+
+    // Act
+    let receipt = env.set_split(
+        BLUEPRINT,
+        indexmap!(
+            recipient1.into() => dec!("0.5"),
+            recipient2.into() => dec!("0.75"),
+        ),
+    );
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::PackageError(
+                PackageError::RoyaltySplitSharesExceedOne(..)
+            ))
+        )
+    });
+}
+
+#[test]
+fn non_positive_share_is_rejected() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let (_, _, recipient) = env.test_runner.new_allocated_account();
+
+    // Act
+    let receipt = env.set_split(BLUEPRINT, indexmap!(recipient.into() => dec!(0)));
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::PackageError(
+                PackageError::InvalidRoyaltySplitShare { .. }
+            ))
+        )
+    });
+}
+
+#[test]
+fn recipient_which_is_not_a_component_is_rejected() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+
+    // Act
+    let receipt = env.set_split(BLUEPRINT, indexmap!(XRD.into() => dec!("0.5")));
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::PackageError(
+                PackageError::RoyaltySplitRecipientNotAComponent(..)
+            ))
+        )
+    });
+}
+
+#[test]
+fn splitting_royalties_of_unknown_blueprint_fails() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let (_, _, recipient) = env.test_runner.new_allocated_account();
+
+    // Act
+    let receipt = env.set_split("Unknown", indexmap!(recipient.into() => dec!("0.5")));
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::SystemError(SystemError::BlueprintDoesNotExist(..))
+        )
+    });
+}
+
+#[test]
+fn splitting_royalties_without_the_owner_badge_fails() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let (_, _, recipient) = env.test_runner.new_allocated_account();
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .set_package_royalty_split(
+            env.package_address,
+            BLUEPRINT,
+            indexmap!(recipient.into() => dec!("0.5")),
+        )
+        .build();
+    let receipt = env.test_runner.execute_manifest_ignoring_fee(manifest, []);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::SystemModuleError(SystemModuleError::AuthError(AuthError::Unauthorized(
+                ..
+            )))
+        )
+    });
+}
+
+#[test]
+fn locked_royalty_split_cannot_be_changed() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let (_, _, recipient) = env.test_runner.new_allocated_account();
+    env.set_split(BLUEPRINT, indexmap!(recipient.into() => dec!("0.5")))
+        .expect_commit_success();
+    env.lock_split(BLUEPRINT).expect_commit_success();
+
+    // Act
+    let receipt = env.set_split(BLUEPRINT, indexmap!());
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::SystemError(SystemError::KeyValueEntryLocked)
+        )
+    });
+}
+
+#[test]
+fn locking_missing_royalty_split_fails() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+
+    // Act
+    let receipt = env.lock_split(BLUEPRINT);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::PackageError(
+                PackageError::RoyaltySplitNotFound(..)
+            ))
+        )
+    });
+}
+
+#[test]
+fn component_recipient_can_only_claim_its_share_itself() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let component_address = env.component_address;
+    env.set_split(
+        BLUEPRINT,
+        indexmap!(component_address.into() => dec!("0.5")),
+    )
+    .expect_commit_success();
+    env.call_paid_method().expect_commit_success();
+
+    // Act
+    let receipt = env.claim_share(component_address.into());
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::SystemError(SystemError::AssertAccessRuleFailed)
+        )
+    });
+}
+
+#[test]
+fn claiming_share_of_unknown_recipient_fails() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let (_, _, recipient) = env.test_runner.new_allocated_account();
+
+    // Act
+    let receipt = env.claim_share(recipient.into());
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::PackageError(
+                PackageError::RoyaltySplitRecipientNotFound(..)
+            ))
+        )
+    });
+}
+
+struct TestEnvironment {
+    test_runner: DefaultTestRunner,
+    public_key: Secp256k1PublicKey,
+    account: ComponentAddress,
+    owner_badge: NonFungibleGlobalId,
+    package_address: PackageAddress,
+    component_address: ComponentAddress,
+}
+
+impl TestEnvironment {
+    fn new() -> Self {
+        let mut test_runner = TestRunnerBuilder::new().without_trace().build();
+        let (public_key, _, account) = test_runner.new_allocated_account();
+
+        let owner_badge_resource = test_runner.create_non_fungible_resource(account);
+        let owner_badge =
+            NonFungibleGlobalId::new(owner_badge_resource, NonFungibleLocalId::integer(1));
+        let package_address = test_runner
+            .publish_package_with_owner(PackageLoader::get("royalty"), owner_badge.clone());
+
+        let receipt = test_runner.execute_manifest_ignoring_fee(
+            ManifestBuilder::new()
+                .call_function(
+                    package_address,
+                    BLUEPRINT,
+                    "create_component_with_royalty_enabled",
+                    manifest_args!(),
+                )
+                .build(),
+            [],
+        );
+        let component_address: ComponentAddress = receipt.expect_commit(true).output(0);
+
+        Self {
+            test_runner,
+            public_key,
+            account,
+            owner_badge,
+            package_address,
+            component_address,
+        }
+    }
+
+    fn set_split(
+        &mut self,
+        blueprint_name: &str,
+        recipients: IndexMap<GlobalAddress, Decimal>,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungibles(
+                self.account,
+                self.owner_badge.resource_address(),
+                [self.owner_badge.local_id().clone()],
+            )
+            .set_package_royalty_split(self.package_address, blueprint_name, recipients)
+            .build();
+        self.test_runner.execute_manifest_ignoring_fee(
+            manifest,
+            [NonFungibleGlobalId::from_public_key(&self.public_key)],
+        )
+    }
+
+    fn lock_split(&mut self, blueprint_name: &str) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungibles(
+                self.account,
+                self.owner_badge.resource_address(),
+                [self.owner_badge.local_id().clone()],
+            )
+            .lock_package_royalty_split(self.package_address, blueprint_name)
+            .build();
+        self.test_runner.execute_manifest_ignoring_fee(
+            manifest,
+            [NonFungibleGlobalId::from_public_key(&self.public_key)],
+        )
+    }
+
+    fn call_paid_method(&mut self) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .call_method(self.component_address, "paid_method", manifest_args!())
+            .build();
+        self.test_runner.execute_manifest(manifest, [])
+    }
+
+    fn claim_share(&mut self, recipient: GlobalAddress) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .claim_package_royalty_share(self.package_address, recipient)
+            .build();
+        self.test_runner.execute_manifest_ignoring_fee(manifest, [])
+    }
+}
@@ -1,4 +1,5 @@
 use radix_engine::{
+    blueprints::account::AccountV1MinorVersion,
     errors::{RuntimeError, SystemError},
     system::system_modules::costing::{
         NATIVE_FUNCTION_BASE_COSTS, NATIVE_FUNCTION_BASE_COSTS_SIZE_DEPENDENT,
@@ -443,7 +444,7 @@ pub fn test_fake_bucket() {
 
 #[test]
 fn native_blueprints_with_typed_addresses_have_expected_schema() {
-    let mut blueprint_definition = AccountNativePackage::definition()
+    let mut blueprint_definition = AccountNativePackage::definition(AccountV1MinorVersion::One)
         .blueprints
         .remove("Account")
         .unwrap();
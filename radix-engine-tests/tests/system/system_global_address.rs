@@ -1,5 +1,5 @@
 use radix_engine_tests::common::*;
-use radix_engine::blueprints::resource::ResourceNativePackage;
+use radix_engine::blueprints::resource::{ResourceNativePackage, ResourceV1MinorVersion};
 use radix_engine::errors::{RuntimeError, SystemError};
 use radix_engine::kernel::kernel_api::{KernelNodeApi, KernelSubstateApi};
 use radix_engine::system::system_callback::SystemLockData;
@@ -80,20 +80,21 @@ fn global_address_access_from_frame_owned_object_should_not_succeed() {
 #[test]
 fn global_address_access_from_direct_access_methods_should_fail_even_with_borrowed_reference() {
     // Arrange
-    let resource_direct_access_methods: HashSet<String> = ResourceNativePackage::definition()
-        .blueprints
-        .into_iter()
-        .flat_map(|(_, def)| def.schema.functions.functions.into_iter())
-        .filter_map(|(_, def)| {
-            def.receiver.and_then(|i| {
-                if matches!(i.ref_types, RefTypes::DIRECT_ACCESS) {
-                    Some(def.export)
-                } else {
-                    None
-                }
+    let resource_direct_access_methods: HashSet<String> =
+        ResourceNativePackage::definition(ResourceV1MinorVersion::Two)
+            .blueprints
+            .into_iter()
+            .flat_map(|(_, def)| def.schema.functions.functions.into_iter())
+            .filter_map(|(_, def)| {
+                def.receiver.and_then(|i| {
+                    if matches!(i.ref_types, RefTypes::DIRECT_ACCESS) {
+                        Some(def.export)
+                    } else {
+                        None
+                    }
+                })
             })
-        })
-        .collect();
+            .collect();
     #[derive(Clone)]
     struct ResourceOverride(HashSet<String>);
     impl VmInvoke for ResourceOverride {
@@ -0,0 +1,234 @@
+use radix_engine::blueprints::transaction_tracker::{
+    TransactionStatusV1, EPOCHS_PER_PARTITION, PARTITION_RANGE_END, PARTITION_RANGE_START,
+};
+use radix_engine::transaction::{CostingParameters, ExecutionConfig, TransactionReceipt};
+use radix_engine::types::*;
+use radix_engine_queries::query::*;
+use radix_engine_stores::memory_db::InMemorySubstateDatabase;
+use scrypto_unit::*;
+use transaction::prelude::*;
+use transaction::validation::*;
+
+#[test]
+fn epoch_partitions_cover_consecutive_epoch_ranges() {
+    // Arrange
+    let env = TestEnvironment::new();
+
+    // Act
+    let partitions = env.reader().epoch_partitions().unwrap();
+
+    // Assert
+    assert_eq!(
+        partitions.len(),
+        (PARTITION_RANGE_END - PARTITION_RANGE_START + 1) as usize
+    );
+    assert_eq!(
+        partitions[0].partition_number,
+        PartitionNumber(PARTITION_RANGE_START)
+    );
+    assert!(partitions[0].overlaps(env.current_epoch, env.current_epoch.next().unwrap()));
+    for (partition, next_partition) in partitions.iter().zip(partitions.iter().skip(1)) {
+        assert_eq!(
+            partition.start_epoch_inclusive.after(EPOCHS_PER_PARTITION),
+            Some(partition.end_epoch_exclusive)
+        );
+        assert_eq!(
+            partition.end_epoch_exclusive,
+            next_partition.start_epoch_inclusive
+        );
+        assert_eq!(
+            partition.partition_number.0 + 1,
+            next_partition.partition_number.0
+        );
+    }
+}
+
+#[test]
+fn committed_success_intent_is_found_in_the_partition_of_its_expiry_epoch() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let expiry_epoch = env.current_epoch.after(EPOCHS_PER_PARTITION).unwrap();
+    let intent_hash = env.submit(1, expiry_epoch, false).0;
+
+    // Act
+    let tracked_intent = env.reader().intent_status(&intent_hash).unwrap();
+
+    // Assert
+    let partition = env.reader().epoch_partitions().unwrap()[1];
+    assert!(partition.overlaps(expiry_epoch, expiry_epoch.next().unwrap()));
+    assert_eq!(
+        tracked_intent,
+        Some(TrackedIntent {
+            intent_hash,
+            status: TransactionStatusV1::CommittedSuccess,
+            partition,
+        })
+    );
+}
+
+#[test]
+fn committed_failure_intent_is_reported() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let expiry_epoch = env.current_epoch.after(10).unwrap();
+    let (intent_hash, receipt) = env.submit(1, expiry_epoch, true);
+    receipt.expect_commit_failure();
+
+    // Act
+    let tracked_intent = env.reader().intent_status(&intent_hash).unwrap();
+
+    // Assert
+    assert_eq!(
+        tracked_intent.map(|tracked_intent| tracked_intent.status),
+        Some(TransactionStatusV1::CommittedFailure)
+    );
+}
+
+#[test]
+fn uncommitted_intent_is_unknown() {
+    // Arrange
+    let env = TestEnvironment::new();
+
+    // Act
+    let tracked_intent = env
+        .reader()
+        .intent_status(&IntentHash::from_hash(hash("not committed")))
+        .unwrap();
+
+    // Assert
+    assert_eq!(tracked_intent, None);
+}
+
+#[test]
+fn expired_intent_is_unknown() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let expiry_epoch = env.current_epoch.after(10).unwrap();
+    let intent_hash = env.submit(1, expiry_epoch, false).0;
+    assert!(env.reader().intent_status(&intent_hash).unwrap().is_some());
+
+    // Act
+    // The first partition is discarded by the next transaction committed after its epoch range
+    let first_partition = env.reader().epoch_partitions().unwrap()[0];
+    env.test_runner
+        .set_current_epoch(first_partition.end_epoch_exclusive);
+    env.test_runner
+        .execute_manifest(
+            ManifestBuilder::new().lock_fee_from_faucet().build(),
+            vec![],
+        )
+        .expect_commit_success();
+
+    // Assert
+    assert_eq!(env.reader().intent_status(&intent_hash).unwrap(), None);
+}
+
+#[test]
+fn tracked_intents_are_listed_by_epoch_range() {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let early_intent_hash = env.submit(1, env.current_epoch.after(10).unwrap(), false).0;
+    let late_intent_hash = env
+        .submit(
+            2,
+            env.current_epoch.after(EPOCHS_PER_PARTITION).unwrap(),
+            false,
+        )
+        .0;
+    let failed_intent_hash = env.submit(3, env.current_epoch.after(20).unwrap(), true).0;
+
+    // Act
+    let early_intents = env
+        .reader()
+        .tracked_intents(env.current_epoch, env.current_epoch.after(50).unwrap())
+        .unwrap();
+    let late_intents = env
+        .reader()
+        .tracked_intents(
+            env.current_epoch.after(EPOCHS_PER_PARTITION).unwrap(),
+            env.current_epoch.after(EPOCHS_PER_PARTITION + 1).unwrap(),
+        )
+        .unwrap();
+    let all_intents = env
+        .reader()
+        .tracked_intents(Epoch::zero(), Epoch::of(u64::MAX))
+        .unwrap();
+
+    // Assert
+    let statuses = |intents: &Vec<TrackedIntent>| {
+        intents
+            .iter()
+            .map(|tracked_intent| (tracked_intent.intent_hash, tracked_intent.status))
+            .collect::<IndexMap<_, _>>()
+    };
+    assert_eq!(
+        statuses(&early_intents),
+        indexmap!(
+            early_intent_hash => TransactionStatusV1::CommittedSuccess,
+            failed_intent_hash => TransactionStatusV1::CommittedFailure,
+        )
+    );
+    assert_eq!(
+        statuses(&late_intents),
+        indexmap!(late_intent_hash => TransactionStatusV1::CommittedSuccess)
+    );
+    assert_eq!(all_intents.len(), 3);
+    assert_eq!(all_intents[2].intent_hash, late_intent_hash);
+}
+
+struct TestEnvironment {
+    test_runner: DefaultTestRunner,
+    current_epoch: Epoch,
+}
+
+impl TestEnvironment {
+    fn new() -> Self {
+        let mut test_runner = TestRunnerBuilder::new().without_trace().build();
+        let current_epoch = test_runner.get_current_epoch();
+
+        Self {
+            test_runner,
+            current_epoch,
+        }
+    }
+
+    fn reader(&self) -> TransactionTrackerReader<InMemorySubstateDatabase> {
+        TransactionTrackerReader::new(self.test_runner.substate_db())
+    }
+
+    fn submit(
+        &mut self,
+        nonce: u32,
+        end_epoch_exclusive: Epoch,
+        fail: bool,
+    ) -> (IntentHash, TransactionReceipt) {
+        let notary = Secp256k1PrivateKey::from_u64(1).unwrap();
+        let mut manifest_builder = ManifestBuilder::new().lock_fee_from_faucet();
+        if fail {
+            manifest_builder = manifest_builder.assert_worktop_contains(XRD, dec!(1));
+        }
+        let transaction = TransactionBuilder::new()
+            .header(TransactionHeaderV1 {
+                network_id: NetworkDefinition::simulator().id,
+                start_epoch_inclusive: self.current_epoch,
+                end_epoch_exclusive,
+                nonce,
+                notary_public_key: notary.public_key().into(),
+                notary_is_signatory: false,
+                tip_percentage: 0,
+            })
+            .manifest(manifest_builder.build())
+            .notarize(&notary)
+            .build();
+        let validated = NotarizedTransactionValidator::new(ValidationConfig::simulator())
+            .validate(transaction.prepare().unwrap())
+            .unwrap();
+
+        let receipt = self.test_runner.execute_transaction(
+            validated.get_executable(),
+            CostingParameters::default(),
+            ExecutionConfig::for_notarized_transaction(NetworkDefinition::simulator()),
+        );
+        (validated.intent_hash(), receipt)
+    }
+}
@@ -236,17 +236,17 @@ fn test_all_scenario_commit_receipts_should_have_events_that_can_be_typed() {
 fn typed_native_event_type_contains_all_native_events() {
     // Arrange
     let package_name_definition_mapping = hashmap! {
-        "ConsensusManager" => CONSENSUS_MANAGER_PACKAGE_DEFINITION.deref(),
-        "Account" => ACCOUNT_PACKAGE_DEFINITION.deref(),
-        "Identity" => IDENTITY_PACKAGE_DEFINITION.deref(),
-        "AccessController" => ACCESS_CONTROLLER_PACKAGE_DEFINITION.deref(),
-        "Pool" => POOL_PACKAGE_DEFINITION_V1_0.deref(),
+        "ConsensusManager" => CONSENSUS_MANAGER_PACKAGE_DEFINITION_V1_1.deref(),
+        "Account" => ACCOUNT_PACKAGE_DEFINITION_V1_2.deref(),
+        "Identity" => IDENTITY_PACKAGE_DEFINITION_V1_1.deref(),
+        "AccessController" => ACCESS_CONTROLLER_PACKAGE_DEFINITION_V1_1.deref(),
+        "Pool" => POOL_PACKAGE_DEFINITION_V1_2.deref(),
         "TransactionTracker" => TRANSACTION_TRACKER_PACKAGE_DEFINITION.deref(),
-        "Resource" => RESOURCE_PACKAGE_DEFINITION.deref(),
-        "Package" => PACKAGE_PACKAGE_DEFINITION.deref(),
+        "Resource" => RESOURCE_PACKAGE_DEFINITION_V1_2.deref(),
+        "Package" => PACKAGE_PACKAGE_DEFINITION_V1_2.deref(),
         "TransactionProcessor" => TRANSACTION_PROCESSOR_PACKAGE_DEFINITION.deref(),
         "Metadata" => METADATA_PACKAGE_DEFINITION.deref(),
-        "Royalty" => ROYALTY_PACKAGE_DEFINITION.deref(),
+        "Royalty" => ROYALTY_PACKAGE_DEFINITION_V1_1.deref(),
         "RoleAssignment" => ROLE_ASSIGNMENT_PACKAGE_DEFINITION.deref(),
     };
 
@@ -71,6 +71,8 @@ fn panics_can_be_caught_in_the_native_vm_and_converted_into_results() {
     let mut system = SystemConfig {
         blueprint_cache: NonIterMap::new(),
         auth_cache: NonIterMap::new(),
+        blueprint_version_cache: NonIterMap::new(),
+        package_blueprint_upgrades: false,
         schema_cache: NonIterMap::new(),
         callback_obj: vm.clone(),
         modules: SystemModuleMixer::new(
@@ -145,6 +147,8 @@ fn any_panics_can_be_caught_in_the_native_vm_and_converted_into_results() {
     let mut system = SystemConfig {
         blueprint_cache: NonIterMap::new(),
         auth_cache: NonIterMap::new(),
+        blueprint_version_cache: NonIterMap::new(),
+        package_blueprint_upgrades: false,
         schema_cache: NonIterMap::new(),
         callback_obj: vm.clone(),
         modules: SystemModuleMixer::new(
@@ -0,0 +1,82 @@
+use radix_engine::{
+    errors::{RuntimeError, VmError},
+    transaction::{CostingParameters, ExecutionConfig, TransactionReceipt},
+    types::*,
+    vm::wasm::WasmRuntimeError,
+};
+use radix_engine_tests::common::*;
+use scrypto_unit::*;
+use transaction::prelude::*;
+
+fn call_trapping_function(enable_wasm_backtraces: bool) -> TransactionReceipt {
+    let mut test_runner = TestRunnerBuilder::new().build();
+    let code = wabt::Wat2Wasm::new()
+        .write_debug_names(true)
+        .convert(include_local_wasm_str!("backtrace.wat"))
+        .unwrap()
+        .as_ref()
+        .to_vec();
+    let package_address = test_runner.publish_package(
+        (code, single_function_package_definition("Test", "f")),
+        BTreeMap::new(),
+        OwnerRole::None,
+    );
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(package_address, "Test", "f", manifest_args!())
+        .build();
+    let nonce = test_runner.next_transaction_nonce();
+    test_runner.execute_transaction(
+        TestTransaction::new_from_nonce(manifest, nonce)
+            .prepare()
+            .unwrap()
+            .get_executable(btreeset!()),
+        CostingParameters::default(),
+        ExecutionConfig::for_test_transaction().with_wasm_backtraces(enable_wasm_backtraces),
+    )
+}
+
+fn expect_execution_error(receipt: &TransactionReceipt) -> String {
+    match receipt.expect_failure() {
+        RuntimeError::VmError(VmError::Wasm(WasmRuntimeError::ExecutionError(message))) => {
+            message.clone()
+        }
+        e => panic!("Unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn trap_has_no_backtrace_by_default() {
+    // Act
+    let receipt = call_trapping_function(false);
+
+    // Assert
+    let message = expect_execution_error(&receipt);
+    assert!(!message.contains("WASM backtrace"));
+    assert!(receipt.wasm_backtraces.is_none());
+}
+
+#[test]
+fn trap_has_symbolized_backtrace_when_enabled() {
+    // Act
+    let receipt = call_trapping_function(true);
+
+    // Assert
+    let message = expect_execution_error(&receipt);
+    assert!(
+        message.ends_with("WASM backtrace:\n  0: inner\n  1: Test_f"),
+        "{}",
+        message
+    );
+    let wasm_backtraces = receipt.wasm_backtraces.as_ref().unwrap();
+    assert_eq!(wasm_backtraces.len(), 1);
+    assert_eq!(
+        wasm_backtraces[0]
+            .frames
+            .iter()
+            .map(|frame| frame.function_name.as_deref())
+            .collect::<Vec<_>>(),
+        vec![Some("inner"), Some("Test_f")]
+    );
+}
@@ -0,0 +1,80 @@
+use radix_engine::types::*;
+use radix_engine::vm::wasm::*;
+use radix_engine::vm::{VmApi, VmPackageValidation};
+use radix_engine_interface::blueprints::package::VmType;
+use radix_engine_tests::common::*;
+use std::fs;
+
+struct DiskCachingVmApi {
+    disk_cache: WasmDiskCache,
+}
+
+impl VmApi for DiskCachingVmApi {
+    fn get_scrypto_minor_version(&self) -> u64 {
+        SCRYPTO_V1_LATEST_MINOR_VERSION
+    }
+
+    fn get_wasm_disk_cache(&self) -> Option<&WasmDiskCache> {
+        Some(&self.disk_cache)
+    }
+}
+
+#[test]
+fn instrumented_code_is_reused_from_disk_cache() {
+    // Arrange
+    let directory = std::env::temp_dir().join(format!(
+        "radix-engine-tests-wasm-disk-cache-{}",
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&directory);
+    let vm_api = DiskCachingVmApi {
+        disk_cache: WasmDiskCache::new(
+            WasmDiskCacheOptions {
+                directory: directory.clone(),
+                max_size_in_bytes: 64 * 1024 * 1024,
+            },
+            &WasmValidatorConfigV1::new(),
+        ),
+    };
+    let (code, definition) = PackageLoader::get("address");
+
+    // Act
+    let instrumented_code =
+        VmPackageValidation::validate(&definition, VmType::ScryptoV1, &code, &vm_api).unwrap();
+    let cached_instrumented_code =
+        VmPackageValidation::validate(&definition, VmType::ScryptoV1, &code, &vm_api).unwrap();
+
+    // Assert
+    assert!(instrumented_code.is_some());
+    assert_eq!(instrumented_code, cached_instrumented_code);
+    assert_eq!(fs::read_dir(&directory).unwrap().count(), 1);
+
+    let _ = fs::remove_dir_all(&directory);
+}
+
+#[test]
+fn code_failing_validation_is_not_cached() {
+    // Arrange
+    let directory = std::env::temp_dir().join(format!(
+        "radix-engine-tests-wasm-disk-cache-invalid-{}",
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&directory);
+    let vm_api = DiskCachingVmApi {
+        disk_cache: WasmDiskCache::new(
+            WasmDiskCacheOptions {
+                directory: directory.clone(),
+                max_size_in_bytes: 64 * 1024 * 1024,
+            },
+            &WasmValidatorConfigV1::new(),
+        ),
+    };
+    let (_, definition) = PackageLoader::get("address");
+
+    // Act
+    let result = VmPackageValidation::validate(&definition, VmType::ScryptoV1, b"invalid", &vm_api);
+
+    // Assert
+    assert!(result.is_err());
+    assert!(!directory.exists());
+}
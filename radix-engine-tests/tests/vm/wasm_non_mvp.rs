@@ -1,7 +1,7 @@
-use radix_engine_tests::common::*;
 use paste::paste;
 use radix_engine::types::*;
 use radix_engine::vm::wasm::WasmModule;
+use radix_engine_tests::common::*;
 use scrypto_unit::*;
 use transaction::prelude::*;
 
@@ -107,3 +107,93 @@ fn test_wasm_non_mvp_mutable_globals_export() {
     // Assert
     assert!(receipt.is_commit_success());
 }
+
+fn bulk_memory_code() -> Vec<u8> {
+    let mut features = wabt::Features::new();
+    features.enable_bulk_memory();
+    wabt::wat2wasm_with_features(include_local_wasm_str!("bulk_memory.wat"), features).unwrap()
+}
+
+#[test]
+fn test_wasm_non_mvp_bulk_memory() {
+    // Arrange
+    let code = bulk_memory_code();
+
+    // Act
+    let mut test_runner = TestRunnerBuilder::new().build();
+    let package_address = test_runner.publish_package(
+        (code, single_function_package_definition("Test", "f")),
+        BTreeMap::new(),
+        OwnerRole::None,
+    );
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(package_address, "Test", "f", manifest_args!())
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    let outcome: u8 = receipt.expect_commit(true).output(1);
+    assert_eq!(outcome, 42);
+}
+
+#[test]
+fn test_wasm_non_mvp_bulk_memory_before_protocol_update() {
+    // Arrange
+    let code = bulk_memory_code();
+
+    // Act
+    let mut test_runner = TestRunnerBuilder::new()
+        .without_bulk_memory_update()
+        .build();
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .publish_package_advanced(
+            None,
+            code,
+            single_function_package_definition("Test", "f"),
+            BTreeMap::new(),
+            OwnerRole::None,
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    let error_message = receipt
+        .expect_commit_failure()
+        .outcome
+        .expect_failure()
+        .to_string();
+    assert!(error_message.contains("ValidationError"));
+}
+
+#[test]
+fn test_wasm_non_mvp_multi_value_rejected() {
+    // Arrange
+    let mut features = wabt::Features::new();
+    features.enable_multi_value();
+    let code =
+        wabt::wat2wasm_with_features(include_local_wasm_str!("multi_value.wat"), features).unwrap();
+
+    // Act
+    let mut test_runner = TestRunnerBuilder::new().build();
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .publish_package_advanced(
+            None,
+            code,
+            single_function_package_definition("Test", "f"),
+            BTreeMap::new(),
+            OwnerRole::None,
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    let error_message = receipt
+        .expect_commit_failure()
+        .outcome
+        .expect_failure()
+        .to_string();
+    assert!(error_message.contains("ValidationError"));
+}
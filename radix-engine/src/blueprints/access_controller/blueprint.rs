@@ -1,4 +1,5 @@
 use super::events::*;
+use super::package::AccessControllerV1MinorVersion;
 use super::state_machine::*;
 use crate::errors::{ApplicationError, RuntimeError};
 use crate::internal_prelude::*;
@@ -149,6 +150,13 @@ pub enum AccessControllerError {
         expected: Box<RecoveryProposal>,
         found: Box<RecoveryProposal>,
     },
+
+    /// Occurs when a resource other than XRD is contributed towards the recovery fees.
+    RecoveryFeeMustBeXrd { resource_address: ResourceAddress },
+
+    /// Occurs when a recovery fee is locked or withdrawn before any XRD was ever contributed
+    /// towards the recovery fees of the access controller.
+    XrdFeeVaultDoesNotExist,
 }
 
 impl From<AccessControllerError> for RuntimeError {
@@ -172,19 +180,35 @@ declare_native_blueprint_state! {
         }
     },
     collections: {
+        xrd_fee_vault: KeyValue {
+            entry_ident: XrdFeeVault,
+            key_type: {
+                kind: Static,
+                content_type: ResourceAddress,
+            },
+            value_type: {
+                kind: StaticSingleVersioned,
+            },
+            allow_ownership: true,
+        },
     }
 }
 
 pub type AccessControllerStateV1 = AccessControllerSubstate;
+pub type AccessControllerXrdFeeVaultV1 = Vault;
 
 pub struct AccessControllerBlueprint;
 
 impl AccessControllerBlueprint {
-    pub fn definition() -> BlueprintDefinitionInit {
+    pub fn definition(minor_version: AccessControllerV1MinorVersion) -> BlueprintDefinitionInit {
         let mut aggregator = TypeAggregator::<ScryptoCustomTypeKind>::new();
 
         let feature_set = AccessControllerFeatureSet::all_features();
-        let state = AccessControllerStateSchemaInit::create_schema_init(&mut aggregator);
+        let mut state = AccessControllerStateSchemaInit::create_schema_init(&mut aggregator);
+        // The XRD fee vault collection was only added in v1.1
+        if minor_version < AccessControllerV1MinorVersion::One {
+            state.collections.pop();
+        }
 
         let mut functions = index_map_new();
         functions.insert(
@@ -422,8 +446,47 @@ impl AccessControllerBlueprint {
                 export: ACCESS_CONTROLLER_MINT_RECOVERY_BADGES_IDENT.to_string(),
             },
         );
+        if minor_version >= AccessControllerV1MinorVersion::One {
+            functions.insert(
+                ACCESS_CONTROLLER_CONTRIBUTE_RECOVERY_FEE_IDENT.to_string(),
+                FunctionSchemaInit {
+                    receiver: Some(ReceiverInfo::normal_ref_mut()),
+                    input: TypeRef::Static(aggregator
+                        .add_child_type_and_descendents::<AccessControllerContributeRecoveryFeeInput>()),
+                    output: TypeRef::Static(aggregator
+                        .add_child_type_and_descendents::<AccessControllerContributeRecoveryFeeOutput>()),
+                    export: ACCESS_CONTROLLER_CONTRIBUTE_RECOVERY_FEE_IDENT.to_string(),
+                },
+            );
+            functions.insert(
+                ACCESS_CONTROLLER_LOCK_RECOVERY_FEE_IDENT.to_string(),
+                FunctionSchemaInit {
+                    receiver: Some(ReceiverInfo::normal_ref_mut()),
+                    input: TypeRef::Static(
+                        aggregator
+                            .add_child_type_and_descendents::<AccessControllerLockRecoveryFeeInput>(),
+                    ),
+                    output: TypeRef::Static(
+                        aggregator
+                            .add_child_type_and_descendents::<AccessControllerLockRecoveryFeeOutput>(),
+                    ),
+                    export: ACCESS_CONTROLLER_LOCK_RECOVERY_FEE_IDENT.to_string(),
+                },
+            );
+            functions.insert(
+                ACCESS_CONTROLLER_WITHDRAW_RECOVERY_FEE_IDENT.to_string(),
+                FunctionSchemaInit {
+                    receiver: Some(ReceiverInfo::normal_ref_mut()),
+                    input: TypeRef::Static(aggregator
+                        .add_child_type_and_descendents::<AccessControllerWithdrawRecoveryFeeInput>()),
+                    output: TypeRef::Static(aggregator
+                        .add_child_type_and_descendents::<AccessControllerWithdrawRecoveryFeeOutput>()),
+                    export: ACCESS_CONTROLLER_WITHDRAW_RECOVERY_FEE_IDENT.to_string(),
+                },
+            );
+        }
 
-        let events = event_schema! {
+        let mut events = event_schema! {
             aggregator,
             [
                 InitiateRecoveryEvent,
@@ -437,8 +500,69 @@ impl AccessControllerBlueprint {
                 CancelBadgeWithdrawAttemptEvent
             ]
         };
+        if minor_version >= AccessControllerV1MinorVersion::One {
+            events.event_schema.extend(
+                event_schema! {
+                    aggregator,
+                    [
+                        ContributeRecoveryFeeEvent,
+                        LockRecoveryFeeEvent,
+                        WithdrawRecoveryFeeEvent
+                    ]
+                }
+                .event_schema,
+            );
+        }
 
         let schema = generate_full_schema(aggregator);
+
+        let mut role_definition = roles_template!(
+            roles {
+                "primary" => updaters: [SELF_ROLE];
+                "recovery" => updaters: [SELF_ROLE];
+                "confirmation" => updaters: [SELF_ROLE];
+            },
+            methods {
+                ACCESS_CONTROLLER_TIMED_CONFIRM_RECOVERY_IDENT => MethodAccessibility::Public;
+
+                ACCESS_CONTROLLER_CREATE_PROOF_IDENT => ["primary"];
+
+                ACCESS_CONTROLLER_INITIATE_RECOVERY_AS_PRIMARY_IDENT => ["primary"];
+                ACCESS_CONTROLLER_CANCEL_PRIMARY_ROLE_RECOVERY_PROPOSAL_IDENT => ["primary"];
+                ACCESS_CONTROLLER_INITIATE_BADGE_WITHDRAW_ATTEMPT_AS_PRIMARY_IDENT => ["primary"];
+                ACCESS_CONTROLLER_CANCEL_PRIMARY_ROLE_BADGE_WITHDRAW_ATTEMPT_IDENT =>  ["primary"];
+
+                ACCESS_CONTROLLER_INITIATE_RECOVERY_AS_RECOVERY_IDENT => ["recovery"];
+                ACCESS_CONTROLLER_CANCEL_RECOVERY_ROLE_RECOVERY_PROPOSAL_IDENT => ["recovery"];
+                ACCESS_CONTROLLER_INITIATE_BADGE_WITHDRAW_ATTEMPT_AS_RECOVERY_IDENT => ["recovery"];
+                ACCESS_CONTROLLER_CANCEL_RECOVERY_ROLE_BADGE_WITHDRAW_ATTEMPT_IDENT => ["recovery"];
+
+                ACCESS_CONTROLLER_LOCK_PRIMARY_ROLE_IDENT => ["recovery"];
+                ACCESS_CONTROLLER_UNLOCK_PRIMARY_ROLE_IDENT => ["recovery"];
+
+                ACCESS_CONTROLLER_QUICK_CONFIRM_PRIMARY_ROLE_RECOVERY_PROPOSAL_IDENT => ["recovery", "confirmation"];
+                ACCESS_CONTROLLER_QUICK_CONFIRM_PRIMARY_ROLE_BADGE_WITHDRAW_ATTEMPT_IDENT => ["recovery", "confirmation"];
+
+                ACCESS_CONTROLLER_QUICK_CONFIRM_RECOVERY_ROLE_RECOVERY_PROPOSAL_IDENT => ["primary", "confirmation"];
+                ACCESS_CONTROLLER_QUICK_CONFIRM_RECOVERY_ROLE_BADGE_WITHDRAW_ATTEMPT_IDENT => ["primary", "confirmation"];
+
+                ACCESS_CONTROLLER_MINT_RECOVERY_BADGES_IDENT => ["primary", "recovery"];
+
+                ACCESS_CONTROLLER_STOP_TIMED_RECOVERY_IDENT => ["primary", "confirmation", "recovery"];
+            }
+        );
+        if minor_version >= AccessControllerV1MinorVersion::One {
+            role_definition.methods.extend(
+                roles_template!(
+                    methods {
+                        ACCESS_CONTROLLER_CONTRIBUTE_RECOVERY_FEE_IDENT => MethodAccessibility::Public;
+                        ACCESS_CONTROLLER_LOCK_RECOVERY_FEE_IDENT => ["recovery", "confirmation"];
+                        ACCESS_CONTROLLER_WITHDRAW_RECOVERY_FEE_IDENT => ["primary"];
+                    }
+                )
+                .methods,
+            );
+        }
         BlueprintDefinitionInit {
             blueprint_type: BlueprintType::default(),
             is_transient: false,
@@ -458,41 +582,7 @@ impl AccessControllerBlueprint {
             royalty_config: PackageRoyaltyConfig::default(),
             auth_config: AuthConfig {
                 function_auth: FunctionAuth::AllowAll,
-                method_auth: MethodAuthTemplate::StaticRoleDefinition(roles_template!(
-                    roles {
-                        "primary" => updaters: [SELF_ROLE];
-                        "recovery" => updaters: [SELF_ROLE];
-                        "confirmation" => updaters: [SELF_ROLE];
-                    },
-                    methods {
-                        ACCESS_CONTROLLER_TIMED_CONFIRM_RECOVERY_IDENT => MethodAccessibility::Public;
-
-                        ACCESS_CONTROLLER_CREATE_PROOF_IDENT => ["primary"];
-
-                        ACCESS_CONTROLLER_INITIATE_RECOVERY_AS_PRIMARY_IDENT => ["primary"];
-                        ACCESS_CONTROLLER_CANCEL_PRIMARY_ROLE_RECOVERY_PROPOSAL_IDENT => ["primary"];
-                        ACCESS_CONTROLLER_INITIATE_BADGE_WITHDRAW_ATTEMPT_AS_PRIMARY_IDENT => ["primary"];
-                        ACCESS_CONTROLLER_CANCEL_PRIMARY_ROLE_BADGE_WITHDRAW_ATTEMPT_IDENT =>  ["primary"];
-
-                        ACCESS_CONTROLLER_INITIATE_RECOVERY_AS_RECOVERY_IDENT => ["recovery"];
-                        ACCESS_CONTROLLER_CANCEL_RECOVERY_ROLE_RECOVERY_PROPOSAL_IDENT => ["recovery"];
-                        ACCESS_CONTROLLER_INITIATE_BADGE_WITHDRAW_ATTEMPT_AS_RECOVERY_IDENT => ["recovery"];
-                        ACCESS_CONTROLLER_CANCEL_RECOVERY_ROLE_BADGE_WITHDRAW_ATTEMPT_IDENT => ["recovery"];
-
-                        ACCESS_CONTROLLER_LOCK_PRIMARY_ROLE_IDENT => ["recovery"];
-                        ACCESS_CONTROLLER_UNLOCK_PRIMARY_ROLE_IDENT => ["recovery"];
-
-                        ACCESS_CONTROLLER_QUICK_CONFIRM_PRIMARY_ROLE_RECOVERY_PROPOSAL_IDENT => ["recovery", "confirmation"];
-                        ACCESS_CONTROLLER_QUICK_CONFIRM_PRIMARY_ROLE_BADGE_WITHDRAW_ATTEMPT_IDENT => ["recovery", "confirmation"];
-
-                        ACCESS_CONTROLLER_QUICK_CONFIRM_RECOVERY_ROLE_RECOVERY_PROPOSAL_IDENT => ["primary", "confirmation"];
-                        ACCESS_CONTROLLER_QUICK_CONFIRM_RECOVERY_ROLE_BADGE_WITHDRAW_ATTEMPT_IDENT => ["primary", "confirmation"];
-
-                        ACCESS_CONTROLLER_MINT_RECOVERY_BADGES_IDENT => ["primary", "recovery"];
-
-                        ACCESS_CONTROLLER_STOP_TIMED_RECOVERY_IDENT => ["primary", "confirmation", "recovery"];
-                    }
-                )),
+                method_auth: MethodAuthTemplate::StaticRoleDefinition(role_definition),
             },
         }
     }
@@ -1138,6 +1228,69 @@ impl AccessControllerBlueprint {
 
         Ok(IndexedScryptoValue::from_slice(&rtn).unwrap())
     }
+
+    pub fn contribute_recovery_fee<Y>(
+        input: &IndexedScryptoValue,
+        api: &mut Y,
+    ) -> Result<IndexedScryptoValue, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let input: AccessControllerContributeRecoveryFeeInput = input
+            .as_typed()
+            .map_err(|e| RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e)))?;
+        let amount = input.bucket.amount(api)?;
+
+        transition(
+            api,
+            AccessControllerContributeRecoveryFeeStateMachineInput {
+                bucket: input.bucket,
+            },
+        )?;
+        Runtime::emit_event(api, ContributeRecoveryFeeEvent { amount })?;
+
+        Ok(IndexedScryptoValue::from_typed(&()))
+    }
+
+    pub fn lock_recovery_fee<Y>(
+        input: &IndexedScryptoValue,
+        api: &mut Y,
+    ) -> Result<IndexedScryptoValue, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let AccessControllerLockRecoveryFeeInput { amount } = input
+            .as_typed()
+            .map_err(|e| RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e)))?;
+
+        transition(
+            api,
+            AccessControllerLockRecoveryFeeStateMachineInput { amount },
+        )?;
+        Runtime::emit_event(api, LockRecoveryFeeEvent { amount })?;
+
+        Ok(IndexedScryptoValue::from_typed(&()))
+    }
+
+    pub fn withdraw_recovery_fee<Y>(
+        input: &IndexedScryptoValue,
+        api: &mut Y,
+    ) -> Result<IndexedScryptoValue, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let AccessControllerWithdrawRecoveryFeeInput { amount } = input
+            .as_typed()
+            .map_err(|e| RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e)))?;
+
+        let bucket = transition(
+            api,
+            AccessControllerWithdrawRecoveryFeeStateMachineInput { amount },
+        )?;
+        Runtime::emit_event(api, WithdrawRecoveryFeeEvent { amount })?;
+
+        Ok(IndexedScryptoValue::from_typed(&bucket))
+    }
 }
 
 //=========
@@ -1219,6 +1372,58 @@ where
     Ok(rtn)
 }
 
+/// Calls the given function with the XRD fee vault of the access controller. The vault is only
+/// created when `create` is set, which is the case for contributions; locking or withdrawing
+/// fees before any XRD was ever contributed fails.
+pub(super) fn with_xrd_fee_vault<Y, F, R>(
+    create: bool,
+    vault_fn: F,
+    api: &mut Y,
+) -> Result<R, RuntimeError>
+where
+    Y: ClientApi<RuntimeError>,
+    F: FnOnce(&mut Vault, &mut Y) -> Result<R, RuntimeError>,
+{
+    let encoded_key = scrypto_encode(&XRD).expect("Impossible Case!");
+
+    let mut handle = api.actor_open_key_value_entry(
+        ACTOR_STATE_SELF,
+        AccessControllerCollection::XrdFeeVaultKeyValue.collection_index(),
+        &encoded_key,
+        LockFlags::read_only(),
+    )?;
+
+    let entry = api
+        .key_value_entry_get_typed::<AccessControllerXrdFeeVaultEntryPayload>(handle)?
+        .map(|entry| entry.into_latest());
+
+    let mut vault = match entry {
+        Some(vault) => vault,
+        None if create => {
+            api.key_value_entry_close(handle)?;
+            handle = api.actor_open_key_value_entry(
+                ACTOR_STATE_SELF,
+                AccessControllerCollection::XrdFeeVaultKeyValue.collection_index(),
+                &encoded_key,
+                LockFlags::MUTABLE,
+            )?;
+            let vault = Vault::create(XRD, api)?;
+            let own = vault.0;
+            api.key_value_entry_set_typed(
+                handle,
+                &AccessControllerXrdFeeVaultEntryPayload::from_content_source(vault),
+            )?;
+            Vault(own)
+        }
+        None => return Err(AccessControllerError::XrdFeeVaultDoesNotExist.into()),
+    };
+
+    let rtn = vault_fn(&mut vault, api)?;
+    api.key_value_entry_close(handle)?;
+
+    Ok(rtn)
+}
+
 fn update_role_assignment<Y>(
     api: &mut Y,
     receiver: &NodeId,
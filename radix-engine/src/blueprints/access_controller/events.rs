@@ -41,3 +41,18 @@ pub struct UnlockPrimaryRoleEvent;
 
 #[derive(ScryptoSbor, ScryptoEvent, Debug)]
 pub struct StopTimedRecoveryEvent;
+
+#[derive(ScryptoSbor, ScryptoEvent, Debug, PartialEq, Eq)]
+pub struct ContributeRecoveryFeeEvent {
+    pub amount: Decimal,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Debug, PartialEq, Eq)]
+pub struct LockRecoveryFeeEvent {
+    pub amount: Decimal,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Debug, PartialEq, Eq)]
+pub struct WithdrawRecoveryFeeEvent {
+    pub amount: Decimal,
+}
@@ -9,12 +9,19 @@ use radix_engine_interface::blueprints::package::PackageDefinition;
 use radix_engine_interface::*;
 use sbor::rust::prelude::*;
 
+/// The minor version of the Access Controller V1 package
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Sbor)]
+pub enum AccessControllerV1MinorVersion {
+    Zero,
+    One,
+}
+
 pub struct AccessControllerNativePackage;
 
 impl AccessControllerNativePackage {
-    pub fn definition() -> PackageDefinition {
+    pub fn definition(minor_version: AccessControllerV1MinorVersion) -> PackageDefinition {
         let blueprints = indexmap!(
-            ACCESS_CONTROLLER_BLUEPRINT.to_string() => AccessControllerBlueprint::definition()
+            ACCESS_CONTROLLER_BLUEPRINT.to_string() => AccessControllerBlueprint::definition(minor_version)
         );
 
         PackageDefinition { blueprints }
@@ -97,6 +104,15 @@ impl AccessControllerNativePackage {
             ACCESS_CONTROLLER_MINT_RECOVERY_BADGES_IDENT => {
                 AccessControllerBlueprint::mint_recovery_badges(input, api)
             }
+            ACCESS_CONTROLLER_CONTRIBUTE_RECOVERY_FEE_IDENT => {
+                AccessControllerBlueprint::contribute_recovery_fee(input, api)
+            }
+            ACCESS_CONTROLLER_LOCK_RECOVERY_FEE_IDENT => {
+                AccessControllerBlueprint::lock_recovery_fee(input, api)
+            }
+            ACCESS_CONTROLLER_WITHDRAW_RECOVERY_FEE_IDENT => {
+                AccessControllerBlueprint::withdraw_recovery_fee(input, api)
+            }
             _ => Err(RuntimeError::ApplicationError(
                 ApplicationError::ExportDoesNotExist(export_name.to_string()),
             )),
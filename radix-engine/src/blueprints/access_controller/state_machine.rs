@@ -1,5 +1,6 @@
 use crate::errors::ApplicationError;
 use crate::errors::RuntimeError;
+use native_sdk::resource::NativeBucket;
 use native_sdk::resource::NativeFungibleVault;
 use native_sdk::resource::NativeNonFungibleVault;
 use native_sdk::resource::NativeVault;
@@ -8,9 +9,12 @@ use radix_engine_interface::api::*;
 use radix_engine_interface::blueprints::access_controller::*;
 use radix_engine_interface::blueprints::consensus_manager::TimePrecision;
 use radix_engine_interface::blueprints::resource::*;
+use radix_engine_interface::constants::XRD;
+use radix_engine_interface::math::Decimal;
 use radix_engine_interface::time::TimeComparisonOperator;
 use sbor::rust::boxed::Box;
 
+use super::with_xrd_fee_vault;
 use super::PrimaryRoleBadgeWithdrawAttemptState;
 use super::RecoveryRoleBadgeWithdrawAttemptState;
 use super::{
@@ -717,3 +721,82 @@ fn validate_recovery_proposal(
         })
     }
 }
+
+pub(super) struct AccessControllerContributeRecoveryFeeStateMachineInput {
+    pub bucket: Bucket,
+}
+
+impl Transition<AccessControllerContributeRecoveryFeeStateMachineInput>
+    for AccessControllerSubstate
+{
+    type Output = ();
+
+    fn transition<Y>(
+        &self,
+        api: &mut Y,
+        input: AccessControllerContributeRecoveryFeeStateMachineInput,
+    ) -> Result<Self::Output, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        // Anybody can contribute XRD towards the recovery fees, regardless of the state of the
+        // access controller.
+        let resource_address = input.bucket.resource_address(api)?;
+        if resource_address != XRD {
+            return Err(RuntimeError::ApplicationError(
+                ApplicationError::AccessControllerError(
+                    AccessControllerError::RecoveryFeeMustBeXrd { resource_address },
+                ),
+            ));
+        }
+
+        with_xrd_fee_vault(true, |vault, api| vault.put(input.bucket, api), api)
+    }
+}
+
+pub(super) struct AccessControllerLockRecoveryFeeStateMachineInput {
+    pub amount: Decimal,
+}
+
+impl Transition<AccessControllerLockRecoveryFeeStateMachineInput> for AccessControllerSubstate {
+    type Output = ();
+
+    fn transition<Y>(
+        &self,
+        api: &mut Y,
+        input: AccessControllerLockRecoveryFeeStateMachineInput,
+    ) -> Result<Self::Output, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        // Fees can be locked in all states since the transactions driving a recovery are the ones
+        // which are submitted while the primary role is lost, locked, or being recovered.
+        with_xrd_fee_vault(false, |vault, api| vault.lock_fee(api, input.amount), api)
+    }
+}
+
+pub(super) struct AccessControllerWithdrawRecoveryFeeStateMachineInput {
+    pub amount: Decimal,
+}
+
+impl Transition<AccessControllerWithdrawRecoveryFeeStateMachineInput> for AccessControllerSubstate {
+    type Output = Bucket;
+
+    fn transition<Y>(
+        &self,
+        api: &mut Y,
+        input: AccessControllerWithdrawRecoveryFeeStateMachineInput,
+    ) -> Result<Self::Output, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        // Just like proofs, recovery fees can only be withdrawn when the primary role is unlocked -
+        // regardless of any pending recovery or withdraw attempts.
+        match self.state {
+            (PrimaryRoleLockingState::Unlocked, _, _, _, _) => {
+                with_xrd_fee_vault(false, |vault, api| vault.take(input.amount, api), api)
+            }
+            _ => access_controller_runtime_error!(OperationRequiresUnlockedPrimaryRole),
+        }
+    }
+}
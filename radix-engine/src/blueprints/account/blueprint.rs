@@ -29,18 +29,51 @@ use radix_engine_interface::metadata_init;
 
 pub const ACCOUNT_CREATE_VIRTUAL_SECP256K1_ID: u8 = 0u8;
 pub const ACCOUNT_CREATE_VIRTUAL_ED25519_ID: u8 = 1u8;
+pub const ACCOUNT_CREATE_VIRTUAL_SECP256R1_ID: u8 = 2u8;
 
 #[derive(Debug, PartialEq, Eq, ScryptoSbor, Clone)]
 pub struct AccountSubstate {
     pub default_deposit_rule: DefaultDepositRule,
 }
 
+/// The key of an allowance: the grantee and the resource which it can withdraw.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, ScryptoSbor)]
+pub struct AllowanceKey {
+    pub grantee: AllowanceGrantee,
+    pub resource_address: ResourceAddress,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
 pub enum AccountError {
-    VaultDoesNotExist { resource_address: ResourceAddress },
-    DepositIsDisallowed { resource_address: ResourceAddress },
+    VaultDoesNotExist {
+        resource_address: ResourceAddress,
+    },
+    DepositIsDisallowed {
+        resource_address: ResourceAddress,
+    },
     NotAllBucketsCouldBeDeposited,
-    NotAnAuthorizedDepositor { depositor: ResourceOrNonFungible },
+    NotAnAuthorizedDepositor {
+        depositor: ResourceOrNonFungible,
+    },
+    InvalidAllowanceAmount {
+        amount: Decimal,
+    },
+    InvalidAllowanceResetPeriod,
+    AllowanceDoesNotExist {
+        grantee: AllowanceGrantee,
+        resource_address: ResourceAddress,
+    },
+    AllowanceExpired {
+        grantee: AllowanceGrantee,
+        resource_address: ResourceAddress,
+        expiry_epoch: Epoch,
+    },
+    AllowanceExceeded {
+        grantee: AllowanceGrantee,
+        resource_address: ResourceAddress,
+        remaining_amount: Decimal,
+        amount: Decimal,
+    },
 }
 
 impl From<AccountError> for RuntimeError {
@@ -109,6 +142,17 @@ declare_native_blueprint_state! {
             },
             allow_ownership: false,
         },
+        allowances: KeyValue {
+            entry_ident: Allowance,
+            key_type: {
+                kind: Static,
+                content_type: AllowanceKey,
+            },
+            value_type: {
+                kind: StaticSingleVersioned,
+            },
+            allow_ownership: false,
+        },
     }
 }
 
@@ -116,15 +160,20 @@ pub type AccountDepositRuleV1 = AccountSubstate;
 pub type AccountResourceVaultV1 = Vault;
 pub type AccountResourcePreferenceV1 = ResourcePreference;
 pub type AccountAuthorizedDepositorV1 = ();
+pub type AccountAllowanceV1 = Allowance;
 
 pub struct AccountBlueprint;
 
 impl AccountBlueprint {
-    pub fn get_definition() -> BlueprintDefinitionInit {
+    pub fn get_definition(minor_version: AccountV1MinorVersion) -> BlueprintDefinitionInit {
         let mut aggregator = TypeAggregator::<ScryptoCustomTypeKind>::new();
 
         let feature_set = AccountFeatureSet::all_features();
-        let state = AccountStateSchemaInit::create_schema_init(&mut aggregator);
+        let mut state = AccountStateSchemaInit::create_schema_init(&mut aggregator);
+        // The allowances collection is the last one and was only added in v1.1
+        if minor_version < AccountV1MinorVersion::One {
+            state.collections.pop();
+        }
 
         let mut functions = index_map_new();
 
@@ -480,7 +529,67 @@ impl AccountBlueprint {
             },
         );
 
-        let events = event_schema! {
+        if minor_version >= AccountV1MinorVersion::One {
+            functions.insert(
+                ACCOUNT_SET_ALLOWANCE_IDENT.to_string(),
+                FunctionSchemaInit {
+                    receiver: Some(ReceiverInfo::normal_ref_mut()),
+                    input: TypeRef::Static(
+                        aggregator.add_child_type_and_descendents::<AccountSetAllowanceInput>(),
+                    ),
+                    output: TypeRef::Static(
+                        aggregator.add_child_type_and_descendents::<AccountSetAllowanceOutput>(),
+                    ),
+                    export: ACCOUNT_SET_ALLOWANCE_IDENT.to_string(),
+                },
+            );
+
+            functions.insert(
+                ACCOUNT_REMOVE_ALLOWANCE_IDENT.to_string(),
+                FunctionSchemaInit {
+                    receiver: Some(ReceiverInfo::normal_ref_mut()),
+                    input: TypeRef::Static(
+                        aggregator.add_child_type_and_descendents::<AccountRemoveAllowanceInput>(),
+                    ),
+                    output: TypeRef::Static(
+                        aggregator.add_child_type_and_descendents::<AccountRemoveAllowanceOutput>(),
+                    ),
+                    export: ACCOUNT_REMOVE_ALLOWANCE_IDENT.to_string(),
+                },
+            );
+
+            functions.insert(
+                ACCOUNT_GET_ALLOWANCE_IDENT.to_string(),
+                FunctionSchemaInit {
+                    receiver: Some(ReceiverInfo::normal_ref()),
+                    input: TypeRef::Static(
+                        aggregator.add_child_type_and_descendents::<AccountGetAllowanceInput>(),
+                    ),
+                    output: TypeRef::Static(
+                        aggregator.add_child_type_and_descendents::<AccountGetAllowanceOutput>(),
+                    ),
+                    export: ACCOUNT_GET_ALLOWANCE_IDENT.to_string(),
+                },
+            );
+
+            functions.insert(
+                ACCOUNT_WITHDRAW_WITH_ALLOWANCE_IDENT.to_string(),
+                FunctionSchemaInit {
+                    receiver: Some(ReceiverInfo::normal_ref_mut()),
+                    input: TypeRef::Static(
+                        aggregator
+                            .add_child_type_and_descendents::<AccountWithdrawWithAllowanceInput>(),
+                    ),
+                    output: TypeRef::Static(
+                        aggregator
+                            .add_child_type_and_descendents::<AccountWithdrawWithAllowanceOutput>(),
+                    ),
+                    export: ACCOUNT_WITHDRAW_WITH_ALLOWANCE_IDENT.to_string(),
+                },
+            );
+        }
+
+        let mut events = event_schema! {
             aggregator,
             [
                 WithdrawEvent,
@@ -493,19 +602,85 @@ impl AccountBlueprint {
                 RemoveAuthorizedDepositorEvent,
             ]
         };
+        if minor_version >= AccountV1MinorVersion::One {
+            events.event_schema.extend(
+                event_schema! {
+                    aggregator,
+                    [
+                        SetAllowanceEvent,
+                        RemoveAllowanceEvent,
+                        AllowanceWithdrawEvent,
+                    ]
+                }
+                .event_schema,
+            );
+        }
 
         let schema = generate_full_schema(aggregator);
 
+        let mut role_definition = roles_template!(
+            roles {
+                SECURIFY_ROLE => updaters: [SELF_ROLE];
+            },
+            methods {
+                ACCOUNT_SECURIFY_IDENT => [SECURIFY_ROLE];
+
+                ACCOUNT_SET_DEFAULT_DEPOSIT_RULE_IDENT => [OWNER_ROLE];
+                ACCOUNT_SET_RESOURCE_PREFERENCE_IDENT => [OWNER_ROLE];
+                ACCOUNT_REMOVE_RESOURCE_PREFERENCE_IDENT => [OWNER_ROLE];
+                ACCOUNT_WITHDRAW_IDENT => [OWNER_ROLE];
+                ACCOUNT_WITHDRAW_NON_FUNGIBLES_IDENT => [OWNER_ROLE];
+                ACCOUNT_LOCK_FEE_IDENT => [OWNER_ROLE];
+                ACCOUNT_LOCK_CONTINGENT_FEE_IDENT => [OWNER_ROLE];
+                ACCOUNT_LOCK_FEE_AND_WITHDRAW_IDENT => [OWNER_ROLE];
+                ACCOUNT_LOCK_FEE_AND_WITHDRAW_NON_FUNGIBLES_IDENT => [OWNER_ROLE];
+                ACCOUNT_CREATE_PROOF_OF_AMOUNT_IDENT => [OWNER_ROLE];
+                ACCOUNT_CREATE_PROOF_OF_NON_FUNGIBLES_IDENT => [OWNER_ROLE];
+                ACCOUNT_DEPOSIT_IDENT => [OWNER_ROLE];
+                ACCOUNT_DEPOSIT_BATCH_IDENT => [OWNER_ROLE];
+                ACCOUNT_BURN_IDENT => [OWNER_ROLE];
+                ACCOUNT_BURN_NON_FUNGIBLES_IDENT => [OWNER_ROLE];
+                ACCOUNT_ADD_AUTHORIZED_DEPOSITOR => [OWNER_ROLE];
+                ACCOUNT_REMOVE_AUTHORIZED_DEPOSITOR => [OWNER_ROLE];
+
+                ACCOUNT_TRY_DEPOSIT_OR_REFUND_IDENT => MethodAccessibility::Public;
+                ACCOUNT_TRY_DEPOSIT_BATCH_OR_REFUND_IDENT => MethodAccessibility::Public;
+                ACCOUNT_TRY_DEPOSIT_OR_ABORT_IDENT => MethodAccessibility::Public;
+                ACCOUNT_TRY_DEPOSIT_BATCH_OR_ABORT_IDENT => MethodAccessibility::Public;
+            }
+        );
+        if minor_version >= AccountV1MinorVersion::One {
+            role_definition.methods.extend(
+                roles_template!(
+                    methods {
+                        ACCOUNT_SET_ALLOWANCE_IDENT => [OWNER_ROLE];
+                        ACCOUNT_REMOVE_ALLOWANCE_IDENT => [OWNER_ROLE];
+
+                        ACCOUNT_GET_ALLOWANCE_IDENT => MethodAccessibility::Public;
+                        // The presence of the grantee is asserted by the method itself
+                        ACCOUNT_WITHDRAW_WITH_ALLOWANCE_IDENT => MethodAccessibility::Public;
+                    }
+                )
+                .methods,
+            );
+        }
+
+        let mut dependencies = indexset!(
+            SECP256K1_SIGNATURE_VIRTUAL_BADGE.into(),
+            ED25519_SIGNATURE_VIRTUAL_BADGE.into(),
+            ACCOUNT_OWNER_BADGE.into(),
+            PACKAGE_OF_DIRECT_CALLER_VIRTUAL_BADGE.into(),
+        );
+        // Secp256r1 accounts are only virtualized once the badge is a dependency, from v1.2
+        if minor_version >= AccountV1MinorVersion::Two {
+            dependencies.insert(SECP256R1_SIGNATURE_VIRTUAL_BADGE.into());
+        }
+
         BlueprintDefinitionInit {
             blueprint_type: BlueprintType::default(),
             is_transient: false,
             feature_set,
-            dependencies: indexset!(
-                SECP256K1_SIGNATURE_VIRTUAL_BADGE.into(),
-                ED25519_SIGNATURE_VIRTUAL_BADGE.into(),
-                ACCOUNT_OWNER_BADGE.into(),
-                PACKAGE_OF_DIRECT_CALLER_VIRTUAL_BADGE.into(),
-            ),
+            dependencies,
 
             schema: BlueprintSchemaInit {
                 generics: vec![],
@@ -522,37 +697,7 @@ impl AccountBlueprint {
             royalty_config: PackageRoyaltyConfig::default(),
             auth_config: AuthConfig {
                 function_auth: FunctionAuth::AllowAll,
-                method_auth: MethodAuthTemplate::StaticRoleDefinition(roles_template!(
-                    roles {
-                        SECURIFY_ROLE => updaters: [SELF_ROLE];
-                    },
-                    methods {
-                        ACCOUNT_SECURIFY_IDENT => [SECURIFY_ROLE];
-
-                        ACCOUNT_SET_DEFAULT_DEPOSIT_RULE_IDENT => [OWNER_ROLE];
-                        ACCOUNT_SET_RESOURCE_PREFERENCE_IDENT => [OWNER_ROLE];
-                        ACCOUNT_REMOVE_RESOURCE_PREFERENCE_IDENT => [OWNER_ROLE];
-                        ACCOUNT_WITHDRAW_IDENT => [OWNER_ROLE];
-                        ACCOUNT_WITHDRAW_NON_FUNGIBLES_IDENT => [OWNER_ROLE];
-                        ACCOUNT_LOCK_FEE_IDENT => [OWNER_ROLE];
-                        ACCOUNT_LOCK_CONTINGENT_FEE_IDENT => [OWNER_ROLE];
-                        ACCOUNT_LOCK_FEE_AND_WITHDRAW_IDENT => [OWNER_ROLE];
-                        ACCOUNT_LOCK_FEE_AND_WITHDRAW_NON_FUNGIBLES_IDENT => [OWNER_ROLE];
-                        ACCOUNT_CREATE_PROOF_OF_AMOUNT_IDENT => [OWNER_ROLE];
-                        ACCOUNT_CREATE_PROOF_OF_NON_FUNGIBLES_IDENT => [OWNER_ROLE];
-                        ACCOUNT_DEPOSIT_IDENT => [OWNER_ROLE];
-                        ACCOUNT_DEPOSIT_BATCH_IDENT => [OWNER_ROLE];
-                        ACCOUNT_BURN_IDENT => [OWNER_ROLE];
-                        ACCOUNT_BURN_NON_FUNGIBLES_IDENT => [OWNER_ROLE];
-                        ACCOUNT_ADD_AUTHORIZED_DEPOSITOR => [OWNER_ROLE];
-                        ACCOUNT_REMOVE_AUTHORIZED_DEPOSITOR => [OWNER_ROLE];
-
-                        ACCOUNT_TRY_DEPOSIT_OR_REFUND_IDENT => MethodAccessibility::Public;
-                        ACCOUNT_TRY_DEPOSIT_BATCH_OR_REFUND_IDENT => MethodAccessibility::Public;
-                        ACCOUNT_TRY_DEPOSIT_OR_ABORT_IDENT => MethodAccessibility::Public;
-                        ACCOUNT_TRY_DEPOSIT_BATCH_OR_ABORT_IDENT => MethodAccessibility::Public;
-                    }
-                )),
+                method_auth: MethodAuthTemplate::StaticRoleDefinition(role_definition),
             },
         }
     }
@@ -592,6 +737,10 @@ impl AccountBlueprint {
                 let public_key_hash = PublicKeyHash::Ed25519(Ed25519PublicKeyHash(input.rid));
                 Self::create_virtual(public_key_hash, input.address_reservation, api)
             }
+            ACCOUNT_CREATE_VIRTUAL_SECP256R1_ID => {
+                let public_key_hash = PublicKeyHash::Secp256r1(Secp256r1PublicKeyHash(input.rid));
+                Self::create_virtual(public_key_hash, input.address_reservation, api)
+            }
             x => Err(RuntimeError::ApplicationError(
                 ApplicationError::PanicMessage(format!("Unexpected variant id: {:?}", x)),
             )),
@@ -611,6 +760,7 @@ impl AccountBlueprint {
             let entity_type = match public_key_hash {
                 PublicKeyHash::Ed25519(..) => EntityType::GlobalVirtualEd25519Account,
                 PublicKeyHash::Secp256k1(..) => EntityType::GlobalVirtualSecp256k1Account,
+                PublicKeyHash::Secp256r1(..) => EntityType::GlobalVirtualSecp256r1Account,
             };
 
             let mut id_bytes = vec![entity_type as u8];
@@ -1271,6 +1421,254 @@ impl AccountBlueprint {
         Ok(())
     }
 
+    pub fn set_allowance<Y>(
+        grantee: AllowanceGrantee,
+        resource_address: ResourceAddress,
+        amount: Option<Decimal>,
+        reset_period: Option<u64>,
+        expiry_epoch: Option<Epoch>,
+        api: &mut Y,
+    ) -> Result<(), RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        if let Some(amount) = amount {
+            if amount.is_negative() {
+                return Err(AccountError::InvalidAllowanceAmount { amount }.into());
+            }
+        }
+        if reset_period == Some(0) {
+            return Err(AccountError::InvalidAllowanceResetPeriod.into());
+        }
+
+        let allowance = Allowance {
+            amount,
+            remaining_amount: amount,
+            reset_period,
+            period_start_epoch: Runtime::current_epoch(api)?,
+            expiry_epoch,
+        };
+
+        let encoded_key = scrypto_encode(&AllowanceKey {
+            grantee: grantee.clone(),
+            resource_address,
+        })
+        .expect("Failed to SBOR encode an `AllowanceKey`.");
+        let kv_store_entry_lock_handle = api.actor_open_key_value_entry(
+            ACTOR_STATE_SELF,
+            AccountCollection::AllowanceKeyValue.collection_index(),
+            &encoded_key,
+            LockFlags::MUTABLE,
+        )?;
+        api.key_value_entry_set_typed(
+            kv_store_entry_lock_handle,
+            &AccountAllowanceEntryPayload::from_content_source(allowance.clone()),
+        )?;
+        api.key_value_entry_close(kv_store_entry_lock_handle)?;
+
+        Runtime::emit_event(
+            api,
+            SetAllowanceEvent {
+                grantee,
+                resource_address,
+                allowance,
+            },
+        )?;
+
+        Ok(())
+    }
+
+    pub fn remove_allowance<Y>(
+        grantee: AllowanceGrantee,
+        resource_address: ResourceAddress,
+        api: &mut Y,
+    ) -> Result<(), RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let encoded_key = scrypto_encode(&AllowanceKey {
+            grantee: grantee.clone(),
+            resource_address,
+        })
+        .expect("Failed to SBOR encode an `AllowanceKey`.");
+        api.actor_remove_key_value_entry(
+            ACTOR_STATE_SELF,
+            AccountCollection::AllowanceKeyValue.collection_index(),
+            &encoded_key,
+        )?;
+
+        Runtime::emit_event(
+            api,
+            RemoveAllowanceEvent {
+                grantee,
+                resource_address,
+            },
+        )?;
+
+        Ok(())
+    }
+
+    /// Returns the allowance as of the current epoch, or `None` if there is no allowance for the
+    /// grantee and resource.
+    pub fn get_allowance<Y>(
+        grantee: AllowanceGrantee,
+        resource_address: ResourceAddress,
+        api: &mut Y,
+    ) -> Result<Option<Allowance>, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let current_epoch = Runtime::current_epoch(api)?;
+
+        let encoded_key = scrypto_encode(&AllowanceKey {
+            grantee,
+            resource_address,
+        })
+        .expect("Failed to SBOR encode an `AllowanceKey`.");
+        let kv_store_entry_lock_handle = api.actor_open_key_value_entry(
+            ACTOR_STATE_SELF,
+            AccountCollection::AllowanceKeyValue.collection_index(),
+            &encoded_key,
+            LockFlags::read_only(),
+        )?;
+        let allowance = api
+            .key_value_entry_get_typed::<AccountAllowanceEntryPayload>(kv_store_entry_lock_handle)?
+            .map(|v| Self::allowance_at_epoch(v.into_latest(), current_epoch));
+        api.key_value_entry_close(kv_store_entry_lock_handle)?;
+
+        Ok(allowance)
+    }
+
+    /// Method is public to all - the grantee of the allowance must be present for the withdrawal
+    /// to go through, and the withdrawn amount is deducted from the allowance.
+    pub fn withdraw_with_allowance<Y>(
+        grantee: AllowanceGrantee,
+        resource_address: ResourceAddress,
+        amount: Decimal,
+        api: &mut Y,
+    ) -> Result<Bucket, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        Self::validate_grantee_is_present(&grantee, api)?;
+        let current_epoch = Runtime::current_epoch(api)?;
+
+        let encoded_key = scrypto_encode(&AllowanceKey {
+            grantee: grantee.clone(),
+            resource_address,
+        })
+        .expect("Failed to SBOR encode an `AllowanceKey`.");
+        let kv_store_entry_lock_handle = api.actor_open_key_value_entry(
+            ACTOR_STATE_SELF,
+            AccountCollection::AllowanceKeyValue.collection_index(),
+            &encoded_key,
+            LockFlags::MUTABLE,
+        )?;
+        let entry = api
+            .key_value_entry_get_typed::<AccountAllowanceEntryPayload>(kv_store_entry_lock_handle)?
+            .map(|v| Self::allowance_at_epoch(v.into_latest(), current_epoch));
+
+        let allowance = match entry {
+            Some(allowance) => {
+                Self::spend_allowance(allowance, &grantee, resource_address, amount, current_epoch)
+            }
+            None => Err(AccountError::AllowanceDoesNotExist {
+                grantee: grantee.clone(),
+                resource_address,
+            }),
+        };
+        let allowance = match allowance {
+            Ok(allowance) => allowance,
+            Err(error) => {
+                api.key_value_entry_close(kv_store_entry_lock_handle)?;
+                return Err(error.into());
+            }
+        };
+        let remaining_amount = allowance.remaining_amount;
+        api.key_value_entry_set_typed(
+            kv_store_entry_lock_handle,
+            &AccountAllowanceEntryPayload::from_content_source(allowance),
+        )?;
+        api.key_value_entry_close(kv_store_entry_lock_handle)?;
+
+        let bucket = Self::withdraw(resource_address, amount, api)?;
+        Runtime::emit_event(
+            api,
+            AllowanceWithdrawEvent {
+                grantee,
+                resource_address,
+                amount,
+                remaining_amount,
+            },
+        )?;
+
+        Ok(bucket)
+    }
+
+    fn validate_grantee_is_present<Y>(
+        grantee: &AllowanceGrantee,
+        api: &mut Y,
+    ) -> Result<(), RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let badge = match grantee {
+            AllowanceGrantee::Badge(badge) => badge.clone(),
+            AllowanceGrantee::Component(component_address) => ResourceOrNonFungible::NonFungible(
+                NonFungibleGlobalId::global_caller_badge(*component_address),
+            ),
+        };
+        Self::validate_badge_is_present(badge, api)
+    }
+
+    /// Returns the allowance as of the given epoch. The remaining amount is reset to the full
+    /// amount if one or more reset periods have elapsed since the current period started.
+    fn allowance_at_epoch(mut allowance: Allowance, epoch: Epoch) -> Allowance {
+        if let Some(reset_period) = allowance.reset_period {
+            let period_start = allowance.period_start_epoch.number();
+            let elapsed_periods = epoch.number().saturating_sub(period_start) / reset_period;
+            if elapsed_periods > 0 {
+                allowance.period_start_epoch =
+                    Epoch::of(period_start + elapsed_periods * reset_period);
+                allowance.remaining_amount = allowance.amount;
+            }
+        }
+        allowance
+    }
+
+    fn spend_allowance(
+        mut allowance: Allowance,
+        grantee: &AllowanceGrantee,
+        resource_address: ResourceAddress,
+        amount: Decimal,
+        epoch: Epoch,
+    ) -> Result<Allowance, AccountError> {
+        if let Some(expiry_epoch) = allowance.expiry_epoch {
+            if epoch >= expiry_epoch {
+                return Err(AccountError::AllowanceExpired {
+                    grantee: grantee.clone(),
+                    resource_address,
+                    expiry_epoch,
+                });
+            }
+        }
+        if amount.is_negative() {
+            return Err(AccountError::InvalidAllowanceAmount { amount });
+        }
+        if let Some(remaining_amount) = allowance.remaining_amount {
+            if amount > remaining_amount {
+                return Err(AccountError::AllowanceExceeded {
+                    grantee: grantee.clone(),
+                    resource_address,
+                    remaining_amount,
+                    amount,
+                });
+            }
+            allowance.remaining_amount = Some(remaining_amount - amount);
+        }
+        Ok(allowance)
+    }
+
     fn get_default_deposit_rule<Y>(api: &mut Y) -> Result<DefaultDepositRule, RuntimeError>
     where
         Y: ClientApi<RuntimeError>,
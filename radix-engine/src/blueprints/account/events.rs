@@ -47,3 +47,26 @@ pub struct AddAuthorizedDepositorEvent {
 pub struct RemoveAuthorizedDepositorEvent {
     pub authorized_depositor_badge: ResourceOrNonFungible,
 }
+
+#[derive(ScryptoSbor, ScryptoEvent, Debug, PartialEq, Eq)]
+pub struct SetAllowanceEvent {
+    pub grantee: AllowanceGrantee,
+    pub resource_address: ResourceAddress,
+    pub allowance: Allowance,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Debug, PartialEq, Eq)]
+pub struct RemoveAllowanceEvent {
+    pub grantee: AllowanceGrantee,
+    pub resource_address: ResourceAddress,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Debug, PartialEq, Eq)]
+pub struct AllowanceWithdrawEvent {
+    pub grantee: AllowanceGrantee,
+    pub resource_address: ResourceAddress,
+    pub amount: Decimal,
+    /// The amount which can still be withdrawn in the current period, or `None` if the allowance
+    /// is not capped.
+    pub remaining_amount: Option<Decimal>,
+}
@@ -9,12 +9,20 @@ use radix_engine_interface::hooks::OnVirtualizeInput;
 
 pub const ACCOUNT_ON_VIRTUALIZE_EXPORT_NAME: &str = "on_virtualize";
 
+/// The minor version of the Account V1 package
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Sbor)]
+pub enum AccountV1MinorVersion {
+    Zero,
+    One,
+    Two,
+}
+
 pub struct AccountNativePackage;
 
 impl AccountNativePackage {
-    pub fn definition() -> PackageDefinition {
+    pub fn definition(minor_version: AccountV1MinorVersion) -> PackageDefinition {
         let blueprints = indexmap!(
-            ACCOUNT_BLUEPRINT.to_string() => AccountBlueprint::get_definition()
+            ACCOUNT_BLUEPRINT.to_string() => AccountBlueprint::get_definition(minor_version)
         );
 
         PackageDefinition { blueprints }
@@ -288,6 +296,62 @@ impl AccountNativePackage {
                 let rtn = AccountBlueprint::remove_authorized_depositor(badge, api)?;
                 Ok(IndexedScryptoValue::from_typed(&rtn))
             }
+            ACCOUNT_SET_ALLOWANCE_IDENT => {
+                let AccountSetAllowanceInput {
+                    grantee,
+                    resource_address,
+                    amount,
+                    reset_period,
+                    expiry_epoch,
+                } = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn = AccountBlueprint::set_allowance(
+                    grantee,
+                    resource_address,
+                    amount,
+                    reset_period,
+                    expiry_epoch,
+                    api,
+                )?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+            ACCOUNT_REMOVE_ALLOWANCE_IDENT => {
+                let AccountRemoveAllowanceInput {
+                    grantee,
+                    resource_address,
+                } = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn = AccountBlueprint::remove_allowance(grantee, resource_address, api)?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+            ACCOUNT_GET_ALLOWANCE_IDENT => {
+                let AccountGetAllowanceInput {
+                    grantee,
+                    resource_address,
+                } = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn = AccountBlueprint::get_allowance(grantee, resource_address, api)?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+            ACCOUNT_WITHDRAW_WITH_ALLOWANCE_IDENT => {
+                let AccountWithdrawWithAllowanceInput {
+                    grantee,
+                    resource_address,
+                    amount,
+                } = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn = AccountBlueprint::withdraw_with_allowance(
+                    grantee,
+                    resource_address,
+                    amount,
+                    api,
+                )?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
 
             _ => Err(RuntimeError::ApplicationError(
                 ApplicationError::ExportDoesNotExist(export_name.to_string()),
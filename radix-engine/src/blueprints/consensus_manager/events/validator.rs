@@ -70,3 +70,21 @@ pub struct ValidatorRewardAppliedEvent {
     /// The reward amount
     pub amount: Decimal,
 }
+
+#[derive(ScryptoSbor, ScryptoEvent, PartialEq, Eq, Debug)]
+pub struct RedelegateEvent {
+    /// The validator which the stake was moved to.
+    pub target_validator: ComponentAddress,
+    /// An amount of this validator's stake units which were burnt.
+    pub stake_units: Decimal,
+    /// An amount of XRD moved from this validator's stake pool to the target validator's.
+    pub xrd_redelegated: Decimal,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, PartialEq, Eq, Debug)]
+pub struct ReceiveRedelegationEvent {
+    /// The validator which the stake was moved from.
+    pub source_validator: ComponentAddress,
+    /// An amount of XRD added to this validator's stake pool.
+    pub xrd_received: Decimal,
+}
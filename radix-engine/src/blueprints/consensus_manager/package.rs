@@ -1,4 +1,6 @@
-use crate::blueprints::consensus_manager::{ConsensusManagerBlueprint, ValidatorBlueprint};
+use crate::blueprints::consensus_manager::{
+    ConsensusManagerBlueprint, ValidatorBlueprint, VALIDATOR_UNSTAKE_V1_1_EXPORT_NAME,
+};
 use crate::errors::{ApplicationError, RuntimeError};
 use crate::kernel::kernel_api::KernelNodeApi;
 use crate::types::*;
@@ -8,13 +10,20 @@ use radix_engine_interface::blueprints::package::PackageDefinition;
 
 pub const VALIDATOR_ROLE: &str = "validator";
 
+/// The minor version of the Consensus Manager V1 package
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Sbor)]
+pub enum ConsensusManagerV1MinorVersion {
+    Zero,
+    One,
+}
+
 pub struct ConsensusManagerNativePackage;
 
 impl ConsensusManagerNativePackage {
-    pub fn definition() -> PackageDefinition {
+    pub fn definition(minor_version: ConsensusManagerV1MinorVersion) -> PackageDefinition {
         let blueprints = indexmap!(
             CONSENSUS_MANAGER_BLUEPRINT.to_string() => ConsensusManagerBlueprint::definition(),
-            VALIDATOR_BLUEPRINT.to_string() => ValidatorBlueprint::definition(),
+            VALIDATOR_BLUEPRINT.to_string() => ValidatorBlueprint::definition(minor_version),
         );
 
         PackageDefinition { blueprints }
@@ -146,6 +155,13 @@ impl ConsensusManagerNativePackage {
                 let rtn = ValidatorBlueprint::unstake(input.stake_unit_bucket, api)?;
                 Ok(IndexedScryptoValue::from_typed(&rtn))
             }
+            VALIDATOR_UNSTAKE_V1_1_EXPORT_NAME => {
+                let input: ValidatorUnstakeInput = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn = ValidatorBlueprint::unstake_v1_1(input.stake_unit_bucket, api)?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
             VALIDATOR_CLAIM_XRD_IDENT => {
                 let input: ValidatorClaimXrdInput = input.as_typed().map_err(|e| {
                     RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
@@ -269,6 +285,28 @@ impl ConsensusManagerNativePackage {
                 let rtn = ValidatorBlueprint::apply_reward(input.xrd_bucket, input.epoch, api)?;
                 Ok(IndexedScryptoValue::from_typed(&rtn))
             }
+            VALIDATOR_REDELEGATE_IDENT => {
+                let input: ValidatorRedelegateInput = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn = ValidatorBlueprint::redelegate(
+                    input.stake_unit_bucket,
+                    input.target_validator,
+                    api,
+                )?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+            VALIDATOR_RECEIVE_REDELEGATION_IDENT => {
+                let input: ValidatorReceiveRedelegationInput = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn = ValidatorBlueprint::receive_redelegation(
+                    input.xrd_bucket,
+                    input.source_validator,
+                    api,
+                )?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
             _ => Err(RuntimeError::ApplicationError(
                 ApplicationError::ExportDoesNotExist(export_name.to_string()),
             )),
@@ -25,8 +25,8 @@ use radix_engine_interface::{burn_roles, metadata_init, mint_roles, rule};
 use sbor::rust::mem;
 
 use super::{
-    ClaimXrdEvent, RegisterValidatorEvent, StakeEvent, UnregisterValidatorEvent, UnstakeEvent,
-    UpdateAcceptingStakeDelegationStateEvent,
+    ClaimXrdEvent, ReceiveRedelegationEvent, RedelegateEvent, RegisterValidatorEvent, StakeEvent,
+    UnregisterValidatorEvent, UnstakeEvent, UpdateAcceptingStakeDelegationStateEvent,
 };
 
 pub const VALIDATOR_PROTOCOL_VERSION_NAME_LEN: usize = 32;
@@ -35,6 +35,18 @@ pub const VALIDATOR_PROTOCOL_VERSION_NAME_LEN: usize = 32;
 /// operations on any validator's owner's stake units vault.
 pub const OWNER_STAKE_UNITS_PENDING_WITHDRAWALS_LIMIT: usize = 100;
 
+/// The number of epochs during which XRD redelegated to a validator cannot be redelegated away
+/// from it again. This prevents hopping the same stake across many validators in quick succession.
+pub const REDELEGATION_COOLDOWN_EPOCHS: u64 = 12;
+
+/// The maximum fraction of a validator's stake which can be redelegated away from it within a
+/// single epoch. This limits the churn of the validator set caused by redelegations.
+pub const REDELEGATION_MAX_FRACTION_PER_EPOCH: Decimal = Decimal::ONE_TENTH;
+
+/// The export of the unstake method since v1.1, which also releases the XRD redelegated to the
+/// validator in proportion to the unstaked XRD.
+pub const VALIDATOR_UNSTAKE_V1_1_EXPORT_NAME: &str = "unstake_v1_1";
+
 #[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
 pub struct ValidatorSubstate {
     /// A key used internally for storage of registered validators sorted by their stake descending.
@@ -147,6 +159,22 @@ pub struct ValidatorFeeChangeRequest {
     pub new_fee_factor: Decimal,
 }
 
+/// The XRD amounts redelegated to and away from a validator during a single epoch.
+/// Note: the records are stored in a ring of [`REDELEGATION_COOLDOWN_EPOCHS`] slots, keyed by the
+/// epoch number modulo the size of the ring, so a record is only relevant if its [`epoch`] falls
+/// within the cooldown window.
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct RedelegationRecord {
+    /// The epoch of this record.
+    pub epoch: Epoch,
+
+    /// An XRD amount redelegated to this validator during the epoch.
+    pub inbound_xrd: Decimal,
+
+    /// An XRD amount redelegated away from this validator during the epoch.
+    pub outbound_xrd: Decimal,
+}
+
 impl NonFungibleData for UnstakeData {
     const MUTABLE_FIELDS: &'static [&'static str] = &[];
 }
@@ -160,8 +188,21 @@ pub enum ValidatorError {
     PendingOwnerStakeWithdrawalLimitReached,
     InvalidValidatorFeeFactor,
     ValidatorIsNotAcceptingDelegatedStake,
-    InvalidProtocolVersionNameLength { expected: usize, actual: usize },
+    InvalidProtocolVersionNameLength {
+        expected: usize,
+        actual: usize,
+    },
     EpochMathOverflow,
+    InvalidRedelegationTarget,
+    InvalidRedelegationSource,
+    RedelegatedStakeInCooldown {
+        redelegatable_xrd: Decimal,
+        requested_xrd: Decimal,
+    },
+    RedelegationEpochLimitExceeded {
+        remaining_xrd: Decimal,
+        requested_xrd: Decimal,
+    },
 }
 
 declare_native_blueprint_state! {
@@ -186,11 +227,23 @@ declare_native_blueprint_state! {
         },
     },
     collections: {
+        redelegations: KeyValue {
+            entry_ident: Redelegation,
+            key_type: {
+                kind: Static,
+                content_type: u64,
+            },
+            value_type: {
+                kind: StaticSingleVersioned,
+            },
+            allow_ownership: false,
+        },
     }
 }
 
 pub type ValidatorStateV1 = ValidatorSubstate;
 pub type ValidatorProtocolUpdateReadinessSignalV1 = ValidatorProtocolUpdateReadinessSignalSubstate;
+pub type ValidatorRedelegationV1 = RedelegationRecord;
 
 #[derive(Debug, Clone, Eq, PartialEq, ScryptoSbor, ManifestSbor)]
 enum UpdateSecondaryIndex {
@@ -216,11 +269,15 @@ enum UpdateSecondaryIndex {
 pub struct ValidatorBlueprint;
 
 impl ValidatorBlueprint {
-    pub fn definition() -> BlueprintDefinitionInit {
+    pub fn definition(minor_version: ConsensusManagerV1MinorVersion) -> BlueprintDefinitionInit {
         let mut aggregator = TypeAggregator::<ScryptoCustomTypeKind>::new();
 
         let feature_set = ValidatorFeatureSet::all_features();
-        let state = ValidatorStateSchemaInit::create_schema_init(&mut aggregator);
+        let mut state = ValidatorStateSchemaInit::create_schema_init(&mut aggregator);
+        // The redelegations collection was only added in v1.1
+        if minor_version < ConsensusManagerV1MinorVersion::One {
+            state.collections.pop();
+        }
 
         let mut functions = index_map_new();
         functions.insert(
@@ -285,7 +342,11 @@ impl ValidatorBlueprint {
                 output: TypeRef::Static(
                     aggregator.add_child_type_and_descendents::<ValidatorUnstakeOutput>(),
                 ),
-                export: VALIDATOR_UNSTAKE_IDENT.to_string(),
+                export: if minor_version >= ConsensusManagerV1MinorVersion::One {
+                    VALIDATOR_UNSTAKE_V1_1_EXPORT_NAME.to_string()
+                } else {
+                    VALIDATOR_UNSTAKE_IDENT.to_string()
+                },
             },
         );
         functions.insert(
@@ -482,8 +543,38 @@ impl ValidatorBlueprint {
                 export: VALIDATOR_APPLY_REWARD_IDENT.to_string(),
             },
         );
+        if minor_version >= ConsensusManagerV1MinorVersion::One {
+            functions.insert(
+                VALIDATOR_REDELEGATE_IDENT.to_string(),
+                FunctionSchemaInit {
+                    receiver: Some(ReceiverInfo::normal_ref_mut()),
+                    input: TypeRef::Static(
+                        aggregator.add_child_type_and_descendents::<ValidatorRedelegateInput>(),
+                    ),
+                    output: TypeRef::Static(
+                        aggregator.add_child_type_and_descendents::<ValidatorRedelegateOutput>(),
+                    ),
+                    export: VALIDATOR_REDELEGATE_IDENT.to_string(),
+                },
+            );
+            functions.insert(
+                VALIDATOR_RECEIVE_REDELEGATION_IDENT.to_string(),
+                FunctionSchemaInit {
+                    receiver: Some(ReceiverInfo::normal_ref_mut()),
+                    input: TypeRef::Static(
+                        aggregator
+                            .add_child_type_and_descendents::<ValidatorReceiveRedelegationInput>(),
+                    ),
+                    output: TypeRef::Static(
+                        aggregator
+                            .add_child_type_and_descendents::<ValidatorReceiveRedelegationOutput>(),
+                    ),
+                    export: VALIDATOR_RECEIVE_REDELEGATION_IDENT.to_string(),
+                },
+            );
+        }
 
-        let event_schema = event_schema! {
+        let mut event_schema = event_schema! {
             aggregator,
             [
                 RegisterValidatorEvent,
@@ -497,9 +588,58 @@ impl ValidatorBlueprint {
                 ValidatorRewardAppliedEvent
             ]
         };
+        if minor_version >= ConsensusManagerV1MinorVersion::One {
+            event_schema.event_schema.extend(
+                event_schema! {
+                    aggregator,
+                    [
+                        RedelegateEvent,
+                        ReceiveRedelegationEvent
+                    ]
+                }
+                .event_schema,
+            );
+        }
 
         let schema = generate_full_schema(aggregator);
 
+        let mut role_definition = roles_template! {
+            methods {
+                VALIDATOR_UNSTAKE_IDENT => MethodAccessibility::Public;
+                VALIDATOR_CLAIM_XRD_IDENT => MethodAccessibility::Public;
+                VALIDATOR_STAKE_IDENT => MethodAccessibility::Public;
+                VALIDATOR_ACCEPTS_DELEGATED_STAKE_IDENT => MethodAccessibility::Public;
+                VALIDATOR_TOTAL_STAKE_XRD_AMOUNT_IDENT => MethodAccessibility::Public;
+                VALIDATOR_TOTAL_STAKE_UNIT_SUPPLY_IDENT => MethodAccessibility::Public;
+                VALIDATOR_GET_REDEMPTION_VALUE_IDENT => MethodAccessibility::Public;
+                VALIDATOR_STAKE_AS_OWNER_IDENT => [OWNER_ROLE];
+                VALIDATOR_REGISTER_IDENT => [OWNER_ROLE];
+                VALIDATOR_UNREGISTER_IDENT => [OWNER_ROLE];
+                VALIDATOR_UPDATE_KEY_IDENT => [OWNER_ROLE];
+                VALIDATOR_UPDATE_FEE_IDENT => [OWNER_ROLE];
+                VALIDATOR_LOCK_OWNER_STAKE_UNITS_IDENT => [OWNER_ROLE];
+                VALIDATOR_START_UNLOCK_OWNER_STAKE_UNITS_IDENT => [OWNER_ROLE];
+                VALIDATOR_FINISH_UNLOCK_OWNER_STAKE_UNITS_IDENT => [OWNER_ROLE];
+                VALIDATOR_UPDATE_ACCEPT_DELEGATED_STAKE_IDENT => [OWNER_ROLE];
+                VALIDATOR_SIGNAL_PROTOCOL_UPDATE_READINESS => [OWNER_ROLE];
+                VALIDATOR_GET_PROTOCOL_UPDATE_READINESS_IDENT => MethodAccessibility::OuterObjectOnly;
+                VALIDATOR_APPLY_EMISSION_IDENT => MethodAccessibility::OuterObjectOnly;
+                VALIDATOR_APPLY_REWARD_IDENT => MethodAccessibility::OuterObjectOnly;
+            }
+        };
+        if minor_version >= ConsensusManagerV1MinorVersion::One {
+            role_definition.methods.extend(
+                roles_template! {
+                    methods {
+                        VALIDATOR_REDELEGATE_IDENT => MethodAccessibility::Public;
+                        // The caller is asserted to be the source validator by the method itself
+                        VALIDATOR_RECEIVE_REDELEGATION_IDENT => MethodAccessibility::Public;
+                    }
+                }
+                .methods,
+            );
+        }
+
         BlueprintDefinitionInit {
             blueprint_type: BlueprintType::Inner {
                 outer_blueprint: CONSENSUS_MANAGER_BLUEPRINT.to_string(),
@@ -519,30 +659,7 @@ impl ValidatorBlueprint {
             royalty_config: PackageRoyaltyConfig::default(),
             auth_config: AuthConfig {
                 function_auth: FunctionAuth::AllowAll,
-                method_auth: MethodAuthTemplate::StaticRoleDefinition(roles_template! {
-                    methods {
-                        VALIDATOR_UNSTAKE_IDENT => MethodAccessibility::Public;
-                        VALIDATOR_CLAIM_XRD_IDENT => MethodAccessibility::Public;
-                        VALIDATOR_STAKE_IDENT => MethodAccessibility::Public;
-                        VALIDATOR_ACCEPTS_DELEGATED_STAKE_IDENT => MethodAccessibility::Public;
-                        VALIDATOR_TOTAL_STAKE_XRD_AMOUNT_IDENT => MethodAccessibility::Public;
-                        VALIDATOR_TOTAL_STAKE_UNIT_SUPPLY_IDENT => MethodAccessibility::Public;
-                        VALIDATOR_GET_REDEMPTION_VALUE_IDENT => MethodAccessibility::Public;
-                        VALIDATOR_STAKE_AS_OWNER_IDENT => [OWNER_ROLE];
-                        VALIDATOR_REGISTER_IDENT => [OWNER_ROLE];
-                        VALIDATOR_UNREGISTER_IDENT => [OWNER_ROLE];
-                        VALIDATOR_UPDATE_KEY_IDENT => [OWNER_ROLE];
-                        VALIDATOR_UPDATE_FEE_IDENT => [OWNER_ROLE];
-                        VALIDATOR_LOCK_OWNER_STAKE_UNITS_IDENT => [OWNER_ROLE];
-                        VALIDATOR_START_UNLOCK_OWNER_STAKE_UNITS_IDENT => [OWNER_ROLE];
-                        VALIDATOR_FINISH_UNLOCK_OWNER_STAKE_UNITS_IDENT => [OWNER_ROLE];
-                        VALIDATOR_UPDATE_ACCEPT_DELEGATED_STAKE_IDENT => [OWNER_ROLE];
-                        VALIDATOR_SIGNAL_PROTOCOL_UPDATE_READINESS => [OWNER_ROLE];
-                        VALIDATOR_GET_PROTOCOL_UPDATE_READINESS_IDENT => MethodAccessibility::OuterObjectOnly;
-                        VALIDATOR_APPLY_EMISSION_IDENT => MethodAccessibility::OuterObjectOnly;
-                        VALIDATOR_APPLY_REWARD_IDENT => MethodAccessibility::OuterObjectOnly;
-                    }
-                }),
+                method_auth: MethodAuthTemplate::StaticRoleDefinition(role_definition),
             },
         }
     }
@@ -607,13 +724,38 @@ impl ValidatorBlueprint {
         }
 
         let xrd_bucket_amount = xrd_bucket.amount(api)?;
+        let stake_unit_bucket = Self::stake_into_vault(&mut validator, xrd_bucket, api)?;
+        api.field_write_typed(
+            handle,
+            &ValidatorStateFieldPayload::from_content_source(validator),
+        )?;
+
+        Runtime::emit_event(
+            api,
+            StakeEvent {
+                xrd_staked: xrd_bucket_amount,
+            },
+        )?;
+
+        Ok(stake_unit_bucket)
+    }
 
+    /// Puts the given XRD into the stake vault, mints the corresponding stake units and updates
+    /// the validator's position in the Consensus Manager's index.
+    fn stake_into_vault<Y>(
+        validator: &mut ValidatorSubstate,
+        xrd_bucket: Bucket,
+        api: &mut Y,
+    ) -> Result<Bucket, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
         // Stake
         let (stake_unit_bucket, new_stake_amount) = {
             let mut stake_unit_resman = ResourceManager(validator.stake_unit_resource);
             let mut xrd_vault = Vault(validator.stake_xrd_vault_id);
             let stake_unit_mint_amount = Self::calculate_stake_unit_amount(
-                xrd_bucket_amount,
+                xrd_bucket.amount(api)?,
                 xrd_vault.amount(api)?,
                 stake_unit_resman.total_supply(api)?.unwrap(),
             )?;
@@ -626,25 +768,51 @@ impl ValidatorBlueprint {
 
         // Update ConsensusManager
         let new_index_key =
-            Self::index_update(&validator, validator.is_registered, new_stake_amount, api)?;
-
+            Self::index_update(validator, validator.is_registered, new_stake_amount, api)?;
         validator.sorted_key = new_index_key;
-        api.field_write_typed(
-            handle,
-            &ValidatorStateFieldPayload::from_content_source(validator),
-        )?;
-
-        Runtime::emit_event(
-            api,
-            StakeEvent {
-                xrd_staked: xrd_bucket_amount,
-            },
-        )?;
 
         Ok(stake_unit_bucket)
     }
 
     pub fn unstake<Y>(stake_unit_bucket: Bucket, api: &mut Y) -> Result<Bucket, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let (unstake_bucket, _xrd_amount, _new_stake_amount) =
+            Self::unstake_internal(stake_unit_bucket, api)?;
+        Ok(unstake_bucket)
+    }
+
+    /// Unstakes as [`ValidatorBlueprint::unstake`], and releases the XRD redelegated to this
+    /// validator within the cooldown window in proportion to the unstaked XRD.
+    /// Stake units are fungible, so every stake unit carries the same share of the redelegated
+    /// XRD, and the unstaked share of it can no longer be redelegated away.
+    pub fn unstake_v1_1<Y>(stake_unit_bucket: Bucket, api: &mut Y) -> Result<Bucket, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let (unstake_bucket, xrd_amount, new_stake_amount) =
+            Self::unstake_internal(stake_unit_bucket, api)?;
+
+        let current_epoch = Self::current_epoch(api)?;
+        let stake_amount =
+            new_stake_amount
+                .checked_add(xrd_amount)
+                .ok_or(RuntimeError::ApplicationError(
+                    ApplicationError::ValidatorError(
+                        ValidatorError::UnexpectedDecimalComputationError,
+                    ),
+                ))?;
+        Self::release_redelegated_in_xrd(current_epoch, new_stake_amount, stake_amount, api)?;
+
+        Ok(unstake_bucket)
+    }
+
+    /// Returns the claim NFT bucket, the unstaked XRD amount and the remaining stake.
+    fn unstake_internal<Y>(
+        stake_unit_bucket: Bucket,
+        api: &mut Y,
+    ) -> Result<(Bucket, Decimal, Decimal), RuntimeError>
     where
         Y: ClientApi<RuntimeError>,
     {
@@ -660,7 +828,7 @@ impl ValidatorBlueprint {
             .into_latest();
 
         // Unstake
-        let (unstake_bucket, new_stake_amount) = {
+        let (unstake_bucket, xrd_amount, new_stake_amount) = {
             let xrd_amount = Self::calculate_redemption_value(
                 stake_unit_bucket_amount,
                 &validator_substate,
@@ -712,7 +880,7 @@ impl ValidatorBlueprint {
 
             let new_stake_amount = stake_vault.amount(api)?;
 
-            (unstake_bucket, new_stake_amount)
+            (unstake_bucket, xrd_amount, new_stake_amount)
         };
 
         // Update ConsensusManager
@@ -736,7 +904,409 @@ impl ValidatorBlueprint {
             },
         )?;
 
-        Ok(unstake_bucket)
+        Ok((unstake_bucket, xrd_amount, new_stake_amount))
+    }
+
+    /// Moves the stake represented by the given stake units of this validator to the target
+    /// validator, without going through the unstake delay. The stake units are redeemed at their
+    /// current value and the XRD is staked to the target validator, whose stake units are
+    /// returned.
+    ///
+    /// To prevent abuse, XRD which was redelegated to this validator cannot be redelegated away
+    /// again for [`REDELEGATION_COOLDOWN_EPOCHS`], and at most
+    /// [`REDELEGATION_MAX_FRACTION_PER_EPOCH`] of this validator's stake can be redelegated away
+    /// within a single epoch.
+    ///
+    /// Note: stake units are fungible, so both limits are tracked per validator rather than per
+    /// holder. Unstaking releases the XRD redelegated to this validator in proportion to the
+    /// unstaked XRD (see [`ValidatorBlueprint::unstake_v1_1`]), so only stake which remains
+    /// staked holds up the cooldown. A large staker can still use up the epoch budget, or keep
+    /// stake redelegated in, and so delay the redelegations of other stakers. This is accepted, as
+    /// the delay is bounded by the cooldown and the regular unstake flow is always available.
+    pub fn redelegate<Y>(
+        stake_unit_bucket: Bucket,
+        target_validator: ComponentAddress,
+        api: &mut Y,
+    ) -> Result<Bucket, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let validator_address: ComponentAddress =
+            ComponentAddress::new_or_panic(api.actor_get_node_id(ACTOR_REF_GLOBAL)?.into());
+        if target_validator.eq(&validator_address)
+            || !Self::is_validator(target_validator.as_node_id(), api)?
+        {
+            return Err(RuntimeError::ApplicationError(
+                ApplicationError::ValidatorError(ValidatorError::InvalidRedelegationTarget),
+            ));
+        }
+
+        let stake_unit_bucket_amount = stake_unit_bucket.amount(api)?;
+        let current_epoch = Self::current_epoch(api)?;
+
+        let handle = api.actor_open_field(
+            ACTOR_STATE_SELF,
+            ValidatorField::State.field_index(),
+            LockFlags::MUTABLE,
+        )?;
+        let mut validator_substate = api
+            .field_read_typed::<ValidatorStateFieldPayload>(handle)?
+            .into_latest();
+
+        let xrd_amount =
+            Self::calculate_redemption_value(stake_unit_bucket_amount, &validator_substate, api)?;
+        let mut stake_vault = Vault(validator_substate.stake_xrd_vault_id);
+        let stake_amount = stake_vault.amount(api)?;
+
+        // Check the redelegation limits
+        let records = Self::read_redelegation_records(current_epoch, api)?;
+        let redelegated_in_xrd = records
+            .iter()
+            .try_fold(Decimal::zero(), |sum, record| {
+                sum.checked_add(record.inbound_xrd)
+            })
+            .ok_or(RuntimeError::ApplicationError(
+                ApplicationError::ValidatorError(ValidatorError::UnexpectedDecimalComputationError),
+            ))?;
+        let redelegated_out_this_epoch = records
+            .iter()
+            .find(|record| record.epoch == current_epoch)
+            .map(|record| record.outbound_xrd)
+            .unwrap_or_default();
+        let check_result = Self::check_redelegation_limits(
+            xrd_amount,
+            stake_amount,
+            redelegated_in_xrd,
+            redelegated_out_this_epoch,
+        );
+        if let Err(error) = check_result {
+            api.field_close(handle)?;
+            return Err(RuntimeError::ApplicationError(
+                ApplicationError::ValidatorError(error),
+            ));
+        }
+
+        // Unstake without the delay
+        let xrd_bucket = {
+            let mut stake_unit_resman = ResourceManager(validator_substate.stake_unit_resource);
+            stake_unit_resman.burn(stake_unit_bucket, api)?;
+            stake_vault.take(xrd_amount, api)?
+        };
+        let new_stake_amount = stake_vault.amount(api)?;
+
+        // Update ConsensusManager
+        let new_index_key = Self::index_update(
+            &validator_substate,
+            validator_substate.is_registered,
+            new_stake_amount,
+            api,
+        )?;
+
+        validator_substate.sorted_key = new_index_key;
+        api.field_write_typed(
+            handle,
+            &ValidatorStateFieldPayload::from_content_source(validator_substate),
+        )?;
+        api.field_close(handle)?;
+
+        Self::record_redelegation(current_epoch, Decimal::zero(), xrd_amount, api)?;
+
+        // Stake to the target validator
+        let rtn = api.call_method(
+            target_validator.as_node_id(),
+            VALIDATOR_RECEIVE_REDELEGATION_IDENT,
+            scrypto_encode(&ValidatorReceiveRedelegationInput {
+                xrd_bucket,
+                source_validator: validator_address,
+            })
+            .unwrap(),
+        )?;
+        let target_stake_unit_bucket: ValidatorReceiveRedelegationOutput =
+            scrypto_decode(&rtn).unwrap();
+
+        Runtime::emit_event(
+            api,
+            RedelegateEvent {
+                target_validator,
+                stake_units: stake_unit_bucket_amount,
+                xrd_redelegated: xrd_amount,
+            },
+        )?;
+
+        Ok(target_stake_unit_bucket)
+    }
+
+    /// Stakes XRD redelegated from the source validator. Only the source validator itself is
+    /// allowed to call this method, as part of [`ValidatorBlueprint::redelegate`].
+    pub fn receive_redelegation<Y>(
+        xrd_bucket: Bucket,
+        source_validator: ComponentAddress,
+        api: &mut Y,
+    ) -> Result<Bucket, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        if !Self::is_validator(source_validator.as_node_id(), api)? {
+            return Err(RuntimeError::ApplicationError(
+                ApplicationError::ValidatorError(ValidatorError::InvalidRedelegationSource),
+            ));
+        }
+        Runtime::assert_access_rule(rule!(require(global_caller(source_validator))), api)?;
+
+        let current_epoch = Self::current_epoch(api)?;
+
+        let handle = api.actor_open_field(
+            ACTOR_STATE_SELF,
+            ValidatorField::State.field_index(),
+            LockFlags::MUTABLE,
+        )?;
+        let mut validator = api
+            .field_read_typed::<ValidatorStateFieldPayload>(handle)?
+            .into_latest();
+
+        if !validator.accepts_delegated_stake {
+            api.field_close(handle)?;
+            return Err(RuntimeError::ApplicationError(
+                ApplicationError::ValidatorError(
+                    ValidatorError::ValidatorIsNotAcceptingDelegatedStake,
+                ),
+            ));
+        }
+
+        let xrd_bucket_amount = xrd_bucket.amount(api)?;
+        let stake_unit_bucket = Self::stake_into_vault(&mut validator, xrd_bucket, api)?;
+        api.field_write_typed(
+            handle,
+            &ValidatorStateFieldPayload::from_content_source(validator),
+        )?;
+        api.field_close(handle)?;
+
+        Self::record_redelegation(current_epoch, xrd_bucket_amount, Decimal::zero(), api)?;
+
+        Runtime::emit_event(
+            api,
+            ReceiveRedelegationEvent {
+                source_validator,
+                xrd_received: xrd_bucket_amount,
+            },
+        )?;
+
+        Ok(stake_unit_bucket)
+    }
+
+    fn is_validator<Y>(node_id: &NodeId, api: &mut Y) -> Result<bool, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let blueprint_id = api.get_blueprint_id(node_id)?;
+        Ok(blueprint_id.eq(&BlueprintId::new(
+            &CONSENSUS_MANAGER_PACKAGE,
+            VALIDATOR_BLUEPRINT,
+        )))
+    }
+
+    fn current_epoch<Y>(api: &mut Y) -> Result<Epoch, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let manager_handle = api.actor_open_field(
+            ACTOR_STATE_OUTER_OBJECT,
+            ConsensusManagerField::State.into(),
+            LockFlags::read_only(),
+        )?;
+        let manager_substate = api
+            .field_read_typed::<ConsensusManagerStateFieldPayload>(manager_handle)?
+            .into_latest();
+        api.field_close(manager_handle)?;
+
+        Ok(manager_substate.epoch)
+    }
+
+    /// Returns the redelegation records of the epochs within the cooldown window.
+    fn read_redelegation_records<Y>(
+        current_epoch: Epoch,
+        api: &mut Y,
+    ) -> Result<Vec<RedelegationRecord>, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let mut records = Vec::new();
+        for slot in 0..REDELEGATION_COOLDOWN_EPOCHS {
+            if let Some(record) = Self::read_redelegation_record(slot, api)? {
+                if Self::is_within_cooldown(record.epoch, current_epoch) {
+                    records.push(record);
+                }
+            }
+        }
+        Ok(records)
+    }
+
+    fn read_redelegation_record<Y>(
+        slot: u64,
+        api: &mut Y,
+    ) -> Result<Option<RedelegationRecord>, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let handle = api.actor_open_key_value_entry(
+            ACTOR_STATE_SELF,
+            ValidatorCollection::RedelegationKeyValue.collection_index(),
+            &scrypto_encode(&slot).unwrap(),
+            LockFlags::read_only(),
+        )?;
+        let record = api
+            .key_value_entry_get_typed::<ValidatorRedelegationEntryPayload>(handle)?
+            .map(|entry| entry.into_latest());
+        api.key_value_entry_close(handle)?;
+        Ok(record)
+    }
+
+    /// Adds the given amounts to the redelegation record of the current epoch, replacing any
+    /// stale record which occupied the same slot.
+    fn record_redelegation<Y>(
+        current_epoch: Epoch,
+        inbound_xrd: Decimal,
+        outbound_xrd: Decimal,
+        api: &mut Y,
+    ) -> Result<(), RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let slot = current_epoch.number() % REDELEGATION_COOLDOWN_EPOCHS;
+        let handle = api.actor_open_key_value_entry(
+            ACTOR_STATE_SELF,
+            ValidatorCollection::RedelegationKeyValue.collection_index(),
+            &scrypto_encode(&slot).unwrap(),
+            LockFlags::MUTABLE,
+        )?;
+        let mut record = api
+            .key_value_entry_get_typed::<ValidatorRedelegationEntryPayload>(handle)?
+            .map(|entry| entry.into_latest())
+            .filter(|record| record.epoch == current_epoch)
+            .unwrap_or(RedelegationRecord {
+                epoch: current_epoch,
+                inbound_xrd: Decimal::zero(),
+                outbound_xrd: Decimal::zero(),
+            });
+        record.inbound_xrd =
+            record
+                .inbound_xrd
+                .checked_add(inbound_xrd)
+                .ok_or(RuntimeError::ApplicationError(
+                    ApplicationError::ValidatorError(
+                        ValidatorError::UnexpectedDecimalComputationError,
+                    ),
+                ))?;
+        record.outbound_xrd =
+            record
+                .outbound_xrd
+                .checked_add(outbound_xrd)
+                .ok_or(RuntimeError::ApplicationError(
+                    ApplicationError::ValidatorError(
+                        ValidatorError::UnexpectedDecimalComputationError,
+                    ),
+                ))?;
+        api.key_value_entry_set_typed(
+            handle,
+            &ValidatorRedelegationEntryPayload::from_content_source(record),
+        )?;
+        api.key_value_entry_close(handle)?;
+        Ok(())
+    }
+
+    /// Scales down the XRD redelegated in within the cooldown window by the fraction of the stake
+    /// which remains after an unstake.
+    fn release_redelegated_in_xrd<Y>(
+        current_epoch: Epoch,
+        remaining_stake_amount: Decimal,
+        stake_amount: Decimal,
+        api: &mut Y,
+    ) -> Result<(), RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        if stake_amount.is_zero() {
+            return Ok(());
+        }
+
+        for slot in 0..REDELEGATION_COOLDOWN_EPOCHS {
+            let handle = api.actor_open_key_value_entry(
+                ACTOR_STATE_SELF,
+                ValidatorCollection::RedelegationKeyValue.collection_index(),
+                &scrypto_encode(&slot).unwrap(),
+                LockFlags::MUTABLE,
+            )?;
+            let record = api
+                .key_value_entry_get_typed::<ValidatorRedelegationEntryPayload>(handle)?
+                .map(|entry| entry.into_latest())
+                .filter(|record| {
+                    Self::is_within_cooldown(record.epoch, current_epoch)
+                        && record.inbound_xrd.is_positive()
+                });
+            if let Some(mut record) = record {
+                record.inbound_xrd = record
+                    .inbound_xrd
+                    .checked_mul(remaining_stake_amount)
+                    .and_then(|amount| amount.checked_div(stake_amount))
+                    .ok_or(RuntimeError::ApplicationError(
+                        ApplicationError::ValidatorError(
+                            ValidatorError::UnexpectedDecimalComputationError,
+                        ),
+                    ))?;
+                api.key_value_entry_set_typed(
+                    handle,
+                    &ValidatorRedelegationEntryPayload::from_content_source(record),
+                )?;
+            }
+            api.key_value_entry_close(handle)?;
+        }
+
+        Ok(())
+    }
+
+    fn is_within_cooldown(epoch: Epoch, current_epoch: Epoch) -> bool {
+        epoch <= current_epoch
+            && current_epoch.number() - epoch.number() < REDELEGATION_COOLDOWN_EPOCHS
+    }
+
+    /// Checks that [`xrd_amount`] can be redelegated away from a validator with the given stake,
+    /// given the XRD redelegated to it within the cooldown window and the XRD already redelegated
+    /// away from it in the current epoch.
+    /// Note: the amounts are pooled across all stakers of the validator, see
+    /// [`ValidatorBlueprint::redelegate`].
+    fn check_redelegation_limits(
+        xrd_amount: Decimal,
+        stake_amount: Decimal,
+        redelegated_in_xrd: Decimal,
+        redelegated_out_this_epoch: Decimal,
+    ) -> Result<(), ValidatorError> {
+        // XRD redelegated in within the cooldown window is considered to be redelegated away last
+        let redelegatable_xrd = stake_amount
+            .checked_sub(redelegated_in_xrd)
+            .ok_or(ValidatorError::UnexpectedDecimalComputationError)?
+            .max(Decimal::zero());
+        if xrd_amount > redelegatable_xrd {
+            return Err(ValidatorError::RedelegatedStakeInCooldown {
+                redelegatable_xrd,
+                requested_xrd: xrd_amount,
+            });
+        }
+
+        // The limit is relative to the stake before any redelegations away in this epoch
+        let remaining_xrd = stake_amount
+            .checked_add(redelegated_out_this_epoch)
+            .and_then(|amount| amount.checked_mul(REDELEGATION_MAX_FRACTION_PER_EPOCH))
+            .and_then(|amount| amount.checked_sub(redelegated_out_this_epoch))
+            .ok_or(ValidatorError::UnexpectedDecimalComputationError)?
+            .max(Decimal::zero());
+        if xrd_amount > remaining_xrd {
+            return Err(ValidatorError::RedelegationEpochLimitExceeded {
+                remaining_xrd,
+                requested_xrd: xrd_amount,
+            });
+        }
+
+        Ok(())
     }
 
     pub fn signal_protocol_update_readiness<Y>(
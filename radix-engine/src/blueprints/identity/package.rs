@@ -26,11 +26,19 @@ pub const IDENTITY_ON_VIRTUALIZE_EXPORT_NAME: &str = "on_virtualize";
 
 pub const IDENTITY_CREATE_VIRTUAL_SECP256K1_ID: u8 = 0u8;
 pub const IDENTITY_CREATE_VIRTUAL_ED25519_ID: u8 = 1u8;
+pub const IDENTITY_CREATE_VIRTUAL_SECP256R1_ID: u8 = 2u8;
+
+/// The minor version of the Identity V1 package
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Sbor)]
+pub enum IdentityV1MinorVersion {
+    Zero,
+    One,
+}
 
 pub struct IdentityNativePackage;
 
 impl IdentityNativePackage {
-    pub fn definition() -> PackageDefinition {
+    pub fn definition(minor_version: IdentityV1MinorVersion) -> PackageDefinition {
         let mut aggregator = TypeAggregator::<ScryptoCustomTypeKind>::new();
 
         let fields = Vec::new();
@@ -78,18 +86,24 @@ impl IdentityNativePackage {
             },
         );
 
+        let mut dependencies = indexset!(
+            SECP256K1_SIGNATURE_VIRTUAL_BADGE.into(),
+            ED25519_SIGNATURE_VIRTUAL_BADGE.into(),
+            IDENTITY_OWNER_BADGE.into(),
+            PACKAGE_OF_DIRECT_CALLER_VIRTUAL_BADGE.into(),
+        );
+        // Secp256r1 identities are only virtualized once the badge is a dependency, from v1.1
+        if minor_version >= IdentityV1MinorVersion::One {
+            dependencies.insert(SECP256R1_SIGNATURE_VIRTUAL_BADGE.into());
+        }
+
         let schema = generate_full_schema(aggregator);
         let blueprints = indexmap!(
             IDENTITY_BLUEPRINT.to_string() => BlueprintDefinitionInit {
                 blueprint_type: BlueprintType::default(),
                 is_transient: false,
                 feature_set: indexset!(),
-                dependencies: indexset!(
-                    SECP256K1_SIGNATURE_VIRTUAL_BADGE.into(),
-                    ED25519_SIGNATURE_VIRTUAL_BADGE.into(),
-                    IDENTITY_OWNER_BADGE.into(),
-                    PACKAGE_OF_DIRECT_CALLER_VIRTUAL_BADGE.into(),
-                ),
+                dependencies,
                 schema: BlueprintSchemaInit {
                     generics: vec![],
                     schema,
@@ -257,6 +271,10 @@ impl IdentityBlueprint {
                 let public_key_hash = PublicKeyHash::Ed25519(Ed25519PublicKeyHash(input.rid));
                 Self::create_virtual(public_key_hash, input.address_reservation, api)
             }
+            IDENTITY_CREATE_VIRTUAL_SECP256R1_ID => {
+                let public_key_hash = PublicKeyHash::Secp256r1(Secp256r1PublicKeyHash(input.rid));
+                Self::create_virtual(public_key_hash, input.address_reservation, api)
+            }
             x => Err(RuntimeError::ApplicationError(
                 ApplicationError::PanicMessage(format!("Unexpected variant id: {:?}", x)),
             )),
@@ -276,6 +294,7 @@ impl IdentityBlueprint {
             let entity_type = match public_key_hash {
                 PublicKeyHash::Ed25519(..) => EntityType::GlobalVirtualEd25519Identity,
                 PublicKeyHash::Secp256k1(..) => EntityType::GlobalVirtualSecp256k1Identity,
+                PublicKeyHash::Secp256r1(..) => EntityType::GlobalVirtualSecp256r1Identity,
             };
 
             let mut id_bytes = vec![entity_type as u8];
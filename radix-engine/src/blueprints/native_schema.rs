@@ -15,30 +15,52 @@ use crate::system::attached_modules::role_assignment::*;
 use crate::system::attached_modules::royalty::*;
 
 lazy_static! {
-    pub static ref CONSENSUS_MANAGER_PACKAGE_DEFINITION: PackageDefinition =
-        ConsensusManagerNativePackage::definition();
-    pub static ref ACCOUNT_PACKAGE_DEFINITION: PackageDefinition =
-        AccountNativePackage::definition();
-    pub static ref IDENTITY_PACKAGE_DEFINITION: PackageDefinition =
-        IdentityNativePackage::definition();
-    pub static ref ACCESS_CONTROLLER_PACKAGE_DEFINITION: PackageDefinition =
-        AccessControllerNativePackage::definition();
+    pub static ref CONSENSUS_MANAGER_PACKAGE_DEFINITION_V1_0: PackageDefinition =
+        ConsensusManagerNativePackage::definition(ConsensusManagerV1MinorVersion::Zero);
+    pub static ref CONSENSUS_MANAGER_PACKAGE_DEFINITION_V1_1: PackageDefinition =
+        ConsensusManagerNativePackage::definition(ConsensusManagerV1MinorVersion::One);
+    pub static ref ACCOUNT_PACKAGE_DEFINITION_V1_0: PackageDefinition =
+        AccountNativePackage::definition(AccountV1MinorVersion::Zero);
+    pub static ref ACCOUNT_PACKAGE_DEFINITION_V1_1: PackageDefinition =
+        AccountNativePackage::definition(AccountV1MinorVersion::One);
+    pub static ref ACCOUNT_PACKAGE_DEFINITION_V1_2: PackageDefinition =
+        AccountNativePackage::definition(AccountV1MinorVersion::Two);
+    pub static ref IDENTITY_PACKAGE_DEFINITION_V1_0: PackageDefinition =
+        IdentityNativePackage::definition(IdentityV1MinorVersion::Zero);
+    pub static ref IDENTITY_PACKAGE_DEFINITION_V1_1: PackageDefinition =
+        IdentityNativePackage::definition(IdentityV1MinorVersion::One);
+    pub static ref ACCESS_CONTROLLER_PACKAGE_DEFINITION_V1_0: PackageDefinition =
+        AccessControllerNativePackage::definition(AccessControllerV1MinorVersion::Zero);
+    pub static ref ACCESS_CONTROLLER_PACKAGE_DEFINITION_V1_1: PackageDefinition =
+        AccessControllerNativePackage::definition(AccessControllerV1MinorVersion::One);
     pub static ref POOL_PACKAGE_DEFINITION_V1_0: PackageDefinition =
         PoolNativePackage::definition(PoolV1MinorVersion::Zero);
     pub static ref POOL_PACKAGE_DEFINITION_V1_1: PackageDefinition =
         PoolNativePackage::definition(PoolV1MinorVersion::One);
+    pub static ref POOL_PACKAGE_DEFINITION_V1_2: PackageDefinition =
+        PoolNativePackage::definition(PoolV1MinorVersion::Two);
     pub static ref TRANSACTION_TRACKER_PACKAGE_DEFINITION: PackageDefinition =
         TransactionTrackerNativePackage::definition();
-    pub static ref RESOURCE_PACKAGE_DEFINITION: PackageDefinition =
-        ResourceNativePackage::definition();
-    pub static ref PACKAGE_PACKAGE_DEFINITION: PackageDefinition =
-        PackageNativePackage::definition();
+    pub static ref RESOURCE_PACKAGE_DEFINITION_V1_0: PackageDefinition =
+        ResourceNativePackage::definition(ResourceV1MinorVersion::Zero);
+    pub static ref RESOURCE_PACKAGE_DEFINITION_V1_1: PackageDefinition =
+        ResourceNativePackage::definition(ResourceV1MinorVersion::One);
+    pub static ref RESOURCE_PACKAGE_DEFINITION_V1_2: PackageDefinition =
+        ResourceNativePackage::definition(ResourceV1MinorVersion::Two);
+    pub static ref PACKAGE_PACKAGE_DEFINITION_V1_0: PackageDefinition =
+        PackageNativePackage::definition(PackageV1MinorVersion::Zero);
+    pub static ref PACKAGE_PACKAGE_DEFINITION_V1_1: PackageDefinition =
+        PackageNativePackage::definition(PackageV1MinorVersion::One);
+    pub static ref PACKAGE_PACKAGE_DEFINITION_V1_2: PackageDefinition =
+        PackageNativePackage::definition(PackageV1MinorVersion::Two);
     pub static ref TRANSACTION_PROCESSOR_PACKAGE_DEFINITION: PackageDefinition =
         TransactionProcessorNativePackage::definition();
     pub static ref METADATA_PACKAGE_DEFINITION: PackageDefinition =
         MetadataNativePackage::definition();
-    pub static ref ROYALTY_PACKAGE_DEFINITION: PackageDefinition =
-        RoyaltyNativePackage::definition();
+    pub static ref ROYALTY_PACKAGE_DEFINITION_V1_0: PackageDefinition =
+        RoyaltyNativePackage::definition(RoyaltyV1MinorVersion::Zero);
+    pub static ref ROYALTY_PACKAGE_DEFINITION_V1_1: PackageDefinition =
+        RoyaltyNativePackage::definition(RoyaltyV1MinorVersion::One);
     pub static ref ROLE_ASSIGNMENT_PACKAGE_DEFINITION: PackageDefinition =
         RoleAssignmentNativePackage::definition();
 }
@@ -0,0 +1,46 @@
+use crate::types::*;
+use radix_engine_interface::blueprints::package::BlueprintVersion;
+
+#[derive(ScryptoSbor, ScryptoEvent, Debug, PartialEq, Eq)]
+pub struct ConfigureBlueprintUpgradesEvent {
+    pub blueprint_name: String,
+    pub timelock_in_minutes: u32,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Debug, PartialEq, Eq)]
+pub struct ProposeBlueprintUpgradeEvent {
+    pub blueprint_name: String,
+    pub from_version: BlueprintVersion,
+    pub to_version: BlueprintVersion,
+    pub executable_after: Instant,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Debug, PartialEq, Eq)]
+pub struct ExecuteBlueprintUpgradeEvent {
+    pub blueprint_name: String,
+    pub from_version: BlueprintVersion,
+    pub to_version: BlueprintVersion,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Debug, PartialEq, Eq)]
+pub struct CancelBlueprintUpgradeEvent {
+    pub blueprint_name: String,
+    pub version: BlueprintVersion,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Debug, PartialEq, Eq)]
+pub struct SetRoyaltySplitEvent {
+    pub blueprint_name: String,
+    pub recipients: IndexMap<GlobalAddress, Decimal>,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Debug, PartialEq, Eq)]
+pub struct LockRoyaltySplitEvent {
+    pub blueprint_name: String,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Debug, PartialEq, Eq)]
+pub struct ClaimRoyaltyShareEvent {
+    pub recipient: GlobalAddress,
+    pub amount: Decimal,
+}
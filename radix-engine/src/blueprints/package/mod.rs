@@ -1,5 +1,11 @@
+mod events;
 mod package;
+mod royalty_split;
 mod substates;
+mod upgrade;
 
+pub use events::*;
 pub use package::*;
+pub use royalty_split::*;
 pub use substates::*;
+pub use upgrade::*;
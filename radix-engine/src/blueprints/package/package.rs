@@ -4,7 +4,6 @@ use crate::internal_prelude::*;
 use crate::kernel::kernel_api::{KernelApi, KernelSubstateApi};
 use crate::system::attached_modules::metadata::MetadataNativePackage;
 use crate::system::node_init::type_info_partition;
-use crate::system::system_modules::costing::{apply_royalty_cost, RoyaltyRecipient};
 use crate::system::type_info::TypeInfoSubstate;
 use crate::track::interface::NodeSubstates;
 use crate::types::*;
@@ -137,6 +136,31 @@ pub enum PackageError {
 
     RoyaltiesNotEnabled,
     RoyaltyAmountIsNegative(RoyaltyAmount),
+
+    BlueprintUpgradesNotEnabled(String),
+    BlueprintUpgradeAlreadyPending(String),
+    NoPendingBlueprintUpgrade(String),
+    BlueprintUpgradeTimelockHasNotElapsed(String),
+    BlueprintUpgradeEntryAlreadyStaged(String),
+    IncompatibleBlueprintUpgrade {
+        blueprint: String,
+        incompatibility: BlueprintUpgradeIncompatibility,
+    },
+    TimeOverflow,
+
+    RoyaltySplitRecipientNotAComponent(GlobalAddress),
+    InvalidRoyaltySplitShare {
+        recipient: GlobalAddress,
+        share: Decimal,
+    },
+    RoyaltySplitSharesExceedOne(String),
+    TooManyRoyaltySplitRecipients {
+        max: usize,
+        actual: usize,
+    },
+    RoyaltySplitRecipientNotFound(GlobalAddress),
+    RoyaltySplitNotFound(String),
+    UnexpectedDecimalComputationError,
 }
 
 impl From<InvalidNameError> for PackageError {
@@ -367,7 +391,10 @@ fn validate_type_schemas<'a, I: Iterator<Item = &'a BlueprintDefinitionInit>>(
     Ok(())
 }
 
-fn validate_royalties<Y>(definition: &PackageDefinition, api: &mut Y) -> Result<(), RuntimeError>
+pub(super) fn validate_royalties<Y>(
+    definition: &PackageDefinition,
+    api: &mut Y,
+) -> Result<(), RuntimeError>
 where
     Y: ClientApi<RuntimeError>,
 {
@@ -653,6 +680,7 @@ fn validate_names(definition: &PackageDefinition) -> Result<(), PackageError> {
 }
 
 const SECURIFY_OWNER_ROLE: &str = "securify_owner";
+pub const UPGRADE_AUTHORITY_ROLE: &str = "upgrade_authority";
 
 struct SecurifiedPackage;
 
@@ -683,6 +711,7 @@ pub fn create_package_partition_substates(
 
     let own_features = PackageFeatureSet {
         package_royalty: royalty_vault.is_some(),
+        package_blueprint_upgrades: false,
     };
 
     //-----------------
@@ -693,7 +722,7 @@ pub fn create_package_partition_substates(
         // Note: We don't include royalty field because it's been disabled
 
         let package_schema = blueprint_state_schema(
-            PackageNativePackage::definition(),
+            PackageNativePackage::definition(PackageV1MinorVersion::Zero),
             PACKAGE_BLUEPRINT,
             indexmap!(PackageCollection::SchemaKeyValue.collection_index() as usize => SCHEMAS_PARTITION),
         );
@@ -817,13 +846,30 @@ pub struct PackageStructure {
     pub package_royalties: IndexMap<String, PackageBlueprintVersionRoyaltyConfigEntryPayload>,
 }
 
+/// The minor version of the Package V1 package
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Sbor)]
+pub enum PackageV1MinorVersion {
+    Zero,
+    One,
+    Two,
+}
+
 pub struct PackageNativePackage;
 
 impl PackageNativePackage {
-    pub fn definition() -> PackageDefinition {
+    pub fn definition(minor_version: PackageV1MinorVersion) -> PackageDefinition {
         let mut aggregator = TypeAggregator::<ScryptoCustomTypeKind>::new();
 
-        let state = PackageStateSchemaInit::create_schema_init(&mut aggregator);
+        let mut state = PackageStateSchemaInit::create_schema_init(&mut aggregator);
+        // The royalty split collections were only added in v1.2
+        if minor_version < PackageV1MinorVersion::Two {
+            state.collections.pop();
+            state.collections.pop();
+        }
+        // The blueprint upgrade config collection was only added in v1.1
+        if minor_version < PackageV1MinorVersion::One {
+            state.collections.pop();
+        }
 
         let mut functions = index_map_new();
         functions.insert(
@@ -878,13 +924,191 @@ impl PackageNativePackage {
                 export: PACKAGE_CLAIM_ROYALTIES_IDENT.to_string(),
             },
         );
+        if minor_version >= PackageV1MinorVersion::One {
+            functions.insert(
+                PACKAGE_CONFIGURE_BLUEPRINT_UPGRADES_IDENT.to_string(),
+                FunctionSchemaInit {
+                    receiver: Some(ReceiverInfo::normal_ref_mut()),
+                    input: TypeRef::Static(aggregator
+                        .add_child_type_and_descendents::<PackageConfigureBlueprintUpgradesInput>()),
+                    output: TypeRef::Static(aggregator
+                        .add_child_type_and_descendents::<PackageConfigureBlueprintUpgradesOutput>()),
+                    export: PACKAGE_CONFIGURE_BLUEPRINT_UPGRADES_IDENT.to_string(),
+                },
+            );
+            functions.insert(
+                PACKAGE_PROPOSE_BLUEPRINT_UPGRADE_IDENT.to_string(),
+                FunctionSchemaInit {
+                    receiver: Some(ReceiverInfo::normal_ref_mut()),
+                    input: TypeRef::Static(
+                        aggregator
+                            .add_child_type_and_descendents::<PackageProposeBlueprintUpgradeInput>(
+                            ),
+                    ),
+                    output: TypeRef::Static(
+                        aggregator
+                            .add_child_type_and_descendents::<PackageProposeBlueprintUpgradeOutput>(
+                            ),
+                    ),
+                    export: PACKAGE_PROPOSE_BLUEPRINT_UPGRADE_IDENT.to_string(),
+                },
+            );
+            functions.insert(
+                PACKAGE_EXECUTE_BLUEPRINT_UPGRADE_IDENT.to_string(),
+                FunctionSchemaInit {
+                    receiver: Some(ReceiverInfo::normal_ref_mut()),
+                    input: TypeRef::Static(
+                        aggregator
+                            .add_child_type_and_descendents::<PackageExecuteBlueprintUpgradeInput>(
+                            ),
+                    ),
+                    output: TypeRef::Static(
+                        aggregator
+                            .add_child_type_and_descendents::<PackageExecuteBlueprintUpgradeOutput>(
+                            ),
+                    ),
+                    export: PACKAGE_EXECUTE_BLUEPRINT_UPGRADE_IDENT.to_string(),
+                },
+            );
+            functions.insert(
+                PACKAGE_CANCEL_BLUEPRINT_UPGRADE_IDENT.to_string(),
+                FunctionSchemaInit {
+                    receiver: Some(ReceiverInfo::normal_ref_mut()),
+                    input: TypeRef::Static(
+                        aggregator
+                            .add_child_type_and_descendents::<PackageCancelBlueprintUpgradeInput>(),
+                    ),
+                    output: TypeRef::Static(
+                        aggregator
+                            .add_child_type_and_descendents::<PackageCancelBlueprintUpgradeOutput>(
+                            ),
+                    ),
+                    export: PACKAGE_CANCEL_BLUEPRINT_UPGRADE_IDENT.to_string(),
+                },
+            );
+        }
+        if minor_version >= PackageV1MinorVersion::Two {
+            functions.insert(
+                PACKAGE_SET_ROYALTY_SPLIT_IDENT.to_string(),
+                FunctionSchemaInit {
+                    receiver: Some(ReceiverInfo::normal_ref_mut()),
+                    input: TypeRef::Static(
+                        aggregator.add_child_type_and_descendents::<PackageSetRoyaltySplitInput>(),
+                    ),
+                    output: TypeRef::Static(
+                        aggregator.add_child_type_and_descendents::<PackageSetRoyaltySplitOutput>(),
+                    ),
+                    export: PACKAGE_SET_ROYALTY_SPLIT_IDENT.to_string(),
+                },
+            );
+            functions.insert(
+                PACKAGE_LOCK_ROYALTY_SPLIT_IDENT.to_string(),
+                FunctionSchemaInit {
+                    receiver: Some(ReceiverInfo::normal_ref_mut()),
+                    input: TypeRef::Static(
+                        aggregator.add_child_type_and_descendents::<PackageLockRoyaltySplitInput>(),
+                    ),
+                    output: TypeRef::Static(
+                        aggregator
+                            .add_child_type_and_descendents::<PackageLockRoyaltySplitOutput>(),
+                    ),
+                    export: PACKAGE_LOCK_ROYALTY_SPLIT_IDENT.to_string(),
+                },
+            );
+            functions.insert(
+                PACKAGE_CLAIM_ROYALTY_SHARE_IDENT.to_string(),
+                FunctionSchemaInit {
+                    receiver: Some(ReceiverInfo::normal_ref_mut()),
+                    input: TypeRef::Static(
+                        aggregator
+                            .add_child_type_and_descendents::<PackageClaimRoyaltyShareInput>(),
+                    ),
+                    output: TypeRef::Static(
+                        aggregator
+                            .add_child_type_and_descendents::<PackageClaimRoyaltyShareOutput>(),
+                    ),
+                    export: PACKAGE_CLAIM_ROYALTY_SHARE_IDENT.to_string(),
+                },
+            );
+        }
+
+        let mut events = BlueprintEventSchemaInit::default();
+        if minor_version >= PackageV1MinorVersion::One {
+            events = event_schema! {
+                aggregator,
+                [
+                    ConfigureBlueprintUpgradesEvent,
+                    ProposeBlueprintUpgradeEvent,
+                    ExecuteBlueprintUpgradeEvent,
+                    CancelBlueprintUpgradeEvent
+                ]
+            };
+        }
+        if minor_version >= PackageV1MinorVersion::Two {
+            events.event_schema.extend(
+                event_schema! {
+                    aggregator,
+                    [
+                        SetRoyaltySplitEvent,
+                        LockRoyaltySplitEvent,
+                        ClaimRoyaltyShareEvent
+                    ]
+                }
+                .event_schema,
+            );
+        }
 
         let schema = generate_full_schema(aggregator);
+
+        let mut role_definition = if minor_version >= PackageV1MinorVersion::One {
+            roles_template! {
+                roles {
+                    SECURIFY_OWNER_ROLE;
+                    UPGRADE_AUTHORITY_ROLE => updaters: [OWNER_ROLE];
+                },
+                methods {
+                    PACKAGE_CLAIM_ROYALTIES_IDENT => [SECURIFY_OWNER_ROLE];
+                    PACKAGE_CONFIGURE_BLUEPRINT_UPGRADES_IDENT => [SECURIFY_OWNER_ROLE];
+                    PACKAGE_PROPOSE_BLUEPRINT_UPGRADE_IDENT => [UPGRADE_AUTHORITY_ROLE];
+                    PACKAGE_EXECUTE_BLUEPRINT_UPGRADE_IDENT => [UPGRADE_AUTHORITY_ROLE];
+                    PACKAGE_CANCEL_BLUEPRINT_UPGRADE_IDENT => [UPGRADE_AUTHORITY_ROLE];
+                }
+            }
+        } else {
+            roles_template! {
+                roles {
+                    SECURIFY_OWNER_ROLE;
+                },
+                methods {
+                    PACKAGE_CLAIM_ROYALTIES_IDENT => [SECURIFY_OWNER_ROLE];
+                }
+            }
+        };
+        if minor_version >= PackageV1MinorVersion::Two {
+            role_definition.methods.extend(
+                roles_template!(
+                    methods {
+                        PACKAGE_SET_ROYALTY_SPLIT_IDENT => [SECURIFY_OWNER_ROLE];
+                        PACKAGE_LOCK_ROYALTY_SPLIT_IDENT => [SECURIFY_OWNER_ROLE];
+                        PACKAGE_CLAIM_ROYALTY_SHARE_IDENT => MethodAccessibility::Public;
+                    }
+                )
+                .methods,
+            );
+        }
         let blueprints = indexmap!(
             PACKAGE_BLUEPRINT.to_string() => BlueprintDefinitionInit {
                 blueprint_type: BlueprintType::default(),
                 is_transient: false,
-                feature_set: PackageFeatureSet::all_features(),
+                feature_set: if minor_version >= PackageV1MinorVersion::One {
+                    PackageFeatureSet::all_features()
+                } else {
+                    PackageFeatureSet {
+                        package_royalty: true,
+                        package_blueprint_upgrades: false,
+                    }
+                    .feature_names_string_set()
+                },
                 dependencies: indexset!(
                     PACKAGE_OF_DIRECT_CALLER_VIRTUAL_BADGE.into(),
                     PACKAGE_OWNER_BADGE.into(),
@@ -893,7 +1117,7 @@ impl PackageNativePackage {
                     generics: vec![],
                     schema,
                     state,
-                    events: BlueprintEventSchemaInit::default(),
+                    events,
                     types: BlueprintTypeSchemaInit::default(),
                     functions: BlueprintFunctionsSchemaInit {
                         functions,
@@ -909,16 +1133,7 @@ impl PackageNativePackage {
                             PACKAGE_PUBLISH_NATIVE_IDENT.to_string() => rule!(require(AuthAddresses::system_role())),
                         )
                     ),
-                    method_auth: MethodAuthTemplate::StaticRoleDefinition(
-                        roles_template! {
-                            roles {
-                                SECURIFY_OWNER_ROLE;
-                            },
-                            methods {
-                                PACKAGE_CLAIM_ROYALTIES_IDENT => [SECURIFY_OWNER_ROLE];
-                            }
-                        },
-                    ),
+                    method_auth: MethodAuthTemplate::StaticRoleDefinition(role_definition),
                 },
             }
         );
@@ -933,7 +1148,7 @@ impl PackageNativePackage {
         vm_api: &V,
     ) -> Result<IndexedScryptoValue, RuntimeError>
     where
-        Y: ClientApi<RuntimeError>,
+        Y: ClientApi<RuntimeError> + KernelSubstateApi<SystemLockData>,
         V: VmApi,
     {
         match export_name {
@@ -987,6 +1202,80 @@ impl PackageNativePackage {
                 let rtn = PackageRoyaltyNativeBlueprint::claim_royalties(api)?;
                 Ok(IndexedScryptoValue::from_typed(&rtn))
             }
+            PACKAGE_CONFIGURE_BLUEPRINT_UPGRADES_IDENT => {
+                let input: PackageConfigureBlueprintUpgradesInput =
+                    input.as_typed().map_err(|e| {
+                        RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                    })?;
+                let rtn = PackageUpgradeNativeBlueprint::configure_blueprint_upgrades(
+                    input.blueprint_name,
+                    input.timelock_in_minutes,
+                    api,
+                )?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+            PACKAGE_PROPOSE_BLUEPRINT_UPGRADE_IDENT => {
+                let input: PackageProposeBlueprintUpgradeInput = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn = PackageUpgradeNativeBlueprint::propose_blueprint_upgrade(
+                    input.blueprint_name,
+                    input.definition,
+                    input.code,
+                    api,
+                    vm_api,
+                )?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+            PACKAGE_EXECUTE_BLUEPRINT_UPGRADE_IDENT => {
+                let input: PackageExecuteBlueprintUpgradeInput = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn = PackageUpgradeNativeBlueprint::execute_blueprint_upgrade(
+                    input.blueprint_name,
+                    api,
+                )?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+            PACKAGE_CANCEL_BLUEPRINT_UPGRADE_IDENT => {
+                let input: PackageCancelBlueprintUpgradeInput = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn = PackageUpgradeNativeBlueprint::cancel_blueprint_upgrade(
+                    input.blueprint_name,
+                    api,
+                )?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+            PACKAGE_SET_ROYALTY_SPLIT_IDENT => {
+                let input: PackageSetRoyaltySplitInput = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn = PackageRoyaltySplitNativeBlueprint::set_royalty_split(
+                    input.blueprint_name,
+                    input.recipients,
+                    api,
+                )?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+            PACKAGE_LOCK_ROYALTY_SPLIT_IDENT => {
+                let input: PackageLockRoyaltySplitInput = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn = PackageRoyaltySplitNativeBlueprint::lock_royalty_split(
+                    input.blueprint_name,
+                    api,
+                )?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+            PACKAGE_CLAIM_ROYALTY_SHARE_IDENT => {
+                let input: PackageClaimRoyaltyShareInput = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn =
+                    PackageRoyaltySplitNativeBlueprint::claim_royalty_share(input.recipient, api)?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
             _ => Err(RuntimeError::ApplicationError(
                 ApplicationError::ExportDoesNotExist(export_name.to_string()),
             )),
@@ -1508,11 +1797,12 @@ impl PackageRoyaltyNativeBlueprint {
                 api.kernel_read_substate(handle)?.as_typed().unwrap();
 
             let vault_id = substate.into_payload().into_latest().royalty_vault.0;
-            let package_address = PackageAddress::new_or_panic(receiver.0);
-            apply_royalty_cost(
-                api,
+            PackageRoyaltySplitNativeBlueprint::apply_package_royalty_cost(
+                receiver,
+                bp_version_key.blueprint.as_str(),
                 royalty_charge,
-                RoyaltyRecipient::Package(package_address, vault_id.0),
+                vault_id.0,
+                api,
             )?;
 
             api.kernel_close_substate(handle)?;
@@ -0,0 +1,292 @@
+use super::*;
+use crate::internal_prelude::*;
+use crate::kernel::kernel_api::KernelApi;
+use crate::system::attached_modules::royalty::RoyaltyUtil;
+use crate::system::system_callback::{SystemConfig, SystemLockData};
+use crate::system::system_callback_api::SystemCallbackObject;
+use crate::system::system_modules::costing::RoyaltyRecipient;
+use crate::types::*;
+use native_sdk::resource::{NativeBucket, NativeVault};
+use native_sdk::runtime::Runtime;
+use radix_engine_interface::api::*;
+use radix_engine_interface::blueprints::account::*;
+use radix_engine_interface::blueprints::resource::{global_caller, require, Bucket};
+use radix_engine_interface::rule;
+
+/// The maximum number of recipients which the royalties of a blueprint may be split between.
+/// Every recipient adds to the cost of charging the blueprint's royalties.
+pub const MAX_ROYALTY_SPLIT_RECIPIENTS: usize = 16;
+
+pub struct PackageRoyaltySplitNativeBlueprint;
+
+impl PackageRoyaltySplitNativeBlueprint {
+    pub(crate) fn set_royalty_split<Y>(
+        blueprint_name: String,
+        recipients: IndexMap<GlobalAddress, Decimal>,
+        api: &mut Y,
+    ) -> Result<(), RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        Self::assert_royalties_enabled(api)?;
+
+        // Only blueprints defined by the package may have their royalties split
+        PackageUpgradeNativeBlueprint::get_current_definition(&blueprint_name, api)?;
+
+        RoyaltyUtil::verify_royalty_split(&blueprint_name, &recipients, false)?;
+
+        let handle = Self::open_royalty_split(&blueprint_name, LockFlags::MUTABLE, api)?;
+        api.key_value_entry_set_typed(
+            handle,
+            PackageBlueprintRoyaltySplitEntryPayload::from_content_source(
+                PackageBlueprintRoyaltySplitV1 {
+                    recipients: recipients.clone(),
+                },
+            ),
+        )?;
+        api.key_value_entry_close(handle)?;
+
+        // Vaults are never removed, so that a recipient may still claim what it has accrued after
+        // being removed from a split.
+        for recipient in recipients.keys() {
+            let handle = Self::open_split_vault(recipient, LockFlags::MUTABLE, api)?;
+            if api
+                .key_value_entry_get_typed::<PackageRoyaltySplitVaultEntryPayload>(handle)?
+                .is_none()
+            {
+                let vault = Vault::create(XRD, api)?;
+                api.key_value_entry_set_typed(
+                    handle,
+                    PackageRoyaltySplitVaultEntryPayload::from_content_source(vault),
+                )?;
+            }
+            api.key_value_entry_close(handle)?;
+        }
+
+        Runtime::emit_event(
+            api,
+            SetRoyaltySplitEvent {
+                blueprint_name,
+                recipients,
+            },
+        )?;
+
+        Ok(())
+    }
+
+    pub(crate) fn lock_royalty_split<Y>(
+        blueprint_name: String,
+        api: &mut Y,
+    ) -> Result<(), RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        Self::assert_royalties_enabled(api)?;
+
+        let handle = Self::open_royalty_split(&blueprint_name, LockFlags::MUTABLE, api)?;
+        // Locking a missing split would prevent a split from ever being set up
+        if api
+            .key_value_entry_get_typed::<PackageBlueprintRoyaltySplitEntryPayload>(handle)?
+            .is_none()
+        {
+            return Err(RuntimeError::ApplicationError(
+                ApplicationError::PackageError(PackageError::RoyaltySplitNotFound(blueprint_name)),
+            ));
+        }
+        api.key_value_entry_lock(handle)?;
+        api.key_value_entry_close(handle)?;
+
+        Runtime::emit_event(api, LockRoyaltySplitEvent { blueprint_name })?;
+
+        Ok(())
+    }
+
+    pub(crate) fn claim_royalty_share<Y>(
+        recipient: GlobalAddress,
+        api: &mut Y,
+    ) -> Result<Option<Bucket>, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        // Accounts are deposited into directly so that anyone may trigger the claim, any other
+        // recipient must claim its share itself.
+        let is_account = RoyaltyUtil::is_account(&recipient);
+        if !is_account {
+            Runtime::assert_access_rule(rule!(require(global_caller(recipient))), api)?;
+        }
+
+        let handle = Self::open_split_vault(&recipient, LockFlags::read_only(), api)?;
+        let mut vault = api
+            .key_value_entry_get_typed::<PackageRoyaltySplitVaultEntryPayload>(handle)?
+            .map(|vault| vault.into_latest())
+            .ok_or(RuntimeError::ApplicationError(
+                ApplicationError::PackageError(PackageError::RoyaltySplitRecipientNotFound(
+                    recipient,
+                )),
+            ))?;
+        let bucket = vault.take_all(api)?;
+        api.key_value_entry_close(handle)?;
+
+        let amount = bucket.amount(api)?;
+        Runtime::emit_event(api, ClaimRoyaltyShareEvent { recipient, amount })?;
+
+        if is_account {
+            api.call_method(
+                recipient.as_node_id(),
+                ACCOUNT_TRY_DEPOSIT_OR_ABORT_IDENT,
+                scrypto_encode(&AccountTryDepositOrAbortInput {
+                    bucket,
+                    authorized_depositor_badge: None,
+                })
+                .unwrap(),
+            )?;
+            Ok(None)
+        } else {
+            Ok(Some(bucket))
+        }
+    }
+
+    /// Applies the royalty charged by a blueprint, crediting each recipient of the blueprint's
+    /// royalty split with its share and the package royalty vault with the remainder.
+    pub(super) fn apply_package_royalty_cost<Y, V>(
+        receiver: &NodeId,
+        blueprint_name: &str,
+        royalty_charge: RoyaltyAmount,
+        package_vault_id: NodeId,
+        api: &mut Y,
+    ) -> Result<(), RuntimeError>
+    where
+        V: SystemCallbackObject,
+        Y: KernelApi<SystemConfig<V>>,
+    {
+        let package_address = PackageAddress::new_or_panic(receiver.0);
+
+        let mut split_vaults = Vec::new();
+        if royalty_charge.is_non_zero() {
+            if let Some(split) = Self::read_royalty_split(receiver, blueprint_name, api)? {
+                for (recipient, share) in split.recipients {
+                    // A recipient without a vault has never been set up, so its share stays with
+                    // the package owner.
+                    if let Some(vault_id) = Self::read_split_vault_id(receiver, &recipient, api)? {
+                        split_vaults.push((vault_id, share));
+                    }
+                }
+            }
+        }
+
+        RoyaltyUtil::apply_split_royalty_cost(
+            royalty_charge,
+            split_vaults,
+            package_vault_id,
+            |vault_id| RoyaltyRecipient::Package(package_address, vault_id),
+            false,
+            api,
+        )
+    }
+
+    fn read_royalty_split<Y, V>(
+        receiver: &NodeId,
+        blueprint_name: &str,
+        api: &mut Y,
+    ) -> Result<Option<PackageBlueprintRoyaltySplitV1>, RuntimeError>
+    where
+        V: SystemCallbackObject,
+        Y: KernelApi<SystemConfig<V>>,
+    {
+        let handle = api.kernel_open_substate_with_default(
+            receiver,
+            MAIN_BASE_PARTITION
+                .at_offset(PACKAGE_ROYALTY_SPLIT_PARTITION_OFFSET)
+                .unwrap(),
+            &SubstateKey::Map(scrypto_encode(blueprint_name).unwrap()),
+            LockFlags::read_only(),
+            Some(|| {
+                let kv_entry = KeyValueEntrySubstate::<()>::default();
+                IndexedScryptoValue::from_typed(&kv_entry)
+            }),
+            SystemLockData::default(),
+        )?;
+        let substate: KeyValueEntrySubstate<PackageBlueprintRoyaltySplitEntryPayload> =
+            api.kernel_read_substate(handle)?.as_typed().unwrap();
+        api.kernel_close_substate(handle)?;
+
+        Ok(substate.into_value().map(|split| split.into_latest()))
+    }
+
+    fn read_split_vault_id<Y, V>(
+        receiver: &NodeId,
+        recipient: &GlobalAddress,
+        api: &mut Y,
+    ) -> Result<Option<NodeId>, RuntimeError>
+    where
+        V: SystemCallbackObject,
+        Y: KernelApi<SystemConfig<V>>,
+    {
+        let handle = api.kernel_open_substate_with_default(
+            receiver,
+            MAIN_BASE_PARTITION
+                .at_offset(PACKAGE_ROYALTY_SPLIT_VAULT_PARTITION_OFFSET)
+                .unwrap(),
+            &SubstateKey::Map(scrypto_encode(recipient).unwrap()),
+            LockFlags::read_only(),
+            Some(|| {
+                let kv_entry = KeyValueEntrySubstate::<()>::default();
+                IndexedScryptoValue::from_typed(&kv_entry)
+            }),
+            SystemLockData::default(),
+        )?;
+        let substate: KeyValueEntrySubstate<PackageRoyaltySplitVaultEntryPayload> =
+            api.kernel_read_substate(handle)?.as_typed().unwrap();
+        api.kernel_close_substate(handle)?;
+
+        Ok(substate.into_value().map(|vault| vault.into_latest().0 .0))
+    }
+
+    fn assert_royalties_enabled<Y>(api: &mut Y) -> Result<(), RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        if !api.actor_is_feature_enabled(
+            ACTOR_STATE_SELF,
+            PackageFeature::PackageRoyalty.feature_name(),
+        )? {
+            return Err(RuntimeError::ApplicationError(
+                ApplicationError::PackageError(PackageError::RoyaltiesNotEnabled),
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn open_royalty_split<Y>(
+        blueprint_name: &str,
+        flags: LockFlags,
+        api: &mut Y,
+    ) -> Result<KeyValueEntryHandle, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        api.actor_open_key_value_entry(
+            ACTOR_STATE_SELF,
+            PackageCollection::BlueprintRoyaltySplitKeyValue.collection_index(),
+            &scrypto_encode(blueprint_name).unwrap(),
+            flags,
+        )
+    }
+
+    fn open_split_vault<Y>(
+        recipient: &GlobalAddress,
+        flags: LockFlags,
+        api: &mut Y,
+    ) -> Result<KeyValueEntryHandle, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        api.actor_open_key_value_entry(
+            ACTOR_STATE_SELF,
+            PackageCollection::RoyaltySplitVaultKeyValue.collection_index(),
+            &scrypto_encode(recipient).unwrap(),
+            flags,
+        )
+    }
+}
@@ -8,6 +8,10 @@ declare_native_blueprint_state! {
         package_royalty: {
             ident: PackageRoyalty,
             description: "Enables the package royalty substate",
+        },
+        package_blueprint_upgrades: {
+            ident: PackageBlueprintUpgrades,
+            description: "Records that the package has configured blueprint upgrades",
         }
     },
     fields: {
@@ -110,6 +114,39 @@ declare_native_blueprint_state! {
             },
             allow_ownership: false,
         },
+        blueprint_upgrade_configs: KeyValue {
+            entry_ident: BlueprintUpgradeConfig,
+            key_type: {
+                kind: Static,
+                content_type: String,
+            },
+            value_type: {
+                kind: StaticSingleVersioned,
+            },
+            allow_ownership: false,
+        },
+        blueprint_royalty_splits: KeyValue {
+            entry_ident: BlueprintRoyaltySplit,
+            key_type: {
+                kind: Static,
+                content_type: String,
+            },
+            value_type: {
+                kind: StaticSingleVersioned,
+            },
+            allow_ownership: false,
+        },
+        royalty_split_vaults: KeyValue {
+            entry_ident: RoyaltySplitVault,
+            key_type: {
+                kind: Static,
+                content_type: GlobalAddress,
+            },
+            value_type: {
+                kind: StaticSingleVersioned,
+            },
+            allow_ownership: true,
+        },
     }
 }
 
@@ -169,3 +206,62 @@ impl Debug for PackageCodeInstrumentedCodeV1 {
             .finish()
     }
 }
+
+//---------------------------------------
+// Collection models - By Blueprint
+//---------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct PackageBlueprintUpgradeConfigV1 {
+    /// The version of the definition which was last upgraded to, see [`Self::active_version`].
+    pub current_version: BlueprintVersion,
+    /// The version which was current before the transaction which last executed an upgrade.
+    pub previous_version: BlueprintVersion,
+    /// The hash of the transaction which last executed an upgrade, if any.
+    pub upgraded_in_transaction: Option<Hash>,
+    /// The number of minutes which must pass between an upgrade being proposed and executed.
+    pub timelock_in_minutes: u32,
+    /// The upgrade which has been proposed but not yet executed or cancelled, if any.
+    pub pending_upgrade: Option<PendingBlueprintUpgrade>,
+}
+
+impl PackageBlueprintUpgradeConfigV1 {
+    /// Returns the version which is active for the blueprint in the given transaction. An executed
+    /// upgrade only becomes active from the transaction after the one which executed it, so every
+    /// invocation within a transaction resolves the blueprint to the same version.
+    pub fn active_version(&self, transaction_hash: Option<Hash>) -> BlueprintVersion {
+        match self.upgraded_in_transaction {
+            Some(hash) if transaction_hash == Some(hash) => self.previous_version,
+            _ => self.current_version,
+        }
+    }
+}
+
+/// An upgrade of a blueprint which has passed validation and awaits execution. The code and the
+/// schema it refers to are already stored in the package.
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct PendingBlueprintUpgrade {
+    pub version: BlueprintVersion,
+    pub definition: BlueprintDefinition,
+    pub dependencies: BlueprintDependencies,
+    pub royalty_config: PackageRoyaltyConfig,
+    pub auth_config: AuthConfig,
+    /// The code which was stored for the upgrade, unlocked until the upgrade is executed.
+    pub staged_code_hashes: IndexSet<CodeHash>,
+    /// The schemas which were stored for the upgrade, unlocked until the upgrade is executed.
+    pub staged_schema_hashes: IndexSet<SchemaHash>,
+    pub executable_after: Instant,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, ScryptoSbor)]
+pub struct PackageBlueprintRoyaltySplitV1 {
+    /// The share of each royalty charged by the blueprint which accrues to each recipient.
+    /// The remainder accrues to the package royalty vault.
+    pub recipients: IndexMap<GlobalAddress, Decimal>,
+}
+
+//---------------------------------------
+// Collection models - By Royalty Split Recipient
+//---------------------------------------
+
+pub type PackageRoyaltySplitVaultV1 = Vault;
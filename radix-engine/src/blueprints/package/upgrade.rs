@@ -0,0 +1,917 @@
+use super::*;
+use crate::internal_prelude::*;
+use crate::kernel::kernel_api::{KernelApi, KernelSubstateApi};
+use crate::system::system_callback::{SystemConfig, SystemLockData};
+use crate::system::system_callback_api::SystemCallbackObject;
+use crate::system::system_substates::KeyValueEntrySubstate;
+use crate::system::type_info::{TypeInfoBlueprint, TypeInfoSubstate};
+use crate::types::*;
+use crate::vm::VmApi;
+use native_sdk::runtime::Runtime;
+use radix_engine_interface::api::*;
+use radix_engine_interface::blueprints::consensus_manager::TimePrecision;
+use radix_engine_interface::time::TimeComparisonOperator;
+use sbor::check_type_backwards_compatibility;
+
+/// The reason why an upgraded blueprint definition may not replace the current one.
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub enum BlueprintUpgradeIncompatibility {
+    BlueprintTypeChanged,
+    TransienceChanged,
+    FeatureSetChanged,
+    GenericsChanged,
+    FieldCountChanged,
+    FieldChanged(usize),
+    FieldTypeChanged(usize, PayloadIncompatibility),
+    CollectionRemoved(usize),
+    CollectionChanged(usize),
+    CollectionKeyChanged(usize, PayloadIncompatibility),
+    CollectionValueChanged(usize, PayloadIncompatibility),
+    EventRemoved(String),
+    EventChanged(String, PayloadIncompatibility),
+}
+
+/// The reason why a payload type of an upgraded blueprint definition may not replace the current
+/// one.
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub enum PayloadIncompatibility {
+    /// The payload changed between a static type and a generic, or to another generic.
+    DefinitionChanged,
+    SchemaNotFound(SchemaHash),
+    /// Values of the current type may not decode, or have another meaning, under the upgraded
+    /// type. The path to the offending type is rendered as by `SchemaIncompatibility`.
+    TypeIncompatible {
+        path: String,
+        reason: String,
+    },
+}
+
+/// Checks that all state and events which may have been written under the `current` definition
+/// remain decodable, with the same meaning, under the `upgraded` definition.
+///
+/// New collections and new events may be added, but nothing which exists may be removed. Existing
+/// types may only change in backwards compatible ways, see [`check_type_backwards_compatibility`].
+pub fn check_blueprint_upgrade_compatibility(
+    current: &BlueprintDefinition,
+    upgraded: &BlueprintDefinition,
+    schemas: &IndexMap<SchemaHash, VersionedScryptoSchema>,
+) -> Result<(), BlueprintUpgradeIncompatibility> {
+    let current = &current.interface;
+    let upgraded = &upgraded.interface;
+
+    if current.blueprint_type != upgraded.blueprint_type {
+        return Err(BlueprintUpgradeIncompatibility::BlueprintTypeChanged);
+    }
+    if current.is_transient != upgraded.is_transient {
+        return Err(BlueprintUpgradeIncompatibility::TransienceChanged);
+    }
+    if current.feature_set != upgraded.feature_set {
+        return Err(BlueprintUpgradeIncompatibility::FeatureSetChanged);
+    }
+    if current.generics != upgraded.generics {
+        return Err(BlueprintUpgradeIncompatibility::GenericsChanged);
+    }
+
+    match (&current.state.fields, &upgraded.state.fields) {
+        (None, None) => {}
+        (
+            Some((current_partition, current_fields)),
+            Some((upgraded_partition, upgraded_fields)),
+        ) if current_partition == upgraded_partition
+            && current_fields.len() == upgraded_fields.len() =>
+        {
+            for (index, (current_field, upgraded_field)) in current_fields
+                .iter()
+                .zip(upgraded_fields.iter())
+                .enumerate()
+            {
+                if current_field.condition != upgraded_field.condition
+                    || current_field.transience != upgraded_field.transience
+                {
+                    return Err(BlueprintUpgradeIncompatibility::FieldChanged(index));
+                }
+                check_payload_compatibility(&current_field.field, &upgraded_field.field, schemas)
+                    .map_err(|incompatibility| {
+                    BlueprintUpgradeIncompatibility::FieldTypeChanged(index, incompatibility)
+                })?;
+            }
+        }
+        _ => return Err(BlueprintUpgradeIncompatibility::FieldCountChanged),
+    }
+
+    for (index, (current_partition, current_collection)) in
+        current.state.collections.iter().enumerate()
+    {
+        let (upgraded_partition, upgraded_collection) = upgraded
+            .state
+            .collections
+            .get(index)
+            .ok_or(BlueprintUpgradeIncompatibility::CollectionRemoved(index))?;
+
+        let (current, upgraded) = match (current_collection, upgraded_collection) {
+            (
+                BlueprintCollectionSchema::KeyValueStore(current),
+                BlueprintCollectionSchema::KeyValueStore(upgraded),
+            )
+            | (
+                BlueprintCollectionSchema::Index(current),
+                BlueprintCollectionSchema::Index(upgraded),
+            )
+            | (
+                BlueprintCollectionSchema::SortedIndex(current),
+                BlueprintCollectionSchema::SortedIndex(upgraded),
+            ) if current_partition == upgraded_partition
+                && current.allow_ownership == upgraded.allow_ownership =>
+            {
+                (current, upgraded)
+            }
+            _ => return Err(BlueprintUpgradeIncompatibility::CollectionChanged(index)),
+        };
+        check_payload_compatibility(&current.key, &upgraded.key, schemas).map_err(
+            |incompatibility| {
+                BlueprintUpgradeIncompatibility::CollectionKeyChanged(index, incompatibility)
+            },
+        )?;
+        check_payload_compatibility(&current.value, &upgraded.value, schemas).map_err(
+            |incompatibility| {
+                BlueprintUpgradeIncompatibility::CollectionValueChanged(index, incompatibility)
+            },
+        )?;
+    }
+
+    for (event_name, current_event) in &current.events {
+        let upgraded_event = upgraded
+            .events
+            .get(event_name)
+            .ok_or_else(|| BlueprintUpgradeIncompatibility::EventRemoved(event_name.clone()))?;
+        check_payload_compatibility(current_event, upgraded_event, schemas).map_err(
+            |incompatibility| {
+                BlueprintUpgradeIncompatibility::EventChanged(event_name.clone(), incompatibility)
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+fn check_payload_compatibility(
+    current: &BlueprintPayloadDef,
+    upgraded: &BlueprintPayloadDef,
+    schemas: &IndexMap<SchemaHash, VersionedScryptoSchema>,
+) -> Result<(), PayloadIncompatibility> {
+    match (current, upgraded) {
+        (BlueprintPayloadDef::Generic(current), BlueprintPayloadDef::Generic(upgraded))
+            if current == upgraded =>
+        {
+            Ok(())
+        }
+        (
+            BlueprintPayloadDef::Static(ScopedTypeId(current_hash, current_type_id)),
+            BlueprintPayloadDef::Static(ScopedTypeId(upgraded_hash, upgraded_type_id)),
+        ) => {
+            let current_schema = schemas
+                .get(current_hash)
+                .ok_or(PayloadIncompatibility::SchemaNotFound(*current_hash))?;
+            let upgraded_schema = schemas
+                .get(upgraded_hash)
+                .ok_or(PayloadIncompatibility::SchemaNotFound(*upgraded_hash))?;
+            check_type_backwards_compatibility(
+                current_schema.v1(),
+                *current_type_id,
+                upgraded_schema.v1(),
+                *upgraded_type_id,
+            )
+            .map_err(|incompatibility| PayloadIncompatibility::TypeIncompatible {
+                path: incompatibility.path_to_string(),
+                reason: format!("{:?}", incompatibility.reason),
+            })
+        }
+        _ => Err(PayloadIncompatibility::DefinitionChanged),
+    }
+}
+
+pub struct PackageUpgradeNativeBlueprint;
+
+impl PackageUpgradeNativeBlueprint {
+    pub(crate) fn configure_blueprint_upgrades<Y>(
+        blueprint_name: String,
+        timelock_in_minutes: u32,
+        api: &mut Y,
+    ) -> Result<(), RuntimeError>
+    where
+        Y: ClientApi<RuntimeError> + KernelSubstateApi<SystemLockData>,
+    {
+        // Only blueprints defined by the package may be configured
+        Self::get_definition(&blueprint_name, BlueprintVersion::default(), api)?;
+
+        let handle = Self::open_upgrade_config(&blueprint_name, LockFlags::MUTABLE, api)?;
+        let config = match api
+            .key_value_entry_get_typed::<PackageBlueprintUpgradeConfigEntryPayload>(handle)?
+        {
+            Some(config) => PackageBlueprintUpgradeConfigV1 {
+                timelock_in_minutes,
+                ..config.into_latest()
+            },
+            None => PackageBlueprintUpgradeConfigV1 {
+                current_version: BlueprintVersion::default(),
+                previous_version: BlueprintVersion::default(),
+                upgraded_in_transaction: None,
+                timelock_in_minutes,
+                pending_upgrade: None,
+            },
+        };
+        api.key_value_entry_set_typed(
+            handle,
+            PackageBlueprintUpgradeConfigEntryPayload::from_content_source(config),
+        )?;
+        api.key_value_entry_close(handle)?;
+
+        Self::enable_blueprint_upgrades_feature(api)?;
+
+        Runtime::emit_event(
+            api,
+            ConfigureBlueprintUpgradesEvent {
+                blueprint_name,
+                timelock_in_minutes,
+            },
+        )?;
+
+        Ok(())
+    }
+
+    pub(crate) fn propose_blueprint_upgrade<Y, V>(
+        blueprint_name: String,
+        definition: BlueprintDefinitionInit,
+        code: Vec<u8>,
+        api: &mut Y,
+        vm_api: &V,
+    ) -> Result<(), RuntimeError>
+    where
+        Y: ClientApi<RuntimeError> + KernelSubstateApi<SystemLockData>,
+        V: VmApi,
+    {
+        let handle = Self::open_upgrade_config(&blueprint_name, LockFlags::MUTABLE, api)?;
+        let mut config = Self::get_upgrade_config(&blueprint_name, handle, api)?;
+        if config.pending_upgrade.is_some() {
+            return Err(RuntimeError::ApplicationError(
+                ApplicationError::PackageError(PackageError::BlueprintUpgradeAlreadyPending(
+                    blueprint_name,
+                )),
+            ));
+        }
+
+        let current_definition =
+            Self::get_definition(&blueprint_name, config.current_version, api)?;
+
+        let package_definition = PackageDefinition {
+            blueprints: indexmap!(blueprint_name.clone() => definition),
+        };
+        validate_royalties(&package_definition, api)?;
+        let mut structure = PackageNativePackage::validate_and_build_package_structure(
+            package_definition,
+            VmType::ScryptoV1,
+            code,
+            Default::default(),
+            vm_api,
+        )?;
+
+        let mut schemas = index_map_new();
+        for schema_hash in Self::referenced_schema_hashes(&current_definition) {
+            let handle = api.actor_open_key_value_entry(
+                ACTOR_STATE_SELF,
+                PackageCollection::SchemaKeyValue.collection_index(),
+                &scrypto_encode(&schema_hash).unwrap(),
+                LockFlags::read_only(),
+            )?;
+            let schema = api.key_value_entry_get_typed::<PackageSchemaEntryPayload>(handle)?;
+            api.key_value_entry_close(handle)?;
+            if let Some(schema) = schema {
+                schemas.insert(schema_hash, schema.into_content());
+            }
+        }
+        for (schema_hash, schema) in &structure.schemas {
+            schemas.insert(*schema_hash, schema.as_ref().clone());
+        }
+
+        let upgraded_definition = structure
+            .definitions
+            .swap_remove(&blueprint_name)
+            .unwrap()
+            .into_latest();
+        check_blueprint_upgrade_compatibility(&current_definition, &upgraded_definition, &schemas)
+            .map_err(|incompatibility| {
+                RuntimeError::ApplicationError(ApplicationError::PackageError(
+                    PackageError::IncompatibleBlueprintUpgrade {
+                        blueprint: blueprint_name.clone(),
+                        incompatibility,
+                    },
+                ))
+            })?;
+
+        // The code and schemas are content addressed, so they are staged under their hashes ahead
+        // of the upgrade. Entries which are already locked belong to a published version and are
+        // shared, everything else is staged unlocked so that it can be removed on cancellation.
+        let mut staged_code_hashes = index_set_new();
+        for (code_hash, vm_type) in structure.vm_type {
+            if !Self::stage_package_entry(
+                &blueprint_name,
+                PackageCollection::CodeOriginalCodeKeyValue,
+                &code_hash,
+                api,
+            )? {
+                continue;
+            }
+            Self::set_package_entry(
+                PackageCollection::CodeVmTypeKeyValue,
+                &code_hash,
+                vm_type,
+                false,
+                api,
+            )?;
+            Self::set_package_entry(
+                PackageCollection::CodeOriginalCodeKeyValue,
+                &code_hash,
+                structure.original_code.swap_remove(&code_hash).unwrap(),
+                false,
+                api,
+            )?;
+            Self::set_package_entry(
+                PackageCollection::CodeInstrumentedCodeKeyValue,
+                &code_hash,
+                structure.instrumented_code.swap_remove(&code_hash).unwrap(),
+                false,
+                api,
+            )?;
+            staged_code_hashes.insert(code_hash);
+        }
+        let mut staged_schema_hashes = index_set_new();
+        for (schema_hash, schema) in structure.schemas {
+            if !Self::stage_package_entry(
+                &blueprint_name,
+                PackageCollection::SchemaKeyValue,
+                &schema_hash,
+                api,
+            )? {
+                continue;
+            }
+            Self::set_package_entry(
+                PackageCollection::SchemaKeyValue,
+                &schema_hash,
+                schema,
+                false,
+                api,
+            )?;
+            staged_schema_hashes.insert(schema_hash);
+        }
+
+        let version = BlueprintVersion {
+            major: config.current_version.major,
+            minor: config.current_version.minor + 1,
+            patch: 0,
+        };
+        let executable_after = Runtime::current_time(api, TimePrecision::Minute)?
+            .add_minutes(config.timelock_in_minutes as i64)
+            .ok_or(RuntimeError::ApplicationError(
+                ApplicationError::PackageError(PackageError::TimeOverflow),
+            ))?;
+
+        config.pending_upgrade = Some(PendingBlueprintUpgrade {
+            version,
+            definition: upgraded_definition,
+            dependencies: structure
+                .dependencies
+                .swap_remove(&blueprint_name)
+                .unwrap()
+                .into_latest(),
+            royalty_config: structure
+                .package_royalties
+                .swap_remove(&blueprint_name)
+                .unwrap()
+                .into_latest(),
+            auth_config: structure
+                .auth_configs
+                .swap_remove(&blueprint_name)
+                .unwrap()
+                .into_latest(),
+            staged_code_hashes,
+            staged_schema_hashes,
+            executable_after,
+        });
+        let from_version = config.current_version;
+        api.key_value_entry_set_typed(
+            handle,
+            PackageBlueprintUpgradeConfigEntryPayload::from_content_source(config),
+        )?;
+        api.key_value_entry_close(handle)?;
+
+        Runtime::emit_event(
+            api,
+            ProposeBlueprintUpgradeEvent {
+                blueprint_name,
+                from_version,
+                to_version: version,
+                executable_after,
+            },
+        )?;
+
+        Ok(())
+    }
+
+    pub(crate) fn execute_blueprint_upgrade<Y>(
+        blueprint_name: String,
+        api: &mut Y,
+    ) -> Result<BlueprintVersion, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let handle = Self::open_upgrade_config(&blueprint_name, LockFlags::MUTABLE, api)?;
+        let mut config = Self::get_upgrade_config(&blueprint_name, handle, api)?;
+        let upgrade = config.pending_upgrade.take().ok_or_else(|| {
+            RuntimeError::ApplicationError(ApplicationError::PackageError(
+                PackageError::NoPendingBlueprintUpgrade(blueprint_name.clone()),
+            ))
+        })?;
+
+        if !Runtime::compare_against_current_time(
+            api,
+            upgrade.executable_after,
+            TimePrecision::Minute,
+            TimeComparisonOperator::Gte,
+        )? {
+            return Err(RuntimeError::ApplicationError(
+                ApplicationError::PackageError(
+                    PackageError::BlueprintUpgradeTimelockHasNotElapsed(blueprint_name),
+                ),
+            ));
+        }
+
+        // The upgraded definition is recorded under its own version, next to the versions which
+        // are already published, and the system resolves the blueprint through the active
+        // version of its upgrade config.
+        let version = upgrade.version;
+        let key = BlueprintVersionKey {
+            blueprint: blueprint_name.clone(),
+            version,
+        };
+        Self::set_package_entry(
+            PackageCollection::BlueprintVersionDefinitionKeyValue,
+            &key,
+            PackageBlueprintVersionDefinitionEntryPayload::from_content_source(upgrade.definition),
+            true,
+            api,
+        )?;
+        Self::set_package_entry(
+            PackageCollection::BlueprintVersionDependenciesKeyValue,
+            &key,
+            PackageBlueprintVersionDependenciesEntryPayload::from_content_source(
+                upgrade.dependencies,
+            ),
+            true,
+            api,
+        )?;
+        Self::set_package_entry(
+            PackageCollection::BlueprintVersionRoyaltyConfigKeyValue,
+            &key,
+            PackageBlueprintVersionRoyaltyConfigEntryPayload::from_content_source(
+                upgrade.royalty_config,
+            ),
+            true,
+            api,
+        )?;
+        Self::set_package_entry(
+            PackageCollection::BlueprintVersionAuthConfigKeyValue,
+            &key,
+            PackageBlueprintVersionAuthConfigEntryPayload::from_content_source(upgrade.auth_config),
+            true,
+            api,
+        )?;
+        for code_hash in &upgrade.staged_code_hashes {
+            for collection in Self::CODE_COLLECTIONS {
+                Self::lock_package_entry(collection, code_hash, api)?;
+            }
+        }
+        for schema_hash in &upgrade.staged_schema_hashes {
+            Self::lock_package_entry(PackageCollection::SchemaKeyValue, schema_hash, api)?;
+        }
+
+        // The upgraded version only becomes active from the next transaction on, see
+        // `PackageBlueprintUpgradeConfigV1::active_version`
+        let transaction_hash = api.get_transaction_hash()?;
+        if config.upgraded_in_transaction != Some(transaction_hash) {
+            config.previous_version = config.current_version;
+            config.upgraded_in_transaction = Some(transaction_hash);
+        }
+        let from_version = config.current_version;
+        config.current_version = version;
+        api.key_value_entry_set_typed(
+            handle,
+            PackageBlueprintUpgradeConfigEntryPayload::from_content_source(config),
+        )?;
+        api.key_value_entry_close(handle)?;
+
+        Runtime::emit_event(
+            api,
+            ExecuteBlueprintUpgradeEvent {
+                blueprint_name,
+                from_version,
+                to_version: version,
+            },
+        )?;
+
+        Ok(version)
+    }
+
+    pub(crate) fn cancel_blueprint_upgrade<Y>(
+        blueprint_name: String,
+        api: &mut Y,
+    ) -> Result<(), RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let handle = Self::open_upgrade_config(&blueprint_name, LockFlags::MUTABLE, api)?;
+        let mut config = Self::get_upgrade_config(&blueprint_name, handle, api)?;
+        let upgrade = config.pending_upgrade.take().ok_or_else(|| {
+            RuntimeError::ApplicationError(ApplicationError::PackageError(
+                PackageError::NoPendingBlueprintUpgrade(blueprint_name.clone()),
+            ))
+        })?;
+        api.key_value_entry_set_typed(
+            handle,
+            PackageBlueprintUpgradeConfigEntryPayload::from_content_source(config),
+        )?;
+        api.key_value_entry_close(handle)?;
+
+        for code_hash in &upgrade.staged_code_hashes {
+            for collection in Self::CODE_COLLECTIONS {
+                api.actor_remove_key_value_entry(
+                    ACTOR_STATE_SELF,
+                    collection.collection_index(),
+                    &scrypto_encode(code_hash).unwrap(),
+                )?;
+            }
+        }
+        for schema_hash in &upgrade.staged_schema_hashes {
+            api.actor_remove_key_value_entry(
+                ACTOR_STATE_SELF,
+                PackageCollection::SchemaKeyValue.collection_index(),
+                &scrypto_encode(schema_hash).unwrap(),
+            )?;
+        }
+
+        Runtime::emit_event(
+            api,
+            CancelBlueprintUpgradeEvent {
+                blueprint_name,
+                version: upgrade.version,
+            },
+        )?;
+
+        Ok(())
+    }
+
+    /// Returns the definition of the version which is currently active for a blueprint.
+    pub(super) fn get_current_definition<Y>(
+        blueprint_name: &str,
+        api: &mut Y,
+    ) -> Result<BlueprintDefinition, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let handle = Self::open_upgrade_config(blueprint_name, LockFlags::read_only(), api)?;
+        let version = api
+            .key_value_entry_get_typed::<PackageBlueprintUpgradeConfigEntryPayload>(handle)?
+            .map(|config| config.into_latest().current_version)
+            .unwrap_or_default();
+        api.key_value_entry_close(handle)?;
+
+        Self::get_definition(blueprint_name, version, api)
+    }
+
+    fn get_definition<Y>(
+        blueprint_name: &str,
+        version: BlueprintVersion,
+        api: &mut Y,
+    ) -> Result<BlueprintDefinition, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let key = BlueprintVersionKey {
+            blueprint: blueprint_name.to_string(),
+            version,
+        };
+        let handle = api.actor_open_key_value_entry(
+            ACTOR_STATE_SELF,
+            PackageCollection::BlueprintVersionDefinitionKeyValue.collection_index(),
+            &scrypto_encode(&key).unwrap(),
+            LockFlags::read_only(),
+        )?;
+        let definition =
+            api.key_value_entry_get_typed::<PackageBlueprintVersionDefinitionEntryPayload>(handle)?;
+        api.key_value_entry_close(handle)?;
+
+        match definition {
+            Some(definition) => Ok(definition.into_latest()),
+            None => {
+                let package_address =
+                    PackageAddress::new_or_panic(api.actor_get_node_id(ACTOR_REF_SELF)?.into());
+                Err(RuntimeError::SystemError(
+                    SystemError::BlueprintDoesNotExist(CanonicalBlueprintId {
+                        address: package_address,
+                        blueprint: blueprint_name.to_string(),
+                        version,
+                    }),
+                ))
+            }
+        }
+    }
+
+    /// Resolves the key of the version which is currently active for a blueprint, as recorded by
+    /// its upgrade config, or of the default version if the blueprint has never been upgraded.
+    ///
+    /// Until blueprint upgrades are enabled by the boot loader, and for packages which never
+    /// configured blueprint upgrades, this resolves to the default version without reading the
+    /// upgrade config. An executed upgrade takes effect from the next transaction on, see
+    /// [`PackageBlueprintUpgradeConfigV1::active_version`].
+    pub fn resolve_current_version_key<Y, V>(
+        blueprint_id: &BlueprintId,
+        api: &mut Y,
+    ) -> Result<BlueprintVersionKey, RuntimeError>
+    where
+        Y: KernelApi<SystemConfig<V>>,
+        V: SystemCallbackObject,
+    {
+        if !api
+            .kernel_get_system_state()
+            .system
+            .package_blueprint_upgrades
+        {
+            return Ok(BlueprintVersionKey::new_default(
+                blueprint_id.blueprint_name.as_str(),
+            ));
+        }
+
+        let cached_version = api
+            .kernel_get_system_state()
+            .system
+            .blueprint_version_cache
+            .get(blueprint_id)
+            .cloned();
+        let version = match cached_version {
+            Some(version) => version,
+            None if !Self::has_configured_blueprint_upgrades(blueprint_id, api)? => {
+                api.kernel_get_system_state()
+                    .system
+                    .blueprint_version_cache
+                    .insert(blueprint_id.clone(), BlueprintVersion::default());
+                BlueprintVersion::default()
+            }
+            None => {
+                let handle = api.kernel_open_substate_with_default(
+                    blueprint_id.package_address.as_node_id(),
+                    MAIN_BASE_PARTITION
+                        .at_offset(PACKAGE_BLUEPRINT_UPGRADE_CONFIG_PARTITION_OFFSET)
+                        .unwrap(),
+                    &SubstateKey::Map(scrypto_encode(&blueprint_id.blueprint_name).unwrap()),
+                    LockFlags::read_only(),
+                    Some(|| {
+                        let kv_entry = KeyValueEntrySubstate::<()>::default();
+                        IndexedScryptoValue::from_typed(&kv_entry)
+                    }),
+                    SystemLockData::default(),
+                )?;
+                let config: PackageBlueprintUpgradeConfigEntrySubstate =
+                    api.kernel_read_substate(handle)?.as_typed().unwrap();
+                api.kernel_close_substate(handle)?;
+
+                let transaction_hash = api
+                    .kernel_get_system_state()
+                    .system
+                    .modules
+                    .transaction_hash();
+                let version = config
+                    .into_value()
+                    .map(|config| config.into_latest().active_version(transaction_hash))
+                    .unwrap_or_default();
+                api.kernel_get_system_state()
+                    .system
+                    .blueprint_version_cache
+                    .insert(blueprint_id.clone(), version);
+                version
+            }
+        };
+
+        Ok(BlueprintVersionKey {
+            blueprint: blueprint_id.blueprint_name.clone(),
+            version,
+        })
+    }
+
+    /// Whether the package of a blueprint has configured upgrades for any of its blueprints, as
+    /// recorded by the blueprint upgrades feature of the package.
+    fn has_configured_blueprint_upgrades<Y, V>(
+        blueprint_id: &BlueprintId,
+        api: &mut Y,
+    ) -> Result<bool, RuntimeError>
+    where
+        Y: KernelApi<SystemConfig<V>>,
+        V: SystemCallbackObject,
+    {
+        let type_info =
+            TypeInfoBlueprint::get_type(blueprint_id.package_address.as_node_id(), api)?;
+        let enabled = match type_info {
+            TypeInfoSubstate::Object(object_info) => object_info
+                .blueprint_info
+                .features
+                .contains(PackageFeature::PackageBlueprintUpgrades.feature_name()),
+            _ => false,
+        };
+        Ok(enabled)
+    }
+
+    /// Records the blueprint upgrades feature on the package, which the system checks before
+    /// resolving any blueprint of the package through its upgrade config.
+    fn enable_blueprint_upgrades_feature<Y>(api: &mut Y) -> Result<(), RuntimeError>
+    where
+        Y: ClientApi<RuntimeError> + KernelSubstateApi<SystemLockData>,
+    {
+        let package_node_id = api.actor_get_node_id(ACTOR_REF_SELF)?;
+        let handle = api.kernel_open_substate(
+            &package_node_id,
+            TYPE_INFO_FIELD_PARTITION,
+            &TypeInfoField::TypeInfo.into(),
+            LockFlags::MUTABLE,
+            SystemLockData::default(),
+        )?;
+        let mut type_info: TypeInfoSubstate = api.kernel_read_substate(handle)?.as_typed().unwrap();
+        if let TypeInfoSubstate::Object(object_info) = &mut type_info {
+            let newly_enabled = object_info.blueprint_info.features.insert(
+                PackageFeature::PackageBlueprintUpgrades
+                    .feature_name()
+                    .to_string(),
+            );
+            if newly_enabled {
+                api.kernel_write_substate(handle, IndexedScryptoValue::from_typed(&type_info))?;
+            }
+        }
+        api.kernel_close_substate(handle)?;
+        Ok(())
+    }
+
+    fn open_upgrade_config<Y>(
+        blueprint_name: &str,
+        flags: LockFlags,
+        api: &mut Y,
+    ) -> Result<KeyValueEntryHandle, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        api.actor_open_key_value_entry(
+            ACTOR_STATE_SELF,
+            PackageCollection::BlueprintUpgradeConfigKeyValue.collection_index(),
+            &scrypto_encode(blueprint_name).unwrap(),
+            flags,
+        )
+    }
+
+    fn get_upgrade_config<Y>(
+        blueprint_name: &str,
+        handle: KeyValueEntryHandle,
+        api: &mut Y,
+    ) -> Result<PackageBlueprintUpgradeConfigV1, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        api.key_value_entry_get_typed::<PackageBlueprintUpgradeConfigEntryPayload>(handle)?
+            .map(|config| config.into_latest())
+            .ok_or_else(|| {
+                RuntimeError::ApplicationError(ApplicationError::PackageError(
+                    PackageError::BlueprintUpgradesNotEnabled(blueprint_name.to_string()),
+                ))
+            })
+    }
+
+    fn referenced_schema_hashes(definition: &BlueprintDefinition) -> IndexSet<SchemaHash> {
+        let state = &definition.interface.state;
+        let payloads = state
+            .fields
+            .iter()
+            .flat_map(|(_, fields)| fields.iter().map(|field| &field.field))
+            .chain(state.collections.iter().flat_map(|(_, collection)| {
+                let schema = match collection {
+                    BlueprintCollectionSchema::KeyValueStore(schema)
+                    | BlueprintCollectionSchema::Index(schema)
+                    | BlueprintCollectionSchema::SortedIndex(schema) => schema,
+                };
+                [&schema.key, &schema.value]
+            }))
+            .chain(definition.interface.events.values());
+
+        payloads
+            .filter_map(|payload| match payload {
+                BlueprintPayloadDef::Static(ScopedTypeId(schema_hash, _)) => Some(*schema_hash),
+                BlueprintPayloadDef::Generic(_) => None,
+            })
+            .collect()
+    }
+
+    const CODE_COLLECTIONS: [PackageCollection; 3] = [
+        PackageCollection::CodeVmTypeKeyValue,
+        PackageCollection::CodeOriginalCodeKeyValue,
+        PackageCollection::CodeInstrumentedCodeKeyValue,
+    ];
+
+    /// Checks whether a content addressed entry needs to be staged for an upgrade. Returns false
+    /// if the entry is locked, i.e. it belongs to a published version, and fails if the entry is
+    /// already staged by the pending upgrade of another blueprint, as that upgrade may remove it.
+    fn stage_package_entry<Y, K>(
+        blueprint_name: &str,
+        collection: PackageCollection,
+        key: &K,
+        api: &mut Y,
+    ) -> Result<bool, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError> + KernelSubstateApi<SystemLockData>,
+        K: ScryptoEncode,
+    {
+        let partition_num = match collection {
+            PackageCollection::SchemaKeyValue => SCHEMAS_PARTITION,
+            PackageCollection::CodeOriginalCodeKeyValue => MAIN_BASE_PARTITION
+                .at_offset(PACKAGE_ORIGINAL_CODE_PARTITION_OFFSET)
+                .unwrap(),
+            _ => panic!("Only code and schemas are staged"),
+        };
+        let node_id = api.actor_get_node_id(ACTOR_REF_SELF)?;
+        let handle = api.kernel_open_substate_with_default(
+            &node_id,
+            partition_num,
+            &SubstateKey::Map(scrypto_encode(key).unwrap()),
+            LockFlags::read_only(),
+            Some(|| {
+                let kv_entry = KeyValueEntrySubstate::<()>::default();
+                IndexedScryptoValue::from_typed(&kv_entry)
+            }),
+            SystemLockData::default(),
+        )?;
+        let entry: KeyValueEntrySubstate<ScryptoValue> =
+            api.kernel_read_substate(handle)?.as_typed().unwrap();
+        api.kernel_close_substate(handle)?;
+
+        if entry.is_locked() {
+            Ok(false)
+        } else if entry.into_value().is_some() {
+            Err(RuntimeError::ApplicationError(
+                ApplicationError::PackageError(PackageError::BlueprintUpgradeEntryAlreadyStaged(
+                    blueprint_name.to_string(),
+                )),
+            ))
+        } else {
+            Ok(true)
+        }
+    }
+
+    fn set_package_entry<Y, K, V>(
+        collection: PackageCollection,
+        key: &K,
+        value: V,
+        lock: bool,
+        api: &mut Y,
+    ) -> Result<(), RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+        K: ScryptoEncode,
+        V: ScryptoEncode,
+    {
+        let handle = api.actor_open_key_value_entry(
+            ACTOR_STATE_SELF,
+            collection.collection_index(),
+            &scrypto_encode(key).unwrap(),
+            LockFlags::MUTABLE,
+        )?;
+        api.key_value_entry_set_typed(handle, value)?;
+        if lock {
+            api.key_value_entry_lock(handle)?;
+        }
+        api.key_value_entry_close(handle)?;
+        Ok(())
+    }
+
+    fn lock_package_entry<Y, K>(
+        collection: PackageCollection,
+        key: &K,
+        api: &mut Y,
+    ) -> Result<(), RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+        K: ScryptoEncode,
+    {
+        let handle = api.actor_open_key_value_entry(
+            ACTOR_STATE_SELF,
+            collection.collection_index(),
+            &scrypto_encode(key).unwrap(),
+            LockFlags::MUTABLE,
+        )?;
+        api.key_value_entry_lock(handle)?;
+        api.key_value_entry_close(handle)?;
+        Ok(())
+    }
+}
@@ -2,6 +2,8 @@
 pub const ONE_RESOURCE_POOL_BLUEPRINT_IDENT: &str = "OneResourcePool";
 pub const TWO_RESOURCE_POOL_BLUEPRINT_IDENT: &str = "TwoResourcePool";
 pub const MULTI_RESOURCE_POOL_BLUEPRINT_IDENT: &str = "MultiResourcePool";
+pub const CONSTANT_PRODUCT_POOL_BLUEPRINT_IDENT: &str = "ConstantProductPool";
 
 // Roles
 pub const POOL_MANAGER_ROLE: &str = "pool_manager_role";
+pub const FEE_MANAGER_ROLE: &str = "fee_manager_role";
@@ -97,3 +97,40 @@ pub mod multi_resource_pool {
         }
     }
 }
+
+pub mod constant_product_pool {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+    pub enum Error {
+        InvalidFeeRate {
+            fee_rate: Decimal,
+        },
+        ResourceDoesNotBelongToPool {
+            resource_address: ResourceAddress,
+        },
+        InvalidSwapAmount {
+            amount: Decimal,
+        },
+        PoolHasNoLiquidity,
+        SwapOutputExceedsReserves {
+            output_amount: Decimal,
+            reserves: Decimal,
+        },
+        SwapOutputBelowMinimum {
+            output_amount: Decimal,
+            minimum_output_amount: Decimal,
+        },
+        SwapInputAboveMaximum {
+            input_amount: Decimal,
+            maximum_input_amount: Decimal,
+        },
+        DecimalOverflowError,
+    }
+
+    impl From<Error> for RuntimeError {
+        fn from(error: Error) -> Self {
+            Self::ApplicationError(ApplicationError::ConstantProductPoolError(error))
+        }
+    }
+}
@@ -81,3 +81,22 @@ pub mod multi_resource_pool {
         pub amount: Decimal,
     }
 }
+
+pub mod constant_product_pool {
+    use super::*;
+
+    #[derive(ScryptoSbor, ScryptoEvent, Debug)]
+    pub struct SwapEvent {
+        pub input_resource_address: ResourceAddress,
+        pub input_amount: Decimal,
+        pub output_resource_address: ResourceAddress,
+        pub output_amount: Decimal,
+        /// The part of the input amount that was charged as a fee and left in the pool.
+        pub fee_amount: Decimal,
+    }
+
+    #[derive(ScryptoSbor, ScryptoEvent, Debug)]
+    pub struct FeeRateChangeEvent {
+        pub fee_rate: Decimal,
+    }
+}
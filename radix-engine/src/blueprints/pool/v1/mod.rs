@@ -1,5 +1,6 @@
 pub mod v1_0;
 pub mod v1_1;
+pub mod v1_2;
 
 pub mod constants;
 pub mod errors;
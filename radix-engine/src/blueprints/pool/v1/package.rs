@@ -1,6 +1,7 @@
 #![allow(clippy::let_unit_value)]
 
 use super::constants::*;
+use super::substates::constant_product_pool::*;
 use super::substates::multi_resource_pool::*;
 use super::substates::one_resource_pool::*;
 use super::substates::two_resource_pool::*;
@@ -17,6 +18,7 @@ use radix_engine_interface::prelude::*;
 pub enum PoolV1MinorVersion {
     Zero,
     One,
+    Two,
 }
 
 pub struct PoolNativePackage;
@@ -48,13 +50,15 @@ impl PoolNativePackage {
                         address_reservation,
                         api,
                     )?,
-                    PoolV1MinorVersion::One => super::v1_1::OneResourcePoolBlueprint::instantiate(
-                        resource_address,
-                        owner_role,
-                        pool_manager_rule,
-                        address_reservation,
-                        api,
-                    )?,
+                    PoolV1MinorVersion::One | PoolV1MinorVersion::Two => {
+                        super::v1_1::OneResourcePoolBlueprint::instantiate(
+                            resource_address,
+                            owner_role,
+                            pool_manager_rule,
+                            address_reservation,
+                            api,
+                        )?
+                    }
                 };
 
                 Ok(IndexedScryptoValue::from_typed(&rtn))
@@ -68,7 +72,7 @@ impl PoolNativePackage {
                     PoolV1MinorVersion::Zero => {
                         super::v1_0::OneResourcePoolBlueprint::contribute(bucket, api)?
                     }
-                    PoolV1MinorVersion::One => {
+                    PoolV1MinorVersion::One | PoolV1MinorVersion::Two => {
                         super::v1_1::OneResourcePoolBlueprint::contribute(bucket, api)?
                     }
                 };
@@ -83,7 +87,7 @@ impl PoolNativePackage {
                     PoolV1MinorVersion::Zero => {
                         super::v1_0::OneResourcePoolBlueprint::redeem(bucket, api)?
                     }
-                    PoolV1MinorVersion::One => {
+                    PoolV1MinorVersion::One | PoolV1MinorVersion::Two => {
                         super::v1_1::OneResourcePoolBlueprint::redeem(bucket, api)?
                     }
                 };
@@ -99,7 +103,7 @@ impl PoolNativePackage {
                     PoolV1MinorVersion::Zero => {
                         super::v1_0::OneResourcePoolBlueprint::protected_deposit(bucket, api)?
                     }
-                    PoolV1MinorVersion::One => {
+                    PoolV1MinorVersion::One | PoolV1MinorVersion::Two => {
                         super::v1_1::OneResourcePoolBlueprint::protected_deposit(bucket, api)?
                     }
                 };
@@ -121,7 +125,7 @@ impl PoolNativePackage {
                             api,
                         )?
                     }
-                    PoolV1MinorVersion::One => {
+                    PoolV1MinorVersion::One | PoolV1MinorVersion::Two => {
                         super::v1_1::OneResourcePoolBlueprint::protected_withdraw(
                             amount,
                             withdraw_strategy,
@@ -145,7 +149,7 @@ impl PoolNativePackage {
                             api,
                         )?
                     }
-                    PoolV1MinorVersion::One => {
+                    PoolV1MinorVersion::One | PoolV1MinorVersion::Two => {
                         super::v1_1::OneResourcePoolBlueprint::get_redemption_value(
                             amount_of_pool_units,
                             api,
@@ -163,7 +167,7 @@ impl PoolNativePackage {
                     PoolV1MinorVersion::Zero => {
                         super::v1_0::OneResourcePoolBlueprint::get_vault_amount(api)?
                     }
-                    PoolV1MinorVersion::One => {
+                    PoolV1MinorVersion::One | PoolV1MinorVersion::Two => {
                         super::v1_1::OneResourcePoolBlueprint::get_vault_amount(api)?
                     }
                 };
@@ -187,13 +191,15 @@ impl PoolNativePackage {
                         address_reservation,
                         api,
                     )?,
-                    PoolV1MinorVersion::One => super::v1_1::TwoResourcePoolBlueprint::instantiate(
-                        resource_addresses,
-                        owner_role,
-                        pool_manager_rule,
-                        address_reservation,
-                        api,
-                    )?,
+                    PoolV1MinorVersion::One | PoolV1MinorVersion::Two => {
+                        super::v1_1::TwoResourcePoolBlueprint::instantiate(
+                            resource_addresses,
+                            owner_role,
+                            pool_manager_rule,
+                            address_reservation,
+                            api,
+                        )?
+                    }
                 };
 
                 Ok(IndexedScryptoValue::from_typed(&rtn))
@@ -207,7 +213,7 @@ impl PoolNativePackage {
                     PoolV1MinorVersion::Zero => {
                         super::v1_0::TwoResourcePoolBlueprint::contribute(buckets, api)?
                     }
-                    PoolV1MinorVersion::One => {
+                    PoolV1MinorVersion::One | PoolV1MinorVersion::Two => {
                         super::v1_1::TwoResourcePoolBlueprint::contribute(buckets, api)?
                     }
                 };
@@ -222,7 +228,7 @@ impl PoolNativePackage {
                     PoolV1MinorVersion::Zero => {
                         super::v1_0::TwoResourcePoolBlueprint::redeem(bucket, api)?
                     }
-                    PoolV1MinorVersion::One => {
+                    PoolV1MinorVersion::One | PoolV1MinorVersion::Two => {
                         super::v1_1::TwoResourcePoolBlueprint::redeem(bucket, api)?
                     }
                 };
@@ -238,7 +244,7 @@ impl PoolNativePackage {
                     PoolV1MinorVersion::Zero => {
                         super::v1_0::TwoResourcePoolBlueprint::protected_deposit(bucket, api)?
                     }
-                    PoolV1MinorVersion::One => {
+                    PoolV1MinorVersion::One | PoolV1MinorVersion::Two => {
                         super::v1_1::TwoResourcePoolBlueprint::protected_deposit(bucket, api)?
                     }
                 };
@@ -262,7 +268,7 @@ impl PoolNativePackage {
                             api,
                         )?
                     }
-                    PoolV1MinorVersion::One => {
+                    PoolV1MinorVersion::One | PoolV1MinorVersion::Two => {
                         super::v1_1::TwoResourcePoolBlueprint::protected_withdraw(
                             resource_address,
                             amount,
@@ -287,7 +293,7 @@ impl PoolNativePackage {
                             api,
                         )?
                     }
-                    PoolV1MinorVersion::One => {
+                    PoolV1MinorVersion::One | PoolV1MinorVersion::Two => {
                         super::v1_1::TwoResourcePoolBlueprint::get_redemption_value(
                             amount_of_pool_units,
                             api,
@@ -305,7 +311,7 @@ impl PoolNativePackage {
                     PoolV1MinorVersion::Zero => {
                         super::v1_0::TwoResourcePoolBlueprint::get_vault_amounts(api)?
                     }
-                    PoolV1MinorVersion::One => {
+                    PoolV1MinorVersion::One | PoolV1MinorVersion::Two => {
                         super::v1_1::TwoResourcePoolBlueprint::get_vault_amounts(api)?
                     }
                 };
@@ -331,7 +337,7 @@ impl PoolNativePackage {
                             api,
                         )?
                     }
-                    PoolV1MinorVersion::One => {
+                    PoolV1MinorVersion::One | PoolV1MinorVersion::Two => {
                         super::v1_1::MultiResourcePoolBlueprint::instantiate(
                             resource_addresses,
                             owner_role,
@@ -354,7 +360,7 @@ impl PoolNativePackage {
                     PoolV1MinorVersion::Zero => {
                         super::v1_0::MultiResourcePoolBlueprint::contribute(buckets, api)?
                     }
-                    PoolV1MinorVersion::One => {
+                    PoolV1MinorVersion::One | PoolV1MinorVersion::Two => {
                         super::v1_1::MultiResourcePoolBlueprint::contribute(buckets, api)?
                     }
                 };
@@ -369,7 +375,7 @@ impl PoolNativePackage {
                     PoolV1MinorVersion::Zero => {
                         super::v1_0::MultiResourcePoolBlueprint::redeem(bucket, api)?
                     }
-                    PoolV1MinorVersion::One => {
+                    PoolV1MinorVersion::One | PoolV1MinorVersion::Two => {
                         super::v1_1::MultiResourcePoolBlueprint::redeem(bucket, api)?
                     }
                 };
@@ -385,7 +391,7 @@ impl PoolNativePackage {
                     PoolV1MinorVersion::Zero => {
                         super::v1_0::MultiResourcePoolBlueprint::protected_deposit(bucket, api)?
                     }
-                    PoolV1MinorVersion::One => {
+                    PoolV1MinorVersion::One | PoolV1MinorVersion::Two => {
                         super::v1_1::MultiResourcePoolBlueprint::protected_deposit(bucket, api)?
                     }
                 };
@@ -409,7 +415,7 @@ impl PoolNativePackage {
                             api,
                         )?
                     }
-                    PoolV1MinorVersion::One => {
+                    PoolV1MinorVersion::One | PoolV1MinorVersion::Two => {
                         super::v1_1::MultiResourcePoolBlueprint::protected_withdraw(
                             resource_address,
                             amount,
@@ -434,7 +440,7 @@ impl PoolNativePackage {
                             api,
                         )?
                     }
-                    PoolV1MinorVersion::One => {
+                    PoolV1MinorVersion::One | PoolV1MinorVersion::Two => {
                         super::v1_1::MultiResourcePoolBlueprint::get_redemption_value(
                             amount_of_pool_units,
                             api,
@@ -452,13 +458,146 @@ impl PoolNativePackage {
                     PoolV1MinorVersion::Zero => {
                         super::v1_0::MultiResourcePoolBlueprint::get_vault_amounts(api)?
                     }
-                    PoolV1MinorVersion::One => {
+                    PoolV1MinorVersion::One | PoolV1MinorVersion::Two => {
                         super::v1_1::MultiResourcePoolBlueprint::get_vault_amounts(api)?
                     }
                 };
                 Ok(IndexedScryptoValue::from_typed(&rtn))
             }
 
+            // The constant product pool is only defined from minor version two onwards, so its
+            // exports are not dispatched on the minor version.
+            CONSTANT_PRODUCT_POOL_INSTANTIATE_EXPORT_NAME => {
+                let ConstantProductPoolInstantiateInput {
+                    owner_role,
+                    fee_manager_rule,
+                    resource_addresses,
+                    fee_rate,
+                    address_reservation,
+                } = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn = super::v1_2::ConstantProductPoolBlueprint::instantiate(
+                    resource_addresses,
+                    owner_role,
+                    fee_manager_rule,
+                    fee_rate,
+                    address_reservation,
+                    api,
+                )?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+
+            CONSTANT_PRODUCT_POOL_CONTRIBUTE_EXPORT_NAME => {
+                let ConstantProductPoolContributeInput { buckets } =
+                    input.as_typed().map_err(|e| {
+                        RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                    })?;
+                let rtn = super::v1_2::ConstantProductPoolBlueprint::contribute(buckets, api)?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+
+            CONSTANT_PRODUCT_POOL_REDEEM_EXPORT_NAME => {
+                let ConstantProductPoolRedeemInput { bucket } = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn = super::v1_2::ConstantProductPoolBlueprint::redeem(bucket, api)?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+
+            CONSTANT_PRODUCT_POOL_SWAP_EXPORT_NAME => {
+                let ConstantProductPoolSwapInput {
+                    input_bucket,
+                    minimum_output_amount,
+                } = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn = super::v1_2::ConstantProductPoolBlueprint::swap(
+                    input_bucket,
+                    minimum_output_amount,
+                    api,
+                )?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+
+            CONSTANT_PRODUCT_POOL_SWAP_FOR_EXACT_OUTPUT_EXPORT_NAME => {
+                let ConstantProductPoolSwapForExactOutputInput {
+                    input_bucket,
+                    output_amount,
+                } = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn = super::v1_2::ConstantProductPoolBlueprint::swap_for_exact_output(
+                    input_bucket,
+                    output_amount,
+                    api,
+                )?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+
+            CONSTANT_PRODUCT_POOL_GET_SWAP_OUTPUT_AMOUNT_EXPORT_NAME => {
+                let ConstantProductPoolGetSwapOutputAmountInput {
+                    input_resource_address,
+                    input_amount,
+                } = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn = super::v1_2::ConstantProductPoolBlueprint::get_swap_output_amount(
+                    input_resource_address,
+                    input_amount,
+                    api,
+                )?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+
+            CONSTANT_PRODUCT_POOL_GET_SWAP_INPUT_AMOUNT_EXPORT_NAME => {
+                let ConstantProductPoolGetSwapInputAmountInput {
+                    output_resource_address,
+                    output_amount,
+                } = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn = super::v1_2::ConstantProductPoolBlueprint::get_swap_input_amount(
+                    output_resource_address,
+                    output_amount,
+                    api,
+                )?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+
+            CONSTANT_PRODUCT_POOL_SET_FEE_RATE_EXPORT_NAME => {
+                let ConstantProductPoolSetFeeRateInput { fee_rate } =
+                    input.as_typed().map_err(|e| {
+                        RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                    })?;
+                let rtn = super::v1_2::ConstantProductPoolBlueprint::set_fee_rate(fee_rate, api)?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+
+            CONSTANT_PRODUCT_POOL_GET_FEE_RATE_EXPORT_NAME => {
+                let ConstantProductPoolGetFeeRateInput {} = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn = super::v1_2::ConstantProductPoolBlueprint::get_fee_rate(api)?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+
+            CONSTANT_PRODUCT_POOL_GET_ORACLE_STATE_EXPORT_NAME => {
+                let ConstantProductPoolGetOracleStateInput {} = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn = super::v1_2::ConstantProductPoolBlueprint::get_oracle_state(api)?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+
+            CONSTANT_PRODUCT_POOL_GET_VAULT_AMOUNTS_EXPORT_NAME => {
+                let ConstantProductPoolGetVaultAmountsInput {} = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn = super::v1_2::ConstantProductPoolBlueprint::get_vault_amounts(api)?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+
             _ => Err(RuntimeError::ApplicationError(
                 ApplicationError::ExportDoesNotExist(export_name.to_string()),
             )),
@@ -466,7 +605,7 @@ impl PoolNativePackage {
     }
 
     pub fn definition(minor_version: PoolV1MinorVersion) -> PackageDefinition {
-        let blueprints = indexmap!(
+        let mut blueprints = indexmap!(
             ONE_RESOURCE_POOL_BLUEPRINT_IDENT.to_string()
                 => Self::one_resource_pool_blueprint_definition(minor_version),
             TWO_RESOURCE_POOL_BLUEPRINT_IDENT.to_string()
@@ -474,6 +613,12 @@ impl PoolNativePackage {
             MULTI_RESOURCE_POOL_BLUEPRINT_IDENT.to_string()
                 => Self::multi_resource_pool_blueprint_definition(minor_version),
         );
+        if minor_version >= PoolV1MinorVersion::Two {
+            blueprints.insert(
+                CONSTANT_PRODUCT_POOL_BLUEPRINT_IDENT.to_string(),
+                Self::constant_product_pool_blueprint_definition(),
+            );
+        }
 
         PackageDefinition { blueprints }
     }
@@ -973,4 +1118,229 @@ impl PoolNativePackage {
             },
         }
     }
+
+    pub fn constant_product_pool_blueprint_definition() -> BlueprintDefinitionInit {
+        let mut aggregator = TypeAggregator::<ScryptoCustomTypeKind>::new();
+        let feature_set = ConstantProductPoolFeatureSet::all_features();
+        let state = ConstantProductPoolStateSchemaInit::create_schema_init(&mut aggregator);
+
+        let mut functions = index_map_new();
+
+        functions.insert(
+            CONSTANT_PRODUCT_POOL_INSTANTIATE_IDENT.to_string(),
+            FunctionSchemaInit {
+                receiver: None,
+                input: TypeRef::Static(
+                    aggregator
+                        .add_child_type_and_descendents::<ConstantProductPoolInstantiateInput>(),
+                ),
+                output: TypeRef::Static(
+                    aggregator
+                        .add_child_type_and_descendents::<ConstantProductPoolInstantiateOutput>(),
+                ),
+                export: CONSTANT_PRODUCT_POOL_INSTANTIATE_EXPORT_NAME.to_string(),
+            },
+        );
+
+        functions.insert(
+            CONSTANT_PRODUCT_POOL_CONTRIBUTE_IDENT.to_string(),
+            FunctionSchemaInit {
+                receiver: Some(ReceiverInfo::normal_ref_mut()),
+                input: TypeRef::Static(
+                    aggregator
+                        .add_child_type_and_descendents::<ConstantProductPoolContributeInput>(),
+                ),
+                output: TypeRef::Static(
+                    aggregator
+                        .add_child_type_and_descendents::<ConstantProductPoolContributeOutput>(),
+                ),
+                export: CONSTANT_PRODUCT_POOL_CONTRIBUTE_EXPORT_NAME.to_string(),
+            },
+        );
+
+        functions.insert(
+            CONSTANT_PRODUCT_POOL_REDEEM_IDENT.to_string(),
+            FunctionSchemaInit {
+                receiver: Some(ReceiverInfo::normal_ref_mut()),
+                input: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<ConstantProductPoolRedeemInput>(),
+                ),
+                output: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<ConstantProductPoolRedeemOutput>(),
+                ),
+                export: CONSTANT_PRODUCT_POOL_REDEEM_EXPORT_NAME.to_string(),
+            },
+        );
+
+        functions.insert(
+            CONSTANT_PRODUCT_POOL_SWAP_IDENT.to_string(),
+            FunctionSchemaInit {
+                receiver: Some(ReceiverInfo::normal_ref_mut()),
+                input: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<ConstantProductPoolSwapInput>(),
+                ),
+                output: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<ConstantProductPoolSwapOutput>(),
+                ),
+                export: CONSTANT_PRODUCT_POOL_SWAP_EXPORT_NAME.to_string(),
+            },
+        );
+
+        functions.insert(
+            CONSTANT_PRODUCT_POOL_SWAP_FOR_EXACT_OUTPUT_IDENT.to_string(),
+            FunctionSchemaInit {
+                receiver: Some(ReceiverInfo::normal_ref_mut()),
+                input: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<ConstantProductPoolSwapForExactOutputInput>(),
+                ),
+                output: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<ConstantProductPoolSwapForExactOutputOutput>(),
+                ),
+                export: CONSTANT_PRODUCT_POOL_SWAP_FOR_EXACT_OUTPUT_EXPORT_NAME.to_string(),
+            },
+        );
+
+        functions.insert(
+            CONSTANT_PRODUCT_POOL_GET_SWAP_OUTPUT_AMOUNT_IDENT.to_string(),
+            FunctionSchemaInit {
+                receiver: Some(ReceiverInfo::normal_ref()),
+                input: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<ConstantProductPoolGetSwapOutputAmountInput>(),
+                ),
+                output: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<ConstantProductPoolGetSwapOutputAmountOutput>(),
+                ),
+                export: CONSTANT_PRODUCT_POOL_GET_SWAP_OUTPUT_AMOUNT_EXPORT_NAME.to_string(),
+            },
+        );
+
+        functions.insert(
+            CONSTANT_PRODUCT_POOL_GET_SWAP_INPUT_AMOUNT_IDENT.to_string(),
+            FunctionSchemaInit {
+                receiver: Some(ReceiverInfo::normal_ref()),
+                input: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<ConstantProductPoolGetSwapInputAmountInput>(),
+                ),
+                output: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<ConstantProductPoolGetSwapInputAmountOutput>(),
+                ),
+                export: CONSTANT_PRODUCT_POOL_GET_SWAP_INPUT_AMOUNT_EXPORT_NAME.to_string(),
+            },
+        );
+
+        functions.insert(
+            CONSTANT_PRODUCT_POOL_SET_FEE_RATE_IDENT.to_string(),
+            FunctionSchemaInit {
+                receiver: Some(ReceiverInfo::normal_ref_mut()),
+                input: TypeRef::Static(
+                    aggregator
+                        .add_child_type_and_descendents::<ConstantProductPoolSetFeeRateInput>(),
+                ),
+                output: TypeRef::Static(
+                    aggregator
+                        .add_child_type_and_descendents::<ConstantProductPoolSetFeeRateOutput>(),
+                ),
+                export: CONSTANT_PRODUCT_POOL_SET_FEE_RATE_EXPORT_NAME.to_string(),
+            },
+        );
+
+        functions.insert(
+            CONSTANT_PRODUCT_POOL_GET_FEE_RATE_IDENT.to_string(),
+            FunctionSchemaInit {
+                receiver: Some(ReceiverInfo::normal_ref()),
+                input: TypeRef::Static(
+                    aggregator
+                        .add_child_type_and_descendents::<ConstantProductPoolGetFeeRateInput>(),
+                ),
+                output: TypeRef::Static(
+                    aggregator
+                        .add_child_type_and_descendents::<ConstantProductPoolGetFeeRateOutput>(),
+                ),
+                export: CONSTANT_PRODUCT_POOL_GET_FEE_RATE_EXPORT_NAME.to_string(),
+            },
+        );
+
+        functions.insert(
+            CONSTANT_PRODUCT_POOL_GET_ORACLE_STATE_IDENT.to_string(),
+            FunctionSchemaInit {
+                receiver: Some(ReceiverInfo::normal_ref()),
+                input: TypeRef::Static(
+                    aggregator
+                        .add_child_type_and_descendents::<ConstantProductPoolGetOracleStateInput>(),
+                ),
+                output: TypeRef::Static(
+                    aggregator
+                        .add_child_type_and_descendents::<ConstantProductPoolGetOracleStateOutput>(
+                        ),
+                ),
+                export: CONSTANT_PRODUCT_POOL_GET_ORACLE_STATE_EXPORT_NAME.to_string(),
+            },
+        );
+
+        functions.insert(
+            CONSTANT_PRODUCT_POOL_GET_VAULT_AMOUNTS_IDENT.to_string(),
+            FunctionSchemaInit {
+                receiver: Some(ReceiverInfo::normal_ref()),
+                input: TypeRef::Static(
+                    aggregator
+                        .add_child_type_and_descendents::<ConstantProductPoolGetVaultAmountsInput>(
+                        ),
+                ),
+                output: TypeRef::Static(
+                    aggregator
+                        .add_child_type_and_descendents::<ConstantProductPoolGetVaultAmountsOutput>(
+                        ),
+                ),
+                export: CONSTANT_PRODUCT_POOL_GET_VAULT_AMOUNTS_EXPORT_NAME.to_string(),
+            },
+        );
+
+        let event_schema = event_schema! {
+            aggregator,
+            [
+                super::events::constant_product_pool::SwapEvent,
+                super::events::constant_product_pool::FeeRateChangeEvent
+            ]
+        };
+
+        let schema = generate_full_schema(aggregator);
+
+        BlueprintDefinitionInit {
+            blueprint_type: BlueprintType::default(),
+            is_transient: false,
+            dependencies: indexset!(),
+            feature_set,
+
+            schema: BlueprintSchemaInit {
+                generics: vec![],
+                schema,
+                state,
+                events: event_schema,
+                types: BlueprintTypeSchemaInit::default(),
+                functions: BlueprintFunctionsSchemaInit { functions },
+                hooks: BlueprintHooksInit::default(),
+            },
+            royalty_config: PackageRoyaltyConfig::default(),
+            auth_config: AuthConfig {
+                function_auth: FunctionAuth::AllowAll,
+                method_auth: MethodAuthTemplate::StaticRoleDefinition(roles_template! {
+                    roles {
+                        FEE_MANAGER_ROLE;
+                    },
+                    methods {
+                        CONSTANT_PRODUCT_POOL_CONTRIBUTE_IDENT => MethodAccessibility::Public;
+                        CONSTANT_PRODUCT_POOL_REDEEM_IDENT => MethodAccessibility::Public;
+                        CONSTANT_PRODUCT_POOL_SWAP_IDENT => MethodAccessibility::Public;
+                        CONSTANT_PRODUCT_POOL_SWAP_FOR_EXACT_OUTPUT_IDENT => MethodAccessibility::Public;
+                        CONSTANT_PRODUCT_POOL_GET_SWAP_OUTPUT_AMOUNT_IDENT => MethodAccessibility::Public;
+                        CONSTANT_PRODUCT_POOL_GET_SWAP_INPUT_AMOUNT_IDENT => MethodAccessibility::Public;
+                        CONSTANT_PRODUCT_POOL_GET_FEE_RATE_IDENT => MethodAccessibility::Public;
+                        CONSTANT_PRODUCT_POOL_GET_ORACLE_STATE_IDENT => MethodAccessibility::Public;
+                        CONSTANT_PRODUCT_POOL_GET_VAULT_AMOUNTS_IDENT => MethodAccessibility::Public;
+                        CONSTANT_PRODUCT_POOL_SET_FEE_RATE_IDENT => [FEE_MANAGER_ROLE];
+                    }
+                }),
+            },
+        }
+    }
 }
@@ -1,5 +1,6 @@
 use crate::blueprints::models::*;
 use native_sdk::resource::*;
+use radix_engine_interface::blueprints::pool::*;
 use radix_engine_interface::prelude::*;
 
 pub mod one_resource_pool {
@@ -134,3 +135,45 @@ pub mod multi_resource_pool {
         pub pool_unit_resource_manager: ResourceManager,
     }
 }
+
+pub mod constant_product_pool {
+    use super::*;
+
+    declare_native_blueprint_state! {
+        blueprint_ident: ConstantProductPool,
+        blueprint_snake_case: constant_product_pool,
+        features: {
+        },
+        fields: {
+            state:  {
+                ident: State,
+                field_type: {
+                    kind: StaticSingleVersioned,
+                },
+                condition: Condition::Always,
+            }
+        },
+        collections: {
+        }
+    }
+
+    pub type ConstantProductPoolStateV1 = Substate;
+
+    #[derive(Debug, PartialEq, Eq, ScryptoSbor)]
+    #[sbor(type_name = "ConstantProductPoolSubstate")]
+    pub struct Substate {
+        /// The two-resource pool which holds the reserves of this pool. This pool is the only one
+        /// allowed to contribute to it and to deposit into or withdraw from its vaults.
+        pub pool: ComponentAddress,
+
+        /// The resources of the pool, in the order they were given when the pool was created.
+        pub resource_addresses: (ResourceAddress, ResourceAddress),
+
+        /// The fraction of the input of every swap which is kept by the pool as a fee, in the
+        /// range `[0, 1)`.
+        pub fee_rate: Decimal,
+
+        /// The state of the time-weighted average price oracle.
+        pub oracle: ConstantProductPoolOracleState,
+    }
+}
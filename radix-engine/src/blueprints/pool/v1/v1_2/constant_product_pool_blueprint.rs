@@ -0,0 +1,687 @@
+use crate::blueprints::pool::v1::constants::*;
+use crate::blueprints::pool::v1::errors::constant_product_pool::*;
+use crate::blueprints::pool::v1::events::constant_product_pool::*;
+use crate::blueprints::pool::v1::substates::constant_product_pool::*;
+use crate::internal_prelude::*;
+use native_sdk::modules::metadata::*;
+use native_sdk::modules::role_assignment::*;
+use native_sdk::modules::royalty::*;
+use native_sdk::resource::*;
+use native_sdk::runtime::*;
+use radix_engine_interface::blueprints::component::*;
+use radix_engine_interface::blueprints::consensus_manager::*;
+use radix_engine_interface::blueprints::pool::*;
+use radix_engine_interface::prelude::*;
+use radix_engine_interface::*;
+
+/// An automated market maker which swaps between the two resources of a two-resource pool along
+/// the constant product curve `x * y = k`.
+///
+/// The reserves are held by a two-resource pool which is created along with this pool and which
+/// only this pool is allowed to contribute to, or to deposit into and withdraw from. Pool units
+/// minted by contributions are the pool units of that pool, so they can also be redeemed from it
+/// directly. The fee charged on the input of every swap is left in the reserves, which makes it
+/// accrue to the holders of the pool units.
+pub struct ConstantProductPoolBlueprint;
+impl ConstantProductPoolBlueprint {
+    pub fn instantiate<Y>(
+        (resource_address1, resource_address2): (ResourceAddress, ResourceAddress),
+        owner_role: OwnerRole,
+        fee_manager_rule: AccessRule,
+        fee_rate: Decimal,
+        address_reservation: Option<GlobalAddressReservation>,
+        api: &mut Y,
+    ) -> Result<ConstantProductPoolInstantiateOutput, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        Self::validate_fee_rate(fee_rate)?;
+
+        // Allocating the address of the pool - this is going to be needed for the rules of the
+        // two-resource pool which holds the reserves.
+        let (address_reservation, address) = {
+            if let Some(address_reservation) = address_reservation {
+                let address = api.get_reservation_address(address_reservation.0.as_node_id())?;
+                (address_reservation, address)
+            } else {
+                api.allocate_global_address(BlueprintId {
+                    package_address: POOL_PACKAGE,
+                    blueprint_name: CONSTANT_PRODUCT_POOL_BLUEPRINT_IDENT.to_string(),
+                })?
+            }
+        };
+
+        // Creating the two-resource pool which holds the reserves. It is owned and managed by this
+        // pool alone so that the reserves can only change through it, or through redemptions.
+        let pool = {
+            let component_caller_badge = NonFungibleGlobalId::global_caller_badge(address);
+            let rtn = api.call_function(
+                POOL_PACKAGE,
+                TWO_RESOURCE_POOL_BLUEPRINT_IDENT,
+                TWO_RESOURCE_POOL_INSTANTIATE_IDENT,
+                scrypto_encode(&TwoResourcePoolInstantiateInput {
+                    owner_role: OwnerRole::Fixed(rule!(require(component_caller_badge.clone()))),
+                    pool_manager_rule: rule!(require(component_caller_badge)),
+                    resource_addresses: (resource_address1, resource_address2),
+                    address_reservation: None,
+                })
+                .unwrap(),
+            )?;
+            scrypto_decode::<TwoResourcePoolInstantiateOutput>(&rtn)
+                .unwrap()
+                .0
+        };
+
+        // Creating the pool nodes
+        let role_assignment = RoleAssignment::create(
+            owner_role,
+            indexmap! {
+                ModuleId::Main => roles_init! {
+                    RoleKey { key: FEE_MANAGER_ROLE.to_owned() } => fee_manager_rule;
+                }
+            },
+            api,
+        )?
+        .0;
+
+        let metadata = Metadata::create_with_data(
+            metadata_init! {
+                "pool" => GlobalAddress::from(pool), locked;
+                "pool_resources" => vec![
+                    GlobalAddress::from(resource_address1),
+                    GlobalAddress::from(resource_address2),
+                ], locked;
+            },
+            api,
+        )?;
+        let royalty = ComponentRoyalty::create(ComponentRoyaltyConfig::default(), api)?;
+        let object_id = {
+            let substate = Substate {
+                pool,
+                resource_addresses: (resource_address1, resource_address2),
+                fee_rate,
+                oracle: ConstantProductPoolOracleState {
+                    cumulative_prices: indexmap! {
+                        resource_address1 => PreciseDecimal::ZERO,
+                        resource_address2 => PreciseDecimal::ZERO,
+                    },
+                    last_update: Runtime::current_time(api, TimePrecision::Second)?,
+                },
+            };
+            api.new_simple_object(
+                CONSTANT_PRODUCT_POOL_BLUEPRINT_IDENT,
+                indexmap! {
+                    ConstantProductPoolField::State.field_index() => FieldValue::new(ConstantProductPoolStateFieldPayload::from_content_source(substate)),
+                },
+            )?
+        };
+
+        api.globalize(
+            object_id,
+            indexmap!(
+                AttachedModuleId::RoleAssignment => role_assignment.0,
+                AttachedModuleId::Metadata => metadata.0,
+                AttachedModuleId::Royalty => royalty.0,
+            ),
+            Some(address_reservation),
+        )?;
+
+        Ok(Global::new(ComponentAddress::new_or_panic(
+            address.as_node_id().0,
+        )))
+    }
+
+    /// Contributes resources to the reserves and returns the minted pool units along with any
+    /// change. The contribution follows the rules of the two-resource pool.
+    pub fn contribute<Y>(
+        buckets: (Bucket, Bucket),
+        api: &mut Y,
+    ) -> Result<ConstantProductPoolContributeOutput, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        Self::with_state_mut(api, |substate, api| {
+            let reserves = Self::reserves(substate.pool, api)?;
+            Self::update_oracle(&mut substate.oracle, &reserves, api)?;
+
+            Self::call_pool(
+                substate.pool,
+                TWO_RESOURCE_POOL_CONTRIBUTE_IDENT,
+                &TwoResourcePoolContributeInput { buckets },
+                api,
+            )
+        })
+    }
+
+    /// Redeems pool units for their share of the reserves.
+    pub fn redeem<Y>(
+        bucket: Bucket,
+        api: &mut Y,
+    ) -> Result<ConstantProductPoolRedeemOutput, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        Self::with_state_mut(api, |substate, api| {
+            let reserves = Self::reserves(substate.pool, api)?;
+            Self::update_oracle(&mut substate.oracle, &reserves, api)?;
+
+            Self::call_pool(
+                substate.pool,
+                TWO_RESOURCE_POOL_REDEEM_IDENT,
+                &TwoResourcePoolRedeemInput { bucket },
+                api,
+            )
+        })
+    }
+
+    /// Swaps all of the input bucket for the other resource of the pool, failing if less than
+    /// `minimum_output_amount` would be returned.
+    pub fn swap<Y>(
+        input_bucket: Bucket,
+        minimum_output_amount: Decimal,
+        api: &mut Y,
+    ) -> Result<ConstantProductPoolSwapOutput, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        Self::with_state_mut(api, |substate, api| {
+            let input_resource_address = input_bucket.resource_address(api)?;
+            let output_resource_address = substate.other_resource(input_resource_address)?;
+            let input_amount = input_bucket.amount(api)?;
+
+            let reserves = Self::reserves(substate.pool, api)?;
+            Self::update_oracle(&mut substate.oracle, &reserves, api)?;
+
+            let (output_amount, fee_amount) = Self::calculate_output_amount(
+                input_amount,
+                reserves[&input_resource_address],
+                reserves[&output_resource_address],
+                substate.fee_rate,
+                Self::divisibility(output_resource_address, api)?,
+            )?;
+            if output_amount < minimum_output_amount {
+                return Err(Error::SwapOutputBelowMinimum {
+                    output_amount,
+                    minimum_output_amount,
+                }
+                .into());
+            }
+
+            let output_bucket = Self::exchange(
+                substate.pool,
+                input_bucket,
+                output_resource_address,
+                output_amount,
+                api,
+            )?;
+
+            Runtime::emit_event(
+                api,
+                SwapEvent {
+                    input_resource_address,
+                    input_amount,
+                    output_resource_address,
+                    output_amount,
+                    fee_amount,
+                },
+            )?;
+
+            Ok(output_bucket)
+        })
+    }
+
+    /// Swaps as little of the input bucket as needed to get exactly `output_amount` of the other
+    /// resource of the pool, failing if the input bucket does not hold enough. The output is
+    /// returned along with the rest of the input bucket.
+    pub fn swap_for_exact_output<Y>(
+        input_bucket: Bucket,
+        output_amount: Decimal,
+        api: &mut Y,
+    ) -> Result<ConstantProductPoolSwapForExactOutputOutput, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        Self::with_state_mut(api, |substate, api| {
+            let input_resource_address = input_bucket.resource_address(api)?;
+            let output_resource_address = substate.other_resource(input_resource_address)?;
+            let maximum_input_amount = input_bucket.amount(api)?;
+
+            let reserves = Self::reserves(substate.pool, api)?;
+            Self::update_oracle(&mut substate.oracle, &reserves, api)?;
+
+            let (input_amount, fee_amount) = Self::calculate_input_amount(
+                output_amount,
+                reserves[&input_resource_address],
+                reserves[&output_resource_address],
+                substate.fee_rate,
+                Self::divisibility(input_resource_address, api)?,
+            )?;
+            if input_amount > maximum_input_amount {
+                return Err(Error::SwapInputAboveMaximum {
+                    input_amount,
+                    maximum_input_amount,
+                }
+                .into());
+            }
+
+            let output_bucket = Self::exchange(
+                substate.pool,
+                input_bucket.take(input_amount, api)?,
+                output_resource_address,
+                output_amount,
+                api,
+            )?;
+
+            Runtime::emit_event(
+                api,
+                SwapEvent {
+                    input_resource_address,
+                    input_amount,
+                    output_resource_address,
+                    output_amount,
+                    fee_amount,
+                },
+            )?;
+
+            Ok((output_bucket, input_bucket))
+        })
+    }
+
+    /// Returns the amount that a swap of `input_amount` of the given resource would return.
+    pub fn get_swap_output_amount<Y>(
+        input_resource_address: ResourceAddress,
+        input_amount: Decimal,
+        api: &mut Y,
+    ) -> Result<ConstantProductPoolGetSwapOutputAmountOutput, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        Self::with_state(api, |substate, api| {
+            let output_resource_address = substate.other_resource(input_resource_address)?;
+            let reserves = Self::reserves(substate.pool, api)?;
+            let (output_amount, _) = Self::calculate_output_amount(
+                input_amount,
+                reserves[&input_resource_address],
+                reserves[&output_resource_address],
+                substate.fee_rate,
+                Self::divisibility(output_resource_address, api)?,
+            )?;
+            Ok(output_amount)
+        })
+    }
+
+    /// Returns the amount of the other resource that a swap for exactly `output_amount` of the
+    /// given resource would take.
+    pub fn get_swap_input_amount<Y>(
+        output_resource_address: ResourceAddress,
+        output_amount: Decimal,
+        api: &mut Y,
+    ) -> Result<ConstantProductPoolGetSwapInputAmountOutput, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        Self::with_state(api, |substate, api| {
+            let input_resource_address = substate.other_resource(output_resource_address)?;
+            let reserves = Self::reserves(substate.pool, api)?;
+            let (input_amount, _) = Self::calculate_input_amount(
+                output_amount,
+                reserves[&input_resource_address],
+                reserves[&output_resource_address],
+                substate.fee_rate,
+                Self::divisibility(input_resource_address, api)?,
+            )?;
+            Ok(input_amount)
+        })
+    }
+
+    pub fn set_fee_rate<Y>(
+        fee_rate: Decimal,
+        api: &mut Y,
+    ) -> Result<ConstantProductPoolSetFeeRateOutput, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        Self::validate_fee_rate(fee_rate)?;
+        Self::with_state_mut(api, |substate, api| {
+            substate.fee_rate = fee_rate;
+            Runtime::emit_event(api, FeeRateChangeEvent { fee_rate })
+        })
+    }
+
+    pub fn get_fee_rate<Y>(api: &mut Y) -> Result<ConstantProductPoolGetFeeRateOutput, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        Self::with_state(api, |substate, _| Ok(substate.fee_rate))
+    }
+
+    /// Returns the state of the oracle as of the current time, accumulating the current prices
+    /// over the time elapsed since the last time the reserves changed.
+    pub fn get_oracle_state<Y>(
+        api: &mut Y,
+    ) -> Result<ConstantProductPoolGetOracleStateOutput, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        Self::with_state(api, |mut substate, api| {
+            let reserves = Self::reserves(substate.pool, api)?;
+            Self::update_oracle(&mut substate.oracle, &reserves, api)?;
+            Ok(substate.oracle)
+        })
+    }
+
+    pub fn get_vault_amounts<Y>(
+        api: &mut Y,
+    ) -> Result<ConstantProductPoolGetVaultAmountsOutput, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        Self::with_state(api, |substate, api| Self::reserves(substate.pool, api))
+    }
+
+    //===================
+    // Utility Functions
+    //===================
+
+    fn validate_fee_rate(fee_rate: Decimal) -> Result<(), RuntimeError> {
+        if fee_rate.is_negative() || fee_rate >= Decimal::ONE {
+            Err(Error::InvalidFeeRate { fee_rate }.into())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Calculates the output of a swap of `input_amount`, returning it along with the part of the
+    /// input charged as a fee.
+    ///
+    /// With the fee rate `f`, the output is `Ro * (1 - f) * I / (Ri + (1 - f) * I)` rounded down,
+    /// which keeps the product of the reserves from decreasing.
+    fn calculate_output_amount(
+        input_amount: Decimal,
+        input_reserves: Decimal,
+        output_reserves: Decimal,
+        fee_rate: Decimal,
+        output_divisibility: u8,
+    ) -> Result<(Decimal, Decimal), RuntimeError> {
+        if !input_amount.is_positive() {
+            return Err(Error::InvalidSwapAmount {
+                amount: input_amount,
+            }
+            .into());
+        }
+        if input_reserves.is_zero() || output_reserves.is_zero() {
+            return Err(Error::PoolHasNoLiquidity.into());
+        }
+
+        let input_amount_after_fee = input_amount
+            .checked_mul(Decimal::ONE - fee_rate)
+            .ok_or(Error::DecimalOverflowError)?;
+        let output_amount = input_reserves
+            .checked_add(input_amount_after_fee)
+            .and_then(|divisor| {
+                output_reserves.checked_mul_div(
+                    input_amount_after_fee,
+                    divisor,
+                    RoundingMode::ToNegativeInfinity,
+                )
+            })
+            .and_then(|amount| {
+                amount.checked_round(output_divisibility, RoundingMode::ToNegativeInfinity)
+            })
+            .ok_or(Error::DecimalOverflowError)?;
+        if output_amount.is_zero() {
+            return Err(Error::InvalidSwapAmount {
+                amount: input_amount,
+            }
+            .into());
+        }
+
+        Ok((output_amount, input_amount - input_amount_after_fee))
+    }
+
+    /// Calculates the input needed for a swap to return exactly `output_amount`, returning it
+    /// along with the part of the input charged as a fee.
+    ///
+    /// With the fee rate `f`, the input is `Ri * O / ((Ro - O) * (1 - f))` rounded up, which keeps
+    /// the product of the reserves from decreasing.
+    fn calculate_input_amount(
+        output_amount: Decimal,
+        input_reserves: Decimal,
+        output_reserves: Decimal,
+        fee_rate: Decimal,
+        input_divisibility: u8,
+    ) -> Result<(Decimal, Decimal), RuntimeError> {
+        if !output_amount.is_positive() {
+            return Err(Error::InvalidSwapAmount {
+                amount: output_amount,
+            }
+            .into());
+        }
+        if input_reserves.is_zero() || output_reserves.is_zero() {
+            return Err(Error::PoolHasNoLiquidity.into());
+        }
+        if output_amount >= output_reserves {
+            return Err(Error::SwapOutputExceedsReserves {
+                output_amount,
+                reserves: output_reserves,
+            }
+            .into());
+        }
+
+        let input_amount_after_fee = input_reserves
+            .checked_mul_div(
+                output_amount,
+                output_reserves - output_amount,
+                RoundingMode::ToPositiveInfinity,
+            )
+            .ok_or(Error::DecimalOverflowError)?;
+        let input_amount = input_amount_after_fee
+            .checked_mul_div(
+                Decimal::ONE,
+                Decimal::ONE - fee_rate,
+                RoundingMode::ToPositiveInfinity,
+            )
+            .and_then(|amount| {
+                amount.checked_round(input_divisibility, RoundingMode::ToPositiveInfinity)
+            })
+            .ok_or(Error::DecimalOverflowError)?;
+
+        Ok((input_amount, input_amount - input_amount_after_fee))
+    }
+
+    /// Accumulates the prices implied by the given reserves over the time elapsed since the last
+    /// update of the oracle.
+    ///
+    /// Prices are only accumulated while both reserves are non-empty. A cumulative price which
+    /// can no longer be represented stops accumulating rather than making the pool unusable.
+    fn update_oracle<Y>(
+        oracle: &mut ConstantProductPoolOracleState,
+        reserves: &IndexMap<ResourceAddress, Decimal>,
+        api: &mut Y,
+    ) -> Result<(), RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let now = Runtime::current_time(api, TimePrecision::Second)?;
+        let elapsed_seconds =
+            now.seconds_since_unix_epoch - oracle.last_update.seconds_since_unix_epoch;
+        if elapsed_seconds <= 0 {
+            return Ok(());
+        }
+
+        if reserves.values().all(|amount| amount.is_positive()) {
+            let elapsed_seconds = PreciseDecimal::from(elapsed_seconds);
+            for (resource_address, cumulative_price) in oracle.cumulative_prices.iter_mut() {
+                let (own_reserves, other_reserves) = reserves.iter().fold(
+                    (PreciseDecimal::ZERO, PreciseDecimal::ZERO),
+                    |(own, other), (address, amount)| {
+                        if address == resource_address {
+                            (PreciseDecimal::from(*amount), other)
+                        } else {
+                            (own, PreciseDecimal::from(*amount))
+                        }
+                    },
+                );
+                if let Some(value) = other_reserves
+                    .checked_div(own_reserves)
+                    .and_then(|price| price.checked_mul(elapsed_seconds))
+                    .and_then(|increment| cumulative_price.checked_add(increment))
+                {
+                    *cumulative_price = value;
+                }
+            }
+        }
+        oracle.last_update = now;
+
+        Ok(())
+    }
+
+    /// Deposits the input into the reserves and withdraws the output from them.
+    fn exchange<Y>(
+        pool: ComponentAddress,
+        input_bucket: Bucket,
+        output_resource_address: ResourceAddress,
+        output_amount: Decimal,
+        api: &mut Y,
+    ) -> Result<Bucket, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        Self::call_pool::<_, _, TwoResourcePoolProtectedDepositOutput>(
+            pool,
+            TWO_RESOURCE_POOL_PROTECTED_DEPOSIT_IDENT,
+            &TwoResourcePoolProtectedDepositInput {
+                bucket: input_bucket,
+            },
+            api,
+        )?;
+        Self::call_pool(
+            pool,
+            TWO_RESOURCE_POOL_PROTECTED_WITHDRAW_IDENT,
+            &TwoResourcePoolProtectedWithdrawInput {
+                resource_address: output_resource_address,
+                amount: output_amount,
+                withdraw_strategy: WithdrawStrategy::Exact,
+            },
+            api,
+        )
+    }
+
+    fn reserves<Y>(
+        pool: ComponentAddress,
+        api: &mut Y,
+    ) -> Result<IndexMap<ResourceAddress, Decimal>, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        Self::call_pool(
+            pool,
+            TWO_RESOURCE_POOL_GET_VAULT_AMOUNTS_IDENT,
+            &TwoResourcePoolGetVaultAmountsInput {},
+            api,
+        )
+    }
+
+    fn call_pool<Y, I, O>(
+        pool: ComponentAddress,
+        method_name: &str,
+        input: &I,
+        api: &mut Y,
+    ) -> Result<O, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+        I: ScryptoEncode,
+        O: ScryptoDecode,
+    {
+        let rtn = api.call_method(
+            pool.as_node_id(),
+            method_name,
+            scrypto_encode(input).unwrap(),
+        )?;
+        Ok(scrypto_decode(&rtn).unwrap())
+    }
+
+    fn divisibility<Y>(resource_address: ResourceAddress, api: &mut Y) -> Result<u8, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        ResourceManager(resource_address)
+            .resource_type(api)
+            .map(|resource_type| {
+                if let ResourceType::Fungible { divisibility } = resource_type {
+                    divisibility
+                } else {
+                    panic!(
+                        "Impossible case, the two-resource pool only accepts fungible resources."
+                    )
+                }
+            })
+    }
+
+    fn with_state<Y, F, O>(api: &mut Y, callback: F) -> Result<O, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+        F: FnOnce(Substate, &mut Y) -> Result<O, RuntimeError>,
+    {
+        // Open
+        let substate_key = ConstantProductPoolField::State.into();
+        let handle =
+            api.actor_open_field(ACTOR_STATE_SELF, substate_key, LockFlags::read_only())?;
+        let substate = api
+            .field_read_typed::<VersionedConstantProductPoolState>(handle)?
+            .into_latest();
+
+        // Op
+        let rtn = callback(substate, api);
+
+        // Close
+        if rtn.is_ok() {
+            api.field_close(handle)?;
+        }
+        rtn
+    }
+
+    fn with_state_mut<Y, F, O>(api: &mut Y, callback: F) -> Result<O, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+        F: FnOnce(&mut Substate, &mut Y) -> Result<O, RuntimeError>,
+    {
+        // Open
+        let substate_key = ConstantProductPoolField::State.into();
+        let handle = api.actor_open_field(ACTOR_STATE_SELF, substate_key, LockFlags::MUTABLE)?;
+        let mut substate = api
+            .field_read_typed::<VersionedConstantProductPoolState>(handle)?
+            .into_latest();
+
+        // Op
+        let rtn = callback(&mut substate, api);
+
+        // Write & Close
+        if rtn.is_ok() {
+            api.field_write_typed(
+                handle,
+                &ConstantProductPoolStateFieldPayload::from_content_source(substate),
+            )?;
+            api.field_close(handle)?;
+        }
+        rtn
+    }
+}
+
+impl Substate {
+    /// Returns the resource of the pool which is not the given one, or an error if the given
+    /// resource is not one of the resources of the pool.
+    fn other_resource(
+        &self,
+        resource_address: ResourceAddress,
+    ) -> Result<ResourceAddress, RuntimeError> {
+        let (resource_address1, resource_address2) = self.resource_addresses;
+        if resource_address == resource_address1 {
+            Ok(resource_address2)
+        } else if resource_address == resource_address2 {
+            Ok(resource_address1)
+        } else {
+            Err(Error::ResourceDoesNotBelongToPool { resource_address }.into())
+        }
+    }
+}
@@ -0,0 +1,3 @@
+mod constant_product_pool_blueprint;
+
+pub use constant_product_pool_blueprint::*;
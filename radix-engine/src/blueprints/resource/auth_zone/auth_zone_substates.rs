@@ -78,11 +78,14 @@ impl AuthZone {
 
     pub fn remove_signature_proofs(&mut self) {
         self.virtual_resources.retain(|x| {
-            x != &SECP256K1_SIGNATURE_VIRTUAL_BADGE && x != &ED25519_SIGNATURE_VIRTUAL_BADGE
+            x != &SECP256K1_SIGNATURE_VIRTUAL_BADGE
+                && x != &ED25519_SIGNATURE_VIRTUAL_BADGE
+                && x != &SECP256R1_SIGNATURE_VIRTUAL_BADGE
         });
         self.virtual_non_fungibles.retain(|x| {
             x.resource_address() != SECP256K1_SIGNATURE_VIRTUAL_BADGE
                 && x.resource_address() != ED25519_SIGNATURE_VIRTUAL_BADGE
+                && x.resource_address() != SECP256R1_SIGNATURE_VIRTUAL_BADGE
         });
     }
 
@@ -0,0 +1,12 @@
+use crate::types::*;
+
+#[derive(ScryptoSbor, ScryptoEvent, PartialEq, Eq, Debug)]
+pub struct WithdrawEvent {
+    pub amount: Decimal,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, PartialEq, Eq, Debug)]
+pub struct RevokeEvent {
+    /// The unvested amount which was returned to the revoker.
+    pub amount: Decimal,
+}
@@ -1,4 +1,5 @@
 pub mod fungible_vault;
+pub mod fungible_vesting;
 pub mod non_fungible_vault;
 mod resource_manager;
 
@@ -24,3 +24,9 @@ pub struct MintNonFungibleResourceEvent {
 pub struct BurnNonFungibleResourceEvent {
     pub ids: IndexSet<NonFungibleLocalId>,
 }
+
+/// Emitted once per batch update, with the names of the updated fields of each non-fungible.
+#[derive(ScryptoSbor, ScryptoEvent, PartialEq, Eq, Debug)]
+pub struct BatchUpdateNonFungibleDataEvent {
+    pub updated_fields: IndexMap<NonFungibleLocalId, IndexSet<String>>,
+}
@@ -0,0 +1,450 @@
+use crate::blueprints::resource::*;
+use crate::errors::ApplicationError;
+use crate::errors::RuntimeError;
+use crate::internal_prelude::*;
+use crate::types::*;
+use native_sdk::modules::metadata::*;
+use native_sdk::modules::role_assignment::*;
+use native_sdk::modules::royalty::*;
+use native_sdk::resource::*;
+use native_sdk::runtime::Runtime;
+use radix_engine_interface::api::field_api::LockFlags;
+use radix_engine_interface::api::{ClientApi, FieldValue, ACTOR_STATE_SELF};
+use radix_engine_interface::blueprints::component::*;
+use radix_engine_interface::blueprints::consensus_manager::TimePrecision;
+use radix_engine_interface::blueprints::resource::*;
+use radix_engine_interface::*;
+
+declare_native_blueprint_state! {
+    blueprint_ident: FungibleVesting,
+    blueprint_snake_case: fungible_vesting,
+    features: {
+    },
+    fields: {
+        state: {
+            ident: State,
+            field_type: {
+                kind: StaticSingleVersioned,
+            },
+            condition: Condition::Always,
+        }
+    },
+    collections: {
+    }
+}
+
+pub type FungibleVestingStateV1 = FungibleVestingSubstate;
+
+#[derive(Debug, PartialEq, Eq, ScryptoSbor)]
+pub struct FungibleVestingSubstate {
+    /// The vault holding the tokens which have not been withdrawn or revoked yet.
+    pub vault: Vault,
+
+    pub divisibility: u8,
+
+    pub schedule: VestingSchedule,
+
+    /// The amount of tokens the component was created with.
+    pub total_amount: Decimal,
+
+    /// The amount of tokens withdrawn by the beneficiary so far.
+    pub withdrawn_amount: Decimal,
+
+    /// The time of the revocation, after which no more tokens vest.
+    pub revoked_at: Option<Instant>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub enum FungibleVestingError {
+    NonFungibleResourcesAreNotAccepted {
+        resource_address: ResourceAddress,
+    },
+    InvalidSchedule(VestingSchedule),
+    AmountExceedsUnlockedAmount {
+        requested: Decimal,
+        unlocked: Decimal,
+    },
+    AlreadyRevoked,
+    UnexpectedDecimalComputationError,
+}
+
+impl From<FungibleVestingError> for RuntimeError {
+    fn from(error: FungibleVestingError) -> Self {
+        RuntimeError::ApplicationError(ApplicationError::FungibleVestingError(error))
+    }
+}
+
+/// A component holding fungible tokens which are released to a beneficiary over time, according
+/// to a [`VestingSchedule`] evaluated against the consensus time.
+///
+/// The beneficiary can withdraw whatever has vested and not been withdrawn yet. If the component
+/// was created with a revoker, the revoker can stop the vesting at any time, which returns the
+/// tokens which have not vested yet and leaves those which have to the beneficiary.
+pub struct FungibleVestingBlueprint;
+
+impl FungibleVestingBlueprint {
+    pub fn get_definition() -> BlueprintDefinitionInit {
+        let mut aggregator = TypeAggregator::<ScryptoCustomTypeKind>::new();
+        let state = FungibleVestingStateSchemaInit::create_schema_init(&mut aggregator);
+
+        let mut functions = index_map_new();
+        functions.insert(
+            FUNGIBLE_VESTING_CREATE_IDENT.to_string(),
+            FunctionSchemaInit {
+                receiver: None,
+                input: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<FungibleVestingCreateInput>(),
+                ),
+                output: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<FungibleVestingCreateOutput>(),
+                ),
+                export: FUNGIBLE_VESTING_CREATE_EXPORT_NAME.to_string(),
+            },
+        );
+        functions.insert(
+            FUNGIBLE_VESTING_WITHDRAW_IDENT.to_string(),
+            FunctionSchemaInit {
+                receiver: Some(ReceiverInfo::normal_ref_mut()),
+                input: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<FungibleVestingWithdrawInput>(),
+                ),
+                output: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<FungibleVestingWithdrawOutput>(),
+                ),
+                export: FUNGIBLE_VESTING_WITHDRAW_EXPORT_NAME.to_string(),
+            },
+        );
+        functions.insert(
+            FUNGIBLE_VESTING_GET_UNLOCKED_AMOUNT_IDENT.to_string(),
+            FunctionSchemaInit {
+                receiver: Some(ReceiverInfo::normal_ref()),
+                input: TypeRef::Static(
+                    aggregator
+                        .add_child_type_and_descendents::<FungibleVestingGetUnlockedAmountInput>(),
+                ),
+                output: TypeRef::Static(
+                    aggregator
+                        .add_child_type_and_descendents::<FungibleVestingGetUnlockedAmountOutput>(),
+                ),
+                export: FUNGIBLE_VESTING_GET_UNLOCKED_AMOUNT_EXPORT_NAME.to_string(),
+            },
+        );
+        functions.insert(
+            FUNGIBLE_VESTING_REVOKE_IDENT.to_string(),
+            FunctionSchemaInit {
+                receiver: Some(ReceiverInfo::normal_ref_mut()),
+                input: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<FungibleVestingRevokeInput>(),
+                ),
+                output: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<FungibleVestingRevokeOutput>(),
+                ),
+                export: FUNGIBLE_VESTING_REVOKE_EXPORT_NAME.to_string(),
+            },
+        );
+
+        let event_schema = event_schema! {
+            aggregator,
+            [
+                fungible_vesting::WithdrawEvent,
+                fungible_vesting::RevokeEvent
+            ]
+        };
+
+        let schema = generate_full_schema(aggregator);
+
+        BlueprintDefinitionInit {
+            blueprint_type: BlueprintType::default(),
+            is_transient: false,
+            dependencies: indexset!(),
+            feature_set: FungibleVestingFeatureSet::all_features(),
+            schema: BlueprintSchemaInit {
+                generics: vec![],
+                schema,
+                state,
+                events: event_schema,
+                types: BlueprintTypeSchemaInit::default(),
+                functions: BlueprintFunctionsSchemaInit { functions },
+                hooks: BlueprintHooksInit::default(),
+            },
+            royalty_config: PackageRoyaltyConfig::default(),
+            auth_config: AuthConfig {
+                function_auth: FunctionAuth::AllowAll,
+                method_auth: MethodAuthTemplate::StaticRoleDefinition(roles_template! {
+                    roles {
+                        FUNGIBLE_VESTING_BENEFICIARY_ROLE => updaters: [FUNGIBLE_VESTING_BENEFICIARY_ROLE];
+                        FUNGIBLE_VESTING_REVOKER_ROLE => updaters: [FUNGIBLE_VESTING_REVOKER_ROLE];
+                    },
+                    methods {
+                        FUNGIBLE_VESTING_GET_UNLOCKED_AMOUNT_IDENT => MethodAccessibility::Public;
+                        FUNGIBLE_VESTING_WITHDRAW_IDENT => [FUNGIBLE_VESTING_BENEFICIARY_ROLE];
+                        FUNGIBLE_VESTING_REVOKE_IDENT => [FUNGIBLE_VESTING_REVOKER_ROLE];
+                    }
+                }),
+            },
+        }
+    }
+
+    pub fn create<Y>(
+        owner_role: OwnerRole,
+        bucket: Bucket,
+        schedule: VestingSchedule,
+        beneficiary_rule: AccessRule,
+        revoker_rule: Option<AccessRule>,
+        address_reservation: Option<GlobalAddressReservation>,
+        api: &mut Y,
+    ) -> Result<FungibleVestingCreateOutput, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        Self::validate_schedule(&schedule)?;
+
+        let resource_address = bucket.resource_address(api)?;
+        let divisibility = match ResourceManager(resource_address).resource_type(api)? {
+            ResourceType::Fungible { divisibility } => divisibility,
+            ResourceType::NonFungible { .. } => {
+                return Err(FungibleVestingError::NonFungibleResourcesAreNotAccepted {
+                    resource_address,
+                }
+                .into())
+            }
+        };
+
+        let (address_reservation, address) = {
+            if let Some(address_reservation) = address_reservation {
+                let address = api.get_reservation_address(address_reservation.0.as_node_id())?;
+                (address_reservation, address)
+            } else {
+                api.allocate_global_address(BlueprintId {
+                    package_address: RESOURCE_PACKAGE,
+                    blueprint_name: FUNGIBLE_VESTING_BLUEPRINT.to_string(),
+                })?
+            }
+        };
+
+        // Without a revoker the vesting can never be stopped
+        let role_assignment = RoleAssignment::create(
+            owner_role,
+            indexmap! {
+                ModuleId::Main => roles_init! {
+                    RoleKey { key: FUNGIBLE_VESTING_BENEFICIARY_ROLE.to_owned() } => beneficiary_rule;
+                    RoleKey { key: FUNGIBLE_VESTING_REVOKER_ROLE.to_owned() } => revoker_rule.unwrap_or(AccessRule::DenyAll);
+                }
+            },
+            api,
+        )?
+        .0;
+        let metadata = Metadata::create_with_data(
+            metadata_init! {
+                "vesting_resource" => GlobalAddress::from(resource_address), locked;
+            },
+            api,
+        )?;
+        let royalty = ComponentRoyalty::create(ComponentRoyaltyConfig::default(), api)?;
+
+        let object_id = {
+            let total_amount = bucket.amount(api)?;
+            let mut vault = Vault::create(resource_address, api)?;
+            vault.put(bucket, api)?;
+
+            let substate = FungibleVestingSubstate {
+                vault,
+                divisibility,
+                schedule,
+                total_amount,
+                withdrawn_amount: Decimal::ZERO,
+                revoked_at: None,
+            };
+            api.new_simple_object(
+                FUNGIBLE_VESTING_BLUEPRINT,
+                indexmap! {
+                    FungibleVestingField::State.field_index() => FieldValue::new(FungibleVestingStateFieldPayload::from_content_source(substate)),
+                },
+            )?
+        };
+
+        api.globalize(
+            object_id,
+            indexmap!(
+                AttachedModuleId::RoleAssignment => role_assignment.0,
+                AttachedModuleId::Metadata => metadata.0,
+                AttachedModuleId::Royalty => royalty.0,
+            ),
+            Some(address_reservation),
+        )?;
+
+        Ok(Global::new(ComponentAddress::new_or_panic(
+            address.as_node_id().0,
+        )))
+    }
+
+    /// Withdraws the given amount, which must not exceed the unlocked amount.
+    pub fn withdraw<Y>(
+        amount: Decimal,
+        api: &mut Y,
+    ) -> Result<FungibleVestingWithdrawOutput, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        Self::with_state_mut(api, |substate, api| {
+            let now = Runtime::current_time(api, TimePrecision::Second)?;
+            let unlocked = substate.unlocked_amount(now)?;
+            if amount > unlocked {
+                return Err(FungibleVestingError::AmountExceedsUnlockedAmount {
+                    requested: amount,
+                    unlocked,
+                }
+                .into());
+            }
+
+            let bucket = substate.vault.take(amount, api)?;
+            substate.withdrawn_amount = substate
+                .withdrawn_amount
+                .checked_add(amount)
+                .ok_or(FungibleVestingError::UnexpectedDecimalComputationError)?;
+
+            Runtime::emit_event(api, fungible_vesting::WithdrawEvent { amount })?;
+
+            Ok(bucket)
+        })
+    }
+
+    /// Returns the amount which has vested and has not been withdrawn yet.
+    pub fn get_unlocked_amount<Y>(
+        api: &mut Y,
+    ) -> Result<FungibleVestingGetUnlockedAmountOutput, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        Self::with_state(api, |substate, api| {
+            let now = Runtime::current_time(api, TimePrecision::Second)?;
+            substate.unlocked_amount(now)
+        })
+    }
+
+    /// Stops the vesting and returns the tokens which have not vested yet. The tokens which have
+    /// vested can still be withdrawn by the beneficiary.
+    pub fn revoke<Y>(api: &mut Y) -> Result<FungibleVestingRevokeOutput, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        Self::with_state_mut(api, |substate, api| {
+            if substate.revoked_at.is_some() {
+                return Err(FungibleVestingError::AlreadyRevoked.into());
+            }
+
+            let now = Runtime::current_time(api, TimePrecision::Second)?;
+            let unvested = substate
+                .total_amount
+                .checked_sub(substate.vested_amount(now)?)
+                .ok_or(FungibleVestingError::UnexpectedDecimalComputationError)?;
+            substate.revoked_at = Some(now);
+
+            let bucket = substate.vault.take(unvested, api)?;
+
+            Runtime::emit_event(api, fungible_vesting::RevokeEvent { amount: unvested })?;
+
+            Ok(bucket)
+        })
+    }
+
+    fn validate_schedule(schedule: &VestingSchedule) -> Result<(), RuntimeError> {
+        let cliff = schedule.cliff.unwrap_or(schedule.start);
+        if schedule.start.seconds_since_unix_epoch <= cliff.seconds_since_unix_epoch
+            && cliff.seconds_since_unix_epoch <= schedule.end.seconds_since_unix_epoch
+        {
+            Ok(())
+        } else {
+            Err(FungibleVestingError::InvalidSchedule(*schedule).into())
+        }
+    }
+
+    fn with_state<Y, F, O>(api: &mut Y, callback: F) -> Result<O, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+        F: FnOnce(FungibleVestingSubstate, &mut Y) -> Result<O, RuntimeError>,
+    {
+        let handle = api.actor_open_field(
+            ACTOR_STATE_SELF,
+            FungibleVestingField::State.into(),
+            LockFlags::read_only(),
+        )?;
+        let substate = api
+            .field_read_typed::<FungibleVestingStateFieldPayload>(handle)?
+            .into_latest();
+
+        let rtn = callback(substate, api);
+
+        if rtn.is_ok() {
+            api.field_close(handle)?;
+        }
+        rtn
+    }
+
+    fn with_state_mut<Y, F, O>(api: &mut Y, callback: F) -> Result<O, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+        F: FnOnce(&mut FungibleVestingSubstate, &mut Y) -> Result<O, RuntimeError>,
+    {
+        let handle = api.actor_open_field(
+            ACTOR_STATE_SELF,
+            FungibleVestingField::State.into(),
+            LockFlags::MUTABLE,
+        )?;
+        let mut substate = api
+            .field_read_typed::<FungibleVestingStateFieldPayload>(handle)?
+            .into_latest();
+
+        let rtn = callback(&mut substate, api);
+
+        if rtn.is_ok() {
+            api.field_write_typed(
+                handle,
+                &FungibleVestingStateFieldPayload::from_content_source(substate),
+            )?;
+            api.field_close(handle)?;
+        }
+        rtn
+    }
+}
+
+impl FungibleVestingSubstate {
+    /// Returns the amount which has vested by the given time, rounded down to the divisibility of
+    /// the resource. Nothing vests after a revocation.
+    fn vested_amount(&self, now: Instant) -> Result<Decimal, RuntimeError> {
+        let now = match self.revoked_at {
+            Some(revoked_at)
+                if revoked_at.seconds_since_unix_epoch < now.seconds_since_unix_epoch =>
+            {
+                revoked_at
+            }
+            _ => now,
+        }
+        .seconds_since_unix_epoch;
+        let start = self.schedule.start.seconds_since_unix_epoch;
+        let cliff = self.schedule.cliff.unwrap_or(self.schedule.start);
+        let end = self.schedule.end.seconds_since_unix_epoch;
+
+        if now < cliff.seconds_since_unix_epoch {
+            Ok(Decimal::ZERO)
+        } else if now >= end {
+            Ok(self.total_amount)
+        } else {
+            // The schedule is validated on creation, so `start <= cliff <= now < end` here
+            self.total_amount
+                .checked_mul_div(
+                    Decimal::from(now - start),
+                    Decimal::from(end - start),
+                    RoundingMode::ToZero,
+                )
+                .and_then(|amount| amount.checked_round(self.divisibility, RoundingMode::ToZero))
+                .ok_or(FungibleVestingError::UnexpectedDecimalComputationError.into())
+        }
+    }
+
+    fn unlocked_amount(&self, now: Instant) -> Result<Decimal, RuntimeError> {
+        self.vested_amount(now)?
+            .checked_sub(self.withdrawn_amount)
+            .ok_or(FungibleVestingError::UnexpectedDecimalComputationError.into())
+    }
+}
@@ -2,8 +2,10 @@ mod fungible_bucket;
 mod fungible_proof;
 mod fungible_resource_manager;
 mod fungible_vault;
+mod fungible_vesting;
 
 pub use fungible_bucket::*;
 pub use fungible_proof::*;
 pub use fungible_resource_manager::*;
 pub use fungible_vault::*;
+pub use fungible_vesting::*;
@@ -178,7 +178,7 @@ where
 pub struct NonFungibleResourceManagerBlueprint;
 
 impl NonFungibleResourceManagerBlueprint {
-    pub fn get_definition() -> BlueprintDefinitionInit {
+    pub fn get_definition(minor_version: ResourceV1MinorVersion) -> BlueprintDefinitionInit {
         let mut aggregator = TypeAggregator::<ScryptoCustomTypeKind>::new();
 
         let state = NonFungibleResourceManagerStateSchemaInit::create_schema_init(&mut aggregator);
@@ -261,6 +261,19 @@ impl NonFungibleResourceManagerBlueprint {
                 export: NON_FUNGIBLE_RESOURCE_MANAGER_UPDATE_DATA_IDENT.to_string(),
             },
         );
+        if minor_version >= ResourceV1MinorVersion::Two {
+            functions.insert(
+                NON_FUNGIBLE_RESOURCE_MANAGER_BATCH_UPDATE_DATA_IDENT.to_string(),
+                FunctionSchemaInit {
+                    receiver: Some(ReceiverInfo::normal_ref_mut()),
+                    input: TypeRef::Static(aggregator
+                        .add_child_type_and_descendents::<NonFungibleResourceManagerBatchUpdateDataInput>()),
+                    output: TypeRef::Static(aggregator
+                        .add_child_type_and_descendents::<NonFungibleResourceManagerBatchUpdateDataOutput>()),
+                    export: NON_FUNGIBLE_RESOURCE_MANAGER_BATCH_UPDATE_DATA_IDENT.to_string(),
+                },
+            );
+        }
         functions.insert(
             NON_FUNGIBLE_RESOURCE_MANAGER_EXISTS_IDENT.to_string(),
             FunctionSchemaInit {
@@ -425,7 +438,7 @@ impl NonFungibleResourceManagerBlueprint {
             },
         );
 
-        let event_schema = event_schema! {
+        let mut event_schema = event_schema! {
             aggregator,
             [
                 VaultCreationEvent,
@@ -433,9 +446,63 @@ impl NonFungibleResourceManagerBlueprint {
                 BurnNonFungibleResourceEvent
             ]
         };
+        if minor_version >= ResourceV1MinorVersion::Two {
+            event_schema.event_schema.extend(
+                event_schema! {
+                    aggregator,
+                    [BatchUpdateNonFungibleDataEvent]
+                }
+                .event_schema,
+            );
+        }
 
         let schema = generate_full_schema(aggregator);
 
+        let mut role_definition = roles_template! {
+            roles {
+                MINTER_ROLE => updaters: [MINTER_UPDATER_ROLE];
+                MINTER_UPDATER_ROLE => updaters: [MINTER_UPDATER_ROLE];
+                BURNER_ROLE => updaters: [BURNER_UPDATER_ROLE];
+                BURNER_UPDATER_ROLE => updaters: [BURNER_UPDATER_ROLE];
+                WITHDRAWER_ROLE => updaters: [WITHDRAWER_UPDATER_ROLE];
+                WITHDRAWER_UPDATER_ROLE => updaters: [WITHDRAWER_UPDATER_ROLE];
+                DEPOSITOR_ROLE => updaters: [DEPOSITOR_UPDATER_ROLE];
+                DEPOSITOR_UPDATER_ROLE => updaters: [DEPOSITOR_UPDATER_ROLE];
+                RECALLER_ROLE => updaters: [RECALLER_UPDATER_ROLE];
+                RECALLER_UPDATER_ROLE => updaters: [RECALLER_UPDATER_ROLE];
+                FREEZER_ROLE => updaters: [FREEZER_UPDATER_ROLE];
+                FREEZER_UPDATER_ROLE => updaters: [FREEZER_UPDATER_ROLE];
+                NON_FUNGIBLE_DATA_UPDATER_ROLE => updaters: [NON_FUNGIBLE_DATA_UPDATER_UPDATER_ROLE];
+                NON_FUNGIBLE_DATA_UPDATER_UPDATER_ROLE => updaters: [NON_FUNGIBLE_DATA_UPDATER_UPDATER_ROLE];
+            },
+            methods {
+                NON_FUNGIBLE_RESOURCE_MANAGER_MINT_IDENT => [MINTER_ROLE];
+                NON_FUNGIBLE_RESOURCE_MANAGER_MINT_RUID_IDENT => [MINTER_ROLE];
+                NON_FUNGIBLE_RESOURCE_MANAGER_MINT_SINGLE_RUID_IDENT => [MINTER_ROLE];
+                RESOURCE_MANAGER_BURN_IDENT => [BURNER_ROLE];
+                RESOURCE_MANAGER_PACKAGE_BURN_IDENT => MethodAccessibility::OwnPackageOnly;
+                NON_FUNGIBLE_RESOURCE_MANAGER_UPDATE_DATA_IDENT => [NON_FUNGIBLE_DATA_UPDATER_ROLE];
+                RESOURCE_MANAGER_CREATE_EMPTY_BUCKET_IDENT => MethodAccessibility::Public;
+                RESOURCE_MANAGER_CREATE_EMPTY_VAULT_IDENT => MethodAccessibility::Public;
+                RESOURCE_MANAGER_GET_TOTAL_SUPPLY_IDENT => MethodAccessibility::Public;
+                RESOURCE_MANAGER_GET_AMOUNT_FOR_WITHDRAWAL_IDENT => MethodAccessibility::Public;
+                RESOURCE_MANAGER_DROP_EMPTY_BUCKET_IDENT => MethodAccessibility::Public;
+                RESOURCE_MANAGER_GET_RESOURCE_TYPE_IDENT => MethodAccessibility::Public;
+                NON_FUNGIBLE_RESOURCE_MANAGER_GET_NON_FUNGIBLE_IDENT => MethodAccessibility::Public;
+                NON_FUNGIBLE_RESOURCE_MANAGER_EXISTS_IDENT => MethodAccessibility::Public;
+            }
+        };
+        if minor_version >= ResourceV1MinorVersion::Two {
+            role_definition.methods.extend(
+                roles_template! {
+                    methods {
+                        NON_FUNGIBLE_RESOURCE_MANAGER_BATCH_UPDATE_DATA_IDENT => [NON_FUNGIBLE_DATA_UPDATER_ROLE];
+                    }
+                }
+                .methods,
+            );
+        }
+
         BlueprintDefinitionInit {
             blueprint_type: BlueprintType::Outer,
             is_transient: false,
@@ -454,40 +521,7 @@ impl NonFungibleResourceManagerBlueprint {
             royalty_config: PackageRoyaltyConfig::default(),
             auth_config: AuthConfig {
                 function_auth: FunctionAuth::AllowAll,
-                method_auth: MethodAuthTemplate::StaticRoleDefinition(roles_template! {
-                    roles {
-                        MINTER_ROLE => updaters: [MINTER_UPDATER_ROLE];
-                        MINTER_UPDATER_ROLE => updaters: [MINTER_UPDATER_ROLE];
-                        BURNER_ROLE => updaters: [BURNER_UPDATER_ROLE];
-                        BURNER_UPDATER_ROLE => updaters: [BURNER_UPDATER_ROLE];
-                        WITHDRAWER_ROLE => updaters: [WITHDRAWER_UPDATER_ROLE];
-                        WITHDRAWER_UPDATER_ROLE => updaters: [WITHDRAWER_UPDATER_ROLE];
-                        DEPOSITOR_ROLE => updaters: [DEPOSITOR_UPDATER_ROLE];
-                        DEPOSITOR_UPDATER_ROLE => updaters: [DEPOSITOR_UPDATER_ROLE];
-                        RECALLER_ROLE => updaters: [RECALLER_UPDATER_ROLE];
-                        RECALLER_UPDATER_ROLE => updaters: [RECALLER_UPDATER_ROLE];
-                        FREEZER_ROLE => updaters: [FREEZER_UPDATER_ROLE];
-                        FREEZER_UPDATER_ROLE => updaters: [FREEZER_UPDATER_ROLE];
-                        NON_FUNGIBLE_DATA_UPDATER_ROLE => updaters: [NON_FUNGIBLE_DATA_UPDATER_UPDATER_ROLE];
-                        NON_FUNGIBLE_DATA_UPDATER_UPDATER_ROLE => updaters: [NON_FUNGIBLE_DATA_UPDATER_UPDATER_ROLE];
-                    },
-                    methods {
-                        NON_FUNGIBLE_RESOURCE_MANAGER_MINT_IDENT => [MINTER_ROLE];
-                        NON_FUNGIBLE_RESOURCE_MANAGER_MINT_RUID_IDENT => [MINTER_ROLE];
-                        NON_FUNGIBLE_RESOURCE_MANAGER_MINT_SINGLE_RUID_IDENT => [MINTER_ROLE];
-                        RESOURCE_MANAGER_BURN_IDENT => [BURNER_ROLE];
-                        RESOURCE_MANAGER_PACKAGE_BURN_IDENT => MethodAccessibility::OwnPackageOnly;
-                        NON_FUNGIBLE_RESOURCE_MANAGER_UPDATE_DATA_IDENT => [NON_FUNGIBLE_DATA_UPDATER_ROLE];
-                        RESOURCE_MANAGER_CREATE_EMPTY_BUCKET_IDENT => MethodAccessibility::Public;
-                        RESOURCE_MANAGER_CREATE_EMPTY_VAULT_IDENT => MethodAccessibility::Public;
-                        RESOURCE_MANAGER_GET_TOTAL_SUPPLY_IDENT => MethodAccessibility::Public;
-                        RESOURCE_MANAGER_GET_AMOUNT_FOR_WITHDRAWAL_IDENT => MethodAccessibility::Public;
-                        RESOURCE_MANAGER_DROP_EMPTY_BUCKET_IDENT => MethodAccessibility::Public;
-                        RESOURCE_MANAGER_GET_RESOURCE_TYPE_IDENT => MethodAccessibility::Public;
-                        NON_FUNGIBLE_RESOURCE_MANAGER_GET_NON_FUNGIBLE_IDENT => MethodAccessibility::Public;
-                        NON_FUNGIBLE_RESOURCE_MANAGER_EXISTS_IDENT => MethodAccessibility::Public;
-                    }
-                }),
+                method_auth: MethodAuthTemplate::StaticRoleDefinition(role_definition),
             },
         }
     }
@@ -969,6 +1003,98 @@ impl NonFungibleResourceManagerBlueprint {
         Ok(())
     }
 
+    /// Applies all updates in a single invocation. The updates are grouped per non-fungible so
+    /// that each data entry is read, validated against the data schema and written only once.
+    pub(crate) fn batch_update_non_fungible_data<Y>(
+        updates: Vec<(NonFungibleLocalId, String, ScryptoValue)>,
+        api: &mut Y,
+    ) -> Result<(), RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let mutable_field_index = {
+            let data_schema_handle = api.actor_open_field(
+                ACTOR_STATE_SELF,
+                NonFungibleResourceManagerField::MutableFields.into(),
+                LockFlags::read_only(),
+            )?;
+            let mutable_fields = api
+                .field_read_typed::<NonFungibleResourceManagerMutableFieldsFieldPayload>(
+                    data_schema_handle,
+                )?
+                .into_latest();
+            api.field_close(data_schema_handle)?;
+            mutable_fields.mutable_field_index
+        };
+
+        let mut grouped_updates: IndexMap<NonFungibleLocalId, Vec<(String, ScryptoValue)>> =
+            index_map_new();
+        for (id, field_name, data) in updates {
+            if !mutable_field_index.contains_key(&field_name) {
+                return Err(RuntimeError::ApplicationError(
+                    ApplicationError::NonFungibleResourceManagerError(
+                        NonFungibleResourceManagerError::UnknownMutableFieldName(field_name),
+                    ),
+                ));
+            }
+            grouped_updates
+                .entry(id)
+                .or_default()
+                .push((field_name, data));
+        }
+
+        let mut updated_fields = index_map_new();
+        for (id, field_updates) in grouped_updates {
+            let non_fungible_handle = api.actor_open_key_value_entry(
+                ACTOR_STATE_SELF,
+                NonFungibleResourceManagerCollection::DataKeyValue.collection_index(),
+                &id.to_key(),
+                LockFlags::MUTABLE,
+            )?;
+
+            let non_fungible_entry = api
+                .key_value_entry_get_typed::<NonFungibleResourceManagerDataEntryPayload>(
+                    non_fungible_handle,
+                )?;
+            let mut non_fungible_data_payload = match non_fungible_entry {
+                Some(non_fungible_data_payload) => non_fungible_data_payload,
+                None => {
+                    let resource_address = ResourceAddress::new_or_panic(
+                        api.actor_get_node_id(ACTOR_REF_GLOBAL)?.into(),
+                    );
+                    let non_fungible_global_id = NonFungibleGlobalId::new(resource_address, id);
+                    return Err(RuntimeError::ApplicationError(
+                        ApplicationError::NonFungibleResourceManagerError(
+                            NonFungibleResourceManagerError::NonFungibleNotFound(Box::new(
+                                non_fungible_global_id,
+                            )),
+                        ),
+                    ));
+                }
+            };
+
+            let mut field_names = index_set_new();
+            match non_fungible_data_payload.as_mut() {
+                Value::Tuple { fields } => {
+                    for (field_name, data) in field_updates {
+                        fields[mutable_field_index[&field_name]] = data;
+                        field_names.insert(field_name);
+                    }
+                }
+                _ => panic!("Non-tuple non-fungible created: id = {}", id),
+            }
+            let buffer = scrypto_encode(&non_fungible_data_payload).unwrap();
+            api.key_value_entry_set(non_fungible_handle, buffer)?;
+            api.key_value_entry_close(non_fungible_handle)?;
+
+            updated_fields.insert(id, field_names);
+        }
+
+        Runtime::emit_event(api, BatchUpdateNonFungibleDataEvent { updated_fields })?;
+
+        Ok(())
+    }
+
     pub(crate) fn non_fungible_exists<Y>(
         id: NonFungibleLocalId,
         api: &mut Y,
@@ -155,15 +155,23 @@ pub(crate) const AUTH_ZONE_DROP_PROOFS_EXPORT_NAME: &str = "AuthZone_drop_proofs
 pub(crate) const AUTH_ZONE_DRAIN_EXPORT_NAME: &str = "AuthZone_drain";
 pub(crate) const AUTH_ZONE_ASSERT_ACCESS_RULE_EXPORT_NAME: &str = "AuthZone_assert_access_rule";
 
+/// The minor version of the Resource V1 package
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Sbor)]
+pub enum ResourceV1MinorVersion {
+    Zero,
+    One,
+    Two,
+}
+
 pub struct ResourceNativePackage;
 
 impl ResourceNativePackage {
-    pub fn definition() -> PackageDefinition {
+    pub fn definition(minor_version: ResourceV1MinorVersion) -> PackageDefinition {
         let fungible_resource_manager_blueprint =
             FungibleResourceManagerBlueprint::get_definition();
 
         let non_fungible_resource_manager_blueprint =
-            NonFungibleResourceManagerBlueprint::get_definition();
+            NonFungibleResourceManagerBlueprint::get_definition(minor_version);
 
         let fungible_vault_blueprint = FungibleVaultBlueprint::get_definition();
 
@@ -1128,7 +1136,7 @@ impl ResourceNativePackage {
             }
         };
 
-        let blueprints = indexmap!(
+        let mut blueprints = indexmap!(
             FUNGIBLE_RESOURCE_MANAGER_BLUEPRINT.to_string() => fungible_resource_manager_blueprint,
             NON_FUNGIBLE_RESOURCE_MANAGER_BLUEPRINT.to_string() => non_fungible_resource_manager_blueprint,
             FUNGIBLE_VAULT_BLUEPRINT.to_string() => fungible_vault_blueprint,
@@ -1140,6 +1148,12 @@ impl ResourceNativePackage {
             WORKTOP_BLUEPRINT.to_string() => worktop_blueprint,
             AUTH_ZONE_BLUEPRINT.to_string() => auth_zone_blueprint,
         );
+        if minor_version >= ResourceV1MinorVersion::One {
+            blueprints.insert(
+                FUNGIBLE_VESTING_BLUEPRINT.to_string(),
+                FungibleVestingBlueprint::get_definition(),
+            );
+        }
 
         PackageDefinition { blueprints }
     }
@@ -1394,6 +1408,17 @@ impl ResourceNativePackage {
                 )?;
                 Ok(IndexedScryptoValue::from_typed(&rtn))
             }
+            NON_FUNGIBLE_RESOURCE_MANAGER_BATCH_UPDATE_DATA_IDENT => {
+                let input: NonFungibleResourceManagerBatchUpdateDataInput =
+                    input.as_typed().map_err(|e| {
+                        RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                    })?;
+                let rtn = NonFungibleResourceManagerBlueprint::batch_update_non_fungible_data(
+                    input.updates,
+                    api,
+                )?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
             NON_FUNGIBLE_RESOURCE_MANAGER_EXISTS_IDENT => {
                 let input: NonFungibleResourceManagerExistsInput =
                     input.as_typed().map_err(|e| {
@@ -2048,6 +2073,51 @@ impl ResourceNativePackage {
 
                 Ok(IndexedScryptoValue::from_typed(&rtn))
             }
+            FUNGIBLE_VESTING_CREATE_EXPORT_NAME => {
+                let input: FungibleVestingCreateInput = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+
+                let rtn = FungibleVestingBlueprint::create(
+                    input.owner_role,
+                    input.bucket,
+                    input.schedule,
+                    input.beneficiary_rule,
+                    input.revoker_rule,
+                    input.address_reservation,
+                    api,
+                )?;
+
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+            FUNGIBLE_VESTING_WITHDRAW_EXPORT_NAME => {
+                let input: FungibleVestingWithdrawInput = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+
+                let rtn = FungibleVestingBlueprint::withdraw(input.amount, api)?;
+
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+            FUNGIBLE_VESTING_GET_UNLOCKED_AMOUNT_EXPORT_NAME => {
+                let _input: FungibleVestingGetUnlockedAmountInput =
+                    input.as_typed().map_err(|e| {
+                        RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                    })?;
+
+                let rtn = FungibleVestingBlueprint::get_unlocked_amount(api)?;
+
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+            FUNGIBLE_VESTING_REVOKE_EXPORT_NAME => {
+                let _input: FungibleVestingRevokeInput = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+
+                let rtn = FungibleVestingBlueprint::revoke(api)?;
+
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
             _ => Err(RuntimeError::ApplicationError(
                 ApplicationError::ExportDoesNotExist(export_name.to_string()),
             )),
@@ -140,7 +140,7 @@ impl TransactionTrackerNativePackage {
     }
 }
 
-#[derive(Debug, Clone, ScryptoSbor)]
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
 pub enum TransactionStatus {
     V1(TransactionStatusV1),
 }
@@ -153,7 +153,7 @@ impl TransactionStatus {
     }
 }
 
-#[derive(Debug, Clone, ScryptoSbor)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ScryptoSbor)]
 pub enum TransactionStatusV1 {
     CommittedSuccess,
     CommittedFailure,
@@ -3,14 +3,15 @@ use crate::blueprints::account::AccountError;
 use crate::blueprints::consensus_manager::{ConsensusManagerError, ValidatorError};
 use crate::blueprints::package::PackageError;
 use crate::blueprints::pool::v1::errors::{
+    constant_product_pool::Error as ConstantProductPoolError,
     multi_resource_pool::Error as MultiResourcePoolError,
     one_resource_pool::Error as OneResourcePoolError,
     two_resource_pool::Error as TwoResourcePoolError,
 };
 use crate::blueprints::resource::{AuthZoneError, NonFungibleVaultError};
 use crate::blueprints::resource::{
-    BucketError, FungibleResourceManagerError, NonFungibleResourceManagerError, ProofError,
-    VaultError, WorktopError,
+    BucketError, FungibleResourceManagerError, FungibleVestingError,
+    NonFungibleResourceManagerError, ProofError, VaultError, WorktopError,
 };
 use crate::blueprints::transaction_processor::TransactionProcessorError;
 use crate::kernel::call_frame::{
@@ -255,6 +256,7 @@ pub enum SystemError {
 
     BlsError(String),
     InputDataEmpty,
+    Secp256k1KeyRecoveryError,
 
     /// A panic that's occurred in the system-layer or below. We're calling it system panic since
     /// we're treating the system as a black-box here.
@@ -456,6 +458,8 @@ pub enum ApplicationError {
 
     VaultError(VaultError),
 
+    FungibleVestingError(FungibleVestingError),
+
     WorktopError(WorktopError),
 
     AuthZoneError(AuthZoneError),
@@ -469,6 +473,8 @@ pub enum ApplicationError {
     TwoResourcePoolError(TwoResourcePoolError),
 
     MultiResourcePoolError(MultiResourcePoolError),
+
+    ConstantProductPoolError(ConstantProductPoolError),
 }
 
 impl From<TransactionProcessorError> for ApplicationError {
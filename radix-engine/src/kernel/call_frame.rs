@@ -3,7 +3,9 @@ use crate::kernel::kernel_callback_api::CallFrameReferences;
 use crate::kernel::substate_io::{
     IOAccessHandler, SubstateDevice, SubstateIO, SubstateReadHandler,
 };
-use crate::track::interface::{CallbackError, CommitableSubstateStore, IOAccess, NodeSubstates};
+use crate::track::interface::{
+    CallbackError, CommitableSubstateStore, IOAccess, NodeSubstates, ScanKeysOrder,
+};
 use crate::types::*;
 use radix_engine_interface::api::field_api::LockFlags;
 use radix_engine_interface::types::{NodeId, SubstateHandle, SubstateKey};
@@ -1206,6 +1208,7 @@ impl<C, L: Clone> CallFrame<C, L> {
         node_id: &NodeId,
         partition_num: PartitionNumber,
         limit: u32,
+        order: ScanKeysOrder,
         handler: &mut impl CallFrameIOAccessHandler<C, L, E>,
     ) -> Result<Vec<SubstateKey>, CallbackError<CallFrameScanKeysError, E>> {
         // Check node visibility
@@ -1219,8 +1222,14 @@ impl<C, L: Clone> CallFrame<C, L> {
             phantom: PhantomData::default(),
         };
 
-        let keys =
-            substate_io.scan_keys::<K, E>(device, node_id, partition_num, limit, &mut adapter)?;
+        let keys = substate_io.scan_keys::<K, E>(
+            device,
+            node_id,
+            partition_num,
+            limit,
+            order,
+            &mut adapter,
+        )?;
 
         for key in &keys {
             self.process_output_substate_key(key).map_err(|e| {
@@ -1293,6 +1302,7 @@ impl<C, L: Clone> CallFrame<C, L> {
         node_id: &NodeId,
         partition_num: PartitionNumber,
         count: u32,
+        after: Option<&SortedKey>,
         handler: &mut impl CallFrameIOAccessHandler<C, L, E>,
     ) -> Result<
         Vec<(SortedKey, IndexedScryptoValue)>,
@@ -1312,7 +1322,7 @@ impl<C, L: Clone> CallFrame<C, L> {
         };
 
         let substates =
-            substate_io.scan_sorted(device, node_id, partition_num, count, &mut adapter)?;
+            substate_io.scan_sorted(device, node_id, partition_num, count, after, &mut adapter)?;
 
         for (key, substate) in &substates {
             self.process_output_substate_key(&SubstateKey::Sorted(key.clone()))
@@ -1,6 +1,7 @@
 use crate::track::interface::IOAccess;
 use crate::track::interface::{CallbackError, CanonicalSubstateKey, NodeSubstates};
 use crate::types::*;
+use sbor::rust::ops::Bound;
 
 pub struct Heap {
     nodes: NonIterMap<NodeId, NodeSubstates>,
@@ -160,16 +161,19 @@ impl Heap {
 
     /// Scans the keys of a node's partition. On an non-existing node/partition, this
     /// will return an empty vector
+    ///
+    /// If `after` is given, the scan continues strictly after that key.
     pub fn scan_keys(
         &self,
         node_id: &NodeId,
         partition_num: PartitionNumber,
         count: u32,
+        after: Option<&SubstateKey>,
     ) -> Vec<SubstateKey> {
         let node_substates = self.nodes.get(node_id).and_then(|n| n.get(&partition_num));
         if let Some(substates) = node_substates {
             let substate_keys: Vec<SubstateKey> = substates
-                .iter()
+                .range(Self::range_after(after))
                 .map(|(key, _value)| key.clone())
                 .take(count.try_into().unwrap())
                 .collect();
@@ -180,6 +184,40 @@ impl Heap {
         }
     }
 
+    /// Scans the substates of a node's sorted partition, in ascending order of their
+    /// sorted keys. On an non-existing node/partition, this will return an empty vector
+    ///
+    /// If `after` is given, the scan continues strictly after that key.
+    pub fn scan_sorted_substates(
+        &self,
+        node_id: &NodeId,
+        partition_num: PartitionNumber,
+        count: u32,
+        after: Option<&SortedKey>,
+    ) -> Vec<(SortedKey, IndexedScryptoValue)> {
+        let node_substates = self.nodes.get(node_id).and_then(|n| n.get(&partition_num));
+        if let Some(substates) = node_substates {
+            let after = after.map(|sorted_key| SubstateKey::Sorted(sorted_key.clone()));
+            substates
+                .range(Self::range_after(after.as_ref()))
+                .map(|(key, value)| match key {
+                    SubstateKey::Sorted(sorted_key) => (sorted_key.clone(), value.clone()),
+                    _ => panic!("Should be a sorted key"),
+                })
+                .take(count.try_into().unwrap())
+                .collect()
+        } else {
+            vec![]
+        }
+    }
+
+    fn range_after(after: Option<&SubstateKey>) -> (Bound<&SubstateKey>, Bound<&SubstateKey>) {
+        match after {
+            Some(after) => (Bound::Excluded(after), Bound::Unbounded),
+            None => (Bound::Unbounded, Bound::Unbounded),
+        }
+    }
+
     /// Drains the substates from a node's partition. On an non-existing node/partition, this
     /// will return an empty vector
     pub fn drain_substates<E, F: FnMut(&Heap, IOAccess) -> Result<(), E>>(
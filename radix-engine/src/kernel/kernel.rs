@@ -20,7 +20,9 @@ use crate::kernel::substate_io::{SubstateDevice, SubstateIO};
 use crate::kernel::substate_locks::SubstateLocks;
 use crate::system::system_modules::execution_trace::{BucketSnapshot, ProofSnapshot};
 use crate::system::type_info::TypeInfoSubstate;
-use crate::track::interface::{CallbackError, CommitableSubstateStore, IOAccess, NodeSubstates};
+use crate::track::interface::{
+    CallbackError, CommitableSubstateStore, IOAccess, NodeSubstates, ScanKeysOrder,
+};
 use crate::track::BootStore;
 use crate::types::*;
 use radix_engine_interface::api::field_api::LockFlags;
@@ -987,6 +989,7 @@ where
         node_id: &NodeId,
         partition_num: PartitionNumber,
         limit: u32,
+        order: ScanKeysOrder,
     ) -> Result<Vec<SubstateKey>, RuntimeError> {
         self.callback.on_scan_keys(ScanKeysEvent::Start)?;
 
@@ -1007,6 +1010,7 @@ where
                 node_id,
                 partition_num,
                 limit,
+                order,
                 &mut handler,
             )
             .map_err(|e| match e {
@@ -1064,6 +1068,7 @@ where
         node_id: &NodeId,
         partition_num: PartitionNumber,
         limit: u32,
+        after: Option<&SortedKey>,
     ) -> Result<Vec<(SortedKey, IndexedScryptoValue)>, RuntimeError> {
         self.callback
             .on_scan_sorted_substates(ScanSortedSubstatesEvent::Start)?;
@@ -1085,6 +1090,7 @@ where
                 node_id,
                 partition_num,
                 limit,
+                after,
                 &mut handler,
             )
             .map_err(|e| match e {
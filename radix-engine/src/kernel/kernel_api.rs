@@ -125,7 +125,8 @@ pub trait KernelSubstateApi<L> {
         substate_key: &SubstateKey,
     ) -> Result<Option<IndexedScryptoValue>, RuntimeError>;
 
-    /// Reads substates under a node in sorted lexicographical order
+    /// Reads substates under a node in sorted lexicographical order, continuing strictly
+    /// after the given key, if any
     ///
     /// Clients must ensure that this isn't used in conjunction with virtualized
     /// substates; otherwise, the behavior is undefined
@@ -134,13 +135,16 @@ pub trait KernelSubstateApi<L> {
         node_id: &NodeId,
         partition_num: PartitionNumber,
         count: u32,
+        after: Option<&SortedKey>,
     ) -> Result<Vec<(SortedKey, IndexedScryptoValue)>, RuntimeError>;
 
+    /// Reads the substate keys under a node, in the given order
     fn kernel_scan_keys<K: SubstateKeyContent + 'static>(
         &mut self,
         node_id: &NodeId,
         partition_num: PartitionNumber,
         count: u32,
+        order: ScanKeysOrder,
     ) -> Result<Vec<SubstateKey>, RuntimeError>;
 
     fn kernel_drain_substates<K: SubstateKeyContent + 'static>(
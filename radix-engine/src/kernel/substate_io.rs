@@ -7,7 +7,8 @@ use crate::kernel::call_frame::{
 use crate::kernel::heap::{Heap, HeapRemoveNodeError};
 use crate::kernel::substate_locks::SubstateLocks;
 use crate::track::interface::{
-    CallbackError, CommitableSubstateStore, IOAccess, NodeSubstates, TrackedSubstateInfo,
+    CallbackError, CommitableSubstateStore, IOAccess, NodeSubstates, ScanKeysOrder,
+    TrackedSubstateInfo,
 };
 use radix_engine_common::prelude::{NodeId, PartitionNumber};
 use radix_engine_common::types::{SortedKey, SubstateKey};
@@ -591,13 +592,20 @@ impl<'g, S: CommitableSubstateStore + 'g> SubstateIO<'g, S> {
         node_id: &NodeId,
         partition_num: PartitionNumber,
         count: u32,
+        order: ScanKeysOrder,
         handler: &mut impl IOAccessHandler<E>,
     ) -> Result<Vec<SubstateKey>, CallbackError<CallFrameScanKeysError, E>> {
         let keys = match device {
-            SubstateDevice::Heap => self.heap.scan_keys(node_id, partition_num, count),
+            SubstateDevice::Heap => {
+                let after = match order {
+                    ScanKeysOrder::TrackedFirst => None,
+                    ScanKeysOrder::Sorted { after } => after,
+                };
+                self.heap.scan_keys(node_id, partition_num, count, after)
+            }
             SubstateDevice::Store => self
                 .store
-                .scan_keys::<K, E, _>(node_id, partition_num, count, &mut |io_access| {
+                .scan_keys::<K, E, _>(node_id, partition_num, count, order, &mut |io_access| {
                     handler.on_io_access(&self.heap, io_access)
                 })
                 .map_err(|e| CallbackError::CallbackError(e))?,
@@ -646,6 +654,7 @@ impl<'g, S: CommitableSubstateStore + 'g> SubstateIO<'g, S> {
         node_id: &NodeId,
         partition_num: PartitionNumber,
         count: u32,
+        after: Option<&SortedKey>,
         handler: &mut impl IOAccessHandler<E>,
     ) -> Result<
         Vec<(SortedKey, IndexedScryptoValue)>,
@@ -653,13 +662,12 @@ impl<'g, S: CommitableSubstateStore + 'g> SubstateIO<'g, S> {
     > {
         let substates = match device {
             SubstateDevice::Heap => {
-                // This should never be triggered because sorted index store is
-                // used by consensus manager only.
-                panic!("Unexpected code path")
+                self.heap
+                    .scan_sorted_substates(node_id, partition_num, count, after)
             }
             SubstateDevice::Store => self
                 .store
-                .scan_sorted_substates(node_id, partition_num, count, &mut |io_access| {
+                .scan_sorted_substates(node_id, partition_num, count, after, &mut |io_access| {
                     handler.on_io_access(&self.heap, io_access)
                 })
                 .map_err(|e| CallbackError::CallbackError(e))?,
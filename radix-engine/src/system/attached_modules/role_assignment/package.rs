@@ -1,5 +1,5 @@
 use crate::blueprints::models::*;
-use crate::blueprints::package::PackageAuthNativeBlueprint;
+use crate::blueprints::package::{PackageAuthNativeBlueprint, PackageUpgradeNativeBlueprint};
 use crate::blueprints::util::*;
 use crate::kernel::kernel_api::{KernelApi, KernelSubstateApi};
 use crate::system::attached_modules::role_assignment::{LockOwnerRoleEvent, SetOwnerRoleEvent};
@@ -18,8 +18,8 @@ use radix_engine_interface::api::{
     ClientApi, FieldValue, GenericArgs, KVEntry, ModuleId, ACTOR_STATE_SELF,
 };
 use radix_engine_interface::blueprints::package::{
-    AuthConfig, BlueprintDefinitionInit, BlueprintType, BlueprintVersionKey, FunctionAuth,
-    MethodAuthTemplate, PackageDefinition, RoleSpecification,
+    AuthConfig, BlueprintDefinitionInit, BlueprintType, FunctionAuth, MethodAuthTemplate,
+    PackageDefinition, RoleSpecification,
 };
 use radix_engine_interface::blueprints::resource::*;
 use radix_engine_interface::schema::{
@@ -335,9 +335,11 @@ impl RoleAssignmentNativePackage {
             .get_blueprint_info(receiver, module.into())?
             .blueprint_id;
 
+        let bp_version_key =
+            PackageUpgradeNativeBlueprint::resolve_current_version_key(&blueprint_id, api.api)?;
         let auth_template = PackageAuthNativeBlueprint::get_bp_auth_template(
             blueprint_id.package_address.as_node_id(),
-            &BlueprintVersionKey::new_default(blueprint_id.blueprint_name.as_str()),
+            &bp_version_key,
             api.api,
         )?
         .method_auth;
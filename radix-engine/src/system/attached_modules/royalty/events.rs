@@ -0,0 +1,18 @@
+use crate::types::*;
+
+#[derive(ScryptoSbor, ScryptoEvent, Debug, PartialEq, Eq)]
+pub struct SetComponentRoyaltySplitEvent {
+    pub method: String,
+    pub recipients: IndexMap<GlobalAddress, Decimal>,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Debug, PartialEq, Eq)]
+pub struct LockComponentRoyaltySplitEvent {
+    pub method: String,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Debug, PartialEq, Eq)]
+pub struct ClaimComponentRoyaltyShareEvent {
+    pub recipient: GlobalAddress,
+    pub amount: Decimal,
+}
@@ -1,29 +1,40 @@
 use crate::errors::*;
 use crate::system::system_modules::costing::{apply_royalty_cost, RoyaltyRecipient};
 use crate::types::*;
-use native_sdk::resource::NativeVault;
+use native_sdk::resource::{NativeBucket, NativeVault};
+use native_sdk::runtime::Runtime;
 use radix_engine_interface::api::field_api::LockFlags;
 use radix_engine_interface::api::node_modules::royalty::*;
-use radix_engine_interface::api::{ClientApi, FieldValue, GenericArgs, KVEntry, ACTOR_STATE_SELF};
+use radix_engine_interface::api::{
+    ClientApi, FieldValue, GenericArgs, KVEntry, KeyValueEntryHandle, ACTOR_REF_SELF,
+    ACTOR_STATE_SELF,
+};
+use radix_engine_interface::blueprints::account::*;
+use radix_engine_interface::blueprints::resource::{global_caller, require, MethodAccessibility};
+use radix_engine_interface::rule;
 use radix_engine_interface::schema::{
     BlueprintEventSchemaInit, BlueprintFunctionsSchemaInit, BlueprintSchemaInit,
     FunctionSchemaInit, TypeRef,
 };
 
 // Re-export substates
-use crate::blueprints::package::PackageError;
+use crate::blueprints::package::{PackageError, MAX_ROYALTY_SPLIT_RECIPIENTS};
 use crate::internal_prelude::*;
 use crate::kernel::kernel_api::KernelApi;
-use crate::roles_template;
 use crate::system::system_callback::{SystemConfig, SystemLockData};
 use crate::system::system_callback_api::SystemCallbackObject;
 use crate::system::system_substates::FieldSubstate;
 use crate::system::system_substates::KeyValueEntrySubstate;
+use crate::{event_schema, roles_template};
 use radix_engine_interface::blueprints::package::{
     AuthConfig, BlueprintDefinitionInit, BlueprintType, FunctionAuth, MethodAuthTemplate,
     PackageDefinition,
 };
 
+use super::{
+    ClaimComponentRoyaltyShareEvent, LockComponentRoyaltySplitEvent, SetComponentRoyaltySplitEvent,
+};
+
 declare_native_blueprint_state! {
     blueprint_ident: ComponentRoyalty,
     blueprint_snake_case: component_royalty,
@@ -50,18 +61,63 @@ declare_native_blueprint_state! {
             },
             allow_ownership: false,
         },
+        method_splits: KeyValue {
+            entry_ident: MethodSplit,
+            mapped_physical_partition: ROYALTY_SPLIT_PARTITION,
+            key_type: {
+                kind: Static,
+                content_type: String,
+            },
+            value_type: {
+                kind: StaticSingleVersioned,
+            },
+            allow_ownership: false,
+        },
+        split_vaults: KeyValue {
+            entry_ident: SplitVault,
+            mapped_physical_partition: ROYALTY_SPLIT_VAULT_PARTITION,
+            key_type: {
+                kind: Static,
+                content_type: GlobalAddress,
+            },
+            value_type: {
+                kind: StaticSingleVersioned,
+            },
+            allow_ownership: true,
+        },
     }
 }
 
 pub type ComponentRoyaltyAccumulatorV1 = ComponentRoyaltySubstate;
 pub type ComponentRoyaltyMethodAmountV1 = RoyaltyAmount;
 
+#[derive(Debug, Clone, PartialEq, Eq, Default, ScryptoSbor)]
+pub struct ComponentRoyaltyMethodSplitV1 {
+    /// The share of each royalty charged for the method which accrues to each recipient.
+    /// The remainder accrues to the component royalty vault.
+    pub recipients: IndexMap<GlobalAddress, Decimal>,
+}
+
+pub type ComponentRoyaltySplitVaultV1 = Vault;
+
+/// The minor version of the Royalty V1 package
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Sbor)]
+pub enum RoyaltyV1MinorVersion {
+    Zero,
+    One,
+}
+
 pub struct RoyaltyNativePackage;
 impl RoyaltyNativePackage {
-    pub fn definition() -> PackageDefinition {
+    pub fn definition(minor_version: RoyaltyV1MinorVersion) -> PackageDefinition {
         let mut aggregator = TypeAggregator::<ScryptoCustomTypeKind>::new();
 
-        let state = ComponentRoyaltyStateSchemaInit::create_schema_init(&mut aggregator);
+        let mut state = ComponentRoyaltyStateSchemaInit::create_schema_init(&mut aggregator);
+        // The royalty split collections were only added in v1.1
+        if minor_version < RoyaltyV1MinorVersion::One {
+            state.collections.pop();
+            state.collections.pop();
+        }
 
         let mut functions = index_map_new();
         functions.insert(
@@ -116,9 +172,96 @@ impl RoyaltyNativePackage {
                 export: COMPONENT_ROYALTY_CLAIM_ROYALTIES_IDENT.to_string(),
             },
         );
+        if minor_version >= RoyaltyV1MinorVersion::One {
+            functions.insert(
+                COMPONENT_ROYALTY_SET_ROYALTY_SPLIT_IDENT.to_string(),
+                FunctionSchemaInit {
+                    receiver: Some(ReceiverInfo::normal_ref_mut()),
+                    input: TypeRef::Static(
+                        aggregator
+                            .add_child_type_and_descendents::<ComponentRoyaltySetSplitInput>(),
+                    ),
+                    output: TypeRef::Static(
+                        aggregator
+                            .add_child_type_and_descendents::<ComponentRoyaltySetSplitOutput>(),
+                    ),
+                    export: COMPONENT_ROYALTY_SET_ROYALTY_SPLIT_IDENT.to_string(),
+                },
+            );
+            functions.insert(
+                COMPONENT_ROYALTY_LOCK_ROYALTY_SPLIT_IDENT.to_string(),
+                FunctionSchemaInit {
+                    receiver: Some(ReceiverInfo::normal_ref_mut()),
+                    input: TypeRef::Static(
+                        aggregator
+                            .add_child_type_and_descendents::<ComponentRoyaltyLockSplitInput>(),
+                    ),
+                    output: TypeRef::Static(
+                        aggregator
+                            .add_child_type_and_descendents::<ComponentRoyaltyLockSplitOutput>(),
+                    ),
+                    export: COMPONENT_ROYALTY_LOCK_ROYALTY_SPLIT_IDENT.to_string(),
+                },
+            );
+            functions.insert(
+                COMPONENT_ROYALTY_CLAIM_ROYALTY_SHARE_IDENT.to_string(),
+                FunctionSchemaInit {
+                    receiver: Some(ReceiverInfo::normal_ref_mut()),
+                    input: TypeRef::Static(
+                        aggregator
+                            .add_child_type_and_descendents::<ComponentClaimRoyaltyShareInput>(),
+                    ),
+                    output: TypeRef::Static(
+                        aggregator
+                            .add_child_type_and_descendents::<ComponentClaimRoyaltyShareOutput>(),
+                    ),
+                    export: COMPONENT_ROYALTY_CLAIM_ROYALTY_SHARE_IDENT.to_string(),
+                },
+            );
+        }
+
+        let mut events = BlueprintEventSchemaInit::default();
+        if minor_version >= RoyaltyV1MinorVersion::One {
+            events = event_schema! {
+                aggregator,
+                [
+                    SetComponentRoyaltySplitEvent,
+                    LockComponentRoyaltySplitEvent,
+                    ClaimComponentRoyaltyShareEvent
+                ]
+            };
+        }
 
         let schema = generate_full_schema(aggregator);
 
+        let mut role_definition = roles_template!(
+            roles {
+                COMPONENT_ROYALTY_SETTER_ROLE => updaters: [COMPONENT_ROYALTY_SETTER_UPDATER_ROLE];
+                COMPONENT_ROYALTY_SETTER_UPDATER_ROLE => updaters: [COMPONENT_ROYALTY_SETTER_UPDATER_ROLE];
+                COMPONENT_ROYALTY_LOCKER_ROLE => updaters: [COMPONENT_ROYALTY_LOCKER_UPDATER_ROLE];
+                COMPONENT_ROYALTY_LOCKER_UPDATER_ROLE => updaters: [COMPONENT_ROYALTY_LOCKER_UPDATER_ROLE];
+                COMPONENT_ROYALTY_CLAIMER_ROLE => updaters: [COMPONENT_ROYALTY_CLAIMER_UPDATER_ROLE];
+                COMPONENT_ROYALTY_CLAIMER_UPDATER_ROLE => updaters: [COMPONENT_ROYALTY_CLAIMER_UPDATER_ROLE];
+            },
+            methods {
+                COMPONENT_ROYALTY_CLAIM_ROYALTIES_IDENT => [COMPONENT_ROYALTY_CLAIMER_ROLE];
+                COMPONENT_ROYALTY_SET_ROYALTY_IDENT => [COMPONENT_ROYALTY_SETTER_ROLE];
+                COMPONENT_ROYALTY_LOCK_ROYALTY_IDENT => [COMPONENT_ROYALTY_LOCKER_ROLE];
+            }
+        );
+        if minor_version >= RoyaltyV1MinorVersion::One {
+            role_definition.methods.extend(
+                roles_template!(
+                    methods {
+                        COMPONENT_ROYALTY_SET_ROYALTY_SPLIT_IDENT => [COMPONENT_ROYALTY_SETTER_ROLE];
+                        COMPONENT_ROYALTY_LOCK_ROYALTY_SPLIT_IDENT => [COMPONENT_ROYALTY_LOCKER_ROLE];
+                        COMPONENT_ROYALTY_CLAIM_ROYALTY_SHARE_IDENT => MethodAccessibility::Public;
+                    }
+                )
+                .methods,
+            );
+        }
+
         let blueprints = indexmap!(
             COMPONENT_ROYALTY_BLUEPRINT.to_string() => BlueprintDefinitionInit {
                 blueprint_type: BlueprintType::default(),
@@ -130,7 +273,7 @@ impl RoyaltyNativePackage {
                     generics: vec![],
                     schema,
                     state,
-                    events: BlueprintEventSchemaInit::default(),
+                    events,
                     types: BlueprintTypeSchemaInit::default(),
                     functions: BlueprintFunctionsSchemaInit {
                         functions,
@@ -141,23 +284,7 @@ impl RoyaltyNativePackage {
                 royalty_config: PackageRoyaltyConfig::default(),
                 auth_config: AuthConfig {
                     function_auth: FunctionAuth::AllowAll,
-                    method_auth: MethodAuthTemplate::StaticRoleDefinition(
-                        roles_template!(
-                            roles {
-                                COMPONENT_ROYALTY_SETTER_ROLE => updaters: [COMPONENT_ROYALTY_SETTER_UPDATER_ROLE];
-                                COMPONENT_ROYALTY_SETTER_UPDATER_ROLE => updaters: [COMPONENT_ROYALTY_SETTER_UPDATER_ROLE];
-                                COMPONENT_ROYALTY_LOCKER_ROLE => updaters: [COMPONENT_ROYALTY_LOCKER_UPDATER_ROLE];
-                                COMPONENT_ROYALTY_LOCKER_UPDATER_ROLE => updaters: [COMPONENT_ROYALTY_LOCKER_UPDATER_ROLE];
-                                COMPONENT_ROYALTY_CLAIMER_ROLE => updaters: [COMPONENT_ROYALTY_CLAIMER_UPDATER_ROLE];
-                                COMPONENT_ROYALTY_CLAIMER_UPDATER_ROLE => updaters: [COMPONENT_ROYALTY_CLAIMER_UPDATER_ROLE];
-                            },
-                            methods {
-                                COMPONENT_ROYALTY_CLAIM_ROYALTIES_IDENT => [COMPONENT_ROYALTY_CLAIMER_ROLE];
-                                COMPONENT_ROYALTY_SET_ROYALTY_IDENT => [COMPONENT_ROYALTY_SETTER_ROLE];
-                                COMPONENT_ROYALTY_LOCK_ROYALTY_IDENT => [COMPONENT_ROYALTY_LOCKER_ROLE];
-                            }
-                        ),
-                    ),
+                    method_auth: MethodAuthTemplate::StaticRoleDefinition(role_definition),
                 },
             },
         );
@@ -204,6 +331,33 @@ impl RoyaltyNativePackage {
                 let rtn = ComponentRoyaltyBlueprint::claim_royalties(api)?;
                 Ok(IndexedScryptoValue::from_typed(&rtn))
             }
+            COMPONENT_ROYALTY_SET_ROYALTY_SPLIT_IDENT => {
+                let input: ComponentRoyaltySetSplitInput = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn = ComponentRoyaltyBlueprint::set_royalty_split(
+                    input.method,
+                    input.recipients,
+                    api,
+                )?;
+
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+            COMPONENT_ROYALTY_LOCK_ROYALTY_SPLIT_IDENT => {
+                let input: ComponentRoyaltyLockSplitInput = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn = ComponentRoyaltyBlueprint::lock_royalty_split(input.method, api)?;
+
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+            COMPONENT_ROYALTY_CLAIM_ROYALTY_SHARE_IDENT => {
+                let input: ComponentClaimRoyaltyShareInput = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn = ComponentRoyaltyBlueprint::claim_royalty_share(input.recipient, api)?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
             _ => Err(RuntimeError::ApplicationError(
                 ApplicationError::ExportDoesNotExist(export_name.to_string()),
             )),
@@ -219,6 +373,19 @@ pub enum ComponentRoyaltyError {
     },
     UnexpectedDecimalComputationError,
     RoyaltyAmountIsNegative(RoyaltyAmount),
+    RoyaltySplitRecipientNotAComponent(GlobalAddress),
+    InvalidRoyaltySplitShare {
+        recipient: GlobalAddress,
+        share: Decimal,
+    },
+    RoyaltySplitSharesExceedOne(String),
+    TooManyRoyaltySplitRecipients {
+        max: usize,
+        actual: usize,
+    },
+    RoyaltySplitRecipientNotFound(GlobalAddress),
+    RoyaltySplitNotFound(String),
+    RoyaltySplitRequiresAttachedModule,
 }
 
 pub struct RoyaltyUtil;
@@ -314,6 +481,145 @@ impl RoyaltyUtil {
 
         Ok(())
     }
+
+    /// Verifies the recipients of a royalty split of the given method or blueprint.
+    pub fn verify_royalty_split(
+        target: &str,
+        recipients: &IndexMap<GlobalAddress, Decimal>,
+        is_component: bool,
+    ) -> Result<(), RuntimeError> {
+        if recipients.len() > MAX_ROYALTY_SPLIT_RECIPIENTS {
+            return Err(Self::royalty_split_error(
+                is_component,
+                PackageError::TooManyRoyaltySplitRecipients {
+                    max: MAX_ROYALTY_SPLIT_RECIPIENTS,
+                    actual: recipients.len(),
+                },
+                ComponentRoyaltyError::TooManyRoyaltySplitRecipients {
+                    max: MAX_ROYALTY_SPLIT_RECIPIENTS,
+                    actual: recipients.len(),
+                },
+            ));
+        }
+
+        let mut total_share = Decimal::ZERO;
+        for (recipient, share) in recipients {
+            if !recipient.as_node_id().is_global_component() {
+                return Err(Self::royalty_split_error(
+                    is_component,
+                    PackageError::RoyaltySplitRecipientNotAComponent(*recipient),
+                    ComponentRoyaltyError::RoyaltySplitRecipientNotAComponent(*recipient),
+                ));
+            }
+            if !share.is_positive() || *share > Decimal::ONE {
+                return Err(Self::royalty_split_error(
+                    is_component,
+                    PackageError::InvalidRoyaltySplitShare {
+                        recipient: *recipient,
+                        share: *share,
+                    },
+                    ComponentRoyaltyError::InvalidRoyaltySplitShare {
+                        recipient: *recipient,
+                        share: *share,
+                    },
+                ));
+            }
+            total_share = total_share.checked_add(*share).ok_or_else(|| {
+                Self::royalty_split_error(
+                    is_component,
+                    PackageError::UnexpectedDecimalComputationError,
+                    ComponentRoyaltyError::UnexpectedDecimalComputationError,
+                )
+            })?;
+        }
+        if total_share > Decimal::ONE {
+            return Err(Self::royalty_split_error(
+                is_component,
+                PackageError::RoyaltySplitSharesExceedOne(target.to_string()),
+                ComponentRoyaltyError::RoyaltySplitSharesExceedOne(target.to_string()),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Applies a royalty charge which is split between recipients, crediting each split vault with
+    /// its share of the charge and the remainder vault with whatever the shares do not cover.
+    pub fn apply_split_royalty_cost<Y, V>(
+        royalty_charge: RoyaltyAmount,
+        split_vaults: Vec<(NodeId, Decimal)>,
+        remainder_vault_id: NodeId,
+        to_recipient: impl Fn(NodeId) -> RoyaltyRecipient,
+        is_component: bool,
+        api: &mut Y,
+    ) -> Result<(), RuntimeError>
+    where
+        V: SystemCallbackObject,
+        Y: KernelApi<SystemConfig<V>>,
+    {
+        let (total, to_royalty_amount): (Decimal, fn(Decimal) -> RoyaltyAmount) =
+            match royalty_charge {
+                RoyaltyAmount::Xrd(amount) => (amount, RoyaltyAmount::Xrd),
+                RoyaltyAmount::Usd(amount) => (amount, RoyaltyAmount::Usd),
+                RoyaltyAmount::Free => return Ok(()),
+            };
+
+        let mut remainder = total;
+        for (vault_id, share) in split_vaults {
+            let amount = total
+                .checked_mul(share)
+                .and_then(|amount| {
+                    remainder = remainder.checked_sub(amount)?;
+                    Some(amount)
+                })
+                .ok_or_else(|| {
+                    Self::royalty_split_error(
+                        is_component,
+                        PackageError::UnexpectedDecimalComputationError,
+                        ComponentRoyaltyError::UnexpectedDecimalComputationError,
+                    )
+                })?;
+            if amount.is_positive() {
+                apply_royalty_cost(api, to_royalty_amount(amount), to_recipient(vault_id))?;
+            }
+        }
+
+        if remainder.is_positive() {
+            apply_royalty_cost(
+                api,
+                to_royalty_amount(remainder),
+                to_recipient(remainder_vault_id),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Accounts are deposited into directly when claiming a royalty share, so that anyone may
+    /// trigger the claim.
+    pub fn is_account(address: &GlobalAddress) -> bool {
+        matches!(
+            address.as_node_id().entity_type(),
+            Some(
+                EntityType::GlobalAccount
+                    | EntityType::GlobalVirtualSecp256k1Account
+                    | EntityType::GlobalVirtualEd25519Account
+                    | EntityType::GlobalVirtualSecp256r1Account
+            )
+        )
+    }
+
+    fn royalty_split_error(
+        is_component: bool,
+        package_error: PackageError,
+        component_error: ComponentRoyaltyError,
+    ) -> RuntimeError {
+        if is_component {
+            RuntimeError::ApplicationError(ApplicationError::ComponentRoyaltyError(component_error))
+        } else {
+            RuntimeError::ApplicationError(ApplicationError::PackageError(package_error))
+        }
+    }
 }
 
 pub struct ComponentRoyaltyBlueprint;
@@ -435,6 +741,127 @@ impl ComponentRoyaltyBlueprint {
         Ok(bucket)
     }
 
+    pub(crate) fn set_royalty_split<Y>(
+        method: String,
+        recipients: IndexMap<GlobalAddress, Decimal>,
+        api: &mut Y,
+    ) -> Result<(), RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        // The split collections are mapped to physical partitions, which are not moved over when
+        // the module is attached, so a split may only be set up on an attached module.
+        if !api.actor_get_node_id(ACTOR_REF_SELF)?.is_global() {
+            return Err(RuntimeError::ApplicationError(
+                ApplicationError::ComponentRoyaltyError(
+                    ComponentRoyaltyError::RoyaltySplitRequiresAttachedModule,
+                ),
+            ));
+        }
+
+        RoyaltyUtil::verify_royalty_split(&method, &recipients, true)?;
+
+        let handle = Self::open_royalty_split(&method, LockFlags::MUTABLE, api)?;
+        api.key_value_entry_set_typed(
+            handle,
+            ComponentRoyaltyMethodSplitEntryPayload::from_content_source(
+                ComponentRoyaltyMethodSplitV1 {
+                    recipients: recipients.clone(),
+                },
+            ),
+        )?;
+        api.key_value_entry_close(handle)?;
+
+        // Vaults are never removed, so that a recipient may still claim what it has accrued after
+        // being removed from a split.
+        for recipient in recipients.keys() {
+            let handle = Self::open_split_vault(recipient, LockFlags::MUTABLE, api)?;
+            if api
+                .key_value_entry_get_typed::<ComponentRoyaltySplitVaultEntryPayload>(handle)?
+                .is_none()
+            {
+                let vault = Vault::create(XRD, api)?;
+                api.key_value_entry_set_typed(
+                    handle,
+                    ComponentRoyaltySplitVaultEntryPayload::from_content_source(vault),
+                )?;
+            }
+            api.key_value_entry_close(handle)?;
+        }
+
+        Runtime::emit_event(api, SetComponentRoyaltySplitEvent { method, recipients })?;
+
+        Ok(())
+    }
+
+    pub(crate) fn lock_royalty_split<Y>(method: String, api: &mut Y) -> Result<(), RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let handle = Self::open_royalty_split(&method, LockFlags::MUTABLE, api)?;
+        // Locking a missing split would prevent a split from ever being set up
+        if api
+            .key_value_entry_get_typed::<ComponentRoyaltyMethodSplitEntryPayload>(handle)?
+            .is_none()
+        {
+            return Err(RuntimeError::ApplicationError(
+                ApplicationError::ComponentRoyaltyError(
+                    ComponentRoyaltyError::RoyaltySplitNotFound(method),
+                ),
+            ));
+        }
+        api.key_value_entry_lock(handle)?;
+        api.key_value_entry_close(handle)?;
+
+        Runtime::emit_event(api, LockComponentRoyaltySplitEvent { method })?;
+
+        Ok(())
+    }
+
+    pub(crate) fn claim_royalty_share<Y>(
+        recipient: GlobalAddress,
+        api: &mut Y,
+    ) -> Result<Option<Bucket>, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        // Any recipient other than an account must claim its share itself
+        let is_account = RoyaltyUtil::is_account(&recipient);
+        if !is_account {
+            Runtime::assert_access_rule(rule!(require(global_caller(recipient))), api)?;
+        }
+
+        let handle = Self::open_split_vault(&recipient, LockFlags::read_only(), api)?;
+        let mut vault = api
+            .key_value_entry_get_typed::<ComponentRoyaltySplitVaultEntryPayload>(handle)?
+            .map(|vault| vault.into_latest())
+            .ok_or(RuntimeError::ApplicationError(
+                ApplicationError::ComponentRoyaltyError(
+                    ComponentRoyaltyError::RoyaltySplitRecipientNotFound(recipient),
+                ),
+            ))?;
+        let bucket = vault.take_all(api)?;
+        api.key_value_entry_close(handle)?;
+
+        let amount = bucket.amount(api)?;
+        Runtime::emit_event(api, ClaimComponentRoyaltyShareEvent { recipient, amount })?;
+
+        if is_account {
+            api.call_method(
+                recipient.as_node_id(),
+                ACCOUNT_TRY_DEPOSIT_OR_ABORT_IDENT,
+                scrypto_encode(&AccountTryDepositOrAbortInput {
+                    bucket,
+                    authorized_depositor_badge: None,
+                })
+                .unwrap(),
+            )?;
+            Ok(None)
+        } else {
+            Ok(Some(bucket))
+        }
+    }
+
     pub fn charge_component_royalty<Y, V>(
         receiver: &NodeId,
         ident: &str,
@@ -494,10 +921,24 @@ impl ComponentRoyaltyBlueprint {
             let vault_id = component_royalty.royalty_vault.0;
             let component_address = ComponentAddress::new_or_panic(receiver.0);
 
-            apply_royalty_cost(
-                api,
+            let mut split_vaults = Vec::new();
+            if let Some(split) = Self::read_royalty_split(receiver, ident, api)? {
+                for (recipient, share) in split.recipients {
+                    // A recipient without a vault has never been set up, so its share stays with
+                    // the component owner.
+                    if let Some(vault_id) = Self::read_split_vault_id(receiver, &recipient, api)? {
+                        split_vaults.push((vault_id, share));
+                    }
+                }
+            }
+
+            RoyaltyUtil::apply_split_royalty_cost(
                 royalty_charge,
-                RoyaltyRecipient::Component(component_address, vault_id.into()),
+                split_vaults,
+                vault_id.into(),
+                |vault_id| RoyaltyRecipient::Component(component_address, vault_id),
+                true,
+                api,
             )?;
         }
 
@@ -505,4 +946,90 @@ impl ComponentRoyaltyBlueprint {
 
         Ok(())
     }
+
+    fn read_royalty_split<Y, V>(
+        receiver: &NodeId,
+        ident: &str,
+        api: &mut Y,
+    ) -> Result<Option<ComponentRoyaltyMethodSplitV1>, RuntimeError>
+    where
+        V: SystemCallbackObject,
+        Y: KernelApi<SystemConfig<V>>,
+    {
+        let handle = api.kernel_open_substate_with_default(
+            receiver,
+            ROYALTY_SPLIT_PARTITION,
+            &SubstateKey::Map(scrypto_encode(ident).unwrap()),
+            LockFlags::read_only(),
+            Some(|| {
+                let kv_entry = KeyValueEntrySubstate::<()>::default();
+                IndexedScryptoValue::from_typed(&kv_entry)
+            }),
+            SystemLockData::default(),
+        )?;
+        let substate: KeyValueEntrySubstate<ComponentRoyaltyMethodSplitEntryPayload> =
+            api.kernel_read_substate(handle)?.as_typed().unwrap();
+        api.kernel_close_substate(handle)?;
+
+        Ok(substate.into_value().map(|split| split.into_latest()))
+    }
+
+    fn read_split_vault_id<Y, V>(
+        receiver: &NodeId,
+        recipient: &GlobalAddress,
+        api: &mut Y,
+    ) -> Result<Option<NodeId>, RuntimeError>
+    where
+        V: SystemCallbackObject,
+        Y: KernelApi<SystemConfig<V>>,
+    {
+        let handle = api.kernel_open_substate_with_default(
+            receiver,
+            ROYALTY_SPLIT_VAULT_PARTITION,
+            &SubstateKey::Map(scrypto_encode(recipient).unwrap()),
+            LockFlags::read_only(),
+            Some(|| {
+                let kv_entry = KeyValueEntrySubstate::<()>::default();
+                IndexedScryptoValue::from_typed(&kv_entry)
+            }),
+            SystemLockData::default(),
+        )?;
+        let substate: KeyValueEntrySubstate<ComponentRoyaltySplitVaultEntryPayload> =
+            api.kernel_read_substate(handle)?.as_typed().unwrap();
+        api.kernel_close_substate(handle)?;
+
+        Ok(substate.into_value().map(|vault| vault.into_latest().0 .0))
+    }
+
+    fn open_royalty_split<Y>(
+        method: &str,
+        flags: LockFlags,
+        api: &mut Y,
+    ) -> Result<KeyValueEntryHandle, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        api.actor_open_key_value_entry(
+            ACTOR_STATE_SELF,
+            ComponentRoyaltyCollection::MethodSplitKeyValue.collection_index(),
+            &scrypto_encode(method).unwrap(),
+            flags,
+        )
+    }
+
+    fn open_split_vault<Y>(
+        recipient: &GlobalAddress,
+        flags: LockFlags,
+        api: &mut Y,
+    ) -> Result<KeyValueEntryHandle, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        api.actor_open_key_value_entry(
+            ACTOR_STATE_SELF,
+            ComponentRoyaltyCollection::SplitVaultKeyValue.collection_index(),
+            &scrypto_encode(recipient).unwrap(),
+            flags,
+        )
+    }
 }
@@ -1,13 +1,19 @@
 use crate::blueprints::access_controller::*;
-use crate::blueprints::account::{AccountNativePackage, AccountOwnerBadgeData};
-use crate::blueprints::consensus_manager::ConsensusManagerNativePackage;
-use crate::blueprints::identity::{IdentityNativePackage, IdentityOwnerBadgeData};
+use crate::blueprints::account::{
+    AccountNativePackage, AccountOwnerBadgeData, AccountV1MinorVersion,
+};
+use crate::blueprints::consensus_manager::{
+    ConsensusManagerNativePackage, ConsensusManagerV1MinorVersion,
+};
+use crate::blueprints::identity::{
+    IdentityNativePackage, IdentityOwnerBadgeData, IdentityV1MinorVersion,
+};
 use crate::blueprints::package::{
     create_package_partition_substates, PackageCollection, PackageNativePackage,
-    PackageOwnerBadgeData, SystemInstruction,
+    PackageOwnerBadgeData, PackageV1MinorVersion, SystemInstruction,
 };
 use crate::blueprints::pool::v1::package::{PoolNativePackage, PoolV1MinorVersion};
-use crate::blueprints::resource::ResourceNativePackage;
+use crate::blueprints::resource::{ResourceNativePackage, ResourceV1MinorVersion};
 use crate::blueprints::test_utils::TestUtilsNativePackage;
 use crate::blueprints::transaction_processor::TransactionProcessorNativePackage;
 use crate::blueprints::transaction_tracker::{
@@ -16,7 +22,7 @@ use crate::blueprints::transaction_tracker::{
 use crate::internal_prelude::*;
 use crate::system::attached_modules::metadata::MetadataNativePackage;
 use crate::system::attached_modules::role_assignment::RoleAssignmentNativePackage;
-use crate::system::attached_modules::royalty::RoyaltyNativePackage;
+use crate::system::attached_modules::royalty::{RoyaltyNativePackage, RoyaltyV1MinorVersion};
 use crate::system::system_callback_api::SystemCallbackObject;
 use crate::system::system_db_reader::SystemDatabaseReader;
 use crate::system::type_info::TypeInfoSubstate;
@@ -29,6 +35,7 @@ use crate::transaction::{
     SubstateSchemaMapper, SubstateSystemStructures, TransactionOutcome, TransactionReceipt,
     TransactionResult,
 };
+use crate::utils::generate_secp256r1_signatures_state_updates;
 use crate::vm::VmVersion;
 use lazy_static::lazy_static;
 use radix_engine_common::crypto::Secp256k1PublicKey;
@@ -483,6 +490,38 @@ where
 
         receipt
     }
+
+    /// Executes the protocol update which introduces secp256r1 signatures. The secp256r1 signature
+    /// virtual badge is created by a system transaction, after which the account and identity
+    /// packages are flashed to definitions which depend on it.
+    pub fn update_to_secp256r1_signatures(&mut self) -> TransactionReceipt {
+        let transaction = create_secp256r1_signature_virtual_badge_transaction();
+
+        let receipt = execute_transaction(
+            self.substate_db,
+            self.vm.clone(),
+            &CostingParameters::default(),
+            &ExecutionConfig::for_system_transaction(self.network_definition.clone())
+                .with_kernel_trace(self.trace),
+            &transaction
+                .prepare()
+                .expect("Expected secp256r1 signatures transaction to be preparable")
+                .get_executable(btreeset![AuthAddresses::system_role()]),
+        );
+
+        let commit_result = receipt.expect_commit(true);
+        self.substate_db.commit(
+            &commit_result
+                .state_updates
+                .create_database_updates::<SpreadPrefixKeyMapper>(),
+        );
+
+        let state_updates = generate_secp256r1_signatures_state_updates(self.substate_db);
+        self.substate_db
+            .commit(&state_updates.create_database_updates::<SpreadPrefixKeyMapper>());
+
+        receipt
+    }
 }
 
 pub fn create_system_bootstrap_flash(
@@ -490,7 +529,7 @@ pub fn create_system_bootstrap_flash(
     let package_flashes = [
         (
             PACKAGE_PACKAGE,
-            PackageNativePackage::definition(),
+            PackageNativePackage::definition(PackageV1MinorVersion::Zero),
             PACKAGE_CODE_ID,
             metadata_init! {
                 "name" => "Package Package".to_owned(), locked;
@@ -536,7 +575,7 @@ pub fn create_system_bootstrap_flash(
         ),
         (
             RESOURCE_PACKAGE,
-            ResourceNativePackage::definition(),
+            ResourceNativePackage::definition(ResourceV1MinorVersion::Zero),
             RESOURCE_CODE_ID,
             metadata_init! {
                 "name" => "Resource Package".to_owned(), locked;
@@ -546,7 +585,7 @@ pub fn create_system_bootstrap_flash(
         ),
         (
             ROYALTY_MODULE_PACKAGE,
-            RoyaltyNativePackage::definition(),
+            RoyaltyNativePackage::definition(RoyaltyV1MinorVersion::Zero),
             ROYALTY_CODE_ID,
             metadata_init! {
                 "name" => "Royalty Package".to_owned(), locked;
@@ -940,7 +979,7 @@ pub fn create_system_bootstrap_transaction(
             function_name: PACKAGE_PUBLISH_NATIVE_IDENT.to_string(),
             args: to_manifest_value_and_unwrap!(&PackagePublishNativeManifestInput {
                 package_address: Some(id_allocator.new_address_reservation_id()),
-                definition: IdentityNativePackage::definition(),
+                definition: IdentityNativePackage::definition(IdentityV1MinorVersion::Zero),
                 native_package_code_id: IDENTITY_CODE_ID,
                 metadata: metadata_init! {
                     "name" => "Identity Package".to_owned(), locked;
@@ -962,7 +1001,7 @@ pub fn create_system_bootstrap_transaction(
             function_name: PACKAGE_PUBLISH_NATIVE_IDENT.to_string(),
             args: to_manifest_value_and_unwrap!(&PackagePublishNativeManifestInput {
                 package_address: Some(id_allocator.new_address_reservation_id()),
-                definition: ConsensusManagerNativePackage::definition(),
+                definition: ConsensusManagerNativePackage::definition(ConsensusManagerV1MinorVersion::Zero),
                 native_package_code_id: CONSENSUS_MANAGER_CODE_ID,
                 metadata: metadata_init! {
                     "name" => "Consensus Manager Package".to_owned(), locked;
@@ -1021,7 +1060,7 @@ pub fn create_system_bootstrap_transaction(
             function_name: PACKAGE_PUBLISH_NATIVE_IDENT.to_string(),
             args: to_manifest_value_and_unwrap!(&PackagePublishNativeManifestInput {
                 package_address: Some(id_allocator.new_address_reservation_id()),
-                definition: AccountNativePackage::definition(),
+                definition: AccountNativePackage::definition(AccountV1MinorVersion::Zero),
                 native_package_code_id: ACCOUNT_CODE_ID,
                 metadata: metadata_init! {
                     "name" => "Account Package".to_owned(), locked;
@@ -1043,7 +1082,7 @@ pub fn create_system_bootstrap_transaction(
             function_name: PACKAGE_PUBLISH_NATIVE_IDENT.to_string(),
             args: to_manifest_value_and_unwrap!(&PackagePublishNativeManifestInput {
                 package_address: Some(id_allocator.new_address_reservation_id()),
-                definition: AccessControllerNativePackage::definition(),
+                definition: AccessControllerNativePackage::definition(AccessControllerV1MinorVersion::Zero),
                 metadata: metadata_init! {
                     "name" => "Access Controller Package".to_owned(), locked;
                     "description" => "A native package that defines the logic of access controller components.".to_owned(), locked;
@@ -1440,3 +1479,50 @@ pub fn create_genesis_wrap_up_transaction() -> SystemTransactionV1 {
         hash_for_execution: hash(format!("Genesis Wrap Up")),
     }
 }
+
+/// Creates the system transaction of the protocol update which introduces secp256r1 signatures.
+/// It creates the resource of the secp256r1 virtual signature badges, which must exist before the
+/// account and identity blueprints are updated to depend on it.
+pub fn create_secp256r1_signature_virtual_badge_transaction() -> SystemTransactionV1 {
+    let mut id_allocator = ManifestIdAllocator::new();
+    let mut instructions = Vec::new();
+    let mut pre_allocated_addresses = vec![];
+
+    pre_allocated_addresses.push((
+        BlueprintId::new(&RESOURCE_PACKAGE, NON_FUNGIBLE_RESOURCE_MANAGER_BLUEPRINT),
+        GlobalAddress::from(SECP256R1_SIGNATURE_VIRTUAL_BADGE),
+    ));
+    instructions.push(InstructionV1::CallFunction {
+        package_address: RESOURCE_PACKAGE.into(),
+        blueprint_name: NON_FUNGIBLE_RESOURCE_MANAGER_BLUEPRINT.to_string(),
+        function_name: NON_FUNGIBLE_RESOURCE_MANAGER_CREATE_IDENT.to_string(),
+        args: to_manifest_value_and_unwrap!(
+            &NonFungibleResourceManagerCreateManifestInput {
+                owner_role: OwnerRole::Fixed(rule!(require(AuthAddresses::system_role()))),
+                id_type: NonFungibleIdType::Bytes,
+                track_total_supply: false,
+                non_fungible_schema: NonFungibleDataSchema::new_local_without_self_package_replacement::<()>(),
+                resource_roles: NonFungibleResourceRoles::default(),
+                metadata: metadata! {
+                    init {
+                        "name" => "ECDSA secp256r1 Virtual Badges".to_owned(), locked;
+                        "description" => "Virtual badges generated automatically by the Radix system to represent ECDSA secp256r1 signatures applied to transactions. These badges cease to exist at the end of their transaction.".to_owned(), locked;
+                        "tags" => vec!["badge".to_owned()], locked;
+                        "icon_url" => UncheckedUrl::of("https://assets.radixdlt.com/icons/icon-ecdsa_secp256r1_signature_virtual_badge.png".to_owned()), locked;
+                    }
+                },
+                address_reservation: Some(id_allocator.new_address_reservation_id()),
+            }
+        ),
+    });
+
+    SystemTransactionV1 {
+        instructions: InstructionsV1(instructions),
+        pre_allocated_addresses: pre_allocated_addresses
+            .into_iter()
+            .map(|allocation_pair| allocation_pair.into())
+            .collect(),
+        blobs: BlobsV1 { blobs: vec![] },
+        hash_for_execution: hash(format!("Secp256r1 Signatures Protocol Update")),
+    }
+}
@@ -47,6 +47,8 @@ impl ApplicationChecker for ComponentRoyaltyDatabaseChecker {
 
                 self.check_royalty_amount(value, location);
             }
+            ComponentRoyaltyCollection::MethodSplitKeyValue
+            | ComponentRoyaltyCollection::SplitVaultKeyValue => {}
         }
     }
 
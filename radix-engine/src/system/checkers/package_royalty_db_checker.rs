@@ -38,8 +38,11 @@ where
         key: &Vec<u8>,
         value: &Vec<u8>,
     ) {
-        // Ignore if the module id is not the royalty module.
-        if module_id != ModuleId::Royalty {
+        // Ignore if this is not the main module of a package, the royalty module of a component
+        // has collections of its own.
+        if module_id != ModuleId::Main
+            || info.blueprint_id != BlueprintId::new(&PACKAGE_PACKAGE, PACKAGE_BLUEPRINT)
+        {
             return;
         }
 
@@ -79,6 +79,9 @@ pub fn get_global_entity_type(blueprint_id: &BlueprintId) -> EntityType {
         (POOL_PACKAGE, ONE_RESOURCE_POOL_BLUEPRINT_IDENT) => EntityType::GlobalOneResourcePool,
         (POOL_PACKAGE, TWO_RESOURCE_POOL_BLUEPRINT_IDENT) => EntityType::GlobalTwoResourcePool,
         (POOL_PACKAGE, MULTI_RESOURCE_POOL_BLUEPRINT_IDENT) => EntityType::GlobalMultiResourcePool,
+        (POOL_PACKAGE, CONSTANT_PRODUCT_POOL_BLUEPRINT_IDENT) => {
+            EntityType::GlobalConstantProductPool
+        }
         _ => EntityType::GlobalGenericComponent,
     }
 }
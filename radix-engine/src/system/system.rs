@@ -1,6 +1,8 @@
 use super::id_allocation::IDAllocation;
 use super::system_modules::costing::ExecutionCostingEntry;
-use crate::blueprints::package::PackageBlueprintVersionDefinitionEntrySubstate;
+use crate::blueprints::package::{
+    PackageBlueprintVersionDefinitionEntrySubstate, PackageUpgradeNativeBlueprint,
+};
 use crate::blueprints::resource::fungible_vault::LockFeeEvent;
 use crate::errors::{
     ApplicationError, CannotGlobalizeError, CreateObjectError, InvalidDropAccess,
@@ -25,7 +27,7 @@ use crate::system::system_type_checker::{
     BlueprintTypeTarget, KVStoreTypeTarget, SchemaValidationMeta, SystemMapper,
 };
 use crate::system::type_info::{TypeInfoBlueprint, TypeInfoSubstate};
-use crate::track::interface::NodeSubstates;
+use crate::track::interface::{NodeSubstates, ScanKeysOrder};
 use crate::types::*;
 use radix_engine_interface::api::actor_api::EventFlags;
 use radix_engine_interface::api::actor_index_api::ClientActorIndexApi;
@@ -313,7 +315,8 @@ where
         &mut self,
         blueprint_id: BlueprintId,
     ) -> Result<Rc<BlueprintDefinition>, RuntimeError> {
-        let bp_version_key = BlueprintVersionKey::new_default(blueprint_id.blueprint_name);
+        let bp_version_key =
+            PackageUpgradeNativeBlueprint::resolve_current_version_key(&blueprint_id, self.api)?;
         Ok(self.load_blueprint_definition(blueprint_id.package_address, &bp_version_key)?)
     }
 
@@ -614,6 +617,36 @@ where
         Ok(())
     }
 
+    fn get_key_value_store_info(
+        &mut self,
+        node_id: &NodeId,
+    ) -> Result<KeyValueStoreInfo, RuntimeError> {
+        match TypeInfoBlueprint::get_type(node_id, self.api)? {
+            TypeInfoSubstate::KeyValueStore(info) => Ok(info),
+            _ => Err(RuntimeError::SystemError(SystemError::NotAKeyValueStore)),
+        }
+    }
+
+    /// Internal, the node must be a key value store
+    fn read_key_value_store_entry(
+        &mut self,
+        node_id: &NodeId,
+        key: &SubstateKey,
+    ) -> Result<Option<ScryptoValue>, RuntimeError> {
+        let handle = self.api.kernel_open_substate(
+            node_id,
+            MAIN_BASE_PARTITION,
+            key,
+            LockFlags::read_only(),
+            SystemLockData::KeyValueEntry(KeyValueEntryLockData::Read),
+        )?;
+        let kv_entry: KeyValueEntrySubstate<ScryptoValue> =
+            self.api.kernel_read_substate(handle)?.as_typed().unwrap();
+        self.api.kernel_close_substate(handle)?;
+
+        Ok(kv_entry.into_value())
+    }
+
     /// Internal, handle must be checked or from trusted sources
     fn key_value_entry_remove_and_close_substate(
         &mut self,
@@ -1881,6 +1914,90 @@ where
         let handle = self.key_value_store_open_entry(node_id, key, LockFlags::MUTABLE)?;
         self.key_value_entry_remove_and_close_substate(handle)
     }
+
+    // Costing through kernel
+    #[trace_resources]
+    fn key_value_store_scan(
+        &mut self,
+        node_id: &NodeId,
+        after: Option<Vec<u8>>,
+        limit: u32,
+    ) -> Result<(Vec<(Vec<u8>, Vec<u8>)>, Option<Vec<u8>>), RuntimeError> {
+        self.get_key_value_store_info(node_id)?;
+
+        if limit == 0 {
+            return Ok((Vec::new(), after));
+        }
+
+        // Entries which have been read but hold no value are skipped, so keep scanning until one
+        // more entry than requested is found, which tells whether any entries are left
+        let batch_size = limit.saturating_add(1);
+        let mut cursor = after.map(SubstateKey::Map);
+        let mut entries: Vec<(Vec<u8>, Vec<u8>)> = Vec::new();
+        loop {
+            let keys = self.api.kernel_scan_keys::<MapKey>(
+                node_id,
+                MAIN_BASE_PARTITION,
+                batch_size,
+                ScanKeysOrder::Sorted {
+                    after: cursor.as_ref(),
+                },
+            )?;
+            let is_last_batch = keys.len() < batch_size as usize;
+
+            for key in keys {
+                if let Some(value) = self.read_key_value_store_entry(node_id, &key)? {
+                    if entries.len() == limit as usize {
+                        let next_cursor = entries.last().map(|(key, _)| key.clone());
+                        return Ok((entries, next_cursor));
+                    }
+                    entries.push((
+                        key.for_map().unwrap().clone(),
+                        scrypto_encode(&value).unwrap(),
+                    ));
+                }
+                cursor = Some(key);
+            }
+
+            if is_last_batch {
+                return Ok((entries, None));
+            }
+        }
+    }
+
+    // Costing through kernel
+    #[trace_resources]
+    fn key_value_store_count(&mut self, node_id: &NodeId, limit: u32) -> Result<u32, RuntimeError> {
+        self.get_key_value_store_info(node_id)?;
+
+        let mut count = 0u32;
+        let mut cursor = None;
+        while count < limit {
+            let batch_size = limit - count;
+            let keys = self.api.kernel_scan_keys::<MapKey>(
+                node_id,
+                MAIN_BASE_PARTITION,
+                batch_size,
+                ScanKeysOrder::Sorted {
+                    after: cursor.as_ref(),
+                },
+            )?;
+            let is_last_batch = keys.len() < batch_size as usize;
+
+            for key in keys {
+                if self.read_key_value_store_entry(node_id, &key)?.is_some() {
+                    count += 1;
+                }
+                cursor = Some(key);
+            }
+
+            if is_last_batch {
+                break;
+            }
+        }
+
+        Ok(count)
+    }
 }
 
 #[cfg_attr(
@@ -1961,6 +2078,41 @@ where
         Ok(rtn)
     }
 
+    // Costing through kernel
+    fn actor_index_get(
+        &mut self,
+        object_handle: ActorStateHandle,
+        collection_index: CollectionIndex,
+        key: Vec<u8>,
+    ) -> Result<Option<Vec<u8>>, RuntimeError> {
+        let actor_object_type: ActorStateRef = object_handle.try_into()?;
+
+        let (node_id, _info, partition_num) = self.get_actor_collection_partition_info(
+            actor_object_type,
+            collection_index,
+            &BlueprintPartitionType::IndexCollection,
+        )?;
+
+        // A missing entry is read as a unit value, which never decodes as an index entry
+        let handle = self.api.kernel_open_substate_with_default(
+            &node_id,
+            partition_num,
+            &SubstateKey::Map(key),
+            LockFlags::read_only(),
+            Some(|| IndexedScryptoValue::from_typed(&())),
+            SystemLockData::default(),
+        )?;
+        let rtn = self
+            .api
+            .kernel_read_substate(handle)?
+            .as_typed::<IndexEntrySubstate<ScryptoValue>>()
+            .ok()
+            .map(|value| scrypto_encode(value.value()).unwrap());
+        self.api.kernel_close_substate(handle)?;
+
+        Ok(rtn)
+    }
+
     // Costing through kernel
     fn actor_index_scan_keys(
         &mut self,
@@ -1978,7 +2130,12 @@ where
 
         let substates = self
             .api
-            .kernel_scan_keys::<MapKey>(&node_id, partition_num, limit)?
+            .kernel_scan_keys::<MapKey>(
+                &node_id,
+                partition_num,
+                limit,
+                ScanKeysOrder::TrackedFirst,
+            )?
             .into_iter()
             .map(|key| key.into_map())
             .collect();
@@ -1986,6 +2143,51 @@ where
         Ok(substates)
     }
 
+    // Costing through kernel
+    fn actor_index_scan_after(
+        &mut self,
+        object_handle: ActorStateHandle,
+        collection_index: CollectionIndex,
+        after: Option<Vec<u8>>,
+        limit: u32,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, RuntimeError> {
+        let actor_object_type: ActorStateRef = object_handle.try_into()?;
+
+        let (node_id, _info, partition_num) = self.get_actor_collection_partition_info(
+            actor_object_type,
+            collection_index,
+            &BlueprintPartitionType::IndexCollection,
+        )?;
+
+        let after = after.map(SubstateKey::Map);
+        let keys = self.api.kernel_scan_keys::<MapKey>(
+            &node_id,
+            partition_num,
+            limit,
+            ScanKeysOrder::Sorted {
+                after: after.as_ref(),
+            },
+        )?;
+
+        let mut entries = Vec::new();
+        for key in keys {
+            let handle = self.api.kernel_open_substate(
+                &node_id,
+                partition_num,
+                &key,
+                LockFlags::read_only(),
+                SystemLockData::default(),
+            )?;
+            let value: IndexEntrySubstate<ScryptoValue> =
+                self.api.kernel_read_substate(handle)?.as_typed().unwrap();
+            self.api.kernel_close_substate(handle)?;
+
+            entries.push((key.into_map(), scrypto_encode(value.value()).unwrap()));
+        }
+
+        Ok(entries)
+    }
+
     // Costing through kernel
     fn actor_index_drain(
         &mut self,
@@ -2123,7 +2325,39 @@ where
 
         let substates = self
             .api
-            .kernel_scan_sorted_substates(&node_id, partition_num, limit)?
+            .kernel_scan_sorted_substates(&node_id, partition_num, limit, None)?
+            .into_iter()
+            .map(|(key, value)| {
+                let value: SortedIndexEntrySubstate<ScryptoValue> = value.as_typed().unwrap();
+                let value = scrypto_encode(value.value()).unwrap();
+
+                (key, value)
+            })
+            .collect();
+
+        Ok(substates)
+    }
+
+    // Costing through kernel
+    #[trace_resources]
+    fn actor_sorted_index_scan_after(
+        &mut self,
+        object_handle: ActorStateHandle,
+        collection_index: CollectionIndex,
+        after: Option<SortedKey>,
+        limit: u32,
+    ) -> Result<Vec<(SortedKey, Vec<u8>)>, RuntimeError> {
+        let actor_object_type: ActorStateRef = object_handle.try_into()?;
+
+        let (node_id, _info, partition_num) = self.get_actor_collection_partition_info(
+            actor_object_type,
+            collection_index,
+            &BlueprintPartitionType::SortedIndexCollection,
+        )?;
+
+        let substates = self
+            .api
+            .kernel_scan_sorted_substates(&node_id, partition_num, limit, after.as_ref())?
             .into_iter()
             .map(|(key, value)| {
                 let value: SortedIndexEntrySubstate<ScryptoValue> = value.as_typed().unwrap();
@@ -2939,6 +3173,59 @@ where
             .apply_execution_cost(ExecutionCostingEntry::Keccak256Hash { size: data.len() })?;
         Ok(keccak256_hash(data))
     }
+
+    #[trace_resources(log=data.len())]
+    fn blake2b_256_hash(&mut self, data: &[u8]) -> Result<Hash, RuntimeError> {
+        self.api
+            .kernel_get_system()
+            .modules
+            .apply_execution_cost(ExecutionCostingEntry::Blake2b256Hash { size: data.len() })?;
+        Ok(blake2b_256_hash(data))
+    }
+
+    #[trace_resources(log=message.len())]
+    fn ed25519_verify(
+        &mut self,
+        message: &[u8],
+        public_key: &Ed25519PublicKey,
+        signature: &Ed25519Signature,
+    ) -> Result<u32, RuntimeError> {
+        self.api.kernel_get_system().modules.apply_execution_cost(
+            ExecutionCostingEntry::Ed25519Verify {
+                size: message.len(),
+            },
+        )?;
+        Ok(verify_ed25519_message(message, public_key, signature) as u32)
+    }
+
+    #[trace_resources]
+    fn secp256k1_ecdsa_verify(
+        &mut self,
+        hash: &Hash,
+        public_key: &Secp256k1PublicKey,
+        signature: &Secp256k1Signature,
+    ) -> Result<u32, RuntimeError> {
+        self.api
+            .kernel_get_system()
+            .modules
+            .apply_execution_cost(ExecutionCostingEntry::Secp256k1EcdsaVerify)?;
+        Ok(verify_secp256k1(hash, public_key, signature) as u32)
+    }
+
+    #[trace_resources]
+    fn secp256k1_ecdsa_verify_and_key_recover(
+        &mut self,
+        hash: &Hash,
+        signature: &Secp256k1Signature,
+    ) -> Result<Secp256k1PublicKey, RuntimeError> {
+        self.api
+            .kernel_get_system()
+            .modules
+            .apply_execution_cost(ExecutionCostingEntry::Secp256k1EcdsaVerifyAndKeyRecover)?;
+        recover_secp256k1(hash, signature).ok_or(RuntimeError::SystemError(
+            SystemError::Secp256k1KeyRecoveryError,
+        ))
+    }
 }
 
 #[cfg_attr(
@@ -3080,9 +3367,10 @@ where
         node_id: &NodeId,
         partition_num: PartitionNumber,
         limit: u32,
+        after: Option<&SortedKey>,
     ) -> Result<Vec<(SortedKey, IndexedScryptoValue)>, RuntimeError> {
         self.api
-            .kernel_scan_sorted_substates(node_id, partition_num, limit)
+            .kernel_scan_sorted_substates(node_id, partition_num, limit, after)
     }
 
     fn kernel_scan_keys<K: SubstateKeyContent + 'static>(
@@ -3090,9 +3378,10 @@ where
         node_id: &NodeId,
         partition_num: PartitionNumber,
         limit: u32,
+        order: ScanKeysOrder,
     ) -> Result<Vec<SubstateKey>, RuntimeError> {
         self.api
-            .kernel_scan_keys::<K>(node_id, partition_num, limit)
+            .kernel_scan_keys::<K>(node_id, partition_num, limit, order)
     }
 
     fn kernel_drain_substates<K: SubstateKeyContent + 'static>(
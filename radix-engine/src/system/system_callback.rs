@@ -1,8 +1,13 @@
 use super::type_info::{TypeInfoBlueprint, TypeInfoSubstate};
 use crate::blueprints::account::ACCOUNT_CREATE_VIRTUAL_ED25519_ID;
 use crate::blueprints::account::ACCOUNT_CREATE_VIRTUAL_SECP256K1_ID;
+use crate::blueprints::account::ACCOUNT_CREATE_VIRTUAL_SECP256R1_ID;
 use crate::blueprints::identity::IDENTITY_CREATE_VIRTUAL_ED25519_ID;
 use crate::blueprints::identity::IDENTITY_CREATE_VIRTUAL_SECP256K1_ID;
+use crate::blueprints::identity::IDENTITY_CREATE_VIRTUAL_SECP256R1_ID;
+use crate::blueprints::package::{
+    PackageBlueprintVersionDependenciesEntrySubstate, PackageUpgradeNativeBlueprint,
+};
 use crate::blueprints::transaction_processor::TransactionProcessorRunInputEfficientEncodable;
 use crate::errors::*;
 use crate::kernel::call_frame::CallFrameMessage;
@@ -43,6 +48,14 @@ use radix_engine_interface::hooks::OnVirtualizeOutput;
 use radix_engine_interface::schema::RefTypes;
 use transaction::model::PreAllocatedAddress;
 
+pub const BOOT_LOADER_SYSTEM_SUBSTATE_FIELD_KEY: FieldKey = 1u8;
+
+/// Boot Loader state for the System Layer
+#[derive(Debug, Clone, PartialEq, Eq, Sbor)]
+pub enum SystemBoot {
+    V1 { package_blueprint_upgrades: bool },
+}
+
 #[derive(Clone)]
 pub enum SystemLockData {
     KeyValueEntry(KeyValueEntryLockData),
@@ -96,9 +109,47 @@ pub struct SystemConfig<C: SystemCallbackObject> {
     pub blueprint_cache: NonIterMap<CanonicalBlueprintId, Rc<BlueprintDefinition>>,
     pub schema_cache: NonIterMap<SchemaHash, Rc<VersionedScryptoSchema>>,
     pub auth_cache: NonIterMap<CanonicalBlueprintId, AuthConfig>,
+    pub blueprint_version_cache: NonIterMap<BlueprintId, BlueprintVersion>,
+    /// Whether blueprints are resolved through their upgrade configs, as read from the boot loader
+    pub package_blueprint_upgrades: bool,
     pub modules: SystemModuleMixer,
 }
 
+impl<C: SystemCallbackObject> SystemConfig<C> {
+    fn blueprint_depends_on<Y>(
+        blueprint_id: &BlueprintId,
+        address: &GlobalAddress,
+        api: &mut Y,
+    ) -> Result<bool, RuntimeError>
+    where
+        Y: KernelApi<Self>,
+    {
+        let key = PackageUpgradeNativeBlueprint::resolve_current_version_key(blueprint_id, api)?;
+
+        let handle = api.kernel_open_substate_with_default(
+            blueprint_id.package_address.as_node_id(),
+            MAIN_BASE_PARTITION
+                .at_offset(PACKAGE_BLUEPRINT_DEPENDENCIES_PARTITION_OFFSET)
+                .unwrap(),
+            &SubstateKey::Map(scrypto_encode(&key).unwrap()),
+            LockFlags::read_only(),
+            Some(|| {
+                let kv_entry = KeyValueEntrySubstate::<()>::default();
+                IndexedScryptoValue::from_typed(&kv_entry)
+            }),
+            SystemLockData::default(),
+        )?;
+        let substate: PackageBlueprintVersionDependenciesEntrySubstate =
+            api.kernel_read_substate(handle)?.as_typed().unwrap();
+        api.kernel_close_substate(handle)?;
+
+        Ok(substate
+            .into_value()
+            .map(|dependencies| dependencies.into_latest().dependencies.contains(address))
+            .unwrap_or(false))
+    }
+}
+
 impl<C: SystemCallbackObject> KernelCallbackObject for SystemConfig<C> {
     type CallFrameData = Actor;
     type LockData = SystemLockData;
@@ -107,6 +158,22 @@ impl<C: SystemCallbackObject> KernelCallbackObject for SystemConfig<C> {
     fn init<S: BootStore>(&mut self, store: &S) -> Result<C::CallbackState, RuntimeError> {
         self.modules.on_init()?;
 
+        let system_boot = store
+            .read_substate(
+                TRANSACTION_TRACKER.as_node_id(),
+                BOOT_LOADER_PARTITION,
+                &SubstateKey::Field(BOOT_LOADER_SYSTEM_SUBSTATE_FIELD_KEY),
+            )
+            .map(|v| scrypto_decode(v.as_slice()).unwrap())
+            .unwrap_or(SystemBoot::V1 {
+                package_blueprint_upgrades: false,
+            });
+        match system_boot {
+            SystemBoot::V1 {
+                package_blueprint_upgrades,
+            } => self.package_blueprint_upgrades = package_blueprint_upgrades,
+        }
+
         let callback_state = self.callback_obj.init(store)?;
 
         Ok(callback_state)
@@ -320,10 +387,10 @@ impl<C: SystemCallbackObject> KernelCallbackObject for SystemConfig<C> {
 
         // Make dependent resources/components visible
         if let Some(blueprint_id) = actor.blueprint_id() {
-            let key = BlueprintVersionKey {
-                blueprint: blueprint_id.blueprint_name.clone(),
-                version: BlueprintVersion::default(),
-            };
+            let key = PackageUpgradeNativeBlueprint::resolve_current_version_key(
+                &blueprint_id,
+                system.api,
+            )?;
 
             let handle = system.kernel_open_substate_with_default(
                 blueprint_id.package_address.as_node_id(),
@@ -349,10 +416,7 @@ impl<C: SystemCallbackObject> KernelCallbackObject for SystemConfig<C> {
                 let blueprint_id = actor.blueprint_id().unwrap();
 
                 //  Validate input
-                let definition = system.load_blueprint_definition(
-                    blueprint_id.package_address,
-                    &BlueprintVersionKey::new_default(blueprint_id.blueprint_name.as_str()),
-                )?;
+                let definition = system.get_blueprint_default_definition(blueprint_id.clone())?;
 
                 let target = system.get_actor_type_target()?;
 
@@ -409,10 +473,7 @@ impl<C: SystemCallbackObject> KernelCallbackObject for SystemConfig<C> {
                 blueprint_id, hook, ..
             }) => {
                 // Find the export
-                let definition = system.load_blueprint_definition(
-                    blueprint_id.package_address,
-                    &BlueprintVersionKey::new_default(blueprint_id.blueprint_name.as_str()),
-                )?;
+                let definition = system.get_blueprint_default_definition(blueprint_id.clone())?;
                 let export =
                     definition
                         .hook_exports
@@ -555,9 +616,31 @@ impl<C: SystemCallbackObject> KernelCallbackObject for SystemConfig<C> {
                 BlueprintId::new(&IDENTITY_PACKAGE, IDENTITY_BLUEPRINT),
                 IDENTITY_CREATE_VIRTUAL_ED25519_ID,
             ),
+            Some(EntityType::GlobalVirtualSecp256r1Account) => (
+                BlueprintId::new(&ACCOUNT_PACKAGE, ACCOUNT_BLUEPRINT),
+                ACCOUNT_CREATE_VIRTUAL_SECP256R1_ID,
+            ),
+            Some(EntityType::GlobalVirtualSecp256r1Identity) => (
+                BlueprintId::new(&IDENTITY_PACKAGE, IDENTITY_BLUEPRINT),
+                IDENTITY_CREATE_VIRTUAL_SECP256R1_ID,
+            ),
             _ => return Ok(false),
         };
 
+        // Secp256r1 accounts and identities only exist once the protocol update which introduces
+        // the secp256r1 signature badge has added it to the dependencies of their blueprint
+        if matches!(
+            node_id.entity_type(),
+            Some(EntityType::GlobalVirtualSecp256r1Account)
+                | Some(EntityType::GlobalVirtualSecp256r1Identity)
+        ) && !Self::blueprint_depends_on(
+            &blueprint_id,
+            &SECP256R1_SIGNATURE_VIRTUAL_BADGE.into(),
+            api,
+        )? {
+            return Ok(false);
+        }
+
         let mut service = SystemService::new(api);
         let definition = service.load_blueprint_definition(
             blueprint_id.package_address,
@@ -21,7 +21,10 @@ use sbor::rust::prelude::*;
 use sbor::LocalTypeId;
 use sbor::{validate_payload_against_schema, HasLatestVersion, LocatedValidationError};
 
-use crate::blueprints::package::PackageBlueprintVersionDefinitionEntrySubstate;
+use crate::blueprints::package::{
+    PackageBlueprintUpgradeConfigEntrySubstate, PackageBlueprintVersionDefinitionEntrySubstate,
+    PackageCodeOriginalCodeEntrySubstate,
+};
 use crate::internal_prelude::{IndexEntrySubstate, SortedIndexEntrySubstate};
 use crate::system::payload_validation::{SchemaOrigin, TypeInfoForValidation, ValidationContext};
 use crate::system::system_substates::FieldSubstate;
@@ -520,10 +523,11 @@ impl<'a, S: SubstateDatabase> SystemDatabaseReader<'a, S> {
         &self,
         blueprint_id: &BlueprintId,
     ) -> Result<Rc<BlueprintDefinition>, SystemReaderError> {
+        let bp_version_key = self.get_blueprint_current_version_key(blueprint_id);
         let canonical_key = CanonicalBlueprintId {
             address: blueprint_id.package_address,
             blueprint: blueprint_id.blueprint_name.clone(),
-            version: BlueprintVersion::default(),
+            version: bp_version_key.version,
         };
         {
             if let Some(cache) = self.blueprint_cache.borrow().get(&canonical_key) {
@@ -531,7 +535,6 @@ impl<'a, S: SubstateDatabase> SystemDatabaseReader<'a, S> {
             }
         }
 
-        let bp_version_key = BlueprintVersionKey::new_default(blueprint_id.blueprint_name.clone());
         let definition = Rc::new(self
             .fetch_substate::<SpreadPrefixKeyMapper, PackageBlueprintVersionDefinitionEntrySubstate>(
                 blueprint_id.package_address.as_node_id(),
@@ -548,6 +551,30 @@ impl<'a, S: SubstateDatabase> SystemDatabaseReader<'a, S> {
         Ok(definition)
     }
 
+    /// Returns the key of the version which is currently active for a blueprint, as recorded by
+    /// its upgrade config, or of the default version if the blueprint has never been upgraded.
+    pub fn get_blueprint_current_version_key(
+        &self,
+        blueprint_id: &BlueprintId,
+    ) -> BlueprintVersionKey {
+        let version = self
+            .fetch_substate::<SpreadPrefixKeyMapper, PackageBlueprintUpgradeConfigEntrySubstate>(
+                blueprint_id.package_address.as_node_id(),
+                MAIN_BASE_PARTITION
+                    .at_offset(PACKAGE_BLUEPRINT_UPGRADE_CONFIG_PARTITION_OFFSET)
+                    .unwrap(),
+                &SubstateKey::Map(scrypto_encode(&blueprint_id.blueprint_name).unwrap()),
+            )
+            .and_then(|config| config.into_value())
+            .map(|config| config.into_latest().current_version)
+            .unwrap_or_default();
+
+        BlueprintVersionKey {
+            blueprint: blueprint_id.blueprint_name.clone(),
+            version,
+        }
+    }
+
     pub fn get_kv_store_type_target(
         &self,
         node_id: &NodeId,
@@ -856,7 +883,7 @@ impl<'a, S: SubstateDatabase> SystemDatabaseReader<'a, S> {
         &self,
         blueprint_id: &BlueprintId,
     ) -> Result<BlueprintDefinition, SystemReaderError> {
-        let bp_version_key = BlueprintVersionKey::new_default(blueprint_id.blueprint_name.clone());
+        let bp_version_key = self.get_blueprint_current_version_key(blueprint_id);
         let definition = self
             .fetch_substate::<SpreadPrefixKeyMapper, PackageBlueprintVersionDefinitionEntrySubstate>(
                 blueprint_id.package_address.as_node_id(),
@@ -971,7 +998,16 @@ impl<'a, S: SubstateDatabase> SystemDatabaseReader<'a, S> {
                 } else {
                     match object_info.object_type {
                         ObjectType::Global { modules } => {
-                            if partition_num.ge(&ROLE_ASSIGNMENT_BASE_PARTITION) {
+                            if partition_num.eq(&ROYALTY_SPLIT_PARTITION)
+                                || partition_num.eq(&ROYALTY_SPLIT_VAULT_PARTITION)
+                            {
+                                // The royalty split collections are mapped to physical partitions
+                                if modules.contains_key(&AttachedModuleId::Royalty) {
+                                    (ModuleId::Royalty, None)
+                                } else {
+                                    (ModuleId::Main, None)
+                                }
+                            } else if partition_num.ge(&ROLE_ASSIGNMENT_BASE_PARTITION) {
                                 if modules.contains_key(&AttachedModuleId::RoleAssignment) {
                                     let partition_offset = PartitionOffset(
                                         partition_num.0 - ROLE_ASSIGNMENT_BASE_PARTITION.0,
@@ -1151,6 +1187,42 @@ impl<'a, S: SubstateDatabase + ListableSubstateDatabase> SystemDatabaseReader<'a
         });
         Box::new(iter)
     }
+
+    /// Finds the original code of the given code hashes, by scanning the code of all the
+    /// packages in the database.
+    pub fn find_package_original_code(
+        &self,
+        code_hashes: &IndexSet<CodeHash>,
+    ) -> IndexMap<CodeHash, Vec<u8>> {
+        let original_code_partition =
+            PackagePartitionOffset::CodeOriginalCodeKeyValue.as_main_partition();
+        let mut original_code = index_map_new();
+
+        for (node_id, partition_num) in self.partitions_iter() {
+            if original_code.len() == code_hashes.len() {
+                break;
+            }
+            if !node_id.is_global_package() || partition_num != original_code_partition {
+                continue;
+            }
+            for code_hash in code_hashes {
+                if original_code.contains_key(code_hash) {
+                    continue;
+                }
+                let entry = self
+                    .fetch_substate::<SpreadPrefixKeyMapper, PackageCodeOriginalCodeEntrySubstate>(
+                        &node_id,
+                        partition_num,
+                        &SubstateKey::Map(scrypto_encode(code_hash).unwrap()),
+                    );
+                if let Some(payload) = entry.and_then(|entry| entry.into_value()) {
+                    original_code.insert(*code_hash, payload.into_latest().code);
+                }
+            }
+        }
+
+        original_code
+    }
 }
 
 pub struct SystemDatabaseWriter<'a, S: SubstateDatabase + CommittableSubstateDatabase> {
@@ -1,5 +1,5 @@
 use super::Authorization;
-use crate::blueprints::package::PackageAuthNativeBlueprint;
+use crate::blueprints::package::{PackageAuthNativeBlueprint, PackageUpgradeNativeBlueprint};
 use crate::blueprints::resource::AuthZone;
 use crate::errors::*;
 use crate::internal_prelude::*;
@@ -16,7 +16,7 @@ use crate::system::type_info::TypeInfoSubstate;
 use crate::types::*;
 use radix_engine_interface::api::{AttachedModuleId, ClientBlueprintApi, LockFlags, ModuleId};
 use radix_engine_interface::blueprints::package::{
-    BlueprintVersion, BlueprintVersionKey, MethodAuthTemplate, RoleSpecification,
+    BlueprintVersion, MethodAuthTemplate, RoleSpecification,
 };
 use radix_engine_interface::blueprints::resource::*;
 use radix_engine_interface::blueprints::transaction_processor::TRANSACTION_PROCESSOR_BLUEPRINT;
@@ -108,9 +108,11 @@ impl AuthModule {
         // Check authorization
         {
             // Step 1: Resolve method to permission
+            let bp_version_key =
+                PackageUpgradeNativeBlueprint::resolve_current_version_key(blueprint_id, api.api)?;
             let permission = PackageAuthNativeBlueprint::resolve_function_permission(
                 blueprint_id.package_address.as_node_id(),
-                &BlueprintVersionKey::new_default(blueprint_id.blueprint_name.as_str()),
+                &bp_version_key,
                 ident,
                 api.api,
             )?;
@@ -464,9 +466,11 @@ impl AuthModule {
             return RoleAssignmentNativePackage::authorization(&global_address, ident, args, api);
         }
 
+        let bp_version_key =
+            PackageUpgradeNativeBlueprint::resolve_current_version_key(blueprint_id, api.api)?;
         let auth_template = PackageAuthNativeBlueprint::get_bp_auth_template(
             blueprint_id.package_address.as_node_id(),
-            &BlueprintVersionKey::new_default(blueprint_id.blueprint_name.as_str()),
+            &bp_version_key,
             api.api,
         )?
         .method_auth;
@@ -128,6 +128,14 @@ pub enum ExecutionCostingEntry<'a> {
     Keccak256Hash {
         size: usize,
     },
+    Blake2b256Hash {
+        size: usize,
+    },
+    Ed25519Verify {
+        size: usize,
+    },
+    Secp256k1EcdsaVerify,
+    Secp256k1EcdsaVerifyAndKeyRecover,
 }
 
 #[derive(Debug, IntoStaticStr)]
@@ -201,6 +209,12 @@ impl<'a> ExecutionCostingEntry<'a> {
                 ft.bls12381_g2_signature_aggregate_cost(*signatures_cnt)
             }
             ExecutionCostingEntry::Keccak256Hash { size } => ft.keccak256_hash_cost(*size),
+            ExecutionCostingEntry::Blake2b256Hash { size } => ft.blake2b256_hash_cost(*size),
+            ExecutionCostingEntry::Ed25519Verify { size } => ft.ed25519_verify_cost(*size),
+            ExecutionCostingEntry::Secp256k1EcdsaVerify => ft.secp256k1_ecdsa_verify_cost(),
+            ExecutionCostingEntry::Secp256k1EcdsaVerifyAndKeyRecover => {
+                ft.secp256k1_ecdsa_verify_and_key_recover_cost()
+            }
         }
     }
 }
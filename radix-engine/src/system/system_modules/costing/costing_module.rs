@@ -1,6 +1,6 @@
 use super::*;
 use super::{FeeReserveError, FeeTable, SystemLoanFeeReserve};
-use crate::blueprints::package::PackageRoyaltyNativeBlueprint;
+use crate::blueprints::package::{PackageRoyaltyNativeBlueprint, PackageUpgradeNativeBlueprint};
 use crate::kernel::kernel_api::{KernelApi, KernelInternalApi, KernelInvocation};
 use crate::kernel::kernel_callback_api::{
     CloseSubstateEvent, CreateNodeEvent, DrainSubstatesEvent, DropNodeEvent, MoveModuleEvent,
@@ -18,7 +18,6 @@ use crate::{
     transaction::AbortReason,
 };
 use radix_engine_interface::api::AttachedModuleId;
-use radix_engine_interface::blueprints::package::BlueprintVersionKey;
 use radix_engine_interface::blueprints::resource::LiquidFungibleResource;
 use radix_engine_interface::{types::NodeId, *};
 
@@ -333,7 +332,7 @@ impl<V: SystemCallbackObject> SystemModule<SystemConfig<V>> for CostingModule {
         //===========================
         if let Some(blueprint_id) = optional_blueprint_id {
             let bp_version_key =
-                BlueprintVersionKey::new_default(blueprint_id.blueprint_name.as_str());
+                PackageUpgradeNativeBlueprint::resolve_current_version_key(&blueprint_id, api)?;
             PackageRoyaltyNativeBlueprint::charge_package_royalty(
                 blueprint_id.package_address.as_node_id(),
                 &bp_version_key,
@@ -50,6 +50,11 @@ lazy_static! {
             .or_default()
             // TODO: publish_wasm_advanced is too expensive, dividing by 6 to let large package (1MiB) to be published, consider using cubic approximation
             .insert(PACKAGE_PUBLISH_WASM_ADVANCED_IDENT, (9063 / 6, 11072798));
+        costs
+            .entry(PACKAGE_PACKAGE)
+            .or_default()
+            // Validates and instruments the upgraded code in the same way as publish_wasm_advanced
+            .insert(PACKAGE_PROPOSE_BLUEPRINT_UPGRADE_IDENT, (9063 / 6, 11072798));
     costs
     };
 }
@@ -505,6 +510,48 @@ impl FeeTable {
         instructions_cnt / CPU_INSTRUCTIONS_TO_COST_UNIT
     }
 
+    #[inline]
+    pub fn blake2b256_hash_cost(&self, size: usize) -> u32 {
+        // Estimated, to be confirmed with `test_crypto_scrypto_blake2b_256_costing`
+        // - For sizes less than 100, instruction count is assumed to remain the same.
+        // - For greater sizes, BLAKE2b is assumed to cost 15 instructions per byte, plus a
+        //   constant 1112 instructions.
+        let size = if size < 100 { 100 } else { cast(size) };
+        let instructions_cnt = add(mul(size, 15), 1112);
+        // Convert to cost units
+        instructions_cnt / CPU_INSTRUCTIONS_TO_COST_UNIT
+    }
+
+    #[inline]
+    pub fn ed25519_verify_cost(&self, size: usize) -> u32 {
+        // Estimated, to be confirmed with `test_crypto_scrypto_verify_ed25519_costing`, which
+        // sweeps the message size from 100 bytes to 900 KiB.
+        // - Verification hashes the message with SHA-512, assumed to cost 34 instructions per byte.
+        // - The remaining curve arithmetic is assumed to be constant, 444421 instructions.
+        let instructions_cnt = add(mul(cast(size), 34), 444421);
+        // Convert to cost units
+        instructions_cnt / CPU_INSTRUCTIONS_TO_COST_UNIT
+    }
+
+    #[inline]
+    pub fn secp256k1_ecdsa_verify_cost(&self) -> u32 {
+        // Estimated, to be confirmed with `test_crypto_scrypto_verify_secp256k1_ecdsa_costing`
+        // The input is always a 32-byte hash, so the cost is constant.
+        let instructions_cnt = 500000;
+        // Convert to cost units
+        instructions_cnt / CPU_INSTRUCTIONS_TO_COST_UNIT
+    }
+
+    #[inline]
+    pub fn secp256k1_ecdsa_verify_and_key_recover_cost(&self) -> u32 {
+        // Estimated, to be confirmed with
+        // `test_crypto_scrypto_secp256k1_ecdsa_verify_and_key_recover_costing`
+        // The input is always a 32-byte hash, so the cost is constant.
+        let instructions_cnt = 550000;
+        // Convert to cost units
+        instructions_cnt / CPU_INSTRUCTIONS_TO_COST_UNIT
+    }
+
     //======================
     // Finalization costs
     // This is primarily to account for the additional work on the Node side
@@ -24,6 +24,7 @@ use crate::system::system_modules::limits::{LimitsModule, TransactionLimitsConfi
 use crate::system::system_modules::transaction_runtime::{Event, TransactionRuntimeModule};
 use crate::transaction::ExecutionConfig;
 use crate::types::*;
+use crate::vm::wasm::WasmBacktrace;
 use bitflags::bitflags;
 use paste::paste;
 use radix_engine_interface::api::ModuleId;
@@ -80,6 +81,7 @@ pub struct SystemModuleMixer {
 
     /* flags */
     pub enabled_modules: EnabledModules,
+    pub enable_wasm_backtraces: bool,
 
     /* states */
     pub(super) kernel_trace: KernelTraceModule,
@@ -88,6 +90,8 @@ pub struct SystemModuleMixer {
     pub(super) auth: AuthModule,
     pub(crate) transaction_runtime: TransactionRuntimeModule,
     pub(super) execution_trace: ExecutionTraceModule,
+    /// The backtraces of the blueprints which trapped, if `enable_wasm_backtraces` is set.
+    pub wasm_backtraces: Vec<WasmBacktrace>,
 }
 
 // Macro generates default modules dispatches call based on passed function name and arguments.
@@ -133,6 +137,7 @@ impl SystemModuleMixer {
     ) -> Self {
         Self {
             enabled_modules,
+            enable_wasm_backtraces: execution_config.enable_wasm_backtraces,
             kernel_trace: KernelTraceModule {},
             costing: CostingModule {
                 fee_reserve,
@@ -171,6 +176,7 @@ impl SystemModuleMixer {
                 events: Vec::new(),
                 replacements: index_map_new(),
             },
+            wasm_backtraces: Vec::new(),
         }
     }
 
@@ -180,8 +186,14 @@ impl SystemModuleMixer {
         CostingModule,
         TransactionRuntimeModule,
         ExecutionTraceModule,
+        Vec<WasmBacktrace>,
     ) {
-        (self.costing, self.transaction_runtime, self.execution_trace)
+        (
+            self.costing,
+            self.transaction_runtime,
+            self.execution_trace,
+            self.wasm_backtraces,
+        )
     }
 }
 
@@ -148,12 +148,15 @@ pub trait CommitableSubstateStore {
     /// this type.
     /// Otherwise, behavior is undefined.
     ///
+    /// See [`ScanKeysOrder`] for the order in which the keys are returned.
+    ///
     /// Returns list of substate keys and database access info
     fn scan_keys<K: SubstateKeyContent + 'static, E, F: FnMut(IOAccess) -> Result<(), E>>(
         &mut self,
         node_id: &NodeId,
         partition_num: PartitionNumber,
         count: u32,
+        order: ScanKeysOrder,
         on_io_access: &mut F,
     ) -> Result<Vec<SubstateKey>, E>;
 
@@ -174,11 +177,14 @@ pub trait CommitableSubstateStore {
     ) -> Result<Vec<(SubstateKey, IndexedScryptoValue)>, E>;
 
     /// Returns tuple of substate vector and boolean which is true for the first database access.
+    ///
+    /// If `after` is given, the scan continues strictly after that key.
     fn scan_sorted_substates<E, F: FnMut(IOAccess) -> Result<(), E>>(
         &mut self,
         node_id: &NodeId,
         partition_num: PartitionNumber,
         count: u32,
+        after: Option<&SortedKey>,
         on_io_access: &mut F,
     ) -> Result<Vec<(SortedKey, IndexedScryptoValue)>, E>;
 
@@ -189,6 +195,18 @@ pub trait CommitableSubstateStore {
     fn get_commit_info(&mut self) -> StoreCommitInfo;
 }
 
+/// The order in which [`CommitableSubstateStore::scan_keys`] returns keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanKeysOrder<'a> {
+    /// The keys of tracked substates first, followed by keys read from the database. The
+    /// database is not read if the tracked substates already fill the count.
+    TrackedFirst,
+    /// Tracked and database keys merged in the database ordering of the partition, continuing
+    /// strictly after the given key, if any, so that the last returned key can be used as the
+    /// cursor of the next scan.
+    Sorted { after: Option<&'a SubstateKey> },
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct CanonicalPartition {
     pub node_id: NodeId,
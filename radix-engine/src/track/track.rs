@@ -1,6 +1,6 @@
 use crate::kernel::call_frame::TransientSubstates;
 use crate::track::interface::{
-    CommitableSubstateStore, IOAccess, NodeSubstates, TrackedSubstateInfo,
+    CommitableSubstateStore, IOAccess, NodeSubstates, ScanKeysOrder, TrackedSubstateInfo,
 };
 use crate::track::state_updates::*;
 use crate::track::utils::OverlayingResultIterator;
@@ -16,6 +16,7 @@ use radix_engine_store_interface::{
 use sbor::rust::collections::btree_map::Entry;
 use sbor::rust::iter::empty;
 use sbor::rust::mem;
+use sbor::rust::ops::Bound;
 
 use super::interface::{CanonicalPartition, CanonicalSubstateKey, StoreCommit, StoreCommitInfo};
 
@@ -51,6 +52,22 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper + 'static> Track<'s, S, M> {
         }
     }
 
+    /// Returns the range of tracked substates which come strictly after the given sort key
+    fn range_after(after: Option<&DbSortKey>) -> (Bound<&DbSortKey>, Bound<&DbSortKey>) {
+        match after {
+            Some(after) => (Bound::Excluded(after), Bound::Unbounded),
+            None => (Bound::Unbounded, Bound::Unbounded),
+        }
+    }
+
+    /// Returns the smallest sort key which is strictly greater than the given one, i.e. the
+    /// inclusive database iteration start for a scan continuing after the given sort key
+    fn db_sort_key_successor(db_sort_key: &DbSortKey) -> DbSortKey {
+        let mut bytes = db_sort_key.0.clone();
+        bytes.push(0u8);
+        DbSortKey(bytes)
+    }
+
     // TODO cleanup interface to avoid redundant information
     fn get_substate_from_db<E, F: FnMut(IOAccess) -> Result<(), E>>(
         substate_db: &'s S,
@@ -79,6 +96,7 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper + 'static> Track<'s, S, M> {
     >(
         substate_db: &'x S,
         partition_key: &DbPartitionKey,
+        from_sort_key: Option<&DbSortKey>,
         on_io_access: &'x mut F,
         canonical_partition: CanonicalPartition,
     ) -> Box<dyn Iterator<Item = Result<(DbSortKey, (SubstateKey, IndexedScryptoValue)), E>> + 'x>
@@ -137,7 +155,7 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper + 'static> Track<'s, S, M> {
         }
 
         Box::new(TracedIterator {
-            iterator: substate_db.list_entries(partition_key),
+            iterator: substate_db.list_entries_from(partition_key, from_sort_key),
             on_io_access,
             canonical_partition,
             errored_out: false,
@@ -315,6 +333,161 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper + 'static> Track<'s, S, M> {
 
         Ok(&mut partition.get_mut(&db_sort_key).unwrap().substate_value)
     }
+
+    fn scan_keys_tracked_first<
+        K: SubstateKeyContent + 'static,
+        E,
+        F: FnMut(IOAccess) -> Result<(), E>,
+    >(
+        &mut self,
+        node_id: &NodeId,
+        partition_number: PartitionNumber,
+        limit: u32,
+        on_io_access: &mut F,
+    ) -> Result<Vec<SubstateKey>, E> {
+        let limit: usize = limit.try_into().unwrap();
+        let mut items = Vec::new();
+
+        let node_updates = self.tracked_nodes.get(node_id);
+        let is_new = node_updates
+            .map(|tracked_node| tracked_node.is_new)
+            .unwrap_or(false);
+        let tracked_partition =
+            node_updates.and_then(|n| n.tracked_partitions.get(&partition_number));
+
+        if let Some(tracked_partition) = tracked_partition {
+            for (_db_sort_key, tracked_substate) in &tracked_partition.substates {
+                if items.len() == limit {
+                    return Ok(items);
+                }
+
+                // TODO: Check that substate is not write locked, before use outside of native blueprints
+                if let Some(_substate) = tracked_substate.substate_value.get() {
+                    items.push(tracked_substate.substate_key.clone());
+                }
+            }
+        }
+
+        // Optimization, no need to go into database if the node is just created
+        if items.len() == limit || is_new {
+            return Ok(items);
+        }
+
+        let db_partition_key = M::to_db_partition_key(node_id, partition_number);
+        let mut tracked_iter = IterationCountedIter::new(Self::list_entries_from_db::<E, F, K>(
+            self.substate_db,
+            &db_partition_key,
+            None,
+            on_io_access,
+            CanonicalPartition {
+                node_id: *node_id,
+                partition_number,
+            },
+        ));
+
+        for result in &mut tracked_iter {
+            let (db_sort_key, (substate_key, _substate_value)) = result?;
+
+            if items.len() == limit {
+                break;
+            }
+
+            if tracked_partition
+                .map(|tracked_partition| tracked_partition.substates.contains_key(&db_sort_key))
+                .unwrap_or(false)
+            {
+                continue;
+            }
+
+            // TODO: cache read substates in Track (and notify upper layer)
+
+            items.push(substate_key);
+        }
+
+        // Update track
+        let num_iterations = tracked_iter.num_iterations;
+        let tracked_partition = self.get_tracked_partition(node_id, partition_number);
+        tracked_partition.range_read = u32::max(tracked_partition.range_read, num_iterations);
+
+        Ok(items)
+    }
+
+    fn scan_keys_sorted<K: SubstateKeyContent + 'static, E, F: FnMut(IOAccess) -> Result<(), E>>(
+        &mut self,
+        node_id: &NodeId,
+        partition_number: PartitionNumber,
+        limit: u32,
+        after: Option<&SubstateKey>,
+        on_io_access: &mut F,
+    ) -> Result<Vec<SubstateKey>, E> {
+        let limit: usize = limit.try_into().unwrap();
+        let after = after.map(M::to_db_sort_key);
+
+        // initialize the track partition, since we will definitely need it: either to read keys from it OR to update the `range_read` on it
+        let tracked_node = self
+            .tracked_nodes
+            .entry(node_id.clone())
+            .or_insert(TrackedNode::new(false));
+        let tracked_partition = tracked_node
+            .tracked_partitions
+            .entry(partition_number)
+            .or_insert(TrackedPartition::new());
+
+        // initialize the "from db" iterator: use `dyn`, since we want to skip it altogether if the node is marked as `is_new` in our track
+        let mut db_values_count = 0u32;
+        let raw_db_entries: Box<
+            dyn Iterator<Item = Result<(DbSortKey, (SubstateKey, IndexedScryptoValue)), E>>,
+        > = if tracked_node.is_new {
+            Box::new(empty()) // optimization: avoid touching the database altogether
+        } else {
+            let partition_key = M::to_db_partition_key(node_id, partition_number);
+            let from_sort_key = after.as_ref().map(Self::db_sort_key_successor);
+            Box::new(Self::list_entries_from_db::<E, F, K>(
+                self.substate_db,
+                &partition_key,
+                from_sort_key.as_ref(),
+                on_io_access,
+                CanonicalPartition {
+                    node_id: *node_id,
+                    partition_number,
+                },
+            ))
+        };
+        let db_read_keys = raw_db_entries
+            .inspect(|_| {
+                db_values_count += 1;
+            })
+            .map(|result| {
+                result.map(|(db_sort_key, (substate_key, _))| (db_sort_key, substate_key))
+            });
+
+        // initialize the "from track" iterator
+        let tracked_key_changes = tracked_partition
+            .substates
+            .range(Self::range_after(after.as_ref()))
+            .map(|(db_sort_key, tracked_substate)| {
+                // TODO: Check that substate is not write locked, before use outside of native blueprints
+                let substate_key = tracked_substate
+                    .substate_value
+                    .get()
+                    .map(|_| tracked_substate.substate_key.clone());
+                (db_sort_key.clone(), substate_key)
+            });
+
+        // merge both in database order, so that the last returned key can be used as a cursor
+        let mut items = Vec::new();
+        for result in OverlayingResultIterator::new(db_read_keys, tracked_key_changes).take(limit) {
+            let (_db_sort_key, substate_key) = result?;
+            items.push(substate_key);
+        }
+
+        // Update track
+        tracked_partition.range_read = u32::max(tracked_partition.range_read, db_values_count);
+
+        // TODO: cache read substates in Track (and notify upper layer)
+
+        Ok(items)
+    }
 }
 
 impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper + 'static> BootStore for Track<'s, S, M> {
@@ -571,72 +744,24 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper + 'static> CommitableSubstate
         node_id: &NodeId,
         partition_number: PartitionNumber,
         limit: u32,
+        order: ScanKeysOrder,
         on_io_access: &mut F,
     ) -> Result<Vec<SubstateKey>, E> {
-        let limit: usize = limit.try_into().unwrap();
-        let mut items = Vec::new();
-
-        let node_updates = self.tracked_nodes.get(node_id);
-        let is_new = node_updates
-            .map(|tracked_node| tracked_node.is_new)
-            .unwrap_or(false);
-        let tracked_partition =
-            node_updates.and_then(|n| n.tracked_partitions.get(&partition_number));
-
-        if let Some(tracked_partition) = tracked_partition {
-            for (_db_sort_key, tracked_substate) in &tracked_partition.substates {
-                if items.len() == limit {
-                    return Ok(items);
-                }
-
-                // TODO: Check that substate is not write locked, before use outside of native blueprints
-                if let Some(_substate) = tracked_substate.substate_value.get() {
-                    items.push(tracked_substate.substate_key.clone());
-                }
-            }
-        }
-
-        // Optimization, no need to go into database if the node is just created
-        if items.len() == limit || is_new {
-            return Ok(items);
-        }
-
-        let db_partition_key = M::to_db_partition_key(node_id, partition_number);
-        let mut tracked_iter = IterationCountedIter::new(Self::list_entries_from_db::<E, F, K>(
-            self.substate_db,
-            &db_partition_key,
-            on_io_access,
-            CanonicalPartition {
-                node_id: *node_id,
+        match order {
+            ScanKeysOrder::TrackedFirst => self.scan_keys_tracked_first::<K, E, F>(
+                node_id,
                 partition_number,
-            },
-        ));
-
-        for result in &mut tracked_iter {
-            let (db_sort_key, (substate_key, _substate_value)) = result?;
-
-            if items.len() == limit {
-                break;
-            }
-
-            if tracked_partition
-                .map(|tracked_partition| tracked_partition.substates.contains_key(&db_sort_key))
-                .unwrap_or(false)
-            {
-                continue;
-            }
-
-            // TODO: cache read substates in Track (and notify upper layer)
-
-            items.push(substate_key);
+                limit,
+                on_io_access,
+            ),
+            ScanKeysOrder::Sorted { after } => self.scan_keys_sorted::<K, E, F>(
+                node_id,
+                partition_number,
+                limit,
+                after,
+                on_io_access,
+            ),
         }
-
-        // Update track
-        let num_iterations = tracked_iter.num_iterations;
-        let tracked_partition = self.get_tracked_partition(node_id, partition_number);
-        tracked_partition.range_read = u32::max(tracked_partition.range_read, num_iterations);
-
-        Ok(items)
     }
 
     fn drain_substates<K: SubstateKeyContent + 'static, E, F: FnMut(IOAccess) -> Result<(), E>>(
@@ -696,6 +821,7 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper + 'static> CommitableSubstate
                 IterationCountedIter::new(Self::list_entries_from_db::<E, F, K>(
                     self.substate_db,
                     &db_partition_key,
+                    None,
                     on_io_access,
                     CanonicalPartition {
                         node_id: *node_id,
@@ -771,10 +897,12 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper + 'static> CommitableSubstate
         node_id: &NodeId,
         partition_number: PartitionNumber,
         limit: u32,
+        after: Option<&SortedKey>,
         on_io_access: &mut F,
     ) -> Result<Vec<(SortedKey, IndexedScryptoValue)>, E> {
         // TODO: ensure we abort if any substates are write locked.
         let limit: usize = limit.try_into().unwrap();
+        let after = after.map(M::sorted_to_db_sort_key);
 
         // initialize the track partition, since we will definitely need it: either to read values from it OR to update the `range_read` on it
         let tracked_node = self
@@ -794,9 +922,11 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper + 'static> CommitableSubstate
             Box::new(empty()) // optimization: avoid touching the database altogether
         } else {
             let partition_key = M::to_db_partition_key(node_id, partition_number);
+            let from_sort_key = after.as_ref().map(Self::db_sort_key_successor);
             Box::new(Self::list_entries_from_db::<E, F, SortedKey>(
                 self.substate_db,
                 &partition_key,
+                from_sort_key.as_ref(),
                 on_io_access,
                 CanonicalPartition {
                     node_id: *node_id,
@@ -809,21 +939,20 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper + 'static> CommitableSubstate
         });
 
         // initialize the "from track" iterator
-        let tracked_entry_changes =
-            tracked_partition
-                .substates
-                .iter()
-                .map(|(db_sort_key, tracked_substate)| {
-                    // TODO: ensure we abort if any substates are write locked.
-                    if let Some(value) = tracked_substate.substate_value.get() {
-                        (
-                            db_sort_key.clone(),
-                            Some((tracked_substate.substate_key.clone(), value.clone())),
-                        )
-                    } else {
-                        (db_sort_key.clone(), None)
-                    }
-                });
+        let tracked_entry_changes = tracked_partition
+            .substates
+            .range(Self::range_after(after.as_ref()))
+            .map(|(db_sort_key, tracked_substate)| {
+                // TODO: ensure we abort if any substates are write locked.
+                if let Some(value) = tracked_substate.substate_value.get() {
+                    (
+                        db_sort_key.clone(),
+                        Some((tracked_substate.substate_key.clone(), value.clone())),
+                    )
+                } else {
+                    (db_sort_key.clone(), None)
+                }
+            });
 
         let mut items = Vec::new();
         // construct the composite iterator, which applies changes read from our track on top of db values
@@ -28,6 +28,7 @@ use crate::track::interface::CommitableSubstateStore;
 use crate::track::{to_state_updates, Track, TrackFinalizeError};
 use crate::transaction::*;
 use crate::types::*;
+use crate::vm::wasm::WasmBacktrace;
 use radix_engine_common::constants::*;
 use radix_engine_interface::api::ModuleId;
 use radix_engine_interface::blueprints::resource::LiquidFungibleResource;
@@ -100,6 +101,9 @@ pub struct ExecutionConfig {
     pub enabled_modules: EnabledModules,
     pub abort_when_loan_repaid: bool,
     pub enable_cost_breakdown: bool,
+    /// Appends a symbolized WASM backtrace to the errors of trapping blueprints.
+    /// This is for debugging only, as it reads the original code of the trapping packages.
+    pub enable_wasm_backtraces: bool,
     pub max_execution_trace_depth: usize,
     pub max_call_depth: usize,
     pub max_heap_substate_total_bytes: usize,
@@ -124,6 +128,7 @@ impl ExecutionConfig {
             enabled_modules: EnabledModules::for_notarized_transaction(),
             abort_when_loan_repaid: false,
             enable_cost_breakdown: false,
+            enable_wasm_backtraces: false,
             max_execution_trace_depth: MAX_EXECUTION_TRACE_DEPTH,
             max_call_depth: MAX_CALL_DEPTH,
             max_heap_substate_total_bytes: MAX_HEAP_SUBSTATE_TOTAL_BYTES,
@@ -195,6 +200,11 @@ impl ExecutionConfig {
         self
     }
 
+    pub fn with_wasm_backtraces(mut self, enabled: bool) -> Self {
+        self.enable_wasm_backtraces = enabled;
+        self
+    }
+
     pub fn up_to_loan_repayment(mut self, enabled: bool) -> Self {
         self.abort_when_loan_repaid = enabled;
         self
@@ -294,11 +304,17 @@ where
         };
 
         // Run manifest
+        let mut wasm_backtraces = execution_config.enable_wasm_backtraces.then(Vec::new);
         let (fee_summary, fee_details, result) = match validation_result {
             Ok(()) => {
                 let (
                     interpretation_result,
-                    (mut costing_module, runtime_module, execution_trace_module),
+                    (
+                        mut costing_module,
+                        runtime_module,
+                        execution_trace_module,
+                        module_wasm_backtraces,
+                    ),
                 ) = self.interpret_manifest::<T>(
                     &mut track,
                     executable,
@@ -317,6 +333,10 @@ where
                     println!("{:?}", interpretation_result);
                 }
 
+                if let Some(wasm_backtraces) = &mut wasm_backtraces {
+                    *wasm_backtraces = module_wasm_backtraces;
+                }
+
                 let fee_details = if execution_config.enable_cost_breakdown {
                     let execution_cost_breakdown = costing_module
                         .execution_cost_breakdown
@@ -498,6 +518,7 @@ where
             transaction_costing_parameters: executable.costing_parameters().clone(),
             fee_summary,
             fee_details,
+            wasm_backtraces,
             result,
             resources_usage,
         };
@@ -615,12 +636,15 @@ where
             CostingModule,
             TransactionRuntimeModule,
             ExecutionTraceModule,
+            Vec<WasmBacktrace>,
         ),
     ) {
         let mut id_allocator = IdAllocator::new(executable.intent_hash().to_hash());
         let system = SystemConfig {
             blueprint_cache: NonIterMap::new(),
             auth_cache: NonIterMap::new(),
+            blueprint_version_cache: NonIterMap::new(),
+            package_blueprint_upgrades: false,
             schema_cache: NonIterMap::new(),
             callback_obj: self.vm.clone(),
             modules: SystemModuleMixer::new(
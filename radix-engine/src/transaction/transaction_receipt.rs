@@ -9,6 +9,7 @@ use crate::track::NodeStateUpdates;
 use crate::track::PartitionStateUpdates;
 use crate::track::StateUpdates;
 use crate::transaction::SystemStructure;
+use crate::vm::wasm::WasmBacktrace;
 use colored::*;
 use radix_engine_interface::blueprints::transaction_processor::InstructionOutput;
 use radix_engine_store_interface::interface::DatabaseUpdate;
@@ -35,6 +36,9 @@ pub struct TransactionReceiptV1 {
     /// Transaction fee detail
     /// Available if `ExecutionConfig::enable_cost_breakdown` is enabled
     pub fee_details: Option<TransactionFeeDetails>,
+    /// The WASM backtraces of the blueprints which trapped, innermost call first
+    /// Available if `ExecutionConfig::enable_wasm_backtraces` is enabled
+    pub wasm_backtraces: Option<Vec<WasmBacktrace>>,
     /// Transaction result
     pub result: TransactionResult,
     /// Hardware resources usage report
@@ -362,6 +366,7 @@ impl TransactionReceipt {
             transaction_costing_parameters: Default::default(),
             fee_summary: Default::default(),
             fee_details: Default::default(),
+            wasm_backtraces: Default::default(),
             result: TransactionResult::Commit(commit_result),
             resources_usage: Default::default(),
         }
@@ -1,10 +1,26 @@
+use crate::blueprints::access_controller::{
+    AccessControllerNativePackage, AccessControllerV1MinorVersion,
+};
+use crate::blueprints::account::{AccountNativePackage, AccountV1MinorVersion};
 use crate::blueprints::consensus_manager::*;
+use crate::blueprints::identity::{IdentityNativePackage, IdentityV1MinorVersion};
 use crate::blueprints::models::KeyValueEntryContentSource;
 use crate::blueprints::package::*;
 use crate::blueprints::pool::v1::constants::*;
+use crate::blueprints::pool::v1::package::*;
+use crate::blueprints::resource::{ResourceNativePackage, ResourceV1MinorVersion};
 use crate::internal_prelude::*;
+use crate::system::attached_modules::royalty::{
+    ComponentRoyaltyCollection, RoyaltyNativePackage, RoyaltyV1MinorVersion,
+};
+use crate::system::system_callback::{SystemBoot, BOOT_LOADER_SYSTEM_SUBSTATE_FIELD_KEY};
 use crate::system::system_db_reader::{ObjectCollectionKey, SystemDatabaseReader};
 use crate::track::{NodeStateUpdates, PartitionStateUpdates, StateUpdates};
+use crate::vm::wasm::{
+    SCRPYTO_VM_CRYPTO_UTILS_MINOR_VERSION, SCRYPTO_VM_BULK_MEMORY_MINOR_VERSION,
+    SCRYPTO_VM_CRYPTO_UTILS_V2_MINOR_VERSION, SCRYPTO_VM_INDEXED_COLLECTIONS_MINOR_VERSION,
+    SCRYPTO_VM_KV_STORE_ITERATION_MINOR_VERSION,
+};
 use crate::vm::*;
 use radix_engine_common::constants::*;
 use radix_engine_common::crypto::hash;
@@ -12,6 +28,7 @@ use radix_engine_common::math::Decimal;
 use radix_engine_common::prelude::ScopedTypeId;
 use radix_engine_common::prelude::{scrypto_encode, ScryptoCustomTypeKind};
 use radix_engine_common::types::SubstateKey;
+use radix_engine_interface::api::node_modules::royalty::COMPONENT_ROYALTY_BLUEPRINT;
 use radix_engine_interface::api::ObjectModuleId;
 use radix_engine_interface::blueprints::consensus_manager::*;
 use radix_engine_interface::prelude::*;
@@ -22,8 +39,36 @@ use sbor::{generate_full_schema, TypeAggregator};
 use utils::indexmap;
 
 pub fn generate_vm_boot_scrypto_minor_version_state_updates() -> StateUpdates {
+    generate_vm_boot_state_updates(SCRPYTO_VM_CRYPTO_UTILS_MINOR_VERSION)
+}
+
+/// Generates the state updates required for enabling the second batch of crypto utils
+/// (Ed25519 and Secp256k1 signature verification, key recovery and Blake2b hashing)
+pub fn generate_vm_boot_crypto_utils_v2_state_updates() -> StateUpdates {
+    generate_vm_boot_state_updates(SCRYPTO_VM_CRYPTO_UTILS_V2_MINOR_VERSION)
+}
+
+/// Generates the state updates required for accepting the `memory.copy` and `memory.fill`
+/// instructions of the bulk memory proposal in Scrypto code
+pub fn generate_vm_boot_bulk_memory_state_updates() -> StateUpdates {
+    generate_vm_boot_state_updates(SCRYPTO_VM_BULK_MEMORY_MINOR_VERSION)
+}
+
+/// Generates the state updates required for enabling the index and sorted index collection
+/// host functions in Scrypto code
+pub fn generate_vm_boot_indexed_collections_state_updates() -> StateUpdates {
+    generate_vm_boot_state_updates(SCRYPTO_VM_INDEXED_COLLECTIONS_MINOR_VERSION)
+}
+
+/// Generates the state updates required for enabling the key value store scan and count
+/// host functions in Scrypto code
+pub fn generate_vm_boot_kv_store_iteration_state_updates() -> StateUpdates {
+    generate_vm_boot_state_updates(SCRYPTO_VM_KV_STORE_ITERATION_MINOR_VERSION)
+}
+
+fn generate_vm_boot_state_updates(scrypto_v1_minor_version: u64) -> StateUpdates {
     let substate = scrypto_encode(&VmBoot::V1 {
-        scrypto_v1_minor_version: 1u64,
+        scrypto_v1_minor_version,
     })
     .unwrap();
 
@@ -350,6 +395,374 @@ pub fn generate_pools_v1_1_state_updates<S: SubstateDatabase>(db: &S) -> StateUp
     }
 }
 
+/// Generates the state updates required to update the pool package from the v1.1 to the v1.2
+/// logic. The v1.2 package adds the constant product pool blueprint, so rather than patching the
+/// existing blueprint definitions, the whole v1.2 package structure is written over the existing
+/// one. It produces the following updates:
+///
+/// * Removes the old code_hash => vm_type substate.
+/// * Removes the old code_hash => original_code substate.
+/// * Adds the new code_hash => vm_type and code_hash => original_code substates.
+/// * Adds the schemas of all of the blueprints of the v1.2 package.
+/// * Sets the definition, dependencies, royalty config and auth config of all of the blueprints
+///   of the v1.2 package, which adds those of the constant product pool blueprint.
+pub fn generate_pools_v1_2_state_updates<S: SubstateDatabase>(db: &S) -> StateUpdates {
+    generate_native_package_structure_state_updates(
+        db,
+        POOL_PACKAGE,
+        PoolNativePackage::definition(PoolV1MinorVersion::Two),
+        POOL_V1_1_CODE_ID,
+        POOL_V1_2_CODE_ID,
+        None,
+        Default::default(),
+    )
+}
+
+/// Generates the state updates required to update the account package from the v1.0 to the v1.1
+/// definition, which adds the spending allowances. The logic of the existing methods did not
+/// change, so the code hash stays the same, but the definition, schema and auth config of the
+/// account blueprint are replaced with those of the v1.1 package.
+pub fn generate_account_allowances_state_updates<S: SubstateDatabase>(db: &S) -> StateUpdates {
+    generate_native_package_structure_state_updates(
+        db,
+        ACCOUNT_PACKAGE,
+        AccountNativePackage::definition(AccountV1MinorVersion::One),
+        ACCOUNT_CODE_ID,
+        ACCOUNT_CODE_ID,
+        None,
+        Default::default(),
+    )
+}
+
+/// Generates the state updates required to update the account package from the v1.1 to the v1.2
+/// definition and the identity package from the v1.0 to the v1.1 definition, which add the
+/// secp256r1 signature virtual badge to the dependencies of both blueprints. This is what enables
+/// the virtualization of secp256r1 accounts and identities, so the badge resource must have been
+/// created before these updates are committed.
+pub fn generate_secp256r1_signatures_state_updates<S: SubstateDatabase>(db: &S) -> StateUpdates {
+    let mut state_updates = generate_native_package_structure_state_updates(
+        db,
+        ACCOUNT_PACKAGE,
+        AccountNativePackage::definition(AccountV1MinorVersion::Two),
+        ACCOUNT_CODE_ID,
+        ACCOUNT_CODE_ID,
+        None,
+        Default::default(),
+    );
+    state_updates.by_node.extend(
+        generate_native_package_structure_state_updates(
+            db,
+            IDENTITY_PACKAGE,
+            IdentityNativePackage::definition(IdentityV1MinorVersion::One),
+            IDENTITY_CODE_ID,
+            IDENTITY_CODE_ID,
+            None,
+            Default::default(),
+        )
+        .by_node,
+    );
+    state_updates
+}
+
+/// Generates the state updates required to update the consensus manager package from the v1.0 to
+/// the v1.1 definition, which adds stake redelegation to the validator blueprint. Only the
+/// validator blueprint is written, as the definition of the consensus manager blueprint may have
+/// been modified by the seconds precision update.
+pub fn generate_validator_redelegation_state_updates<S: SubstateDatabase>(db: &S) -> StateUpdates {
+    generate_native_package_structure_state_updates(
+        db,
+        CONSENSUS_MANAGER_PACKAGE,
+        ConsensusManagerNativePackage::definition(ConsensusManagerV1MinorVersion::One),
+        CONSENSUS_MANAGER_CODE_ID,
+        CONSENSUS_MANAGER_CODE_ID,
+        Some(&[VALIDATOR_BLUEPRINT]),
+        Default::default(),
+    )
+}
+
+/// Generates the state updates required to update the access controller package from the v1.0 to
+/// the v1.1 definition, which adds the XRD fee vault used to pay for recovery transactions.
+pub fn generate_access_controller_xrd_fee_vault_state_updates<S: SubstateDatabase>(
+    db: &S,
+) -> StateUpdates {
+    generate_native_package_structure_state_updates(
+        db,
+        ACCESS_CONTROLLER_PACKAGE,
+        AccessControllerNativePackage::definition(AccessControllerV1MinorVersion::One),
+        ACCESS_CONTROLLER_CODE_ID,
+        ACCESS_CONTROLLER_CODE_ID,
+        None,
+        Default::default(),
+    )
+}
+
+/// Generates the state updates required to update the package package from the v1.0 to the v1.1
+/// definition, which adds opt-in blueprint upgrades to the package blueprint, and enables the
+/// resolution of blueprints through their upgrade configs in the system.
+pub fn generate_package_blueprint_upgrades_state_updates<S: SubstateDatabase>(
+    db: &S,
+) -> StateUpdates {
+    let mut state_updates = generate_native_package_structure_state_updates(
+        db,
+        PACKAGE_PACKAGE,
+        PackageNativePackage::definition(PackageV1MinorVersion::One),
+        PACKAGE_CODE_ID,
+        PACKAGE_CODE_ID,
+        None,
+        // Must match the system instructions the package package was bootstrapped with
+        btreemap! {
+            PACKAGE_BLUEPRINT.to_string() => vec![SystemInstruction::MapCollectionToPhysicalPartition {
+                collection_index: PackageCollection::SchemaKeyValue.collection_index(),
+                partition_num: SCHEMAS_PARTITION,
+            }],
+        },
+    );
+
+    let system_boot = scrypto_encode(&SystemBoot::V1 {
+        package_blueprint_upgrades: true,
+    })
+    .unwrap();
+    state_updates.by_node.insert(
+        TRANSACTION_TRACKER.into_node_id(),
+        NodeStateUpdates::Delta {
+            by_partition: indexmap! {
+                BOOT_LOADER_PARTITION => PartitionStateUpdates::Delta {
+                    by_substate: indexmap! {
+                        SubstateKey::Field(BOOT_LOADER_SYSTEM_SUBSTATE_FIELD_KEY) => DatabaseUpdate::Set(system_boot)
+                    }
+                },
+            },
+        },
+    );
+
+    state_updates
+}
+
+/// Generates the state updates required to update the package package from the v1.1 to the v1.2
+/// definition, which adds the splitting of package royalties between multiple recipients.
+pub fn generate_package_royalty_splits_state_updates<S: SubstateDatabase>(db: &S) -> StateUpdates {
+    generate_native_package_structure_state_updates(
+        db,
+        PACKAGE_PACKAGE,
+        PackageNativePackage::definition(PackageV1MinorVersion::Two),
+        PACKAGE_CODE_ID,
+        PACKAGE_CODE_ID,
+        None,
+        // Must match the system instructions the package package was bootstrapped with
+        btreemap! {
+            PACKAGE_BLUEPRINT.to_string() => vec![SystemInstruction::MapCollectionToPhysicalPartition {
+                collection_index: PackageCollection::SchemaKeyValue.collection_index(),
+                partition_num: SCHEMAS_PARTITION,
+            }],
+        },
+    )
+}
+
+/// Generates the state updates required to update the royalty package from the v1.0 to the v1.1
+/// definition, which adds the splitting of component royalties between multiple recipients.
+pub fn generate_component_royalty_splits_state_updates<S: SubstateDatabase>(
+    db: &S,
+) -> StateUpdates {
+    generate_native_package_structure_state_updates(
+        db,
+        ROYALTY_MODULE_PACKAGE,
+        RoyaltyNativePackage::definition(RoyaltyV1MinorVersion::One),
+        ROYALTY_CODE_ID,
+        ROYALTY_CODE_ID,
+        None,
+        // The split collections are mapped past the partitions of the other modules
+        btreemap! {
+            COMPONENT_ROYALTY_BLUEPRINT.to_string() => vec![
+                SystemInstruction::MapCollectionToPhysicalPartition {
+                    collection_index: ComponentRoyaltyCollection::MethodSplitKeyValue.collection_index(),
+                    partition_num: ROYALTY_SPLIT_PARTITION,
+                },
+                SystemInstruction::MapCollectionToPhysicalPartition {
+                    collection_index: ComponentRoyaltyCollection::SplitVaultKeyValue.collection_index(),
+                    partition_num: ROYALTY_SPLIT_VAULT_PARTITION,
+                },
+            ],
+        },
+    )
+}
+
+/// Generates the state updates required to update the resource package from the v1.0 to the v1.1
+/// definition, which adds the fungible vesting blueprint. Only that blueprint is written, as the
+/// definitions of the existing blueprints did not change.
+pub fn generate_fungible_vesting_state_updates<S: SubstateDatabase>(db: &S) -> StateUpdates {
+    generate_native_package_structure_state_updates(
+        db,
+        RESOURCE_PACKAGE,
+        ResourceNativePackage::definition(ResourceV1MinorVersion::One),
+        RESOURCE_CODE_ID,
+        RESOURCE_CODE_ID,
+        Some(&[FUNGIBLE_VESTING_BLUEPRINT]),
+        Default::default(),
+    )
+}
+
+/// Generates the state updates required to update the resource package from the v1.1 to the v1.2
+/// definition, which adds batch data updates to the non-fungible resource manager blueprint. Only
+/// that blueprint is written, as the definitions of the other blueprints did not change.
+pub fn generate_non_fungible_batch_data_updates_state_updates<S: SubstateDatabase>(
+    db: &S,
+) -> StateUpdates {
+    generate_native_package_structure_state_updates(
+        db,
+        RESOURCE_PACKAGE,
+        ResourceNativePackage::definition(ResourceV1MinorVersion::Two),
+        RESOURCE_CODE_ID,
+        RESOURCE_CODE_ID,
+        Some(&[NON_FUNGIBLE_RESOURCE_MANAGER_BLUEPRINT]),
+        Default::default(),
+    )
+}
+
+/// Generates the state updates which write the structure of a native package built from the given
+/// definition over that of an existing native package. The code of `old_code_id` is removed if it
+/// differs from `new_code_id`. If `blueprints` is given, only the definitions, dependencies,
+/// royalty and auth configs of those blueprints are written.
+fn generate_native_package_structure_state_updates<S: SubstateDatabase>(
+    db: &S,
+    package_address: PackageAddress,
+    definition: PackageDefinition,
+    old_code_id: u64,
+    new_code_id: u64,
+    blueprints: Option<&[&str]>,
+    system_instructions: BTreeMap<String, Vec<SystemInstruction>>,
+) -> StateUpdates {
+    let reader = SystemDatabaseReader::new(db);
+
+    let package_node_id = package_address.into_node_id();
+
+    let package_structure = PackageNativePackage::validate_and_build_package_structure(
+        definition,
+        VmType::Native,
+        new_code_id.to_be_bytes().to_vec(),
+        system_instructions,
+        &VmVersion::default(),
+    )
+    .unwrap_or_else(|err| panic!("Invalid native package definition: {:?}", err));
+
+    let partition_number = |collection: PackageCollection| {
+        reader
+            .get_partition_of_collection(
+                &package_node_id,
+                ObjectModuleId::Main,
+                collection.collection_index(),
+            )
+            .unwrap()
+    };
+
+    fn set_all<K: ScryptoEncode, V: ScryptoEncode>(
+        entries: impl IntoIterator<Item = (K, V)>,
+    ) -> IndexMap<SubstateKey, DatabaseUpdate> {
+        entries
+            .into_iter()
+            .map(|(key, value)| {
+                (
+                    SubstateKey::Map(scrypto_encode(&key).unwrap()),
+                    DatabaseUpdate::Set(scrypto_encode(&value).unwrap()),
+                )
+            })
+            .collect()
+    }
+
+    let PackageStructure {
+        mut definitions,
+        mut dependencies,
+        schemas,
+        vm_type,
+        original_code,
+        mut auth_configs,
+        mut package_royalties,
+        ..
+    } = package_structure;
+
+    if let Some(blueprints) = blueprints {
+        let is_included = |blueprint: &String| blueprints.contains(&blueprint.as_str());
+        definitions.retain(|blueprint, _| is_included(blueprint));
+        dependencies.retain(|blueprint, _| is_included(blueprint));
+        auth_configs.retain(|blueprint, _| is_included(blueprint));
+        package_royalties.retain(|blueprint, _| is_included(blueprint));
+    }
+
+    let mut vm_type_updates = index_map_new();
+    let mut original_code_updates = index_map_new();
+    if old_code_id != new_code_id {
+        let old_code_hash = CodeHash::from_hash(hash(old_code_id.to_be_bytes()));
+        let old_code_key = SubstateKey::Map(scrypto_encode(&old_code_hash).unwrap());
+        vm_type_updates.insert(old_code_key.clone(), DatabaseUpdate::Delete);
+        original_code_updates.insert(old_code_key, DatabaseUpdate::Delete);
+    }
+
+    vm_type_updates.extend(set_all(
+        vm_type
+            .into_iter()
+            .map(|(hash, payload)| (hash, payload.into_locked_substate())),
+    ));
+
+    original_code_updates.extend(set_all(
+        original_code
+            .into_iter()
+            .map(|(hash, payload)| (hash, payload.into_locked_substate())),
+    ));
+
+    StateUpdates {
+        by_node: indexmap! {
+            package_node_id => NodeStateUpdates::Delta {
+                by_partition: indexmap! {
+                    partition_number(PackageCollection::CodeVmTypeKeyValue) => PartitionStateUpdates::Delta {
+                        by_substate: vm_type_updates,
+                    },
+                    partition_number(PackageCollection::CodeOriginalCodeKeyValue) => PartitionStateUpdates::Delta {
+                        by_substate: original_code_updates,
+                    },
+                    partition_number(PackageCollection::SchemaKeyValue) => PartitionStateUpdates::Delta {
+                        by_substate: set_all(
+                            schemas
+                                .into_iter()
+                                .map(|(hash, payload)| (hash, payload.into_locked_substate())),
+                        ),
+                    },
+                    partition_number(PackageCollection::BlueprintVersionDefinitionKeyValue) => PartitionStateUpdates::Delta {
+                        by_substate: set_all(definitions.into_iter().map(|(blueprint, payload)| {
+                            (
+                                BlueprintVersionKey::new_default(blueprint),
+                                payload.into_locked_substate(),
+                            )
+                        })),
+                    },
+                    partition_number(PackageCollection::BlueprintVersionDependenciesKeyValue) => PartitionStateUpdates::Delta {
+                        by_substate: set_all(dependencies.into_iter().map(|(blueprint, payload)| {
+                            (
+                                BlueprintVersionKey::new_default(blueprint),
+                                payload.into_locked_substate(),
+                            )
+                        })),
+                    },
+                    partition_number(PackageCollection::BlueprintVersionRoyaltyConfigKeyValue) => PartitionStateUpdates::Delta {
+                        by_substate: set_all(package_royalties.into_iter().map(|(blueprint, payload)| {
+                            (
+                                BlueprintVersionKey::new_default(blueprint),
+                                payload.into_locked_substate(),
+                            )
+                        })),
+                    },
+                    partition_number(PackageCollection::BlueprintVersionAuthConfigKeyValue) => PartitionStateUpdates::Delta {
+                        by_substate: set_all(auth_configs.into_iter().map(|(blueprint, payload)| {
+                            (
+                                BlueprintVersionKey::new_default(blueprint),
+                                payload.into_locked_substate(),
+                            )
+                        })),
+                    },
+                }
+            }
+        },
+    }
+}
+
 pub fn generate_validator_fee_fix_state_updates<S: SubstateDatabase>(db: &S) -> StateUpdates {
     let reader = SystemDatabaseReader::new(db);
     let consensus_mgr_node_id = CONSENSUS_MANAGER.into_node_id();
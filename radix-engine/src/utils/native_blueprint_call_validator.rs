@@ -130,24 +130,36 @@ fn get_arguments_schema<'s>(
 
     let blueprint_schema = match invocation {
         Invocation::Function(package_address @ PACKAGE_PACKAGE, ref blueprint, _) => {
-            get_blueprint_schema(&PACKAGE_PACKAGE_DEFINITION, package_address, &blueprint)
-                .map(Some)?
+            get_blueprint_schema(
+                &PACKAGE_PACKAGE_DEFINITION_V1_2,
+                package_address,
+                &blueprint,
+            )
+            .map(Some)?
         }
         Invocation::Function(package_address @ RESOURCE_PACKAGE, ref blueprint, _) => {
-            get_blueprint_schema(&RESOURCE_PACKAGE_DEFINITION, package_address, blueprint)
-                .map(Some)?
+            get_blueprint_schema(
+                &RESOURCE_PACKAGE_DEFINITION_V1_2,
+                package_address,
+                blueprint,
+            )
+            .map(Some)?
         }
         Invocation::Function(package_address @ ACCOUNT_PACKAGE, ref blueprint, _) => {
-            get_blueprint_schema(&ACCOUNT_PACKAGE_DEFINITION, package_address, blueprint)
+            get_blueprint_schema(&ACCOUNT_PACKAGE_DEFINITION_V1_2, package_address, blueprint)
                 .map(Some)?
         }
         Invocation::Function(package_address @ IDENTITY_PACKAGE, ref blueprint, _) => {
-            get_blueprint_schema(&IDENTITY_PACKAGE_DEFINITION, package_address, blueprint)
-                .map(Some)?
+            get_blueprint_schema(
+                &IDENTITY_PACKAGE_DEFINITION_V1_1,
+                package_address,
+                blueprint,
+            )
+            .map(Some)?
         }
         Invocation::Function(package_address @ CONSENSUS_MANAGER_PACKAGE, ref blueprint, _) => {
             get_blueprint_schema(
-                &CONSENSUS_MANAGER_PACKAGE_DEFINITION,
+                &CONSENSUS_MANAGER_PACKAGE_DEFINITION_V1_1,
                 package_address,
                 blueprint,
             )
@@ -155,14 +167,14 @@ fn get_arguments_schema<'s>(
         }
         Invocation::Function(package_address @ ACCESS_CONTROLLER_PACKAGE, ref blueprint, _) => {
             get_blueprint_schema(
-                &ACCESS_CONTROLLER_PACKAGE_DEFINITION,
+                &ACCESS_CONTROLLER_PACKAGE_DEFINITION_V1_1,
                 package_address,
                 blueprint,
             )
             .map(Some)?
         }
         Invocation::Function(package_address @ POOL_PACKAGE, ref blueprint, _) => {
-            get_blueprint_schema(&POOL_PACKAGE_DEFINITION_V1_0, package_address, blueprint)
+            get_blueprint_schema(&POOL_PACKAGE_DEFINITION_V1_2, package_address, blueprint)
                 .map(Some)?
         }
         Invocation::Function(package_address @ TRANSACTION_PROCESSOR_PACKAGE, ref blueprint, _) => {
@@ -178,7 +190,7 @@ fn get_arguments_schema<'s>(
                 .map(Some)?
         }
         Invocation::Function(package_address @ ROYALTY_MODULE_PACKAGE, ref blueprint, _) => {
-            get_blueprint_schema(&ROYALTY_PACKAGE_DEFINITION, package_address, blueprint)
+            get_blueprint_schema(&ROYALTY_PACKAGE_DEFINITION_V1_1, package_address, blueprint)
                 .map(Some)?
         }
         Invocation::Function(
@@ -194,30 +206,32 @@ fn get_arguments_schema<'s>(
         Invocation::Function(..) => None,
         Invocation::Method(_, ModuleId::Main, _) | Invocation::DirectMethod(..) => {
             match entity_type {
-                EntityType::GlobalPackage => {
-                    PACKAGE_PACKAGE_DEFINITION.blueprints.get(PACKAGE_BLUEPRINT)
-                }
+                EntityType::GlobalPackage => PACKAGE_PACKAGE_DEFINITION_V1_2
+                    .blueprints
+                    .get(PACKAGE_BLUEPRINT),
 
-                EntityType::GlobalConsensusManager => CONSENSUS_MANAGER_PACKAGE_DEFINITION
+                EntityType::GlobalConsensusManager => CONSENSUS_MANAGER_PACKAGE_DEFINITION_V1_1
                     .blueprints
                     .get(CONSENSUS_MANAGER_BLUEPRINT),
-                EntityType::GlobalValidator => CONSENSUS_MANAGER_PACKAGE_DEFINITION
+                EntityType::GlobalValidator => CONSENSUS_MANAGER_PACKAGE_DEFINITION_V1_1
                     .blueprints
                     .get(VALIDATOR_BLUEPRINT),
 
                 EntityType::GlobalAccount
                 | EntityType::GlobalVirtualEd25519Account
-                | EntityType::GlobalVirtualSecp256k1Account => {
-                    ACCOUNT_PACKAGE_DEFINITION.blueprints.get(ACCOUNT_BLUEPRINT)
-                }
+                | EntityType::GlobalVirtualSecp256k1Account
+                | EntityType::GlobalVirtualSecp256r1Account => ACCOUNT_PACKAGE_DEFINITION_V1_2
+                    .blueprints
+                    .get(ACCOUNT_BLUEPRINT),
 
                 EntityType::GlobalIdentity
                 | EntityType::GlobalVirtualEd25519Identity
-                | EntityType::GlobalVirtualSecp256k1Identity => IDENTITY_PACKAGE_DEFINITION
+                | EntityType::GlobalVirtualSecp256k1Identity
+                | EntityType::GlobalVirtualSecp256r1Identity => IDENTITY_PACKAGE_DEFINITION_V1_1
                     .blueprints
                     .get(IDENTITY_BLUEPRINT),
 
-                EntityType::GlobalAccessController => ACCESS_CONTROLLER_PACKAGE_DEFINITION
+                EntityType::GlobalAccessController => ACCESS_CONTROLLER_PACKAGE_DEFINITION_V1_1
                     .blueprints
                     .get(ACCESS_CONTROLLER_BLUEPRINT),
 
@@ -230,21 +244,24 @@ fn get_arguments_schema<'s>(
                 EntityType::GlobalMultiResourcePool => POOL_PACKAGE_DEFINITION_V1_0
                     .blueprints
                     .get(MULTI_RESOURCE_POOL_BLUEPRINT_IDENT),
+                EntityType::GlobalConstantProductPool => POOL_PACKAGE_DEFINITION_V1_2
+                    .blueprints
+                    .get(CONSTANT_PRODUCT_POOL_BLUEPRINT_IDENT),
 
                 EntityType::GlobalTransactionTracker => TRANSACTION_TRACKER_PACKAGE_DEFINITION
                     .blueprints
                     .get(TRANSACTION_TRACKER_BLUEPRINT),
 
-                EntityType::GlobalFungibleResourceManager => RESOURCE_PACKAGE_DEFINITION
+                EntityType::GlobalFungibleResourceManager => RESOURCE_PACKAGE_DEFINITION_V1_2
                     .blueprints
                     .get(FUNGIBLE_RESOURCE_MANAGER_BLUEPRINT),
-                EntityType::GlobalNonFungibleResourceManager => RESOURCE_PACKAGE_DEFINITION
+                EntityType::GlobalNonFungibleResourceManager => RESOURCE_PACKAGE_DEFINITION_V1_2
                     .blueprints
                     .get(NON_FUNGIBLE_RESOURCE_MANAGER_BLUEPRINT),
-                EntityType::InternalFungibleVault => RESOURCE_PACKAGE_DEFINITION
+                EntityType::InternalFungibleVault => RESOURCE_PACKAGE_DEFINITION_V1_2
                     .blueprints
                     .get(FUNGIBLE_VAULT_BLUEPRINT),
-                EntityType::InternalNonFungibleVault => RESOURCE_PACKAGE_DEFINITION
+                EntityType::InternalNonFungibleVault => RESOURCE_PACKAGE_DEFINITION_V1_2
                     .blueprints
                     .get(NON_FUNGIBLE_VAULT_BLUEPRINT),
 
@@ -259,7 +276,7 @@ fn get_arguments_schema<'s>(
         Invocation::Method(_, ModuleId::RoleAssignment, _) => ROLE_ASSIGNMENT_PACKAGE_DEFINITION
             .blueprints
             .get(ROLE_ASSIGNMENT_BLUEPRINT),
-        Invocation::Method(_, ModuleId::Royalty, _) => ROYALTY_PACKAGE_DEFINITION
+        Invocation::Method(_, ModuleId::Royalty, _) => ROYALTY_PACKAGE_DEFINITION_V1_1
             .blueprints
             .get(COMPONENT_ROYALTY_BLUEPRINT),
     };
@@ -21,11 +21,14 @@ impl From<PrepareError> for ExtractSchemaError {
 }
 
 pub fn extract_definition(code: &[u8]) -> Result<PackageDefinition, ExtractSchemaError> {
-    let function_exports = WasmModule::init(code)
-        .and_then(WasmModule::to_bytes)?
-        .1
-        .into_iter()
-        .filter(|s| s.ends_with("_schema"));
+    let function_exports = WasmModule::init_with_features(
+        code,
+        WasmFeatureSet::for_minor_version(SCRYPTO_V1_LATEST_MINOR_VERSION),
+    )
+    .and_then(WasmModule::to_bytes)?
+    .1
+    .into_iter()
+    .filter(|s| s.ends_with("_schema"));
 
     // Validate WASM
     let validator = ScryptoV1WasmValidator::new(SCRYPTO_V1_LATEST_MINOR_VERSION);
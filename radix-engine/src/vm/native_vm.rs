@@ -159,6 +159,12 @@ impl<I: VmInvoke> VmInvoke for NativeVmInstance<I> {
                         PoolV1MinorVersion::One,
                         api,
                     ),
+                    POOL_V1_2_CODE_ID => PoolNativePackage::invoke_export(
+                        export_name,
+                        input,
+                        PoolV1MinorVersion::Two,
+                        api,
+                    ),
                     TRANSACTION_TRACKER_CODE_ID => {
                         TransactionTrackerNativePackage::invoke_export(export_name, input, api)
                     }
@@ -28,9 +28,17 @@ impl<W: WasmEngine> ScryptoVm<W> {
         package_address: &PackageAddress,
         code_hash: CodeHash,
         instrumented_code: &[u8],
+        enable_wasm_backtraces: bool,
     ) -> ScryptoVmInstance<W::WasmInstance> {
+        let instance = if enable_wasm_backtraces {
+            self.wasm_engine
+                .instantiate_with_backtraces(code_hash, instrumented_code)
+        } else {
+            self.wasm_engine.instantiate(code_hash, instrumented_code)
+        };
+
         ScryptoVmInstance {
-            instance: self.wasm_engine.instantiate(code_hash, instrumented_code),
+            instance,
             package_address: *package_address,
         }
     }
@@ -41,6 +49,13 @@ pub struct ScryptoVmInstance<I: WasmInstance> {
     package_address: PackageAddress,
 }
 
+impl<I: WasmInstance> ScryptoVmInstance<I> {
+    /// See `WasmInstance::take_trap_backtrace`.
+    pub fn take_trap_backtrace(&mut self) -> Result<Vec<WasmTrapFrame>, PrepareError> {
+        self.instance.take_trap_backtrace()
+    }
+}
+
 impl<I: WasmInstance> VmInvoke for ScryptoVmInstance<I> {
     #[trace_resources(log=self.package_address.is_native_package(), log=self.package_address.to_hex(), log=export_name)]
     fn invoke<Y, V>(
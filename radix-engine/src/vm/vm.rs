@@ -6,10 +6,14 @@ use crate::system::system_callback_api::SystemCallbackObject;
 use crate::system::system_substates::KeyValueEntrySubstate;
 use crate::track::BootStore;
 use crate::types::*;
-use crate::vm::wasm::{ScryptoV1WasmValidator, WasmEngine};
+#[cfg(feature = "std")]
+use crate::vm::wasm::WasmDiskCache;
+use crate::vm::wasm::{ScryptoV1WasmValidator, WasmBacktrace, WasmEngine};
 use crate::vm::{NativeVm, NativeVmExtension, ScryptoVm};
 use radix_engine_interface::api::field_api::LockFlags;
 use radix_engine_interface::api::ClientApi;
+#[cfg(feature = "std")]
+use radix_engine_interface::blueprints::package::CodeHash;
 
 pub const BOOT_LOADER_VM_SUBSTATE_FIELD_KEY: FieldKey = 2u8;
 
@@ -40,18 +44,31 @@ impl<'g, W: WasmEngine, E: NativeVmExtension> Clone for Vm<'g, W, E> {
 pub trait VmApi {
     /// Retrieve the current minor version of the Scrypto VM
     fn get_scrypto_minor_version(&self) -> u64;
+
+    /// Retrieve the persistent cache of instrumented code, if the Scrypto VM has one
+    #[cfg(feature = "std")]
+    fn get_wasm_disk_cache(&self) -> Option<&WasmDiskCache> {
+        None
+    }
 }
 
 /// Simple implementation of the VmAPI
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct VmVersion {
     scrypto_v1_minor_version: u64,
+    #[cfg(feature = "std")]
+    wasm_disk_cache: Option<WasmDiskCache>,
 }
 
 impl VmApi for VmVersion {
     fn get_scrypto_minor_version(&self) -> u64 {
         self.scrypto_v1_minor_version
     }
+
+    #[cfg(feature = "std")]
+    fn get_wasm_disk_cache(&self) -> Option<&WasmDiskCache> {
+        self.wasm_disk_cache.as_ref()
+    }
 }
 
 /// Boot Loader state for the VM Layer
@@ -80,6 +97,8 @@ impl<'g, W: WasmEngine + 'g, E: NativeVmExtension> SystemCallbackObject for Vm<'
                 scrypto_v1_minor_version,
             } => VmVersion {
                 scrypto_v1_minor_version,
+                #[cfg(feature = "std")]
+                wasm_disk_cache: self.scrypto_vm.wasm_engine.disk_cache().cloned(),
             },
         };
 
@@ -125,28 +144,7 @@ impl<'g, W: WasmEngine + 'g, E: NativeVmExtension> SystemCallbackObject for Vm<'
 
         let output = match vm_type.into_latest().vm_type {
             VmType::Native => {
-                let original_code = {
-                    let handle = api.kernel_open_substate_with_default(
-                        address.as_node_id(),
-                        MAIN_BASE_PARTITION
-                            .at_offset(PACKAGE_ORIGINAL_CODE_PARTITION_OFFSET)
-                            .unwrap(),
-                        &SubstateKey::Map(scrypto_encode(&export.code_hash).unwrap()),
-                        LockFlags::read_only(),
-                        Some(|| {
-                            let kv_entry = KeyValueEntrySubstate::<()>::default();
-                            IndexedScryptoValue::from_typed(&kv_entry)
-                        }),
-                        SystemLockData::default(),
-                    )?;
-                    let original_code = api.kernel_read_substate(handle)?;
-                    let original_code: PackageCodeOriginalCodeEntrySubstate =
-                        original_code.as_typed().unwrap();
-                    api.kernel_close_substate(handle)?;
-                    original_code
-                        .into_value()
-                        .unwrap_or_else(|| panic!("Original code not found: {:?}", export))
-                };
+                let original_code = Self::read_original_code(address, &export, api)?;
 
                 let mut vm_instance = api
                     .kernel_get_system()
@@ -184,25 +182,51 @@ impl<'g, W: WasmEngine + 'g, E: NativeVmExtension> SystemCallbackObject for Vm<'
                 };
 
                 let mut scrypto_vm_instance = {
-                    api.kernel_get_system()
-                        .callback_obj
-                        .scrypto_vm
-                        .create_instance(
-                            address,
-                            export.code_hash,
-                            &instrumented_code.instrumented_code,
-                        )
+                    let system = api.kernel_get_system();
+                    let enable_wasm_backtraces = system.modules.enable_wasm_backtraces;
+                    system.callback_obj.scrypto_vm.create_instance(
+                        address,
+                        export.code_hash,
+                        &instrumented_code.instrumented_code,
+                        enable_wasm_backtraces,
+                    )
                 };
 
                 api.consume_cost_units(ClientCostingEntry::PrepareWasmCode {
                     size: instrumented_code.instrumented_code.len(),
                 })?;
 
-                let output = {
-                    scrypto_vm_instance.invoke(export.export_name.as_str(), input, api, &vm_api)?
-                };
-
-                output
+                let output =
+                    scrypto_vm_instance.invoke(export.export_name.as_str(), input, api, &vm_api);
+
+                match output {
+                    Ok(output) => output,
+                    Err(error) if api.kernel_get_system().modules.enable_wasm_backtraces => {
+                        let backtrace = match scrypto_vm_instance.take_trap_backtrace() {
+                            Ok(frames) if frames.is_empty() => return Err(error),
+                            Ok(frames) => {
+                                let original_code =
+                                    Self::read_original_code(address, &export, api)?;
+                                WasmBacktrace::symbolize(
+                                    &original_code.into_latest().code,
+                                    &instrumented_code.instrumented_code,
+                                    &frames,
+                                )
+                            }
+                            Err(tag_error) => WasmBacktrace::unavailable(format!(
+                                "the code could not be rewritten to capture the call frames: {:?}",
+                                tag_error
+                            )),
+                        };
+                        let error = backtrace.attach_to(error);
+                        api.kernel_get_system()
+                            .modules
+                            .wasm_backtraces
+                            .push(backtrace);
+                        return Err(error);
+                    }
+                    Err(error) => return Err(error),
+                }
             }
         };
 
@@ -210,6 +234,38 @@ impl<'g, W: WasmEngine + 'g, E: NativeVmExtension> SystemCallbackObject for Vm<'
     }
 }
 
+impl<'g, W: WasmEngine + 'g, E: NativeVmExtension> Vm<'g, W, E> {
+    fn read_original_code<Y>(
+        address: &PackageAddress,
+        export: &PackageExport,
+        api: &mut Y,
+    ) -> Result<PackageCodeOriginalCodeEntryPayload, RuntimeError>
+    where
+        Y: KernelSubstateApi<SystemLockData>,
+    {
+        let handle = api.kernel_open_substate_with_default(
+            address.as_node_id(),
+            MAIN_BASE_PARTITION
+                .at_offset(PACKAGE_ORIGINAL_CODE_PARTITION_OFFSET)
+                .unwrap(),
+            &SubstateKey::Map(scrypto_encode(&export.code_hash).unwrap()),
+            LockFlags::read_only(),
+            Some(|| {
+                let kv_entry = KeyValueEntrySubstate::<()>::default();
+                IndexedScryptoValue::from_typed(&kv_entry)
+            }),
+            SystemLockData::default(),
+        )?;
+        let original_code = api.kernel_read_substate(handle)?;
+        let original_code: PackageCodeOriginalCodeEntrySubstate = original_code.as_typed().unwrap();
+        api.kernel_close_substate(handle)?;
+
+        Ok(original_code
+            .into_value()
+            .unwrap_or_else(|| panic!("Original code not found: {:?}", export)))
+    }
+}
+
 pub trait VmInvoke {
     // TODO: Remove KernelNodeAPI + KernelSubstateAPI from api, unify with VmApi
     fn invoke<Y, V>(
@@ -227,6 +283,56 @@ pub trait VmInvoke {
 pub struct VmPackageValidation;
 
 impl VmPackageValidation {
+    /// Validates and instruments the code, reusing the instrumented code from a previous run if
+    /// the same code has already been published with the same blueprints.
+    ///
+    /// Only successfully validated code is cached, so invalid code is always rejected.
+    #[allow(unused_variables)]
+    fn validate_wasm<V: VmApi>(
+        definition: &PackageDefinition,
+        code: &[u8],
+        minor_version: u64,
+        vm_api: &V,
+    ) -> Result<Vec<u8>, RuntimeError> {
+        #[cfg(feature = "std")]
+        let cache_key = vm_api.get_wasm_disk_cache().map(|disk_cache| {
+            (
+                disk_cache,
+                CodeHash::from_hash(hash(code)),
+                hash(scrypto_encode(&definition.blueprints).unwrap()),
+            )
+        });
+        #[cfg(feature = "std")]
+        if let Some((disk_cache, code_hash, blueprints_hash)) = &cache_key {
+            if let Some(instrumented_code) =
+                disk_cache.get_instrumented_code(code_hash, minor_version, blueprints_hash)
+            {
+                return Ok(instrumented_code);
+            }
+        }
+
+        let instrumented_code = ScryptoV1WasmValidator::new(minor_version)
+            .validate(code, definition.blueprints.values())
+            .map_err(|e| {
+                RuntimeError::ApplicationError(ApplicationError::PackageError(
+                    PackageError::InvalidWasm(e),
+                ))
+            })?
+            .0;
+
+        #[cfg(feature = "std")]
+        if let Some((disk_cache, code_hash, blueprints_hash)) = cache_key {
+            disk_cache.put_instrumented_code(
+                &code_hash,
+                minor_version,
+                blueprints_hash,
+                &instrumented_code,
+            );
+        }
+
+        Ok(instrumented_code)
+    }
+
     pub fn validate<V: VmApi>(
         definition: &PackageDefinition,
         vm_type: VmType,
@@ -239,14 +345,8 @@ impl VmPackageValidation {
                 let minor_version = vm_api.get_scrypto_minor_version();
 
                 // Validate WASM
-                let instrumented_code = ScryptoV1WasmValidator::new(minor_version)
-                    .validate(&code, definition.blueprints.values())
-                    .map_err(|e| {
-                        RuntimeError::ApplicationError(ApplicationError::PackageError(
-                            PackageError::InvalidWasm(e),
-                        ))
-                    })?
-                    .0;
+                let instrumented_code =
+                    Self::validate_wasm(definition, code, minor_version, vm_api)?;
 
                 for BlueprintDefinitionInit {
                     is_transient,
@@ -0,0 +1,1053 @@
+use super::bulk_memory::{encode_u32, OPCODE_CALL};
+use super::profiling::{encode_i64, tag_wasm_code, OPCODE_I64_CONST};
+use crate::errors::{ApplicationError, RuntimeError, VmError};
+use crate::types::*;
+use crate::vm::wasm::constants::*;
+use crate::vm::wasm::{PrepareError, WasmRuntimeError};
+use sbor::rust::ops::Range;
+use wasmparser::{FunctionBody, Name, NameSectionReader, Operator, Parser, Payload, TypeRef};
+
+/// Set in the units consumed by the markers of `tag_wasm_call_frames`, which never consume
+/// that many units otherwise (and which the profiling tags never reach either).
+const CALL_FRAME_TAG: u64 = 1 << 63;
+const CALL_FRAME_TAG_CALL: u64 = 1 << 62;
+const CALL_FRAME_TAG_RETURN: u64 = 1 << 61;
+
+/// A frame of the WASM call stack at the time of a trap, as captured by an engine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WasmTrapFrame {
+    /// The index of the function within the instrumented code.
+    pub function_index: u32,
+    /// The offset (within the instrumented code) of the instruction the frame was executing -
+    /// the trapping instruction for the innermost frame, and the pending call for the others.
+    pub code_offset: Option<u32>,
+}
+
+/// A call frame event reported by `tag_wasm_call_frames`-rewritten code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WasmCallFrameTag {
+    /// The function with the given index (within the instrumented code) has been entered.
+    Enter(u32),
+    /// The current function is about to make the call at the given offset.
+    Call(u32),
+    /// The pending call of the current function has returned.
+    Return,
+}
+
+impl WasmCallFrameTag {
+    fn tag(self) -> u64 {
+        match self {
+            Self::Enter(function_index) => CALL_FRAME_TAG | function_index as u64,
+            Self::Call(code_offset) => CALL_FRAME_TAG | CALL_FRAME_TAG_CALL | code_offset as u64,
+            Self::Return => CALL_FRAME_TAG | CALL_FRAME_TAG_RETURN,
+        }
+    }
+
+    /// Returns the event reported through the given units, or `None` if they are actual
+    /// WASM execution units.
+    pub fn untag(tagged_units: u64) -> Option<Self> {
+        if tagged_units & CALL_FRAME_TAG == 0 {
+            None
+        } else if tagged_units & CALL_FRAME_TAG_CALL != 0 {
+            Some(Self::Call(tagged_units as u32))
+        } else if tagged_units & CALL_FRAME_TAG_RETURN != 0 {
+            Some(Self::Return)
+        } else {
+            Some(Self::Enter(tagged_units as u32))
+        }
+    }
+
+    /// Applies the event to the call stack of an instance, outermost frame first.
+    pub fn apply(self, frames: &mut Vec<WasmTrapFrame>) {
+        match self {
+            Self::Enter(function_index) => frames.push(WasmTrapFrame {
+                function_index,
+                code_offset: None,
+            }),
+            Self::Call(code_offset) => {
+                if let Some(frame) = frames.last_mut() {
+                    frame.code_offset = Some(code_offset);
+                }
+            }
+            Self::Return => {
+                // Only the callee can have no pending call by now (a call to an imported
+                // function has no frame of its own)
+                if frames
+                    .last()
+                    .is_some_and(|frame| frame.code_offset.is_none())
+                {
+                    frames.pop();
+                }
+                if let Some(frame) = frames.last_mut() {
+                    frame.code_offset = None;
+                }
+            }
+        }
+    }
+}
+
+/// Rewrites the instrumented code, so that every function reports being entered, and every
+/// call reports being made and returning, through the number of WASM execution units consumed
+/// by injected markers, see `WasmCallFrameTag`.
+///
+/// This lets engines which can't walk the stack of a trap (eg. `wasmi`) capture it, and the
+/// rewritten code must only be run by an engine which handles the markers.
+pub fn tag_wasm_call_frames(code: &[u8]) -> Result<Vec<u8>, PrepareError> {
+    tag_wasm_code(code, false, true)
+}
+
+pub(super) fn wasm_call_frame_tags(
+    code: &[u8],
+) -> Result<Vec<(Range<usize>, Vec<u8>)>, PrepareError> {
+    let mut gas_function_index = None;
+    let mut num_of_imported_functions = 0u32;
+    let mut defined_function_index = 0u32;
+    let mut replacements: Vec<(Range<usize>, Vec<u8>)> = Vec::new();
+
+    for payload in Parser::new(0).parse_all(code) {
+        match payload.map_err(|err| PrepareError::WasmParserError(err.to_string()))? {
+            Payload::ImportSection(reader) => {
+                for import in reader {
+                    let import =
+                        import.map_err(|err| PrepareError::WasmParserError(err.to_string()))?;
+                    if let TypeRef::Func(_) = import.ty {
+                        if import.module == MODULE_ENV_NAME
+                            && import.name == COSTING_CONSUME_WASM_EXECUTION_UNITS_FUNCTION_NAME
+                        {
+                            gas_function_index = Some(num_of_imported_functions);
+                        }
+                        num_of_imported_functions += 1;
+                    }
+                }
+            }
+            Payload::CodeSectionEntry(body) => {
+                let Some(gas_function_index) = gas_function_index else {
+                    return Ok(Vec::new());
+                };
+                let marker = |tag: WasmCallFrameTag| {
+                    let mut encoded = vec![OPCODE_I64_CONST];
+                    encoded.extend(encode_i64(tag.tag() as i64));
+                    encoded.push(OPCODE_CALL);
+                    encoded.extend(encode_u32(gas_function_index));
+                    encoded
+                };
+
+                let mut reader = body
+                    .get_operators_reader()
+                    .map_err(|err| PrepareError::WasmParserError(err.to_string()))?;
+                let body_start = reader.original_position();
+                replacements.push((
+                    body_start..body_start,
+                    marker(WasmCallFrameTag::Enter(
+                        num_of_imported_functions + defined_function_index,
+                    )),
+                ));
+                while !reader.eof() {
+                    let start = reader.original_position();
+                    let op = reader
+                        .read()
+                        .map_err(|err| PrepareError::WasmParserError(err.to_string()))?;
+                    let end = reader.original_position();
+                    match op {
+                        Operator::Call { function_index }
+                            if function_index == gas_function_index => {}
+                        Operator::Call { .. } | Operator::CallIndirect { .. } => {
+                            replacements
+                                .push((start..start, marker(WasmCallFrameTag::Call(start as u32))));
+                            replacements.push((end..end, marker(WasmCallFrameTag::Return)));
+                        }
+                        _ => {}
+                    }
+                }
+                defined_function_index += 1;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(replacements)
+}
+
+/// A single symbolized frame of a WASM backtrace.
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct WasmBacktraceFrame {
+    /// The index of the function within the original (non-instrumented) code.
+    pub function_index: u32,
+    /// The demangled function name, if the code contains a `name` section.
+    pub function_name: Option<String>,
+    /// The source location of the frame, if the code contains DWARF debug info.
+    pub location: Option<WasmSourceLocation>,
+}
+
+impl WasmBacktraceFrame {
+    /// Symbolizes the given defined (ie. non-imported) functions of the original code, located
+    /// at their start. Indices with no counterpart in the original code (eg. functions added by
+    /// the instrumentation) resolve to `None`.
+    pub fn resolve_defined(original_code: &[u8], defined_indices: &[u32]) -> Vec<Option<Self>> {
+        let original = WasmDebugInfo::parse(original_code);
+
+        let known_functions: Vec<(u32, u64)> = defined_indices
+            .iter()
+            .filter_map(|index| {
+                original
+                    .function_body_offsets
+                    .get(*index as usize)
+                    .map(|address| (*index, *address))
+            })
+            .collect();
+        let mut frames = original.frames(&known_functions).into_iter();
+
+        defined_indices
+            .iter()
+            .map(|index| {
+                if (*index as usize) < original.function_body_offsets.len() {
+                    frames.next()
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Symbolizes the instructions at the given offsets of the instrumented code, as frames of the
+    /// defined functions they are within, located at the matching instructions of the original
+    /// code.
+    ///
+    /// Returns the index of the defined function each instruction is within (if any), along with
+    /// its frame - `None` for the functions added by the instrumentation.
+    pub fn resolve_instructions(
+        original_code: &[u8],
+        instrumented_code: &[u8],
+        offsets: &[u32],
+    ) -> Vec<Option<(u32, Option<Self>)>> {
+        let original = WasmDebugInfo::parse(original_code);
+        let instrumented = WasmDebugInfo::parse(instrumented_code);
+        let num_of_original_functions = original.function_body_offsets.len();
+
+        let defined_indices: Vec<Option<u32>> = offsets
+            .iter()
+            .map(|offset| instrumented.defined_function_at(*offset as usize))
+            .collect();
+        let known_functions: Vec<(u32, u64)> = offsets
+            .iter()
+            .zip(&defined_indices)
+            .filter_map(|(offset, defined_index)| {
+                let defined_index = (*defined_index)?;
+                let function_start = *original.function_body_offsets.get(defined_index as usize)?;
+                let address = instrumented
+                    .original_operator_ordinal(
+                        defined_index,
+                        *offset as usize,
+                        original.num_of_globals,
+                    )
+                    .and_then(|ordinal| original.operator_address(defined_index, ordinal))
+                    .unwrap_or(function_start);
+                Some((defined_index, address))
+            })
+            .collect();
+        let mut frames = original.frames(&known_functions).into_iter();
+
+        defined_indices
+            .into_iter()
+            .map(|defined_index| {
+                let defined_index = defined_index?;
+                let frame = if (defined_index as usize) < num_of_original_functions {
+                    frames.next()
+                } else {
+                    None
+                };
+                Some((defined_index, frame))
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, ScryptoSbor)]
+pub struct WasmSourceLocation {
+    pub file: String,
+    pub line: Option<u32>,
+}
+
+impl fmt::Display for WasmSourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{}", self.file, line),
+            None => write!(f, "{}", self.file),
+        }
+    }
+}
+
+/// The WASM call stack captured at the time a Scrypto blueprint trapped, innermost frame first.
+///
+/// This is a debugging aid only, and is never part of consensus - the frames are captured
+/// only if `ExecutionConfig::enable_wasm_backtraces` is set.
+#[derive(Debug, Clone, PartialEq, Eq, Default, ScryptoSbor)]
+pub struct WasmBacktrace {
+    pub frames: Vec<WasmBacktraceFrame>,
+    /// Why the frames could not be captured, if they couldn't.
+    pub unavailable_reason: Option<String>,
+}
+
+impl WasmBacktrace {
+    /// Symbolizes the frames captured while running the instrumented code, using the
+    /// debug information of the original code.
+    ///
+    /// Instrumentation injects host function imports (which shifts the indices of all the
+    /// defined functions), appends stack limiter thunks (which have no counterpart in the
+    /// original code, and are skipped) and injects code (which shifts the offsets of the
+    /// instructions). A frame is thus located at the original call matching its pending
+    /// call, or at the start of its function when that's unknown (eg. for the trapping frame).
+    pub fn symbolize(
+        original_code: &[u8],
+        instrumented_code: &[u8],
+        trap_frames: &[WasmTrapFrame],
+    ) -> Self {
+        let original = WasmDebugInfo::parse(original_code);
+        let instrumented = WasmDebugInfo::parse(instrumented_code);
+        let num_of_original_functions = original.function_body_offsets.len();
+
+        let functions: Vec<(u32, u64)> = trap_frames
+            .iter()
+            .filter_map(|frame| {
+                let defined_index = frame
+                    .function_index
+                    .checked_sub(instrumented.num_of_imports)?;
+                let function_start = *original.function_body_offsets.get(defined_index as usize)?;
+                let address = frame
+                    .code_offset
+                    .and_then(|offset| {
+                        instrumented.original_call_ordinal(
+                            defined_index,
+                            offset as usize,
+                            num_of_original_functions,
+                        )
+                    })
+                    .and_then(|ordinal| original.call_sites[defined_index as usize].get(ordinal))
+                    .map(|call_site| call_site.address)
+                    .unwrap_or(function_start);
+                Some((defined_index, address))
+            })
+            .collect();
+
+        Self {
+            frames: original.frames(&functions),
+            unavailable_reason: None,
+        }
+    }
+
+    /// A backtrace which could not be captured, for the given reason.
+    pub fn unavailable(reason: String) -> Self {
+        Self {
+            frames: Vec::new(),
+            unavailable_reason: Some(reason),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty() && self.unavailable_reason.is_none()
+    }
+
+    /// Appends the backtrace to the message of a panic or a trap, leaving other errors intact.
+    pub fn attach_to(&self, error: RuntimeError) -> RuntimeError {
+        if self.is_empty() {
+            return error;
+        }
+
+        match error {
+            RuntimeError::ApplicationError(ApplicationError::PanicMessage(message)) => {
+                RuntimeError::ApplicationError(ApplicationError::PanicMessage(format!(
+                    "{}\n{}",
+                    message, self
+                )))
+            }
+            RuntimeError::VmError(VmError::Wasm(WasmRuntimeError::ExecutionError(message))) => {
+                RuntimeError::VmError(VmError::Wasm(WasmRuntimeError::ExecutionError(format!(
+                    "{}\n{}",
+                    message, self
+                ))))
+            }
+            error => error,
+        }
+    }
+}
+
+impl fmt::Display for WasmBacktrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(reason) = &self.unavailable_reason {
+            return write!(f, "WASM backtrace unavailable: {}", reason);
+        }
+        write!(f, "WASM backtrace:")?;
+        for (i, frame) in self.frames.iter().enumerate() {
+            match &frame.function_name {
+                Some(name) => write!(f, "\n  {}: {}", i, name)?,
+                None => write!(f, "\n  {}: <function #{}>", i, frame.function_index)?,
+            }
+            if let Some(location) = &frame.location {
+                write!(f, "\n        at {}", location)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A call instruction of a function body.
+struct WasmCallSite {
+    /// The offset of the instruction within the code.
+    offset: usize,
+    /// The offset of the instruction relative to the start of the code section.
+    address: u64,
+    /// The index of the called function, unless called indirectly.
+    target: Option<u32>,
+}
+
+struct WasmDebugInfo<'a> {
+    num_of_imports: u32,
+    /// The number of globals, imported and defined.
+    num_of_globals: u32,
+    gas_function_index: Option<u32>,
+    function_names: IndexMap<u32, String>,
+    /// The offset of each defined function body, relative to the start of the code section
+    /// (which is how DWARF addresses WASM code).
+    function_body_offsets: Vec<u64>,
+    function_bodies: Vec<FunctionBody<'a>>,
+    code_section_start: usize,
+    /// The call instructions of each defined function, in order.
+    call_sites: Vec<Vec<WasmCallSite>>,
+    #[cfg_attr(not(feature = "wasm_backtrace_dwarf"), allow(dead_code))]
+    debug_sections: IndexMap<&'a str, &'a [u8]>,
+}
+
+impl<'a> WasmDebugInfo<'a> {
+    /// Parses whatever is available, ignoring malformed sections - the code has been
+    /// validated on publishing, and the debug info is best-effort.
+    fn parse(code: &'a [u8]) -> Self {
+        let mut info = Self {
+            num_of_imports: 0,
+            num_of_globals: 0,
+            gas_function_index: None,
+            function_names: index_map_new(),
+            function_body_offsets: Vec::new(),
+            function_bodies: Vec::new(),
+            code_section_start: 0,
+            call_sites: Vec::new(),
+            debug_sections: index_map_new(),
+        };
+        for payload in Parser::new(0).parse_all(code) {
+            let Ok(payload) = payload else {
+                break;
+            };
+            match payload {
+                Payload::ImportSection(reader) => {
+                    for import in reader.into_iter().flatten() {
+                        if let TypeRef::Func(_) = import.ty {
+                            if import.module == MODULE_ENV_NAME
+                                && import.name == COSTING_CONSUME_WASM_EXECUTION_UNITS_FUNCTION_NAME
+                            {
+                                info.gas_function_index = Some(info.num_of_imports);
+                            }
+                            info.num_of_imports += 1;
+                        }
+                        if let TypeRef::Global(_) = import.ty {
+                            info.num_of_globals += 1;
+                        }
+                    }
+                }
+                Payload::GlobalSection(reader) => {
+                    info.num_of_globals += reader.count();
+                }
+                Payload::CodeSectionStart { range, .. } => {
+                    info.code_section_start = range.start;
+                }
+                Payload::CodeSectionEntry(body) => {
+                    let code_section_start = info.code_section_start;
+                    info.function_body_offsets
+                        .push((body.range().start - code_section_start) as u64);
+                    info.function_bodies.push(body.clone());
+
+                    let mut call_sites = Vec::new();
+                    if let Ok(mut reader) = body.get_operators_reader() {
+                        while !reader.eof() {
+                            let offset = reader.original_position();
+                            let target = match reader.read() {
+                                Ok(Operator::Call { function_index }) => Some(function_index),
+                                Ok(Operator::CallIndirect { .. }) => None,
+                                Ok(_) => continue,
+                                Err(_) => break,
+                            };
+                            call_sites.push(WasmCallSite {
+                                offset,
+                                address: (offset - code_section_start) as u64,
+                                target,
+                            });
+                        }
+                    }
+                    info.call_sites.push(call_sites);
+                }
+                Payload::CustomSection(reader) if reader.name() == "name" => {
+                    let names = NameSectionReader::new(reader.data(), reader.data_offset());
+                    for name in names.into_iter().flatten() {
+                        if let Name::Function(map) = name {
+                            for naming in map.into_iter().flatten() {
+                                info.function_names
+                                    .insert(naming.index, naming.name.to_string());
+                            }
+                        }
+                    }
+                }
+                Payload::CustomSection(reader) if reader.name().starts_with(".debug_") => {
+                    info.debug_sections.insert(reader.name(), reader.data());
+                }
+                _ => {}
+            }
+        }
+
+        info
+    }
+
+    /// Returns the position of the call at the given offset among the calls of the defined
+    /// function which are in the original code too - ie. other than the calls injected by the
+    /// instrumentation, to consume WASM execution units or to appended functions.
+    fn original_call_ordinal(
+        &self,
+        defined_index: u32,
+        offset: usize,
+        num_of_original_functions: usize,
+    ) -> Option<usize> {
+        let is_original = |call_site: &&WasmCallSite| match call_site.target {
+            Some(target) if Some(target) == self.gas_function_index => false,
+            Some(target) => match target.checked_sub(self.num_of_imports) {
+                Some(defined_target) => (defined_target as usize) < num_of_original_functions,
+                None => true,
+            },
+            None => true,
+        };
+
+        let call_sites = self.call_sites.get(defined_index as usize)?;
+        let position = call_sites
+            .iter()
+            .position(|call_site| call_site.offset == offset)?;
+        if !is_original(&&call_sites[position]) {
+            return None;
+        }
+        Some(call_sites[..position].iter().filter(is_original).count())
+    }
+
+    /// Returns the index of the defined function whose body contains the given offset.
+    fn defined_function_at(&self, offset: usize) -> Option<u32> {
+        self.function_bodies
+            .iter()
+            .position(|body| body.range().contains(&offset))
+            .map(|index| index as u32)
+    }
+
+    /// Returns the instructions of the defined function, with their offsets within the code.
+    fn operators(&self, defined_index: u32) -> Vec<(usize, Operator<'a>)> {
+        let mut operators = Vec::new();
+        let Some(Ok(mut reader)) = self
+            .function_bodies
+            .get(defined_index as usize)
+            .map(|body| body.get_operators_reader())
+        else {
+            return operators;
+        };
+        while !reader.eof() {
+            let offset = reader.original_position();
+            let Ok(op) = reader.read() else {
+                break;
+            };
+            operators.push((offset, op));
+        }
+        operators
+    }
+
+    /// Returns the position of the first instruction at or after the given offset, among the
+    /// instructions of the defined function which are in the original code too - ie. skipping
+    /// the code injected by the instrumentation to consume WASM execution units, and to limit
+    /// the stack height (which uses a global beyond the original ones).
+    fn original_operator_ordinal(
+        &self,
+        defined_index: u32,
+        offset: usize,
+        num_of_original_globals: u32,
+    ) -> Option<usize> {
+        let operators = self.operators(defined_index);
+        let mut ordinal = 0;
+        let mut i = 0;
+        while i < operators.len() {
+            let injected_len = match &operators[i].1 {
+                Operator::I64Const { .. }
+                    if matches!(
+                        operators.get(i + 1),
+                        Some((_, Operator::Call { function_index }))
+                            if Some(*function_index) == self.gas_function_index
+                    ) =>
+                {
+                    2
+                }
+                // The stack height is updated by `global.get`..`global.set` sequences, and
+                // checked by a `global.get`..`if unreachable end` sequence
+                Operator::GlobalGet { global_index }
+                    if *global_index >= num_of_original_globals =>
+                {
+                    operators[i..]
+                        .iter()
+                        .position(|(_, op)| {
+                            matches!(op, Operator::GlobalSet { .. } | Operator::End)
+                        })
+                        .map_or(operators.len() - i, |position| position + 1)
+                }
+                _ => 0,
+            };
+            if injected_len > 0 {
+                i += injected_len;
+                continue;
+            }
+            if operators[i].0 >= offset {
+                return Some(ordinal);
+            }
+            ordinal += 1;
+            i += 1;
+        }
+        None
+    }
+
+    /// Returns the address of the instruction at the given position within the defined function.
+    fn operator_address(&self, defined_index: u32, ordinal: usize) -> Option<u64> {
+        let (offset, _) = self.operators(defined_index).into_iter().nth(ordinal)?;
+        Some((offset - self.code_section_start) as u64)
+    }
+
+    /// Symbolizes the given defined functions, located at the given addresses.
+    fn frames(&self, functions: &[(u32, u64)]) -> Vec<WasmBacktraceFrame> {
+        let addresses: Vec<u64> = functions.iter().map(|(_, address)| *address).collect();
+        functions
+            .iter()
+            .zip(self.locations(&addresses))
+            .map(|((defined_index, _), location)| {
+                let function_index = self.num_of_imports + defined_index;
+                WasmBacktraceFrame {
+                    function_index,
+                    function_name: self
+                        .function_names
+                        .get(&function_index)
+                        .map(|name| demangle(name)),
+                    location,
+                }
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "wasm_backtrace_dwarf")]
+    fn locations(&self, addresses: &[u64]) -> Vec<Option<WasmSourceLocation>> {
+        use addr2line::gimli;
+
+        let dwarf = gimli::Dwarf::load(|id| -> Result<_, gimli::Error> {
+            let data = self
+                .debug_sections
+                .get(id.name())
+                .copied()
+                .unwrap_or_default();
+            Ok(gimli::EndianSlice::new(data, gimli::LittleEndian))
+        });
+        let Some(context) = dwarf
+            .ok()
+            .and_then(|dwarf| addr2line::Context::from_dwarf(dwarf).ok())
+        else {
+            return vec![None; addresses.len()];
+        };
+
+        addresses
+            .iter()
+            .map(|address| {
+                let location = context.find_location(*address).ok()??;
+                Some(WasmSourceLocation {
+                    file: location.file?.to_string(),
+                    line: location.line,
+                })
+            })
+            .collect()
+    }
+
+    #[cfg(not(feature = "wasm_backtrace_dwarf"))]
+    fn locations(&self, addresses: &[u64]) -> Vec<Option<WasmSourceLocation>> {
+        vec![None; addresses.len()]
+    }
+}
+
+/// Demangles a legacy Rust symbol (eg. `_ZN4core9panicking5panic17h0123456789abcdefE`),
+/// dropping the trailing hash. Other names are returned as they are.
+pub fn demangle(name: &str) -> String {
+    let Some(mut rest) = name
+        .strip_prefix("_ZN")
+        .and_then(|name| name.strip_suffix('E'))
+    else {
+        return name.to_string();
+    };
+
+    let mut segments = Vec::new();
+    while !rest.is_empty() {
+        let len_digits = rest.bytes().take_while(|b| b.is_ascii_digit()).count();
+        let Ok(len) = rest[..len_digits].parse::<usize>() else {
+            return name.to_string();
+        };
+        let Some(segment) = rest.get(len_digits..len_digits + len) else {
+            return name.to_string();
+        };
+        segments.push(segment);
+        rest = &rest[len_digits + len..];
+    }
+
+    if let Some(last) = segments.last() {
+        if last.len() == 17
+            && last.starts_with('h')
+            && last[1..].bytes().all(|b| b.is_ascii_hexdigit())
+        {
+            segments.pop();
+        }
+    }
+
+    segments
+        .into_iter()
+        .map(demangle_segment)
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+fn demangle_segment(segment: &str) -> String {
+    // A leading `_` is only added to escape a segment starting with `$`
+    let mut rest = match segment.strip_prefix('_') {
+        Some(stripped) if stripped.starts_with('$') => stripped,
+        _ => segment,
+    };
+
+    let mut output = String::new();
+    while !rest.is_empty() {
+        if let Some(stripped) = rest.strip_prefix("..") {
+            output.push_str("::");
+            rest = stripped;
+        } else if rest.starts_with('$') {
+            let Some(end) = rest[1..].find('$') else {
+                output.push_str(rest);
+                break;
+            };
+            let escape = &rest[1..end + 1];
+            let decoded = match escape {
+                "SP" => Some('@'),
+                "BP" => Some('*'),
+                "RF" => Some('&'),
+                "LT" => Some('<'),
+                "GT" => Some('>'),
+                "LP" => Some('('),
+                "RP" => Some(')'),
+                "C" => Some(','),
+                _ => escape
+                    .strip_prefix('u')
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .and_then(char::from_u32),
+            };
+            match decoded {
+                Some(c) => output.push(c),
+                None => output.push_str(&rest[..end + 2]),
+            }
+            rest = &rest[end + 2..];
+        } else {
+            let c = rest.chars().next().unwrap();
+            output.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_demangle() {
+        assert_eq!(
+            demangle("_ZN4core9panicking5panic17h0123456789abcdefE"),
+            "core::panicking::panic"
+        );
+        assert_eq!(
+            demangle("_ZN60_$LT$alloc..string..String$u20$as$u20$core..fmt..Display$GT$3fmt17h0123456789abcdefE"),
+            "<alloc::string::String as core::fmt::Display>::fmt"
+        );
+        assert_eq!(demangle("my_blueprint_fn"), "my_blueprint_fn");
+        assert_eq!(demangle("_ZN3foo"), "_ZN3foo");
+    }
+
+    #[test]
+    fn test_symbolize_maps_instrumented_indices_to_original_names() {
+        let original_code = wabt::Wat2Wasm::new()
+            .write_debug_names(true)
+            .convert(
+                r#"
+                (module
+                    (import "env" "host" (func $host))
+                    (func $_ZN8blueprint5inner17h0123456789abcdefE)
+                    (func $outer call 1)
+                )
+                "#,
+            )
+            .unwrap();
+        // Instrumentation adds one more import and a thunk at the end
+        let instrumented_code = wabt::Wat2Wasm::new()
+            .convert(
+                r#"
+                (module
+                    (import "env" "host" (func))
+                    (import "env" "gas" (func (param i64)))
+                    (func)
+                    (func call 2)
+                    (func call 3)
+                )
+                "#,
+            )
+            .unwrap();
+
+        let trap_frames: Vec<WasmTrapFrame> = [2, 3, 4]
+            .into_iter()
+            .map(|function_index| WasmTrapFrame {
+                function_index,
+                code_offset: None,
+            })
+            .collect();
+
+        let backtrace = WasmBacktrace::symbolize(
+            original_code.as_ref(),
+            instrumented_code.as_ref(),
+            &trap_frames,
+        );
+
+        assert_eq!(
+            backtrace
+                .frames
+                .iter()
+                .map(|frame| (frame.function_index, frame.function_name.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, Some("blueprint::inner".to_string())),
+                (2, Some("outer".to_string())),
+            ]
+        );
+        assert_eq!(
+            backtrace.to_string(),
+            "WASM backtrace:\n  0: blueprint::inner\n  1: outer"
+        );
+    }
+
+    #[test]
+    fn test_symbolize_locates_frames_at_original_calls() {
+        let original_code = wabt::wat2wasm(
+            r#"
+            (module
+                (import "env" "host" (func $host))
+                (func $f
+                    call $host
+                    call $host
+                )
+            )
+            "#,
+        )
+        .unwrap();
+        // Instrumentation injects calls to consume units, and to an appended thunk
+        let instrumented_code = wabt::wat2wasm(
+            r#"
+            (module
+                (import "env" "host" (func $host))
+                (import "env" "gas" (func $gas (param i64)))
+                (func $f
+                    (call $gas (i64.const 1))
+                    call $host
+                    call $thunk
+                    call $host
+                )
+                (func $thunk)
+            )
+            "#,
+        )
+        .unwrap();
+        let original = WasmDebugInfo::parse(&original_code);
+        let instrumented = WasmDebugInfo::parse(&instrumented_code);
+        let offsets: Vec<usize> = instrumented.call_sites[0]
+            .iter()
+            .map(|call_site| call_site.offset)
+            .collect();
+
+        assert_eq!(
+            offsets
+                .iter()
+                .map(|offset| instrumented.original_call_ordinal(0, *offset, 1))
+                .collect::<Vec<_>>(),
+            vec![None, Some(0), None, Some(1)]
+        );
+        assert_eq!(
+            original.call_sites[0]
+                .iter()
+                .map(|call_site| call_site.address)
+                .collect::<Vec<_>>(),
+            vec![
+                original.function_body_offsets[0] + 1,
+                original.function_body_offsets[0] + 3
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unavailable_backtrace_is_attached_with_its_reason() {
+        let backtrace = WasmBacktrace::unavailable("bad code".to_string());
+
+        let error = backtrace.attach_to(RuntimeError::ApplicationError(
+            ApplicationError::PanicMessage("boom".to_string()),
+        ));
+
+        assert!(!backtrace.is_empty());
+        assert_eq!(
+            error,
+            RuntimeError::ApplicationError(ApplicationError::PanicMessage(
+                "boom\nWASM backtrace unavailable: bad code".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_tag_wasm_call_frames() {
+        let code = wabt::wat2wasm(
+            r#"
+            (module
+                (import "env" "gas" (func $gas (param i64)))
+                (func $f
+                    (call $gas (i64.const 5))
+                )
+                (func $g
+                    (call $f)
+                )
+            )
+            "#,
+        )
+        .unwrap();
+        let call_offset = WasmDebugInfo::parse(&code).call_sites[1][0].offset as u32;
+
+        let tagged = tag_wasm_call_frames(&code).unwrap();
+
+        wasmparser::Validator::new().validate_all(&tagged).unwrap();
+        let mut tags = Vec::new();
+        for payload in Parser::new(0).parse_all(&tagged) {
+            if let Payload::CodeSectionEntry(body) = payload.unwrap() {
+                for op in body.get_operators_reader().unwrap() {
+                    if let Operator::I64Const { value } = op.unwrap() {
+                        tags.push(WasmCallFrameTag::untag(value as u64));
+                    }
+                }
+            }
+        }
+        assert_eq!(
+            tags,
+            vec![
+                Some(WasmCallFrameTag::Enter(1)),
+                None,
+                Some(WasmCallFrameTag::Enter(2)),
+                Some(WasmCallFrameTag::Call(call_offset)),
+                Some(WasmCallFrameTag::Return),
+            ]
+        );
+
+        // $g calls $f, which traps
+        let mut frames = Vec::new();
+        WasmCallFrameTag::Enter(2).apply(&mut frames);
+        WasmCallFrameTag::Call(call_offset).apply(&mut frames);
+        WasmCallFrameTag::Enter(1).apply(&mut frames);
+        assert_eq!(
+            frames,
+            vec![
+                WasmTrapFrame {
+                    function_index: 2,
+                    code_offset: Some(call_offset),
+                },
+                WasmTrapFrame {
+                    function_index: 1,
+                    code_offset: None,
+                },
+            ]
+        );
+
+        // $f returns instead
+        WasmCallFrameTag::Return.apply(&mut frames);
+        assert_eq!(
+            frames,
+            vec![WasmTrapFrame {
+                function_index: 2,
+                code_offset: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_original_operator_ordinal_skips_injected_code() {
+        let original_code = wabt::wat2wasm(
+            r#"
+            (module
+                (global $g (mut i32) (i32.const 0))
+                (func $f (param i32)
+                    local.get 0
+                    call $f
+                )
+            )
+            "#,
+        )
+        .unwrap();
+        // Instrumentation injects the consumption of units and the stack height limit
+        let instrumented_code = wabt::wat2wasm(
+            r#"
+            (module
+                (import "env" "gas" (func $gas (param i64)))
+                (global $g (mut i32) (i32.const 0))
+                (global $height (mut i32) (i32.const 0))
+                (func $f (param i32)
+                    (call $gas (i64.const 2))
+                    local.get 0
+                    global.get $height
+                    i32.const 1
+                    i32.add
+                    global.set $height
+                    global.get $height
+                    i32.const 100
+                    i32.gt_u
+                    if
+                        unreachable
+                    end
+                    call $f
+                    global.get $height
+                    i32.const 1
+                    i32.sub
+                    global.set $height
+                )
+            )
+            "#,
+        )
+        .unwrap();
+        let original = WasmDebugInfo::parse(&original_code);
+        let instrumented = WasmDebugInfo::parse(&instrumented_code);
+        let call_offsets: Vec<usize> = instrumented.call_sites[0]
+            .iter()
+            .map(|call_site| call_site.offset)
+            .collect();
+
+        assert_eq!(
+            call_offsets
+                .iter()
+                .map(|offset| instrumented.original_operator_ordinal(
+                    0,
+                    *offset,
+                    original.num_of_globals
+                ))
+                .collect::<Vec<_>>(),
+            vec![Some(0), Some(1)]
+        );
+        assert_eq!(
+            original.operator_address(0, 1),
+            Some(original.call_sites[0][0].address)
+        );
+    }
+}
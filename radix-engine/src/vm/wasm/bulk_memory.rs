@@ -0,0 +1,401 @@
+use crate::types::*;
+use crate::vm::wasm::PrepareError;
+use wasmparser::{Operator, Parser, Payload};
+
+// The cost of `memory.copy` and `memory.fill` depends on the number of bytes, which can't be
+// expressed by the static per-instruction costs of the gas metering injector. Instead, both
+// instructions are replaced with calls to the MVP functions below, whose loops are metered
+// like any other code - and deterministically so, regardless of how an engine implements
+// the bulk memory proposal.
+//
+// Unlike the bulk memory proposal, these functions don't check the bounds of the whole range
+// up front, ie. an out of bounds access traps after the in-bounds bytes have been written.
+// This is unobservable, as a trap fails the whole invocation.
+
+/// `(func (param $dst i32) (param $src i32) (param $len i32))`, with `memmove` semantics.
+#[rustfmt::skip]
+const MEMORY_COPY_BODY: &[u8] = &[
+    0x00, // no locals
+    0x02, 0x40, // block $done
+    0x20, 0x00, 0x20, 0x01, 0x4D, // dst <= src
+    0x04, 0x40, // if (copy forward)
+    0x02, 0x40, // block
+    0x03, 0x40, // loop
+    0x20, 0x02, 0x41, 0x08, 0x49, 0x0D, 0x01, // br_if 1 (len < 8)
+    0x20, 0x00, 0x20, 0x01, 0x29, 0x00, 0x00, 0x37, 0x00, 0x00, // i64.store dst (i64.load src)
+    0x20, 0x00, 0x41, 0x08, 0x6A, 0x21, 0x00, // dst += 8
+    0x20, 0x01, 0x41, 0x08, 0x6A, 0x21, 0x01, // src += 8
+    0x20, 0x02, 0x41, 0x08, 0x6B, 0x21, 0x02, // len -= 8
+    0x0C, 0x00, // br 0
+    0x0B, 0x0B, // end loop, end block
+    0x03, 0x40, // loop
+    0x20, 0x02, 0x45, 0x0D, 0x02, // br_if $done (len == 0)
+    0x20, 0x00, 0x20, 0x01, 0x2D, 0x00, 0x00, 0x3A, 0x00, 0x00, // i32.store8 dst (i32.load8_u src)
+    0x20, 0x00, 0x41, 0x01, 0x6A, 0x21, 0x00, // dst += 1
+    0x20, 0x01, 0x41, 0x01, 0x6A, 0x21, 0x01, // src += 1
+    0x20, 0x02, 0x41, 0x01, 0x6B, 0x21, 0x02, // len -= 1
+    0x0C, 0x00, // br 0
+    0x0B, // end loop
+    0x05, // else (copy backward)
+    0x02, 0x40, // block
+    0x03, 0x40, // loop
+    0x20, 0x02, 0x41, 0x08, 0x49, 0x0D, 0x01, // br_if 1 (len < 8)
+    0x20, 0x02, 0x41, 0x08, 0x6B, 0x21, 0x02, // len -= 8
+    0x20, 0x00, 0x20, 0x02, 0x6A, // dst + len
+    0x20, 0x01, 0x20, 0x02, 0x6A, 0x29, 0x00, 0x00, // i64.load (src + len)
+    0x37, 0x00, 0x00, // i64.store
+    0x0C, 0x00, // br 0
+    0x0B, 0x0B, // end loop, end block
+    0x03, 0x40, // loop
+    0x20, 0x02, 0x45, 0x0D, 0x02, // br_if $done (len == 0)
+    0x20, 0x02, 0x41, 0x01, 0x6B, 0x21, 0x02, // len -= 1
+    0x20, 0x00, 0x20, 0x02, 0x6A, // dst + len
+    0x20, 0x01, 0x20, 0x02, 0x6A, 0x2D, 0x00, 0x00, // i32.load8_u (src + len)
+    0x3A, 0x00, 0x00, // i32.store8
+    0x0C, 0x00, // br 0
+    0x0B, // end loop
+    0x0B, // end if
+    0x0B, // end block
+    0x0B, // end function
+];
+
+/// `(func (param $dst i32) (param $val i32) (param $len i32))`
+#[rustfmt::skip]
+const MEMORY_FILL_BODY: &[u8] = &[
+    0x01, 0x01, 0x7E, // local $pattern i64
+    0x02, 0x40, // block $done
+    0x20, 0x01, 0xAD, 0x42, 0xFF, 0x01, 0x83, // (val as u64) & 0xff
+    0x42, 0x81, 0x82, 0x84, 0x88, 0x90, 0xA0, 0xC0, 0x80, 0x01, 0x7E, // * 0x0101010101010101
+    0x21, 0x03, // pattern =
+    0x02, 0x40, // block
+    0x03, 0x40, // loop
+    0x20, 0x02, 0x41, 0x08, 0x49, 0x0D, 0x01, // br_if 1 (len < 8)
+    0x20, 0x00, 0x20, 0x03, 0x37, 0x00, 0x00, // i64.store dst pattern
+    0x20, 0x00, 0x41, 0x08, 0x6A, 0x21, 0x00, // dst += 8
+    0x20, 0x02, 0x41, 0x08, 0x6B, 0x21, 0x02, // len -= 8
+    0x0C, 0x00, // br 0
+    0x0B, 0x0B, // end loop, end block
+    0x03, 0x40, // loop
+    0x20, 0x02, 0x45, 0x0D, 0x01, // br_if $done (len == 0)
+    0x20, 0x00, 0x20, 0x01, 0x3A, 0x00, 0x00, // i32.store8 dst val
+    0x20, 0x00, 0x41, 0x01, 0x6A, 0x21, 0x00, // dst += 1
+    0x20, 0x02, 0x41, 0x01, 0x6B, 0x21, 0x02, // len -= 1
+    0x0C, 0x00, // br 0
+    0x0B, // end loop
+    0x0B, // end block
+    0x0B, // end function
+];
+
+/// `(func (param i32 i32 i32))`
+const HELPER_FUNCTION_TYPE: &[u8] = &[0x60, 0x03, 0x7F, 0x7F, 0x7F, 0x00];
+
+const SECTION_ID_TYPE: u8 = 1;
+const SECTION_ID_FUNCTION: u8 = 3;
+const SECTION_ID_CODE: u8 = 10;
+
+pub(super) const OPCODE_CALL: u8 = 0x10;
+const OPCODE_NOP: u8 = 0x01;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BulkMemoryOp {
+    Copy,
+    Fill,
+}
+
+/// Replaces every `memory.copy` and `memory.fill` instruction with a call to an equivalent
+/// MVP function appended to the module.
+///
+/// Every replacement is encoded in place, using the same number of bytes as the replaced
+/// instruction, so that no other part of the module needs to be re-encoded.
+///
+/// Returns `None` if the code contains no such instructions.
+pub fn lower_bulk_memory_ops(code: &[u8]) -> Result<Option<Vec<u8>>, PrepareError> {
+    let mut num_of_types = 0u32;
+    let mut num_of_imported_functions = 0u32;
+    let mut num_of_local_functions = 0u32;
+    let mut replacements = Vec::new();
+
+    for payload in Parser::new(0).parse_all(code) {
+        match payload.map_err(|err| PrepareError::WasmParserError(err.to_string()))? {
+            Payload::TypeSection(reader) => {
+                num_of_types = reader.count();
+            }
+            Payload::ImportSection(reader) => {
+                for import in reader {
+                    let import =
+                        import.map_err(|err| PrepareError::WasmParserError(err.to_string()))?;
+                    if let wasmparser::TypeRef::Func(_) = import.ty {
+                        num_of_imported_functions += 1;
+                    }
+                }
+            }
+            Payload::FunctionSection(reader) => {
+                num_of_local_functions = reader.count();
+            }
+            Payload::CodeSectionEntry(body) => {
+                let mut reader = body
+                    .get_operators_reader()
+                    .map_err(|err| PrepareError::WasmParserError(err.to_string()))?;
+                while !reader.eof() {
+                    let start = reader.original_position();
+                    let op = reader
+                        .read()
+                        .map_err(|err| PrepareError::WasmParserError(err.to_string()))?;
+                    let end = reader.original_position();
+                    match op {
+                        Operator::MemoryCopy { .. } => {
+                            replacements.push((start..end, BulkMemoryOp::Copy))
+                        }
+                        Operator::MemoryFill { .. } => {
+                            replacements.push((start..end, BulkMemoryOp::Fill))
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if replacements.is_empty() {
+        return Ok(None);
+    }
+
+    let memory_copy_index = num_of_imported_functions
+        .checked_add(num_of_local_functions)
+        .ok_or(PrepareError::TooManyFunctions)?;
+    let memory_fill_index = memory_copy_index
+        .checked_add(1)
+        .ok_or(PrepareError::TooManyFunctions)?;
+
+    let mut code = code.to_vec();
+    for (range, op) in replacements {
+        let function_index = match op {
+            BulkMemoryOp::Copy => memory_copy_index,
+            BulkMemoryOp::Fill => memory_fill_index,
+        };
+        let call = encode_call_in_place(function_index, range.len())
+            .ok_or(PrepareError::TooManyFunctions)?;
+        code[range].copy_from_slice(&call);
+    }
+
+    append_helper_functions(&code, num_of_types).map(Some)
+}
+
+/// Encodes `call $function_index` using exactly `len` bytes, by padding the LEB128 encoded
+/// index with redundant bytes and filling the rest with `nop`s.
+fn encode_call_in_place(function_index: u32, len: usize) -> Option<Vec<u8>> {
+    let mut index = encode_u32(function_index);
+    let padded_len = len.checked_sub(1)?.min(5);
+    if index.len() > padded_len {
+        return None;
+    }
+    while index.len() < padded_len {
+        let last = index.len() - 1;
+        index[last] |= 0x80;
+        index.push(0x00);
+    }
+
+    let mut call = vec![OPCODE_CALL];
+    call.extend(index);
+    call.resize(len, OPCODE_NOP);
+    Some(call)
+}
+
+fn append_helper_functions(code: &[u8], num_of_types: u32) -> Result<Vec<u8>, PrepareError> {
+    let invalid_section = || PrepareError::WasmParserError("Invalid section".to_string());
+
+    // Magic number and version
+    let mut output = code.get(..8).ok_or_else(invalid_section)?.to_vec();
+    let mut offset = 8;
+
+    while offset < code.len() {
+        let id = code[offset];
+        let (size, size_len) = decode_u32(&code[offset + 1..]).ok_or_else(invalid_section)?;
+        let content_start = offset + 1 + size_len;
+        let content_end = content_start
+            .checked_add(size as usize)
+            .filter(|end| *end <= code.len())
+            .ok_or_else(invalid_section)?;
+        let content = &code[content_start..content_end];
+
+        let appended: Option<(u32, Vec<u8>)> = match id {
+            SECTION_ID_TYPE => Some((1, HELPER_FUNCTION_TYPE.to_vec())),
+            SECTION_ID_FUNCTION => {
+                let mut entries = encode_u32(num_of_types);
+                entries.extend(encode_u32(num_of_types));
+                Some((2, entries))
+            }
+            SECTION_ID_CODE => {
+                let mut entries = Vec::new();
+                for body in [MEMORY_COPY_BODY, MEMORY_FILL_BODY] {
+                    entries.extend(encode_u32(body.len() as u32));
+                    entries.extend_from_slice(body);
+                }
+                Some((2, entries))
+            }
+            _ => None,
+        };
+
+        match appended {
+            Some((num_of_appended, appended)) => {
+                let (count, count_len) = decode_u32(content).ok_or_else(invalid_section)?;
+                let count = count
+                    .checked_add(num_of_appended)
+                    .ok_or(PrepareError::Overflow)?;
+                let mut new_content = encode_u32(count);
+                new_content.extend_from_slice(&content[count_len..]);
+                new_content.extend(appended);
+
+                output.push(id);
+                output.extend(encode_u32(new_content.len() as u32));
+                output.extend(new_content);
+            }
+            None => output.extend_from_slice(&code[offset..content_end]),
+        }
+
+        offset = content_end;
+    }
+
+    Ok(output)
+}
+
+pub(super) fn encode_u32(mut value: u32) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            bytes.push(byte);
+            return bytes;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+/// Returns the decoded value and the number of bytes read.
+pub(super) fn decode_u32(bytes: &[u8]) -> Option<(u32, usize)> {
+    let mut value = 0u64;
+    for (i, byte) in bytes.iter().take(5).enumerate() {
+        value |= ((byte & 0x7F) as u64) << (7 * i);
+        if byte & 0x80 == 0 {
+            return u32::try_from(value).ok().map(|value| (value, i + 1));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wabt::{wat2wasm_with_features, Features};
+    use wasmi::{Engine, Extern, Linker, Module, Store, Value};
+
+    const MODULE: &str = r#"
+        (module
+            (func $copy (param $dst i32) (param $src i32) (param $len i32)
+                (memory.copy (local.get $dst) (local.get $src) (local.get $len))
+            )
+            (func $fill (param $dst i32) (param $val i32) (param $len i32)
+                (memory.fill (local.get $dst) (local.get $val) (local.get $len))
+            )
+            (memory $0 1)
+            (export "memory" (memory $0))
+            (export "copy" (func $copy))
+            (export "fill" (func $fill))
+        )
+    "#;
+
+    fn bulk_memory_code() -> Vec<u8> {
+        let mut features = Features::new();
+        features.enable_bulk_memory();
+        wat2wasm_with_features(MODULE, features).unwrap()
+    }
+
+    fn contains_bulk_memory_ops(code: &[u8]) -> bool {
+        for payload in Parser::new(0).parse_all(code) {
+            if let Payload::CodeSectionEntry(body) = payload.unwrap() {
+                for op in body.get_operators_reader().unwrap() {
+                    if let Operator::MemoryCopy { .. } | Operator::MemoryFill { .. } = op.unwrap() {
+                        return true;
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    fn call(code: &[u8], func_name: &str, args: [i32; 3], memory_init: &[u8]) -> Vec<u8> {
+        let engine = Engine::default();
+        let module = Module::new(&engine, code).unwrap();
+        let mut store = Store::new(&engine, ());
+        let instance = <Linker<()>>::new()
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .ensure_no_start(&mut store)
+            .unwrap();
+        let memory = instance
+            .get_export(&store, "memory")
+            .and_then(Extern::into_memory)
+            .unwrap();
+        memory.data_mut(&mut store)[..memory_init.len()].copy_from_slice(memory_init);
+
+        instance
+            .get_export(&store, func_name)
+            .and_then(Extern::into_func)
+            .unwrap()
+            .call(&mut store, &args.map(Value::I32), &mut [])
+            .unwrap();
+
+        memory.data(&store)[..memory_init.len()].to_vec()
+    }
+
+    #[test]
+    fn test_lowered_code_contains_no_bulk_memory_ops() {
+        let code = bulk_memory_code();
+        assert!(contains_bulk_memory_ops(&code));
+
+        let lowered = lower_bulk_memory_ops(&code).unwrap().unwrap();
+        assert!(!contains_bulk_memory_ops(&lowered));
+        wasmparser::Validator::new_with_features(wasmparser::WasmFeatures {
+            bulk_memory: false,
+            ..Default::default()
+        })
+        .validate_all(&lowered)
+        .unwrap();
+    }
+
+    #[test]
+    fn test_lowered_code_is_equivalent() {
+        let code = bulk_memory_code();
+        let lowered = lower_bulk_memory_ops(&code).unwrap().unwrap();
+        let memory_init: Vec<u8> = (0u8..64).collect();
+
+        for (dst, src, len) in [(0, 32, 20), (32, 0, 20), (3, 7, 30), (7, 3, 30), (5, 5, 9)] {
+            assert_eq!(
+                call(&code, "copy", [dst, src, len], &memory_init),
+                call(&lowered, "copy", [dst, src, len], &memory_init)
+            );
+        }
+        for (dst, val, len) in [(0, 0xAB, 20), (3, 0x1FF, 17), (9, 7, 0)] {
+            assert_eq!(
+                call(&code, "fill", [dst, val, len], &memory_init),
+                call(&lowered, "fill", [dst, val, len], &memory_init)
+            );
+        }
+    }
+
+    #[test]
+    fn test_encode_call_in_place() {
+        assert_eq!(encode_call_in_place(5, 3), Some(vec![0x10, 0x85, 0x00]));
+        assert_eq!(
+            encode_call_in_place(5, 4),
+            Some(vec![0x10, 0x85, 0x80, 0x00])
+        );
+        assert_eq!(
+            encode_call_in_place(200, 8),
+            Some(vec![0x10, 0xC8, 0x81, 0x80, 0x80, 0x00, 0x01, 0x01])
+        );
+        assert_eq!(encode_call_in_place(1 << 14, 3), None);
+    }
+}
@@ -29,6 +29,13 @@ pub const ACTOR_GET_BLUEPRINT_NAME_FUNCTION_NAME: &str = "actor_get_blueprint_na
 pub const ACTOR_OPEN_FIELD_FUNCTION_NAME: &str = "actor_open_field";
 pub const ACTOR_GET_OBJECT_ID_FUNCTION_NAME: &str = "actor_get_object_id";
 pub const ACTOR_EMIT_EVENT_FUNCTION_NAME: &str = "actor_emit_event";
+pub const ACTOR_INDEX_INSERT_FUNCTION_NAME: &str = "actor_index_insert";
+pub const ACTOR_INDEX_REMOVE_FUNCTION_NAME: &str = "actor_index_remove";
+pub const ACTOR_INDEX_GET_FUNCTION_NAME: &str = "actor_index_get";
+pub const ACTOR_INDEX_SCAN_FUNCTION_NAME: &str = "actor_index_scan";
+pub const ACTOR_SORTED_INDEX_INSERT_FUNCTION_NAME: &str = "actor_sorted_index_insert";
+pub const ACTOR_SORTED_INDEX_REMOVE_FUNCTION_NAME: &str = "actor_sorted_index_remove";
+pub const ACTOR_SORTED_INDEX_SCAN_FUNCTION_NAME: &str = "actor_sorted_index_scan";
 
 //=================
 // Key Value Store
@@ -36,6 +43,8 @@ pub const ACTOR_EMIT_EVENT_FUNCTION_NAME: &str = "actor_emit_event";
 pub const KEY_VALUE_STORE_NEW_FUNCTION_NAME: &str = "kv_store_new";
 pub const KEY_VALUE_STORE_OPEN_ENTRY_FUNCTION_NAME: &str = "kv_store_open_entry";
 pub const KEY_VALUE_STORE_REMOVE_ENTRY_FUNCTION_NAME: &str = "kv_store_remove_entry";
+pub const KEY_VALUE_STORE_SCAN_FUNCTION_NAME: &str = "kv_store_scan";
+pub const KEY_VALUE_STORE_COUNT_FUNCTION_NAME: &str = "kv_store_count";
 
 //=================
 // KV Entry
@@ -88,6 +97,12 @@ pub const CRYPTO_UTILS_BLS12381_V1_FAST_AGGREGATE_VERIFY_FUNCTION_NAME: &str =
 pub const CRYPTO_UTILS_BLS12381_G2_SIGNATURE_AGGREGATE_FUNCTION_NAME: &str =
     "crypto_utils_bls12381_g2_signature_aggregate";
 pub const CRYPTO_UTILS_KECCAK256_HASH_FUNCTION_NAME: &str = "crypto_utils_keccak256_hash";
+pub const CRYPTO_UTILS_BLAKE2B_256_HASH_FUNCTION_NAME: &str = "crypto_utils_blake2b_256_hash";
+pub const CRYPTO_UTILS_ED25519_VERIFY_FUNCTION_NAME: &str = "crypto_utils_ed25519_verify";
+pub const CRYPTO_UTILS_SECP256K1_ECDSA_VERIFY_FUNCTION_NAME: &str =
+    "crypto_utils_secp256k1_ecdsa_verify";
+pub const CRYPTO_UTILS_SECP256K1_ECDSA_VERIFY_AND_KEY_RECOVER_FUNCTION_NAME: &str =
+    "crypto_utils_secp256k1_ecdsa_verify_and_key_recover";
 
 //=================
 // WASM Shim
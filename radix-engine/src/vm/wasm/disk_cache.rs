@@ -0,0 +1,335 @@
+use crate::types::*;
+use crate::vm::wasm::WasmValidatorConfigV1;
+use radix_engine_interface::blueprints::package::CodeHash;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CACHE_FILE_EXTENSION: &str = "wasmcache";
+
+#[derive(Debug, Clone)]
+pub struct WasmDiskCacheOptions {
+    /// The directory which holds the cache entries, created on first write if missing.
+    pub directory: PathBuf,
+    /// The maximum total size of all cache entries; the oldest entries are evicted first
+    /// once this is exceeded.
+    pub max_size_in_bytes: u64,
+}
+
+/// A single cache entry, holding the instrumented code which was compiled alongside the
+/// engine-specific compiled artifact (eg. a `wasmer` serialized module).
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct WasmDiskCacheEntry {
+    pub instrumented_code: Vec<u8>,
+    pub engine_artifact: Vec<u8>,
+}
+
+/// A single cache entry, holding the code produced by validating and instrumenting the original
+/// code of a package.
+///
+/// The outcome of the validation also depends on the blueprints the code was published with, so
+/// the entry records a hash of them and is only used for the very same blueprints.
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct WasmDiskCacheInstrumentedCodeEntry {
+    pub blueprints_hash: Hash,
+    pub instrumented_code: Vec<u8>,
+}
+
+/// A persistent, best-effort cache of compiled WASM modules which survives process restarts.
+///
+/// It holds both the instrumented code (produced when a package is published) and the
+/// engine-specific compiled artifacts. Entries are keyed by the code hash and the version of the
+/// `WasmValidatorConfigV1` which was used to instrument the code. Every entry is stored together with a hash of its content, and
+/// entries failing the integrity check (or not matching the requested instrumented code) are
+/// treated as a cache miss and removed.
+///
+/// All IO errors are swallowed - a broken cache results in recompilation, never in a failure.
+#[derive(Debug, Clone)]
+pub struct WasmDiskCache {
+    directory: PathBuf,
+    max_size_in_bytes: u64,
+    validator_config_version: u8,
+}
+
+impl WasmDiskCache {
+    pub fn new(options: WasmDiskCacheOptions, validator_config: &WasmValidatorConfigV1) -> Self {
+        Self {
+            directory: options.directory,
+            max_size_in_bytes: options.max_size_in_bytes,
+            validator_config_version: validator_config.version(),
+        }
+    }
+
+    /// Returns the cached engine artifact for the given code, if present and valid.
+    pub fn get(&self, code_hash: &CodeHash, instrumented_code: &[u8]) -> Option<Vec<u8>> {
+        let path = self.entry_path(code_hash);
+
+        match Self::read_entry::<WasmDiskCacheEntry>(&path) {
+            Some(entry) if entry.instrumented_code == instrumented_code => {
+                Some(entry.engine_artifact)
+            }
+            _ => {
+                let _ = fs::remove_file(&path);
+                None
+            }
+        }
+    }
+
+    /// Stores the engine artifact for the given code, evicting old entries if needed.
+    pub fn put(&self, code_hash: &CodeHash, instrumented_code: &[u8], engine_artifact: Vec<u8>) {
+        let entry = WasmDiskCacheEntry {
+            instrumented_code: instrumented_code.to_vec(),
+            engine_artifact,
+        };
+        self.write_entry(&self.entry_path(code_hash), &entry);
+    }
+
+    /// Returns the cached instrumented code for the given code, if it was validated against the
+    /// same blueprints under the same Scrypto VM minor version.
+    pub fn get_instrumented_code(
+        &self,
+        code_hash: &CodeHash,
+        minor_version: u64,
+        blueprints_hash: &Hash,
+    ) -> Option<Vec<u8>> {
+        let path = self.instrumented_code_entry_path(code_hash, minor_version);
+
+        match Self::read_entry::<WasmDiskCacheInstrumentedCodeEntry>(&path) {
+            Some(entry) if entry.blueprints_hash == *blueprints_hash => {
+                Some(entry.instrumented_code)
+            }
+            _ => {
+                let _ = fs::remove_file(&path);
+                None
+            }
+        }
+    }
+
+    /// Stores the instrumented code for the given code, evicting old entries if needed.
+    pub fn put_instrumented_code(
+        &self,
+        code_hash: &CodeHash,
+        minor_version: u64,
+        blueprints_hash: Hash,
+        instrumented_code: &[u8],
+    ) {
+        let entry = WasmDiskCacheInstrumentedCodeEntry {
+            blueprints_hash,
+            instrumented_code: instrumented_code.to_vec(),
+        };
+        self.write_entry(
+            &self.instrumented_code_entry_path(code_hash, minor_version),
+            &entry,
+        );
+    }
+
+    /// Removes the entry for the given code, if any.
+    pub fn remove(&self, code_hash: &CodeHash) {
+        let _ = fs::remove_file(self.entry_path(code_hash));
+    }
+
+    fn read_entry<T: ScryptoDecode>(path: &Path) -> Option<T> {
+        let content = fs::read(path).ok()?;
+        if content.len() < Hash::LENGTH {
+            return None;
+        }
+        let (checksum, payload) = content.split_at(Hash::LENGTH);
+        if hash(payload).0[..] != *checksum {
+            return None;
+        }
+        scrypto_decode(payload).ok()
+    }
+
+    fn write_entry<T: ScryptoEncode>(&self, path: &Path, entry: &T) {
+        let Ok(payload) = scrypto_encode(entry) else {
+            return;
+        };
+        let mut content = Vec::with_capacity(Hash::LENGTH + payload.len());
+        content.extend_from_slice(&hash(&payload).0);
+        content.extend(payload);
+        if content.len() as u64 > self.max_size_in_bytes {
+            return;
+        }
+        if fs::create_dir_all(&self.directory).is_err() {
+            return;
+        }
+
+        // Write to a temporary file first, so that concurrent readers never observe
+        // a partially written entry.
+        let temp_path = path.with_extension(format!("{}.tmp", CACHE_FILE_EXTENSION));
+        if fs::write(&temp_path, &content).is_err() {
+            let _ = fs::remove_file(&temp_path);
+            return;
+        }
+        if fs::rename(&temp_path, path).is_err() {
+            let _ = fs::remove_file(&temp_path);
+            return;
+        }
+
+        self.evict(path);
+    }
+
+    fn entry_path(&self, code_hash: &CodeHash) -> PathBuf {
+        self.directory.join(format!(
+            "{}_v{}.{}",
+            code_hash.0, self.validator_config_version, CACHE_FILE_EXTENSION
+        ))
+    }
+
+    fn instrumented_code_entry_path(&self, code_hash: &CodeHash, minor_version: u64) -> PathBuf {
+        self.directory.join(format!(
+            "{}_v{}_m{}.instrumented.{}",
+            code_hash.0, self.validator_config_version, minor_version, CACHE_FILE_EXTENSION
+        ))
+    }
+
+    /// Evicts the least recently written entries (other than the one just written) until
+    /// the cache fits within its size limit.
+    fn evict(&self, just_written: &Path) {
+        let Ok(read_dir) = fs::read_dir(&self.directory) else {
+            return;
+        };
+
+        let mut entries: Vec<_> = read_dir
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                entry.path().extension().and_then(|ext| ext.to_str()) == Some(CACHE_FILE_EXTENSION)
+            })
+            .filter_map(|entry| {
+                let metadata = entry.metadata().ok()?;
+                let modified = metadata.modified().ok()?;
+                Some((entry.path(), metadata.len(), modified))
+            })
+            .collect();
+
+        let mut total_size: u64 = entries.iter().map(|(_, size, _)| size).sum();
+        if total_size <= self.max_size_in_bytes {
+            return;
+        }
+
+        entries.sort_by_key(|(_, _, modified)| *modified);
+        for (path, size, _) in entries {
+            if total_size <= self.max_size_in_bytes {
+                break;
+            }
+            if path == just_written {
+                continue;
+            }
+            if fs::remove_file(&path).is_ok() {
+                total_size -= size;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_cache(name: &str, max_size_in_bytes: u64) -> WasmDiskCache {
+        let directory = std::env::temp_dir().join(format!(
+            "radix-engine-wasm-disk-cache-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&directory);
+        WasmDiskCache::new(
+            WasmDiskCacheOptions {
+                directory,
+                max_size_in_bytes,
+            },
+            &WasmValidatorConfigV1::new(),
+        )
+    }
+
+    #[test]
+    fn test_put_and_get() {
+        let cache = new_cache("put_and_get", 1024 * 1024);
+        let code_hash = CodeHash(hash("code"));
+
+        assert_eq!(cache.get(&code_hash, b"code"), None);
+        cache.put(&code_hash, b"code", vec![1, 2, 3]);
+        assert_eq!(cache.get(&code_hash, b"code"), Some(vec![1, 2, 3]));
+
+        // A different instrumented code (eg. from a different validator setup) is a miss
+        assert_eq!(cache.get(&code_hash, b"other code"), None);
+        assert_eq!(cache.get(&code_hash, b"code"), None);
+
+        let _ = fs::remove_dir_all(&cache.directory);
+    }
+
+    #[test]
+    fn test_put_and_get_instrumented_code() {
+        let cache = new_cache("instrumented_code", 1024 * 1024);
+        let code_hash = CodeHash(hash("code"));
+        let blueprints_hash = hash("blueprints");
+
+        assert_eq!(
+            cache.get_instrumented_code(&code_hash, 1, &blueprints_hash),
+            None
+        );
+        cache.put_instrumented_code(&code_hash, 1, blueprints_hash, b"instrumented");
+        assert_eq!(
+            cache.get_instrumented_code(&code_hash, 1, &blueprints_hash),
+            Some(b"instrumented".to_vec())
+        );
+
+        // The instrumented code is not shared with the engine artifacts of the same code
+        assert_eq!(cache.get(&code_hash, b"instrumented"), None);
+
+        // Neither another minor version nor other blueprints may reuse the instrumented code
+        assert_eq!(
+            cache.get_instrumented_code(&code_hash, 2, &blueprints_hash),
+            None
+        );
+        assert_eq!(
+            cache.get_instrumented_code(&code_hash, 1, &hash("other blueprints")),
+            None
+        );
+        assert_eq!(
+            cache.get_instrumented_code(&code_hash, 1, &blueprints_hash),
+            None
+        );
+
+        let _ = fs::remove_dir_all(&cache.directory);
+    }
+
+    #[test]
+    fn test_corrupted_entry_is_removed() {
+        let cache = new_cache("corrupted", 1024 * 1024);
+        let code_hash = CodeHash(hash("code"));
+
+        cache.put(&code_hash, b"code", vec![1, 2, 3]);
+        let path = cache.entry_path(&code_hash);
+        let mut content = fs::read(&path).unwrap();
+        let last = content.len() - 1;
+        content[last] ^= 0xff;
+        fs::write(&path, content).unwrap();
+
+        assert_eq!(cache.get(&code_hash, b"code"), None);
+        assert!(!path.exists());
+
+        let _ = fs::remove_dir_all(&cache.directory);
+    }
+
+    #[test]
+    fn test_eviction_keeps_cache_within_size_limit() {
+        let cache = new_cache("eviction", 3000);
+
+        for i in 0..10u8 {
+            let code_hash = CodeHash(hash([i]));
+            cache.put(&code_hash, &[i], vec![i; 1000]);
+        }
+
+        let total_size: u64 = fs::read_dir(&cache.directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().metadata().unwrap().len())
+            .sum();
+        assert!(total_size <= 3000);
+
+        // The most recently written entry is always kept
+        let code_hash = CodeHash(hash([9u8]));
+        assert_eq!(cache.get(&code_hash, &[9u8]), Some(vec![9u8; 1000]));
+
+        let _ = fs::remove_dir_all(&cache.directory);
+    }
+}
@@ -157,6 +157,13 @@ pub enum WasmRuntimeError {
     InvalidBlsPublicKey(DecodeError),
     InvalidBlsSignature(DecodeError),
     InvalidBlsPublicKeyOrMessage(DecodeError),
+    InvalidEd25519PublicKey(DecodeError),
+    InvalidEd25519Signature(DecodeError),
+    InvalidSecp256k1PublicKey(DecodeError),
+    InvalidSecp256k1Signature(DecodeError),
+    InvalidHash(DecodeError),
+    InvalidScanCursor(DecodeError),
+    InvalidSortPrefix(u32),
 }
 
 impl SelfError for WasmRuntimeError {
@@ -1,6 +1,11 @@
+mod backtrace;
+mod bulk_memory;
 mod constants;
+#[cfg(feature = "std")]
+mod disk_cache;
 mod errors;
 mod prepare;
+mod profiling;
 mod traits;
 mod wasm_validator;
 mod wasm_validator_config;
@@ -12,9 +17,13 @@ mod weights;
 #[cfg(feature = "wasmer")]
 pub use self::wasmer::*;
 pub use self::wasmi::*;
+pub use backtrace::*;
 pub use constants::*;
+#[cfg(feature = "std")]
+pub use disk_cache::*;
 pub use errors::*;
 pub use prepare::*;
+pub use profiling::*;
 pub use traits::*;
 pub use wasm_validator::*;
 pub use wasm_validator_config::*;
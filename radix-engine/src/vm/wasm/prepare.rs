@@ -1,5 +1,6 @@
 use crate::types::*;
-use crate::vm::wasm::{constants::*, errors::*, PrepareError};
+use crate::vm::wasm::bulk_memory::lower_bulk_memory_ops;
+use crate::vm::wasm::{constants::*, errors::*, PrepareError, WasmFeatureSet};
 use num_traits::CheckedAdd;
 use radix_engine_interface::blueprints::package::BlueprintDefinitionInit;
 use syn::Ident;
@@ -13,6 +14,10 @@ use wasmparser::{ExternalKind, FuncType, Operator, Type, TypeRef, ValType, WasmF
 use super::WasmiModule;
 
 pub const SCRPYTO_VM_CRYPTO_UTILS_MINOR_VERSION: u64 = 1u64;
+pub const SCRYPTO_VM_CRYPTO_UTILS_V2_MINOR_VERSION: u64 = 2u64;
+pub const SCRYPTO_VM_BULK_MEMORY_MINOR_VERSION: u64 = 3u64;
+pub const SCRYPTO_VM_INDEXED_COLLECTIONS_MINOR_VERSION: u64 = 4u64;
+pub const SCRYPTO_VM_KV_STORE_ITERATION_MINOR_VERSION: u64 = 5u64;
 
 #[derive(Debug)]
 pub struct WasmModule {
@@ -20,18 +25,27 @@ pub struct WasmModule {
 }
 
 impl WasmModule {
+    /// Deserializes and validates the code, allowing the WASM proposals supported since the
+    /// very first Scrypto VM version.
     pub fn init(code: &[u8]) -> Result<Self, PrepareError> {
+        Self::init_with_features(code, WasmFeatureSet::for_minor_version(0))
+    }
+
+    pub fn init_with_features(
+        code: &[u8],
+        wasm_features: WasmFeatureSet,
+    ) -> Result<Self, PrepareError> {
         // deserialize
         let module = ModuleInfo::new(code).map_err(|_| PrepareError::DeserializationError)?;
 
-        // Radix Engine supports MVP + proposals: mutable globals and sign-extension-ops
+        // Radix Engine supports MVP + the proposals enabled by the feature set
         let features = WasmFeatures {
-            mutable_global: true,
+            mutable_global: wasm_features.mutable_global,
             saturating_float_to_int: false,
-            sign_extension: true,
+            sign_extension: wasm_features.sign_extension,
             reference_types: false,
-            multi_value: false,
-            bulk_memory: false,
+            multi_value: wasm_features.multi_value,
+            bulk_memory: wasm_features.bulk_memory,
             simd: false,
             relaxed_simd: false,
             threads: false,
@@ -285,6 +299,54 @@ impl WasmModule {
                             ));
                         }
                     }
+                    KEY_VALUE_STORE_SCAN_FUNCTION_NAME => {
+                        if minor_version < SCRYPTO_VM_KV_STORE_ITERATION_MINOR_VERSION {
+                            return Err(PrepareError::InvalidImport(
+                                InvalidImport::ImportNotAllowed(entry.name.to_string()),
+                            ));
+                        }
+
+                        if let TypeRef::Func(type_index) = entry.ty {
+                            if Self::function_type_matches(
+                                &self.module,
+                                type_index,
+                                vec![
+                                    ValType::I32,
+                                    ValType::I32,
+                                    ValType::I32,
+                                    ValType::I32,
+                                    ValType::I32,
+                                ],
+                                vec![ValType::I64],
+                            ) {
+                                continue;
+                            }
+                            return Err(PrepareError::InvalidImport(
+                                InvalidImport::InvalidFunctionType(entry.name.to_string()),
+                            ));
+                        }
+                    }
+                    KEY_VALUE_STORE_COUNT_FUNCTION_NAME => {
+                        if minor_version < SCRYPTO_VM_KV_STORE_ITERATION_MINOR_VERSION {
+                            return Err(PrepareError::InvalidImport(
+                                InvalidImport::ImportNotAllowed(entry.name.to_string()),
+                            ));
+                        }
+
+                        if let TypeRef::Func(type_index) = entry.ty {
+                            if Self::function_type_matches(
+                                &self.module,
+                                type_index,
+                                vec![ValType::I32, ValType::I32, ValType::I32],
+                                vec![ValType::I32],
+                            ) {
+                                continue;
+                            }
+                            return Err(PrepareError::InvalidImport(
+                                InvalidImport::InvalidFunctionType(entry.name.to_string()),
+                            ));
+                        }
+                    }
                     ACTOR_OPEN_FIELD_FUNCTION_NAME => {
                         if let TypeRef::Func(type_index) = entry.ty {
                             if Self::function_type_matches(
@@ -301,6 +363,186 @@ impl WasmModule {
                             ));
                         }
                     }
+                    ACTOR_INDEX_INSERT_FUNCTION_NAME => {
+                        if minor_version < SCRYPTO_VM_INDEXED_COLLECTIONS_MINOR_VERSION {
+                            return Err(PrepareError::InvalidImport(
+                                InvalidImport::ImportNotAllowed(entry.name.to_string()),
+                            ));
+                        }
+
+                        if let TypeRef::Func(type_index) = entry.ty {
+                            if Self::function_type_matches(
+                                &self.module,
+                                type_index,
+                                vec![
+                                    ValType::I32,
+                                    ValType::I32,
+                                    ValType::I32,
+                                    ValType::I32,
+                                    ValType::I32,
+                                    ValType::I32,
+                                ],
+                                vec![],
+                            ) {
+                                continue;
+                            }
+                            return Err(PrepareError::InvalidImport(
+                                InvalidImport::InvalidFunctionType(entry.name.to_string()),
+                            ));
+                        }
+                    }
+                    ACTOR_INDEX_REMOVE_FUNCTION_NAME => {
+                        if minor_version < SCRYPTO_VM_INDEXED_COLLECTIONS_MINOR_VERSION {
+                            return Err(PrepareError::InvalidImport(
+                                InvalidImport::ImportNotAllowed(entry.name.to_string()),
+                            ));
+                        }
+
+                        if let TypeRef::Func(type_index) = entry.ty {
+                            if Self::function_type_matches(
+                                &self.module,
+                                type_index,
+                                vec![ValType::I32, ValType::I32, ValType::I32, ValType::I32],
+                                vec![ValType::I64],
+                            ) {
+                                continue;
+                            }
+                            return Err(PrepareError::InvalidImport(
+                                InvalidImport::InvalidFunctionType(entry.name.to_string()),
+                            ));
+                        }
+                    }
+                    ACTOR_INDEX_GET_FUNCTION_NAME => {
+                        if minor_version < SCRYPTO_VM_INDEXED_COLLECTIONS_MINOR_VERSION {
+                            return Err(PrepareError::InvalidImport(
+                                InvalidImport::ImportNotAllowed(entry.name.to_string()),
+                            ));
+                        }
+
+                        if let TypeRef::Func(type_index) = entry.ty {
+                            if Self::function_type_matches(
+                                &self.module,
+                                type_index,
+                                vec![ValType::I32, ValType::I32, ValType::I32, ValType::I32],
+                                vec![ValType::I64],
+                            ) {
+                                continue;
+                            }
+                            return Err(PrepareError::InvalidImport(
+                                InvalidImport::InvalidFunctionType(entry.name.to_string()),
+                            ));
+                        }
+                    }
+                    ACTOR_INDEX_SCAN_FUNCTION_NAME => {
+                        if minor_version < SCRYPTO_VM_INDEXED_COLLECTIONS_MINOR_VERSION {
+                            return Err(PrepareError::InvalidImport(
+                                InvalidImport::ImportNotAllowed(entry.name.to_string()),
+                            ));
+                        }
+
+                        if let TypeRef::Func(type_index) = entry.ty {
+                            if Self::function_type_matches(
+                                &self.module,
+                                type_index,
+                                vec![
+                                    ValType::I32,
+                                    ValType::I32,
+                                    ValType::I32,
+                                    ValType::I32,
+                                    ValType::I32,
+                                ],
+                                vec![ValType::I64],
+                            ) {
+                                continue;
+                            }
+                            return Err(PrepareError::InvalidImport(
+                                InvalidImport::InvalidFunctionType(entry.name.to_string()),
+                            ));
+                        }
+                    }
+                    ACTOR_SORTED_INDEX_INSERT_FUNCTION_NAME => {
+                        if minor_version < SCRYPTO_VM_INDEXED_COLLECTIONS_MINOR_VERSION {
+                            return Err(PrepareError::InvalidImport(
+                                InvalidImport::ImportNotAllowed(entry.name.to_string()),
+                            ));
+                        }
+
+                        if let TypeRef::Func(type_index) = entry.ty {
+                            if Self::function_type_matches(
+                                &self.module,
+                                type_index,
+                                vec![
+                                    ValType::I32,
+                                    ValType::I32,
+                                    ValType::I32,
+                                    ValType::I32,
+                                    ValType::I32,
+                                    ValType::I32,
+                                    ValType::I32,
+                                ],
+                                vec![],
+                            ) {
+                                continue;
+                            }
+                            return Err(PrepareError::InvalidImport(
+                                InvalidImport::InvalidFunctionType(entry.name.to_string()),
+                            ));
+                        }
+                    }
+                    ACTOR_SORTED_INDEX_REMOVE_FUNCTION_NAME => {
+                        if minor_version < SCRYPTO_VM_INDEXED_COLLECTIONS_MINOR_VERSION {
+                            return Err(PrepareError::InvalidImport(
+                                InvalidImport::ImportNotAllowed(entry.name.to_string()),
+                            ));
+                        }
+
+                        if let TypeRef::Func(type_index) = entry.ty {
+                            if Self::function_type_matches(
+                                &self.module,
+                                type_index,
+                                vec![
+                                    ValType::I32,
+                                    ValType::I32,
+                                    ValType::I32,
+                                    ValType::I32,
+                                    ValType::I32,
+                                ],
+                                vec![ValType::I64],
+                            ) {
+                                continue;
+                            }
+                            return Err(PrepareError::InvalidImport(
+                                InvalidImport::InvalidFunctionType(entry.name.to_string()),
+                            ));
+                        }
+                    }
+                    ACTOR_SORTED_INDEX_SCAN_FUNCTION_NAME => {
+                        if minor_version < SCRYPTO_VM_INDEXED_COLLECTIONS_MINOR_VERSION {
+                            return Err(PrepareError::InvalidImport(
+                                InvalidImport::ImportNotAllowed(entry.name.to_string()),
+                            ));
+                        }
+
+                        if let TypeRef::Func(type_index) = entry.ty {
+                            if Self::function_type_matches(
+                                &self.module,
+                                type_index,
+                                vec![
+                                    ValType::I32,
+                                    ValType::I32,
+                                    ValType::I32,
+                                    ValType::I32,
+                                    ValType::I32,
+                                ],
+                                vec![ValType::I64],
+                            ) {
+                                continue;
+                            }
+                            return Err(PrepareError::InvalidImport(
+                                InvalidImport::InvalidFunctionType(entry.name.to_string()),
+                            ));
+                        }
+                    }
                     FIELD_ENTRY_READ_FUNCTION_NAME => {
                         if let TypeRef::Func(type_index) = entry.ty {
                             if Self::function_type_matches(
@@ -855,6 +1097,104 @@ impl WasmModule {
                             ));
                         }
                     }
+                    CRYPTO_UTILS_BLAKE2B_256_HASH_FUNCTION_NAME => {
+                        if minor_version < SCRYPTO_VM_CRYPTO_UTILS_V2_MINOR_VERSION {
+                            return Err(PrepareError::InvalidImport(
+                                InvalidImport::ImportNotAllowed(entry.name.to_string()),
+                            ));
+                        }
+
+                        if let TypeRef::Func(type_index) = entry.ty {
+                            if Self::function_type_matches(
+                                &self.module,
+                                type_index,
+                                vec![ValType::I32, ValType::I32],
+                                vec![ValType::I64],
+                            ) {
+                                continue;
+                            }
+                            return Err(PrepareError::InvalidImport(
+                                InvalidImport::InvalidFunctionType(entry.name.to_string()),
+                            ));
+                        }
+                    }
+                    CRYPTO_UTILS_ED25519_VERIFY_FUNCTION_NAME => {
+                        if minor_version < SCRYPTO_VM_CRYPTO_UTILS_V2_MINOR_VERSION {
+                            return Err(PrepareError::InvalidImport(
+                                InvalidImport::ImportNotAllowed(entry.name.to_string()),
+                            ));
+                        }
+
+                        if let TypeRef::Func(type_index) = entry.ty {
+                            if Self::function_type_matches(
+                                &self.module,
+                                type_index,
+                                vec![
+                                    ValType::I32,
+                                    ValType::I32,
+                                    ValType::I32,
+                                    ValType::I32,
+                                    ValType::I32,
+                                    ValType::I32,
+                                ],
+                                vec![ValType::I32],
+                            ) {
+                                continue;
+                            }
+                            return Err(PrepareError::InvalidImport(
+                                InvalidImport::InvalidFunctionType(entry.name.to_string()),
+                            ));
+                        }
+                    }
+                    CRYPTO_UTILS_SECP256K1_ECDSA_VERIFY_FUNCTION_NAME => {
+                        if minor_version < SCRYPTO_VM_CRYPTO_UTILS_V2_MINOR_VERSION {
+                            return Err(PrepareError::InvalidImport(
+                                InvalidImport::ImportNotAllowed(entry.name.to_string()),
+                            ));
+                        }
+
+                        if let TypeRef::Func(type_index) = entry.ty {
+                            if Self::function_type_matches(
+                                &self.module,
+                                type_index,
+                                vec![
+                                    ValType::I32,
+                                    ValType::I32,
+                                    ValType::I32,
+                                    ValType::I32,
+                                    ValType::I32,
+                                    ValType::I32,
+                                ],
+                                vec![ValType::I32],
+                            ) {
+                                continue;
+                            }
+                            return Err(PrepareError::InvalidImport(
+                                InvalidImport::InvalidFunctionType(entry.name.to_string()),
+                            ));
+                        }
+                    }
+                    CRYPTO_UTILS_SECP256K1_ECDSA_VERIFY_AND_KEY_RECOVER_FUNCTION_NAME => {
+                        if minor_version < SCRYPTO_VM_CRYPTO_UTILS_V2_MINOR_VERSION {
+                            return Err(PrepareError::InvalidImport(
+                                InvalidImport::ImportNotAllowed(entry.name.to_string()),
+                            ));
+                        }
+
+                        if let TypeRef::Func(type_index) = entry.ty {
+                            if Self::function_type_matches(
+                                &self.module,
+                                type_index,
+                                vec![ValType::I32, ValType::I32, ValType::I32, ValType::I32],
+                                vec![ValType::I64],
+                            ) {
+                                continue;
+                            }
+                            return Err(PrepareError::InvalidImport(
+                                InvalidImport::InvalidFunctionType(entry.name.to_string()),
+                            ));
+                        }
+                    }
                     _ => {}
                 };
             }
@@ -1101,6 +1441,20 @@ impl WasmModule {
         }
     }
 
+    /// Replaces the bulk memory instructions whose cost depends on their operands
+    /// (`memory.copy` and `memory.fill`) with calls to equivalent MVP functions, so that they
+    /// are metered per byte by the instruction metering.
+    pub fn lower_bulk_memory_ops(self) -> Result<Self, PrepareError> {
+        match lower_bulk_memory_ops(&self.module.bytes())? {
+            Some(code) => {
+                let module =
+                    ModuleInfo::new(&code).map_err(|_| PrepareError::DeserializationError)?;
+                Ok(Self { module })
+            }
+            None => Ok(self),
+        }
+    }
+
     pub fn inject_instruction_metering<R: Rules>(
         mut self,
         rules: &R,
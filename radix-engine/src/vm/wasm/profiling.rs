@@ -0,0 +1,731 @@
+use super::bulk_memory::{decode_u32, encode_u32};
+use crate::types::*;
+use crate::vm::wasm::backtrace::wasm_call_frame_tags;
+use crate::vm::wasm::constants::*;
+use crate::vm::wasm::{PrepareError, WasmBacktraceFrame, WasmSourceLocation};
+use radix_engine_interface::blueprints::package::CodeHash;
+use sbor::rust::ops::Range;
+#[cfg(feature = "wasm_profiling")]
+use sbor::rust::sync::{Arc, Mutex};
+use wasmparser::{Operator, Parser, Payload, TypeRef};
+
+const SECTION_ID_CODE: u8 = 10;
+pub(super) const OPCODE_I64_CONST: u8 = 0x42;
+const OPCODE_I64_OR: u8 = 0x84;
+
+/// An export of a Scrypto package code, as seen on the invocation stack.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WasmProfileFrame {
+    pub code_hash: CodeHash,
+    pub export_name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WasmProfileKey {
+    /// The stack of the invoked exports, outermost first.
+    pub stack: Vec<WasmProfileFrame>,
+    /// The offset (within the instrumented code of the innermost export) of the call which
+    /// consumed the units.
+    pub code_offset: u32,
+}
+
+/// The WASM execution units consumed by Scrypto code, attributed to the WASM calls which
+/// consumed them.
+#[derive(Debug, Clone)]
+pub struct WasmProfile {
+    pub execution_units: IndexMap<WasmProfileKey, u64>,
+    /// The instrumented code of the profiled exports, which the offsets refer to.
+    pub instrumented_code: IndexMap<CodeHash, Vec<u8>>,
+    /// The code which could not be rewritten to be profiled (and which was run unprofiled), with
+    /// the reason - the units it consumed are missing from the profile.
+    pub untagged_code: IndexMap<CodeHash, PrepareError>,
+}
+
+impl Default for WasmProfile {
+    fn default() -> Self {
+        Self {
+            execution_units: index_map_new(),
+            instrumented_code: index_map_new(),
+            untagged_code: index_map_new(),
+        }
+    }
+}
+
+impl WasmProfile {
+    pub fn record(&mut self, stack: &[WasmProfileFrame], code_offset: u32, units: u32) {
+        let key = WasmProfileKey {
+            stack: stack.to_vec(),
+            code_offset,
+        };
+        *self.execution_units.entry(key).or_default() += units as u64;
+    }
+
+    pub fn total_execution_units(&self) -> u64 {
+        self.execution_units.values().sum()
+    }
+
+    pub fn code_hashes(&self) -> IndexSet<CodeHash> {
+        self.execution_units
+            .keys()
+            .filter_map(|key| key.stack.last())
+            .map(|frame| frame.code_hash)
+            .collect()
+    }
+
+    /// Resolves the profiled calls to the names of the functions they are made from (and to
+    /// source locations, if the code contains DWARF debug info), using the original code of
+    /// the profiled packages.
+    pub fn symbolize<F>(&self, mut original_code: F) -> WasmProfileReport
+    where
+        F: FnMut(&CodeHash) -> Option<Vec<u8>>,
+    {
+        let mut offsets: IndexMap<CodeHash, Vec<u32>> = index_map_new();
+        for key in self.execution_units.keys() {
+            if let Some(frame) = key.stack.last() {
+                let code_offsets = offsets.entry(frame.code_hash).or_default();
+                if !code_offsets.contains(&key.code_offset) {
+                    code_offsets.push(key.code_offset);
+                }
+            }
+        }
+
+        let mut symbols: IndexMap<(CodeHash, u32), WasmProfileSymbol> = index_map_new();
+        for (code_hash, code_offsets) in offsets {
+            let original_code = original_code(&code_hash);
+            let instructions = match (&original_code, self.instrumented_code.get(&code_hash)) {
+                (Some(original_code), Some(instrumented_code)) => {
+                    WasmBacktraceFrame::resolve_instructions(
+                        original_code,
+                        instrumented_code,
+                        &code_offsets,
+                    )
+                }
+                _ => vec![None; code_offsets.len()],
+            };
+
+            let defined_indices: Vec<u32> = instructions
+                .iter()
+                .flatten()
+                .map(|(defined_index, _)| *defined_index)
+                .collect();
+            let function_locations: IndexMap<u32, Option<WasmSourceLocation>> = match &original_code
+            {
+                Some(original_code) => defined_indices
+                    .iter()
+                    .copied()
+                    .zip(WasmBacktraceFrame::resolve_defined(
+                        original_code,
+                        &defined_indices,
+                    ))
+                    .map(|(defined_index, frame)| {
+                        (defined_index, frame.and_then(|frame| frame.location))
+                    })
+                    .collect(),
+                None => index_map_new(),
+            };
+
+            for (code_offset, instruction) in code_offsets.into_iter().zip(instructions) {
+                let symbol = match instruction {
+                    Some((defined_index, Some(frame))) => WasmProfileSymbol {
+                        function: (code_hash, defined_index),
+                        function_name: frame
+                            .function_name
+                            .unwrap_or_else(|| format!("<function #{}>", frame.function_index)),
+                        function_location: function_locations
+                            .get(&defined_index)
+                            .cloned()
+                            .flatten(),
+                        line_location: frame.location,
+                    },
+                    // Functions added by the instrumentation, eg. to consume the units of
+                    // `memory.grow`
+                    Some((defined_index, None)) => WasmProfileSymbol {
+                        function: (code_hash, defined_index),
+                        function_name: format!("<defined function #{}>", defined_index),
+                        function_location: None,
+                        line_location: None,
+                    },
+                    None => WasmProfileSymbol {
+                        function: (code_hash, u32::MAX),
+                        function_name: "<unknown function>".to_string(),
+                        function_location: None,
+                        line_location: None,
+                    },
+                };
+                symbols.insert((code_hash, code_offset), symbol);
+            }
+        }
+
+        let mut functions: IndexMap<(CodeHash, u32), u64> = index_map_new();
+        let mut lines: IndexMap<(CodeHash, u32, WasmSourceLocation), u64> = index_map_new();
+        let mut stacks: IndexMap<Vec<String>, u64> = index_map_new();
+        for (key, units) in &self.execution_units {
+            let Some(innermost) = key.stack.last() else {
+                continue;
+            };
+            let symbol = &symbols[&(innermost.code_hash, key.code_offset)];
+            *functions.entry(symbol.function).or_default() += units;
+            if let Some(location) = &symbol.line_location {
+                let line = (symbol.function.0, symbol.function.1, location.clone());
+                *lines.entry(line).or_default() += units;
+            }
+
+            let mut stack: Vec<String> = key
+                .stack
+                .iter()
+                .map(|frame| frame.export_name.clone())
+                .collect();
+            stack.push(symbol.function_name.clone());
+            *stacks.entry(stack).or_default() += units;
+        }
+
+        let function_symbols: IndexMap<(CodeHash, u32), &WasmProfileSymbol> = symbols
+            .values()
+            .map(|symbol| (symbol.function, symbol))
+            .collect();
+
+        let mut functions: Vec<WasmProfileFunction> = functions
+            .into_iter()
+            .map(|(function, execution_units)| {
+                let symbol = function_symbols[&function];
+                WasmProfileFunction {
+                    name: symbol.function_name.clone(),
+                    location: symbol.function_location.clone(),
+                    execution_units,
+                }
+            })
+            .collect();
+        functions.sort_by(|a, b| b.execution_units.cmp(&a.execution_units));
+
+        let mut lines: Vec<WasmProfileLine> = lines
+            .into_iter()
+            .map(
+                |((code_hash, defined_index, location), execution_units)| WasmProfileLine {
+                    function_name: function_symbols[&(code_hash, defined_index)]
+                        .function_name
+                        .clone(),
+                    location,
+                    execution_units,
+                },
+            )
+            .collect();
+        lines.sort_by(|a, b| b.execution_units.cmp(&a.execution_units));
+
+        WasmProfileReport {
+            total_execution_units: self.total_execution_units(),
+            functions,
+            lines,
+            stacks: stacks.into_iter().collect(),
+            untagged_code: self.untagged_code.clone(),
+        }
+    }
+}
+
+struct WasmProfileSymbol {
+    /// The code hash and the index (within the instrumented code) of the defined function.
+    function: (CodeHash, u32),
+    function_name: String,
+    function_location: Option<WasmSourceLocation>,
+    line_location: Option<WasmSourceLocation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WasmProfileFunction {
+    pub name: String,
+    pub location: Option<WasmSourceLocation>,
+    pub execution_units: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WasmProfileLine {
+    pub function_name: String,
+    pub location: WasmSourceLocation,
+    pub execution_units: u64,
+}
+
+/// A symbolized `WasmProfile`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WasmProfileReport {
+    pub total_execution_units: u64,
+    /// The units consumed by each function, summed over all stacks, most expensive first.
+    pub functions: Vec<WasmProfileFunction>,
+    /// The units consumed by each source line, summed over all stacks, most expensive first.
+    /// Only available if the code contains DWARF debug info.
+    pub lines: Vec<WasmProfileLine>,
+    /// The units consumed by each distinct stack of exports and the innermost function.
+    pub stacks: Vec<(Vec<String>, u64)>,
+    /// See `WasmProfile::untagged_code`.
+    pub untagged_code: IndexMap<CodeHash, PrepareError>,
+}
+
+impl WasmProfileReport {
+    /// Formats the stacks in the "folded" format, as consumed by `flamegraph.pl` and `inferno`.
+    pub fn to_folded_stacks(&self) -> String {
+        let mut output = String::new();
+        for (stack, units) in &self.stacks {
+            let stack: Vec<String> = stack.iter().map(|frame| frame.replace(';', ",")).collect();
+            output.push_str(&format!("{} {}\n", stack.join(";"), units));
+        }
+        output
+    }
+
+    fn percentage(&self, execution_units: u64) -> f64 {
+        if self.total_execution_units == 0 {
+            0f64
+        } else {
+            execution_units as f64 * 100f64 / self.total_execution_units as f64
+        }
+    }
+}
+
+impl fmt::Display for WasmProfileReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Total WASM execution units: {}",
+            self.total_execution_units
+        )?;
+        write!(f, "\n{:>14} {:>7}  {}", "Units", "%", "Function")?;
+        for function in &self.functions {
+            write!(
+                f,
+                "\n{:>14} {:>7.2}  {}",
+                function.execution_units,
+                self.percentage(function.execution_units),
+                function.name
+            )?;
+            if let Some(location) = &function.location {
+                write!(f, " ({})", location)?;
+            }
+        }
+        if !self.lines.is_empty() {
+            write!(f, "\n\n{:>14} {:>7}  {}", "Units", "%", "Line")?;
+            for line in &self.lines {
+                write!(
+                    f,
+                    "\n{:>14} {:>7.2}  {} ({})",
+                    line.execution_units,
+                    self.percentage(line.execution_units),
+                    line.location,
+                    line.function_name
+                )?;
+            }
+        }
+        if !self.untagged_code.is_empty() {
+            write!(f, "\n\nNot profiled (the code could not be rewritten):")?;
+            for (code_hash, error) in &self.untagged_code {
+                write!(f, "\n  {:?}: {:?}", code_hash, error)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Collects a `WasmProfile` of the code run by an engine, following the invocation stack.
+///
+/// Profiling is a debugging aid only: it requires the instrumented code to be rewritten with
+/// `tag_wasm_execution_units`, which is done by the engine the profiler is attached to.
+#[cfg(feature = "wasm_profiling")]
+#[derive(Debug, Clone, Default)]
+pub struct WasmProfiler(Arc<Mutex<WasmProfilerState>>);
+
+#[cfg(feature = "wasm_profiling")]
+#[derive(Debug, Default)]
+struct WasmProfilerState {
+    stack: Vec<WasmProfileFrame>,
+    profile: WasmProfile,
+    /// The instrumented code of all the profiled modules, kept across `take_profile`s.
+    instrumented_code: IndexMap<CodeHash, Vec<u8>>,
+    /// The modules which could not be profiled, kept across `take_profile`s.
+    untagged_code: IndexMap<CodeHash, PrepareError>,
+}
+
+#[cfg(feature = "wasm_profiling")]
+impl WasmProfilerState {
+    fn with_instrumented_code(&self, mut profile: WasmProfile) -> WasmProfile {
+        for code_hash in profile.code_hashes() {
+            if let Some(instrumented_code) = self.instrumented_code.get(&code_hash) {
+                profile
+                    .instrumented_code
+                    .insert(code_hash, instrumented_code.clone());
+            }
+        }
+        profile.untagged_code = self.untagged_code.clone();
+        profile
+    }
+}
+
+#[cfg(feature = "wasm_profiling")]
+impl WasmProfiler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn enter(&self, code_hash: CodeHash, export_name: &str) {
+        self.0.lock().unwrap().stack.push(WasmProfileFrame {
+            code_hash,
+            export_name: export_name.to_string(),
+        });
+    }
+
+    pub fn exit(&self) {
+        self.0.lock().unwrap().stack.pop();
+    }
+
+    /// Keeps the instrumented code of a profiled module, which the recorded offsets refer to.
+    pub fn register_code(&self, code_hash: CodeHash, instrumented_code: &[u8]) {
+        self.0
+            .lock()
+            .unwrap()
+            .instrumented_code
+            .entry(code_hash)
+            .or_insert_with(|| instrumented_code.to_vec());
+    }
+
+    /// Records a module which could not be rewritten to be profiled, and is run unprofiled.
+    pub fn register_untagged_code(&self, code_hash: CodeHash, error: PrepareError) {
+        self.0
+            .lock()
+            .unwrap()
+            .untagged_code
+            .insert(code_hash, error);
+    }
+
+    /// Attributes the units consumed by a `tag_wasm_execution_units`-rewritten call to the
+    /// current invocation stack.
+    pub fn record(&self, tagged_units: u64) {
+        let (code_offset, units) = untag_wasm_execution_units(tagged_units);
+        let mut state = self.0.lock().unwrap();
+        let WasmProfilerState { stack, profile, .. } = &mut *state;
+        profile.record(stack, code_offset, units);
+    }
+
+    pub fn profile(&self) -> WasmProfile {
+        let state = self.0.lock().unwrap();
+        state.with_instrumented_code(state.profile.clone())
+    }
+
+    pub fn take_profile(&self) -> WasmProfile {
+        let mut state = self.0.lock().unwrap();
+        let profile = sbor::rust::mem::take(&mut state.profile);
+        state.with_instrumented_code(profile)
+    }
+}
+
+/// Rewrites the instrumented code, so that every call to consume WASM execution units also
+/// passes its own offset (within the given code), in the upper 32 bits of the number of units.
+///
+/// The rewritten code must only be run by a profiling engine, see `untag_wasm_execution_units`.
+pub fn tag_wasm_execution_units(code: &[u8]) -> Result<Vec<u8>, PrepareError> {
+    tag_wasm_code(code, true, false)
+}
+
+/// Rewrites the instrumented code with `tag_wasm_execution_units` and/or `tag_wasm_call_frames`,
+/// in a single pass so that the offsets tagged by either refer to the given code.
+pub fn tag_wasm_code(
+    code: &[u8],
+    tag_execution_units: bool,
+    tag_call_frames: bool,
+) -> Result<Vec<u8>, PrepareError> {
+    let mut replacements = Vec::new();
+    if tag_execution_units {
+        replacements.extend(wasm_execution_units_tags(code)?);
+    }
+    if tag_call_frames {
+        replacements.extend(wasm_call_frame_tags(code)?);
+    }
+
+    if replacements.is_empty() {
+        return Ok(code.to_vec());
+    }
+
+    // Insertions go before the instructions replaced at the same offset
+    replacements.sort_by_key(|(range, _)| (range.start, range.end));
+    rewrite_code_section(code, &replacements)
+}
+
+fn wasm_execution_units_tags(code: &[u8]) -> Result<Vec<(Range<usize>, Vec<u8>)>, PrepareError> {
+    let mut gas_function_index = None;
+    let mut num_of_imported_functions = 0u32;
+    let mut replacements: Vec<(Range<usize>, Vec<u8>)> = Vec::new();
+
+    for payload in Parser::new(0).parse_all(code) {
+        match payload.map_err(|err| PrepareError::WasmParserError(err.to_string()))? {
+            Payload::ImportSection(reader) => {
+                for import in reader {
+                    let import =
+                        import.map_err(|err| PrepareError::WasmParserError(err.to_string()))?;
+                    if let TypeRef::Func(_) = import.ty {
+                        if import.module == MODULE_ENV_NAME
+                            && import.name == COSTING_CONSUME_WASM_EXECUTION_UNITS_FUNCTION_NAME
+                        {
+                            gas_function_index = Some(num_of_imported_functions);
+                        }
+                        num_of_imported_functions += 1;
+                    }
+                }
+            }
+            Payload::CodeSectionEntry(body) => {
+                let mut reader = body
+                    .get_operators_reader()
+                    .map_err(|err| PrepareError::WasmParserError(err.to_string()))?;
+                let mut previous_const = None;
+                while !reader.eof() {
+                    let start = reader.original_position();
+                    let op = reader
+                        .read()
+                        .map_err(|err| PrepareError::WasmParserError(err.to_string()))?;
+                    let end = reader.original_position();
+                    match (op, previous_const.take()) {
+                        (Operator::I64Const { value }, _) => {
+                            previous_const = Some((start..end, value));
+                        }
+                        (Operator::Call { function_index }, previous_const)
+                            if Some(function_index) == gas_function_index =>
+                        {
+                            let tag = (start as u64) << 32;
+                            match previous_const {
+                                Some((range, value)) => {
+                                    if let Ok(units) = u32::try_from(value) {
+                                        let mut encoded = vec![OPCODE_I64_CONST];
+                                        encoded.extend(encode_i64((tag | units as u64) as i64));
+                                        replacements.push((range, encoded));
+                                    }
+                                }
+                                None => {
+                                    // The units are computed (eg. for `memory.grow`), so the tag
+                                    // is added to them at runtime
+                                    let mut encoded = vec![OPCODE_I64_CONST];
+                                    encoded.extend(encode_i64(tag as i64));
+                                    encoded.push(OPCODE_I64_OR);
+                                    replacements.push((start..start, encoded));
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(replacements)
+}
+
+/// Splits units consumed by `tag_wasm_execution_units`-rewritten code into the offset of the
+/// call which consumed them, and the actual units.
+pub fn untag_wasm_execution_units(tagged_units: u64) -> (u32, u32) {
+    ((tagged_units >> 32) as u32, tagged_units as u32)
+}
+
+/// Applies the replacements (which must be sorted and within function bodies), re-encoding
+/// the sizes of the modified function bodies and of the code section.
+fn rewrite_code_section(
+    code: &[u8],
+    replacements: &[(Range<usize>, Vec<u8>)],
+) -> Result<Vec<u8>, PrepareError> {
+    let invalid_section = || PrepareError::WasmParserError("Invalid section".to_string());
+
+    let mut output = code.get(..8).ok_or_else(invalid_section)?.to_vec();
+    let mut offset = 8;
+    let mut replacements = replacements.iter().peekable();
+
+    while offset < code.len() {
+        let id = code[offset];
+        let (size, size_len) = decode_u32(&code[offset + 1..]).ok_or_else(invalid_section)?;
+        let content_start = offset + 1 + size_len;
+        let content_end = content_start
+            .checked_add(size as usize)
+            .filter(|end| *end <= code.len())
+            .ok_or_else(invalid_section)?;
+
+        if id != SECTION_ID_CODE {
+            output.extend_from_slice(&code[offset..content_end]);
+            offset = content_end;
+            continue;
+        }
+
+        let (count, count_len) =
+            decode_u32(&code[content_start..content_end]).ok_or_else(invalid_section)?;
+        let mut new_content = encode_u32(count);
+        let mut body_offset = content_start + count_len;
+        for _ in 0..count {
+            let (body_size, body_size_len) =
+                decode_u32(&code[body_offset..content_end]).ok_or_else(invalid_section)?;
+            let body_start = body_offset + body_size_len;
+            let body_end = body_start
+                .checked_add(body_size as usize)
+                .filter(|end| *end <= content_end)
+                .ok_or_else(invalid_section)?;
+
+            let mut new_body = Vec::with_capacity(body_size as usize);
+            let mut copied = body_start;
+            while let Some((range, encoded)) = replacements.next_if(|(r, _)| r.end <= body_end) {
+                if range.start < copied {
+                    return Err(invalid_section());
+                }
+                new_body.extend_from_slice(&code[copied..range.start]);
+                new_body.extend_from_slice(encoded);
+                copied = range.end;
+            }
+            new_body.extend_from_slice(&code[copied..body_end]);
+
+            new_content.extend(encode_u32(new_body.len() as u32));
+            new_content.extend(new_body);
+            body_offset = body_end;
+        }
+
+        output.push(id);
+        output.extend(encode_u32(new_content.len() as u32));
+        output.extend(new_content);
+        offset = content_end;
+    }
+
+    Ok(output)
+}
+
+pub(super) fn encode_i64(mut value: i64) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if (value == 0 && byte & 0x40 == 0) || (value == -1 && byte & 0x40 != 0) {
+            bytes.push(byte);
+            return bytes;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn consumed_units(code: &[u8]) -> Vec<u64> {
+        let mut units = Vec::new();
+        for payload in Parser::new(0).parse_all(code) {
+            if let Payload::CodeSectionEntry(body) = payload.unwrap() {
+                for op in body.get_operators_reader().unwrap() {
+                    if let Operator::I64Const { value } = op.unwrap() {
+                        units.push(value as u64);
+                    }
+                }
+            }
+        }
+        units
+    }
+
+    fn gas_call_offsets(code: &[u8]) -> Vec<u32> {
+        let mut offsets = Vec::new();
+        for payload in Parser::new(0).parse_all(code) {
+            if let Payload::CodeSectionEntry(body) = payload.unwrap() {
+                let mut reader = body.get_operators_reader().unwrap();
+                while !reader.eof() {
+                    let offset = reader.original_position();
+                    if let Operator::Call { function_index: 0 } = reader.read().unwrap() {
+                        offsets.push(offset as u32);
+                    }
+                }
+            }
+        }
+        offsets
+    }
+
+    #[test]
+    fn test_tag_wasm_execution_units() {
+        let code = wabt::wat2wasm(
+            r#"
+            (module
+                (import "env" "gas" (func $gas (param i64)))
+                (func $f
+                    (call $gas (i64.const 5))
+                )
+                (func $g
+                    (call $gas (i64.const 300))
+                    (drop (i64.const 7))
+                    (call $f)
+                )
+                (func $h (param i64)
+                    (call $gas (local.get 0))
+                )
+            )
+            "#,
+        )
+        .unwrap();
+        let offsets = gas_call_offsets(&code);
+
+        let tagged = tag_wasm_execution_units(&code).unwrap();
+
+        wasmparser::Validator::new().validate_all(&tagged).unwrap();
+        assert_eq!(
+            consumed_units(&tagged)
+                .into_iter()
+                .map(untag_wasm_execution_units)
+                .collect::<Vec<_>>(),
+            vec![(offsets[0], 5), (offsets[1], 300), (0, 7), (offsets[2], 0)]
+        );
+    }
+
+    #[test]
+    fn test_profile_report() {
+        let original_code = wabt::Wat2Wasm::new()
+            .write_debug_names(true)
+            .convert(
+                r#"
+                (module
+                    (func $_ZN8blueprint5inner17h0123456789abcdefE)
+                    (func $Test_f call 0)
+                )
+                "#,
+            )
+            .unwrap();
+        // Instrumentation adds an import and a function consuming computed units
+        let instrumented_code = wabt::wat2wasm(
+            r#"
+            (module
+                (import "env" "gas" (func $gas (param i64)))
+                (func (call $gas (i64.const 1)))
+                (func (call $gas (i64.const 1)) call 1)
+                (func (param i64) (call $gas (local.get 0)))
+            )
+            "#,
+        )
+        .unwrap();
+        let offsets = gas_call_offsets(&instrumented_code);
+        let code_hash = CodeHash(hash(original_code.as_ref()));
+        let frame = WasmProfileFrame {
+            code_hash,
+            export_name: "Test_f".to_string(),
+        };
+
+        let mut profile = WasmProfile::default();
+        profile.record(&[frame.clone()], offsets[1], 10);
+        profile.record(&[frame.clone()], offsets[0], 30);
+        profile.record(&[frame.clone()], offsets[0], 20);
+        profile.record(&[frame], offsets[2], 40);
+        profile
+            .instrumented_code
+            .insert(code_hash, instrumented_code);
+
+        let report = profile.symbolize(|_| Some(original_code.as_ref().to_vec()));
+
+        assert_eq!(report.total_execution_units, 100);
+        assert_eq!(
+            report
+                .functions
+                .iter()
+                .map(|function| (function.name.as_str(), function.execution_units))
+                .collect::<Vec<_>>(),
+            vec![
+                ("blueprint::inner", 50),
+                ("<defined function #2>", 40),
+                ("Test_f", 10)
+            ]
+        );
+        assert_eq!(
+            report.to_folded_stacks(),
+            "Test_f;Test_f 10\nTest_f;blueprint::inner 50\nTest_f;<defined function #2> 40\n"
+        );
+    }
+}
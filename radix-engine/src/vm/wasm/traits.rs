@@ -1,6 +1,9 @@
 use crate::errors::InvokeError;
 use crate::types::*;
 use crate::vm::wasm::errors::*;
+#[cfg(feature = "std")]
+use crate::vm::wasm::WasmDiskCache;
+use crate::vm::wasm::WasmTrapFrame;
 use radix_engine_interface::api::actor_api::EventFlags;
 use radix_engine_interface::api::ActorRefHandle;
 use radix_engine_interface::blueprints::package::CodeHash;
@@ -105,6 +108,19 @@ pub trait WasmRuntime {
         key: Vec<u8>,
     ) -> Result<Buffer, InvokeError<WasmRuntimeError>>;
 
+    fn key_value_store_scan(
+        &mut self,
+        node_id: Vec<u8>,
+        cursor: Vec<u8>,
+        limit: u32,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>>;
+
+    fn key_value_store_count(
+        &mut self,
+        node_id: Vec<u8>,
+        limit: u32,
+    ) -> Result<u32, InvokeError<WasmRuntimeError>>;
+
     fn instance_of(
         &mut self,
         object_id: Vec<u8>,
@@ -152,6 +168,61 @@ pub trait WasmRuntime {
 
     fn actor_get_blueprint_name(&mut self) -> Result<Buffer, InvokeError<WasmRuntimeError>>;
 
+    fn actor_index_insert(
+        &mut self,
+        object_handle: u32,
+        collection_index: u8,
+        key: Vec<u8>,
+        value: Vec<u8>,
+    ) -> Result<(), InvokeError<WasmRuntimeError>>;
+
+    fn actor_index_remove(
+        &mut self,
+        object_handle: u32,
+        collection_index: u8,
+        key: Vec<u8>,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>>;
+
+    fn actor_index_get(
+        &mut self,
+        object_handle: u32,
+        collection_index: u8,
+        key: Vec<u8>,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>>;
+
+    fn actor_index_scan(
+        &mut self,
+        object_handle: u32,
+        collection_index: u8,
+        cursor: Vec<u8>,
+        limit: u32,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>>;
+
+    fn actor_sorted_index_insert(
+        &mut self,
+        object_handle: u32,
+        collection_index: u8,
+        sort_prefix: u32,
+        key: Vec<u8>,
+        value: Vec<u8>,
+    ) -> Result<(), InvokeError<WasmRuntimeError>>;
+
+    fn actor_sorted_index_remove(
+        &mut self,
+        object_handle: u32,
+        collection_index: u8,
+        sort_prefix: u32,
+        key: Vec<u8>,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>>;
+
+    fn actor_sorted_index_scan(
+        &mut self,
+        object_handle: u32,
+        collection_index: u8,
+        cursor: Vec<u8>,
+        limit: u32,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>>;
+
     fn consume_wasm_execution_units(&mut self, n: u32)
         -> Result<(), InvokeError<WasmRuntimeError>>;
 
@@ -230,6 +301,31 @@ pub trait WasmRuntime {
         &mut self,
         data: Vec<u8>,
     ) -> Result<Buffer, InvokeError<WasmRuntimeError>>;
+
+    fn crypto_utils_blake2b_256_hash(
+        &mut self,
+        data: Vec<u8>,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>>;
+
+    fn crypto_utils_ed25519_verify(
+        &mut self,
+        message: Vec<u8>,
+        public_key: Vec<u8>,
+        signature: Vec<u8>,
+    ) -> Result<u32, InvokeError<WasmRuntimeError>>;
+
+    fn crypto_utils_secp256k1_ecdsa_verify(
+        &mut self,
+        hash: Vec<u8>,
+        public_key: Vec<u8>,
+        signature: Vec<u8>,
+    ) -> Result<u32, InvokeError<WasmRuntimeError>>;
+
+    fn crypto_utils_secp256k1_ecdsa_verify_and_key_recover(
+        &mut self,
+        hash: Vec<u8>,
+        signature: Vec<u8>,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>>;
 }
 
 /// Represents an instantiated, invocable Scrypto module.
@@ -247,6 +343,15 @@ pub trait WasmInstance {
         args: Vec<Buffer>,
         runtime: &mut Box<dyn WasmRuntime + 'r>,
     ) -> Result<Vec<u8>, InvokeError<WasmRuntimeError>>;
+
+    /// Returns the WASM call stack at the time of the last trap, innermost frame first.
+    ///
+    /// The stack is only captured by instances created with
+    /// `WasmEngine::instantiate_with_backtraces`, by engines which support that. Returns an error
+    /// if the code of the instance could not be prepared to capture it.
+    fn take_trap_backtrace(&mut self) -> Result<Vec<WasmTrapFrame>, PrepareError> {
+        Ok(Vec::new())
+    }
 }
 
 /// A Scrypto WASM engine validates, instruments and runs Scrypto modules.
@@ -257,4 +362,23 @@ pub trait WasmEngine {
     ///
     /// The code must have been validated and instrumented!
     fn instantiate(&self, code_hash: CodeHash, instrumented_code: &[u8]) -> Self::WasmInstance;
+
+    /// Instantiate a Scrypto module which captures the WASM call stack of its traps, see
+    /// `WasmInstance::take_trap_backtrace`.
+    ///
+    /// Engines which capture it anyway needn't override this.
+    fn instantiate_with_backtraces(
+        &self,
+        code_hash: CodeHash,
+        instrumented_code: &[u8],
+    ) -> Self::WasmInstance {
+        self.instantiate(code_hash, instrumented_code)
+    }
+
+    /// Returns the persistent cache of this engine, which also holds the instrumented code of the
+    /// packages published through it.
+    #[cfg(feature = "std")]
+    fn disk_cache(&self) -> Option<&WasmDiskCache> {
+        None
+    }
 }
@@ -2,7 +2,7 @@ use crate::types::*;
 use crate::vm::wasm::*;
 use radix_engine_interface::blueprints::package::BlueprintDefinitionInit;
 
-pub const SCRYPTO_V1_LATEST_MINOR_VERSION: u64 = 1u64;
+pub const SCRYPTO_V1_LATEST_MINOR_VERSION: u64 = 5u64;
 
 pub struct ScryptoV1WasmValidator {
     pub max_memory_size_in_pages: u32,
@@ -13,6 +13,7 @@ pub struct ScryptoV1WasmValidator {
     pub max_number_of_function_locals: u32,
     pub max_number_of_globals: u32,
     pub instrumenter_config: WasmValidatorConfigV1,
+    pub wasm_features: WasmFeatureSet,
     pub minor_version: u64,
 }
 
@@ -31,6 +32,7 @@ impl ScryptoV1WasmValidator {
             max_number_of_function_locals: MAX_NUMBER_OF_FUNCTION_LOCALS,
             max_number_of_globals: MAX_NUMBER_OF_GLOBALS,
             instrumenter_config: WasmValidatorConfigV1::new(),
+            wasm_features: WasmFeatureSet::for_minor_version(minor_version),
             minor_version,
         }
     }
@@ -47,6 +49,7 @@ impl Default for ScryptoV1WasmValidator {
             max_number_of_function_locals: MAX_NUMBER_OF_FUNCTION_LOCALS,
             max_number_of_globals: MAX_NUMBER_OF_GLOBALS,
             instrumenter_config: WasmValidatorConfigV1::new(),
+            wasm_features: WasmFeatureSet::for_minor_version(SCRYPTO_V1_LATEST_MINOR_VERSION),
             minor_version: SCRYPTO_V1_LATEST_MINOR_VERSION,
         }
     }
@@ -58,7 +61,7 @@ impl ScryptoV1WasmValidator {
         code: &[u8],
         blueprints: I,
     ) -> Result<(Vec<u8>, Vec<String>), PrepareError> {
-        WasmModule::init(code)?
+        WasmModule::init_with_features(code, self.wasm_features)?
             .enforce_no_start_function()?
             .enforce_import_constraints(self.minor_version)?
             .enforce_export_names()?
@@ -72,6 +75,7 @@ impl ScryptoV1WasmValidator {
             )?
             .enforce_global_limit(self.max_number_of_globals)?
             .enforce_export_constraints(blueprints)?
+            .lower_bulk_memory_ops()?
             .inject_instruction_metering(&self.instrumenter_config)?
             .inject_stack_metering(self.instrumenter_config.max_stack_size())?
             .ensure_instantiatable()?
@@ -3,7 +3,32 @@ use wasm_instrument::gas_metering::MemoryGrowCost;
 use wasm_instrument::gas_metering::Rules;
 use wasmparser::Operator::{self, *};
 
-use super::InstructionWeights;
+use super::{InstructionWeights, SCRYPTO_VM_BULK_MEMORY_MINOR_VERSION};
+
+/// The WASM proposals allowed on top of the MVP.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WasmFeatureSet {
+    pub mutable_global: bool,
+    pub sign_extension: bool,
+    /// Both `memory.copy` and `memory.fill` are lowered into MVP code before instrumentation,
+    /// while the remaining bulk memory instructions are rejected by the instruction metering.
+    pub bulk_memory: bool,
+    /// Out of scope for now: the `wasmer` singlepass compiler doesn't support multiple return
+    /// values, so no Scrypto VM minor version enables it.
+    pub multi_value: bool,
+}
+
+impl WasmFeatureSet {
+    /// Returns the features enabled by the given Scrypto VM minor version.
+    pub fn for_minor_version(minor_version: u64) -> Self {
+        Self {
+            mutable_global: true,
+            sign_extension: true,
+            bulk_memory: minor_version >= SCRYPTO_VM_BULK_MEMORY_MINOR_VERSION,
+            multi_value: false,
+        }
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct WasmValidatorConfigV1 {
@@ -19,8 +44,10 @@ impl WasmValidatorConfigV1 {
         }
     }
 
+    /// Bumped whenever the validation or instrumentation output changes, e.g. version 2 lowers
+    /// bulk memory instructions before instrumentation.
     pub fn version(&self) -> u8 {
-        1
+        2
     }
 
     pub fn max_stack_size(&self) -> u32 {
@@ -213,16 +240,18 @@ impl Rules for WasmValidatorConfigV1 {
             I64Extend32S => Some(self.weights.i64extendsi32),
 
             // Bulk memory proposal
-            | MemoryInit { .. }
+            // `memory.copy` and `memory.fill` are lowered into MVP code before the metering is
+            // injected, and the remaining instructions are not supported
+            MemoryInit { .. }
             | DataDrop { .. }
             | MemoryCopy { .. }
             | MemoryFill { .. }
             | TableInit { .. }
             | ElemDrop { .. }
-            | TableCopy { .. }
+            | TableCopy { .. } => None,
 
             // Exception handling proposal
-            | Try { .. }
+            Try { .. }
             | Catch { .. }
             | Throw { .. }
             | Rethrow { .. }
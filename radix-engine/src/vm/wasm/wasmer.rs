@@ -2,7 +2,10 @@ use crate::errors::InvokeError;
 use crate::types::*;
 #[cfg(feature = "coverage")]
 use crate::utils::save_coverage_data;
+use crate::vm::wasm::backtrace::WasmTrapFrame;
 use crate::vm::wasm::constants::*;
+#[cfg(feature = "std")]
+use crate::vm::wasm::disk_cache::WasmDiskCache;
 use crate::vm::wasm::errors::*;
 use crate::vm::wasm::traits::*;
 use radix_engine_interface::api::actor_api::EventFlags;
@@ -50,6 +53,9 @@ pub struct WasmerInstance {
     ///
     /// For information on why the pointer is masked, see the docs for `WasmerInstanceEnv`
     runtime_ptr: Arc<Mutex<usize>>,
+
+    /// The WASM call stack at the time of the last trap.
+    trap_backtrace: Vec<WasmTrapFrame>,
 }
 
 /// The WasmerInstanceEnv implements WasmerEnv - and this needs to be `Send + Sync` for
@@ -87,6 +93,8 @@ pub struct WasmerEngine {
     modules_cache: moka::sync::Cache<CodeHash, Arc<WasmerModule>>,
     #[cfg(feature = "radix_engine_fuzzing")]
     modules_cache: usize,
+    #[cfg(feature = "std")]
+    disk_cache: Option<WasmDiskCache>,
 }
 
 pub fn read_memory(instance: &Instance, ptr: u32, len: u32) -> Result<Vec<u8>, WasmRuntimeError> {
@@ -421,6 +429,36 @@ impl WasmerModule {
                 .map(|buffer| buffer.0)
         }
 
+        pub fn key_value_store_scan(
+            env: &WasmerInstanceEnv,
+            node_id_ptr: u32,
+            node_id_len: u32,
+            cursor_ptr: u32,
+            cursor_len: u32,
+            limit: u32,
+        ) -> Result<u64, InvokeError<WasmRuntimeError>> {
+            let (instance, runtime) = grab_runtime!(env);
+
+            runtime
+                .key_value_store_scan(
+                    read_memory(&instance, node_id_ptr, node_id_len)?,
+                    read_memory(&instance, cursor_ptr, cursor_len)?,
+                    limit,
+                )
+                .map(|buffer| buffer.0)
+        }
+
+        pub fn key_value_store_count(
+            env: &WasmerInstanceEnv,
+            node_id_ptr: u32,
+            node_id_len: u32,
+            limit: u32,
+        ) -> Result<u32, InvokeError<WasmRuntimeError>> {
+            let (instance, runtime) = grab_runtime!(env);
+
+            runtime.key_value_store_count(read_memory(&instance, node_id_ptr, node_id_len)?, limit)
+        }
+
         pub fn key_value_entry_read(
             env: &WasmerInstanceEnv,
             handle: u32,
@@ -505,6 +543,130 @@ impl WasmerModule {
             runtime.actor_open_field(object_handle, field, flags)
         }
 
+        pub fn actor_index_insert(
+            env: &WasmerInstanceEnv,
+            object_handle: u32,
+            collection_index: u8,
+            key_ptr: u32,
+            key_len: u32,
+            value_ptr: u32,
+            value_len: u32,
+        ) -> Result<(), InvokeError<WasmRuntimeError>> {
+            let (instance, runtime) = grab_runtime!(env);
+
+            let key = read_memory(&instance, key_ptr, key_len)?;
+            let value = read_memory(&instance, value_ptr, value_len)?;
+
+            runtime.actor_index_insert(object_handle, collection_index, key, value)
+        }
+
+        pub fn actor_index_remove(
+            env: &WasmerInstanceEnv,
+            object_handle: u32,
+            collection_index: u8,
+            key_ptr: u32,
+            key_len: u32,
+        ) -> Result<u64, InvokeError<WasmRuntimeError>> {
+            let (instance, runtime) = grab_runtime!(env);
+
+            let key = read_memory(&instance, key_ptr, key_len)?;
+
+            runtime
+                .actor_index_remove(object_handle, collection_index, key)
+                .map(|buffer| buffer.0)
+        }
+
+        pub fn actor_index_get(
+            env: &WasmerInstanceEnv,
+            object_handle: u32,
+            collection_index: u8,
+            key_ptr: u32,
+            key_len: u32,
+        ) -> Result<u64, InvokeError<WasmRuntimeError>> {
+            let (instance, runtime) = grab_runtime!(env);
+
+            let key = read_memory(&instance, key_ptr, key_len)?;
+
+            runtime
+                .actor_index_get(object_handle, collection_index, key)
+                .map(|buffer| buffer.0)
+        }
+
+        pub fn actor_index_scan(
+            env: &WasmerInstanceEnv,
+            object_handle: u32,
+            collection_index: u8,
+            cursor_ptr: u32,
+            cursor_len: u32,
+            limit: u32,
+        ) -> Result<u64, InvokeError<WasmRuntimeError>> {
+            let (instance, runtime) = grab_runtime!(env);
+
+            let cursor = read_memory(&instance, cursor_ptr, cursor_len)?;
+
+            runtime
+                .actor_index_scan(object_handle, collection_index, cursor, limit)
+                .map(|buffer| buffer.0)
+        }
+
+        pub fn actor_sorted_index_insert(
+            env: &WasmerInstanceEnv,
+            object_handle: u32,
+            collection_index: u8,
+            sort_prefix: u32,
+            key_ptr: u32,
+            key_len: u32,
+            value_ptr: u32,
+            value_len: u32,
+        ) -> Result<(), InvokeError<WasmRuntimeError>> {
+            let (instance, runtime) = grab_runtime!(env);
+
+            let key = read_memory(&instance, key_ptr, key_len)?;
+            let value = read_memory(&instance, value_ptr, value_len)?;
+
+            runtime.actor_sorted_index_insert(
+                object_handle,
+                collection_index,
+                sort_prefix,
+                key,
+                value,
+            )
+        }
+
+        pub fn actor_sorted_index_remove(
+            env: &WasmerInstanceEnv,
+            object_handle: u32,
+            collection_index: u8,
+            sort_prefix: u32,
+            key_ptr: u32,
+            key_len: u32,
+        ) -> Result<u64, InvokeError<WasmRuntimeError>> {
+            let (instance, runtime) = grab_runtime!(env);
+
+            let key = read_memory(&instance, key_ptr, key_len)?;
+
+            runtime
+                .actor_sorted_index_remove(object_handle, collection_index, sort_prefix, key)
+                .map(|buffer| buffer.0)
+        }
+
+        pub fn actor_sorted_index_scan(
+            env: &WasmerInstanceEnv,
+            object_handle: u32,
+            collection_index: u8,
+            cursor_ptr: u32,
+            cursor_len: u32,
+            limit: u32,
+        ) -> Result<u64, InvokeError<WasmRuntimeError>> {
+            let (instance, runtime) = grab_runtime!(env);
+
+            let cursor = read_memory(&instance, cursor_ptr, cursor_len)?;
+
+            runtime
+                .actor_sorted_index_scan(object_handle, collection_index, cursor, limit)
+                .map(|buffer| buffer.0)
+        }
+
         pub fn actor_get_node_id(
             env: &WasmerInstanceEnv,
             actor_ref_handle: u32,
@@ -760,6 +922,75 @@ impl WasmerModule {
                 .map(|buffer| buffer.0)
         }
 
+        pub fn blake2b_256_hash(
+            env: &WasmerInstanceEnv,
+            data_ptr: u32,
+            data_len: u32,
+        ) -> Result<u64, InvokeError<WasmRuntimeError>> {
+            let (instance, runtime) = grab_runtime!(env);
+
+            let data = read_memory(instance, data_ptr, data_len)?;
+
+            runtime
+                .crypto_utils_blake2b_256_hash(data)
+                .map(|buffer| buffer.0)
+        }
+
+        pub fn ed25519_verify(
+            env: &WasmerInstanceEnv,
+            message_ptr: u32,
+            message_len: u32,
+            public_key_ptr: u32,
+            public_key_len: u32,
+            signature_ptr: u32,
+            signature_len: u32,
+        ) -> Result<u32, InvokeError<WasmRuntimeError>> {
+            let (instance, runtime) = grab_runtime!(env);
+
+            let message = read_memory(&instance, message_ptr, message_len)?;
+
+            let public_key = read_memory(&instance, public_key_ptr, public_key_len)?;
+            let signature = read_memory(instance, signature_ptr, signature_len)?;
+
+            runtime.crypto_utils_ed25519_verify(message, public_key, signature)
+        }
+
+        pub fn secp256k1_ecdsa_verify(
+            env: &WasmerInstanceEnv,
+            hash_ptr: u32,
+            hash_len: u32,
+            public_key_ptr: u32,
+            public_key_len: u32,
+            signature_ptr: u32,
+            signature_len: u32,
+        ) -> Result<u32, InvokeError<WasmRuntimeError>> {
+            let (instance, runtime) = grab_runtime!(env);
+
+            let hash = read_memory(&instance, hash_ptr, hash_len)?;
+
+            let public_key = read_memory(&instance, public_key_ptr, public_key_len)?;
+            let signature = read_memory(instance, signature_ptr, signature_len)?;
+
+            runtime.crypto_utils_secp256k1_ecdsa_verify(hash, public_key, signature)
+        }
+
+        pub fn secp256k1_ecdsa_verify_and_key_recover(
+            env: &WasmerInstanceEnv,
+            hash_ptr: u32,
+            hash_len: u32,
+            signature_ptr: u32,
+            signature_len: u32,
+        ) -> Result<u64, InvokeError<WasmRuntimeError>> {
+            let (instance, runtime) = grab_runtime!(env);
+
+            let hash = read_memory(&instance, hash_ptr, hash_len)?;
+            let signature = read_memory(instance, signature_ptr, signature_len)?;
+
+            runtime
+                .crypto_utils_secp256k1_ecdsa_verify_and_key_recover(hash, signature)
+                .map(|buffer| buffer.0)
+        }
+
         #[cfg(feature = "radix_engine_tests")]
         pub fn host_read_memory(
             env: &WasmerInstanceEnv,
@@ -837,6 +1068,8 @@ impl WasmerModule {
                 KEY_VALUE_STORE_NEW_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), key_value_store_new),
                 KEY_VALUE_STORE_OPEN_ENTRY_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), key_value_store_open_entry),
                 KEY_VALUE_STORE_REMOVE_ENTRY_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), key_value_store_remove_entry),
+                KEY_VALUE_STORE_SCAN_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), key_value_store_scan),
+                KEY_VALUE_STORE_COUNT_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), key_value_store_count),
                 KEY_VALUE_ENTRY_READ_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), key_value_entry_read),
                 KEY_VALUE_ENTRY_WRITE_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), key_value_entry_write),
                 KEY_VALUE_ENTRY_REMOVE_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), key_value_entry_remove),
@@ -845,6 +1078,13 @@ impl WasmerModule {
                 FIELD_ENTRY_WRITE_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), field_entry_write),
                 FIELD_ENTRY_CLOSE_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), field_entry_close),
                 ACTOR_OPEN_FIELD_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), actor_open_field),
+                ACTOR_INDEX_INSERT_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), actor_index_insert),
+                ACTOR_INDEX_REMOVE_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), actor_index_remove),
+                ACTOR_INDEX_GET_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), actor_index_get),
+                ACTOR_INDEX_SCAN_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), actor_index_scan),
+                ACTOR_SORTED_INDEX_INSERT_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), actor_sorted_index_insert),
+                ACTOR_SORTED_INDEX_REMOVE_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), actor_sorted_index_remove),
+                ACTOR_SORTED_INDEX_SCAN_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), actor_sorted_index_scan),
                 ACTOR_GET_OBJECT_ID_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), actor_get_node_id),
                 ACTOR_GET_PACKAGE_ADDRESS_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), actor_get_package_address),
                 ACTOR_GET_BLUEPRINT_NAME_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), actor_get_blueprint_name),
@@ -868,6 +1108,10 @@ impl WasmerModule {
                 CRYPTO_UTILS_BLS12381_V1_FAST_AGGREGATE_VERIFY_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), bls12381_v1_fast_aggregate_verify),
                 CRYPTO_UTILS_BLS12381_G2_SIGNATURE_AGGREGATE_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), bls12381_g2_signature_aggregate),
                 CRYPTO_UTILS_KECCAK256_HASH_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), keccak256_hash),
+                CRYPTO_UTILS_BLAKE2B_256_HASH_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), blake2b_256_hash),
+                CRYPTO_UTILS_ED25519_VERIFY_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), ed25519_verify),
+                CRYPTO_UTILS_SECP256K1_ECDSA_VERIFY_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), secp256k1_ecdsa_verify),
+                CRYPTO_UTILS_SECP256K1_ECDSA_VERIFY_AND_KEY_RECOVER_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), secp256k1_ecdsa_verify_and_key_recover),
             }
         };
 
@@ -905,6 +1149,7 @@ impl WasmerModule {
         WasmerInstance {
             instance,
             runtime_ptr: env.runtime_ptr,
+            trap_backtrace: Vec::new(),
         }
     }
 }
@@ -945,6 +1190,14 @@ impl WasmInstance for WasmerInstance {
             })?
             .call(&input)
             .map_err(|e| {
+                self.trap_backtrace = e
+                    .trace()
+                    .iter()
+                    .map(|frame| WasmTrapFrame {
+                        function_index: frame.func_index(),
+                        code_offset: Some(frame.module_offset() as u32),
+                    })
+                    .collect();
                 let err: InvokeError<WasmRuntimeError> = e.into();
                 err
             });
@@ -982,6 +1235,10 @@ impl WasmInstance for WasmerInstance {
 
         result
     }
+
+    fn take_trap_backtrace(&mut self) -> Result<Vec<WasmTrapFrame>, PrepareError> {
+        Ok(sbor::rust::mem::take(&mut self.trap_backtrace))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -1021,8 +1278,48 @@ impl WasmerEngine {
         Self {
             store: Store::new(&Universal::new(compiler).engine()),
             modules_cache,
+            #[cfg(feature = "std")]
+            disk_cache: None,
         }
     }
+
+    /// Enables the persistent cache of compiled modules, so that packages don't need to be
+    /// recompiled after a process restart.
+    ///
+    /// Compiled artifacts are loaded from the cache without being re-validated by Wasmer, so the
+    /// cache directory must not be writable by untrusted parties.
+    #[cfg(feature = "std")]
+    pub fn with_disk_cache(mut self, disk_cache: WasmDiskCache) -> Self {
+        self.disk_cache = Some(disk_cache);
+        self
+    }
+
+    fn load_or_compile_module(&self, code_hash: CodeHash, instrumented_code: &[u8]) -> Module {
+        #[cfg(feature = "std")]
+        if let Some(disk_cache) = &self.disk_cache {
+            if let Some(artifact) = disk_cache.get(&code_hash, instrumented_code) {
+                // SAFETY: the artifact has been produced by `Module::serialize` and has passed
+                // the integrity check of the cache.
+                if let Ok(module) = unsafe { Module::deserialize(&self.store, &artifact) } {
+                    return module;
+                }
+                // Most likely produced by an incompatible version of Wasmer
+                disk_cache.remove(&code_hash);
+            }
+        }
+
+        let module =
+            Module::new(&self.store, instrumented_code).expect("Failed to parse WASM module");
+
+        #[cfg(feature = "std")]
+        if let Some(disk_cache) = &self.disk_cache {
+            if let Ok(artifact) = module.serialize() {
+                disk_cache.put(&code_hash, instrumented_code, artifact);
+            }
+        }
+
+        module
+    }
 }
 
 impl WasmEngine for WasmerEngine {
@@ -1044,8 +1341,7 @@ impl WasmEngine for WasmerEngine {
         }
 
         let new_module = Arc::new(WasmerModule {
-            module: Module::new(&self.store, instrumented_code)
-                .expect("Failed to parse WASM module"),
+            module: self.load_or_compile_module(code_hash, instrumented_code),
             code_size_bytes: instrumented_code.len(),
         });
 
@@ -1061,4 +1357,9 @@ impl WasmEngine for WasmerEngine {
 
         new_module.instantiate()
     }
+
+    #[cfg(feature = "std")]
+    fn disk_cache(&self) -> Option<&WasmDiskCache> {
+        self.disk_cache.as_ref()
+    }
 }
@@ -13,8 +13,14 @@ use crate::errors::InvokeError;
 use crate::types::*;
 #[cfg(feature = "coverage")]
 use crate::utils::save_coverage_data;
+use crate::vm::wasm::backtrace::{WasmCallFrameTag, WasmTrapFrame};
 use crate::vm::wasm::constants::*;
+#[cfg(feature = "std")]
+use crate::vm::wasm::disk_cache::WasmDiskCache;
 use crate::vm::wasm::errors::*;
+use crate::vm::wasm::profiling::tag_wasm_code;
+#[cfg(feature = "wasm_profiling")]
+use crate::vm::wasm::profiling::*;
 use crate::vm::wasm::traits::*;
 use crate::vm::wasm::WasmEngine;
 
@@ -37,12 +43,16 @@ pub struct WasmiModule {
     template_instance: Instance,
     #[allow(dead_code)]
     code_size_bytes: usize,
+    /// Why the code could not be rewritten to capture the call frames, if it was requested.
+    call_frames_error: Option<PrepareError>,
 }
 
 pub struct WasmiInstance {
     store: Store<HostState>,
     instance: Instance,
     memory: Memory,
+    trap_backtrace: Vec<WasmTrapFrame>,
+    call_frames_error: Option<PrepareError>,
 }
 
 /// This is to construct a stub `Store<FakeWasmiInstanceEnv>`, which is a part of
@@ -56,12 +66,20 @@ pub struct WasmiInstance {
 pub struct FakeWasmiInstanceEnv {
     #[allow(dead_code)]
     runtime_ptr: MaybeUninit<*mut Box<dyn WasmRuntime>>,
+    #[allow(dead_code)]
+    call_frames: Option<Vec<WasmTrapFrame>>,
+    #[cfg(feature = "wasm_profiling")]
+    #[allow(dead_code)]
+    profiler: Option<(WasmProfiler, CodeHash)>,
 }
 
 impl FakeWasmiInstanceEnv {
     pub fn new() -> Self {
         Self {
             runtime_ptr: MaybeUninit::uninit(),
+            call_frames: None,
+            #[cfg(feature = "wasm_profiling")]
+            profiler: None,
         }
     }
 }
@@ -72,12 +90,22 @@ unsafe impl Sync for FakeWasmiInstanceEnv {}
 /// This is to construct a real `Store<WasmiInstanceEnv>
 pub struct WasmiInstanceEnv {
     runtime_ptr: MaybeUninit<*mut Box<dyn WasmRuntime>>,
+    /// The call stack of the running export, if the code has been rewritten with
+    /// `tag_wasm_call_frames`.
+    call_frames: Option<Vec<WasmTrapFrame>>,
+    /// The profiler (and the code hash of the instance), if the code has been rewritten
+    /// with `tag_wasm_execution_units`.
+    #[cfg(feature = "wasm_profiling")]
+    profiler: Option<(WasmProfiler, CodeHash)>,
 }
 
 impl WasmiInstanceEnv {
     pub fn new() -> Self {
         Self {
             runtime_ptr: MaybeUninit::uninit(),
+            call_frames: None,
+            #[cfg(feature = "wasm_profiling")]
+            profiler: None,
         }
     }
 }
@@ -504,6 +532,35 @@ fn key_value_store_remove(
         .map(|buffer| buffer.0)
 }
 
+fn key_value_store_scan(
+    mut caller: Caller<'_, HostState>,
+    node_id_ptr: u32,
+    node_id_len: u32,
+    cursor_ptr: u32,
+    cursor_len: u32,
+    limit: u32,
+) -> Result<u64, InvokeError<WasmRuntimeError>> {
+    let (memory, runtime) = grab_runtime!(caller);
+    let node_id = read_memory(caller.as_context_mut(), memory, node_id_ptr, node_id_len)?;
+    let cursor = read_memory(caller.as_context_mut(), memory, cursor_ptr, cursor_len)?;
+
+    runtime
+        .key_value_store_scan(node_id, cursor, limit)
+        .map(|buffer| buffer.0)
+}
+
+fn key_value_store_count(
+    mut caller: Caller<'_, HostState>,
+    node_id_ptr: u32,
+    node_id_len: u32,
+    limit: u32,
+) -> Result<u32, InvokeError<WasmRuntimeError>> {
+    let (memory, runtime) = grab_runtime!(caller);
+    let node_id = read_memory(caller.as_context_mut(), memory, node_id_ptr, node_id_len)?;
+
+    runtime.key_value_store_count(node_id, limit)
+}
+
 fn lock_field(
     caller: Caller<'_, HostState>,
     object_handle: u32,
@@ -545,6 +602,130 @@ fn field_lock_release(
     runtime.field_entry_close(handle)
 }
 
+fn actor_index_insert(
+    mut caller: Caller<'_, HostState>,
+    object_handle: u32,
+    collection_index: u32,
+    key_ptr: u32,
+    key_len: u32,
+    value_ptr: u32,
+    value_len: u32,
+) -> Result<(), InvokeError<WasmRuntimeError>> {
+    let (memory, runtime) = grab_runtime!(caller);
+
+    let key = read_memory(caller.as_context_mut(), memory, key_ptr, key_len)?;
+    let value = read_memory(caller.as_context_mut(), memory, value_ptr, value_len)?;
+
+    runtime.actor_index_insert(object_handle, collection_index as u8, key, value)
+}
+
+fn actor_index_remove(
+    mut caller: Caller<'_, HostState>,
+    object_handle: u32,
+    collection_index: u32,
+    key_ptr: u32,
+    key_len: u32,
+) -> Result<u64, InvokeError<WasmRuntimeError>> {
+    let (memory, runtime) = grab_runtime!(caller);
+
+    let key = read_memory(caller.as_context_mut(), memory, key_ptr, key_len)?;
+
+    runtime
+        .actor_index_remove(object_handle, collection_index as u8, key)
+        .map(|buffer| buffer.0)
+}
+
+fn actor_index_get(
+    mut caller: Caller<'_, HostState>,
+    object_handle: u32,
+    collection_index: u32,
+    key_ptr: u32,
+    key_len: u32,
+) -> Result<u64, InvokeError<WasmRuntimeError>> {
+    let (memory, runtime) = grab_runtime!(caller);
+
+    let key = read_memory(caller.as_context_mut(), memory, key_ptr, key_len)?;
+
+    runtime
+        .actor_index_get(object_handle, collection_index as u8, key)
+        .map(|buffer| buffer.0)
+}
+
+fn actor_index_scan(
+    mut caller: Caller<'_, HostState>,
+    object_handle: u32,
+    collection_index: u32,
+    cursor_ptr: u32,
+    cursor_len: u32,
+    limit: u32,
+) -> Result<u64, InvokeError<WasmRuntimeError>> {
+    let (memory, runtime) = grab_runtime!(caller);
+
+    let cursor = read_memory(caller.as_context_mut(), memory, cursor_ptr, cursor_len)?;
+
+    runtime
+        .actor_index_scan(object_handle, collection_index as u8, cursor, limit)
+        .map(|buffer| buffer.0)
+}
+
+fn actor_sorted_index_insert(
+    mut caller: Caller<'_, HostState>,
+    object_handle: u32,
+    collection_index: u32,
+    sort_prefix: u32,
+    key_ptr: u32,
+    key_len: u32,
+    value_ptr: u32,
+    value_len: u32,
+) -> Result<(), InvokeError<WasmRuntimeError>> {
+    let (memory, runtime) = grab_runtime!(caller);
+
+    let key = read_memory(caller.as_context_mut(), memory, key_ptr, key_len)?;
+    let value = read_memory(caller.as_context_mut(), memory, value_ptr, value_len)?;
+
+    runtime.actor_sorted_index_insert(
+        object_handle,
+        collection_index as u8,
+        sort_prefix,
+        key,
+        value,
+    )
+}
+
+fn actor_sorted_index_remove(
+    mut caller: Caller<'_, HostState>,
+    object_handle: u32,
+    collection_index: u32,
+    sort_prefix: u32,
+    key_ptr: u32,
+    key_len: u32,
+) -> Result<u64, InvokeError<WasmRuntimeError>> {
+    let (memory, runtime) = grab_runtime!(caller);
+
+    let key = read_memory(caller.as_context_mut(), memory, key_ptr, key_len)?;
+
+    runtime
+        .actor_sorted_index_remove(object_handle, collection_index as u8, sort_prefix, key)
+        .map(|buffer| buffer.0)
+}
+
+fn actor_sorted_index_scan(
+    mut caller: Caller<'_, HostState>,
+    object_handle: u32,
+    collection_index: u32,
+    cursor_ptr: u32,
+    cursor_len: u32,
+    limit: u32,
+) -> Result<u64, InvokeError<WasmRuntimeError>> {
+    let (memory, runtime) = grab_runtime!(caller);
+
+    let cursor = read_memory(caller.as_context_mut(), memory, cursor_ptr, cursor_len)?;
+
+    runtime
+        .actor_sorted_index_scan(object_handle, collection_index as u8, cursor, limit)
+        .map(|buffer| buffer.0)
+}
+
 fn actor_get_node_id(
     caller: Caller<'_, HostState>,
     handle: u32,
@@ -569,11 +750,25 @@ fn get_blueprint_name(caller: Caller<'_, HostState>) -> Result<u64, InvokeError<
 }
 
 fn consume_wasm_execution_units(
-    caller: Caller<'_, HostState>,
+    mut caller: Caller<'_, HostState>,
     n: u64,
 ) -> Result<(), InvokeError<WasmRuntimeError>> {
+    if let Some(call_frames) = &mut caller.data_mut().call_frames {
+        if let Some(tag) = WasmCallFrameTag::untag(n) {
+            tag.apply(call_frames);
+            return Ok(());
+        }
+    }
+
     let (_memory, runtime) = grab_runtime!(caller);
 
+    #[cfg(feature = "wasm_profiling")]
+    if let Some((profiler, _)) = &caller.data().profiler {
+        profiler.record(n);
+        let (_, units) = untag_wasm_execution_units(n);
+        return runtime.consume_wasm_execution_units(units);
+    }
+
     // TODO: wasm-instrument uses u64 for cost units. We need to decide if we want to move from u32
     // to u64 as well.
     runtime.consume_wasm_execution_units(n as u32)
@@ -777,6 +972,98 @@ fn keccak256_hash(
         .map(|buffer| buffer.0)
 }
 
+fn blake2b_256_hash(
+    mut caller: Caller<'_, HostState>,
+    data_ptr: u32,
+    data_len: u32,
+) -> Result<u64, InvokeError<WasmRuntimeError>> {
+    let (memory, runtime) = grab_runtime!(caller);
+
+    let data = read_memory(caller.as_context_mut(), memory, data_ptr, data_len)?;
+
+    runtime
+        .crypto_utils_blake2b_256_hash(data)
+        .map(|buffer| buffer.0)
+}
+
+fn ed25519_verify(
+    mut caller: Caller<'_, HostState>,
+    message_ptr: u32,
+    message_len: u32,
+    public_key_ptr: u32,
+    public_key_len: u32,
+    signature_ptr: u32,
+    signature_len: u32,
+) -> Result<u32, InvokeError<WasmRuntimeError>> {
+    let (memory, runtime) = grab_runtime!(caller);
+
+    let message = read_memory(caller.as_context_mut(), memory, message_ptr, message_len)?;
+    let public_key = read_memory(
+        caller.as_context_mut(),
+        memory,
+        public_key_ptr,
+        public_key_len,
+    )?;
+    let signature = read_memory(
+        caller.as_context_mut(),
+        memory,
+        signature_ptr,
+        signature_len,
+    )?;
+
+    runtime.crypto_utils_ed25519_verify(message, public_key, signature)
+}
+
+fn secp256k1_ecdsa_verify(
+    mut caller: Caller<'_, HostState>,
+    hash_ptr: u32,
+    hash_len: u32,
+    public_key_ptr: u32,
+    public_key_len: u32,
+    signature_ptr: u32,
+    signature_len: u32,
+) -> Result<u32, InvokeError<WasmRuntimeError>> {
+    let (memory, runtime) = grab_runtime!(caller);
+
+    let hash = read_memory(caller.as_context_mut(), memory, hash_ptr, hash_len)?;
+    let public_key = read_memory(
+        caller.as_context_mut(),
+        memory,
+        public_key_ptr,
+        public_key_len,
+    )?;
+    let signature = read_memory(
+        caller.as_context_mut(),
+        memory,
+        signature_ptr,
+        signature_len,
+    )?;
+
+    runtime.crypto_utils_secp256k1_ecdsa_verify(hash, public_key, signature)
+}
+
+fn secp256k1_ecdsa_verify_and_key_recover(
+    mut caller: Caller<'_, HostState>,
+    hash_ptr: u32,
+    hash_len: u32,
+    signature_ptr: u32,
+    signature_len: u32,
+) -> Result<u64, InvokeError<WasmRuntimeError>> {
+    let (memory, runtime) = grab_runtime!(caller);
+
+    let hash = read_memory(caller.as_context_mut(), memory, hash_ptr, hash_len)?;
+    let signature = read_memory(
+        caller.as_context_mut(),
+        memory,
+        signature_ptr,
+        signature_len,
+    )?;
+
+    runtime
+        .crypto_utils_secp256k1_ecdsa_verify_and_key_recover(hash, signature)
+        .map(|buffer| buffer.0)
+}
+
 #[cfg(feature = "radix_engine_tests")]
 fn test_host_read_memory(
     mut caller: Caller<'_, HostState>,
@@ -854,6 +1141,7 @@ impl WasmiModule {
             template_store: unsafe { transmute(store) },
             template_instance: instance,
             code_size_bytes: code.len(),
+            call_frames_error: None,
         })
     }
 
@@ -1208,6 +1496,38 @@ impl WasmiModule {
             },
         );
 
+        let host_key_value_store_scan = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>,
+             node_id_ptr: u32,
+             node_id_len: u32,
+             cursor_ptr: u32,
+             cursor_len: u32,
+             limit: u32|
+             -> Result<u64, Trap> {
+                key_value_store_scan(
+                    caller,
+                    node_id_ptr,
+                    node_id_len,
+                    cursor_ptr,
+                    cursor_len,
+                    limit,
+                )
+                .map_err(|e| e.into())
+            },
+        );
+
+        let host_key_value_store_count = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>,
+             node_id_ptr: u32,
+             node_id_len: u32,
+             limit: u32|
+             -> Result<u32, Trap> {
+                key_value_store_count(caller, node_id_ptr, node_id_len, limit).map_err(|e| e.into())
+            },
+        );
+
         let host_lock_field = Func::wrap(
             store.as_context_mut(),
             |caller: Caller<'_, HostState>,
@@ -1219,6 +1539,143 @@ impl WasmiModule {
             },
         );
 
+        let host_actor_index_insert = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>,
+             object_handle: u32,
+             collection_index: u32,
+             key_ptr: u32,
+             key_len: u32,
+             value_ptr: u32,
+             value_len: u32|
+             -> Result<(), Trap> {
+                actor_index_insert(
+                    caller,
+                    object_handle,
+                    collection_index,
+                    key_ptr,
+                    key_len,
+                    value_ptr,
+                    value_len,
+                )
+                .map_err(|e| e.into())
+            },
+        );
+
+        let host_actor_index_remove = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>,
+             object_handle: u32,
+             collection_index: u32,
+             key_ptr: u32,
+             key_len: u32|
+             -> Result<u64, Trap> {
+                actor_index_remove(caller, object_handle, collection_index, key_ptr, key_len)
+                    .map_err(|e| e.into())
+            },
+        );
+
+        let host_actor_index_get = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>,
+             object_handle: u32,
+             collection_index: u32,
+             key_ptr: u32,
+             key_len: u32|
+             -> Result<u64, Trap> {
+                actor_index_get(caller, object_handle, collection_index, key_ptr, key_len)
+                    .map_err(|e| e.into())
+            },
+        );
+
+        let host_actor_index_scan = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>,
+             object_handle: u32,
+             collection_index: u32,
+             cursor_ptr: u32,
+             cursor_len: u32,
+             limit: u32|
+             -> Result<u64, Trap> {
+                actor_index_scan(
+                    caller,
+                    object_handle,
+                    collection_index,
+                    cursor_ptr,
+                    cursor_len,
+                    limit,
+                )
+                .map_err(|e| e.into())
+            },
+        );
+
+        let host_actor_sorted_index_insert = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>,
+             object_handle: u32,
+             collection_index: u32,
+             sort_prefix: u32,
+             key_ptr: u32,
+             key_len: u32,
+             value_ptr: u32,
+             value_len: u32|
+             -> Result<(), Trap> {
+                actor_sorted_index_insert(
+                    caller,
+                    object_handle,
+                    collection_index,
+                    sort_prefix,
+                    key_ptr,
+                    key_len,
+                    value_ptr,
+                    value_len,
+                )
+                .map_err(|e| e.into())
+            },
+        );
+
+        let host_actor_sorted_index_remove = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>,
+             object_handle: u32,
+             collection_index: u32,
+             sort_prefix: u32,
+             key_ptr: u32,
+             key_len: u32|
+             -> Result<u64, Trap> {
+                actor_sorted_index_remove(
+                    caller,
+                    object_handle,
+                    collection_index,
+                    sort_prefix,
+                    key_ptr,
+                    key_len,
+                )
+                .map_err(|e| e.into())
+            },
+        );
+
+        let host_actor_sorted_index_scan = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>,
+             object_handle: u32,
+             collection_index: u32,
+             cursor_ptr: u32,
+             cursor_len: u32,
+             limit: u32|
+             -> Result<u64, Trap> {
+                actor_sorted_index_scan(
+                    caller,
+                    object_handle,
+                    collection_index,
+                    cursor_ptr,
+                    cursor_len,
+                    limit,
+                )
+                .map_err(|e| e.into())
+            },
+        );
+
         let host_field_lock_read = Func::wrap(
             store.as_context_mut(),
             |caller: Caller<'_, HostState>, handle: u32| -> Result<u64, Trap> {
@@ -1423,6 +1880,78 @@ impl WasmiModule {
             },
         );
 
+        let host_blake2b_256_hash = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>, data_ptr: u32, data_len: u32| -> Result<u64, Trap> {
+                blake2b_256_hash(caller, data_ptr, data_len).map_err(|e| e.into())
+            },
+        );
+
+        let host_ed25519_verify = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>,
+             message_ptr: u32,
+             message_len: u32,
+             public_key_ptr: u32,
+             public_key_len: u32,
+             signature_ptr: u32,
+             signature_len: u32|
+             -> Result<u32, Trap> {
+                ed25519_verify(
+                    caller,
+                    message_ptr,
+                    message_len,
+                    public_key_ptr,
+                    public_key_len,
+                    signature_ptr,
+                    signature_len,
+                )
+                .map_err(|e| e.into())
+            },
+        );
+
+        let host_secp256k1_ecdsa_verify = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>,
+             hash_ptr: u32,
+             hash_len: u32,
+             public_key_ptr: u32,
+             public_key_len: u32,
+             signature_ptr: u32,
+             signature_len: u32|
+             -> Result<u32, Trap> {
+                secp256k1_ecdsa_verify(
+                    caller,
+                    hash_ptr,
+                    hash_len,
+                    public_key_ptr,
+                    public_key_len,
+                    signature_ptr,
+                    signature_len,
+                )
+                .map_err(|e| e.into())
+            },
+        );
+
+        let host_secp256k1_ecdsa_verify_and_key_recover = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>,
+             hash_ptr: u32,
+             hash_len: u32,
+             signature_ptr: u32,
+             signature_len: u32|
+             -> Result<u64, Trap> {
+                secp256k1_ecdsa_verify_and_key_recover(
+                    caller,
+                    hash_ptr,
+                    hash_len,
+                    signature_ptr,
+                    signature_len,
+                )
+                .map_err(|e| e.into())
+            },
+        );
+
         let mut linker = <Linker<HostState>>::new();
 
         linker_define!(linker, BUFFER_CONSUME_FUNCTION_NAME, host_consume_buffer);
@@ -1498,6 +2027,37 @@ impl WasmiModule {
             host_get_outer_object
         );
         linker_define!(linker, ACTOR_OPEN_FIELD_FUNCTION_NAME, host_lock_field);
+        linker_define!(
+            linker,
+            ACTOR_INDEX_INSERT_FUNCTION_NAME,
+            host_actor_index_insert
+        );
+        linker_define!(
+            linker,
+            ACTOR_INDEX_REMOVE_FUNCTION_NAME,
+            host_actor_index_remove
+        );
+        linker_define!(linker, ACTOR_INDEX_GET_FUNCTION_NAME, host_actor_index_get);
+        linker_define!(
+            linker,
+            ACTOR_INDEX_SCAN_FUNCTION_NAME,
+            host_actor_index_scan
+        );
+        linker_define!(
+            linker,
+            ACTOR_SORTED_INDEX_INSERT_FUNCTION_NAME,
+            host_actor_sorted_index_insert
+        );
+        linker_define!(
+            linker,
+            ACTOR_SORTED_INDEX_REMOVE_FUNCTION_NAME,
+            host_actor_sorted_index_remove
+        );
+        linker_define!(
+            linker,
+            ACTOR_SORTED_INDEX_SCAN_FUNCTION_NAME,
+            host_actor_sorted_index_scan
+        );
 
         linker_define!(
             linker,
@@ -1534,6 +2094,16 @@ impl WasmiModule {
             KEY_VALUE_STORE_REMOVE_ENTRY_FUNCTION_NAME,
             host_key_value_store_remove
         );
+        linker_define!(
+            linker,
+            KEY_VALUE_STORE_SCAN_FUNCTION_NAME,
+            host_key_value_store_scan
+        );
+        linker_define!(
+            linker,
+            KEY_VALUE_STORE_COUNT_FUNCTION_NAME,
+            host_key_value_store_count
+        );
 
         linker_define!(linker, FIELD_ENTRY_READ_FUNCTION_NAME, host_field_lock_read);
         linker_define!(
@@ -1606,6 +2176,26 @@ impl WasmiModule {
             CRYPTO_UTILS_KECCAK256_HASH_FUNCTION_NAME,
             host_keccak256_hash
         );
+        linker_define!(
+            linker,
+            CRYPTO_UTILS_BLAKE2B_256_HASH_FUNCTION_NAME,
+            host_blake2b_256_hash
+        );
+        linker_define!(
+            linker,
+            CRYPTO_UTILS_ED25519_VERIFY_FUNCTION_NAME,
+            host_ed25519_verify
+        );
+        linker_define!(
+            linker,
+            CRYPTO_UTILS_SECP256K1_ECDSA_VERIFY_FUNCTION_NAME,
+            host_secp256k1_ecdsa_verify
+        );
+        linker_define!(
+            linker,
+            CRYPTO_UTILS_SECP256K1_ECDSA_VERIFY_AND_KEY_RECOVER_FUNCTION_NAME,
+            host_secp256k1_ecdsa_verify_and_key_recover
+        );
 
         #[cfg(feature = "radix_engine_tests")]
         {
@@ -1657,6 +2247,8 @@ impl WasmiModule {
             instance,
             store: unsafe { transmute(store) },
             memory,
+            trap_backtrace: Vec::new(),
+            call_frames_error: self.call_frames_error.clone(),
         }
     }
 }
@@ -1754,6 +2346,17 @@ impl WasmInstance for WasmiInstance {
                 .write(runtime as *mut _ as usize as *mut _);
         }
 
+        #[cfg(feature = "wasm_profiling")]
+        let profiler = self.store.data().profiler.clone();
+        #[cfg(feature = "wasm_profiling")]
+        if let Some((profiler, code_hash)) = &profiler {
+            profiler.enter(*code_hash, func_name);
+        }
+
+        if let Some(call_frames) = &mut self.store.data_mut().call_frames {
+            call_frames.clear();
+        }
+
         let func = self.get_export_func(func_name).unwrap();
         let input: Vec<Value> = args
             .into_iter()
@@ -1780,6 +2383,17 @@ impl WasmInstance for WasmiInstance {
             Err(err) => Err(err),
         };
 
+        if result.is_err() {
+            if let Some(call_frames) = &self.store.data().call_frames {
+                self.trap_backtrace = call_frames.iter().rev().copied().collect();
+            }
+        }
+
+        #[cfg(feature = "wasm_profiling")]
+        if let Some((profiler, _)) = &profiler {
+            profiler.exit();
+        }
+
         #[cfg(feature = "coverage")]
         if let Ok(dump_coverage) = self.get_export_func("dump_coverage") {
             if let Ok(blueprint_buffer) = runtime.actor_get_blueprint_name() {
@@ -1803,6 +2417,13 @@ impl WasmInstance for WasmiInstance {
 
         result
     }
+
+    fn take_trap_backtrace(&mut self) -> Result<Vec<WasmTrapFrame>, PrepareError> {
+        if let Some(error) = &self.call_frames_error {
+            return Err(error.clone());
+        }
+        Ok(sbor::rust::mem::take(&mut self.trap_backtrace))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -1819,6 +2440,10 @@ pub struct WasmiEngine {
     #[cfg(feature = "radix_engine_fuzzing")]
     #[allow(dead_code)]
     modules_cache: usize,
+    #[cfg(feature = "wasm_profiling")]
+    profiler: Option<WasmProfiler>,
+    #[cfg(feature = "std")]
+    disk_cache: Option<WasmDiskCache>,
 }
 
 impl Default for WasmiEngine {
@@ -1846,15 +2471,35 @@ impl WasmiEngine {
         #[cfg(feature = "radix_engine_fuzzing")]
         let modules_cache = options.max_cache_size;
 
-        Self { modules_cache }
+        Self {
+            modules_cache,
+            #[cfg(feature = "wasm_profiling")]
+            profiler: None,
+            #[cfg(feature = "std")]
+            disk_cache: None,
+        }
     }
-}
 
-impl WasmEngine for WasmiEngine {
-    type WasmInstance = WasmiInstance;
+    /// Enables the persistent cache of instrumented code, so that packages published through
+    /// this engine don't need to be validated and instrumented again after a process restart.
+    ///
+    /// Wasmi modules can't be serialized, so unlike with Wasmer, only the instrumented code is
+    /// cached and the modules are still parsed on their first instantiation.
+    #[cfg(feature = "std")]
+    pub fn with_disk_cache(mut self, disk_cache: WasmDiskCache) -> Self {
+        self.disk_cache = Some(disk_cache);
+        self
+    }
+
+    /// Profiles all the code run by this engine, see `WasmProfiler`.
+    #[cfg(feature = "wasm_profiling")]
+    pub fn with_profiler(mut self, profiler: WasmProfiler) -> Self {
+        self.profiler = Some(profiler);
+        self
+    }
 
     #[allow(unused_variables)]
-    fn instantiate(&self, code_hash: CodeHash, instrumented_code: &[u8]) -> WasmiInstance {
+    fn instantiate_module(&self, code_hash: CodeHash, instrumented_code: &[u8]) -> WasmiInstance {
         #[cfg(not(feature = "radix_engine_fuzzing"))]
         {
             #[cfg(not(feature = "moka"))]
@@ -1869,7 +2514,7 @@ impl WasmEngine for WasmiEngine {
             }
         }
 
-        let module = WasmiModule::new(instrumented_code).expect("Failed to instantiate module");
+        let module = self.new_module(code_hash, instrumented_code, false);
         let instance = module.instantiate();
 
         #[cfg(not(feature = "radix_engine_fuzzing"))]
@@ -1884,6 +2529,80 @@ impl WasmEngine for WasmiEngine {
 
         instance
     }
+
+    /// Creates a module of the instrumented code, rewritten to report the units consumed by each
+    /// call (if profiling) and the call frames (if requested).
+    ///
+    /// Code which fails to be rewritten is run as it is, without profiling nor backtraces, and
+    /// the failure is recorded in the profile and returned in place of the backtrace.
+    #[allow(unused_variables)]
+    fn new_module(
+        &self,
+        code_hash: CodeHash,
+        instrumented_code: &[u8],
+        tag_call_frames: bool,
+    ) -> WasmiModule {
+        #[cfg(feature = "wasm_profiling")]
+        let tag_execution_units = self.profiler.is_some();
+        #[cfg(not(feature = "wasm_profiling"))]
+        let tag_execution_units = false;
+
+        let (tagged_code, tag_error) = if tag_execution_units || tag_call_frames {
+            match tag_wasm_code(instrumented_code, tag_execution_units, tag_call_frames) {
+                Ok(tagged_code) => (Some(tagged_code), None),
+                Err(err) => (None, Some(err)),
+            }
+        } else {
+            (None, None)
+        };
+
+        let mut module = WasmiModule::new(tagged_code.as_deref().unwrap_or(instrumented_code))
+            .expect("Failed to instantiate module");
+        if let Some(tag_error) = tag_error {
+            #[cfg(feature = "wasm_profiling")]
+            if let Some(profiler) = &self.profiler {
+                profiler.register_untagged_code(code_hash, tag_error.clone());
+            }
+            if tag_call_frames {
+                module.call_frames_error = Some(tag_error);
+            }
+        } else if tagged_code.is_some() {
+            let env = module.template_store.data_mut();
+            if tag_call_frames {
+                env.call_frames = Some(Vec::new());
+            }
+            #[cfg(feature = "wasm_profiling")]
+            if let Some(profiler) = &self.profiler {
+                profiler.register_code(code_hash, instrumented_code);
+                env.profiler = Some((profiler.clone(), code_hash));
+            }
+        }
+        module
+    }
+}
+
+impl WasmEngine for WasmiEngine {
+    type WasmInstance = WasmiInstance;
+
+    fn instantiate(&self, code_hash: CodeHash, instrumented_code: &[u8]) -> WasmiInstance {
+        self.instantiate_module(code_hash, instrumented_code)
+    }
+
+    fn instantiate_with_backtraces(
+        &self,
+        code_hash: CodeHash,
+        instrumented_code: &[u8],
+    ) -> WasmiInstance {
+        // The call frames are reported by rewritten code, which is never cached so that it's
+        // only run when backtraces are enabled
+        self.new_module(code_hash, instrumented_code, true)
+            .instantiate()
+    }
+
+    #[cfg(feature = "std")]
+    fn disk_cache(&self) -> Option<&WasmDiskCache> {
+        self.disk_cache.as_ref()
+    }
 }
 
 // Below tests verify WASM "mutable-global" feature, which allows importing/exporting mutable globals.
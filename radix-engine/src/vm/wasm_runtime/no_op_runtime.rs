@@ -161,6 +161,23 @@ impl<'a> WasmRuntime for NoOpWasmRuntime<'a> {
         Err(InvokeError::SelfError(WasmRuntimeError::NotImplemented))
     }
 
+    fn key_value_store_scan(
+        &mut self,
+        node_id: Vec<u8>,
+        cursor: Vec<u8>,
+        limit: u32,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
+        Err(InvokeError::SelfError(WasmRuntimeError::NotImplemented))
+    }
+
+    fn key_value_store_count(
+        &mut self,
+        node_id: Vec<u8>,
+        limit: u32,
+    ) -> Result<u32, InvokeError<WasmRuntimeError>> {
+        Err(InvokeError::SelfError(WasmRuntimeError::NotImplemented))
+    }
+
     fn actor_open_field(
         &mut self,
         object_handle: u32,
@@ -198,6 +215,75 @@ impl<'a> WasmRuntime for NoOpWasmRuntime<'a> {
         Err(InvokeError::SelfError(WasmRuntimeError::NotImplemented))
     }
 
+    fn actor_index_insert(
+        &mut self,
+        object_handle: u32,
+        collection_index: u8,
+        key: Vec<u8>,
+        value: Vec<u8>,
+    ) -> Result<(), InvokeError<WasmRuntimeError>> {
+        Err(InvokeError::SelfError(WasmRuntimeError::NotImplemented))
+    }
+
+    fn actor_index_remove(
+        &mut self,
+        object_handle: u32,
+        collection_index: u8,
+        key: Vec<u8>,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
+        Err(InvokeError::SelfError(WasmRuntimeError::NotImplemented))
+    }
+
+    fn actor_index_get(
+        &mut self,
+        object_handle: u32,
+        collection_index: u8,
+        key: Vec<u8>,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
+        Err(InvokeError::SelfError(WasmRuntimeError::NotImplemented))
+    }
+
+    fn actor_index_scan(
+        &mut self,
+        object_handle: u32,
+        collection_index: u8,
+        cursor: Vec<u8>,
+        limit: u32,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
+        Err(InvokeError::SelfError(WasmRuntimeError::NotImplemented))
+    }
+
+    fn actor_sorted_index_insert(
+        &mut self,
+        object_handle: u32,
+        collection_index: u8,
+        sort_prefix: u32,
+        key: Vec<u8>,
+        value: Vec<u8>,
+    ) -> Result<(), InvokeError<WasmRuntimeError>> {
+        Err(InvokeError::SelfError(WasmRuntimeError::NotImplemented))
+    }
+
+    fn actor_sorted_index_remove(
+        &mut self,
+        object_handle: u32,
+        collection_index: u8,
+        sort_prefix: u32,
+        key: Vec<u8>,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
+        Err(InvokeError::SelfError(WasmRuntimeError::NotImplemented))
+    }
+
+    fn actor_sorted_index_scan(
+        &mut self,
+        object_handle: u32,
+        collection_index: u8,
+        cursor: Vec<u8>,
+        limit: u32,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
+        Err(InvokeError::SelfError(WasmRuntimeError::NotImplemented))
+    }
+
     fn consume_wasm_execution_units(
         &mut self,
         n: u32,
@@ -345,4 +431,37 @@ impl<'a> WasmRuntime for NoOpWasmRuntime<'a> {
     ) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
         Err(InvokeError::SelfError(WasmRuntimeError::NotImplemented))
     }
+
+    fn crypto_utils_blake2b_256_hash(
+        &mut self,
+        data: Vec<u8>,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
+        Err(InvokeError::SelfError(WasmRuntimeError::NotImplemented))
+    }
+
+    fn crypto_utils_ed25519_verify(
+        &mut self,
+        message: Vec<u8>,
+        public_key: Vec<u8>,
+        signature: Vec<u8>,
+    ) -> Result<u32, InvokeError<WasmRuntimeError>> {
+        Err(InvokeError::SelfError(WasmRuntimeError::NotImplemented))
+    }
+
+    fn crypto_utils_secp256k1_ecdsa_verify(
+        &mut self,
+        hash: Vec<u8>,
+        public_key: Vec<u8>,
+        signature: Vec<u8>,
+    ) -> Result<u32, InvokeError<WasmRuntimeError>> {
+        Err(InvokeError::SelfError(WasmRuntimeError::NotImplemented))
+    }
+
+    fn crypto_utils_secp256k1_ecdsa_verify_and_key_recover(
+        &mut self,
+        hash: Vec<u8>,
+        signature: Vec<u8>,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
+        Err(InvokeError::SelfError(WasmRuntimeError::NotImplemented))
+    }
 }
@@ -309,6 +309,36 @@ where
         self.allocate_buffer(rtn)
     }
 
+    fn key_value_store_scan(
+        &mut self,
+        node_id: Vec<u8>,
+        cursor: Vec<u8>,
+        limit: u32,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
+        let node_id = NodeId(
+            TryInto::<[u8; NodeId::LENGTH]>::try_into(node_id.as_ref())
+                .map_err(|_| WasmRuntimeError::InvalidNodeId)?,
+        );
+        let after: Option<Vec<u8>> =
+            scrypto_decode(&cursor).map_err(WasmRuntimeError::InvalidScanCursor)?;
+
+        let rtn = self.api.key_value_store_scan(&node_id, after, limit)?;
+        self.allocate_buffer(scrypto_encode(&rtn).expect("Failed to encode key value store scan"))
+    }
+
+    fn key_value_store_count(
+        &mut self,
+        node_id: Vec<u8>,
+        limit: u32,
+    ) -> Result<u32, InvokeError<WasmRuntimeError>> {
+        let node_id = NodeId(
+            TryInto::<[u8; NodeId::LENGTH]>::try_into(node_id.as_ref())
+                .map_err(|_| WasmRuntimeError::InvalidNodeId)?,
+        );
+        let count = self.api.key_value_store_count(&node_id, limit)?;
+        Ok(count)
+    }
+
     fn actor_open_field(
         &mut self,
         object_handle: u32,
@@ -370,6 +400,118 @@ where
         self.allocate_buffer(blueprint_id.blueprint_name.into_bytes())
     }
 
+    fn actor_index_insert(
+        &mut self,
+        object_handle: u32,
+        collection_index: u8,
+        key: Vec<u8>,
+        value: Vec<u8>,
+    ) -> Result<(), InvokeError<WasmRuntimeError>> {
+        self.api
+            .actor_index_insert(object_handle, collection_index, key, value)?;
+        Ok(())
+    }
+
+    fn actor_index_remove(
+        &mut self,
+        object_handle: u32,
+        collection_index: u8,
+        key: Vec<u8>,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
+        let rtn = self
+            .api
+            .actor_index_remove(object_handle, collection_index, key)?;
+        self.allocate_buffer(scrypto_encode(&rtn).expect("Failed to encode index entry"))
+    }
+
+    fn actor_index_get(
+        &mut self,
+        object_handle: u32,
+        collection_index: u8,
+        key: Vec<u8>,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
+        let rtn = self
+            .api
+            .actor_index_get(object_handle, collection_index, key)?;
+        self.allocate_buffer(scrypto_encode(&rtn).expect("Failed to encode index entry"))
+    }
+
+    fn actor_index_scan(
+        &mut self,
+        object_handle: u32,
+        collection_index: u8,
+        cursor: Vec<u8>,
+        limit: u32,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
+        let after: Option<Vec<u8>> =
+            scrypto_decode(&cursor).map_err(WasmRuntimeError::InvalidScanCursor)?;
+
+        let entries =
+            self.api
+                .actor_index_scan_after(object_handle, collection_index, after, limit)?;
+        self.allocate_buffer(scrypto_encode(&entries).expect("Failed to encode index entries"))
+    }
+
+    fn actor_sorted_index_insert(
+        &mut self,
+        object_handle: u32,
+        collection_index: u8,
+        sort_prefix: u32,
+        key: Vec<u8>,
+        value: Vec<u8>,
+    ) -> Result<(), InvokeError<WasmRuntimeError>> {
+        let sort_prefix = u16::try_from(sort_prefix)
+            .map_err(|_| WasmRuntimeError::InvalidSortPrefix(sort_prefix))?;
+
+        self.api.actor_sorted_index_insert(
+            object_handle,
+            collection_index,
+            (sort_prefix.to_be_bytes(), key),
+            value,
+        )?;
+        Ok(())
+    }
+
+    fn actor_sorted_index_remove(
+        &mut self,
+        object_handle: u32,
+        collection_index: u8,
+        sort_prefix: u32,
+        key: Vec<u8>,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
+        let sort_prefix = u16::try_from(sort_prefix)
+            .map_err(|_| WasmRuntimeError::InvalidSortPrefix(sort_prefix))?;
+
+        let rtn = self.api.actor_sorted_index_remove(
+            object_handle,
+            collection_index,
+            &(sort_prefix.to_be_bytes(), key),
+        )?;
+        self.allocate_buffer(scrypto_encode(&rtn).expect("Failed to encode sorted index entry"))
+    }
+
+    fn actor_sorted_index_scan(
+        &mut self,
+        object_handle: u32,
+        collection_index: u8,
+        cursor: Vec<u8>,
+        limit: u32,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
+        let after: Option<(u16, Vec<u8>)> =
+            scrypto_decode(&cursor).map_err(WasmRuntimeError::InvalidScanCursor)?;
+        let after = after.map(|(sort_prefix, key)| (sort_prefix.to_be_bytes(), key));
+
+        let entries: Vec<((u16, Vec<u8>), Vec<u8>)> = self
+            .api
+            .actor_sorted_index_scan_after(object_handle, collection_index, after, limit)?
+            .into_iter()
+            .map(|((sort_prefix, key), value)| ((u16::from_be_bytes(sort_prefix), key), value))
+            .collect();
+        self.allocate_buffer(
+            scrypto_encode(&entries).expect("Failed to encode sorted index entries"),
+        )
+    }
+
     fn consume_wasm_execution_units(
         &mut self,
         mut n: u32,
@@ -633,4 +775,64 @@ where
 
         self.allocate_buffer(hash.to_vec())
     }
+
+    fn crypto_utils_blake2b_256_hash(
+        &mut self,
+        data: Vec<u8>,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
+        let hash = self.api.blake2b_256_hash(&data)?;
+
+        self.allocate_buffer(hash.to_vec())
+    }
+
+    fn crypto_utils_ed25519_verify(
+        &mut self,
+        message: Vec<u8>,
+        public_key: Vec<u8>,
+        signature: Vec<u8>,
+    ) -> Result<u32, InvokeError<WasmRuntimeError>> {
+        let public_key: Ed25519PublicKey =
+            scrypto_decode(&public_key).map_err(WasmRuntimeError::InvalidEd25519PublicKey)?;
+        let signature: Ed25519Signature =
+            scrypto_decode(&signature).map_err(WasmRuntimeError::InvalidEd25519Signature)?;
+
+        let result = self.api.ed25519_verify(&message, &public_key, &signature)?;
+        Ok(result)
+    }
+
+    fn crypto_utils_secp256k1_ecdsa_verify(
+        &mut self,
+        hash: Vec<u8>,
+        public_key: Vec<u8>,
+        signature: Vec<u8>,
+    ) -> Result<u32, InvokeError<WasmRuntimeError>> {
+        let hash: Hash = scrypto_decode(&hash).map_err(WasmRuntimeError::InvalidHash)?;
+        let public_key: Secp256k1PublicKey =
+            scrypto_decode(&public_key).map_err(WasmRuntimeError::InvalidSecp256k1PublicKey)?;
+        let signature: Secp256k1Signature =
+            scrypto_decode(&signature).map_err(WasmRuntimeError::InvalidSecp256k1Signature)?;
+
+        let result = self
+            .api
+            .secp256k1_ecdsa_verify(&hash, &public_key, &signature)?;
+        Ok(result)
+    }
+
+    fn crypto_utils_secp256k1_ecdsa_verify_and_key_recover(
+        &mut self,
+        hash: Vec<u8>,
+        signature: Vec<u8>,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
+        let hash: Hash = scrypto_decode(&hash).map_err(WasmRuntimeError::InvalidHash)?;
+        let signature: Secp256k1Signature =
+            scrypto_decode(&signature).map_err(WasmRuntimeError::InvalidSecp256k1Signature)?;
+
+        let public_key = self
+            .api
+            .secp256k1_ecdsa_verify_and_key_recover(&hash, &signature)?;
+
+        self.allocate_buffer(
+            scrypto_encode(&public_key).expect("Failed to encode Secp256k1PublicKey"),
+        )
+    }
 }
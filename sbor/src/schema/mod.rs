@@ -2,6 +2,7 @@ mod custom_traits;
 mod describe;
 mod macros;
 mod schema;
+mod schema_comparison;
 mod schema_validation;
 mod type_aggregator;
 mod type_data;
@@ -12,6 +13,7 @@ pub use custom_traits::*;
 pub use describe::*;
 pub(crate) use macros::*;
 pub use schema::*;
+pub use schema_comparison::*;
 pub use schema_validation::*;
 pub use type_aggregator::*;
 pub use type_data::*;
@@ -0,0 +1,611 @@
+use crate::rust::fmt::Write;
+use crate::rust::prelude::*;
+use crate::*;
+
+/// A single step into a child type, used to locate an incompatibility within the old schema's root type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaComparisonPathStep {
+    TupleField {
+        type_name: Option<String>,
+        field_index: usize,
+        field_name: Option<String>,
+    },
+    EnumVariantField {
+        type_name: Option<String>,
+        discriminator: u8,
+        variant_name: Option<String>,
+        field_index: usize,
+        field_name: Option<String>,
+    },
+    ArrayElement {
+        type_name: Option<String>,
+    },
+    MapKey {
+        type_name: Option<String>,
+    },
+    MapValue {
+        type_name: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaIncompatibilityReason<S: CustomSchema> {
+    OldTypeNotFound(LocalTypeId),
+    NewTypeNotFound(LocalTypeId),
+    TypeKindChanged {
+        old: TypeKindLabel,
+        new: TypeKindLabel,
+    },
+    CustomTypeKindChanged {
+        old: S::CustomTypeKind<LocalTypeId>,
+        new: S::CustomTypeKind<LocalTypeId>,
+    },
+    TupleLengthChanged {
+        old: usize,
+        new: usize,
+    },
+    EnumVariantRemoved {
+        discriminator: u8,
+    },
+    EnumVariantLengthChanged {
+        discriminator: u8,
+        old: usize,
+        new: usize,
+    },
+    TypeValidationNarrowed {
+        old: TypeValidation<S::CustomTypeValidation>,
+        new: TypeValidation<S::CustomTypeValidation>,
+    },
+}
+
+/// Describes why a payload valid under the old schema may be invalid (or have a different meaning)
+/// under the new schema, and where in the old type this happens.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaIncompatibility<S: CustomSchema> {
+    pub path: Vec<SchemaComparisonPathStep>,
+    pub reason: SchemaIncompatibilityReason<S>,
+}
+
+impl<S: CustomSchema> SchemaIncompatibility<S> {
+    /// Renders the path in the same style as payload validation errors.
+    /// EG: `MyStruct.[0|hello]->MyEnum::{1|Option2}.[0|inner]->Array.[*]->Map.Value`
+    ///
+    /// An empty string means that the root types themselves are incompatible.
+    pub fn path_to_string(&self) -> String {
+        let mut buf = String::new();
+        for (i, step) in self.path.iter().enumerate() {
+            if i > 0 {
+                write!(buf, "->").unwrap();
+            }
+            match step {
+                SchemaComparisonPathStep::TupleField {
+                    type_name,
+                    field_index,
+                    field_name,
+                } => {
+                    write!(buf, "{}", type_name.as_deref().unwrap_or("Tuple")).unwrap();
+                    write_field(&mut buf, *field_index, field_name.as_deref());
+                }
+                SchemaComparisonPathStep::EnumVariantField {
+                    type_name,
+                    discriminator,
+                    variant_name,
+                    field_index,
+                    field_name,
+                } => {
+                    write!(buf, "{}", type_name.as_deref().unwrap_or("Enum")).unwrap();
+                    match variant_name {
+                        Some(variant_name) => {
+                            write!(buf, "::{{{}|{}}}", discriminator, variant_name).unwrap()
+                        }
+                        None => write!(buf, "::{{{}}}", discriminator).unwrap(),
+                    }
+                    write_field(&mut buf, *field_index, field_name.as_deref());
+                }
+                SchemaComparisonPathStep::ArrayElement { type_name } => {
+                    write!(buf, "{}.[*]", type_name.as_deref().unwrap_or("Array")).unwrap();
+                }
+                SchemaComparisonPathStep::MapKey { type_name } => {
+                    write!(buf, "{}.Key", type_name.as_deref().unwrap_or("Map")).unwrap();
+                }
+                SchemaComparisonPathStep::MapValue { type_name } => {
+                    write!(buf, "{}.Value", type_name.as_deref().unwrap_or("Map")).unwrap();
+                }
+            }
+        }
+        buf
+    }
+}
+
+fn write_field(buf: &mut String, field_index: usize, field_name: Option<&str>) {
+    match field_name {
+        Some(field_name) => write!(buf, ".[{}|{}]", field_index, field_name).unwrap(),
+        None => write!(buf, ".[{}]", field_index).unwrap(),
+    }
+}
+
+/// Checks that every payload which is valid under `old_type_id` in `old_schema` is also valid
+/// under `new_type_id` in `new_schema`, and is interpreted the same way.
+///
+/// Type metadata (type, field and variant names) is not compared, as it does not affect the
+/// encoding. The permitted changes are:
+/// * Replacing any type with `Any`
+/// * Adding new enum variants
+/// * Widening or removing a `TypeValidation`
+pub fn check_type_backwards_compatibility<S: CustomSchema>(
+    old_schema: &Schema<S>,
+    old_type_id: LocalTypeId,
+    new_schema: &Schema<S>,
+    new_type_id: LocalTypeId,
+) -> Result<(), SchemaIncompatibility<S>> {
+    SchemaComparison {
+        old_schema,
+        new_schema,
+        path: vec![],
+        visited: index_set_new(),
+    }
+    .compare(old_type_id, new_type_id)
+}
+
+struct SchemaComparison<'s, S: CustomSchema> {
+    old_schema: &'s Schema<S>,
+    new_schema: &'s Schema<S>,
+    path: Vec<SchemaComparisonPathStep>,
+    visited: IndexSet<(LocalTypeId, LocalTypeId)>,
+}
+
+impl<'s, S: CustomSchema> SchemaComparison<'s, S> {
+    fn compare(
+        &mut self,
+        old_type_id: LocalTypeId,
+        new_type_id: LocalTypeId,
+    ) -> Result<(), SchemaIncompatibility<S>> {
+        // A pair which is already being compared further up is assumed to be compatible, which
+        // terminates the comparison of recursive types.
+        if !self.visited.insert((old_type_id, new_type_id)) {
+            return Ok(());
+        }
+
+        let (old_schema, new_schema) = (self.old_schema, self.new_schema);
+        let old_kind = old_schema
+            .resolve_type_kind(old_type_id)
+            .ok_or_else(|| self.error(SchemaIncompatibilityReason::OldTypeNotFound(old_type_id)))?;
+        let new_kind = new_schema
+            .resolve_type_kind(new_type_id)
+            .ok_or_else(|| self.error(SchemaIncompatibilityReason::NewTypeNotFound(new_type_id)))?;
+
+        match (old_kind, new_kind) {
+            (_, TypeKind::Any) => {}
+            (
+                TypeKind::Array {
+                    element_type: old_element,
+                },
+                TypeKind::Array {
+                    element_type: new_element,
+                },
+            ) => {
+                self.compare_validation(old_kind, old_type_id, new_type_id)?;
+                let type_name = self.old_type_name(old_type_id);
+                self.compare_child(
+                    SchemaComparisonPathStep::ArrayElement { type_name },
+                    *old_element,
+                    *new_element,
+                )?;
+            }
+            (
+                TypeKind::Tuple {
+                    field_types: old_fields,
+                },
+                TypeKind::Tuple {
+                    field_types: new_fields,
+                },
+            ) => {
+                if old_fields.len() != new_fields.len() {
+                    return Err(self.error(SchemaIncompatibilityReason::TupleLengthChanged {
+                        old: old_fields.len(),
+                        new: new_fields.len(),
+                    }));
+                }
+                let tuple_data =
+                    old_schema.resolve_matching_tuple_metadata(old_type_id, old_fields.len());
+                for (field_index, (old_field, new_field)) in
+                    old_fields.iter().zip(new_fields.iter()).enumerate()
+                {
+                    let step = SchemaComparisonPathStep::TupleField {
+                        type_name: tuple_data.name.map(|name| name.to_string()),
+                        field_index,
+                        field_name: tuple_data
+                            .field_names
+                            .and_then(|names| names.get(field_index))
+                            .map(|name| name.to_string()),
+                    };
+                    self.compare_child(step, *old_field, *new_field)?;
+                }
+            }
+            (
+                TypeKind::Enum {
+                    variants: old_variants,
+                },
+                TypeKind::Enum {
+                    variants: new_variants,
+                },
+            ) => {
+                for (discriminator, old_fields) in old_variants {
+                    let new_fields = new_variants.get(discriminator).ok_or_else(|| {
+                        self.error(SchemaIncompatibilityReason::EnumVariantRemoved {
+                            discriminator: *discriminator,
+                        })
+                    })?;
+                    if old_fields.len() != new_fields.len() {
+                        return Err(self.error(
+                            SchemaIncompatibilityReason::EnumVariantLengthChanged {
+                                discriminator: *discriminator,
+                                old: old_fields.len(),
+                                new: new_fields.len(),
+                            },
+                        ));
+                    }
+                    let variant_data = old_schema.resolve_matching_enum_metadata(
+                        old_type_id,
+                        *discriminator,
+                        old_fields.len(),
+                    );
+                    for (field_index, (old_field, new_field)) in
+                        old_fields.iter().zip(new_fields.iter()).enumerate()
+                    {
+                        let step = SchemaComparisonPathStep::EnumVariantField {
+                            type_name: variant_data.enum_name.map(|name| name.to_string()),
+                            discriminator: *discriminator,
+                            variant_name: variant_data.variant_name.map(|name| name.to_string()),
+                            field_index,
+                            field_name: variant_data
+                                .field_names
+                                .and_then(|names| names.get(field_index))
+                                .map(|name| name.to_string()),
+                        };
+                        self.compare_child(step, *old_field, *new_field)?;
+                    }
+                }
+            }
+            (
+                TypeKind::Map {
+                    key_type: old_key,
+                    value_type: old_value,
+                },
+                TypeKind::Map {
+                    key_type: new_key,
+                    value_type: new_value,
+                },
+            ) => {
+                self.compare_validation(old_kind, old_type_id, new_type_id)?;
+                let type_name = self.old_type_name(old_type_id);
+                self.compare_child(
+                    SchemaComparisonPathStep::MapKey {
+                        type_name: type_name.clone(),
+                    },
+                    *old_key,
+                    *new_key,
+                )?;
+                self.compare_child(
+                    SchemaComparisonPathStep::MapValue { type_name },
+                    *old_value,
+                    *new_value,
+                )?;
+            }
+            (TypeKind::Custom(old_custom), TypeKind::Custom(new_custom)) => {
+                if old_custom != new_custom {
+                    return Err(
+                        self.error(SchemaIncompatibilityReason::CustomTypeKindChanged {
+                            old: old_custom.clone(),
+                            new: new_custom.clone(),
+                        }),
+                    );
+                }
+                self.compare_validation(old_kind, old_type_id, new_type_id)?;
+            }
+            (old_kind, new_kind) => {
+                if old_kind.label() != new_kind.label() {
+                    return Err(self.error(SchemaIncompatibilityReason::TypeKindChanged {
+                        old: old_kind.label(),
+                        new: new_kind.label(),
+                    }));
+                }
+                self.compare_validation(old_kind, old_type_id, new_type_id)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn compare_child(
+        &mut self,
+        step: SchemaComparisonPathStep,
+        old_type_id: LocalTypeId,
+        new_type_id: LocalTypeId,
+    ) -> Result<(), SchemaIncompatibility<S>> {
+        self.path.push(step);
+        self.compare(old_type_id, new_type_id)?;
+        self.path.pop();
+        Ok(())
+    }
+
+    fn compare_validation(
+        &self,
+        kind: &TypeKind<S::CustomTypeKind<LocalTypeId>, LocalTypeId>,
+        old_type_id: LocalTypeId,
+        new_type_id: LocalTypeId,
+    ) -> Result<(), SchemaIncompatibility<S>> {
+        let none = TypeValidation::None;
+        let old = self
+            .old_schema
+            .resolve_type_validation(old_type_id)
+            .unwrap_or(&none);
+        let new = self
+            .new_schema
+            .resolve_type_validation(new_type_id)
+            .unwrap_or(&none);
+        if validation_is_widened(kind, old, new) {
+            Ok(())
+        } else {
+            Err(
+                self.error(SchemaIncompatibilityReason::TypeValidationNarrowed {
+                    old: old.clone(),
+                    new: new.clone(),
+                }),
+            )
+        }
+    }
+
+    fn old_type_name(&self, type_id: LocalTypeId) -> Option<String> {
+        self.old_schema
+            .resolve_type_name_from_metadata(type_id)
+            .map(|name| name.to_string())
+    }
+
+    fn error(&self, reason: SchemaIncompatibilityReason<S>) -> SchemaIncompatibility<S> {
+        SchemaIncompatibility {
+            path: self.path.clone(),
+            reason,
+        }
+    }
+}
+
+macro_rules! numeric_validation_is_widened {
+    ($old:expr, $new:expr, $variant:ident, $t:ty) => {{
+        let old = match $old {
+            TypeValidation::$variant(validation) => *validation,
+            _ => NumericValidation::<$t>::none(),
+        };
+        let new = match $new {
+            TypeValidation::$variant(validation) => *validation,
+            _ => NumericValidation::<$t>::none(),
+        };
+        new.is_valid(old.min.unwrap_or(<$t>::MIN)) && new.is_valid(old.max.unwrap_or(<$t>::MAX))
+    }};
+}
+
+macro_rules! length_validation_is_widened {
+    ($old:expr, $new:expr, $variant:ident) => {{
+        let old = match $old {
+            TypeValidation::$variant(validation) => *validation,
+            _ => LengthValidation::none(),
+        };
+        let new = match $new {
+            TypeValidation::$variant(validation) => *validation,
+            _ => LengthValidation::none(),
+        };
+        new.is_valid(old.min.unwrap_or(0) as usize)
+            && new.is_valid(old.max.unwrap_or(u32::MAX) as usize)
+    }};
+}
+
+/// Returns whether every value permitted by the old validation is also permitted by the new one.
+fn validation_is_widened<C: CustomTypeKind<LocalTypeId>, E: CustomTypeValidation>(
+    kind: &TypeKind<C, LocalTypeId>,
+    old: &TypeValidation<E>,
+    new: &TypeValidation<E>,
+) -> bool {
+    if matches!(new, TypeValidation::None) {
+        return true;
+    }
+    match kind {
+        TypeKind::I8 => numeric_validation_is_widened!(old, new, I8, i8),
+        TypeKind::I16 => numeric_validation_is_widened!(old, new, I16, i16),
+        TypeKind::I32 => numeric_validation_is_widened!(old, new, I32, i32),
+        TypeKind::I64 => numeric_validation_is_widened!(old, new, I64, i64),
+        TypeKind::I128 => numeric_validation_is_widened!(old, new, I128, i128),
+        TypeKind::U8 => numeric_validation_is_widened!(old, new, U8, u8),
+        TypeKind::U16 => numeric_validation_is_widened!(old, new, U16, u16),
+        TypeKind::U32 => numeric_validation_is_widened!(old, new, U32, u32),
+        TypeKind::U64 => numeric_validation_is_widened!(old, new, U64, u64),
+        TypeKind::U128 => numeric_validation_is_widened!(old, new, U128, u128),
+        TypeKind::String => length_validation_is_widened!(old, new, String),
+        TypeKind::Array { .. } => length_validation_is_widened!(old, new, Array),
+        TypeKind::Map { .. } => length_validation_is_widened!(old, new, Map),
+        // Custom validations are opaque, so can only be kept as-is
+        _ => old == new,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(BasicSbor)]
+    pub struct MyStruct {
+        hello: u32,
+        world: MyEnum,
+    }
+
+    #[derive(BasicSbor)]
+    pub enum MyEnum {
+        Option1,
+        Option2 { inner: Vec<u32> },
+    }
+
+    mod v2 {
+        use super::*;
+
+        #[derive(BasicSbor)]
+        pub struct RenamedStruct {
+            renamed: u32,
+            world: MyEnum,
+        }
+
+        #[derive(BasicSbor)]
+        pub enum MyEnum {
+            Option1,
+            Option2 { inner: Vec<u32> },
+            Option3(String),
+        }
+
+        #[derive(BasicSbor)]
+        pub enum MyEnumWithChangedInner {
+            Option1,
+            Option2 { inner: Vec<String> },
+        }
+
+        #[derive(BasicSbor)]
+        pub struct MyStructWithChangedInner {
+            hello: u32,
+            world: MyEnumWithChangedInner,
+        }
+
+        #[derive(BasicSbor)]
+        pub struct MyStructWithExtraField {
+            hello: u32,
+            world: super::MyEnum,
+            extra: u8,
+        }
+    }
+
+    fn check<T1: Describe<NoCustomTypeKind>, T2: Describe<NoCustomTypeKind>>(
+    ) -> Result<(), SchemaIncompatibility<NoCustomSchema>> {
+        let (old_type_id, old_schema) =
+            generate_full_schema_from_single_type::<T1, NoCustomSchema>();
+        let (new_type_id, new_schema) =
+            generate_full_schema_from_single_type::<T2, NoCustomSchema>();
+        check_type_backwards_compatibility(
+            old_schema.v1(),
+            old_type_id,
+            new_schema.v1(),
+            new_type_id,
+        )
+    }
+
+    #[test]
+    fn identical_and_renamed_types_are_compatible() {
+        assert_eq!(check::<MyStruct, MyStruct>(), Ok(()));
+        assert_eq!(check::<MyStruct, v2::RenamedStruct>(), Ok(()));
+    }
+
+    #[test]
+    fn adding_enum_variants_is_compatible_but_removing_is_not() {
+        assert_eq!(check::<MyEnum, v2::MyEnum>(), Ok(()));
+        let error = check::<v2::MyEnum, MyEnum>().unwrap_err();
+        assert_eq!(
+            error.reason,
+            SchemaIncompatibilityReason::EnumVariantRemoved { discriminator: 2 }
+        );
+        assert_eq!(error.path_to_string(), "");
+    }
+
+    #[test]
+    fn any_type_accepts_everything() {
+        assert_eq!(check::<MyStruct, BasicValue>(), Ok(()));
+        let error = check::<BasicValue, MyStruct>().unwrap_err();
+        assert_eq!(
+            error.reason,
+            SchemaIncompatibilityReason::TypeKindChanged {
+                old: TypeKindLabel::Any,
+                new: TypeKindLabel::Tuple,
+            }
+        );
+    }
+
+    #[test]
+    fn nested_type_change_reports_path() {
+        let error = check::<MyStruct, v2::MyStructWithChangedInner>().unwrap_err();
+        assert_eq!(
+            error.reason,
+            SchemaIncompatibilityReason::TypeKindChanged {
+                old: TypeKindLabel::U32,
+                new: TypeKindLabel::String,
+            }
+        );
+        assert_eq!(
+            error.path_to_string(),
+            "MyStruct.[1|world]->MyEnum::{1|Option2}.[0|inner]->Array.[*]"
+        );
+    }
+
+    #[test]
+    fn tuple_length_change_is_incompatible() {
+        let error = check::<MyStruct, v2::MyStructWithExtraField>().unwrap_err();
+        assert_eq!(
+            error.reason,
+            SchemaIncompatibilityReason::TupleLengthChanged { old: 2, new: 3 }
+        );
+    }
+
+    #[test]
+    fn map_key_and_value_are_compared() {
+        assert_eq!(
+            check::<IndexMap<u32, String>, IndexMap<u32, String>>(),
+            Ok(())
+        );
+        let error = check::<IndexMap<u32, String>, IndexMap<u32, u32>>().unwrap_err();
+        assert_eq!(error.path_to_string(), "Map.Value");
+    }
+
+    #[test]
+    fn recursive_types_are_compared() {
+        #[derive(BasicSbor)]
+        pub struct Node {
+            children: Vec<Node>,
+        }
+
+        assert_eq!(check::<Node, Node>(), Ok(()));
+    }
+
+    #[test]
+    fn validation_may_only_be_widened() {
+        let type_id = LocalTypeId::SchemaLocalIndex(0);
+        let array_schema = |validation| BasicSchema {
+            type_kinds: vec![TypeKind::Array {
+                element_type: LocalTypeId::from(basic_well_known_types::U8_TYPE),
+            }],
+            type_metadata: vec![TypeMetadata::unnamed()],
+            type_validations: vec![validation],
+        };
+        let with_length =
+            |min, max| array_schema(TypeValidation::Array(LengthValidation { min, max }));
+        let check_validation = |old: &BasicSchema, new: &BasicSchema| {
+            check_type_backwards_compatibility(old, type_id, new, type_id)
+        };
+
+        let unvalidated = array_schema(TypeValidation::None);
+        let narrow = with_length(Some(2), Some(5));
+        let wide = with_length(Some(1), Some(10));
+        let unbounded = with_length(None, None);
+
+        assert_eq!(check_validation(&narrow, &wide), Ok(()));
+        assert_eq!(check_validation(&narrow, &unvalidated), Ok(()));
+        assert_eq!(check_validation(&unbounded, &unvalidated), Ok(()));
+        assert_eq!(check_validation(&unvalidated, &unbounded), Ok(()));
+        assert_eq!(
+            check_validation(&wide, &narrow).unwrap_err().reason,
+            SchemaIncompatibilityReason::TypeValidationNarrowed {
+                old: TypeValidation::Array(LengthValidation {
+                    min: Some(1),
+                    max: Some(10)
+                }),
+                new: TypeValidation::Array(LengthValidation {
+                    min: Some(2),
+                    max: Some(5)
+                }),
+            }
+        );
+        assert!(check_validation(&unvalidated, &narrow).is_err());
+    }
+}
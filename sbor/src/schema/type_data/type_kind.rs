@@ -34,3 +34,51 @@ pub enum TypeKind<C: CustomTypeKind<L>, L: SchemaTypeLink> {
     // Custom Types
     Custom(C),
 }
+
+/// The discriminator of a [`TypeKind`], without any of its type links or custom contents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeKindLabel {
+    Any,
+    Bool,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    String,
+    Array,
+    Tuple,
+    Enum,
+    Map,
+    Custom,
+}
+
+impl<C: CustomTypeKind<L>, L: SchemaTypeLink> TypeKind<C, L> {
+    pub fn label(&self) -> TypeKindLabel {
+        match self {
+            TypeKind::Any => TypeKindLabel::Any,
+            TypeKind::Bool => TypeKindLabel::Bool,
+            TypeKind::I8 => TypeKindLabel::I8,
+            TypeKind::I16 => TypeKindLabel::I16,
+            TypeKind::I32 => TypeKindLabel::I32,
+            TypeKind::I64 => TypeKindLabel::I64,
+            TypeKind::I128 => TypeKindLabel::I128,
+            TypeKind::U8 => TypeKindLabel::U8,
+            TypeKind::U16 => TypeKindLabel::U16,
+            TypeKind::U32 => TypeKindLabel::U32,
+            TypeKind::U64 => TypeKindLabel::U64,
+            TypeKind::U128 => TypeKindLabel::U128,
+            TypeKind::String => TypeKindLabel::String,
+            TypeKind::Array { .. } => TypeKindLabel::Array,
+            TypeKind::Tuple { .. } => TypeKindLabel::Tuple,
+            TypeKind::Enum { .. } => TypeKindLabel::Enum,
+            TypeKind::Map { .. } => TypeKindLabel::Map,
+            TypeKind::Custom(_) => TypeKindLabel::Custom,
+        }
+    }
+}
@@ -269,6 +269,13 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
     validate_type_ident(&bp_ident)?;
     let bp_items = &mut bp_impl.items;
     let bp_name = bp_ident.to_string();
+    let bp_collections = collection_fields(bp_fields);
+    if bp_collections.len() > usize::from(u8::MAX) {
+        return Err(Error::new(
+            bp_strut.span(),
+            format!("A blueprint can have at most {} collections", u8::MAX),
+        ));
+    }
 
     trace!("Blueprint name: {}", bp_name);
 
@@ -690,6 +697,23 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
     let output_schema = quote! {};
     #[cfg(not(feature = "no-schema"))]
     let output_schema = {
+        let collections = if bp_collections.is_empty() {
+            quote! { Vec::new() }
+        } else {
+            let collection_types = bp_collections.iter().map(|(_, ty)| ty);
+            quote! {
+                {
+                    let mut collections = Vec::new();
+                    #(
+                        collections.push(
+                            <#collection_types as ::scrypto::component::BlueprintCollection>::collection_schema(&mut aggregator)
+                        );
+                    )*
+                    collections
+                }
+            }
+        };
+
         let function_auth_statements = {
             let function_auth_index = macro_statements.iter().position(|item| {
                 item.mac
@@ -811,7 +835,7 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
 
                     let state = BlueprintStateSchemaInit {
                         fields,
-                        collections: Vec::new(),
+                        collections: #collections,
                     };
 
                     // Aggregate functions
@@ -892,6 +916,20 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
         }
     };
 
+    let init_collections = if bp_collections.is_empty() {
+        quote! {}
+    } else {
+        let members = bp_collections.iter().map(|(member, _)| member);
+        let collection_indices = (0..bp_collections.len()).map(|index| index as u8);
+        quote! {
+            fn init_collections(&mut self) {
+                #(
+                    ::scrypto::component::BlueprintCollection::bind(&mut self.#members, #collection_indices);
+                )*
+            }
+        }
+    };
+
     let output_original_code = quote! {
         #[derive(::scrypto::prelude::ScryptoSbor)]
         pub struct #bp_ident #bp_fields #bp_semi_token
@@ -902,6 +940,8 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
 
         impl ::scrypto::component::ComponentState for #bp_ident {
             const BLUEPRINT_NAME: &'static str = #bp_name;
+
+            #init_collections
         }
 
         impl HasStub for #bp_ident {
@@ -1770,6 +1810,35 @@ fn generate_test_bindings_state(bp_struct: &ItemStruct) -> ItemStruct {
     bp_struct
 }
 
+/// Returns the fields of the blueprint state which are stored as collections of the component,
+/// in the order of their collection indices.
+fn collection_fields(fields: &Fields) -> Vec<(Member, Type)> {
+    fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| is_collection_type(&field.ty))
+        .map(|(index, field)| {
+            let member = match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(Index::from(index)),
+            };
+            (member, field.ty.clone())
+        })
+        .collect()
+}
+
+fn is_collection_type(ty: &Type) -> bool {
+    match ty {
+        Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .filter(|segment| segment.ident == "IterableMap" || segment.ident == "SortedMap")
+            .is_some(),
+        _ => false,
+    }
+}
+
 /// This function performs the following replacement and is used for the function returns:
 ///
 /// Before:
@@ -97,12 +97,25 @@ implement_client_api! {
             collection_index: CollectionIndex,
             key: Vec<u8>,
         ) -> Result<Option<Vec<u8>>, RuntimeError>,
+        actor_index_get: (
+            &mut self,
+            object_handle: ActorStateHandle,
+            collection_index: CollectionIndex,
+            key: Vec<u8>,
+        ) -> Result<Option<Vec<u8>>, RuntimeError>,
         actor_index_scan_keys: (
             &mut self,
             object_handle: ActorStateHandle,
             collection_index: CollectionIndex,
             limit: u32,
         ) -> Result<Vec<Vec<u8>>, RuntimeError>,
+        actor_index_scan_after: (
+            &mut self,
+            object_handle: ActorStateHandle,
+            collection_index: CollectionIndex,
+            after: Option<Vec<u8>>,
+            limit: u32,
+        ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, RuntimeError>,
         actor_index_drain: (
             &mut self,
             object_handle: ActorStateHandle,
@@ -145,6 +158,13 @@ implement_client_api! {
             collection_index: CollectionIndex,
             count: u32,
         ) -> Result<Vec<(SortedKey, Vec<u8>)>, RuntimeError>,
+        actor_sorted_index_scan_after: (
+            &mut self,
+            object_handle: ActorStateHandle,
+            collection_index: CollectionIndex,
+            after: Option<SortedKey>,
+            count: u32,
+        ) -> Result<Vec<(SortedKey, Vec<u8>)>, RuntimeError>,
     },
     ClientBlueprintApi: {
         call_function: (
@@ -189,6 +209,17 @@ implement_client_api! {
             node_id: &NodeId,
             key: &Vec<u8>,
         ) -> Result<Vec<u8>, RuntimeError>,
+        key_value_store_scan: (
+            &mut self,
+            node_id: &NodeId,
+            after: Option<Vec<u8>>,
+            limit: u32,
+        ) -> Result<(Vec<(Vec<u8>, Vec<u8>)>, Option<Vec<u8>>), RuntimeError>,
+        key_value_store_count: (
+            &mut self,
+            node_id: &NodeId,
+            limit: u32,
+        ) -> Result<u32, RuntimeError>,
     },
     ClientObjectApi: {
         new_object: (
@@ -281,5 +312,9 @@ implement_client_api! {
         bls12381_v1_fast_aggregate_verify: (&mut self, message: &[u8], public_keys: &[Bls12381G1PublicKey], signature: &Bls12381G2Signature) -> Result<u32, RuntimeError>,
         bls12381_g2_signature_aggregate: (&mut self, signatures: &[Bls12381G2Signature]) -> Result<Bls12381G2Signature, RuntimeError>,
         keccak256_hash: (&mut self, data: &[u8]) -> Result<Hash, RuntimeError>,
+        blake2b_256_hash: (&mut self, data: &[u8]) -> Result<Hash, RuntimeError>,
+        ed25519_verify: (&mut self, message: &[u8], public_key: &Ed25519PublicKey, signature: &Ed25519Signature) -> Result<u32, RuntimeError>,
+        secp256k1_ecdsa_verify: (&mut self, hash: &Hash, public_key: &Secp256k1PublicKey, signature: &Secp256k1Signature) -> Result<u32, RuntimeError>,
+        secp256k1_ecdsa_verify_and_key_recover: (&mut self, hash: &Hash, signature: &Secp256k1Signature) -> Result<Secp256k1PublicKey, RuntimeError>,
     },
 }
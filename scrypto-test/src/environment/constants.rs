@@ -2,10 +2,11 @@ use crate::prelude::*;
 
 /// Defines the set of Nodes that all test [`CallFrame`]s have visibility to when they're first
 /// created. This contains all of the well-known addresses of nodes.
-pub(super) const GLOBAL_VISIBLE_NODES: [NodeId; 28] = [
+pub(super) const GLOBAL_VISIBLE_NODES: [NodeId; 29] = [
     XRD.into_node_id(),
     SECP256K1_SIGNATURE_VIRTUAL_BADGE.into_node_id(),
     ED25519_SIGNATURE_VIRTUAL_BADGE.into_node_id(),
+    SECP256R1_SIGNATURE_VIRTUAL_BADGE.into_node_id(),
     PACKAGE_OF_DIRECT_CALLER_VIRTUAL_BADGE.into_node_id(),
     GLOBAL_CALLER_VIRTUAL_BADGE.into_node_id(),
     SYSTEM_TRANSACTION_BADGE.into_node_id(),
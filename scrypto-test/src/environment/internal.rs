@@ -56,9 +56,67 @@ impl EncapsulatedRadixEngine {
             let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
             substate_db.commit(&db_updates);
 
+            let state_updates = generate_vm_boot_crypto_utils_v2_state_updates();
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            substate_db.commit(&db_updates);
+
+            let state_updates = generate_vm_boot_bulk_memory_state_updates();
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            substate_db.commit(&db_updates);
+
+            let state_updates = generate_vm_boot_indexed_collections_state_updates();
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            substate_db.commit(&db_updates);
+
+            let state_updates = generate_vm_boot_kv_store_iteration_state_updates();
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            substate_db.commit(&db_updates);
+
             let state_updates = generate_pools_v1_1_state_updates(&substate_db);
             let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
             substate_db.commit(&db_updates);
+
+            let state_updates = generate_pools_v1_2_state_updates(&substate_db);
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            substate_db.commit(&db_updates);
+
+            let state_updates = generate_account_allowances_state_updates(&substate_db);
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            substate_db.commit(&db_updates);
+
+            let state_updates = generate_validator_redelegation_state_updates(&substate_db);
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            substate_db.commit(&db_updates);
+
+            let state_updates =
+                generate_access_controller_xrd_fee_vault_state_updates(&substate_db);
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            substate_db.commit(&db_updates);
+
+            let state_updates = generate_package_blueprint_upgrades_state_updates(&substate_db);
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            substate_db.commit(&db_updates);
+
+            let state_updates = generate_package_royalty_splits_state_updates(&substate_db);
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            substate_db.commit(&db_updates);
+
+            let state_updates = generate_component_royalty_splits_state_updates(&substate_db);
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            substate_db.commit(&db_updates);
+
+            let state_updates = generate_fungible_vesting_state_updates(&substate_db);
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            substate_db.commit(&db_updates);
+
+            let state_updates =
+                generate_non_fungible_batch_data_updates_state_updates(&substate_db);
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            substate_db.commit(&db_updates);
+
+            let vm = Vm::new(&scrypto_vm, native_vm.clone());
+            Bootstrapper::new(NetworkDefinition::simulator(), &mut substate_db, vm, false)
+                .update_to_secp256r1_signatures();
         }
 
         // Create the Id allocator we will be using throughout this test
@@ -88,6 +146,9 @@ impl EncapsulatedRadixEngine {
         SystemConfig {
             blueprint_cache: NonIterMap::new(),
             auth_cache: NonIterMap::new(),
+            blueprint_version_cache: NonIterMap::new(),
+            // The environment applies the package blueprint upgrades protocol update above
+            package_blueprint_upgrades: true,
             schema_cache: NonIterMap::new(),
             callback_obj: Vm::new(scrypto_vm, native_vm),
             modules: SystemModuleMixer::new(
@@ -13,7 +13,7 @@ use radix_engine::system::system_callback::SystemConfig;
 use radix_engine::system::system_callback_api::SystemCallbackObject;
 use radix_engine::system::system_modules::costing::{CostingError, FeeReserveError, OnApplyCost};
 use radix_engine::system::system_modules::execution_trace::{BucketSnapshot, ProofSnapshot};
-use radix_engine::track::{BootStore, NodeSubstates};
+use radix_engine::track::{BootStore, NodeSubstates, ScanKeysOrder};
 use radix_engine::transaction::WrappedSystem;
 use radix_engine::types::*;
 use radix_engine::vm::wasm::DefaultWasmEngine;
@@ -474,9 +474,10 @@ impl<'a, M: KernelCallbackObject, Y: KernelApi<InjectCostingError<M>>>
         node_id: &NodeId,
         partition_num: PartitionNumber,
         count: u32,
+        after: Option<&SortedKey>,
     ) -> Result<Vec<(SortedKey, IndexedScryptoValue)>, RuntimeError> {
         self.api
-            .kernel_scan_sorted_substates(node_id, partition_num, count)
+            .kernel_scan_sorted_substates(node_id, partition_num, count, after)
     }
 
     fn kernel_scan_keys<K: SubstateKeyContent + 'static>(
@@ -484,9 +485,10 @@ impl<'a, M: KernelCallbackObject, Y: KernelApi<InjectCostingError<M>>>
         node_id: &NodeId,
         partition_num: PartitionNumber,
         count: u32,
+        order: ScanKeysOrder,
     ) -> Result<Vec<SubstateKey>, RuntimeError> {
         self.api
-            .kernel_scan_keys::<K>(node_id, partition_num, count)
+            .kernel_scan_keys::<K>(node_id, partition_num, count, order)
     }
 
     fn kernel_drain_substates<K: SubstateKeyContent + 'static>(
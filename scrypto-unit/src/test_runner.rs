@@ -22,7 +22,11 @@ use radix_engine::transaction::{
 };
 use radix_engine::types::*;
 use radix_engine::utils::*;
-use radix_engine::vm::wasm::{DefaultWasmEngine, WasmValidatorConfigV1};
+#[cfg(feature = "wasm_profiling")]
+use radix_engine::vm::wasm::WasmProfiler;
+use radix_engine::vm::wasm::{
+    DefaultWasmEngine, WasmDiskCache, WasmDiskCacheOptions, WasmValidatorConfigV1,
+};
 use radix_engine::vm::{NativeVm, NativeVmExtension, NoExtension, ScryptoVm, Vm};
 use radix_engine_interface::api::node_modules::auth::*;
 use radix_engine_interface::api::ModuleId;
@@ -350,7 +354,21 @@ pub struct TestRunnerBuilder<E, D> {
     // The following are protocol updates on mainnet
     with_seconds_precision_update: bool,
     with_crypto_utils_update: bool,
+    with_crypto_utils_v2_update: bool,
+    with_bulk_memory_update: bool,
+    with_indexed_collections_update: bool,
+    with_kv_store_iteration_update: bool,
     with_pools_v1_1: bool,
+    with_pools_v1_2: bool,
+    with_account_allowances: bool,
+    with_validator_redelegation: bool,
+    with_access_controller_xrd_fee_vault: bool,
+    with_package_blueprint_upgrades: bool,
+    with_package_royalty_splits: bool,
+    with_component_royalty_splits: bool,
+    with_fungible_vesting: bool,
+    with_non_fungible_batch_data_updates: bool,
+    with_secp256r1_signatures: bool,
 }
 
 impl TestRunnerBuilder<NoExtension, InMemorySubstateDatabase> {
@@ -363,7 +381,21 @@ impl TestRunnerBuilder<NoExtension, InMemorySubstateDatabase> {
             skip_receipt_check: false,
             with_seconds_precision_update: true,
             with_crypto_utils_update: true,
+            with_crypto_utils_v2_update: true,
+            with_bulk_memory_update: true,
+            with_indexed_collections_update: true,
+            with_kv_store_iteration_update: true,
             with_pools_v1_1: true,
+            with_pools_v1_2: true,
+            with_account_allowances: true,
+            with_validator_redelegation: true,
+            with_access_controller_xrd_fee_vault: true,
+            with_package_blueprint_upgrades: true,
+            with_package_royalty_splits: true,
+            with_component_royalty_splits: true,
+            with_fungible_vesting: true,
+            with_non_fungible_batch_data_updates: true,
+            with_secp256r1_signatures: true,
         }
     }
 }
@@ -383,7 +415,21 @@ impl<E: NativeVmExtension, D: TestDatabase> TestRunnerBuilder<E, D> {
             skip_receipt_check: false,
             with_seconds_precision_update: self.with_seconds_precision_update,
             with_crypto_utils_update: self.with_crypto_utils_update,
+            with_crypto_utils_v2_update: self.with_crypto_utils_v2_update,
+            with_bulk_memory_update: self.with_bulk_memory_update,
+            with_indexed_collections_update: self.with_indexed_collections_update,
+            with_kv_store_iteration_update: self.with_kv_store_iteration_update,
             with_pools_v1_1: self.with_pools_v1_1,
+            with_pools_v1_2: self.with_pools_v1_2,
+            with_account_allowances: self.with_account_allowances,
+            with_validator_redelegation: self.with_validator_redelegation,
+            with_access_controller_xrd_fee_vault: self.with_access_controller_xrd_fee_vault,
+            with_package_blueprint_upgrades: self.with_package_blueprint_upgrades,
+            with_package_royalty_splits: self.with_package_royalty_splits,
+            with_component_royalty_splits: self.with_component_royalty_splits,
+            with_fungible_vesting: self.with_fungible_vesting,
+            with_non_fungible_batch_data_updates: self.with_non_fungible_batch_data_updates,
+            with_secp256r1_signatures: self.with_secp256r1_signatures,
         }
     }
 
@@ -409,7 +455,21 @@ impl<E: NativeVmExtension, D: TestDatabase> TestRunnerBuilder<E, D> {
             skip_receipt_check: self.skip_receipt_check,
             with_seconds_precision_update: self.with_seconds_precision_update,
             with_crypto_utils_update: self.with_crypto_utils_update,
+            with_crypto_utils_v2_update: self.with_crypto_utils_v2_update,
+            with_bulk_memory_update: self.with_bulk_memory_update,
+            with_indexed_collections_update: self.with_indexed_collections_update,
+            with_kv_store_iteration_update: self.with_kv_store_iteration_update,
             with_pools_v1_1: self.with_pools_v1_1,
+            with_pools_v1_2: self.with_pools_v1_2,
+            with_account_allowances: self.with_account_allowances,
+            with_validator_redelegation: self.with_validator_redelegation,
+            with_access_controller_xrd_fee_vault: self.with_access_controller_xrd_fee_vault,
+            with_package_blueprint_upgrades: self.with_package_blueprint_upgrades,
+            with_package_royalty_splits: self.with_package_royalty_splits,
+            with_component_royalty_splits: self.with_component_royalty_splits,
+            with_fungible_vesting: self.with_fungible_vesting,
+            with_non_fungible_batch_data_updates: self.with_non_fungible_batch_data_updates,
+            with_secp256r1_signatures: self.with_secp256r1_signatures,
         }
     }
 
@@ -422,7 +482,21 @@ impl<E: NativeVmExtension, D: TestDatabase> TestRunnerBuilder<E, D> {
             skip_receipt_check: self.skip_receipt_check,
             with_seconds_precision_update: self.with_seconds_precision_update,
             with_crypto_utils_update: self.with_crypto_utils_update,
+            with_crypto_utils_v2_update: self.with_crypto_utils_v2_update,
+            with_bulk_memory_update: self.with_bulk_memory_update,
+            with_indexed_collections_update: self.with_indexed_collections_update,
+            with_kv_store_iteration_update: self.with_kv_store_iteration_update,
             with_pools_v1_1: self.with_pools_v1_1,
+            with_pools_v1_2: self.with_pools_v1_2,
+            with_account_allowances: self.with_account_allowances,
+            with_validator_redelegation: self.with_validator_redelegation,
+            with_access_controller_xrd_fee_vault: self.with_access_controller_xrd_fee_vault,
+            with_package_blueprint_upgrades: self.with_package_blueprint_upgrades,
+            with_package_royalty_splits: self.with_package_royalty_splits,
+            with_component_royalty_splits: self.with_component_royalty_splits,
+            with_fungible_vesting: self.with_fungible_vesting,
+            with_non_fungible_batch_data_updates: self.with_non_fungible_batch_data_updates,
+            with_secp256r1_signatures: self.with_secp256r1_signatures,
         }
     }
 
@@ -436,11 +510,81 @@ impl<E: NativeVmExtension, D: TestDatabase> TestRunnerBuilder<E, D> {
         self
     }
 
+    pub fn without_crypto_utils_v2_update(mut self) -> Self {
+        self.with_crypto_utils_v2_update = false;
+        self
+    }
+
+    pub fn without_bulk_memory_update(mut self) -> Self {
+        self.with_bulk_memory_update = false;
+        self
+    }
+
+    pub fn without_indexed_collections_update(mut self) -> Self {
+        self.with_indexed_collections_update = false;
+        self
+    }
+
+    pub fn without_kv_store_iteration_update(mut self) -> Self {
+        self.with_kv_store_iteration_update = false;
+        self
+    }
+
     pub fn without_pools_v1_1(mut self) -> Self {
         self.with_pools_v1_1 = false;
         self
     }
 
+    pub fn without_pools_v1_2(mut self) -> Self {
+        self.with_pools_v1_2 = false;
+        self
+    }
+
+    pub fn without_account_allowances(mut self) -> Self {
+        self.with_account_allowances = false;
+        self
+    }
+
+    pub fn without_validator_redelegation(mut self) -> Self {
+        self.with_validator_redelegation = false;
+        self
+    }
+
+    pub fn without_access_controller_xrd_fee_vault(mut self) -> Self {
+        self.with_access_controller_xrd_fee_vault = false;
+        self
+    }
+
+    pub fn without_package_blueprint_upgrades(mut self) -> Self {
+        self.with_package_blueprint_upgrades = false;
+        self
+    }
+
+    pub fn without_package_royalty_splits(mut self) -> Self {
+        self.with_package_royalty_splits = false;
+        self
+    }
+
+    pub fn without_component_royalty_splits(mut self) -> Self {
+        self.with_component_royalty_splits = false;
+        self
+    }
+
+    pub fn without_fungible_vesting(mut self) -> Self {
+        self.with_fungible_vesting = false;
+        self
+    }
+
+    pub fn without_non_fungible_batch_data_updates(mut self) -> Self {
+        self.with_non_fungible_batch_data_updates = false;
+        self
+    }
+
+    pub fn without_secp256r1_signatures(mut self) -> Self {
+        self.with_secp256r1_signatures = false;
+        self
+    }
+
     pub fn build_from_snapshot(
         self,
         snapshot: TestRunnerSnapshot,
@@ -452,8 +596,18 @@ impl<E: NativeVmExtension, D: TestDatabase> TestRunnerBuilder<E, D> {
         let trace = false;
         //----------------------------------------------------------------
 
+        #[cfg(feature = "wasm_profiling")]
+        let (scrypto_vm, wasm_profiler) = new_profiled_scrypto_vm();
+        #[cfg(not(feature = "wasm_profiling"))]
+        let scrypto_vm = ScryptoVm {
+            wasm_engine: new_wasm_engine(),
+            wasm_validator_config: WasmValidatorConfigV1::new(),
+        };
+
         TestRunner {
-            scrypto_vm: ScryptoVm::default(),
+            scrypto_vm,
+            #[cfg(feature = "wasm_profiling")]
+            wasm_profiler,
             native_vm: NativeVm::new_with_extension(self.custom_extension),
             database: snapshot.database,
             next_private_key: snapshot.next_private_key,
@@ -475,8 +629,11 @@ impl<E: NativeVmExtension, D: TestDatabase> TestRunnerBuilder<E, D> {
         let trace = false;
         //----------------------------------------------------------------
 
+        #[cfg(feature = "wasm_profiling")]
+        let (scrypto_vm, wasm_profiler) = new_profiled_scrypto_vm();
+        #[cfg(not(feature = "wasm_profiling"))]
         let scrypto_vm = ScryptoVm {
-            wasm_engine: DefaultWasmEngine::default(),
+            wasm_engine: new_wasm_engine(),
             wasm_validator_config: WasmValidatorConfigV1::new(),
         };
         let native_vm = NativeVm::new_with_extension(self.custom_extension);
@@ -544,6 +701,42 @@ impl<E: NativeVmExtension, D: TestDatabase> TestRunnerBuilder<E, D> {
                 substate_db.commit(&db_updates);
             }
 
+            if self.with_crypto_utils_update && self.with_crypto_utils_v2_update {
+                let state_updates = generate_vm_boot_crypto_utils_v2_state_updates();
+                let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+                substate_db.commit(&db_updates);
+            }
+
+            if self.with_crypto_utils_update
+                && self.with_crypto_utils_v2_update
+                && self.with_bulk_memory_update
+            {
+                let state_updates = generate_vm_boot_bulk_memory_state_updates();
+                let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+                substate_db.commit(&db_updates);
+            }
+
+            if self.with_crypto_utils_update
+                && self.with_crypto_utils_v2_update
+                && self.with_bulk_memory_update
+                && self.with_indexed_collections_update
+            {
+                let state_updates = generate_vm_boot_indexed_collections_state_updates();
+                let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+                substate_db.commit(&db_updates);
+            }
+
+            if self.with_crypto_utils_update
+                && self.with_crypto_utils_v2_update
+                && self.with_bulk_memory_update
+                && self.with_indexed_collections_update
+                && self.with_kv_store_iteration_update
+            {
+                let state_updates = generate_vm_boot_kv_store_iteration_state_updates();
+                let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+                substate_db.commit(&db_updates);
+            }
+
             {
                 let state_updates = generate_validator_fee_fix_state_updates(&substate_db);
                 let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
@@ -557,8 +750,76 @@ impl<E: NativeVmExtension, D: TestDatabase> TestRunnerBuilder<E, D> {
             substate_db.commit(&db_updates);
         }
 
+        if self.with_pools_v1_1 && self.with_pools_v1_2 {
+            let state_updates = generate_pools_v1_2_state_updates(&substate_db);
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            substate_db.commit(&db_updates);
+        }
+
+        if self.with_account_allowances {
+            let state_updates = generate_account_allowances_state_updates(&substate_db);
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            substate_db.commit(&db_updates);
+        }
+
+        if self.with_validator_redelegation {
+            let state_updates = generate_validator_redelegation_state_updates(&substate_db);
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            substate_db.commit(&db_updates);
+        }
+
+        if self.with_access_controller_xrd_fee_vault {
+            let state_updates =
+                generate_access_controller_xrd_fee_vault_state_updates(&substate_db);
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            substate_db.commit(&db_updates);
+        }
+
+        if self.with_package_blueprint_upgrades {
+            let state_updates = generate_package_blueprint_upgrades_state_updates(&substate_db);
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            substate_db.commit(&db_updates);
+        }
+
+        if self.with_package_royalty_splits {
+            let state_updates = generate_package_royalty_splits_state_updates(&substate_db);
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            substate_db.commit(&db_updates);
+        }
+
+        if self.with_component_royalty_splits {
+            let state_updates = generate_component_royalty_splits_state_updates(&substate_db);
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            substate_db.commit(&db_updates);
+        }
+
+        if self.with_fungible_vesting {
+            let state_updates = generate_fungible_vesting_state_updates(&substate_db);
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            substate_db.commit(&db_updates);
+        }
+
+        if self.with_non_fungible_batch_data_updates {
+            let state_updates =
+                generate_non_fungible_batch_data_updates_state_updates(&substate_db);
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            substate_db.commit(&db_updates);
+        }
+
+        if self.with_account_allowances && self.with_secp256r1_signatures {
+            Bootstrapper::new(
+                NetworkDefinition::simulator(),
+                &mut substate_db,
+                Vm::new(&scrypto_vm, native_vm.clone()),
+                bootstrap_trace,
+            )
+            .update_to_secp256r1_signatures();
+        }
+
         let runner = TestRunner {
             scrypto_vm,
+            #[cfg(feature = "wasm_profiling")]
+            wasm_profiler,
             native_vm,
             database: substate_db,
             next_private_key,
@@ -583,6 +844,8 @@ impl<E: NativeVmExtension, D: TestDatabase> TestRunnerBuilder<E, D> {
 
 pub struct TestRunner<E: NativeVmExtension, D: TestDatabase> {
     scrypto_vm: ScryptoVm<DefaultWasmEngine>,
+    #[cfg(feature = "wasm_profiling")]
+    wasm_profiler: Option<WasmProfiler>,
     native_vm: NativeVm<E>,
     database: D,
     next_private_key: u64,
@@ -593,13 +856,52 @@ pub struct TestRunner<E: NativeVmExtension, D: TestDatabase> {
     skip_receipt_check: bool,
 }
 
-#[cfg(feature = "post_run_db_check")]
+#[cfg(any(feature = "post_run_db_check", feature = "wasm_profiling"))]
 impl<E: NativeVmExtension, D: TestDatabase> Drop for TestRunner<E, D> {
     fn drop(&mut self) {
+        #[cfg(feature = "wasm_profiling")]
+        self.save_wasm_profile();
+        #[cfg(feature = "post_run_db_check")]
         self.check_database()
     }
 }
 
+/// The maximum size of the on-disk WASM cache shared by all test runners.
+const WASM_DISK_CACHE_MAX_SIZE_IN_BYTES: u64 = 512 * 1024 * 1024;
+
+/// Creates a WASM engine which caches the instrumented code of the published packages on disk,
+/// if the `WASM_DISK_CACHE_DIRECTORY` environment variable is set, so that the same packages
+/// don't need to be instrumented again by every test.
+fn new_wasm_engine() -> DefaultWasmEngine {
+    let wasm_engine = DefaultWasmEngine::default();
+    match std::env::var_os("WASM_DISK_CACHE_DIRECTORY") {
+        Some(directory) => wasm_engine.with_disk_cache(WasmDiskCache::new(
+            WasmDiskCacheOptions {
+                directory: directory.into(),
+                max_size_in_bytes: WASM_DISK_CACHE_MAX_SIZE_IN_BYTES,
+            },
+            &WasmValidatorConfigV1::new(),
+        )),
+        None => wasm_engine,
+    }
+}
+
+/// Creates a Scrypto VM which profiles the WASM code it runs, if the `WASM_PROFILING_DIRECTORY`
+/// environment variable is set (see `TestRunner::save_wasm_profile`).
+#[cfg(feature = "wasm_profiling")]
+fn new_profiled_scrypto_vm() -> (ScryptoVm<DefaultWasmEngine>, Option<WasmProfiler>) {
+    let wasm_profiler = std::env::var_os("WASM_PROFILING_DIRECTORY").map(|_| WasmProfiler::new());
+    let wasm_engine = match &wasm_profiler {
+        Some(wasm_profiler) => new_wasm_engine().with_profiler(wasm_profiler.clone()),
+        None => new_wasm_engine(),
+    };
+    let scrypto_vm = ScryptoVm {
+        wasm_engine,
+        wasm_validator_config: WasmValidatorConfigV1::new(),
+    };
+    (scrypto_vm, wasm_profiler)
+}
+
 #[derive(Clone)]
 pub struct TestRunnerSnapshot {
     database: InMemorySubstateDatabase,
@@ -645,6 +947,19 @@ impl<E: NativeVmExtension, D: TestDatabase> TestRunner<E, D> {
         &mut self.database
     }
 
+    /// Enacts the secp256r1 signatures protocol update, for test runners built without it. Unlike
+    /// the other protocol updates, it executes a system transaction in addition to a flash.
+    pub fn update_to_secp256r1_signatures(&mut self) -> TransactionReceipt {
+        let vm = Vm::new(&self.scrypto_vm, self.native_vm.clone());
+        Bootstrapper::new(
+            NetworkDefinition::simulator(),
+            &mut self.database,
+            vm,
+            false,
+        )
+        .update_to_secp256r1_signatures()
+    }
+
     pub fn collected_events(&self) -> &Vec<Vec<(EventTypeIdentifier, Vec<u8>)>> {
         self.collected_events.as_ref()
     }
@@ -673,6 +988,13 @@ impl<E: NativeVmExtension, D: TestDatabase> TestRunner<E, D> {
         (public_key, private_key)
     }
 
+    pub fn new_secp256r1_key_pair(&mut self) -> (Secp256r1PublicKey, Secp256r1PrivateKey) {
+        let private_key = Secp256r1PrivateKey::from_u64(self.next_private_key()).unwrap();
+        let public_key = private_key.public_key();
+
+        (public_key, private_key)
+    }
+
     pub fn new_key_pair_with_auth_address(
         &mut self,
     ) -> (Secp256k1PublicKey, Secp256k1PrivateKey, NonFungibleGlobalId) {
@@ -1040,6 +1362,17 @@ impl<E: NativeVmExtension, D: TestDatabase> TestRunner<E, D> {
         (pub_key, priv_key, account)
     }
 
+    pub fn new_secp256r1_virtual_account(
+        &mut self,
+    ) -> (Secp256r1PublicKey, Secp256r1PrivateKey, ComponentAddress) {
+        let (pub_key, priv_key) = self.new_secp256r1_key_pair();
+        let account = ComponentAddress::virtual_account_from_public_key(&PublicKey::Secp256r1(
+            pub_key.clone(),
+        ));
+        self.load_account_from_faucet(account);
+        (pub_key, priv_key, account)
+    }
+
     pub fn get_active_validator_info_by_key(&self, key: &Secp256k1PublicKey) -> ValidatorSubstate {
         let address = self.get_active_validator_with_key(key);
         self.get_validator_info(address)
@@ -1535,7 +1868,9 @@ impl<E: NativeVmExtension, D: TestDatabase> TestRunner<E, D> {
         network: &NetworkDefinition,
         raw_transaction: &RawNotarizedTransaction,
     ) -> TransactionReceipt {
-        let validator = NotarizedTransactionValidator::new(ValidationConfig::default(network.id));
+        let validator = NotarizedTransactionValidator::new(
+            ValidationConfig::default(network.id).with_secp256r1_signatures(),
+        );
         let validated = validator
             .validate_from_raw(&raw_transaction)
             .expect("Expected raw transaction to be valid");
@@ -2585,6 +2920,54 @@ impl<E: NativeVmExtension, D: TestDatabase> TestRunner<E, D> {
         event_checker.check_all_events(&self.database, self.collected_events())
     }
 
+    /// Writes the WASM profile collected so far into the `WASM_PROFILING_DIRECTORY`, as a
+    /// `<test name>.txt` report and a `<test name>.folded` stacks file.
+    ///
+    /// This runs when the test runner is dropped, so a failure to write is only logged.
+    #[cfg(feature = "wasm_profiling")]
+    pub fn save_wasm_profile(&self) {
+        use std::io::Write;
+
+        let (Some(wasm_profiler), Some(dir)) = (
+            &self.wasm_profiler,
+            std::env::var_os("WASM_PROFILING_DIRECTORY"),
+        ) else {
+            return;
+        };
+        let profile = wasm_profiler.take_profile();
+        if profile.execution_units.is_empty() {
+            return;
+        }
+
+        let mut original_code = SystemDatabaseReader::new(&self.database)
+            .find_package_original_code(&profile.code_hashes());
+        let report = profile.symbolize(|code_hash| original_code.swap_remove(code_hash));
+
+        // Tests run in threads named after them
+        let file_name = std::thread::current()
+            .name()
+            .unwrap_or("test_runner")
+            .replace("::", "-");
+        let file_path = Path::new(&dir).join(file_name);
+        // error is ignored because the directory may be created by another test
+        fs::create_dir_all(&dir).ok();
+        for (extension, content) in [
+            ("txt", format!("{}\n", report)),
+            ("folded", report.to_folded_stacks()),
+        ] {
+            // Appending, as a test may use more than one test runner
+            let path = file_path.with_extension(extension);
+            if let Err(err) = fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .and_then(|mut file| file.write_all(content.as_bytes()))
+            {
+                eprintln!("Failed to save WASM profile to {:?} - {:?}", path, err);
+            }
+        }
+    }
+
     pub fn check_database(&self) {
         let mut kernel_checker = KernelDatabaseChecker::new();
         kernel_checker
@@ -2830,9 +3213,11 @@ pub fn validate_notarized_transaction<'a>(
     network: &'a NetworkDefinition,
     transaction: &'a NotarizedTransactionV1,
 ) -> ValidatedNotarizedTransactionV1 {
-    NotarizedTransactionValidator::new(ValidationConfig::default(network.id))
-        .validate(transaction.prepare().unwrap())
-        .unwrap()
+    NotarizedTransactionValidator::new(
+        ValidationConfig::default(network.id).with_secp256r1_signatures(),
+    )
+    .validate(transaction.prepare().unwrap())
+    .unwrap()
 }
 
 pub fn assert_receipt_substate_changes_can_be_typed(commit_result: &CommitResult) {
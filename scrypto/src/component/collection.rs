@@ -0,0 +1,21 @@
+use radix_engine_interface::data::scrypto::ScryptoCustomTypeKind;
+use radix_engine_interface::schema::{BlueprintCollectionSchema, TypeRef};
+use sbor::{LocalTypeId, TypeAggregator};
+
+/// Marks an unbound collection, i.e. one which has not been attached to a component yet.
+pub(crate) const UNBOUND_COLLECTION_INDEX: u8 = u8::MAX;
+
+/// A collection which is stored in a partition of the component itself, rather than in
+/// a separate node, and which is declared as a field of the `#[blueprint]` state.
+///
+/// The `#[blueprint]` macro registers one collection schema per such field, in field
+/// order, and binds each field to its collection index when the component is instantiated.
+pub trait BlueprintCollection {
+    /// Returns the schema of this collection.
+    fn collection_schema(
+        aggregator: &mut TypeAggregator<ScryptoCustomTypeKind>,
+    ) -> BlueprintCollectionSchema<TypeRef<LocalTypeId>>;
+
+    /// Binds this collection to the collection with the given index.
+    fn bind(&mut self, collection_index: u8);
+}
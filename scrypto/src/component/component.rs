@@ -81,7 +81,9 @@ pub trait HasMethods {
 pub trait ComponentState: HasMethods + HasStub + ScryptoEncode + ScryptoDecode {
     const BLUEPRINT_NAME: &'static str;
 
-    fn instantiate(self) -> Owned<Self> {
+    fn instantiate(mut self) -> Owned<Self> {
+        self.init_collections();
+
         let node_id = ScryptoVmV1Api::object_new(
             Self::BLUEPRINT_NAME,
             indexmap![0u8 => FieldValue::new(&self)],
@@ -90,6 +92,10 @@ pub trait ComponentState: HasMethods + HasStub + ScryptoEncode + ScryptoDecode {
         let stub = Self::Stub::new(ObjectStubHandle::Own(Own(node_id)));
         Owned(stub)
     }
+
+    /// Binds the `BlueprintCollection` fields of the state to their collection indices.
+    #[doc(hidden)]
+    fn init_collections(&mut self) {}
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -0,0 +1,170 @@
+use super::collection::{BlueprintCollection, UNBOUND_COLLECTION_INDEX};
+use crate::engine::scrypto_env::ScryptoVmV1Api;
+use radix_engine_interface::api::ACTOR_STATE_SELF;
+use radix_engine_interface::data::scrypto::*;
+use radix_engine_interface::schema::{BlueprintCollectionSchema, BlueprintKeyValueSchema, TypeRef};
+use sbor::rust::marker::PhantomData;
+use sbor::rust::prelude::*;
+use sbor::*;
+
+/// A scalable map which is stored in an index partition of the component itself, and whose
+/// entries can be listed page by page.
+///
+/// It must be declared as a field of the `#[blueprint]` state, and can only be accessed from
+/// methods of the component once it has been instantiated. Entries are listed in an order which
+/// is stable but unspecified.
+pub struct IterableMap<
+    K: ScryptoEncode + ScryptoDecode + ScryptoDescribe,
+    V: ScryptoEncode + ScryptoDecode + ScryptoDescribe,
+> {
+    collection_index: u8,
+    key: PhantomData<K>,
+    value: PhantomData<V>,
+}
+
+impl<
+        K: ScryptoEncode + ScryptoDecode + ScryptoDescribe,
+        V: ScryptoEncode + ScryptoDecode + ScryptoDescribe,
+    > IterableMap<K, V>
+{
+    /// Creates a new iterable map, to be bound when the component is instantiated.
+    pub fn new() -> Self {
+        Self {
+            collection_index: UNBOUND_COLLECTION_INDEX,
+            key: PhantomData,
+            value: PhantomData,
+        }
+    }
+
+    /// Returns the value that is associated with the given key.
+    pub fn get(&self, key: &K) -> Option<V> {
+        let key_payload = scrypto_encode(key).unwrap();
+        ScryptoVmV1Api::actor_index_get(ACTOR_STATE_SELF, self.collection_index(), key_payload)
+            .map(|value| scrypto_decode(&value).unwrap())
+    }
+
+    /// Inserts a new key-value pair into this map, replacing any existing value.
+    pub fn insert(&self, key: K, value: V) {
+        let key_payload = scrypto_encode(&key).unwrap();
+        let value_payload = scrypto_encode(&value).unwrap();
+        ScryptoVmV1Api::actor_index_insert(
+            ACTOR_STATE_SELF,
+            self.collection_index(),
+            key_payload,
+            value_payload,
+        );
+    }
+
+    /// Remove an entry from the map and return the original value if it exists
+    pub fn remove(&self, key: &K) -> Option<V> {
+        let key_payload = scrypto_encode(key).unwrap();
+        ScryptoVmV1Api::actor_index_remove(ACTOR_STATE_SELF, self.collection_index(), key_payload)
+            .map(|value| scrypto_decode(&value).unwrap())
+    }
+
+    /// Returns up to `limit` entries, starting after the given key, or from the first entry
+    /// if `after` is `None`.
+    ///
+    /// Pass the key of the last returned entry to get the next page.
+    pub fn scan(&self, after: Option<&K>, limit: u32) -> Vec<(K, V)> {
+        let after = after.map(|key| scrypto_encode(key).unwrap());
+        ScryptoVmV1Api::actor_index_scan(ACTOR_STATE_SELF, self.collection_index(), after, limit)
+            .into_iter()
+            .map(|(key, value)| {
+                (
+                    scrypto_decode(&key).unwrap(),
+                    scrypto_decode(&value).unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    fn collection_index(&self) -> u8 {
+        if self.collection_index == UNBOUND_COLLECTION_INDEX {
+            panic!("IterableMap is not bound to a component");
+        }
+        self.collection_index
+    }
+}
+
+impl<
+        K: ScryptoEncode + ScryptoDecode + ScryptoDescribe,
+        V: ScryptoEncode + ScryptoDecode + ScryptoDescribe,
+    > BlueprintCollection for IterableMap<K, V>
+{
+    fn collection_schema(
+        aggregator: &mut TypeAggregator<ScryptoCustomTypeKind>,
+    ) -> BlueprintCollectionSchema<TypeRef<LocalTypeId>> {
+        BlueprintCollectionSchema::Index(BlueprintKeyValueSchema {
+            key: TypeRef::Static(aggregator.add_child_type_and_descendents::<K>()),
+            value: TypeRef::Static(aggregator.add_child_type_and_descendents::<V>()),
+            allow_ownership: false,
+        })
+    }
+
+    fn bind(&mut self, collection_index: u8) {
+        self.collection_index = collection_index;
+    }
+}
+
+//========
+// binary
+//========
+impl<
+        K: ScryptoEncode + ScryptoDecode + ScryptoDescribe,
+        V: ScryptoEncode + ScryptoDecode + ScryptoDescribe,
+    > Categorize<ScryptoCustomValueKind> for IterableMap<K, V>
+{
+    #[inline]
+    fn value_kind() -> ValueKind<ScryptoCustomValueKind> {
+        ValueKind::U8
+    }
+}
+
+impl<
+        K: ScryptoEncode + ScryptoDecode + ScryptoDescribe,
+        V: ScryptoEncode + ScryptoDecode + ScryptoDescribe,
+        E: Encoder<ScryptoCustomValueKind>,
+    > Encode<ScryptoCustomValueKind, E> for IterableMap<K, V>
+{
+    #[inline]
+    fn encode_value_kind(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        encoder.write_value_kind(Self::value_kind())
+    }
+
+    #[inline]
+    fn encode_body(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.collection_index.encode_body(encoder)
+    }
+}
+
+impl<
+        K: ScryptoEncode + ScryptoDecode + ScryptoDescribe,
+        V: ScryptoEncode + ScryptoDecode + ScryptoDescribe,
+        D: Decoder<ScryptoCustomValueKind>,
+    > Decode<ScryptoCustomValueKind, D> for IterableMap<K, V>
+{
+    fn decode_body_with_value_kind(
+        decoder: &mut D,
+        value_kind: ValueKind<ScryptoCustomValueKind>,
+    ) -> Result<Self, DecodeError> {
+        let collection_index = u8::decode_body_with_value_kind(decoder, value_kind)?;
+        Ok(Self {
+            collection_index,
+            key: PhantomData,
+            value: PhantomData,
+        })
+    }
+}
+
+impl<
+        K: ScryptoEncode + ScryptoDecode + ScryptoDescribe,
+        V: ScryptoEncode + ScryptoDecode + ScryptoDescribe,
+    > Describe<ScryptoCustomTypeKind> for IterableMap<K, V>
+{
+    const TYPE_ID: RustTypeId = <u8 as Describe<ScryptoCustomTypeKind>>::TYPE_ID;
+
+    fn type_data() -> sbor::TypeData<ScryptoCustomTypeKind, RustTypeId> {
+        <u8 as Describe<ScryptoCustomTypeKind>>::type_data()
+    }
+}
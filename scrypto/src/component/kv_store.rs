@@ -11,6 +11,7 @@ use radix_engine_interface::prelude::{
     LocalKeyValueStoreDataSchema, KV_STORE_DATA_SCHEMA_VARIANT_LOCAL,
 };
 use sbor::rust::marker::PhantomData;
+use sbor::rust::prelude::*;
 use sbor::*;
 
 /// A scalable key-value map which loads entries on demand.
@@ -115,6 +116,37 @@ impl<
 
         scrypto_decode(&rtn).unwrap()
     }
+
+    /// Scans up to `limit` entries of this map, starting after the given key, or from the first
+    /// entry if `after` is `None`. Entries are scanned in an unspecified but stable order.
+    ///
+    /// Returns the scanned entries which hold a value, along with the key to pass as `after`
+    /// to continue the scan, which is `None` once every entry has been scanned.
+    pub fn scan(&self, after: Option<&K>, limit: u32) -> (Vec<(K, V)>, Option<K>) {
+        let after = after.map(|key| scrypto_encode(key).unwrap());
+        let (entries, next) = ScryptoVmV1Api::kv_store_scan(self.id.as_node_id(), after, limit);
+
+        let entries = entries
+            .into_iter()
+            .map(|(key, value)| {
+                (
+                    scrypto_decode(&key).unwrap(),
+                    scrypto_decode(&value).unwrap(),
+                )
+            })
+            .collect();
+        let next = next.map(|key| scrypto_decode(&key).unwrap());
+
+        (entries, next)
+    }
+
+    /// Returns the number of entries in this map, counting at most `limit` of them.
+    ///
+    /// Entries are only read until `limit` of them have been counted, so the cost of this call
+    /// is bounded by `limit` rather than the size of the map.
+    pub fn count(&self, limit: u32) -> u32 {
+        ScryptoVmV1Api::kv_store_count(self.id.as_node_id(), limit)
+    }
 }
 
 //========
@@ -1,13 +1,19 @@
+mod collection;
 mod component;
+mod iterable_map;
 mod kv_store;
 mod kv_store_data_ref;
 mod object;
 mod package;
+mod sorted_map;
 mod stubs;
 
+pub use collection::*;
 pub use component::*;
+pub use iterable_map::*;
 pub use kv_store::*;
 pub use kv_store_data_ref::*;
 pub use object::*;
 pub use package::*;
+pub use sorted_map::*;
 pub use stubs::*;
@@ -2,9 +2,17 @@ use super::HasTypeInfo;
 use crate::prelude::{Global, HasStub, ObjectStub, ObjectStubHandle};
 use radix_engine_common::prelude::PACKAGE_PACKAGE;
 use radix_engine_interface::blueprints::package::{
-    PackageClaimRoyaltiesInput, PACKAGE_BLUEPRINT, PACKAGE_CLAIM_ROYALTIES_IDENT,
+    BlueprintDefinitionInit, BlueprintVersion, PackageCancelBlueprintUpgradeInput,
+    PackageClaimRoyaltiesInput, PackageClaimRoyaltyShareInput,
+    PackageConfigureBlueprintUpgradesInput, PackageExecuteBlueprintUpgradeInput,
+    PackageLockRoyaltySplitInput, PackageProposeBlueprintUpgradeInput, PackageSetRoyaltySplitInput,
+    PACKAGE_BLUEPRINT, PACKAGE_CANCEL_BLUEPRINT_UPGRADE_IDENT, PACKAGE_CLAIM_ROYALTIES_IDENT,
+    PACKAGE_CLAIM_ROYALTY_SHARE_IDENT, PACKAGE_CONFIGURE_BLUEPRINT_UPGRADES_IDENT,
+    PACKAGE_EXECUTE_BLUEPRINT_UPGRADE_IDENT, PACKAGE_LOCK_ROYALTY_SPLIT_IDENT,
+    PACKAGE_PROPOSE_BLUEPRINT_UPGRADE_IDENT, PACKAGE_SET_ROYALTY_SPLIT_IDENT,
 };
 use radix_engine_interface::blueprints::resource::Bucket;
+use radix_engine_interface::math::Decimal;
 use radix_engine_interface::types::*;
 use radix_engine_interface::*;
 use sbor::rust::prelude::*;
@@ -47,6 +55,84 @@ impl PackageStub {
             &PackageClaimRoyaltiesInput {},
         )
     }
+
+    pub fn configure_blueprint_upgrades<S: ToString>(
+        &self,
+        blueprint_name: S,
+        timelock_in_minutes: u32,
+    ) {
+        self.call(
+            PACKAGE_CONFIGURE_BLUEPRINT_UPGRADES_IDENT,
+            &PackageConfigureBlueprintUpgradesInput {
+                blueprint_name: blueprint_name.to_string(),
+                timelock_in_minutes,
+            },
+        )
+    }
+
+    pub fn propose_blueprint_upgrade<S: ToString>(
+        &self,
+        blueprint_name: S,
+        definition: BlueprintDefinitionInit,
+        code: Vec<u8>,
+    ) {
+        self.call(
+            PACKAGE_PROPOSE_BLUEPRINT_UPGRADE_IDENT,
+            &PackageProposeBlueprintUpgradeInput {
+                blueprint_name: blueprint_name.to_string(),
+                definition,
+                code,
+            },
+        )
+    }
+
+    pub fn execute_blueprint_upgrade<S: ToString>(&self, blueprint_name: S) -> BlueprintVersion {
+        self.call(
+            PACKAGE_EXECUTE_BLUEPRINT_UPGRADE_IDENT,
+            &PackageExecuteBlueprintUpgradeInput {
+                blueprint_name: blueprint_name.to_string(),
+            },
+        )
+    }
+
+    pub fn cancel_blueprint_upgrade<S: ToString>(&self, blueprint_name: S) {
+        self.call(
+            PACKAGE_CANCEL_BLUEPRINT_UPGRADE_IDENT,
+            &PackageCancelBlueprintUpgradeInput {
+                blueprint_name: blueprint_name.to_string(),
+            },
+        )
+    }
+
+    pub fn set_royalty_split<S: ToString>(
+        &self,
+        blueprint_name: S,
+        recipients: IndexMap<GlobalAddress, Decimal>,
+    ) {
+        self.call(
+            PACKAGE_SET_ROYALTY_SPLIT_IDENT,
+            &PackageSetRoyaltySplitInput {
+                blueprint_name: blueprint_name.to_string(),
+                recipients,
+            },
+        )
+    }
+
+    pub fn lock_royalty_split<S: ToString>(&self, blueprint_name: S) {
+        self.call(
+            PACKAGE_LOCK_ROYALTY_SPLIT_IDENT,
+            &PackageLockRoyaltySplitInput {
+                blueprint_name: blueprint_name.to_string(),
+            },
+        )
+    }
+
+    pub fn claim_royalty_share(&self, recipient: GlobalAddress) -> Option<Bucket> {
+        self.call(
+            PACKAGE_CLAIM_ROYALTY_SHARE_IDENT,
+            &PackageClaimRoyaltyShareInput { recipient },
+        )
+    }
 }
 
 impl From<PackageAddress> for Package {
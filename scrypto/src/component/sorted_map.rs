@@ -0,0 +1,176 @@
+use super::collection::{BlueprintCollection, UNBOUND_COLLECTION_INDEX};
+use crate::engine::scrypto_env::ScryptoVmV1Api;
+use radix_engine_interface::api::ACTOR_STATE_SELF;
+use radix_engine_interface::data::scrypto::*;
+use radix_engine_interface::schema::{BlueprintCollectionSchema, BlueprintKeyValueSchema, TypeRef};
+use sbor::rust::marker::PhantomData;
+use sbor::rust::prelude::*;
+use sbor::*;
+
+/// A scalable map which is stored in a sorted index partition of the component itself, and
+/// whose entries are keyed by a `u16` sort prefix plus a key.
+///
+/// It must be declared as a field of the `#[blueprint]` state, and can only be accessed from
+/// methods of the component once it has been instantiated. Entries are listed in ascending
+/// sort prefix order; the order of entries sharing the same sort prefix is unspecified.
+pub struct SortedMap<
+    K: ScryptoEncode + ScryptoDecode + ScryptoDescribe,
+    V: ScryptoEncode + ScryptoDecode + ScryptoDescribe,
+> {
+    collection_index: u8,
+    key: PhantomData<K>,
+    value: PhantomData<V>,
+}
+
+impl<
+        K: ScryptoEncode + ScryptoDecode + ScryptoDescribe,
+        V: ScryptoEncode + ScryptoDecode + ScryptoDescribe,
+    > SortedMap<K, V>
+{
+    /// Creates a new sorted map, to be bound when the component is instantiated.
+    pub fn new() -> Self {
+        Self {
+            collection_index: UNBOUND_COLLECTION_INDEX,
+            key: PhantomData,
+            value: PhantomData,
+        }
+    }
+
+    /// Inserts a new entry into this map, replacing any existing value with the same
+    /// sort prefix and key.
+    pub fn insert(&self, sort_prefix: u16, key: K, value: V) {
+        let key_payload = scrypto_encode(&key).unwrap();
+        let value_payload = scrypto_encode(&value).unwrap();
+        ScryptoVmV1Api::actor_sorted_index_insert(
+            ACTOR_STATE_SELF,
+            self.collection_index(),
+            sort_prefix,
+            key_payload,
+            value_payload,
+        );
+    }
+
+    /// Remove an entry from the map and return the original value if it exists
+    pub fn remove(&self, sort_prefix: u16, key: &K) -> Option<V> {
+        let key_payload = scrypto_encode(key).unwrap();
+        ScryptoVmV1Api::actor_sorted_index_remove(
+            ACTOR_STATE_SELF,
+            self.collection_index(),
+            sort_prefix,
+            key_payload,
+        )
+        .map(|value| scrypto_decode(&value).unwrap())
+    }
+
+    /// Returns up to `limit` entries in ascending sort prefix order, starting after the given
+    /// sort prefix and key, or from the first entry if `after` is `None`.
+    ///
+    /// Pass the sort prefix and key of the last returned entry to get the next page.
+    pub fn scan(&self, after: Option<(u16, &K)>, limit: u32) -> Vec<(u16, K, V)> {
+        let after = after.map(|(sort_prefix, key)| (sort_prefix, scrypto_encode(key).unwrap()));
+        ScryptoVmV1Api::actor_sorted_index_scan(
+            ACTOR_STATE_SELF,
+            self.collection_index(),
+            after,
+            limit,
+        )
+        .into_iter()
+        .map(|((sort_prefix, key), value)| {
+            (
+                sort_prefix,
+                scrypto_decode(&key).unwrap(),
+                scrypto_decode(&value).unwrap(),
+            )
+        })
+        .collect()
+    }
+
+    fn collection_index(&self) -> u8 {
+        if self.collection_index == UNBOUND_COLLECTION_INDEX {
+            panic!("SortedMap is not bound to a component");
+        }
+        self.collection_index
+    }
+}
+
+impl<
+        K: ScryptoEncode + ScryptoDecode + ScryptoDescribe,
+        V: ScryptoEncode + ScryptoDecode + ScryptoDescribe,
+    > BlueprintCollection for SortedMap<K, V>
+{
+    fn collection_schema(
+        aggregator: &mut TypeAggregator<ScryptoCustomTypeKind>,
+    ) -> BlueprintCollectionSchema<TypeRef<LocalTypeId>> {
+        BlueprintCollectionSchema::SortedIndex(BlueprintKeyValueSchema {
+            key: TypeRef::Static(aggregator.add_child_type_and_descendents::<K>()),
+            value: TypeRef::Static(aggregator.add_child_type_and_descendents::<V>()),
+            allow_ownership: false,
+        })
+    }
+
+    fn bind(&mut self, collection_index: u8) {
+        self.collection_index = collection_index;
+    }
+}
+
+//========
+// binary
+//========
+impl<
+        K: ScryptoEncode + ScryptoDecode + ScryptoDescribe,
+        V: ScryptoEncode + ScryptoDecode + ScryptoDescribe,
+    > Categorize<ScryptoCustomValueKind> for SortedMap<K, V>
+{
+    #[inline]
+    fn value_kind() -> ValueKind<ScryptoCustomValueKind> {
+        ValueKind::U8
+    }
+}
+
+impl<
+        K: ScryptoEncode + ScryptoDecode + ScryptoDescribe,
+        V: ScryptoEncode + ScryptoDecode + ScryptoDescribe,
+        E: Encoder<ScryptoCustomValueKind>,
+    > Encode<ScryptoCustomValueKind, E> for SortedMap<K, V>
+{
+    #[inline]
+    fn encode_value_kind(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        encoder.write_value_kind(Self::value_kind())
+    }
+
+    #[inline]
+    fn encode_body(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.collection_index.encode_body(encoder)
+    }
+}
+
+impl<
+        K: ScryptoEncode + ScryptoDecode + ScryptoDescribe,
+        V: ScryptoEncode + ScryptoDecode + ScryptoDescribe,
+        D: Decoder<ScryptoCustomValueKind>,
+    > Decode<ScryptoCustomValueKind, D> for SortedMap<K, V>
+{
+    fn decode_body_with_value_kind(
+        decoder: &mut D,
+        value_kind: ValueKind<ScryptoCustomValueKind>,
+    ) -> Result<Self, DecodeError> {
+        let collection_index = u8::decode_body_with_value_kind(decoder, value_kind)?;
+        Ok(Self {
+            collection_index,
+            key: PhantomData,
+            value: PhantomData,
+        })
+    }
+}
+
+impl<
+        K: ScryptoEncode + ScryptoDecode + ScryptoDescribe,
+        V: ScryptoEncode + ScryptoDecode + ScryptoDescribe,
+    > Describe<ScryptoCustomTypeKind> for SortedMap<K, V>
+{
+    const TYPE_ID: RustTypeId = <u8 as Describe<ScryptoCustomTypeKind>>::TYPE_ID;
+
+    fn type_data() -> sbor::TypeData<ScryptoCustomTypeKind, RustTypeId> {
+        <u8 as Describe<ScryptoCustomTypeKind>>::type_data()
+    }
+}
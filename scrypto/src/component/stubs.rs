@@ -1,6 +1,7 @@
 use radix_engine_interface::blueprints::access_controller::*;
 use radix_engine_interface::blueprints::account::*;
 use radix_engine_interface::blueprints::consensus_manager::*;
+use radix_engine_interface::blueprints::pool::ConstantProductPoolOracleState;
 
 use crate::prelude::*;
 
@@ -92,6 +93,16 @@ extern_blueprint_internal! {
         fn stake_as_owner(&mut self, stake: Bucket) -> Bucket;
         fn stake(&mut self, stake: Bucket) -> Bucket;
         fn unstake(&mut self, stake_unit_bucket: Bucket) -> Bucket;
+        fn redelegate(
+            &mut self,
+            stake_unit_bucket: Bucket,
+            target_validator: ComponentAddress,
+        ) -> Bucket;
+        fn receive_redelegation(
+            &mut self,
+            xrd_bucket: Bucket,
+            source_validator: ComponentAddress,
+        ) -> Bucket;
         fn claim_xrd(&mut self, bucket: Bucket) -> Bucket;
         fn update_key(&mut self, key: Secp256k1PublicKey);
         fn update_fee(&mut self, new_fee_factor: Decimal);
@@ -217,6 +228,26 @@ extern_blueprint_internal! {
         );
         fn add_authorized_depositor(&mut self, badge: ResourceOrNonFungible);
         fn remove_authorized_depositor(&mut self, badge: ResourceOrNonFungible);
+        fn set_allowance(
+            &mut self,
+            grantee: AllowanceGrantee,
+            resource_address: ResourceAddress,
+            amount: Option<Decimal>,
+            reset_period: Option<u64>,
+            expiry_epoch: Option<Epoch>,
+        );
+        fn remove_allowance(&mut self, grantee: AllowanceGrantee, resource_address: ResourceAddress);
+        fn get_allowance(
+            &self,
+            grantee: AllowanceGrantee,
+            resource_address: ResourceAddress,
+        ) -> Option<Allowance>;
+        fn withdraw_with_allowance(
+            &mut self,
+            grantee: AllowanceGrantee,
+            resource_address: ResourceAddress,
+            amount: Decimal,
+        ) -> Bucket;
     }
 }
 
@@ -321,6 +352,50 @@ extern_blueprint_internal! {
         fn get_vault_amounts(&self) -> IndexMap<ResourceAddress, Decimal>;
     }
 }
+extern_blueprint_internal! {
+    PackageAddress::new_or_panic([
+        13u8, 144u8, 99u8, 24u8, 198u8, 49u8, 140u8, 96u8, 252u8, 198u8, 49u8, 140u8, 99u8, 24u8,
+        198u8, 49u8, 140u8, 245u8, 62u8, 62u8, 42u8, 148u8, 250u8, 42u8, 166u8, 49u8, 140u8, 99u8,
+        24u8, 198u8,
+    ]),
+    ConstantProductPool,
+    "ConstantProductPool",
+    "OwnedConstantProductPool",
+    "GlobalConstantProductPool",
+    ConstantProductPoolFunctions {
+        fn instantiate(
+            owner_role: OwnerRole,
+            fee_manager_rule: AccessRule,
+            resource_addresses: (ResourceAddress, ResourceAddress),
+            fee_rate: Decimal,
+            address_reservation: Option<GlobalAddressReservation>,
+        ) -> Global<ConstantProductPool>;
+    },
+    {
+        fn contribute(&mut self, buckets: (Bucket, Bucket)) -> (Bucket, Option<Bucket>);
+        fn redeem(&mut self, bucket: Bucket) -> (Bucket, Bucket);
+        fn swap(&mut self, input_bucket: Bucket, minimum_output_amount: Decimal) -> Bucket;
+        fn swap_for_exact_output(
+            &mut self,
+            input_bucket: Bucket,
+            output_amount: Decimal,
+        ) -> (Bucket, Bucket);
+        fn get_swap_output_amount(
+            &self,
+            input_resource_address: ResourceAddress,
+            input_amount: Decimal,
+        ) -> Decimal;
+        fn get_swap_input_amount(
+            &self,
+            output_resource_address: ResourceAddress,
+            output_amount: Decimal,
+        ) -> Decimal;
+        fn set_fee_rate(&mut self, fee_rate: Decimal);
+        fn get_fee_rate(&self) -> Decimal;
+        fn get_oracle_state(&self) -> ConstantProductPoolOracleState;
+        fn get_vault_amounts(&self) -> IndexMap<ResourceAddress, Decimal>;
+    }
+}
 
 extern_blueprint_internal! {
     PackageAddress::new_or_panic([
@@ -383,5 +458,8 @@ extern_blueprint_internal! {
         fn cancel_primary_role_badge_withdraw_attempt(&mut self);
         fn cancel_recovery_role_badge_withdraw_attempt(&mut self);
         fn mint_recovery_badges(&mut self, non_fungible_local_ids: Vec<NonFungibleLocalId>) -> Bucket;
+        fn contribute_recovery_fee(&mut self, bucket: Bucket);
+        fn lock_recovery_fee(&mut self, amount: Decimal);
+        fn withdraw_recovery_fee(&mut self, amount: Decimal) -> Bucket;
     }
 }
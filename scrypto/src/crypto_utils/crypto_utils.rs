@@ -1,6 +1,7 @@
 use crate::engine::wasm_api::{copy_buffer, crypto_utils};
 use radix_engine_common::prelude::{
-    scrypto_decode, scrypto_encode, Bls12381G1PublicKey, Bls12381G2Signature, Hash,
+    scrypto_decode, scrypto_encode, Bls12381G1PublicKey, Bls12381G2Signature, Ed25519PublicKey,
+    Ed25519Signature, Hash, Secp256k1PublicKey, Secp256k1Signature,
 };
 use sbor::prelude::Vec;
 
@@ -94,4 +95,75 @@ impl CryptoUtils {
 
         Hash(hash.try_into().unwrap())
     }
+
+    /// Calculates Blake2b-256 digest over given vector of bytes
+    pub fn blake2b_256_hash(data: Vec<u8>) -> Hash {
+        let hash = copy_buffer(unsafe {
+            crypto_utils::crypto_utils_blake2b_256_hash(data.as_ptr(), data.len())
+        });
+
+        Hash(hash.try_into().unwrap())
+    }
+
+    /// Performs Ed25519 signature verification of the given message.
+    pub fn ed25519_verify(
+        message: Vec<u8>,
+        public_key: Ed25519PublicKey,
+        signature: Ed25519Signature,
+    ) -> bool {
+        let public_key: Vec<u8> = scrypto_encode(&public_key).unwrap();
+        let signature: Vec<u8> = scrypto_encode(&signature).unwrap();
+        unsafe {
+            crypto_utils::crypto_utils_ed25519_verify(
+                message.as_ptr(),
+                message.len(),
+                public_key.as_ptr(),
+                public_key.len(),
+                signature.as_ptr(),
+                signature.len(),
+            ) != 0
+        }
+    }
+
+    /// Performs ECDSA Secp256k1 signature verification of the given 32-byte hash.
+    pub fn secp256k1_ecdsa_verify(
+        hash: Hash,
+        public_key: Secp256k1PublicKey,
+        signature: Secp256k1Signature,
+    ) -> bool {
+        let hash: Vec<u8> = scrypto_encode(&hash).unwrap();
+        let public_key: Vec<u8> = scrypto_encode(&public_key).unwrap();
+        let signature: Vec<u8> = scrypto_encode(&signature).unwrap();
+        unsafe {
+            crypto_utils::crypto_utils_secp256k1_ecdsa_verify(
+                hash.as_ptr(),
+                hash.len(),
+                public_key.as_ptr(),
+                public_key.len(),
+                signature.as_ptr(),
+                signature.len(),
+            ) != 0
+        }
+    }
+
+    /// Recovers the Secp256k1 public key which signed the given 32-byte hash.
+    /// The signature is expected in the recoverable `v + r + s` format.
+    /// The transaction fails if no public key can be recovered from the signature.
+    pub fn secp256k1_ecdsa_verify_and_key_recover(
+        hash: Hash,
+        signature: Secp256k1Signature,
+    ) -> Secp256k1PublicKey {
+        let hash: Vec<u8> = scrypto_encode(&hash).unwrap();
+        let signature: Vec<u8> = scrypto_encode(&signature).unwrap();
+        let public_key = copy_buffer(unsafe {
+            crypto_utils::crypto_utils_secp256k1_ecdsa_verify_and_key_recover(
+                hash.as_ptr(),
+                hash.len(),
+                signature.as_ptr(),
+                signature.len(),
+            )
+        });
+
+        scrypto_decode::<Secp256k1PublicKey>(&public_key).unwrap()
+    }
 }
@@ -194,6 +194,31 @@ impl ScryptoVmV1Api {
         removed
     }
 
+    pub fn kv_store_scan(
+        node_id: &NodeId,
+        after: Option<Vec<u8>>,
+        limit: u32,
+    ) -> (Vec<(Vec<u8>, Vec<u8>)>, Option<Vec<u8>>) {
+        let cursor = scrypto_encode(&after).unwrap();
+        let scan = copy_buffer(unsafe {
+            kv_store::kv_store_scan(
+                node_id.as_ref().as_ptr(),
+                node_id.as_ref().len(),
+                cursor.as_ptr(),
+                cursor.len(),
+                limit,
+            )
+        });
+
+        scrypto_decode(&scan).unwrap()
+    }
+
+    pub fn kv_store_count(node_id: &NodeId, limit: u32) -> u32 {
+        unsafe {
+            kv_store::kv_store_count(node_id.as_ref().as_ptr(), node_id.as_ref().len(), limit)
+        }
+    }
+
     pub fn actor_open_field(object_handle: u32, field: u8, flags: LockFlags) -> SubstateHandle {
         let handle =
             unsafe { actor::actor_open_field(object_handle, u32::from(field), flags.bits()) };
@@ -230,6 +255,137 @@ impl ScryptoVmV1Api {
         };
     }
 
+    pub fn actor_index_insert(
+        object_handle: u32,
+        collection_index: u8,
+        key: Vec<u8>,
+        value: Vec<u8>,
+    ) {
+        unsafe {
+            actor::actor_index_insert(
+                object_handle,
+                u32::from(collection_index),
+                key.as_ptr(),
+                key.len(),
+                value.as_ptr(),
+                value.len(),
+            )
+        };
+    }
+
+    pub fn actor_index_remove(
+        object_handle: u32,
+        collection_index: u8,
+        key: Vec<u8>,
+    ) -> Option<Vec<u8>> {
+        let value = copy_buffer(unsafe {
+            actor::actor_index_remove(
+                object_handle,
+                u32::from(collection_index),
+                key.as_ptr(),
+                key.len(),
+            )
+        });
+
+        scrypto_decode(&value).unwrap()
+    }
+
+    pub fn actor_index_get(
+        object_handle: u32,
+        collection_index: u8,
+        key: Vec<u8>,
+    ) -> Option<Vec<u8>> {
+        let value = copy_buffer(unsafe {
+            actor::actor_index_get(
+                object_handle,
+                u32::from(collection_index),
+                key.as_ptr(),
+                key.len(),
+            )
+        });
+
+        scrypto_decode(&value).unwrap()
+    }
+
+    pub fn actor_index_scan(
+        object_handle: u32,
+        collection_index: u8,
+        after: Option<Vec<u8>>,
+        limit: u32,
+    ) -> Vec<(Vec<u8>, Vec<u8>)> {
+        let cursor = scrypto_encode(&after).unwrap();
+        let entries = copy_buffer(unsafe {
+            actor::actor_index_scan(
+                object_handle,
+                u32::from(collection_index),
+                cursor.as_ptr(),
+                cursor.len(),
+                limit,
+            )
+        });
+
+        scrypto_decode(&entries).unwrap()
+    }
+
+    pub fn actor_sorted_index_insert(
+        object_handle: u32,
+        collection_index: u8,
+        sort_prefix: u16,
+        key: Vec<u8>,
+        value: Vec<u8>,
+    ) {
+        unsafe {
+            actor::actor_sorted_index_insert(
+                object_handle,
+                u32::from(collection_index),
+                u32::from(sort_prefix),
+                key.as_ptr(),
+                key.len(),
+                value.as_ptr(),
+                value.len(),
+            )
+        };
+    }
+
+    pub fn actor_sorted_index_remove(
+        object_handle: u32,
+        collection_index: u8,
+        sort_prefix: u16,
+        key: Vec<u8>,
+    ) -> Option<Vec<u8>> {
+        let value = copy_buffer(unsafe {
+            actor::actor_sorted_index_remove(
+                object_handle,
+                u32::from(collection_index),
+                u32::from(sort_prefix),
+                key.as_ptr(),
+                key.len(),
+            )
+        });
+
+        scrypto_decode(&value).unwrap()
+    }
+
+    pub fn actor_sorted_index_scan(
+        object_handle: u32,
+        collection_index: u8,
+        after: Option<(u16, Vec<u8>)>,
+        limit: u32,
+    ) -> Vec<((u16, Vec<u8>), Vec<u8>)> {
+        let cursor = scrypto_encode(&after).unwrap();
+        let entries = copy_buffer(unsafe {
+            actor::actor_sorted_index_scan(
+                object_handle,
+                u32::from(collection_index),
+                cursor.as_ptr(),
+                cursor.len(),
+                limit,
+            )
+        });
+
+        scrypto_decode(&entries).unwrap()
+    }
+
     pub fn field_entry_read(lock_handle: SubstateHandle) -> Vec<u8> {
         copy_buffer(unsafe { field_entry::field_entry_read(lock_handle) })
     }
@@ -173,6 +173,72 @@ pub mod actor {
             event_data_len: usize,
             event_flags: u32,
         );
+
+        /// Inserts an entry into an index collection of the current actor
+        pub fn actor_index_insert(
+            object_handle: u32,
+            collection_index: u32,
+            key_ptr: *const u8,
+            key_len: usize,
+            value_ptr: *const u8,
+            value_len: usize,
+        );
+
+        /// Removes an entry from an index collection of the current actor
+        pub fn actor_index_remove(
+            object_handle: u32,
+            collection_index: u32,
+            key_ptr: *const u8,
+            key_len: usize,
+        ) -> Buffer;
+
+        /// Reads an entry of an index collection of the current actor
+        pub fn actor_index_get(
+            object_handle: u32,
+            collection_index: u32,
+            key_ptr: *const u8,
+            key_len: usize,
+        ) -> Buffer;
+
+        /// Lists up to `limit` entries of an index collection of the current actor, starting
+        /// after the key given by the cursor
+        pub fn actor_index_scan(
+            object_handle: u32,
+            collection_index: u32,
+            cursor_ptr: *const u8,
+            cursor_len: usize,
+            limit: u32,
+        ) -> Buffer;
+
+        /// Inserts an entry into a sorted index collection of the current actor
+        pub fn actor_sorted_index_insert(
+            object_handle: u32,
+            collection_index: u32,
+            sort_prefix: u32,
+            key_ptr: *const u8,
+            key_len: usize,
+            value_ptr: *const u8,
+            value_len: usize,
+        );
+
+        /// Removes an entry from a sorted index collection of the current actor
+        pub fn actor_sorted_index_remove(
+            object_handle: u32,
+            collection_index: u32,
+            sort_prefix: u32,
+            key_ptr: *const u8,
+            key_len: usize,
+        ) -> Buffer;
+
+        /// Lists up to `limit` entries of a sorted index collection of the current actor, in
+        /// ascending sort prefix order, starting after the sorted key given by the cursor
+        pub fn actor_sorted_index_scan(
+            object_handle: u32,
+            collection_index: u32,
+            cursor_ptr: *const u8,
+            cursor_len: usize,
+            limit: u32,
+        ) -> Buffer;
     }
 }
 
@@ -199,6 +265,23 @@ pub mod kv_store {
             key: *const u8,
             key_len: usize,
         ) -> Buffer;
+
+        /// Scans up to `limit` entries of a key value store, starting after the key given by
+        /// the cursor
+        pub fn kv_store_scan(
+            key_value_store_id_ptr: *const u8,
+            key_value_store_id_len: usize,
+            cursor_ptr: *const u8,
+            cursor_len: usize,
+            limit: u32,
+        ) -> Buffer;
+
+        /// Counts the non-empty entries of a key value store, up to the given limit
+        pub fn kv_store_count(
+            key_value_store_id_ptr: *const u8,
+            key_value_store_id_len: usize,
+            limit: u32,
+        ) -> u32;
     }
 }
 
@@ -318,6 +401,32 @@ pub mod crypto_utils {
         pub fn crypto_utils_keccak256_hash(
             message_ptr: *const u8,
             message_len: usize) -> Buffer;
+
+        pub fn crypto_utils_blake2b_256_hash(
+            message_ptr: *const u8,
+            message_len: usize) -> Buffer;
+
+        pub fn crypto_utils_ed25519_verify(
+            message_ptr: *const u8,
+            message_len: usize,
+            public_key_ptr: *const u8,
+            public_key_len: usize,
+            signature_ptr: *const u8,
+            signature_len: usize) -> u32;
+
+        pub fn crypto_utils_secp256k1_ecdsa_verify(
+            hash_ptr: *const u8,
+            hash_len: usize,
+            public_key_ptr: *const u8,
+            public_key_len: usize,
+            signature_ptr: *const u8,
+            signature_len: usize) -> u32;
+
+        pub fn crypto_utils_secp256k1_ecdsa_verify_and_key_recover(
+            hash_ptr: *const u8,
+            hash_len: usize,
+            signature_ptr: *const u8,
+            signature_len: usize) -> Buffer;
     }
 }
 
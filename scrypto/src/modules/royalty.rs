@@ -4,19 +4,23 @@ use crate::runtime::*;
 use crate::*;
 use radix_engine_common::types::RoyaltyAmount;
 use radix_engine_interface::api::node_modules::royalty::{
-    ComponentClaimRoyaltiesInput, ComponentRoyaltyCreateInput, ComponentRoyaltyLockInput,
-    ComponentRoyaltySetInput, COMPONENT_ROYALTY_BLUEPRINT, COMPONENT_ROYALTY_CLAIMER_ROLE,
+    ComponentClaimRoyaltiesInput, ComponentClaimRoyaltyShareInput, ComponentRoyaltyCreateInput,
+    ComponentRoyaltyLockInput, ComponentRoyaltyLockSplitInput, ComponentRoyaltySetInput,
+    ComponentRoyaltySetSplitInput, COMPONENT_ROYALTY_BLUEPRINT, COMPONENT_ROYALTY_CLAIMER_ROLE,
     COMPONENT_ROYALTY_CLAIMER_UPDATER_ROLE, COMPONENT_ROYALTY_CLAIM_ROYALTIES_IDENT,
-    COMPONENT_ROYALTY_CREATE_IDENT, COMPONENT_ROYALTY_LOCKER_ROLE,
-    COMPONENT_ROYALTY_LOCKER_UPDATER_ROLE, COMPONENT_ROYALTY_LOCK_ROYALTY_IDENT,
+    COMPONENT_ROYALTY_CLAIM_ROYALTY_SHARE_IDENT, COMPONENT_ROYALTY_CREATE_IDENT,
+    COMPONENT_ROYALTY_LOCKER_ROLE, COMPONENT_ROYALTY_LOCKER_UPDATER_ROLE,
+    COMPONENT_ROYALTY_LOCK_ROYALTY_IDENT, COMPONENT_ROYALTY_LOCK_ROYALTY_SPLIT_IDENT,
     COMPONENT_ROYALTY_SETTER_ROLE, COMPONENT_ROYALTY_SETTER_UPDATER_ROLE,
-    COMPONENT_ROYALTY_SET_ROYALTY_IDENT,
+    COMPONENT_ROYALTY_SET_ROYALTY_IDENT, COMPONENT_ROYALTY_SET_ROYALTY_SPLIT_IDENT,
 };
 use radix_engine_interface::api::AttachedModuleId;
 use radix_engine_interface::blueprints::resource::Bucket;
 use radix_engine_interface::constants::ROYALTY_MODULE_PACKAGE;
 use radix_engine_interface::data::scrypto::{scrypto_decode, scrypto_encode};
-use radix_engine_interface::types::ComponentRoyaltyConfig;
+use radix_engine_interface::math::Decimal;
+use radix_engine_interface::types::{ComponentRoyaltyConfig, GlobalAddress};
+use sbor::rust::collections::IndexMap;
 use sbor::rust::string::ToString;
 use sbor::rust::vec;
 use sbor::rust::vec::Vec;
@@ -87,6 +91,36 @@ impl Royalty {
             &ComponentClaimRoyaltiesInput {},
         )
     }
+
+    pub fn set_royalty_split<M: ToString>(
+        &self,
+        method: M,
+        recipients: IndexMap<GlobalAddress, Decimal>,
+    ) {
+        self.call_ignore_rtn(
+            COMPONENT_ROYALTY_SET_ROYALTY_SPLIT_IDENT,
+            &ComponentRoyaltySetSplitInput {
+                method: method.to_string(),
+                recipients,
+            },
+        );
+    }
+
+    pub fn lock_royalty_split<M: ToString>(&self, method: M) {
+        self.call_ignore_rtn(
+            COMPONENT_ROYALTY_LOCK_ROYALTY_SPLIT_IDENT,
+            &ComponentRoyaltyLockSplitInput {
+                method: method.to_string(),
+            },
+        );
+    }
+
+    pub fn claim_royalty_share(&self, recipient: GlobalAddress) -> Option<Bucket> {
+        self.call(
+            COMPONENT_ROYALTY_CLAIM_ROYALTY_SHARE_IDENT,
+            &ComponentClaimRoyaltyShareInput { recipient },
+        )
+    }
 }
 
 pub struct RoyaltyRoles<T> {
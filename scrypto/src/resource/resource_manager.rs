@@ -257,6 +257,31 @@ impl ResourceManagerStub {
         )
     }
 
+    /// Updates many mutable fields of many non-fungible units in a single invocation.
+    ///
+    /// # Panics
+    /// Panics if this is not a non-fungible resource or any of the non-fungibles is not found.
+    pub fn batch_update_non_fungible_data<D: ScryptoEncode>(
+        &self,
+        updates: Vec<(NonFungibleLocalId, String, D)>,
+    ) {
+        self.call(
+            NON_FUNGIBLE_RESOURCE_MANAGER_BATCH_UPDATE_DATA_IDENT,
+            &NonFungibleResourceManagerBatchUpdateDataInput {
+                updates: updates
+                    .into_iter()
+                    .map(|(id, field_name, new_data)| {
+                        (
+                            id,
+                            field_name,
+                            scrypto_decode(&scrypto_encode(&new_data).unwrap()).unwrap(),
+                        )
+                    })
+                    .collect(),
+            },
+        )
+    }
+
     pub fn amount_for_withdrawal(
         &self,
         request_amount: Decimal,
@@ -0,0 +1,9 @@
+#[cfg(windows)]
+use colored::*;
+use simulator::rtmi;
+
+pub fn main() -> Result<(), rtmi::Error> {
+    #[cfg(windows)]
+    control::set_virtual_terminal(true).unwrap();
+    rtmi::run()
+}
@@ -6,6 +6,8 @@ pub mod resim;
 pub mod rtmc;
 /// Radix transaction manifest decompiler CLI.
 pub mod rtmd;
+/// Radix transaction inspector CLI.
+pub mod rtmi;
 /// Scrypto CLI.
 pub mod scrypto;
 /// Stubs Generator CLI.
@@ -5,6 +5,8 @@ use crate::replay::ledger_transaction::PreparedLedgerTransactionInner;
 use clap::Parser;
 use flate2::read::GzDecoder;
 use flume;
+#[cfg(feature = "wasm_profiling")]
+use radix_engine::system::system_db_reader::SystemDatabaseReader;
 use radix_engine::types::*;
 use radix_engine::vm::wasm::*;
 use radix_engine::vm::ScryptoVm;
@@ -42,6 +44,13 @@ pub struct TxnMeasure {
     /// Trace transaction execution
     #[clap(long)]
     pub trace: bool,
+
+    /// Profile the WASM execution units consumed by Scrypto code, saving a report
+    /// (`profile.txt`) and a folded stacks file (`profile.folded`) into the given directory
+    /// (requires the `wasm_profiling` feature)
+    #[cfg(feature = "wasm_profiling")]
+    #[clap(long)]
+    pub wasm_profile: Option<PathBuf>,
 }
 
 impl TxnMeasure {
@@ -96,7 +105,20 @@ impl TxnMeasure {
         }
 
         let trace = self.trace;
+        #[cfg(feature = "wasm_profiling")]
+        let wasm_profile = self.wasm_profile.clone();
         let txn_write_thread_handle = thread::spawn(move || {
+            #[cfg(feature = "wasm_profiling")]
+            let wasm_profiler = wasm_profile.as_ref().map(|_| WasmProfiler::new());
+            #[cfg(feature = "wasm_profiling")]
+            let scrypto_vm = match &wasm_profiler {
+                Some(wasm_profiler) => ScryptoVm {
+                    wasm_engine: DefaultWasmEngine::default().with_profiler(wasm_profiler.clone()),
+                    wasm_validator_config: WasmValidatorConfigV1::new(),
+                },
+                None => ScryptoVm::<DefaultWasmEngine>::default(),
+            };
+            #[cfg(not(feature = "wasm_profiling"))]
             let scrypto_vm = ScryptoVm::<DefaultWasmEngine>::default();
             let iter = rx.iter();
             for tx_payload in iter {
@@ -142,6 +164,11 @@ impl TxnMeasure {
                 }
             }
 
+            #[cfg(feature = "wasm_profiling")]
+            if let (Some(wasm_profiler), Some(wasm_profile)) = (wasm_profiler, wasm_profile) {
+                save_wasm_profile(&database, wasm_profiler.take_profile(), &wasm_profile)?;
+            }
+
             let duration = start.elapsed();
             println!("Time elapsed: {:?}", duration);
             println!("State version: {}", database.get_current_version());
@@ -156,6 +183,25 @@ impl TxnMeasure {
     }
 }
 
+#[cfg(feature = "wasm_profiling")]
+fn save_wasm_profile(
+    database: &RocksDBWithMerkleTreeSubstateStore,
+    profile: WasmProfile,
+    dir: &PathBuf,
+) -> Result<(), Error> {
+    println!("Resolving WASM profile symbols...");
+    let mut original_code =
+        SystemDatabaseReader::new(database).find_package_original_code(&profile.code_hashes());
+    let report = profile.symbolize(|code_hash| original_code.swap_remove(code_hash));
+
+    std::fs::create_dir_all(dir).map_err(Error::IOError)?;
+    std::fs::write(dir.join("profile.txt"), format!("{}\n", report)).map_err(Error::IOError)?;
+    std::fs::write(dir.join("profile.folded"), report.to_folded_stacks())
+        .map_err(Error::IOError)?;
+    println!("WASM profile saved to {:?}", dir);
+    Ok(())
+}
+
 fn print_progress(duration: Duration, new_version: u64, new_root: Hash) {
     let seconds = duration.as_secs() % 60;
     let minutes = (duration.as_secs() / 60) % 60;
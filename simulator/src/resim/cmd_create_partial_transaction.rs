@@ -0,0 +1,127 @@
+use clap::Parser;
+use colored::*;
+use radix_engine::utils::validate_call_arguments_to_native_components;
+use std::path::PathBuf;
+use transaction::manifest::BlobProvider;
+
+use crate::resim::*;
+
+/// Compiles a transaction manifest into a partially signed transaction, to be signed offline
+#[derive(Parser, Debug)]
+pub struct CreatePartialTransaction {
+    /// The path to a transaction manifest file
+    pub path: PathBuf,
+
+    /// The path to write the partially signed transaction to
+    #[clap(short, long)]
+    pub output: PathBuf,
+
+    /// The network to use, [simulator | adapanet | nebunet | mainnet]
+    #[clap(short, long)]
+    pub network: Option<String>,
+
+    /// The paths to blobs
+    #[clap(short, long, multiple = true)]
+    pub blobs: Option<Vec<String>>,
+
+    /// The Secp256k1 public key of the notary, defaults to the public key of the default account
+    #[clap(long)]
+    pub notary_public_key: Option<String>,
+
+    /// Whether the notary should also count as a signatory
+    #[clap(long)]
+    pub notary_is_signatory: bool,
+
+    /// The first epoch in which the transaction may be committed
+    #[clap(long, default_value = "0")]
+    pub start_epoch: u64,
+
+    /// The number of epochs for which the transaction may be committed
+    #[clap(long, default_value = "100")]
+    pub epoch_validity: u64,
+
+    /// The tip percentage
+    #[clap(long, default_value = "0")]
+    pub tip_percentage: u16,
+}
+
+impl CreatePartialTransaction {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
+        let manifest = std::fs::read_to_string(&self.path).map_err(Error::IOError)?;
+        let pre_processed_manifest = Run::pre_process_manifest(&manifest);
+        let network = match &self.network {
+            Some(n) => NetworkDefinition::from_str(&n).map_err(Error::ParseNetworkError)?,
+            None => NetworkDefinition::simulator(),
+        };
+        let mut blobs = Vec::new();
+        if let Some(paths) = &self.blobs {
+            for path in paths {
+                blobs.push(std::fs::read(path).map_err(Error::IOError)?);
+            }
+        }
+        let compiled_manifest = transaction::manifest::compile(
+            &pre_processed_manifest,
+            &network,
+            BlobProvider::new_with_blobs(blobs),
+        )
+        .map_err(Error::CompileError)?;
+        validate_call_arguments_to_native_components(&compiled_manifest.instructions)
+            .map_err(Error::InstructionSchemaValidationError)?;
+
+        let notary_public_key: PublicKey = match &self.notary_public_key {
+            Some(key) => Secp256k1PublicKey::from_str(key)
+                .map_err(|_| Error::InvalidPublicKey)?
+                .into(),
+            None => get_default_private_key()?.public_key().into(),
+        };
+        let end_epoch = self
+            .start_epoch
+            .checked_add(self.epoch_validity)
+            .ok_or(Error::EpochOverflow)?;
+        let (instructions, blobs) = compiled_manifest.for_intent();
+        let intent = IntentV1 {
+            header: TransactionHeaderV1 {
+                network_id: network.id,
+                start_epoch_inclusive: Epoch::of(self.start_epoch),
+                end_epoch_exclusive: Epoch::of(end_epoch),
+                nonce: get_nonce()?,
+                notary_public_key,
+                notary_is_signatory: self.notary_is_signatory,
+                tip_percentage: self.tip_percentage,
+            },
+            instructions,
+            blobs,
+            message: MessageV1::None,
+        };
+
+        let transaction = PartiallySignedTransactionV1::new(intent)
+            .map_err(Error::PartiallySignedTransactionError)?;
+        write_partial_transaction(&self.output, &transaction)?;
+        writeln!(
+            out,
+            "Intent hash: {}",
+            TransactionHashBech32Encoder::new(&network)
+                .encode(&transaction.intent_hash)
+                .unwrap()
+                .green()
+        )
+        .map_err(Error::IOError)?;
+        Ok(())
+    }
+}
+
+pub fn read_partial_transaction(path: &PathBuf) -> Result<PartiallySignedTransactionV1, Error> {
+    let payload = std::fs::read(path).map_err(|e| Error::IOErrorAtPath(e, path.clone()))?;
+    PartiallySignedTransactionV1::from_payload_bytes(&payload)
+        .map_err(Error::PartiallySignedTransactionDecodeError)
+}
+
+pub fn write_partial_transaction(
+    path: &PathBuf,
+    transaction: &PartiallySignedTransactionV1,
+) -> Result<(), Error> {
+    let payload = transaction
+        .to_payload_bytes()
+        .map_err(Error::SborEncodeError)?;
+    std::fs::write(path, payload).map_err(|e| Error::IOErrorAtPath(e, path.clone()))
+}
@@ -0,0 +1,66 @@
+use clap::Parser;
+use colored::*;
+use std::path::PathBuf;
+use transaction::validation::*;
+
+use crate::resim::*;
+
+/// Notarizes a partially signed transaction into a notarized transaction
+#[derive(Parser, Debug)]
+pub struct FinalizePartialTransaction {
+    /// The path to a partially signed transaction
+    pub path: PathBuf,
+
+    /// The path to write the notarized transaction payload to
+    #[clap(short, long)]
+    pub output: PathBuf,
+
+    /// The network to validate the notarized transaction against, [simulator | adapanet | nebunet | mainnet]
+    #[clap(short, long)]
+    pub network: Option<String>,
+
+    /// The private key of the notary, defaults to the private key of the default account
+    #[clap(long)]
+    pub notary_private_key: Option<String>,
+}
+
+impl FinalizePartialTransaction {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
+        let network = match &self.network {
+            Some(n) => NetworkDefinition::from_str(&n).map_err(Error::ParseNetworkError)?,
+            None => NetworkDefinition::simulator(),
+        };
+        let notary_private_key = match &self.notary_private_key {
+            Some(key) => parse_private_key_from_str(key)?,
+            None => get_default_private_key()?,
+        };
+
+        let transaction = read_partial_transaction(&self.path)?
+            .finalize(&notary_private_key)
+            .map_err(Error::PartiallySignedTransactionError)?;
+        let prepared = transaction
+            .prepare()
+            .map_err(Error::TransactionPrepareError)?;
+        NotarizedTransactionValidator::new(
+            ValidationConfig::default(network.id).with_secp256r1_signatures(),
+        )
+        .validate(prepared.clone())
+        .map_err(Error::TransactionValidationError)?;
+
+        let payload = transaction
+            .to_payload_bytes()
+            .map_err(Error::SborEncodeError)?;
+        std::fs::write(&self.output, payload)
+            .map_err(|e| Error::IOErrorAtPath(e, self.output.clone()))?;
+        writeln!(
+            out,
+            "Notarized transaction hash: {}",
+            TransactionHashBech32Encoder::new(&network)
+                .encode(&prepared.notarized_transaction_hash())
+                .unwrap()
+                .green()
+        )
+        .map_err(Error::IOError)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use clap::Parser;
+use std::path::PathBuf;
+
+use crate::resim::*;
+
+/// Merges the intent signatures of several copies of the same partially signed transaction
+#[derive(Parser, Debug)]
+pub struct MergePartialTransactions {
+    /// The paths to the partially signed transactions
+    #[clap(required = true, multiple = true)]
+    pub paths: Vec<PathBuf>,
+
+    /// The path to write the merged transaction to
+    #[clap(short, long)]
+    pub output: PathBuf,
+}
+
+impl MergePartialTransactions {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
+        let mut paths = self.paths.iter();
+        let mut transaction = read_partial_transaction(paths.next().unwrap())?;
+        for path in paths {
+            transaction
+                .merge(read_partial_transaction(path)?)
+                .map_err(Error::PartiallySignedTransactionError)?;
+        }
+        write_partial_transaction(&self.output, &transaction)?;
+        writeln!(
+            out,
+            "Signatures: {}",
+            transaction.intent_signatures.signatures.len()
+        )
+        .map_err(Error::IOError)?;
+        Ok(())
+    }
+}
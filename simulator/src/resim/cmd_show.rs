@@ -8,31 +8,46 @@ pub struct Show {
     /// The address of a package, component or resource manager, if no
     /// address is provided, then we default to `show <DEFAULT_ACCOUNT_ADDRESS>`.
     pub address: Option<String>,
+
+    /// Verify the metadata of the entity against the metadata standard
+    #[clap(long)]
+    pub verify_metadata: bool,
 }
 
 impl Show {
     pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
         let SimulatorEnvironment { db, .. } = SimulatorEnvironment::new()?;
 
-        match &self.address {
+        let address: GlobalAddress = match &self.address {
             Some(address) => {
                 if let Ok(a) = SimulatorPackageAddress::from_str(address) {
-                    dump_package(a.0, &db, out).map_err(Error::LedgerDumpError)
+                    dump_package(a.0, &db, out).map_err(Error::LedgerDumpError)?;
+                    a.0.into()
                 } else if let Ok(a) = SimulatorComponentAddress::from_str(address) {
-                    dump_component(a.0, &db, out).map_err(Error::LedgerDumpError)
+                    dump_component(a.0, &db, out).map_err(Error::LedgerDumpError)?;
+                    a.0.into()
                 } else if let Ok(a) = SimulatorResourceAddress::from_str(address) {
-                    dump_resource_manager(a.0, &db, out).map_err(Error::LedgerDumpError)
+                    dump_resource_manager(a.0, &db, out).map_err(Error::LedgerDumpError)?;
+                    a.0.into()
                 } else {
-                    Err(Error::InvalidId(address.clone()))
+                    return Err(Error::InvalidId(address.clone()));
                 }
             }
-            None => get_configs()
-                .and_then(|c| {
+            None => {
+                let account = get_configs().and_then(|c| {
                     c.default_account.ok_or(Error::LedgerDumpError(
                         EntityDumpError::NoAddressProvidedAndNotDefaultAccountSet,
                     ))
-                })
-                .and_then(|x| dump_component(x, &db, out).map_err(Error::LedgerDumpError)),
+                })?;
+                dump_component(account, &db, out).map_err(Error::LedgerDumpError)?;
+                account.into()
+            }
+        };
+
+        if self.verify_metadata {
+            dump_metadata_findings(address, &db, out).map_err(Error::LedgerDumpError)?;
         }
+
+        Ok(())
     }
 }
@@ -0,0 +1,43 @@
+use crate::resim::*;
+use clap::Parser;
+use radix_engine::types::*;
+use transaction::prelude::{IntentHash, TransactionHashBech32Decoder};
+
+/// Show the replay protection status of transaction intents
+#[derive(Parser, Debug)]
+pub struct ShowTransactionStatus {
+    /// The intent hash, either Bech32m encoded or in hex. If no intent hash
+    /// is provided, then the intents tracked in the epoch range are listed.
+    pub intent_hash: Option<String>,
+
+    /// The first expiry epoch of the intents to list
+    #[clap(long)]
+    pub start_epoch: Option<u64>,
+
+    /// The expiry epoch (exclusive) up to which intents are listed
+    #[clap(long)]
+    pub end_epoch: Option<u64>,
+}
+
+impl ShowTransactionStatus {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
+        let SimulatorEnvironment { db, .. } = SimulatorEnvironment::new()?;
+
+        match &self.intent_hash {
+            Some(intent_hash) => {
+                let intent_hash = TransactionHashBech32Decoder::for_simulator()
+                    .validate_and_decode::<IntentHash>(intent_hash)
+                    .or_else(|_| Hash::from_str(intent_hash).map(IntentHash::from_hash))
+                    .map_err(|_| Error::InvalidId(intent_hash.clone()))?;
+                dump_intent_status(intent_hash, &db, out).map_err(Error::LedgerDumpError)
+            }
+            None => dump_tracked_intents(
+                Epoch::of(self.start_epoch.unwrap_or(0)),
+                Epoch::of(self.end_epoch.unwrap_or(u64::MAX)),
+                &db,
+                out,
+            )
+            .map_err(Error::LedgerDumpError),
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use clap::Parser;
+use colored::*;
+use std::path::PathBuf;
+
+use crate::resim::*;
+
+/// Adds intent signatures to a partially signed transaction
+#[derive(Parser, Debug)]
+pub struct SignPartialTransaction {
+    /// The path to a partially signed transaction
+    pub path: PathBuf,
+
+    /// The path to write the signed transaction to, defaults to overwriting the input
+    #[clap(short, long)]
+    pub output: Option<PathBuf>,
+
+    /// The private keys used for signing, separated by comma
+    #[clap(short, long)]
+    pub signing_keys: Option<String>,
+}
+
+impl SignPartialTransaction {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
+        let mut transaction = read_partial_transaction(&self.path)?;
+        for private_key in get_signing_keys(&self.signing_keys)? {
+            transaction
+                .sign(&private_key)
+                .map_err(Error::PartiallySignedTransactionError)?;
+            writeln!(
+                out,
+                "Signed by: {}",
+                private_key.public_key().to_string().green()
+            )
+            .map_err(Error::IOError)?;
+        }
+        write_partial_transaction(self.output.as_ref().unwrap_or(&self.path), &transaction)
+    }
+}
@@ -9,6 +9,11 @@ use radix_engine_store_interface::interface::*;
 use crate::resim::*;
 use std::env;
 
+/// The directory under the data directory which holds the on-disk WASM cache.
+const WASM_DISK_CACHE_DIR: &str = "wasm_cache";
+/// The maximum size of the on-disk WASM cache.
+const WASM_DISK_CACHE_MAX_SIZE_IN_BYTES: u64 = 256 * 1024 * 1024;
+
 /// The environment that the simulator runs in.
 pub struct SimulatorEnvironment {
     // Db
@@ -23,8 +28,19 @@ impl SimulatorEnvironment {
         // Create the database
         let db = RocksdbSubstateStore::standard(get_data_dir()?);
 
-        // Create the VMs
-        let scrypto_vm = ScryptoVm::<DefaultWasmEngine>::default();
+        // Create the VMs, caching the instrumented code of the published packages across runs
+        let wasm_validator_config = WasmValidatorConfigV1::new();
+        let wasm_disk_cache = WasmDiskCache::new(
+            WasmDiskCacheOptions {
+                directory: get_data_dir()?.join(WASM_DISK_CACHE_DIR),
+                max_size_in_bytes: WASM_DISK_CACHE_MAX_SIZE_IN_BYTES,
+            },
+            &wasm_validator_config,
+        );
+        let scrypto_vm = ScryptoVm {
+            wasm_engine: DefaultWasmEngine::default().with_disk_cache(wasm_disk_cache),
+            wasm_validator_config,
+        };
         let native_vm = DefaultNativeVm::new();
 
         let mut env = Self {
@@ -65,11 +81,81 @@ impl SimulatorEnvironment {
             let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
             self.db.commit(&db_updates);
         }
+        {
+            let state_updates = generate_vm_boot_crypto_utils_v2_state_updates();
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            self.db.commit(&db_updates);
+        }
+        {
+            let state_updates = generate_vm_boot_bulk_memory_state_updates();
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            self.db.commit(&db_updates);
+        }
+        {
+            let state_updates = generate_vm_boot_indexed_collections_state_updates();
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            self.db.commit(&db_updates);
+        }
+        {
+            let state_updates = generate_vm_boot_kv_store_iteration_state_updates();
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            self.db.commit(&db_updates);
+        }
         {
             let state_updates = generate_pools_v1_1_state_updates(&self.db);
             let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
             self.db.commit(&db_updates);
         }
+        {
+            let state_updates = generate_pools_v1_2_state_updates(&self.db);
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            self.db.commit(&db_updates);
+        }
+        {
+            let state_updates = generate_account_allowances_state_updates(&self.db);
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            self.db.commit(&db_updates);
+        }
+        {
+            let state_updates = generate_validator_redelegation_state_updates(&self.db);
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            self.db.commit(&db_updates);
+        }
+        {
+            let state_updates = generate_access_controller_xrd_fee_vault_state_updates(&self.db);
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            self.db.commit(&db_updates);
+        }
+        {
+            let state_updates = generate_package_blueprint_upgrades_state_updates(&self.db);
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            self.db.commit(&db_updates);
+        }
+        {
+            let state_updates = generate_package_royalty_splits_state_updates(&self.db);
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            self.db.commit(&db_updates);
+        }
+        {
+            let state_updates = generate_component_royalty_splits_state_updates(&self.db);
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            self.db.commit(&db_updates);
+        }
+        {
+            let state_updates = generate_fungible_vesting_state_updates(&self.db);
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            self.db.commit(&db_updates);
+        }
+        {
+            let state_updates = generate_non_fungible_batch_data_updates_state_updates(&self.db);
+            let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+            self.db.commit(&db_updates);
+        }
+        {
+            let vm = Vm::new(&self.scrypto_vm, self.native_vm.clone());
+            Bootstrapper::new(NetworkDefinition::simulator(), &mut self.db, vm, false)
+                .update_to_secp256r1_signatures();
+        }
     }
 }
 
@@ -6,12 +6,15 @@ use radix_engine::system::system_db_reader::SystemDatabaseReader;
 use radix_engine::types::*;
 use radix_engine_interface::api::ModuleId;
 use radix_engine_interface::network::NetworkDefinition;
-use radix_engine_queries::query::ResourceAccounter;
+use radix_engine_queries::query::{
+    MetadataVerifier, ResourceAccounter, TrackedIntent, TransactionTrackerReader,
+};
 use radix_engine_queries::typed_substate_layout::*;
 use radix_engine_store_interface::{
     db_key_mapper::{MappedSubstateDatabase, SpreadPrefixKeyMapper},
     interface::SubstateDatabase,
 };
+use transaction::prelude::{IntentHash, TransactionHashBech32Encoder};
 use utils::ContextualDisplay;
 
 /// Represents an error when displaying an entity.
@@ -285,6 +288,121 @@ pub fn dump_resource_manager<T: SubstateDatabase, O: std::io::Write>(
     Ok(())
 }
 
+/// Dump the findings of verifying the metadata of an entity against the metadata standard.
+pub fn dump_metadata_findings<T: SubstateDatabase, O: std::io::Write>(
+    address: GlobalAddress,
+    substate_db: &T,
+    output: &mut O,
+) -> Result<(), EntityDumpError> {
+    let findings = MetadataVerifier::new(substate_db)
+        .verify(address)
+        .map_err(|e| EntityDumpError::InvalidStore(format!("{:?}", e)))?;
+
+    writeln!(
+        output,
+        "{}: {}",
+        "Metadata Findings".green().bold(),
+        findings.len()
+    );
+    for (last, finding) in findings.iter().identify_last() {
+        writeln!(
+            output,
+            "{} {}: {:?}",
+            list_item_prefix(last),
+            finding.key,
+            finding.issue
+        );
+    }
+
+    Ok(())
+}
+
+/// Dump the replay protection status of a transaction intent into console.
+pub fn dump_intent_status<T: SubstateDatabase, O: std::io::Write>(
+    intent_hash: IntentHash,
+    substate_db: &T,
+    output: &mut O,
+) -> Result<(), EntityDumpError> {
+    let tracked_intent = TransactionTrackerReader::new(substate_db)
+        .intent_status(&intent_hash)
+        .map_err(|e| EntityDumpError::InvalidStore(format!("{:?}", e)))?;
+
+    writeln!(
+        output,
+        "{}: {}",
+        "Intent Hash".green().bold(),
+        encode_intent_hash(&intent_hash)
+    );
+    match tracked_intent {
+        Some(tracked_intent) => {
+            writeln!(
+                output,
+                "{}: {:?}",
+                "Status".green().bold(),
+                tracked_intent.status
+            );
+            writeln!(
+                output,
+                "{}: {}",
+                "Epoch Partition".green().bold(),
+                format_partition(&tracked_intent)
+            );
+        }
+        None => {
+            writeln!(output, "{}: Unknown", "Status".green().bold());
+        }
+    }
+
+    Ok(())
+}
+
+/// Dump the transaction intents tracked for the given range of expiry epochs into console.
+pub fn dump_tracked_intents<T: SubstateDatabase, O: std::io::Write>(
+    start_epoch_inclusive: Epoch,
+    end_epoch_exclusive: Epoch,
+    substate_db: &T,
+    output: &mut O,
+) -> Result<(), EntityDumpError> {
+    let tracked_intents = TransactionTrackerReader::new(substate_db)
+        .tracked_intents(start_epoch_inclusive, end_epoch_exclusive)
+        .map_err(|e| EntityDumpError::InvalidStore(format!("{:?}", e)))?;
+
+    writeln!(
+        output,
+        "{}: {}",
+        "Tracked Intents".green().bold(),
+        tracked_intents.len()
+    );
+    for (last, tracked_intent) in tracked_intents.iter().identify_last() {
+        writeln!(
+            output,
+            "{} {}: {:?}, {}",
+            list_item_prefix(last),
+            encode_intent_hash(&tracked_intent.intent_hash),
+            tracked_intent.status,
+            format_partition(tracked_intent)
+        );
+    }
+
+    Ok(())
+}
+
+fn encode_intent_hash(intent_hash: &IntentHash) -> String {
+    TransactionHashBech32Encoder::for_simulator()
+        .encode(intent_hash)
+        .unwrap()
+}
+
+fn format_partition(tracked_intent: &TrackedIntent) -> String {
+    let partition = &tracked_intent.partition;
+    format!(
+        "partition {} (expiry epochs {}..{})",
+        partition.partition_number.0,
+        partition.start_epoch_inclusive.number(),
+        partition.end_epoch_exclusive.number()
+    )
+}
+
 fn get_entity_metadata<T: SubstateDatabase>(
     entity_node_id: &NodeId,
     substate_db: &T,
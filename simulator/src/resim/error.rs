@@ -11,6 +11,7 @@ use radix_engine_interface::network::ParseNetworkError;
 use radix_engine_interface::types::SchemaHash;
 use sbor::*;
 use transaction::errors::*;
+use transaction::model::PartiallySignedTransactionDecodeError;
 use transaction::model::PrepareError as TransactionPrepareError;
 
 use crate::resim::EntityDumpError;
@@ -51,6 +52,10 @@ pub enum Error {
 
     TransactionPrepareError(TransactionPrepareError),
 
+    PartiallySignedTransactionError(PartiallySignedTransactionError),
+
+    PartiallySignedTransactionDecodeError(PartiallySignedTransactionDecodeError),
+
     TransactionFailed(RuntimeError),
 
     TransactionRejected(RejectionReason),
@@ -67,6 +72,8 @@ pub enum Error {
 
     InvalidPrivateKey,
 
+    InvalidPublicKey,
+
     /// e.g. if you accidentally pass in a public key in `set_default_account` command.
     GotPublicKeyExpectedPrivateKey,
 
@@ -82,5 +89,8 @@ pub enum Error {
 
     InvalidResourceSpecifier(String),
 
+    /// The end epoch of a transaction, i.e. the start epoch plus the epoch validity, overflows.
+    EpochOverflow,
+
     RemoteGenericSubstitutionNotSupported,
 }
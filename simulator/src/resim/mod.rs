@@ -1,8 +1,11 @@
 mod addressing;
 mod cmd_call_function;
 mod cmd_call_method;
+mod cmd_create_partial_transaction;
 mod cmd_export_package_definition;
+mod cmd_finalize_partial_transaction;
 mod cmd_generate_key_pair;
+mod cmd_merge_partial_transactions;
 mod cmd_mint;
 mod cmd_new_account;
 mod cmd_new_badge_fixed;
@@ -19,6 +22,8 @@ mod cmd_set_default_account;
 mod cmd_show;
 mod cmd_show_configs;
 mod cmd_show_ledger;
+mod cmd_show_transaction_status;
+mod cmd_sign_partial_transaction;
 mod cmd_transfer;
 mod config;
 mod dumper;
@@ -27,8 +32,11 @@ mod error;
 pub use addressing::*;
 pub use cmd_call_function::*;
 pub use cmd_call_method::*;
+pub use cmd_create_partial_transaction::*;
 pub use cmd_export_package_definition::*;
+pub use cmd_finalize_partial_transaction::*;
 pub use cmd_generate_key_pair::*;
+pub use cmd_merge_partial_transactions::*;
 pub use cmd_new_account::*;
 pub use cmd_new_badge_fixed::*;
 pub use cmd_new_badge_mutable::*;
@@ -44,6 +52,8 @@ pub use cmd_set_default_account::*;
 pub use cmd_show::*;
 pub use cmd_show_configs::*;
 pub use cmd_show_ledger::*;
+pub use cmd_show_transaction_status::*;
+pub use cmd_sign_partial_transaction::*;
 pub use cmd_transfer::*;
 pub use config::*;
 pub use dumper::*;
@@ -109,8 +119,11 @@ impl ResimCli {
 pub enum Command {
     CallFunction(CallFunction),
     CallMethod(CallMethod),
+    CreatePartialTransaction(CreatePartialTransaction),
     ExportPackageDefinition(ExportPackageDefinition),
+    FinalizePartialTransaction(FinalizePartialTransaction),
     GenerateKeyPair(GenerateKeyPair),
+    MergePartialTransactions(MergePartialTransactions),
     Mint(crate::resim::cmd_mint::Mint),
     NewAccount(NewAccount),
     NewSimpleBadge(NewSimpleBadge),
@@ -126,7 +139,9 @@ pub enum Command {
     SetDefaultAccount(SetDefaultAccount),
     ShowConfigs(ShowConfigs),
     ShowLedger(ShowLedger),
+    ShowTransactionStatus(ShowTransactionStatus),
     Show(Show),
+    SignPartialTransaction(SignPartialTransaction),
     Transfer(Transfer),
 }
 
@@ -138,8 +153,11 @@ pub fn run() -> Result<(), Error> {
     match cli.command {
         Command::CallFunction(cmd) => cmd.run(&mut out),
         Command::CallMethod(cmd) => cmd.run(&mut out),
+        Command::CreatePartialTransaction(cmd) => cmd.run(&mut out),
         Command::ExportPackageDefinition(cmd) => cmd.run(&mut out),
+        Command::FinalizePartialTransaction(cmd) => cmd.run(&mut out),
         Command::GenerateKeyPair(cmd) => cmd.run(&mut out),
+        Command::MergePartialTransactions(cmd) => cmd.run(&mut out),
         Command::Mint(cmd) => cmd.run(&mut out),
         Command::NewAccount(cmd) => cmd.run(&mut out),
         Command::NewSimpleBadge(cmd) => cmd.run(&mut out).map(|_| ()),
@@ -155,7 +173,9 @@ pub fn run() -> Result<(), Error> {
         Command::SetDefaultAccount(cmd) => cmd.run(&mut out),
         Command::ShowConfigs(cmd) => cmd.run(&mut out),
         Command::ShowLedger(cmd) => cmd.run(&mut out),
+        Command::ShowTransactionStatus(cmd) => cmd.run(&mut out),
         Command::Show(cmd) => cmd.run(&mut out),
+        Command::SignPartialTransaction(cmd) => cmd.run(&mut out),
         Command::Transfer(cmd) => cmd.run(&mut out),
     }
 }
@@ -367,8 +387,15 @@ pub fn export_blueprint_interface(
     package_address: PackageAddress,
     blueprint_name: &str,
 ) -> Result<BlueprintInterface, Error> {
+    let version_key = {
+        let SimulatorEnvironment { db, .. } = SimulatorEnvironment::new()?;
+        SystemDatabaseReader::new(&db).get_blueprint_current_version_key(&BlueprintId::new(
+            &package_address,
+            blueprint_name,
+        ))
+    };
     let interface = export_package_schema(package_address)?
-        .get(&BlueprintVersionKey::new_default(blueprint_name))
+        .get(&version_key)
         .cloned()
         .ok_or(Error::BlueprintNotFound(
             package_address,
@@ -404,7 +431,7 @@ pub fn get_event_schema<S: SubstateDatabase>(
         }
     };
 
-    let version_key = BlueprintVersionKey::new_default(blueprint_id.blueprint_name.as_str());
+    let version_key = system_reader.get_blueprint_current_version_key(&blueprint_id);
     let bp_definition: VersionedPackageBlueprintVersionDefinition = system_reader
         .read_object_collection_entry(
             blueprint_id.package_address.as_node_id(),
@@ -528,7 +555,10 @@ mod tests {
             trace: false,
         };
         assert!(new_account.run(&mut out).is_ok());
-        let cmd = Show { address: None };
+        let cmd = Show {
+            address: None,
+            verify_metadata: false,
+        };
         assert!(cmd.run(&mut out).is_ok());
     }
 
@@ -2,7 +2,9 @@ use clap::Parser;
 use radix_engine::{types::*, utils::*};
 use std::path::PathBuf;
 use std::str::FromStr;
-use transaction::manifest::{compile, BlobProvider};
+use transaction::manifest::{
+    compile_with_parameters, BlobProvider, FragmentProvider, ManifestParameters,
+};
 
 /// Radix transaction manifest compiler
 #[derive(Parser, Debug)]
@@ -20,6 +22,14 @@ pub struct Args {
     #[clap(short, long, multiple = true)]
     blobs: Option<Vec<String>>,
 
+    /// The template parameters, in the form of `name=value`
+    #[clap(short, long, multiple = true)]
+    param: Option<Vec<String>>,
+
+    /// The includable template fragments, in the form of `name=path`
+    #[clap(short, long, multiple = true)]
+    fragment: Option<Vec<String>>,
+
     /// Input file
     #[clap(required = true)]
     input: PathBuf,
@@ -31,6 +41,8 @@ pub enum Error {
     EncodeError(sbor::EncodeError),
     CompileError(transaction::manifest::CompileError),
     ParseNetworkError(ParseNetworkError),
+    InvalidParameter(String),
+    InvalidFragment(String),
     InstructionSchemaValidationError(radix_engine::utils::LocatedInstructionSchemaValidationError),
 }
 
@@ -48,8 +60,35 @@ pub fn run() -> Result<(), Error> {
             blobs.push(std::fs::read(path).map_err(Error::IoError)?);
         }
     }
-    let transaction = compile(&content, &network, BlobProvider::new_with_blobs(blobs))
-        .map_err(Error::CompileError)?;
+    let mut fragments = FragmentProvider::new();
+    if let Some(fragment_args) = args.fragment {
+        for fragment_arg in fragment_args {
+            let (name, path) = fragment_arg
+                .split_once('=')
+                .ok_or_else(|| Error::InvalidFragment(fragment_arg.clone()))?;
+            fragments.add_fragment(
+                name.to_owned(),
+                std::fs::read_to_string(path).map_err(Error::IoError)?,
+            );
+        }
+    }
+    let mut parameters = ManifestParameters::new();
+    if let Some(param_args) = args.param {
+        for param_arg in param_args {
+            let (name, value) = param_arg
+                .split_once('=')
+                .ok_or_else(|| Error::InvalidParameter(param_arg.clone()))?;
+            parameters.insert(name, value);
+        }
+    }
+    let transaction = compile_with_parameters(
+        &content,
+        &network,
+        BlobProvider::new_with_blobs(blobs),
+        &fragments,
+        &parameters,
+    )
+    .map_err(Error::CompileError)?;
     validate_call_arguments_to_native_components(&transaction.instructions)
         .map_err(Error::InstructionSchemaValidationError)?;
     std::fs::write(
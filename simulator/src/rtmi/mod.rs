@@ -0,0 +1,552 @@
+use clap::Parser;
+use radix_engine::types::*;
+use radix_engine_interface::data::manifest::manifest_decode;
+use std::io::Write;
+use std::str::FromStr;
+use transaction::manifest::decompile;
+use transaction::model::PrepareError;
+use transaction::prelude::*;
+use transaction::validation::*;
+
+/// Radix transaction inspector
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None, name = "rtmi")]
+pub struct Args {
+    /// Network to Use [Simulator | Alphanet | Mainnet]
+    #[clap(short, long)]
+    network: Option<String>,
+
+    /// Whether the input is a hex-encoded payload rather than a path to a payload file
+    #[clap(short = 'x', long, action)]
+    hex: bool,
+
+    /// Input file, or hex-encoded payload if `--hex` is set
+    #[clap(required = true)]
+    input: String,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    IoError(std::io::Error),
+    HexDecodeError(hex::FromHexError),
+    UnrecognizedPayload(sbor::DecodeError),
+    PrepareError(PrepareError),
+    ParseNetworkError(ParseNetworkError),
+    TransactionHashBech32EncodeError(TransactionHashBech32EncodeError),
+}
+
+/// A decoded transaction payload of any of the supported kinds.
+pub enum InspectedPayload {
+    Intent(IntentV1),
+    SignedIntent(SignedIntentV1),
+    NotarizedTransaction(NotarizedTransactionV1),
+    SystemTransaction(SystemTransactionV1),
+    PreviewIntent(PreviewIntentV1),
+}
+
+impl InspectedPayload {
+    pub fn decode(payload: &[u8]) -> Result<Self, Error> {
+        match manifest_decode::<VersionedTransactionPayload>(payload) {
+            Ok(VersionedTransactionPayload::IntentV1 {
+                header,
+                instructions,
+                blobs,
+                message,
+            }) => Ok(Self::Intent(IntentV1 {
+                header,
+                instructions,
+                blobs,
+                message,
+            })),
+            Ok(VersionedTransactionPayload::SignedIntentV1 {
+                intent,
+                intent_signatures,
+            }) => Ok(Self::SignedIntent(SignedIntentV1 {
+                intent,
+                intent_signatures,
+            })),
+            Ok(VersionedTransactionPayload::NotarizedTransactionV1 {
+                signed_intent,
+                notary_signature,
+            }) => Ok(Self::NotarizedTransaction(NotarizedTransactionV1 {
+                signed_intent,
+                notary_signature,
+            })),
+            Ok(VersionedTransactionPayload::SystemTransactionV1 {
+                instructions,
+                blobs,
+                pre_allocated_addresses,
+                hash_for_execution,
+            }) => Ok(Self::SystemTransaction(SystemTransactionV1 {
+                instructions,
+                blobs,
+                pre_allocated_addresses,
+                hash_for_execution,
+            })),
+            Err(e) => {
+                // Preview intents are not versioned payloads, so try them last
+                match manifest_decode::<PreviewIntentV1>(payload) {
+                    Ok(preview_intent) => Ok(Self::PreviewIntent(preview_intent)),
+                    Err(_) => Err(Error::UnrecognizedPayload(e)),
+                }
+            }
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Intent(_) => "Intent V1",
+            Self::SignedIntent(_) => "Signed Intent V1",
+            Self::NotarizedTransaction(_) => "Notarized Transaction V1",
+            Self::SystemTransaction(_) => "System Transaction V1",
+            Self::PreviewIntent(_) => "Preview Intent V1",
+        }
+    }
+
+    pub fn intent(&self) -> Option<&IntentV1> {
+        match self {
+            Self::Intent(intent) => Some(intent),
+            Self::SignedIntent(signed_intent) => Some(&signed_intent.intent),
+            Self::NotarizedTransaction(transaction) => Some(&transaction.signed_intent.intent),
+            Self::SystemTransaction(_) => None,
+            Self::PreviewIntent(preview_intent) => Some(&preview_intent.intent),
+        }
+    }
+
+    pub fn instructions(&self) -> &[InstructionV1] {
+        match self {
+            Self::SystemTransaction(transaction) => &transaction.instructions.0,
+            _ => &self.intent().unwrap().instructions.0,
+        }
+    }
+
+    pub fn blobs(&self) -> &[BlobV1] {
+        match self {
+            Self::SystemTransaction(transaction) => &transaction.blobs.blobs,
+            _ => &self.intent().unwrap().blobs.blobs,
+        }
+    }
+}
+
+pub fn run() -> Result<(), Error> {
+    let args = Args::parse();
+
+    let payload = if args.hex {
+        hex::decode(args.input.trim()).map_err(Error::HexDecodeError)?
+    } else {
+        std::fs::read(&args.input).map_err(Error::IoError)?
+    };
+    let network = match args.network {
+        Some(n) => NetworkDefinition::from_str(&n).map_err(Error::ParseNetworkError)?,
+        None => NetworkDefinition::simulator(),
+    };
+
+    inspect(&payload, &network, &mut std::io::stdout())
+}
+
+/// Writes a human-readable report about the given transaction payload.
+pub fn inspect<O: Write>(
+    payload: &[u8],
+    network: &NetworkDefinition,
+    out: &mut O,
+) -> Result<(), Error> {
+    let inspected = InspectedPayload::decode(payload)?;
+    let encoder = TransactionHashBech32Encoder::new(network);
+
+    writeln!(out, "Payload Type: {}", inspected.kind()).map_err(Error::IoError)?;
+    writeln!(out, "Payload Size: {} bytes", payload.len()).map_err(Error::IoError)?;
+
+    // Hashes
+    match &inspected {
+        InspectedPayload::Intent(intent) => {
+            let prepared = intent.prepare().map_err(Error::PrepareError)?;
+            write_hash(out, &encoder, "Intent Hash", &prepared.intent_hash())?;
+        }
+        InspectedPayload::SignedIntent(signed_intent) => {
+            let prepared = signed_intent.prepare().map_err(Error::PrepareError)?;
+            write_hash(out, &encoder, "Intent Hash", &prepared.intent_hash())?;
+            write_hash(
+                out,
+                &encoder,
+                "Signed Intent Hash",
+                &prepared.signed_intent_hash(),
+            )?;
+        }
+        InspectedPayload::NotarizedTransaction(transaction) => {
+            let prepared = transaction.prepare().map_err(Error::PrepareError)?;
+            write_hash(out, &encoder, "Intent Hash", &prepared.intent_hash())?;
+            write_hash(
+                out,
+                &encoder,
+                "Signed Intent Hash",
+                &prepared.signed_intent_hash(),
+            )?;
+            write_hash(
+                out,
+                &encoder,
+                "Notarized Transaction Hash",
+                &prepared.notarized_transaction_hash(),
+            )?;
+        }
+        InspectedPayload::SystemTransaction(transaction) => {
+            let prepared = transaction.prepare().map_err(Error::PrepareError)?;
+            write_hash(
+                out,
+                &encoder,
+                "System Transaction Hash",
+                &prepared.system_transaction_hash(),
+            )?;
+        }
+        InspectedPayload::PreviewIntent(preview_intent) => {
+            let prepared = preview_intent
+                .intent
+                .prepare()
+                .map_err(Error::PrepareError)?;
+            write_hash(out, &encoder, "Intent Hash", &prepared.intent_hash())?;
+        }
+    }
+
+    // Header
+    if let Some(intent) = inspected.intent() {
+        let header = &intent.header;
+        writeln!(out, "Header:").map_err(Error::IoError)?;
+        writeln!(out, "├─ Network ID: {}", header.network_id).map_err(Error::IoError)?;
+        writeln!(
+            out,
+            "├─ Epoch Range: [{}, {})",
+            header.start_epoch_inclusive.number(),
+            header.end_epoch_exclusive.number()
+        )
+        .map_err(Error::IoError)?;
+        writeln!(out, "├─ Nonce: {}", header.nonce).map_err(Error::IoError)?;
+        writeln!(
+            out,
+            "├─ Notary Public Key: {}",
+            format_public_key(&header.notary_public_key)
+        )
+        .map_err(Error::IoError)?;
+        writeln!(
+            out,
+            "├─ Notary Is Signatory: {}",
+            header.notary_is_signatory
+        )
+        .map_err(Error::IoError)?;
+        writeln!(out, "└─ Tip Percentage: {}%", header.tip_percentage).map_err(Error::IoError)?;
+    }
+
+    // Signers
+    match &inspected {
+        InspectedPayload::SignedIntent(SignedIntentV1 {
+            intent,
+            intent_signatures,
+        })
+        | InspectedPayload::NotarizedTransaction(NotarizedTransactionV1 {
+            signed_intent:
+                SignedIntentV1 {
+                    intent,
+                    intent_signatures,
+                },
+            ..
+        }) => {
+            let intent_hash = intent
+                .prepare()
+                .map_err(Error::PrepareError)?
+                .intent_hash()
+                .into_hash();
+            writeln!(
+                out,
+                "Intent Signers: {}",
+                intent_signatures.signatures.len()
+            )
+            .map_err(Error::IoError)?;
+            for (i, signature) in intent_signatures.signatures.iter().enumerate() {
+                let signer = match verified_signer(&intent_hash, &signature.0) {
+                    Some(public_key) => format_public_key(&public_key),
+                    None => "<invalid signature>".to_owned(),
+                };
+                writeln!(
+                    out,
+                    "{} {}",
+                    list_item_prefix(i == intent_signatures.signatures.len() - 1),
+                    signer
+                )
+                .map_err(Error::IoError)?;
+            }
+        }
+        InspectedPayload::PreviewIntent(preview_intent) => {
+            writeln!(
+                out,
+                "Assumed Signers: {}",
+                preview_intent.signer_public_keys.len()
+            )
+            .map_err(Error::IoError)?;
+            for (i, public_key) in preview_intent.signer_public_keys.iter().enumerate() {
+                writeln!(
+                    out,
+                    "{} {}",
+                    list_item_prefix(i == preview_intent.signer_public_keys.len() - 1),
+                    format_public_key(public_key)
+                )
+                .map_err(Error::IoError)?;
+            }
+        }
+        _ => {}
+    }
+
+    // Message
+    if let Some(intent) = inspected.intent() {
+        write_message(out, &intent.message)?;
+    }
+
+    // Manifest
+    writeln!(out, "Manifest:").map_err(Error::IoError)?;
+    match decompile(inspected.instructions(), network) {
+        Ok(manifest) => write!(out, "{}", manifest).map_err(Error::IoError)?,
+        Err(e) => writeln!(out, "<failed to decompile: {:?}>", e).map_err(Error::IoError)?,
+    }
+
+    // Blobs
+    let blobs = inspected.blobs();
+    writeln!(out, "Blobs: {}", blobs.len()).map_err(Error::IoError)?;
+    for (i, blob) in blobs.iter().enumerate() {
+        writeln!(
+            out,
+            "{} {} ({} bytes)",
+            list_item_prefix(i == blobs.len() - 1),
+            hash(&blob.0),
+            blob.0.len()
+        )
+        .map_err(Error::IoError)?;
+    }
+
+    // Validation
+    let validator = NotarizedTransactionValidator::new(ValidationConfig::default(network.id));
+    let validation_result = match &inspected {
+        InspectedPayload::NotarizedTransaction(_) => {
+            Some(validator.validate_from_payload_bytes(payload).map(|_| ()))
+        }
+        InspectedPayload::PreviewIntent(preview_intent) => Some(
+            validator
+                .validate_preview_intent_v1(preview_intent.clone())
+                .map(|_| ()),
+        ),
+        InspectedPayload::Intent(_) | InspectedPayload::SignedIntent(_) => {
+            let intent = inspected.intent().unwrap();
+            let prepared = intent.prepare().map_err(Error::PrepareError)?;
+            Some(validator.validate_intent_v1(&prepared))
+        }
+        InspectedPayload::SystemTransaction(_) => None,
+    };
+    match validation_result {
+        Some(Ok(())) => writeln!(out, "Validation ({}): Valid", network.logical_name),
+        Some(Err(e)) => writeln!(
+            out,
+            "Validation ({}): Invalid - {:?}",
+            network.logical_name, e
+        ),
+        None => writeln!(out, "Validation: Not applicable to system transactions"),
+    }
+    .map_err(Error::IoError)?;
+
+    Ok(())
+}
+
+fn write_hash<O: Write, T: HashHasHrp>(
+    out: &mut O,
+    encoder: &TransactionHashBech32Encoder,
+    label: &str,
+    hash: &T,
+) -> Result<(), Error> {
+    let encoded = encoder
+        .encode(hash)
+        .map_err(Error::TransactionHashBech32EncodeError)?;
+    writeln!(out, "{}: {} ({})", label, encoded, hash.as_hash()).map_err(Error::IoError)
+}
+
+fn write_message<O: Write>(out: &mut O, message: &MessageV1) -> Result<(), Error> {
+    match message {
+        MessageV1::None => writeln!(out, "Message: None").map_err(Error::IoError),
+        MessageV1::Plaintext(PlaintextMessageV1 { mime_type, message }) => {
+            writeln!(out, "Message: Plaintext").map_err(Error::IoError)?;
+            writeln!(out, "├─ MIME Type: {}", mime_type).map_err(Error::IoError)?;
+            match message {
+                MessageContentsV1::String(content) => {
+                    writeln!(out, "└─ Content (String): {}", content)
+                }
+                MessageContentsV1::Bytes(content) => {
+                    writeln!(out, "└─ Content (Bytes): {}", hex::encode(content))
+                }
+            }
+            .map_err(Error::IoError)
+        }
+        MessageV1::Encrypted(EncryptedMessageV1 {
+            encrypted,
+            decryptors_by_curve,
+        }) => {
+            writeln!(out, "Message: Encrypted").map_err(Error::IoError)?;
+            writeln!(
+                out,
+                "├─ Encrypted Payload Size: {} bytes",
+                encrypted.0.len()
+            )
+            .map_err(Error::IoError)?;
+            writeln!(out, "└─ Decryptors By Curve: {}", decryptors_by_curve.len())
+                .map_err(Error::IoError)?;
+            for (i, (curve_type, decryptors)) in decryptors_by_curve.iter().enumerate() {
+                let is_last_curve = i == decryptors_by_curve.len() - 1;
+                let indent = if is_last_curve { "   " } else { "   │" };
+                let (dh_ephemeral_public_key, fingerprints) = match decryptors {
+                    DecryptorsByCurve::Ed25519 {
+                        dh_ephemeral_public_key,
+                        decryptors,
+                    } => (dh_ephemeral_public_key.to_string(), decryptors.keys()),
+                    DecryptorsByCurve::Secp256k1 {
+                        dh_ephemeral_public_key,
+                        decryptors,
+                    } => (dh_ephemeral_public_key.to_string(), decryptors.keys()),
+                };
+                writeln!(
+                    out,
+                    "   {} {:?}",
+                    list_item_prefix(is_last_curve),
+                    curve_type
+                )
+                .map_err(Error::IoError)?;
+                writeln!(
+                    out,
+                    "{}  ├─ Ephemeral Public Key: {}",
+                    indent, dh_ephemeral_public_key
+                )
+                .map_err(Error::IoError)?;
+                writeln!(out, "{}  └─ Decryptor Fingerprints:", indent).map_err(Error::IoError)?;
+                let count = fingerprints.len();
+                for (j, fingerprint) in fingerprints.enumerate() {
+                    writeln!(
+                        out,
+                        "{}     {} {}",
+                        indent,
+                        list_item_prefix(j == count - 1),
+                        hex::encode(fingerprint.0)
+                    )
+                    .map_err(Error::IoError)?;
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Returns the signer of the given signature, if it is a valid signature of the signed hash.
+/// Note: for signatures which carry the public key, recovery alone doesn't check the signature.
+fn verified_signer(signed_hash: &Hash, signature: &SignatureWithPublicKeyV1) -> Option<PublicKey> {
+    recover(signed_hash, signature)
+        .filter(|public_key| verify(signed_hash, public_key, &signature.signature()))
+}
+
+fn format_public_key(public_key: &PublicKey) -> String {
+    match public_key {
+        PublicKey::Secp256k1(public_key) => format!("Secp256k1({})", public_key),
+        PublicKey::Ed25519(public_key) => format!("Ed25519({})", public_key),
+        PublicKey::Secp256r1(public_key) => format!("Secp256r1({})", public_key),
+    }
+}
+
+fn list_item_prefix(last: bool) -> &'static str {
+    if last {
+        "└─"
+    } else {
+        "├─"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transaction_builder(notary: &Ed25519PrivateKey) -> TransactionBuilder {
+        TransactionBuilder::new()
+            .header(TransactionHeaderV1 {
+                network_id: NetworkDefinition::simulator().id,
+                start_epoch_inclusive: Epoch::zero(),
+                end_epoch_exclusive: Epoch::of(10),
+                nonce: 5,
+                notary_public_key: notary.public_key().into(),
+                notary_is_signatory: false,
+                tip_percentage: 0,
+            })
+            .manifest(ManifestBuilder::new().lock_fee_from_faucet().build())
+    }
+
+    fn inspect_to_string(payload: &[u8]) -> String {
+        let mut out = Vec::new();
+        inspect(payload, &NetworkDefinition::simulator(), &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn notarized_transaction_signers_are_reported() {
+        // Arrange
+        let signer_1 = Secp256k1PrivateKey::from_u64(1).unwrap();
+        let signer_2 = Ed25519PrivateKey::from_u64(2).unwrap();
+        let notary = Ed25519PrivateKey::from_u64(3).unwrap();
+        let transaction = transaction_builder(&notary)
+            .sign(&signer_1)
+            .sign(&signer_2)
+            .notarize(&notary)
+            .build();
+
+        // Act
+        let report = inspect_to_string(&transaction.to_payload_bytes().unwrap());
+
+        // Assert
+        assert!(report.contains("Payload Type: Notarized Transaction V1"));
+        assert!(report.contains("Intent Signers: 2"));
+        assert!(report.contains(&format_public_key(&signer_1.public_key().into())));
+        assert!(report.contains(&format_public_key(&signer_2.public_key().into())));
+        assert!(!report.contains("<invalid signature>"));
+        assert!(report.contains("Validation (simulator): Valid"));
+    }
+
+    #[test]
+    fn signatures_of_another_hash_are_reported_as_invalid() {
+        // Arrange
+        let signer = Ed25519PrivateKey::from_u64(2).unwrap();
+        let notary = Ed25519PrivateKey::from_u64(3).unwrap();
+        let mut signed_intent = transaction_builder(&notary)
+            .sign(&signer)
+            .notarize(&notary)
+            .build()
+            .signed_intent;
+        signed_intent.intent_signatures.signatures[0] =
+            IntentSignatureV1(signer.sign_with_public_key(&hash("Another intent")));
+
+        // Act
+        let report = inspect_to_string(&signed_intent.to_payload_bytes().unwrap());
+
+        // Assert
+        assert!(report.contains("Payload Type: Signed Intent V1"));
+        assert!(report.contains("Intent Signers: 1"));
+        assert!(report.contains("<invalid signature>"));
+        assert!(!report.contains(&format_public_key(&signer.public_key().into())));
+    }
+
+    #[test]
+    fn verified_signer_checks_the_signed_hash() {
+        let signed_hash = hash("Intent");
+        let other_hash = hash("Another intent");
+        for signer in [
+            PrivateKey::from(Secp256k1PrivateKey::from_u64(1).unwrap()),
+            PrivateKey::from(Ed25519PrivateKey::from_u64(2).unwrap()),
+        ] {
+            let signature = signer.sign_with_public_key(&signed_hash);
+            assert_eq!(
+                verified_signer(&signed_hash, &signature),
+                Some(signer.public_key())
+            );
+            assert_ne!(
+                verified_signer(&other_hash, &signature),
+                Some(signer.public_key())
+            );
+        }
+    }
+}
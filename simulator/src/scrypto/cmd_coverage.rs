@@ -133,7 +133,7 @@ impl Coverage {
         env::set_var("COVERAGE_DIRECTORY", data_path.to_str().unwrap());
 
         // Run tests
-        test_package(path, self.arguments.clone(), true)
+        test_package(path, self.arguments.clone(), true, false)
             .map(|_| ())
             .map_err(Error::TestError)?;
 
@@ -1,5 +1,7 @@
 use clap::Parser;
+use std::env;
 use std::env::current_dir;
+use std::fs;
 use std::path::PathBuf;
 
 use crate::scrypto::*;
@@ -14,14 +16,30 @@ pub struct Test {
     /// The package directory
     #[clap(long)]
     path: Option<PathBuf>,
+
+    /// Profile the WASM execution units consumed by each test, saving a report and a folded
+    /// stacks file (for flame graphs) per test into the given directory
+    #[clap(long)]
+    wasm_profile: Option<PathBuf>,
 }
 
 impl Test {
     pub fn run(&self) -> Result<(), Error> {
+        if let Some(wasm_profile) = &self.wasm_profile {
+            // Tests run in the package directory, so the path must be absolute
+            let profile_path = current_dir().unwrap().join(wasm_profile);
+            if profile_path.exists() {
+                fs::remove_dir_all(&profile_path).map_err(Error::IOError)?;
+            }
+            fs::create_dir_all(&profile_path).map_err(Error::IOError)?;
+            env::set_var("WASM_PROFILING_DIRECTORY", profile_path.to_str().unwrap());
+        }
+
         test_package(
             self.path.clone().unwrap_or(current_dir().unwrap()),
             self.arguments.clone(),
             false,
+            self.wasm_profile.is_some(),
         )
         .map(|_| ())
         .map_err(Error::TestError)
@@ -242,7 +242,12 @@ pub fn build_package<P: AsRef<Path>>(
 }
 
 /// Runs tests within a package.
-pub fn test_package<P: AsRef<Path>, I, S>(path: P, args: I, coverage: bool) -> Result<(), TestError>
+pub fn test_package<P: AsRef<Path>, I, S>(
+    path: P,
+    args: I,
+    coverage: bool,
+    wasm_profiling: bool,
+) -> Result<(), TestError>
 where
     I: IntoIterator<Item = S>,
     S: AsRef<OsStr>,
@@ -255,11 +260,13 @@ where
     let mut cargo = path.as_ref().to_owned();
     cargo.push("Cargo.toml");
     if cargo.exists() {
-        let features = if coverage {
-            vec!["--features", "scrypto-unit/coverage"]
-        } else {
-            vec![]
-        };
+        let mut features = vec![];
+        if coverage {
+            features.extend(["--features", "scrypto-unit/coverage"]);
+        }
+        if wasm_profiling {
+            features.extend(["--features", "scrypto-unit/wasm_profiling"]);
+        }
         let status = Command::new("cargo")
             .arg("test")
             .arg("--release")
@@ -6,6 +6,7 @@ set -e
 cd "$(dirname "$0")/.."
 
 resim="cargo run --bin resim $@ --"
+rtmi="cargo run --bin rtmi $@ --"
 
 # Create test accounts and public keys
 $resim reset
@@ -78,6 +79,15 @@ $resim show $component
 $resim show $account
 $resim show $account2
 $resim show $token_address
+$resim show $account --verify-metadata
+
+# Test - show transaction status
+$resim show-transaction-status --start-epoch 1 --end-epoch 1000
+intent_status=`$resim show-transaction-status 0000000000000000000000000000000000000000000000000000000000000000`
+if [[ ${intent_status} != *"Unknown"* ]];then
+    echo "Intent status not unknown!"
+    exit 1
+fi
 
 # Test - output manifest
 mkdir -p target
@@ -94,6 +104,17 @@ $resim run ./target/temp3.rtm
 $resim generate-key-pair
 $resim run ./target/temp2.rtm --blobs $blobs
 
+# Test - multi-party signing of a partially signed transaction
+key1=`$resim generate-key-pair | awk '/Private key:/ {print $NF}'`
+key2=`$resim generate-key-pair | awk '/Private key:/ {print $NF}'`
+$resim create-partial-transaction ./target/temp3.rtm --output ./target/temp3.pst
+cp ./target/temp3.pst ./target/temp3_copy.pst
+$resim sign-partial-transaction ./target/temp3.pst --signing-keys $key1
+$resim sign-partial-transaction ./target/temp3_copy.pst --signing-keys $key2
+$resim merge-partial-transactions ./target/temp3.pst ./target/temp3_copy.pst --output ./target/temp3_merged.pst
+$resim finalize-partial-transaction ./target/temp3_merged.pst --output ./target/temp3.ntx
+$rtmi ./target/temp3.ntx | grep 'Validation (simulator): Valid'
+
 # Test - nft
 package=`$resim publish ./tests/blueprints --owner-badge $owner_badge | awk '/Package:/ {print $NF}'`
 $resim call-function $package Foo nfts
@@ -11,6 +11,7 @@ use radix_engine_interface::blueprints::account::*;
 use radix_engine_interface::blueprints::consensus_manager::*;
 use radix_engine_interface::blueprints::identity::*;
 use radix_engine_interface::blueprints::package::*;
+use radix_engine_interface::blueprints::pool::*;
 use radix_engine_interface::blueprints::resource::*;
 
 /// A manifest builder for use in tests.
@@ -705,6 +706,27 @@ impl ManifestBuilder {
         )
     }
 
+    /// Updates many mutable fields of many non-fungibles in a single invocation.
+    pub fn batch_update_non_fungible_data<F: Into<String>, D: ManifestEncode>(
+        self,
+        resource_address: impl ResolvableResourceAddress,
+        updates: impl IntoIterator<Item = (NonFungibleLocalId, F, D)>,
+    ) -> Self {
+        let address = resource_address.resolve(&self.registrar);
+        let updates = updates
+            .into_iter()
+            .map(|(id, field_name, data)| {
+                let data = manifest_decode(&manifest_encode(&data).unwrap()).unwrap();
+                (id, field_name.into(), data)
+            })
+            .collect();
+        self.call_method(
+            address,
+            NON_FUNGIBLE_RESOURCE_MANAGER_BATCH_UPDATE_DATA_IDENT,
+            NonFungibleResourceManagerBatchUpdateDataManifestInput { updates },
+        )
+    }
+
     pub fn create_identity_advanced(self, owner_role: OwnerRole) -> Self {
         self.add_instruction(InstructionV1::CallFunction {
             package_address: IDENTITY_PACKAGE.into(),
@@ -791,6 +813,24 @@ impl ManifestBuilder {
         self.call_method(address, VALIDATOR_UNSTAKE_IDENT, (bucket,))
     }
 
+    pub fn redelegate_validator_stake(
+        self,
+        validator_address: impl ResolvableComponentAddress,
+        stake_unit_bucket: impl ExistingManifestBucket,
+        target_validator: ComponentAddress,
+    ) -> Self {
+        let address = validator_address.resolve(&self.registrar);
+        let stake_unit_bucket = stake_unit_bucket.mark_consumed(&self.registrar);
+        self.call_method(
+            address,
+            VALIDATOR_REDELEGATE_IDENT,
+            ValidatorRedelegateManifestInput {
+                stake_unit_bucket,
+                target_validator,
+            },
+        )
+    }
+
     pub fn claim_xrd(
         self,
         validator_address: impl ResolvableComponentAddress,
@@ -1137,6 +1177,120 @@ impl ManifestBuilder {
         })
     }
 
+    pub fn configure_package_blueprint_upgrades(
+        self,
+        package_address: impl ResolvablePackageAddress,
+        blueprint_name: impl Into<String>,
+        timelock_in_minutes: u32,
+    ) -> Self {
+        let address = package_address.resolve(&self.registrar);
+        self.add_instruction(InstructionV1::CallMethod {
+            address: address.into(),
+            method_name: PACKAGE_CONFIGURE_BLUEPRINT_UPGRADES_IDENT.to_string(),
+            args: to_manifest_value_and_unwrap!(&PackageConfigureBlueprintUpgradesInput {
+                blueprint_name: blueprint_name.into(),
+                timelock_in_minutes,
+            }),
+        })
+    }
+
+    pub fn propose_package_blueprint_upgrade(
+        mut self,
+        package_address: impl ResolvablePackageAddress,
+        blueprint_name: impl Into<String>,
+        code: Vec<u8>,
+        definition: BlueprintDefinitionInit,
+    ) -> Self {
+        let address = package_address.resolve(&self.registrar);
+        let code_blob_ref = self.add_blob(code);
+        self.add_instruction(InstructionV1::CallMethod {
+            address: address.into(),
+            method_name: PACKAGE_PROPOSE_BLUEPRINT_UPGRADE_IDENT.to_string(),
+            args: to_manifest_value_and_unwrap!(&PackageProposeBlueprintUpgradeManifestInput {
+                blueprint_name: blueprint_name.into(),
+                definition,
+                code: code_blob_ref,
+            }),
+        })
+    }
+
+    pub fn execute_package_blueprint_upgrade(
+        self,
+        package_address: impl ResolvablePackageAddress,
+        blueprint_name: impl Into<String>,
+    ) -> Self {
+        let address = package_address.resolve(&self.registrar);
+        self.add_instruction(InstructionV1::CallMethod {
+            address: address.into(),
+            method_name: PACKAGE_EXECUTE_BLUEPRINT_UPGRADE_IDENT.to_string(),
+            args: to_manifest_value_and_unwrap!(&PackageExecuteBlueprintUpgradeInput {
+                blueprint_name: blueprint_name.into(),
+            }),
+        })
+    }
+
+    pub fn cancel_package_blueprint_upgrade(
+        self,
+        package_address: impl ResolvablePackageAddress,
+        blueprint_name: impl Into<String>,
+    ) -> Self {
+        let address = package_address.resolve(&self.registrar);
+        self.add_instruction(InstructionV1::CallMethod {
+            address: address.into(),
+            method_name: PACKAGE_CANCEL_BLUEPRINT_UPGRADE_IDENT.to_string(),
+            args: to_manifest_value_and_unwrap!(&PackageCancelBlueprintUpgradeInput {
+                blueprint_name: blueprint_name.into(),
+            }),
+        })
+    }
+
+    pub fn set_package_royalty_split(
+        self,
+        package_address: impl ResolvablePackageAddress,
+        blueprint_name: impl Into<String>,
+        recipients: IndexMap<GlobalAddress, Decimal>,
+    ) -> Self {
+        let address = package_address.resolve(&self.registrar);
+        self.add_instruction(InstructionV1::CallMethod {
+            address: address.into(),
+            method_name: PACKAGE_SET_ROYALTY_SPLIT_IDENT.to_string(),
+            args: to_manifest_value_and_unwrap!(&PackageSetRoyaltySplitInput {
+                blueprint_name: blueprint_name.into(),
+                recipients,
+            }),
+        })
+    }
+
+    pub fn lock_package_royalty_split(
+        self,
+        package_address: impl ResolvablePackageAddress,
+        blueprint_name: impl Into<String>,
+    ) -> Self {
+        let address = package_address.resolve(&self.registrar);
+        self.add_instruction(InstructionV1::CallMethod {
+            address: address.into(),
+            method_name: PACKAGE_LOCK_ROYALTY_SPLIT_IDENT.to_string(),
+            args: to_manifest_value_and_unwrap!(&PackageLockRoyaltySplitInput {
+                blueprint_name: blueprint_name.into(),
+            }),
+        })
+    }
+
+    pub fn claim_package_royalty_share(
+        self,
+        package_address: impl ResolvablePackageAddress,
+        recipient: impl Into<GlobalAddress>,
+    ) -> Self {
+        let address = package_address.resolve(&self.registrar);
+        self.add_instruction(InstructionV1::CallMethod {
+            address: address.into(),
+            method_name: PACKAGE_CLAIM_ROYALTY_SHARE_IDENT.to_string(),
+            args: to_manifest_value_and_unwrap!(&PackageClaimRoyaltyShareInput {
+                recipient: recipient.into(),
+            }),
+        })
+    }
+
     pub fn set_component_royalty(
         self,
         component_address: impl ResolvableComponentAddress,
@@ -1181,6 +1335,53 @@ impl ManifestBuilder {
         })
     }
 
+    pub fn set_component_royalty_split(
+        self,
+        component_address: impl ResolvableComponentAddress,
+        method: impl Into<String>,
+        recipients: IndexMap<GlobalAddress, Decimal>,
+    ) -> Self {
+        let address = component_address.resolve(&self.registrar);
+        self.add_instruction(InstructionV1::CallRoyaltyMethod {
+            address: address.into(),
+            method_name: COMPONENT_ROYALTY_SET_ROYALTY_SPLIT_IDENT.to_string(),
+            args: to_manifest_value_and_unwrap!(&ComponentRoyaltySetSplitInput {
+                method: method.into(),
+                recipients,
+            }),
+        })
+    }
+
+    pub fn lock_component_royalty_split(
+        self,
+        component_address: impl ResolvableComponentAddress,
+        method: impl Into<String>,
+    ) -> Self {
+        let address = component_address.resolve(&self.registrar);
+        self.add_instruction(InstructionV1::CallRoyaltyMethod {
+            address: address.into(),
+            method_name: COMPONENT_ROYALTY_LOCK_ROYALTY_SPLIT_IDENT.to_string(),
+            args: to_manifest_value_and_unwrap!(&ComponentRoyaltyLockSplitInput {
+                method: method.into(),
+            }),
+        })
+    }
+
+    pub fn claim_component_royalty_share(
+        self,
+        component_address: impl ResolvableComponentAddress,
+        recipient: impl Into<GlobalAddress>,
+    ) -> Self {
+        let address = component_address.resolve(&self.registrar);
+        self.add_instruction(InstructionV1::CallRoyaltyMethod {
+            address: address.into(),
+            method_name: COMPONENT_ROYALTY_CLAIM_ROYALTY_SHARE_IDENT.to_string(),
+            args: to_manifest_value_and_unwrap!(&ComponentClaimRoyaltyShareInput {
+                recipient: recipient.into(),
+            }),
+        })
+    }
+
     pub fn set_metadata(
         self,
         address: impl ResolvableGlobalAddress,
@@ -1756,6 +1957,78 @@ impl ManifestBuilder {
         })
     }
 
+    /// Grants a withdrawal allowance of a resource of an account.
+    pub fn set_account_allowance(
+        self,
+        account_address: impl ResolvableComponentAddress,
+        grantee: AllowanceGrantee,
+        resource_address: impl ResolvableResourceAddress,
+        amount: Option<Decimal>,
+        reset_period: Option<u64>,
+        expiry_epoch: Option<Epoch>,
+    ) -> Self {
+        let address = account_address.resolve(&self.registrar);
+        let resource_address = resource_address.resolve_static(&self.registrar);
+        let args = to_manifest_value_and_unwrap!(&AccountSetAllowanceInput {
+            grantee,
+            resource_address,
+            amount,
+            reset_period,
+            expiry_epoch,
+        });
+
+        self.add_instruction(InstructionV1::CallMethod {
+            address: address.into(),
+            method_name: ACCOUNT_SET_ALLOWANCE_IDENT.to_string(),
+            args,
+        })
+    }
+
+    /// Removes a withdrawal allowance of a resource of an account.
+    pub fn remove_account_allowance(
+        self,
+        account_address: impl ResolvableComponentAddress,
+        grantee: AllowanceGrantee,
+        resource_address: impl ResolvableResourceAddress,
+    ) -> Self {
+        let address = account_address.resolve(&self.registrar);
+        let resource_address = resource_address.resolve_static(&self.registrar);
+        let args = to_manifest_value_and_unwrap!(&AccountRemoveAllowanceInput {
+            grantee,
+            resource_address,
+        });
+
+        self.add_instruction(InstructionV1::CallMethod {
+            address: address.into(),
+            method_name: ACCOUNT_REMOVE_ALLOWANCE_IDENT.to_string(),
+            args,
+        })
+    }
+
+    /// Withdraws resource from an account by spending an allowance granted by the account.
+    pub fn withdraw_from_account_with_allowance(
+        self,
+        account_address: impl ResolvableComponentAddress,
+        grantee: AllowanceGrantee,
+        resource_address: impl ResolvableResourceAddress,
+        amount: impl ResolvableDecimal,
+    ) -> Self {
+        let address = account_address.resolve(&self.registrar);
+        let resource_address = resource_address.resolve_static(&self.registrar);
+        let amount = amount.resolve();
+        let args = to_manifest_value_and_unwrap!(&AccountWithdrawWithAllowanceInput {
+            grantee,
+            resource_address,
+            amount,
+        });
+
+        self.add_instruction(InstructionV1::CallMethod {
+            address: address.into(),
+            method_name: ACCOUNT_WITHDRAW_WITH_ALLOWANCE_IDENT.to_string(),
+            args,
+        })
+    }
+
     /// Withdraws resource from an account.
     pub fn burn_in_account(
         self,
@@ -2014,6 +2287,206 @@ impl ManifestBuilder {
         )
     }
 
+    pub fn contribute_access_controller_recovery_fee(
+        self,
+        access_controller_address: impl ResolvableComponentAddress,
+        bucket: impl ExistingManifestBucket,
+    ) -> Self {
+        let address = access_controller_address.resolve(&self.registrar);
+        let bucket = bucket.mark_consumed(&self.registrar);
+        self.call_method(
+            address,
+            ACCESS_CONTROLLER_CONTRIBUTE_RECOVERY_FEE_IDENT,
+            AccessControllerContributeRecoveryFeeManifestInput { bucket },
+        )
+    }
+
+    pub fn lock_access_controller_recovery_fee(
+        self,
+        access_controller_address: impl ResolvableComponentAddress,
+        amount: impl ResolvableDecimal,
+    ) -> Self {
+        let address = access_controller_address.resolve(&self.registrar);
+        let amount = amount.resolve();
+        self.call_method(
+            address,
+            ACCESS_CONTROLLER_LOCK_RECOVERY_FEE_IDENT,
+            AccessControllerLockRecoveryFeeInput { amount },
+        )
+    }
+
+    pub fn withdraw_access_controller_recovery_fee(
+        self,
+        access_controller_address: impl ResolvableComponentAddress,
+        amount: impl ResolvableDecimal,
+    ) -> Self {
+        let address = access_controller_address.resolve(&self.registrar);
+        let amount = amount.resolve();
+        self.call_method(
+            address,
+            ACCESS_CONTROLLER_WITHDRAW_RECOVERY_FEE_IDENT,
+            AccessControllerWithdrawRecoveryFeeInput { amount },
+        )
+    }
+
+    pub fn create_constant_product_pool(
+        self,
+        owner_role: OwnerRole,
+        fee_manager_rule: AccessRule,
+        resource_addresses: (ResourceAddress, ResourceAddress),
+        fee_rate: impl ResolvableDecimal,
+    ) -> Self {
+        let fee_rate = fee_rate.resolve();
+        self.call_function(
+            POOL_PACKAGE,
+            CONSTANT_PRODUCT_POOL_BLUEPRINT,
+            CONSTANT_PRODUCT_POOL_INSTANTIATE_IDENT,
+            ConstantProductPoolInstantiateManifestInput {
+                owner_role,
+                fee_manager_rule,
+                resource_addresses,
+                fee_rate,
+                address_reservation: None,
+            },
+        )
+    }
+
+    pub fn contribute_to_constant_product_pool(
+        self,
+        pool_address: impl ResolvableComponentAddress,
+        bucket1: impl ExistingManifestBucket,
+        bucket2: impl ExistingManifestBucket,
+    ) -> Self {
+        let address = pool_address.resolve(&self.registrar);
+        let bucket1 = bucket1.mark_consumed(&self.registrar);
+        let bucket2 = bucket2.mark_consumed(&self.registrar);
+        self.call_method(
+            address,
+            CONSTANT_PRODUCT_POOL_CONTRIBUTE_IDENT,
+            ConstantProductPoolContributeManifestInput {
+                buckets: (bucket1, bucket2),
+            },
+        )
+    }
+
+    pub fn redeem_from_constant_product_pool(
+        self,
+        pool_address: impl ResolvableComponentAddress,
+        bucket: impl ExistingManifestBucket,
+    ) -> Self {
+        let address = pool_address.resolve(&self.registrar);
+        let bucket = bucket.mark_consumed(&self.registrar);
+        self.call_method(
+            address,
+            CONSTANT_PRODUCT_POOL_REDEEM_IDENT,
+            ConstantProductPoolRedeemManifestInput { bucket },
+        )
+    }
+
+    /// Swaps the whole input bucket, failing if less than `minimum_output_amount` is returned.
+    pub fn swap_with_constant_product_pool(
+        self,
+        pool_address: impl ResolvableComponentAddress,
+        input_bucket: impl ExistingManifestBucket,
+        minimum_output_amount: impl ResolvableDecimal,
+    ) -> Self {
+        let address = pool_address.resolve(&self.registrar);
+        let input_bucket = input_bucket.mark_consumed(&self.registrar);
+        let minimum_output_amount = minimum_output_amount.resolve();
+        self.call_method(
+            address,
+            CONSTANT_PRODUCT_POOL_SWAP_IDENT,
+            ConstantProductPoolSwapManifestInput {
+                input_bucket,
+                minimum_output_amount,
+            },
+        )
+    }
+
+    /// Swaps as little of the input bucket as needed to get exactly `output_amount`, failing if
+    /// the input bucket does not hold enough. The rest of the input bucket is returned.
+    pub fn swap_with_constant_product_pool_for_exact_output(
+        self,
+        pool_address: impl ResolvableComponentAddress,
+        input_bucket: impl ExistingManifestBucket,
+        output_amount: impl ResolvableDecimal,
+    ) -> Self {
+        let address = pool_address.resolve(&self.registrar);
+        let input_bucket = input_bucket.mark_consumed(&self.registrar);
+        let output_amount = output_amount.resolve();
+        self.call_method(
+            address,
+            CONSTANT_PRODUCT_POOL_SWAP_FOR_EXACT_OUTPUT_IDENT,
+            ConstantProductPoolSwapForExactOutputManifestInput {
+                input_bucket,
+                output_amount,
+            },
+        )
+    }
+
+    pub fn set_constant_product_pool_fee_rate(
+        self,
+        pool_address: impl ResolvableComponentAddress,
+        fee_rate: impl ResolvableDecimal,
+    ) -> Self {
+        let address = pool_address.resolve(&self.registrar);
+        let fee_rate = fee_rate.resolve();
+        self.call_method(
+            address,
+            CONSTANT_PRODUCT_POOL_SET_FEE_RATE_IDENT,
+            ConstantProductPoolSetFeeRateManifestInput { fee_rate },
+        )
+    }
+
+    /// Locks the whole bucket in a new fungible vesting component. Without a `revoker_rule`, the
+    /// vesting can never be revoked.
+    pub fn create_fungible_vesting(
+        self,
+        owner_role: OwnerRole,
+        bucket: impl ExistingManifestBucket,
+        schedule: VestingSchedule,
+        beneficiary_rule: AccessRule,
+        revoker_rule: Option<AccessRule>,
+    ) -> Self {
+        let bucket = bucket.mark_consumed(&self.registrar);
+        self.call_function(
+            RESOURCE_PACKAGE,
+            FUNGIBLE_VESTING_BLUEPRINT,
+            FUNGIBLE_VESTING_CREATE_IDENT,
+            FungibleVestingCreateManifestInput {
+                owner_role,
+                bucket,
+                schedule,
+                beneficiary_rule,
+                revoker_rule,
+                address_reservation: None,
+            },
+        )
+    }
+
+    pub fn withdraw_from_fungible_vesting(
+        self,
+        vesting_address: impl ResolvableComponentAddress,
+        amount: impl ResolvableDecimal,
+    ) -> Self {
+        let address = vesting_address.resolve(&self.registrar);
+        let amount = amount.resolve();
+        self.call_method(
+            address,
+            FUNGIBLE_VESTING_WITHDRAW_IDENT,
+            FungibleVestingWithdrawManifestInput { amount },
+        )
+    }
+
+    pub fn revoke_fungible_vesting(self, vesting_address: impl ResolvableComponentAddress) -> Self {
+        let address = vesting_address.resolve(&self.registrar);
+        self.call_method(
+            address,
+            FUNGIBLE_VESTING_REVOKE_IDENT,
+            FungibleVestingRevokeManifestInput {},
+        )
+    }
+
     /// Builds a transaction manifest.
     pub fn build(self) -> TransactionManifestV1 {
         let manifest = TransactionManifestV1 {
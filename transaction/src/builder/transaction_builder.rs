@@ -1,5 +1,8 @@
+#[cfg(feature = "std")]
+use crate::encryption::MessageEncryptionError;
 use crate::model::*;
 use crate::signing::Signer;
+use radix_engine_common::crypto::PublicKey;
 
 pub struct TransactionBuilder {
     manifest: Option<TransactionManifestV1>,
@@ -35,6 +38,19 @@ impl TransactionBuilder {
         self
     }
 
+    /// Encrypts the message so that it can only be read by the given decryptors.
+    /// Fails if there are no decryptors, or if any of them is invalid or duplicated.
+    #[cfg(feature = "std")]
+    pub fn encrypted_message(
+        mut self,
+        message: PlaintextMessageV1,
+        decryptors: &[PublicKey],
+    ) -> Result<Self, MessageEncryptionError> {
+        let encrypted = crate::encryption::encrypt_message(&message, decryptors)?;
+        self.message = Some(MessageV1::Encrypted(encrypted));
+        Ok(self)
+    }
+
     pub fn sign<S: Signer>(mut self, signer: &S) -> Self {
         let intent = self.transaction_intent();
         let prepared = intent.prepare().expect("Intent could be prepared");
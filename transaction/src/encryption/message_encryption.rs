@@ -0,0 +1,451 @@
+use crate::internal_prelude::*;
+use crate::signing::PrivateKey;
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes128Gcm, Nonce};
+use aes_kw::KekAes256;
+use blake2::digest::consts::U32;
+use blake2::Blake2b;
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use curve25519_dalek::scalar::Scalar;
+use hkdf::SimpleHkdf;
+use rand_core::{CryptoRng, RngCore};
+
+//=================================================================================
+// NOTE:
+// This implements the "MultiPartyECIES" scheme documented on `EncryptedMessageV1`.
+//=================================================================================
+
+const AES_GCM_KEY_LENGTH: usize = 16;
+const AES_GCM_NONCE_LENGTH: usize = 12;
+const AES_GCM_TAG_LENGTH: usize = 16;
+const KEY_ENCRYPTING_KEY_LENGTH: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MessageEncryptionError {
+    NoDecryptors,
+    DuplicateDecryptor(PublicKey),
+    InvalidDecryptorPublicKey(PublicKey),
+    UnsupportedDecryptorCurve(PublicKey),
+    EncodeError(EncodeError),
+    EncryptionFailed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MessageDecryptionError {
+    UnsupportedPrivateKeyCurve,
+    NoDecryptorsForCurveType(CurveType),
+    NotADecryptor(PublicKeyFingerprint),
+    InvalidEphemeralPublicKey,
+    KeyUnwrapFailed,
+    InvalidPayloadLength,
+    DecryptionFailed,
+    DecodeError(DecodeError),
+}
+
+/// Encrypts a plaintext message for the given decryptors, using the operating system's
+/// randomness for the ephemeral keys.
+#[cfg(feature = "std")]
+pub fn encrypt_message(
+    message: &PlaintextMessageV1,
+    decryptors: &[PublicKey],
+) -> Result<EncryptedMessageV1, MessageEncryptionError> {
+    encrypt_message_with_rng(message, decryptors, &mut rand_core::OsRng)
+}
+
+/// Encrypts a plaintext message for the given decryptors.
+///
+/// A fresh AES-GCM key and nonce are generated per message, and a fresh ephemeral
+/// Diffie-Hellman key is generated per curve type used by the decryptors.
+pub fn encrypt_message_with_rng<R: RngCore + CryptoRng>(
+    message: &PlaintextMessageV1,
+    decryptors: &[PublicKey],
+    rng: &mut R,
+) -> Result<EncryptedMessageV1, MessageEncryptionError> {
+    if decryptors.is_empty() {
+        return Err(MessageEncryptionError::NoDecryptors);
+    }
+
+    let payload = manifest_encode(message).map_err(MessageEncryptionError::EncodeError)?;
+    let mut aes_key = [0u8; AES_GCM_KEY_LENGTH];
+    rng.fill_bytes(&mut aes_key);
+    let mut nonce = [0u8; AES_GCM_NONCE_LENGTH];
+    rng.fill_bytes(&mut nonce);
+
+    let cipher_and_tag = Aes128Gcm::new(&aes_key.into())
+        .encrypt(Nonce::from_slice(&nonce), payload.as_slice())
+        .map_err(|_| MessageEncryptionError::EncryptionFailed)?;
+    let mut encrypted = nonce.to_vec();
+    encrypted.extend(cipher_and_tag);
+
+    let mut ed25519_decryptors = index_map_new();
+    let mut secp256k1_decryptors = index_map_new();
+    let mut ed25519_ephemeral_key = None;
+    let mut secp256k1_ephemeral_key = None;
+
+    for decryptor in decryptors {
+        let fingerprint = PublicKeyFingerprint::from(*decryptor);
+        let (shared_secret, decryptors_of_curve) = match decryptor {
+            PublicKey::Ed25519(public_key) => {
+                let ephemeral_key =
+                    ed25519_ephemeral_key.get_or_insert_with(|| new_ed25519_private_key(rng));
+                let shared_secret = ed25519_ecdh(ephemeral_key, public_key).ok_or(
+                    MessageEncryptionError::InvalidDecryptorPublicKey(*decryptor),
+                )?;
+                (shared_secret, &mut ed25519_decryptors)
+            }
+            PublicKey::Secp256k1(public_key) => {
+                let ephemeral_key =
+                    secp256k1_ephemeral_key.get_or_insert_with(|| new_secp256k1_private_key(rng));
+                let shared_secret = secp256k1_ecdh(ephemeral_key, public_key).ok_or(
+                    MessageEncryptionError::InvalidDecryptorPublicKey(*decryptor),
+                )?;
+                (shared_secret, &mut secp256k1_decryptors)
+            }
+            PublicKey::Secp256r1(_) => {
+                return Err(MessageEncryptionError::UnsupportedDecryptorCurve(
+                    *decryptor,
+                ))
+            }
+        };
+
+        let mut wrapped_key = [0u8; AesWrapped128BitKey::LENGTH];
+        KekAes256::from(derive_key_encrypting_key(&shared_secret))
+            .wrap(&aes_key, &mut wrapped_key)
+            .map_err(|_| MessageEncryptionError::EncryptionFailed)?;
+        if decryptors_of_curve
+            .insert(fingerprint, AesWrapped128BitKey(wrapped_key))
+            .is_some()
+        {
+            return Err(MessageEncryptionError::DuplicateDecryptor(*decryptor));
+        }
+    }
+
+    let mut decryptors_by_curve = index_map_new();
+    if let Some(ephemeral_key) = ed25519_ephemeral_key {
+        decryptors_by_curve.insert(
+            CurveType::Ed25519,
+            DecryptorsByCurve::Ed25519 {
+                dh_ephemeral_public_key: ephemeral_key.public_key(),
+                decryptors: ed25519_decryptors,
+            },
+        );
+    }
+    if let Some(ephemeral_key) = secp256k1_ephemeral_key {
+        decryptors_by_curve.insert(
+            CurveType::Secp256k1,
+            DecryptorsByCurve::Secp256k1 {
+                dh_ephemeral_public_key: ephemeral_key.public_key(),
+                decryptors: secp256k1_decryptors,
+            },
+        );
+    }
+
+    Ok(EncryptedMessageV1 {
+        encrypted: AesGcmPayload(encrypted),
+        decryptors_by_curve,
+    })
+}
+
+/// Decrypts an encrypted message with the private key of one of its decryptors.
+pub fn decrypt_message(
+    message: &EncryptedMessageV1,
+    private_key: &PrivateKey,
+) -> Result<PlaintextMessageV1, MessageDecryptionError> {
+    let fingerprint = PublicKeyFingerprint::from(private_key.public_key());
+    let curve_type = match private_key {
+        PrivateKey::Ed25519(_) => CurveType::Ed25519,
+        PrivateKey::Secp256k1(_) => CurveType::Secp256k1,
+        PrivateKey::Secp256r1(_) => return Err(MessageDecryptionError::UnsupportedPrivateKeyCurve),
+    };
+    let decryptors_of_curve = message
+        .decryptors_by_curve
+        .get(&curve_type)
+        .ok_or(MessageDecryptionError::NoDecryptorsForCurveType(curve_type))?;
+
+    let (shared_secret, wrapped_key) = match (private_key, decryptors_of_curve) {
+        (
+            PrivateKey::Ed25519(private_key),
+            DecryptorsByCurve::Ed25519 {
+                dh_ephemeral_public_key,
+                decryptors,
+            },
+        ) => (
+            ed25519_ecdh(private_key, dh_ephemeral_public_key),
+            decryptors.get(&fingerprint),
+        ),
+        (
+            PrivateKey::Secp256k1(private_key),
+            DecryptorsByCurve::Secp256k1 {
+                dh_ephemeral_public_key,
+                decryptors,
+            },
+        ) => (
+            secp256k1_ecdh(private_key, dh_ephemeral_public_key),
+            decryptors.get(&fingerprint),
+        ),
+        _ => return Err(MessageDecryptionError::NoDecryptorsForCurveType(curve_type)),
+    };
+    let wrapped_key = wrapped_key.ok_or(MessageDecryptionError::NotADecryptor(fingerprint))?;
+    let shared_secret = shared_secret.ok_or(MessageDecryptionError::InvalidEphemeralPublicKey)?;
+
+    let mut aes_key = [0u8; AES_GCM_KEY_LENGTH];
+    KekAes256::from(derive_key_encrypting_key(&shared_secret))
+        .unwrap(&wrapped_key.0, &mut aes_key)
+        .map_err(|_| MessageDecryptionError::KeyUnwrapFailed)?;
+
+    let encrypted = &message.encrypted.0;
+    if encrypted.len() < AES_GCM_NONCE_LENGTH + AES_GCM_TAG_LENGTH {
+        return Err(MessageDecryptionError::InvalidPayloadLength);
+    }
+    let (nonce, cipher_and_tag) = encrypted.split_at(AES_GCM_NONCE_LENGTH);
+    let payload = Aes128Gcm::new(&aes_key.into())
+        .decrypt(Nonce::from_slice(nonce), cipher_and_tag)
+        .map_err(|_| MessageDecryptionError::DecryptionFailed)?;
+
+    manifest_decode(&payload).map_err(MessageDecryptionError::DecodeError)
+}
+
+/// `KEK = HKDF(hash: Blake2b, secret: x co-ord of G, salt: [], length: 256 bits)`
+fn derive_key_encrypting_key(shared_secret: &[u8; 32]) -> [u8; KEY_ENCRYPTING_KEY_LENGTH] {
+    let mut key_encrypting_key = [0u8; KEY_ENCRYPTING_KEY_LENGTH];
+    SimpleHkdf::<Blake2b<U32>>::new(Some(&[]), shared_secret)
+        .expand(&[], &mut key_encrypting_key)
+        .expect("32 bytes is a valid HKDF output length");
+    key_encrypting_key
+}
+
+/// Returns the `x` co-ordinate of the shared point, as per the X9.63 variant of ECDH.
+fn secp256k1_ecdh(
+    private_key: &Secp256k1PrivateKey,
+    public_key: &Secp256k1PublicKey,
+) -> Option<[u8; 32]> {
+    let secret_key = secp256k1::SecretKey::from_slice(&private_key.to_bytes()).ok()?;
+    let public_key = secp256k1::PublicKey::from_slice(&public_key.0).ok()?;
+    let shared_point = secp256k1::ecdh::shared_secret_point(&public_key, &secret_key);
+    Some(copy_u8_array(&shared_point[..32]))
+}
+
+/// Returns the `u` co-ordinate of the shared point on Curve25519, using the birationally
+/// equivalent Montgomery form of the Ed25519 keys (as in X25519).
+fn ed25519_ecdh(
+    private_key: &Ed25519PrivateKey,
+    public_key: &Ed25519PublicKey,
+) -> Option<[u8; 32]> {
+    let secret_key = ed25519_dalek::SecretKey::from_bytes(&private_key.to_bytes()).ok()?;
+    let expanded_secret_key = ed25519_dalek::ExpandedSecretKey::from(&secret_key);
+    let scalar = Scalar::from_bits(copy_u8_array(&expanded_secret_key.to_bytes()[..32]));
+    let point = CompressedEdwardsY(public_key.0).decompress()?;
+    Some((point.to_montgomery() * scalar).to_bytes())
+}
+
+fn new_ed25519_private_key<R: RngCore + CryptoRng>(rng: &mut R) -> Ed25519PrivateKey {
+    let mut bytes = [0u8; Ed25519PrivateKey::LENGTH];
+    rng.fill_bytes(&mut bytes);
+    Ed25519PrivateKey::from_bytes(&bytes).expect("Any 32 bytes are a valid Ed25519 key")
+}
+
+fn new_secp256k1_private_key<R: RngCore + CryptoRng>(rng: &mut R) -> Secp256k1PrivateKey {
+    loop {
+        let mut bytes = [0u8; Secp256k1PrivateKey::LENGTH];
+        rng.fill_bytes(&mut bytes);
+        // Fails with negligible probability, if the bytes are zero or exceed the curve order
+        if let Ok(private_key) = Secp256k1PrivateKey::from_bytes(&bytes) {
+            return private_key;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::TransactionBuilder;
+
+    fn plaintext_message() -> PlaintextMessageV1 {
+        PlaintextMessageV1 {
+            mime_type: "text/plain".to_owned(),
+            message: MessageContentsV1::String("Private memo".to_owned()),
+        }
+    }
+
+    /// Yields the bytes `0, 1, 2, ...`, so that the encryption is reproducible.
+    struct CountingRng(u8);
+
+    impl RngCore for CountingRng {
+        fn next_u32(&mut self) -> u32 {
+            rand_core::impls::next_u32_via_fill(self)
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            rand_core::impls::next_u64_via_fill(self)
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            for byte in dest {
+                *byte = self.0;
+                self.0 = self.0.wrapping_add(1);
+            }
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    impl CryptoRng for CountingRng {}
+
+    #[test]
+    fn message_encryption_matches_test_vector() {
+        // The AES key is bytes 0..16 and the nonce is bytes 16..28 of the RNG output, followed
+        // by the Ed25519 and Secp256k1 ephemeral private keys
+        let ed25519_decryptor = Ed25519PrivateKey::from_u64(1).unwrap();
+        let secp256k1_decryptor = Secp256k1PrivateKey::from_u64(1).unwrap();
+        let decryptors: Vec<PublicKey> = vec![
+            ed25519_decryptor.public_key().into(),
+            secp256k1_decryptor.public_key().into(),
+        ];
+
+        let encrypted =
+            encrypt_message_with_rng(&plaintext_message(), &decryptors, &mut CountingRng(0))
+                .unwrap();
+
+        let expected = EncryptedMessageV1 {
+            encrypted: AesGcmPayload(
+                hex::decode(
+                    "101112131415161718191a1b890f01a3053bd39763f22d99a64e851c3abd788b668602c9e4\
+                     bf4c310a607cc2dc6324838776ec388d3867d9bce16ae7",
+                )
+                .unwrap(),
+            ),
+            decryptors_by_curve: indexmap!(
+                CurveType::Ed25519 => DecryptorsByCurve::Ed25519 {
+                    dh_ephemeral_public_key: Ed25519PublicKey::from_str(
+                        "a8d29047e38586d402f41f9906a2c6ddc2589fdf64e9b73dabd23d35bc7cd41d",
+                    )
+                    .unwrap(),
+                    decryptors: indexmap!(
+                        fingerprint("f91fa8df2486c9ea") =>
+                            wrapped_key("1e04335c8707d861c91dcd1c4c84c3c8d28d86eee19a17a9")
+                    ),
+                },
+                CurveType::Secp256k1 => DecryptorsByCurve::Secp256k1 {
+                    dh_ephemeral_public_key: Secp256k1PublicKey::from_str(
+                        "02a4ded95f487c2c5bb688d106218b11cffeca11fb8c622c862ae238265ea25ad5",
+                    )
+                    .unwrap(),
+                    decryptors: indexmap!(
+                        fingerprint("a243c1128c2fe737") =>
+                            wrapped_key("8e296fd36a7ae09745881366359de2bcc05b7fa08344ed22")
+                    ),
+                }
+            ),
+        };
+        assert_eq!(encrypted, expected);
+        assert_eq!(
+            decrypt_message(&expected, &ed25519_decryptor.into()),
+            Ok(plaintext_message())
+        );
+        assert_eq!(
+            decrypt_message(&expected, &secp256k1_decryptor.into()),
+            Ok(plaintext_message())
+        );
+    }
+
+    fn fingerprint(bytes: &str) -> PublicKeyFingerprint {
+        PublicKeyFingerprint(copy_u8_array(&hex::decode(bytes).unwrap()))
+    }
+
+    fn wrapped_key(bytes: &str) -> AesWrapped128BitKey {
+        AesWrapped128BitKey(copy_u8_array(&hex::decode(bytes).unwrap()))
+    }
+
+    #[test]
+    fn message_can_be_decrypted_by_every_decryptor_on_both_curves() {
+        let decryptors: Vec<PrivateKey> = vec![
+            Secp256k1PrivateKey::from_u64(1).unwrap().into(),
+            Secp256k1PrivateKey::from_u64(2).unwrap().into(),
+            Ed25519PrivateKey::from_u64(3).unwrap().into(),
+            Ed25519PrivateKey::from_u64(4).unwrap().into(),
+        ];
+        let public_keys: Vec<PublicKey> = decryptors.iter().map(|k| k.public_key()).collect();
+
+        let encrypted = encrypt_message(&plaintext_message(), &public_keys).unwrap();
+        assert_eq!(encrypted.decryptors_by_curve.len(), 2);
+        for decryptor in &decryptors {
+            assert_eq!(
+                decrypt_message(&encrypted, decryptor),
+                Ok(plaintext_message())
+            );
+        }
+
+        // The encrypted message passes the validator's shape checks
+        NotarizedTransactionValidator::new(ValidationConfig::simulator())
+            .validate_message_v1(&MessageV1::Encrypted(encrypted))
+            .unwrap();
+    }
+
+    #[test]
+    fn message_cannot_be_decrypted_by_others() {
+        let decryptor = Ed25519PrivateKey::from_u64(1).unwrap();
+        let encrypted =
+            encrypt_message(&plaintext_message(), &[decryptor.public_key().into()]).unwrap();
+
+        let other: PrivateKey = Ed25519PrivateKey::from_u64(2).unwrap().into();
+        assert_eq!(
+            decrypt_message(&encrypted, &other),
+            Err(MessageDecryptionError::NotADecryptor(
+                other.public_key().into()
+            ))
+        );
+        let other_curve: PrivateKey = Secp256k1PrivateKey::from_u64(1).unwrap().into();
+        assert_eq!(
+            decrypt_message(&encrypted, &other_curve),
+            Err(MessageDecryptionError::NoDecryptorsForCurveType(
+                CurveType::Secp256k1
+            ))
+        );
+
+        let mut tampered = encrypted.clone();
+        *tampered.encrypted.0.last_mut().unwrap() ^= 1;
+        assert_eq!(
+            decrypt_message(&tampered, &decryptor.into()),
+            Err(MessageDecryptionError::DecryptionFailed)
+        );
+    }
+
+    #[test]
+    fn duplicate_decryptors_are_rejected() {
+        let public_key: PublicKey = Secp256k1PrivateKey::from_u64(1)
+            .unwrap()
+            .public_key()
+            .into();
+        assert_eq!(
+            encrypt_message(&plaintext_message(), &[public_key, public_key]),
+            Err(MessageEncryptionError::DuplicateDecryptor(public_key))
+        );
+        assert_eq!(
+            encrypt_message(&plaintext_message(), &[]),
+            Err(MessageEncryptionError::NoDecryptors)
+        );
+    }
+
+    #[test]
+    fn transaction_builder_returns_encryption_errors() {
+        let public_key: PublicKey = Ed25519PrivateKey::from_u64(1).unwrap().public_key().into();
+        let invalid_public_key: PublicKey = Secp256k1PublicKey([0u8; 33]).into();
+
+        assert!(matches!(
+            TransactionBuilder::new().encrypted_message(plaintext_message(), &[public_key]),
+            Ok(_)
+        ));
+        assert!(matches!(
+            TransactionBuilder::new()
+                .encrypted_message(plaintext_message(), &[public_key, public_key]),
+            Err(MessageEncryptionError::DuplicateDecryptor(key)) if key == public_key
+        ));
+        assert!(matches!(
+            TransactionBuilder::new().encrypted_message(plaintext_message(), &[invalid_public_key]),
+            Err(MessageEncryptionError::InvalidDecryptorPublicKey(key)) if key == invalid_public_key
+        ));
+    }
+}
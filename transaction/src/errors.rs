@@ -16,6 +16,7 @@ pub enum SignatureValidationError {
     InvalidIntentSignature,
     InvalidNotarySignature,
     DuplicateSigner,
+    Secp256r1SignaturesNotAllowed,
     SerializationError(EncodeError),
 }
 
@@ -97,3 +98,26 @@ pub enum InvalidMessageError {
         curve_type: CurveType,
     },
 }
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartiallySignedTransactionError {
+    PrepareError(PrepareError),
+    IntentHashMismatch {
+        expected: IntentHash,
+        actual: IntentHash,
+    },
+    InvalidIntentSignature(SignatureWithPublicKeyV1),
+    DuplicateSigner(PublicKey),
+    TooManySignatures,
+    NotaryPublicKeyMismatch {
+        expected: PublicKey,
+        actual: PublicKey,
+    },
+    InvalidNotarySignature,
+}
+
+impl From<PrepareError> for PartiallySignedTransactionError {
+    fn from(value: PrepareError) -> Self {
+        Self::PrepareError(value)
+    }
+}
@@ -1,5 +1,6 @@
 pub mod builder;
 pub mod data;
+pub mod encryption;
 pub mod errors;
 pub mod manifest;
 pub mod model;
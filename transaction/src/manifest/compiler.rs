@@ -3,6 +3,7 @@ use crate::internal_prelude::*;
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CompileError {
     LexerError(lexer::LexerError),
+    TemplateError(template::TemplateError),
     ParserError(parser::ParserError),
     GeneratorError(generator::GeneratorError),
 }
@@ -24,3 +25,28 @@ where
     generator::generate_manifest(&instructions, &address_bech32_decoder, blobs)
         .map_err(CompileError::GeneratorError)
 }
+
+/// Compiles a manifest template, expanding its `INCLUDE` instructions from the given fragments
+/// and substituting its `Parameter(..)` placeholders with the given parameter values.
+pub fn compile_with_parameters<B, F>(
+    s: &str,
+    network: &NetworkDefinition,
+    blobs: B,
+    fragments: &F,
+    parameters: &ManifestParameters,
+) -> Result<TransactionManifestV1, CompileError>
+where
+    B: IsBlobProvider,
+    F: IsFragmentProvider,
+{
+    let address_bech32_decoder = AddressBech32Decoder::new(network);
+
+    let tokens = lexer::tokenize(s).map_err(CompileError::LexerError)?;
+    let tokens = template::expand_template(tokens, &address_bech32_decoder, fragments, parameters)
+        .map_err(CompileError::TemplateError)?;
+    let instructions = parser::Parser::new(tokens, parser::PARSER_MAX_DEPTH)
+        .parse_manifest()
+        .map_err(CompileError::ParserError)?;
+    generator::generate_manifest(&instructions, &address_bech32_decoder, blobs)
+        .map_err(CompileError::GeneratorError)
+}
@@ -169,6 +169,7 @@ lazy_static! {
             enum PublicKey {
                 Secp256k1 = 0;
                 Ed25519 = 1;
+                Secp256r1 = 2;
             }
         );
 
@@ -177,6 +178,7 @@ lazy_static! {
             enum PublicKeyHash {
                 Secp256k1 = 0;
                 Ed25519 = 1;
+                Secp256r1 = 2;
             }
         );
 
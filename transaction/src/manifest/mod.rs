@@ -9,8 +9,10 @@ pub mod generator;
 pub mod lexer;
 pub mod manifest_enums;
 pub mod parser;
+pub mod template;
 
 pub use blob_provider::*;
-pub use compiler::{compile, CompileError};
+pub use compiler::{compile, compile_with_parameters, CompileError};
 pub use decompiler::{decompile, DecompileError};
 pub use manifest_enums::*;
+pub use template::{FragmentProvider, IsFragmentProvider, ManifestParameters};
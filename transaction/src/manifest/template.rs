@@ -0,0 +1,581 @@
+use crate::manifest::ast;
+use crate::manifest::lexer::{tokenize, LexerError, Span, Token, TokenKind};
+use radix_engine_common::types::NodeId;
+use radix_engine_interface::address::AddressBech32Decoder;
+use radix_engine_interface::blueprints::resource::NonFungibleGlobalId;
+use radix_engine_interface::data::scrypto::model::NonFungibleLocalId;
+use radix_engine_interface::math::{Decimal, PreciseDecimal};
+use sbor::rust::prelude::*;
+use sbor::rust::str::FromStr;
+
+/// The identifier of the pseudo-instruction which splices a fragment into the manifest.
+pub const INCLUDE_IDENT: &str = "INCLUDE";
+/// The identifier of the placeholder value which is replaced by a parameter value.
+pub const PARAMETER_IDENT: &str = "Parameter";
+
+/// The maximum nesting depth of included fragments.
+pub const TEMPLATE_MAX_INCLUDE_DEPTH: usize = 16;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    FragmentNotFound(String),
+    FragmentLexerError(String, LexerError),
+    RecursiveInclude(String),
+    MaxIncludeDepthExceeded(usize),
+    InvalidInclude(Token),
+    UndefinedParameter(String),
+    UnusedParameter(String),
+    InvalidParameterPosition(Token),
+    ConflictingParameterType {
+        name: String,
+        first_type: ast::ValueKind,
+        second_type: ast::ValueKind,
+    },
+    InvalidParameterValue {
+        name: String,
+        expected_type: ast::ValueKind,
+        value: String,
+    },
+}
+
+//========
+// Traits
+//========
+
+pub trait IsFragmentProvider {
+    fn get_fragment(&self, name: &str) -> Option<String>;
+}
+
+//===========================
+// Default Fragment Provider
+//===========================
+
+#[derive(Default, Debug, Clone)]
+pub struct FragmentProvider(BTreeMap<String, String>);
+
+impl FragmentProvider {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn new_with_fragments(fragments: BTreeMap<String, String>) -> Self {
+        Self(fragments)
+    }
+
+    pub fn add_fragment(&mut self, name: String, fragment: String) {
+        self.0.insert(name, fragment);
+    }
+}
+
+impl IsFragmentProvider for FragmentProvider {
+    fn get_fragment(&self, name: &str) -> Option<String> {
+        self.0.get(name).cloned()
+    }
+}
+
+//=====================
+// Manifest Parameters
+//=====================
+
+/// The values of the parameters of a manifest template, keyed by the parameter name.
+///
+/// Parameters are declared in the template by the typed value which wraps them, e.g.
+/// `Decimal(Parameter("amount"))` declares `amount` as a decimal. Values are validated against
+/// the declared type when they are substituted.
+///
+/// The values of `Bucket(..)` and `Proof(..)` parameters are names, which are bound and resolved
+/// by the generator's `NameResolver`, exactly as the names in a hand-written manifest.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct ManifestParameters(IndexMap<String, String>);
+
+impl ManifestParameters {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.insert(name, value);
+        self
+    }
+
+    pub fn insert(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.0.insert(name.into(), value.into());
+    }
+
+    pub fn get(&self, name: &str) -> Option<&String> {
+        self.0.get(name)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<N: Into<String>, V: Into<String>> FromIterator<(N, V)> for ManifestParameters {
+    fn from_iter<T: IntoIterator<Item = (N, V)>>(iter: T) -> Self {
+        let mut parameters = Self::new();
+        for (name, value) in iter {
+            parameters.insert(name, value);
+        }
+        parameters
+    }
+}
+
+//==================
+// Template Expansion
+//==================
+
+/// Expands the includes of a tokenized manifest template and substitutes its parameters.
+///
+/// The output is a plain token stream which can be handed to the parser.
+pub fn expand_template<F>(
+    tokens: Vec<Token>,
+    address_bech32_decoder: &AddressBech32Decoder,
+    fragments: &F,
+    parameters: &ManifestParameters,
+) -> Result<Vec<Token>, TemplateError>
+where
+    F: IsFragmentProvider,
+{
+    let tokens = expand_includes(tokens, fragments, &mut Vec::new())?;
+    substitute_parameters(tokens, address_bech32_decoder, parameters)
+}
+
+fn expand_includes<F>(
+    tokens: Vec<Token>,
+    fragments: &F,
+    include_stack: &mut Vec<String>,
+) -> Result<Vec<Token>, TemplateError>
+where
+    F: IsFragmentProvider,
+{
+    let mut output = Vec::with_capacity(tokens.len());
+    let mut at_instruction_start = true;
+    let mut iter = tokens.into_iter();
+
+    while let Some(token) = iter.next() {
+        let is_include =
+            matches!(&token.kind, TokenKind::Ident(ident) if ident.as_str() == INCLUDE_IDENT);
+        if !is_include {
+            at_instruction_start = token.kind == TokenKind::Semicolon;
+            output.push(token);
+            continue;
+        }
+        if !at_instruction_start {
+            return Err(TemplateError::InvalidInclude(token));
+        }
+
+        let name = match iter.next() {
+            Some(Token {
+                kind: TokenKind::StringLiteral(name),
+                ..
+            }) => name,
+            _ => return Err(TemplateError::InvalidInclude(token)),
+        };
+        match iter.next() {
+            Some(Token {
+                kind: TokenKind::Semicolon,
+                ..
+            }) => {}
+            _ => return Err(TemplateError::InvalidInclude(token)),
+        }
+
+        if include_stack.contains(&name) {
+            return Err(TemplateError::RecursiveInclude(name));
+        }
+        if include_stack.len() >= TEMPLATE_MAX_INCLUDE_DEPTH {
+            return Err(TemplateError::MaxIncludeDepthExceeded(
+                TEMPLATE_MAX_INCLUDE_DEPTH,
+            ));
+        }
+
+        let fragment = fragments
+            .get_fragment(&name)
+            .ok_or_else(|| TemplateError::FragmentNotFound(name.clone()))?;
+        let fragment_tokens =
+            tokenize(&fragment).map_err(|e| TemplateError::FragmentLexerError(name.clone(), e))?;
+
+        include_stack.push(name);
+        output.extend(expand_includes(fragment_tokens, fragments, include_stack)?);
+        include_stack.pop();
+    }
+
+    Ok(output)
+}
+
+fn substitute_parameters(
+    tokens: Vec<Token>,
+    address_bech32_decoder: &AddressBech32Decoder,
+    parameters: &ManifestParameters,
+) -> Result<Vec<Token>, TemplateError> {
+    let mut output = Vec::with_capacity(tokens.len());
+    let mut declared_types = IndexMap::<String, ast::ValueKind>::new();
+    let mut i = 0;
+
+    while i < tokens.len() {
+        let token = &tokens[i];
+        if let TokenKind::Ident(ident) = &token.kind {
+            if ident.as_str() == PARAMETER_IDENT {
+                // Any placeholder which is not consumed below sits in an untyped position
+                return Err(TemplateError::InvalidParameterPosition(token.clone()));
+            }
+            if let (Some(value_kind), Some((name, span))) = (
+                parameter_value_kind(ident),
+                match_placeholder(&tokens[i + 1..]),
+            ) {
+                if let Some(first_type) = declared_types.get(&name) {
+                    if *first_type != value_kind {
+                        return Err(TemplateError::ConflictingParameterType {
+                            name,
+                            first_type: *first_type,
+                            second_type: value_kind,
+                        });
+                    }
+                }
+                let value = parameters
+                    .get(&name)
+                    .ok_or_else(|| TemplateError::UndefinedParameter(name.clone()))?;
+                validate_parameter_value(&name, value, value_kind, address_bech32_decoder)?;
+                declared_types.insert(name, value_kind);
+
+                // `Decimal(Parameter("amount"))` becomes `Decimal("<value>")`
+                output.push(tokens[i].clone());
+                output.push(tokens[i + 1].clone());
+                output.push(Token {
+                    kind: TokenKind::StringLiteral(value.clone()),
+                    span,
+                });
+                i += 6;
+                continue;
+            }
+        }
+        output.push(token.clone());
+        i += 1;
+    }
+
+    for name in parameters.0.keys() {
+        if !declared_types.contains_key(name) {
+            return Err(TemplateError::UnusedParameter(name.clone()));
+        }
+    }
+
+    Ok(output)
+}
+
+fn parameter_value_kind(ident: &str) -> Option<ast::ValueKind> {
+    let value_kind = match ident {
+        "Address" => ast::ValueKind::Address,
+        "Decimal" => ast::ValueKind::Decimal,
+        "PreciseDecimal" => ast::ValueKind::PreciseDecimal,
+        "NonFungibleLocalId" => ast::ValueKind::NonFungibleLocalId,
+        "NonFungibleGlobalId" => ast::ValueKind::NonFungibleGlobalId,
+        "Bucket" => ast::ValueKind::Bucket,
+        "Proof" => ast::ValueKind::Proof,
+        _ => return None,
+    };
+    Some(value_kind)
+}
+
+/// Matches `(Parameter("<name>"))`, returning the name and the span of the placeholder.
+fn match_placeholder(tokens: &[Token]) -> Option<(String, Span)> {
+    match tokens {
+        [Token {
+            kind: TokenKind::OpenParenthesis,
+            ..
+        }, Token {
+            kind: TokenKind::Ident(ident),
+            span: start,
+        }, Token {
+            kind: TokenKind::OpenParenthesis,
+            ..
+        }, Token {
+            kind: TokenKind::StringLiteral(name),
+            ..
+        }, Token {
+            kind: TokenKind::CloseParenthesis,
+            span: end,
+        }, Token {
+            kind: TokenKind::CloseParenthesis,
+            ..
+        }, ..]
+            if ident.as_str() == PARAMETER_IDENT =>
+        {
+            Some((
+                name.clone(),
+                Span {
+                    start: start.start,
+                    end: end.end,
+                },
+            ))
+        }
+        _ => None,
+    }
+}
+
+fn validate_parameter_value(
+    name: &str,
+    value: &str,
+    expected_type: ast::ValueKind,
+    address_bech32_decoder: &AddressBech32Decoder,
+) -> Result<(), TemplateError> {
+    let is_valid = match expected_type {
+        ast::ValueKind::Address => address_bech32_decoder
+            .validate_and_decode(value)
+            .map(|(_, full_data)| full_data.len() == NodeId::LENGTH)
+            .unwrap_or(false),
+        ast::ValueKind::Decimal => Decimal::from_str(value).is_ok(),
+        ast::ValueKind::PreciseDecimal => PreciseDecimal::from_str(value).is_ok(),
+        ast::ValueKind::NonFungibleLocalId => NonFungibleLocalId::from_str(value).is_ok(),
+        ast::ValueKind::NonFungibleGlobalId => {
+            NonFungibleGlobalId::try_from_canonical_string(address_bech32_decoder, value).is_ok()
+        }
+        // Names are checked by the `NameResolver` when the manifest is generated
+        ast::ValueKind::Bucket | ast::ValueKind::Proof => true,
+        _ => false,
+    };
+    if is_valid {
+        Ok(())
+    } else {
+        Err(TemplateError::InvalidParameterValue {
+            name: name.to_string(),
+            expected_type,
+            value: value.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::generator::{GeneratorError, NameResolverError};
+    use crate::manifest::lexer::tokenize;
+    use crate::manifest::{compile_with_parameters, BlobProvider, CompileError};
+    use radix_engine_interface::network::NetworkDefinition;
+
+    const ACCOUNT: &str = "account_sim1cyvgx33089ukm2pl97pv4max0x40ruvfy4lt60yvya744cve475w0q";
+    const XRD: &str = "resource_sim1tknxxxxxxxxxradxrdxxxxxxxxx009923554798xxxxxxxxxakj8n3";
+
+    fn expand(
+        manifest: &str,
+        fragments: &FragmentProvider,
+        parameters: &ManifestParameters,
+    ) -> Result<Vec<TokenKind>, TemplateError> {
+        expand_template(
+            tokenize(manifest).unwrap(),
+            &AddressBech32Decoder::new(&NetworkDefinition::simulator()),
+            fragments,
+            parameters,
+        )
+        .map(|tokens| tokens.into_iter().map(|t| t.kind).collect())
+    }
+
+    fn kinds(manifest: &str) -> Vec<TokenKind> {
+        tokenize(manifest)
+            .unwrap()
+            .into_iter()
+            .map(|t| t.kind)
+            .collect()
+    }
+
+    #[test]
+    fn test_parameters_are_substituted() {
+        let parameters = ManifestParameters::new()
+            .with("account", ACCOUNT)
+            .with("amount", "10.5");
+        assert_eq!(
+            expand(
+                r#"CALL_METHOD Address(Parameter("account")) "lock_fee" Decimal(Parameter("amount"));"#,
+                &FragmentProvider::new(),
+                &parameters,
+            ),
+            Ok(kinds(&format!(
+                r#"CALL_METHOD Address("{}") "lock_fee" Decimal("10.5");"#,
+                ACCOUNT
+            )))
+        );
+    }
+
+    #[test]
+    fn test_invalid_parameter_value_is_rejected() {
+        let parameters = ManifestParameters::new().with("amount", "ten");
+        assert_eq!(
+            expand(
+                r#"Decimal(Parameter("amount"))"#,
+                &FragmentProvider::new(),
+                &parameters,
+            ),
+            Err(TemplateError::InvalidParameterValue {
+                name: "amount".to_string(),
+                expected_type: ast::ValueKind::Decimal,
+                value: "ten".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parameter_errors() {
+        let fragments = FragmentProvider::new();
+        assert_eq!(
+            expand(
+                r#"Decimal(Parameter("amount"))"#,
+                &fragments,
+                &ManifestParameters::new()
+            ),
+            Err(TemplateError::UndefinedParameter("amount".to_string()))
+        );
+        assert_eq!(
+            expand(
+                r#"Decimal("1")"#,
+                &fragments,
+                &ManifestParameters::new().with("amount", "1")
+            ),
+            Err(TemplateError::UnusedParameter("amount".to_string()))
+        );
+        assert!(matches!(
+            expand(
+                r#"Parameter("amount")"#,
+                &fragments,
+                &ManifestParameters::new().with("amount", "1")
+            ),
+            Err(TemplateError::InvalidParameterPosition(..))
+        ));
+        assert_eq!(
+            expand(
+                r#"Decimal(Parameter("x")) PreciseDecimal(Parameter("x"))"#,
+                &fragments,
+                &ManifestParameters::new().with("x", "1")
+            ),
+            Err(TemplateError::ConflictingParameterType {
+                name: "x".to_string(),
+                first_type: ast::ValueKind::Decimal,
+                second_type: ast::ValueKind::PreciseDecimal,
+            })
+        );
+    }
+
+    #[test]
+    fn test_named_bucket_and_proof_parameters_are_substituted() {
+        let parameters = ManifestParameters::new()
+            .with("bucket", "xrd_bucket")
+            .with("proof", "xrd_proof");
+        assert_eq!(
+            expand(
+                r#"CREATE_PROOF_FROM_BUCKET_OF_ALL Bucket(Parameter("bucket")) Proof(Parameter("proof"));"#,
+                &FragmentProvider::new(),
+                &parameters,
+            ),
+            Ok(kinds(
+                r#"CREATE_PROOF_FROM_BUCKET_OF_ALL Bucket("xrd_bucket") Proof("xrd_proof");"#
+            ))
+        );
+    }
+
+    #[test]
+    fn test_named_bucket_parameters_are_bound_by_the_name_resolver() {
+        let mut fragments = FragmentProvider::new();
+        fragments.add_fragment(
+            "deposit".to_string(),
+            r#"CALL_METHOD Address(Parameter("account")) "deposit" Bucket(Parameter("bucket"));"#
+                .to_string(),
+        );
+        let compile = |manifest: &str, bucket: &str| {
+            compile_with_parameters(
+                manifest,
+                &NetworkDefinition::simulator(),
+                BlobProvider::new(),
+                &fragments,
+                &ManifestParameters::new()
+                    .with("account", ACCOUNT)
+                    .with("xrd", XRD)
+                    .with("bucket", bucket),
+            )
+        };
+
+        let manifest = compile(
+            r#"
+            TAKE_ALL_FROM_WORKTOP Address(Parameter("xrd")) Bucket(Parameter("bucket"));
+            INCLUDE "deposit";
+            "#,
+            "xrd_bucket",
+        );
+        assert_eq!(manifest.unwrap().instructions.len(), 2);
+
+        let manifest = compile(
+            r#"
+            TAKE_ALL_FROM_WORKTOP Address(Parameter("xrd")) Bucket("xrd_bucket");
+            INCLUDE "deposit";
+            "#,
+            "other_bucket",
+        );
+        assert_eq!(
+            manifest,
+            Err(CompileError::GeneratorError(
+                GeneratorError::NameResolverError(NameResolverError::UndefinedBucket(
+                    "other_bucket".to_string()
+                ))
+            ))
+        );
+
+        let manifest = compile(
+            r#"
+            TAKE_ALL_FROM_WORKTOP Address(Parameter("xrd")) Bucket("xrd_bucket");
+            TAKE_ALL_FROM_WORKTOP Address(Parameter("xrd")) Bucket(Parameter("bucket"));
+            INCLUDE "deposit";
+            "#,
+            "xrd_bucket",
+        );
+        assert_eq!(
+            manifest,
+            Err(CompileError::GeneratorError(
+                GeneratorError::NameResolverError(NameResolverError::NamedAlreadyDefined(
+                    "xrd_bucket".to_string()
+                ))
+            ))
+        );
+    }
+
+    #[test]
+    fn test_includes_are_expanded() {
+        let mut fragments = FragmentProvider::new();
+        fragments.add_fragment(
+            "lock_fee".to_string(),
+            r#"CALL_METHOD Address(Parameter("account")) "lock_fee" Decimal("10");"#.to_string(),
+        );
+        fragments.add_fragment(
+            "outer".to_string(),
+            r#"INCLUDE "lock_fee"; DROP_ALL_PROOFS;"#.to_string(),
+        );
+        assert_eq!(
+            expand(
+                r#"INCLUDE "outer"; DROP_AUTH_ZONE_PROOFS;"#,
+                &fragments,
+                &ManifestParameters::new().with("account", ACCOUNT),
+            ),
+            Ok(kinds(&format!(
+                r#"CALL_METHOD Address("{}") "lock_fee" Decimal("10"); DROP_ALL_PROOFS; DROP_AUTH_ZONE_PROOFS;"#,
+                ACCOUNT
+            )))
+        );
+    }
+
+    #[test]
+    fn test_include_errors() {
+        let mut fragments = FragmentProvider::new();
+        fragments.add_fragment("a".to_string(), r#"INCLUDE "b";"#.to_string());
+        fragments.add_fragment("b".to_string(), r#"INCLUDE "a";"#.to_string());
+        let parameters = ManifestParameters::new();
+
+        assert_eq!(
+            expand(r#"INCLUDE "a";"#, &fragments, &parameters),
+            Err(TemplateError::RecursiveInclude("a".to_string()))
+        );
+        assert_eq!(
+            expand(r#"INCLUDE "c";"#, &fragments, &parameters),
+            Err(TemplateError::FragmentNotFound("c".to_string()))
+        );
+        assert!(matches!(
+            expand(r#"CALL_METHOD INCLUDE "a";"#, &fragments, &parameters),
+            Err(TemplateError::InvalidInclude(..))
+        ));
+    }
+}
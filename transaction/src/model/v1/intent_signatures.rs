@@ -16,6 +16,10 @@ pub enum SignatureWithPublicKeyV1 {
         public_key: Ed25519PublicKey,
         signature: Ed25519Signature,
     },
+    Secp256r1 {
+        public_key: Secp256r1PublicKey,
+        signature: Secp256r1Signature,
+    },
 }
 
 impl SignatureWithPublicKeyV1 {
@@ -23,6 +27,7 @@ impl SignatureWithPublicKeyV1 {
         match &self {
             Self::Secp256k1 { signature } => signature.clone().into(),
             Self::Ed25519 { signature, .. } => signature.clone().into(),
+            Self::Secp256r1 { signature, .. } => signature.clone().into(),
         }
     }
 }
@@ -42,6 +47,15 @@ impl From<(Ed25519PublicKey, Ed25519Signature)> for SignatureWithPublicKeyV1 {
     }
 }
 
+impl From<(Secp256r1PublicKey, Secp256r1Signature)> for SignatureWithPublicKeyV1 {
+    fn from((public_key, signature): (Secp256r1PublicKey, Secp256r1Signature)) -> Self {
+        Self::Secp256r1 {
+            public_key,
+            signature,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, ManifestSbor)]
 #[sbor(transparent)]
 pub struct IntentSignatureV1(pub SignatureWithPublicKeyV1);
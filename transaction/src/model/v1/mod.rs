@@ -10,6 +10,7 @@ mod manifest;
 mod message;
 mod notarized_transaction;
 mod notary_signature;
+mod partially_signed_transaction;
 mod preview_transaction;
 mod signed_intent;
 mod system_transaction;
@@ -26,6 +27,7 @@ pub use manifest::*;
 pub use message::*;
 pub use notarized_transaction::*;
 pub use notary_signature::*;
+pub use partially_signed_transaction::*;
 pub use preview_transaction::*;
 pub use signed_intent::*;
 pub use system_transaction::*;
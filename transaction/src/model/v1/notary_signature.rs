@@ -11,6 +11,7 @@ use crate::internal_prelude::*;
 pub enum SignatureV1 {
     Secp256k1(Secp256k1Signature),
     Ed25519(Ed25519Signature),
+    Secp256r1(Secp256r1Signature),
 }
 
 impl From<Secp256k1Signature> for SignatureV1 {
@@ -25,6 +26,12 @@ impl From<Ed25519Signature> for SignatureV1 {
     }
 }
 
+impl From<Secp256r1Signature> for SignatureV1 {
+    fn from(signature: Secp256r1Signature) -> Self {
+        Self::Secp256r1(signature)
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, ManifestSbor)]
 #[sbor(transparent)]
 pub struct NotarySignatureV1(pub SignatureV1);
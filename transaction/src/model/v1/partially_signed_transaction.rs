@@ -0,0 +1,319 @@
+use super::*;
+use crate::internal_prelude::*;
+use crate::signing::Signer;
+
+/// An intent together with the intent signatures collected so far, which can be passed between
+/// offline signers and finally handed to the notary.
+///
+/// Unlike the transaction payloads, this is purely an exchange format - it is never submitted to
+/// the network. The intent hash is carried alongside the intent so that signers can check what
+/// they are signing, and it is re-validated against the intent whenever the artifact is loaded.
+#[derive(Debug, Clone, Eq, PartialEq, ManifestSbor)]
+pub struct PartiallySignedTransactionV1 {
+    pub intent: IntentV1,
+    pub intent_hash: IntentHash,
+    pub intent_signatures: IntentSignaturesV1,
+}
+
+impl PartiallySignedTransactionV1 {
+    pub fn new(intent: IntentV1) -> Result<Self, PartiallySignedTransactionError> {
+        let intent_hash = intent.prepare()?.intent_hash();
+        Ok(Self {
+            intent,
+            intent_hash,
+            intent_signatures: IntentSignaturesV1 { signatures: vec![] },
+        })
+    }
+
+    pub fn to_payload_bytes(&self) -> Result<Vec<u8>, EncodeError> {
+        manifest_encode(self)
+    }
+
+    /// Decodes the artifact, and validates the intent hash and all signatures against the intent.
+    pub fn from_payload_bytes(
+        payload_bytes: &[u8],
+    ) -> Result<Self, PartiallySignedTransactionDecodeError> {
+        let transaction = manifest_decode::<Self>(payload_bytes)
+            .map_err(PartiallySignedTransactionDecodeError::DecodeError)?;
+        transaction
+            .validate()
+            .map_err(PartiallySignedTransactionDecodeError::ValidationError)?;
+        Ok(transaction)
+    }
+
+    /// Returns the public keys of the signers so far, in signing order.
+    pub fn signer_public_keys(&self) -> Result<Vec<PublicKey>, PartiallySignedTransactionError> {
+        self.intent_signatures
+            .signatures
+            .iter()
+            .map(|signature| self.verify_signature(&signature.0))
+            .collect()
+    }
+
+    /// Checks that the intent hash matches the intent, and that every signature is a valid
+    /// signature of the intent hash by a distinct signer.
+    pub fn validate(&self) -> Result<(), PartiallySignedTransactionError> {
+        let intent_hash = self.intent.prepare()?.intent_hash();
+        if intent_hash != self.intent_hash {
+            return Err(PartiallySignedTransactionError::IntentHashMismatch {
+                expected: intent_hash,
+                actual: self.intent_hash,
+            });
+        }
+        if self.intent_signatures.signatures.len() > MAX_NUMBER_OF_INTENT_SIGNATURES {
+            return Err(PartiallySignedTransactionError::TooManySignatures);
+        }
+
+        let mut signers = index_set_new();
+        for public_key in self.signer_public_keys()? {
+            if !signers.insert(public_key) {
+                return Err(PartiallySignedTransactionError::DuplicateSigner(public_key));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn sign<S: Signer>(&mut self, signer: &S) -> Result<(), PartiallySignedTransactionError> {
+        self.add_signature(signer.sign_with_public_key(&self.intent_hash))
+    }
+
+    /// Adds a signature collected elsewhere, after checking it against the intent hash.
+    pub fn add_signature(
+        &mut self,
+        signature: SignatureWithPublicKeyV1,
+    ) -> Result<(), PartiallySignedTransactionError> {
+        let public_key = self.verify_signature(&signature)?;
+        if self.signer_public_keys()?.contains(&public_key) {
+            return Err(PartiallySignedTransactionError::DuplicateSigner(public_key));
+        }
+        if self.intent_signatures.signatures.len() >= MAX_NUMBER_OF_INTENT_SIGNATURES {
+            return Err(PartiallySignedTransactionError::TooManySignatures);
+        }
+        self.intent_signatures
+            .signatures
+            .push(IntentSignatureV1(signature));
+        Ok(())
+    }
+
+    /// Merges in the signatures of another copy of the same intent.
+    ///
+    /// Signatures from signers which have already signed this copy are skipped, so merging is
+    /// idempotent and the copies can be merged in any order. On error, this copy is left
+    /// unchanged.
+    pub fn merge(&mut self, other: Self) -> Result<(), PartiallySignedTransactionError> {
+        other.validate()?;
+        if other.intent_hash != self.intent_hash || other.intent != self.intent {
+            return Err(PartiallySignedTransactionError::IntentHashMismatch {
+                expected: self.intent_hash,
+                actual: other.intent_hash,
+            });
+        }
+
+        let existing_signers = self.signer_public_keys()?;
+        let mut new_signatures = Vec::new();
+        for signature in other.intent_signatures.signatures {
+            let public_key = self.verify_signature(&signature.0)?;
+            if !existing_signers.contains(&public_key) {
+                new_signatures.push(signature);
+            }
+        }
+        if self.intent_signatures.signatures.len() + new_signatures.len()
+            > MAX_NUMBER_OF_INTENT_SIGNATURES
+        {
+            return Err(PartiallySignedTransactionError::TooManySignatures);
+        }
+        self.intent_signatures.signatures.extend(new_signatures);
+        Ok(())
+    }
+
+    /// Finalizes the transaction by notarizing it with the notary key from the intent header.
+    pub fn finalize<S: Signer>(
+        self,
+        notary: &S,
+    ) -> Result<NotarizedTransactionV1, PartiallySignedTransactionError> {
+        let expected = self.intent.header.notary_public_key;
+        let actual = notary.public_key();
+        if expected != actual {
+            return Err(PartiallySignedTransactionError::NotaryPublicKeyMismatch {
+                expected,
+                actual,
+            });
+        }
+
+        let signed_intent = self.into_signed_intent()?;
+        let signed_intent_hash = signed_intent.prepare()?.signed_intent_hash();
+        Ok(NotarizedTransactionV1 {
+            signed_intent,
+            notary_signature: NotarySignatureV1(
+                notary.sign_without_public_key(&signed_intent_hash),
+            ),
+        })
+    }
+
+    /// Finalizes the transaction with a notary signature produced elsewhere.
+    pub fn finalize_with_notary_signature(
+        self,
+        notary_signature: SignatureV1,
+    ) -> Result<NotarizedTransactionV1, PartiallySignedTransactionError> {
+        let signed_intent = self.into_signed_intent()?;
+        let signed_intent_hash = signed_intent.prepare()?.signed_intent_hash();
+        if !verify(
+            &signed_intent_hash.into_hash(),
+            &signed_intent.intent.header.notary_public_key,
+            &notary_signature,
+        ) {
+            return Err(PartiallySignedTransactionError::InvalidNotarySignature);
+        }
+        Ok(NotarizedTransactionV1 {
+            signed_intent,
+            notary_signature: NotarySignatureV1(notary_signature),
+        })
+    }
+
+    /// Returns the signed intent which the notary is expected to sign.
+    pub fn into_signed_intent(self) -> Result<SignedIntentV1, PartiallySignedTransactionError> {
+        self.validate()?;
+        Ok(SignedIntentV1 {
+            intent: self.intent,
+            intent_signatures: self.intent_signatures,
+        })
+    }
+
+    fn verify_signature(
+        &self,
+        signature: &SignatureWithPublicKeyV1,
+    ) -> Result<PublicKey, PartiallySignedTransactionError> {
+        let intent_hash = self.intent_hash.into_hash();
+        recover(&intent_hash, signature)
+            .filter(|public_key| verify(&intent_hash, public_key, &signature.signature()))
+            .ok_or(PartiallySignedTransactionError::InvalidIntentSignature(
+                signature.clone(),
+            ))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartiallySignedTransactionDecodeError {
+    DecodeError(DecodeError),
+    ValidationError(PartiallySignedTransactionError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn intent(notary_public_key: PublicKey) -> IntentV1 {
+        let (instructions, blobs) = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .build()
+            .for_intent();
+        IntentV1 {
+            header: TransactionHeaderV1 {
+                network_id: NetworkDefinition::simulator().id,
+                start_epoch_inclusive: Epoch::zero(),
+                end_epoch_exclusive: Epoch::of(100),
+                nonce: 5,
+                notary_public_key,
+                notary_is_signatory: false,
+                tip_percentage: 0,
+            },
+            instructions,
+            blobs,
+            message: MessageV1::None,
+        }
+    }
+
+    #[test]
+    fn signatures_from_several_parties_can_be_merged_and_notarized() {
+        let signer_1 = Secp256k1PrivateKey::from_u64(1).unwrap();
+        let signer_2 = Ed25519PrivateKey::from_u64(2).unwrap();
+        let notary = Ed25519PrivateKey::from_u64(3).unwrap();
+
+        let base = PartiallySignedTransactionV1::new(intent(notary.public_key().into())).unwrap();
+        let mut party_1 =
+            PartiallySignedTransactionV1::from_payload_bytes(&base.to_payload_bytes().unwrap())
+                .unwrap();
+        let mut party_2 = base.clone();
+        party_1.sign(&signer_1).unwrap();
+        party_2.sign(&signer_2).unwrap();
+
+        party_1.merge(party_2.clone()).unwrap();
+        party_1.merge(party_2).unwrap();
+        assert_eq!(
+            party_1.signer_public_keys().unwrap(),
+            vec![signer_1.public_key().into(), signer_2.public_key().into()]
+        );
+
+        let transaction = party_1.finalize(&notary).unwrap();
+        let validated = NotarizedTransactionValidator::new(ValidationConfig::simulator())
+            .validate(transaction.prepare().unwrap())
+            .unwrap();
+        assert_eq!(validated.signer_keys.len(), 2);
+    }
+
+    #[test]
+    fn merging_too_many_signatures_leaves_the_transaction_unchanged() {
+        let notary = Ed25519PrivateKey::from_u64(1).unwrap();
+        let base = PartiallySignedTransactionV1::new(intent(notary.public_key().into())).unwrap();
+
+        let mut party_1 = base.clone();
+        let mut party_2 = base.clone();
+        for n in 0..MAX_NUMBER_OF_INTENT_SIGNATURES as u64 {
+            party_1
+                .sign(&Secp256k1PrivateKey::from_u64(n + 1).unwrap())
+                .unwrap();
+        }
+        party_2
+            .sign(&Ed25519PrivateKey::from_u64(2).unwrap())
+            .unwrap();
+
+        let before = party_1.clone();
+        assert_eq!(
+            party_1.merge(party_2),
+            Err(PartiallySignedTransactionError::TooManySignatures)
+        );
+        assert_eq!(party_1, before);
+    }
+
+    #[test]
+    fn invalid_steps_are_rejected() {
+        let signer = Secp256k1PrivateKey::from_u64(1).unwrap();
+        let notary = Ed25519PrivateKey::from_u64(3).unwrap();
+        let other = Ed25519PrivateKey::from_u64(4).unwrap();
+
+        let mut transaction =
+            PartiallySignedTransactionV1::new(intent(notary.public_key().into())).unwrap();
+        transaction.sign(&signer).unwrap();
+        assert_eq!(
+            transaction.sign(&signer),
+            Err(PartiallySignedTransactionError::DuplicateSigner(
+                signer.public_key().into()
+            ))
+        );
+
+        let other_intent =
+            PartiallySignedTransactionV1::new(intent(other.public_key().into())).unwrap();
+        assert!(matches!(
+            transaction.merge(other_intent.clone()),
+            Err(PartiallySignedTransactionError::IntentHashMismatch { .. })
+        ));
+        assert!(matches!(
+            transaction.add_signature(signer.sign_with_public_key(&other_intent.intent_hash)),
+            Err(PartiallySignedTransactionError::InvalidIntentSignature(..))
+        ));
+
+        let mut tampered = transaction.clone();
+        tampered.intent.header.nonce = 6;
+        assert!(matches!(
+            PartiallySignedTransactionV1::from_payload_bytes(&tampered.to_payload_bytes().unwrap()),
+            Err(PartiallySignedTransactionDecodeError::ValidationError(
+                PartiallySignedTransactionError::IntentHashMismatch { .. }
+            ))
+        ));
+
+        assert!(matches!(
+            transaction.finalize(&other),
+            Err(PartiallySignedTransactionError::NotaryPublicKeyMismatch { .. })
+        ));
+    }
+}
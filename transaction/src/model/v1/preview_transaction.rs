@@ -31,6 +31,7 @@ impl ValidatedPreviewIntent {
         if self.flags.assume_all_signature_proofs {
             virtual_resources.insert(SECP256K1_SIGNATURE_VIRTUAL_BADGE);
             virtual_resources.insert(ED25519_SIGNATURE_VIRTUAL_BADGE);
+            virtual_resources.insert(SECP256R1_SIGNATURE_VIRTUAL_BADGE);
         }
 
         let header = &intent.header.inner;
@@ -4,6 +4,7 @@ use radix_engine_common::prelude::IsHash;
 pub enum PrivateKey {
     Secp256k1(Secp256k1PrivateKey),
     Ed25519(Ed25519PrivateKey),
+    Secp256r1(Secp256r1PrivateKey),
 }
 
 impl PrivateKey {
@@ -11,6 +12,7 @@ impl PrivateKey {
         match self {
             PrivateKey::Secp256k1(key) => key.public_key().into(),
             PrivateKey::Ed25519(key) => key.public_key().into(),
+            PrivateKey::Secp256r1(key) => key.public_key().into(),
         }
     }
 }
@@ -27,6 +29,12 @@ impl From<Ed25519PrivateKey> for PrivateKey {
     }
 }
 
+impl From<Secp256r1PrivateKey> for PrivateKey {
+    fn from(public_key: Secp256r1PrivateKey) -> Self {
+        Self::Secp256r1(public_key)
+    }
+}
+
 pub trait Signer {
     fn public_key(&self) -> PublicKey;
     fn sign_without_public_key(&self, message_hash: &impl IsHash) -> SignatureV1;
@@ -61,11 +69,26 @@ impl Signer for Ed25519PrivateKey {
     }
 }
 
+impl Signer for Secp256r1PrivateKey {
+    fn sign_without_public_key(&self, message_hash: &impl IsHash) -> SignatureV1 {
+        self.sign(message_hash).into()
+    }
+
+    fn sign_with_public_key(&self, message_hash: &impl IsHash) -> SignatureWithPublicKeyV1 {
+        (self.public_key(), self.sign(message_hash)).into()
+    }
+
+    fn public_key(&self) -> PublicKey {
+        self.public_key().into()
+    }
+}
+
 impl Signer for PrivateKey {
     fn sign_without_public_key(&self, message_hash: &impl IsHash) -> SignatureV1 {
         match self {
             PrivateKey::Secp256k1(key) => key.sign_without_public_key(message_hash),
             PrivateKey::Ed25519(key) => key.sign_without_public_key(message_hash),
+            PrivateKey::Secp256r1(key) => key.sign_without_public_key(message_hash),
         }
     }
 
@@ -73,6 +96,7 @@ impl Signer for PrivateKey {
         match self {
             PrivateKey::Secp256k1(key) => key.sign_with_public_key(message_hash),
             PrivateKey::Ed25519(key) => key.sign_with_public_key(message_hash),
+            PrivateKey::Secp256r1(key) => key.sign_with_public_key(message_hash),
         }
     }
 
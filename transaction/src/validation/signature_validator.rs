@@ -6,6 +6,7 @@ pub fn recover(signed_hash: &Hash, signature: &SignatureWithPublicKeyV1) -> Opti
             recover_secp256k1(signed_hash, signature).map(Into::into)
         }
         SignatureWithPublicKeyV1::Ed25519 { public_key, .. } => Some(public_key.clone().into()),
+        SignatureWithPublicKeyV1::Secp256r1 { public_key, .. } => Some(public_key.clone().into()),
     }
 }
 
@@ -17,6 +18,9 @@ pub fn verify(signed_hash: &Hash, public_key: &PublicKey, signature: &SignatureV
         (PublicKey::Ed25519(pk), SignatureV1::Ed25519(sig)) => {
             verify_ed25519(&signed_hash, pk, sig)
         }
+        (PublicKey::Secp256r1(pk), SignatureV1::Secp256r1(sig)) => {
+            verify_secp256r1(&signed_hash, pk, sig)
+        }
         _ => false,
     }
 }
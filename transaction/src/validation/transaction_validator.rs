@@ -52,6 +52,9 @@ pub struct ValidationConfig {
     pub max_tip_percentage: u16,
     pub max_epoch_range: u64,
     pub message_validation: MessageValidationConfig,
+    /// Whether secp256r1 (P-256) signatures are accepted, which is only the case once the
+    /// protocol update introducing the secp256r1 signature virtual badge has been enacted
+    pub allow_secp256r1_signatures: bool,
 }
 
 impl ValidationConfig {
@@ -63,11 +66,19 @@ impl ValidationConfig {
             max_tip_percentage: MAX_TIP_PERCENTAGE,
             max_epoch_range: MAX_EPOCH_RANGE,
             message_validation: MessageValidationConfig::default(),
+            allow_secp256r1_signatures: false,
         }
     }
 
     pub fn simulator() -> Self {
-        Self::default(NetworkDefinition::simulator().id)
+        Self::default(NetworkDefinition::simulator().id).with_secp256r1_signatures()
+    }
+
+    /// Accepts secp256r1 signatures, which is only allowed on networks which have enacted the
+    /// secp256r1 signatures protocol update.
+    pub fn with_secp256r1_signatures(mut self) -> Self {
+        self.allow_secp256r1_signatures = true;
+        self
     }
 }
 
@@ -327,6 +338,23 @@ impl NotarizedTransactionValidator {
             return Err(SignatureValidationError::TooManySignatures);
         }
 
+        let header = &transaction.signed_intent.intent.header.inner;
+
+        if !self.config.allow_secp256r1_signatures {
+            let has_secp256r1_intent_signature = transaction
+                .signed_intent
+                .intent_signatures
+                .inner
+                .signatures
+                .iter()
+                .any(|signature| matches!(signature.0, SignatureWithPublicKeyV1::Secp256r1 { .. }));
+            if has_secp256r1_intent_signature
+                || matches!(header.notary_public_key, PublicKey::Secp256r1(..))
+            {
+                return Err(SignatureValidationError::Secp256r1SignaturesNotAllowed);
+            }
+        }
+
         // verify intent signature
         let mut signers = index_set_new();
         let intent_hash = transaction.intent_hash().into_hash();
@@ -343,8 +371,6 @@ impl NotarizedTransactionValidator {
             }
         }
 
-        let header = &transaction.signed_intent.intent.header.inner;
-
         if header.notary_is_signatory {
             signers.insert(header.notary_public_key);
         }
@@ -500,6 +526,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_secp256r1_signatures_are_only_accepted_when_allowed() {
+        let sk_signer = Secp256r1PrivateKey::from_u64(1).unwrap();
+        let sk_notary = Secp256k1PrivateKey::from_u64(2).unwrap();
+        let transaction = TransactionBuilder::new()
+            .header(TransactionHeaderV1 {
+                network_id: NetworkDefinition::simulator().id,
+                start_epoch_inclusive: Epoch::zero(),
+                end_epoch_exclusive: Epoch::of(100),
+                nonce: 5,
+                notary_public_key: sk_notary.public_key().into(),
+                notary_is_signatory: false,
+                tip_percentage: 5,
+            })
+            .manifest(ManifestBuilder::new().drop_auth_zone_proofs().build())
+            .sign(&sk_signer)
+            .notarize(&sk_notary)
+            .build();
+
+        let config = ValidationConfig::default(NetworkDefinition::simulator().id);
+        assert_eq!(
+            NotarizedTransactionValidator::new(config)
+                .validate(transaction.prepare().unwrap())
+                .expect_err("Should be an error"),
+            TransactionValidationError::SignatureValidationError(
+                SignatureValidationError::Secp256r1SignaturesNotAllowed
+            )
+        );
+
+        let config = ValidationConfig::default(NetworkDefinition::simulator().id)
+            .with_secp256r1_signatures();
+        let validated = NotarizedTransactionValidator::new(config)
+            .validate(transaction.prepare().unwrap())
+            .unwrap();
+        assert_eq!(
+            validated.signer_keys,
+            vec![PublicKey::from(sk_signer.public_key())]
+        );
+    }
+
     #[test]
     fn test_valid_preview() {
         // Build the whole transaction but only really care about the intent